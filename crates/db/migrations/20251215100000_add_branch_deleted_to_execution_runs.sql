@@ -0,0 +1,9 @@
+-- ============================================================================
+-- Add branch_deleted column to execution_runs table
+-- ============================================================================
+-- Worktree cleanup only flipped worktree_deleted, leaving run branches
+-- (e.g. run/ab12cd34) behind forever. This flag records that the branch was
+-- removed so cleanup is never attempted twice.
+-- ============================================================================
+
+ALTER TABLE execution_runs ADD COLUMN branch_deleted BOOLEAN NOT NULL DEFAULT FALSE;
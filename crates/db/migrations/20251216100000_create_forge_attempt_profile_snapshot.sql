@@ -0,0 +1,8 @@
+-- Resolved executor profile (model, flags, appended prompt) each attempt was started with
+CREATE TABLE IF NOT EXISTS forge_attempt_profile_snapshot (
+    task_attempt_id TEXT PRIMARY KEY NOT NULL,
+    executor_profile TEXT NOT NULL,
+    config TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE
+);
@@ -0,0 +1,6 @@
+-- When each project's remote was last fetched successfully, so the UI can show staleness
+CREATE TABLE IF NOT EXISTS forge_project_fetch_state (
+    project_id TEXT PRIMARY KEY NOT NULL,
+    last_fetch_at DATETIME NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+-- How many delivery attempts each Omni notification has had, including manual retries
+ALTER TABLE forge_omni_notifications ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+-- Kanban attempt status aggregates group each task's attempts and their processes.
+-- task_attempts(task_id) lookups are already served by idx_task_attempts_task_id_created_at;
+-- this covers the running/failed checks on execution_processes without touching the table.
+CREATE INDEX IF NOT EXISTS idx_execution_processes_task_attempt_status
+ON execution_processes (task_attempt_id, status);
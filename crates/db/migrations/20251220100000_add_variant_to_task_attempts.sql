@@ -0,0 +1,8 @@
+-- Executor variant each attempt was started with. Attempts used to store it in `executor`
+-- as `EXECUTOR:VARIANT`; split those so `executor` only holds the base coding agent.
+ALTER TABLE task_attempts ADD COLUMN variant TEXT;
+
+UPDATE task_attempts
+   SET variant  = substr(executor, instr(executor, ':') + 1),
+       executor = substr(executor, 1, instr(executor, ':') - 1)
+ WHERE instr(executor, ':') > 0;
@@ -0,0 +1,10 @@
+-- Client-supplied keys for task creation, so a retried request returns the task it created
+CREATE TABLE IF NOT EXISTS forge_task_idempotency_keys (
+    project_id BLOB NOT NULL,
+    idempotency_key TEXT NOT NULL,
+    task_id BLOB NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (project_id, idempotency_key),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Attempts whose branch is created before the attempt (forks) check out that branch instead
+-- of creating a new one from the base branch
+ALTER TABLE forge_task_attempt_config ADD COLUMN reuse_branch BOOLEAN NOT NULL DEFAULT 0;
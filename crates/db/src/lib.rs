@@ -1,4 +1,8 @@
-use std::{path::PathBuf, str::FromStr, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 
 use forge_core_utils::assets::asset_dir;
 use sqlx::{
@@ -44,10 +48,10 @@ impl DBService {
     /// SQLite URL format: sqlite:// + path
     /// For absolute paths on Unix (starting with /), this results in sqlite:///path (3 slashes)
     /// For Windows paths, this results in sqlite://C:/path
-    fn format_sqlite_url(path: &PathBuf) -> String {
+    fn format_sqlite_url(path: &Path) -> String {
         // Ensure the path is absolute
         let abs_path = if path.is_absolute() {
-            path.clone()
+            path.to_path_buf()
         } else {
             std::env::current_dir()
                 .unwrap_or_else(|_| PathBuf::from("."))
@@ -72,8 +76,17 @@ impl DBService {
     }
 
     pub async fn new() -> Result<DBService, Error> {
-        let database_url = Self::get_database_url();
-        let options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
+        Self::connect(&Self::get_database_url()).await
+    }
+
+    /// Open (creating if needed) and migrate the database file at `path`, ignoring
+    /// `DATABASE_URL`. Lets tests each use their own database.
+    pub async fn new_at(path: &Path) -> Result<DBService, Error> {
+        Self::connect(&Self::format_sqlite_url(path)).await
+    }
+
+    async fn connect(database_url: &str) -> Result<DBService, Error> {
+        let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
         let pool = SqlitePool::connect_with(options).await?;
         sqlx::migrate!("./migrations").run(&pool).await?;
         Ok(DBService { pool })
@@ -89,11 +102,35 @@ impl DBService {
             + Sync
             + 'static,
     {
-        let pool = Self::create_pool(Some(Arc::new(after_connect))).await?;
+        let pool =
+            Self::create_pool(&Self::get_database_url(), Some(Arc::new(after_connect))).await?;
+        Ok(DBService { pool })
+    }
+
+    /// Like [`Self::new_with_after_connect`], for the database file at `path`
+    pub async fn new_at_with_after_connect<F>(
+        path: &Path,
+        after_connect: F,
+    ) -> Result<DBService, Error>
+    where
+        F: for<'a> Fn(
+                &'a mut SqliteConnection,
+            ) -> std::pin::Pin<
+                Box<dyn std::future::Future<Output = Result<(), Error>> + Send + 'a>,
+            > + Send
+            + Sync
+            + 'static,
+    {
+        let pool =
+            Self::create_pool(&Self::format_sqlite_url(path), Some(Arc::new(after_connect)))
+                .await?;
         Ok(DBService { pool })
     }
 
-    async fn create_pool<F>(after_connect: Option<Arc<F>>) -> Result<Pool<Sqlite>, Error>
+    async fn create_pool<F>(
+        database_url: &str,
+        after_connect: Option<Arc<F>>,
+    ) -> Result<Pool<Sqlite>, Error>
     where
         F: for<'a> Fn(
                 &'a mut SqliteConnection,
@@ -103,8 +140,7 @@ impl DBService {
             + Sync
             + 'static,
     {
-        let database_url = Self::get_database_url();
-        let options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
+        let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
 
         let pool = if let Some(hook) = after_connect {
             SqlitePoolOptions::new()
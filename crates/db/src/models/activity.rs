@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs_forge::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityEventType {
+    TaskCreated,
+    TaskUpdated,
+    AttemptStarted,
+    RunStarted,
+}
+
+/// A single entry of the cross-project activity feed
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ActivityEvent {
+    pub event_type: ActivityEventType,
+    pub project_id: Uuid,
+    pub project_name: String,
+    /// Id of the task, task attempt or execution run the event is about
+    pub entity_id: Uuid,
+    pub task_id: Option<Uuid>,
+    /// Task title, or the start of the prompt for execution runs
+    pub title: String,
+    /// Task status for task events, executor for attempts and runs
+    pub detail: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ActivityQuery {
+    pub limit: i64,
+    pub since: Option<DateTime<Utc>>,
+    /// Include agent chat tasks (those registered in `forge_agents`)
+    pub include_agents: bool,
+}
+
+impl ActivityEvent {
+    /// Most recent task, attempt and execution run events across all projects
+    pub async fn find_recent(
+        pool: &SqlitePool,
+        query: &ActivityQuery,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ActivityEvent>(
+            r#"SELECT event_type, project_id, project_name, entity_id, task_id, title, detail, occurred_at
+               FROM (
+                   SELECT 'task_created' AS event_type, t.project_id, p.name AS project_name,
+                          t.id AS entity_id, t.id AS task_id, t.title, t.status AS detail,
+                          t.created_at AS occurred_at
+                     FROM tasks t
+                     JOIN projects p ON p.id = t.project_id
+                    WHERE (?1 OR t.id NOT IN (SELECT task_id FROM forge_agents))
+                   UNION ALL
+                   SELECT 'task_updated', t.project_id, p.name, t.id, t.id, t.title, t.status,
+                          t.updated_at
+                     FROM tasks t
+                     JOIN projects p ON p.id = t.project_id
+                    WHERE t.updated_at > t.created_at
+                      AND (?1 OR t.id NOT IN (SELECT task_id FROM forge_agents))
+                   UNION ALL
+                   SELECT 'attempt_started', t.project_id, p.name, ta.id, t.id, t.title,
+                          ta.executor, ta.created_at
+                     FROM task_attempts ta
+                     JOIN tasks t ON t.id = ta.task_id
+                     JOIN projects p ON p.id = t.project_id
+                    WHERE (?1 OR t.id NOT IN (SELECT task_id FROM forge_agents))
+                   UNION ALL
+                   SELECT 'run_started', r.project_id, p.name, r.id, NULL, substr(r.prompt, 1, 200),
+                          r.executor, r.created_at
+                     FROM execution_runs r
+                     JOIN projects p ON p.id = r.project_id
+               )
+               WHERE ?2 IS NULL OR julianday(occurred_at) > julianday(?2)
+               ORDER BY julianday(occurred_at) DESC
+               LIMIT ?3"#,
+        )
+        .bind(query.include_agents)
+        .bind(query.since)
+        .bind(query.limit)
+        .fetch_all(pool)
+        .await
+    }
+}
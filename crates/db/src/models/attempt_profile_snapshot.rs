@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs_forge::TS;
+use uuid::Uuid;
+
+/// Executor configuration an attempt was started with, kept so the run can be audited or
+/// reproduced after the profile changes
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct AttemptProfileSnapshot {
+    pub task_attempt_id: Uuid,
+    /// Profile the attempt was started with, e.g. `CLAUDE_CODE:GENIE`
+    pub executor_profile: String,
+    /// Resolved coding agent configuration (model, flags, appended prompt)
+    pub config: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AttemptProfileSnapshot {
+    /// Records the snapshot for an attempt. The first snapshot wins: an attempt keeps the
+    /// configuration it was started with.
+    pub async fn create(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        executor_profile: &str,
+        config: &serde_json::Value,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"INSERT INTO forge_attempt_profile_snapshot
+                   (task_attempt_id, executor_profile, config)
+               VALUES (?, ?, ?)
+               ON CONFLICT (task_attempt_id) DO NOTHING"#,
+        )
+        .bind(task_attempt_id)
+        .bind(executor_profile)
+        .bind(config.to_string())
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let row: Option<(Uuid, String, String, DateTime<Utc>)> = sqlx::query_as(
+            r#"SELECT task_attempt_id, executor_profile, config, created_at
+               FROM forge_attempt_profile_snapshot
+               WHERE task_attempt_id = ?"#,
+        )
+        .bind(task_attempt_id)
+        .fetch_optional(pool)
+        .await?;
+
+        row.map(|(task_attempt_id, executor_profile, config, created_at)| {
+            Ok(Self {
+                task_attempt_id,
+                executor_profile,
+                config: serde_json::from_str(&config)
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+                created_at,
+            })
+        })
+        .transpose()
+    }
+}
@@ -5,6 +5,10 @@ use sqlx::{FromRow, SqlitePool};
 use ts_rs_forge::TS;
 use uuid::Uuid;
 
+/// Text of the stderr line that stands in for log lines dropped by truncation
+const ELISION_MARKER_PREFIX: &str = "[forge] ";
+const ELISION_MARKER_SUFFIX: &str = " log line(s) elided to bound stored log size";
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct ExecutionProcessLogs {
     pub execution_id: Uuid,
@@ -82,6 +86,128 @@ impl ExecutionProcessLogs {
         Ok(messages)
     }
 
+    /// Whether `msg` is the marker left behind by [`Self::truncate_jsonl`]
+    pub fn is_elision_marker(msg: &LogMsg) -> bool {
+        matches!(msg, LogMsg::Stderr(text) if Self::elided_line_count(text).is_some())
+    }
+
+    fn elided_line_count(text: &str) -> Option<usize> {
+        text.strip_prefix(ELISION_MARKER_PREFIX)?
+            .strip_suffix(ELISION_MARKER_SUFFIX)?
+            .parse()
+            .ok()
+    }
+
+    fn elision_marker_line(elided: usize) -> String {
+        let marker = LogMsg::Stderr(format!(
+            "{ELISION_MARKER_PREFIX}{elided}{ELISION_MARKER_SUFFIX}"
+        ));
+        let mut line = serde_json::to_string(&marker).unwrap_or_default();
+        line.push('\n');
+        line
+    }
+
+    /// Shrinks JSONL logs to roughly `max_bytes`, keeping whole lines from the head (a quarter
+    /// of the budget) and the tail, with a single marker line in place of what was dropped.
+    /// The last line is always kept so the final agent output survives. Truncating logs that
+    /// were already truncated keeps the original head and folds the counts into one marker.
+    ///
+    /// Returns `None` when the logs already fit.
+    pub fn truncate_jsonl(logs: &str, max_bytes: usize) -> Option<String> {
+        if logs.len() <= max_bytes {
+            return None;
+        }
+        let lines: Vec<&str> = logs.split_inclusive('\n').collect();
+        let marker_index = lines.iter().position(|line| {
+            serde_json::from_str::<LogMsg>(line.trim_end())
+                .is_ok_and(|msg| Self::is_elision_marker(&msg))
+        });
+
+        let (head, previously_elided, rest) = match marker_index {
+            Some(index) => {
+                let count = match serde_json::from_str::<LogMsg>(lines[index].trim_end()) {
+                    Ok(LogMsg::Stderr(text)) => Self::elided_line_count(&text).unwrap_or(0),
+                    _ => 0,
+                };
+                (&lines[..index], count, &lines[index + 1..])
+            }
+            None => {
+                let head_budget = max_bytes / 4;
+                let mut used = 0;
+                let head_len = lines
+                    .iter()
+                    .take_while(|line| {
+                        used += line.len();
+                        used <= head_budget
+                    })
+                    .count();
+                (&lines[..head_len], 0, &lines[head_len..])
+            }
+        };
+
+        let head_bytes: usize = head.iter().map(|line| line.len()).sum();
+        let marker_bytes = Self::elision_marker_line(lines.len()).len();
+        let tail_budget = max_bytes.saturating_sub(head_bytes + marker_bytes);
+        let mut used = 0;
+        let tail_len = rest
+            .iter()
+            .rev()
+            .enumerate()
+            .take_while(|(index, line)| {
+                used += line.len();
+                *index == 0 || used <= tail_budget
+            })
+            .count();
+
+        let elided = rest.len() - tail_len;
+        if elided == 0 {
+            return None;
+        }
+
+        let mut truncated = head.concat();
+        truncated.push_str(&Self::elision_marker_line(previously_elided + elided));
+        truncated.push_str(&rest[rest.len() - tail_len..].concat());
+        Some(truncated)
+    }
+
+    /// Overwrites the stored logs of an execution process
+    pub async fn replace_logs(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        logs: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE execution_process_logs SET logs = ?, byte_size = ? WHERE execution_id = ?",
+        )
+        .bind(logs)
+        .bind(logs.len() as i64)
+        .bind(execution_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Logs larger than `min_bytes` belonging to processes that finished before `completed_before`
+    pub async fn find_compactable(
+        pool: &SqlitePool,
+        completed_before: DateTime<Utc>,
+        min_bytes: i64,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"SELECT l.execution_id
+               FROM execution_process_logs l
+               JOIN execution_processes ep ON ep.id = l.execution_id
+               WHERE ep.status != 'running'
+                 AND ep.completed_at IS NOT NULL
+                 AND datetime(ep.completed_at) < datetime(?)
+                 AND l.byte_size > ?"#,
+        )
+        .bind(completed_before)
+        .bind(min_bytes)
+        .fetch_all(pool)
+        .await
+    }
+
     /// Convert Vec<LogMsg> to JSONL format
     pub fn serialize_logs(messages: &[LogMsg]) -> Result<String, serde_json::Error> {
         let mut jsonl = String::new();
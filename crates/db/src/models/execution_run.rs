@@ -237,6 +237,25 @@ impl ExecutionRun {
         Ok(())
     }
 
+    /// Mark the run's branch as deleted so cleanup isn't attempted twice
+    pub async fn mark_branch_deleted(pool: &SqlitePool, run_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE execution_runs SET branch_deleted = TRUE, updated_at = datetime('now', 'subsec') WHERE id = ?",
+        )
+        .bind(run_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Check whether the run's branch has already been deleted
+    pub async fn is_branch_deleted(pool: &SqlitePool, run_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar::<_, bool>("SELECT branch_deleted FROM execution_runs WHERE id = ?")
+            .bind(run_id)
+            .fetch_one(pool)
+            .await
+    }
+
     /// Check if container_ref exists
     pub async fn container_ref_exists(
         pool: &SqlitePool,
@@ -0,0 +1,217 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use tokio::sync::broadcast;
+use ts_rs_forge::TS;
+use uuid::Uuid;
+
+/// Agent type used for agent chat tasks started without a worktree
+pub const AGENT_CHAT_TYPE: &str = "genie_chat";
+
+/// Buffered registration events per subscriber; lagging subscribers should reload from the db
+const REGISTRATION_EVENT_CAPACITY: usize = 256;
+
+static REGISTRATIONS: LazyLock<broadcast::Sender<AgentRegistered>> =
+    LazyLock::new(|| broadcast::channel(REGISTRATION_EVENT_CAPACITY).0);
+
+/// Published whenever a task is registered as a project agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgentRegistered {
+    pub project_id: Uuid,
+    pub task_id: Uuid,
+}
+
+/// Registration of a task as a project agent. Registered tasks are hidden from the kanban
+/// and are expected to carry `TaskStatus::Agent`.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ForgeAgent {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub agent_type: String,
+    pub task_id: Uuid,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Mismatches between `tasks.status = 'agent'` and `forge_agents` membership
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct AgentConsistencyReport {
+    /// `forge_agents` rows whose task no longer exists
+    pub orphaned_rows: u64,
+    /// Registered agent tasks whose status is no longer `agent`
+    pub status_drift: Vec<Uuid>,
+    /// Tasks with status `agent` that are not registered in `forge_agents`
+    pub unregistered: Vec<Uuid>,
+    /// Unregistered tasks that could not be registered because their project already has an
+    /// agent chat, and were archived instead
+    pub archived: Vec<Uuid>,
+    /// Whether the mismatches above were repaired
+    pub repaired: bool,
+}
+
+impl AgentConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.orphaned_rows == 0 && self.status_drift.is_empty() && self.unregistered.is_empty()
+    }
+}
+
+impl ForgeAgent {
+    pub async fn is_agent_task(pool: &SqlitePool, task_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM forge_agents WHERE task_id = ?)")
+            .bind(task_id)
+            .fetch_one(pool)
+            .await
+    }
+
+    /// Registers a task as an agent of its project. Returns false when the project already has
+    /// an agent of this type, in which case nothing is inserted.
+    pub async fn register_task(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        task_id: Uuid,
+        agent_type: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let registered = Self::insert_registration(pool, project_id, task_id, agent_type).await?;
+        if registered {
+            Self::announce_registration(project_id, task_id);
+        }
+        Ok(registered)
+    }
+
+    /// Inserts the `forge_agents` row without announcing it, for callers that hold a
+    /// transaction and must only announce once it commits
+    async fn insert_registration<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        task_id: Uuid,
+        agent_type: &str,
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query(
+            r#"INSERT OR IGNORE INTO forge_agents (id, project_id, agent_type, task_id, created_at, updated_at)
+               VALUES (?, ?, ?, ?, datetime('now'), datetime('now'))"#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(project_id)
+        .bind(agent_type)
+        .bind(task_id)
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn announce_registration(project_id: Uuid, task_id: Uuid) {
+        // No subscribers is fine; nobody is streaming this project's kanban
+        let _ = REGISTRATIONS.send(AgentRegistered {
+            project_id,
+            task_id,
+        });
+    }
+
+    /// Receive an [`AgentRegistered`] event whenever a task is registered as an agent
+    pub fn subscribe_registrations() -> broadcast::Receiver<AgentRegistered> {
+        REGISTRATIONS.subscribe()
+    }
+
+    pub async fn unregister_task<'e, E>(executor: E, task_id: Uuid) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query("DELETE FROM forge_agents WHERE task_id = ?")
+            .bind(task_id)
+            .execute(executor)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Detects drift between agent task statuses and `forge_agents` membership, and repairs it
+    /// when `repair` is set. Membership is treated as the source of truth: orphaned rows are
+    /// removed, registered tasks get their `agent` status back, and unregistered `agent` tasks
+    /// are registered as agent chats. A project has at most one agent chat, so the tasks that
+    /// don't fit are archived rather than left with a status nothing else can change.
+    pub async fn reconcile(
+        pool: &SqlitePool,
+        repair: bool,
+    ) -> Result<AgentConsistencyReport, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        let orphaned_rows: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM forge_agents WHERE task_id NOT IN (SELECT id FROM tasks)",
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let status_drift: Vec<Uuid> = sqlx::query_scalar(
+            r#"SELECT t.id FROM tasks t
+               JOIN forge_agents fa ON fa.task_id = t.id
+               WHERE t.status != 'agent'
+               ORDER BY t.created_at"#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let unregistered: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            r#"SELECT id, project_id FROM tasks
+               WHERE status = 'agent' AND id NOT IN (SELECT task_id FROM forge_agents)
+               ORDER BY created_at, rowid"#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut report = AgentConsistencyReport {
+            orphaned_rows: orphaned_rows as u64,
+            status_drift,
+            unregistered: unregistered.iter().map(|(task_id, _)| *task_id).collect(),
+            archived: Vec::new(),
+            repaired: false,
+        };
+
+        if !repair || report.is_consistent() {
+            return Ok(report);
+        }
+
+        sqlx::query("DELETE FROM forge_agents WHERE task_id NOT IN (SELECT id FROM tasks)")
+            .execute(&mut *tx)
+            .await?;
+
+        for task_id in &report.status_drift {
+            sqlx::query(
+                "UPDATE tasks SET status = 'agent', updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            )
+            .bind(task_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let mut registered = Vec::new();
+        for (task_id, project_id) in &unregistered {
+            if Self::insert_registration(&mut *tx, *project_id, *task_id, AGENT_CHAT_TYPE).await? {
+                registered.push((*project_id, *task_id));
+                continue;
+            }
+            tracing::warn!(
+                "Archiving agent task {}: project {} already has a {} agent",
+                task_id,
+                project_id,
+                AGENT_CHAT_TYPE
+            );
+            sqlx::query(
+                "UPDATE tasks SET status = 'archived', updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            )
+            .bind(task_id)
+            .execute(&mut *tx)
+            .await?;
+            report.archived.push(*task_id);
+        }
+
+        tx.commit().await?;
+        for (project_id, task_id) in registered {
+            Self::announce_registration(project_id, task_id);
+        }
+        report.repaired = true;
+        Ok(report)
+    }
+}
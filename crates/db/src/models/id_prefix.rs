@@ -0,0 +1,81 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Shortest UUID prefix accepted as a reference, matching the 8-character
+/// ids used in `run/` branch names with some room to spare.
+pub const MIN_ID_PREFIX_LEN: usize = 6;
+
+/// Maximum number of candidates reported for an ambiguous prefix
+pub const MAX_AMBIGUOUS_CANDIDATES: usize = 10;
+
+/// Outcome of resolving a full UUID or a UUID prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdResolution {
+    Found(Uuid),
+    NotFound,
+    /// The prefix matches several rows; holds up to `MAX_AMBIGUOUS_CANDIDATES`
+    /// of them, most recent first.
+    Ambiguous(Vec<Uuid>),
+    /// The reference is neither a UUID nor a long enough hexadecimal prefix.
+    Invalid,
+}
+
+/// Normalizes a UUID prefix to the lowercase, hyphen-less form produced by
+/// `hex(id)` in SQLite.
+pub fn normalize_id_prefix(reference: &str) -> Option<String> {
+    let prefix: String = reference
+        .trim()
+        .chars()
+        .filter(|c| *c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+
+    if prefix.len() < MIN_ID_PREFIX_LEN
+        || prefix.len() > 32
+        || !prefix.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return None;
+    }
+    Some(prefix)
+}
+
+/// Resolves `reference` against the `id` column of `table`, accepting a full
+/// UUID or an unambiguous prefix of one.
+pub(crate) async fn resolve_id(
+    pool: &SqlitePool,
+    table: &'static str,
+    reference: &str,
+) -> Result<IdResolution, sqlx::Error> {
+    if let Ok(id) = Uuid::parse_str(reference.trim()) {
+        let exists: Option<Uuid> =
+            sqlx::query_scalar(&format!("SELECT id FROM {table} WHERE id = ?"))
+                .bind(id)
+                .fetch_optional(pool)
+                .await?;
+        return Ok(match exists {
+            Some(id) => IdResolution::Found(id),
+            None => IdResolution::NotFound,
+        });
+    }
+
+    let Some(prefix) = normalize_id_prefix(reference) else {
+        return Ok(IdResolution::Invalid);
+    };
+
+    let mut candidates: Vec<Uuid> = sqlx::query_scalar(&format!(
+        "SELECT id FROM {table} WHERE lower(hex(id)) LIKE ? ORDER BY created_at DESC LIMIT ?"
+    ))
+    .bind(format!("{prefix}%"))
+    .bind(MAX_AMBIGUOUS_CANDIDATES as i64 + 1)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(match candidates.len() {
+        0 => IdResolution::NotFound,
+        1 => IdResolution::Found(candidates[0]),
+        _ => {
+            candidates.truncate(MAX_AMBIGUOUS_CANDIDATES);
+            IdResolution::Ambiguous(candidates)
+        }
+    })
+}
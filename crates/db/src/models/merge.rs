@@ -213,6 +213,16 @@ impl Merge {
 
         Ok(())
     }
+
+    /// Delete a merge record, e.g. to detach a PR from its attempt
+    pub async fn delete(pool: &SqlitePool, merge_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM merges WHERE id = ?")
+            .bind(merge_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
     /// Find all merges for a task attempt (returns both direct and PR merges)
     pub async fn find_by_task_attempt_id(
         pool: &SqlitePool,
@@ -1,11 +1,17 @@
+pub mod activity;
+pub mod attempt_profile_snapshot;
 pub mod draft;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod execution_run;
 pub mod executor_session;
+pub mod forge_agent;
+pub mod id_prefix;
 pub mod image;
 pub mod merge;
 pub mod project;
+pub mod project_fetch_state;
 pub mod tag;
 pub mod task;
 pub mod task_attempt;
+pub mod task_idempotency_key;
@@ -39,9 +39,11 @@ pub struct Project {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS)]
 pub struct CreateProject {
     pub name: String,
+    /// May be left empty when `clone_url` is set, to clone into the workspace directory
+    #[serde(default)]
     pub git_repo_path: String,
     pub use_existing_repo: bool,
     pub setup_script: Option<String>,
@@ -49,6 +51,10 @@ pub struct CreateProject {
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
     pub commit_prompt: Option<String>,
+    /// Remote repository to clone for the project instead of using a local path
+    #[serde(default)]
+    #[ts(optional)]
+    pub clone_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Last successful `git fetch` of a project's remote
+pub struct ProjectFetchState;
+
+impl ProjectFetchState {
+    pub async fn record_fetch(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        fetched_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"INSERT INTO forge_project_fetch_state (project_id, last_fetch_at)
+               VALUES (?, ?)
+               ON CONFLICT (project_id) DO UPDATE SET last_fetch_at = excluded.last_fetch_at"#,
+        )
+        .bind(project_id)
+        .bind(fetched_at)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn last_fetch_at(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"SELECT last_fetch_at FROM forge_project_fetch_state WHERE project_id = ?"#,
+        )
+        .bind(project_id)
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -1,13 +1,17 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
-use strum_macros::{Display, EnumString};
+use strum_macros::Display;
 use ts_rs_forge::TS;
 use uuid::Uuid;
 
-use super::{project::Project, task_attempt::TaskAttempt};
+use super::{
+    id_prefix::{self, IdResolution},
+    project::Project,
+    task_attempt::TaskAttempt,
+};
 
-#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display)]
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, TS, Display)]
 #[sqlx(type_name = "task_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 #[strum(serialize_all = "kebab_case")]
@@ -21,6 +25,33 @@ pub enum TaskStatus {
     Archived,
 }
 
+/// Accepts the kebab-case `Display` form (`in-progress`), the lowercase form
+/// stored in the database (`inprogress`), and snake/space separated variants,
+/// all case-insensitively.
+impl std::str::FromStr for TaskStatus {
+    type Err = strum::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized: String = s
+            .trim()
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+
+        match normalized.as_str() {
+            "todo" => Ok(TaskStatus::Todo),
+            "inprogress" => Ok(TaskStatus::InProgress),
+            "inreview" => Ok(TaskStatus::InReview),
+            "done" => Ok(TaskStatus::Done),
+            "cancelled" => Ok(TaskStatus::Cancelled),
+            "agent" => Ok(TaskStatus::Agent),
+            "archived" => Ok(TaskStatus::Archived),
+            _ => Err(strum::ParseError::VariantNotFound),
+        }
+    }
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct Task {
     pub id: Uuid,
@@ -34,6 +65,14 @@ pub struct Task {
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectTaskCounts {
+    pub project_id: Uuid,
+    /// Tasks in progress or with a running attempt
+    pub active: i64,
+    pub total: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct TaskWithAttemptStatus {
     #[serde(flatten)]
@@ -98,9 +137,21 @@ pub struct UpdateTask {
     pub status: Option<TaskStatus>,
     pub parent_task_attempt: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
+    /// Only apply the update if the task's `updated_at` still equals this value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl Task {
+    /// Resolves a full task id or an unambiguous prefix of one
+    pub async fn resolve_id(
+        pool: &SqlitePool,
+        reference: &str,
+    ) -> Result<IdResolution, sqlx::Error> {
+        id_prefix::resolve_id(pool, "tasks", reference).await
+    }
+
     pub fn to_prompt(&self) -> String {
         if let Some(description) = self.description.as_ref().filter(|d| !d.trim().is_empty()) {
             format!("{}\n\n{}", &self.title, description)
@@ -281,6 +332,74 @@ WHERE t.id = $1"#,
         .await
     }
 
+    /// Ids and titles of a project's tasks, leaving out archived and agent chat tasks
+    pub async fn find_titles_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<(Uuid, String)>, sqlx::Error> {
+        sqlx::query_as::<_, (Uuid, String)>(
+            r#"SELECT id, title
+               FROM tasks
+               WHERE project_id = ?
+                 AND status != 'archived'
+                 AND id NOT IN (SELECT task_id FROM forge_agents)"#,
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Tasks a search may return: everything except archived tasks and agent chat tasks,
+    /// optionally narrowed to one project and status
+    pub async fn find_searchable(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        status: Option<TaskStatus>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Task>(
+            r#"SELECT id, project_id, title, description, status, parent_task_attempt,
+                      dev_server_id, created_at, updated_at
+               FROM tasks
+               WHERE status != 'archived'
+                 AND id NOT IN (SELECT task_id FROM forge_agents)
+                 AND (?1 IS NULL OR project_id = ?1)
+                 AND (?2 IS NULL OR status = ?2)"#,
+        )
+        .bind(project_id)
+        .bind(status)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Active and total task counts for every project, in one query. Agent chat tasks are left
+    /// out like on the kanban board; a task is active when it is in progress or has a running
+    /// attempt.
+    pub async fn count_by_project(
+        pool: &SqlitePool,
+    ) -> Result<Vec<ProjectTaskCounts>, sqlx::Error> {
+        sqlx::query_as::<_, ProjectTaskCounts>(
+            r#"SELECT p.id AS project_id,
+                      COALESCE(SUM(CASE WHEN t.status = 'inprogress' OR EXISTS (
+                          SELECT 1
+                            FROM task_attempts ta
+                            JOIN execution_processes ep ON ep.task_attempt_id = ta.id
+                           WHERE ta.task_id = t.id
+                             AND ep.status = 'running'
+                             AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+                      ) THEN 1 ELSE 0 END), 0) AS active,
+                      COUNT(t.id) AS total
+                 FROM projects p
+                 LEFT JOIN tasks t
+                   ON t.project_id = p.id
+                  AND t.status != 'agent'
+                  AND t.id NOT IN (SELECT task_id FROM forge_agents)
+                GROUP BY p.id
+                ORDER BY p.created_at DESC"#,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
@@ -344,6 +463,9 @@ WHERE t.id = $1"#,
         .await
     }
 
+    /// Update a task and bump its `updated_at`. With `expected_updated_at`, the write only
+    /// happens if the stored `updated_at` still matches it; `None` is returned when it doesn't.
+    #[allow(clippy::too_many_arguments)]
     pub async fn update(
         pool: &SqlitePool,
         id: Uuid,
@@ -352,39 +474,64 @@ WHERE t.id = $1"#,
         description: Option<String>,
         status: TaskStatus,
         parent_task_attempt: Option<Uuid>,
-    ) -> Result<Self, sqlx::Error> {
-        sqlx::query_as!(
-            Task,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, Task>(
             r#"UPDATE tasks
-               SET title = $3, description = $4, status = $5, parent_task_attempt = $6
-               WHERE id = $1 AND project_id = $2
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", dev_server_id as "dev_server_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
-            id,
-            project_id,
-            title,
-            description,
-            status,
-            parent_task_attempt
+               SET title = ?3, description = ?4, status = ?5, parent_task_attempt = ?6,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = ?1 AND project_id = ?2
+                 AND (?7 IS NULL OR datetime(updated_at, 'subsec') = datetime(?7, 'subsec'))
+               RETURNING id, project_id, title, description, status, parent_task_attempt,
+                         dev_server_id, created_at, updated_at"#,
         )
-        .fetch_one(pool)
+        .bind(id)
+        .bind(project_id)
+        .bind(title)
+        .bind(description)
+        .bind(status)
+        .bind(parent_task_attempt)
+        .bind(expected_updated_at)
+        .fetch_optional(pool)
         .await
     }
 
+    /// Lifecycle status transition (attempt started, finished, merged, ...). Agent tasks keep
+    /// their `agent` status so they stay consistent with their `forge_agents` registration;
+    /// use [`Task::update`] to change it explicitly.
     pub async fn update_status(
         pool: &SqlitePool,
         id: Uuid,
         status: TaskStatus,
     ) -> Result<(), sqlx::Error> {
-        sqlx::query!(
-            "UPDATE tasks SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
-            id,
-            status
+        sqlx::query(
+            "UPDATE tasks SET status = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ? AND status != 'agent'",
         )
+        .bind(status)
+        .bind(id)
         .execute(pool)
         .await?;
         Ok(())
     }
 
+    /// Moves a task to `to` only while it is still `from`. Returns whether the task changed.
+    pub async fn update_status_from(
+        pool: &SqlitePool,
+        id: Uuid,
+        from: TaskStatus,
+        to: TaskStatus,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE tasks SET status = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ? AND status = ?",
+        )
+        .bind(to)
+        .bind(id)
+        .bind(from)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Nullify parent_task_attempt for all tasks that reference the given attempt ID
     /// This breaks parent-child relationships before deleting a parent task
     pub async fn nullify_children_by_attempt_id<'e, E>(
@@ -478,3 +625,49 @@ WHERE t.id = $1"#,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::TaskStatus;
+
+    const ALL_STATUSES: [TaskStatus; 7] = [
+        TaskStatus::Todo,
+        TaskStatus::InProgress,
+        TaskStatus::InReview,
+        TaskStatus::Done,
+        TaskStatus::Cancelled,
+        TaskStatus::Agent,
+        TaskStatus::Archived,
+    ];
+
+    #[test]
+    fn task_status_round_trips_through_display() {
+        for status in ALL_STATUSES {
+            assert_eq!(TaskStatus::from_str(&status.to_string()).unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn task_status_parses_all_input_forms() {
+        for input in ["in-progress", "in_progress", "in progress", "InProgress", "IN-PROGRESS"] {
+            assert_eq!(TaskStatus::from_str(input).unwrap(), TaskStatus::InProgress);
+        }
+        for input in ["in-review", "inreview", "In Review", "IN_REVIEW"] {
+            assert_eq!(TaskStatus::from_str(input).unwrap(), TaskStatus::InReview);
+        }
+        assert_eq!(TaskStatus::from_str(" Todo ").unwrap(), TaskStatus::Todo);
+        assert!(TaskStatus::from_str("in-flight").is_err());
+        assert!(TaskStatus::from_str("").is_err());
+    }
+
+    #[test]
+    fn task_status_parses_database_form() {
+        for status in ALL_STATUSES {
+            let stored = serde_json::to_value(status).unwrap();
+            let stored = stored.as_str().unwrap();
+            assert_eq!(TaskStatus::from_str(stored).unwrap(), status);
+        }
+    }
+}
@@ -6,7 +6,11 @@ use thiserror::Error;
 use ts_rs_forge::TS;
 use uuid::Uuid;
 
-use super::{project::Project, task::Task};
+use super::{
+    id_prefix::{self, IdResolution},
+    project::Project,
+    task::Task,
+};
 
 #[derive(Debug, Error)]
 pub enum TaskAttemptError {
@@ -43,6 +47,7 @@ pub struct TaskAttempt {
     pub target_branch: String,         // Target branch for this attempt
     pub executor: String, // Name of the base coding agent to use ("AMP", "CLAUDE_CODE",
     // "GEMINI", etc.)
+    pub variant: Option<String>, // Executor variant the attempt was started with, e.g. "GENIE"
     pub worktree_deleted: bool, // Flag indicating if worktree has been cleaned up
     pub setup_completed_at: Option<DateTime<Utc>>, // When setup script was last completed
     pub input_tokens: Option<i32>, // LLM input tokens used
@@ -86,11 +91,29 @@ pub struct TaskAttemptContext {
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateTaskAttempt {
     pub executor: BaseCodingAgent,
+    pub variant: Option<String>,
     pub base_branch: String,
     pub branch: String,
 }
 
 impl TaskAttempt {
+    /// Resolves a full task attempt id or an unambiguous prefix of one
+    pub async fn resolve_id(
+        pool: &SqlitePool,
+        reference: &str,
+    ) -> Result<IdResolution, sqlx::Error> {
+        id_prefix::resolve_id(pool, "task_attempts", reference).await
+    }
+
+    /// Base executor and variant the attempt was started with. Attempts created before the
+    /// `variant` column existed stored both in `executor` as `EXECUTOR:VARIANT`.
+    pub fn executor_and_variant(&self) -> (&str, Option<&str>) {
+        match (&self.variant, self.executor.split_once(':')) {
+            (None, Some((executor, variant))) => (executor, Some(variant)),
+            (variant, _) => (&self.executor, variant.as_deref()),
+        }
+    }
+
     pub async fn parent_task(&self, pool: &SqlitePool) -> Result<Option<Task>, sqlx::Error> {
         Task::find_by_id(pool, self.task_id).await
     }
@@ -109,6 +132,7 @@ impl TaskAttempt {
                               branch,
                               target_branch,
                               executor AS "executor!",
+                              variant,
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                               input_tokens AS "input_tokens: i32",
@@ -133,6 +157,7 @@ impl TaskAttempt {
                               branch,
                               target_branch,
                               executor AS "executor!",
+                              variant,
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                               input_tokens AS "input_tokens: i32",
@@ -168,6 +193,7 @@ impl TaskAttempt {
                        ta.branch,
                        ta.target_branch,
                        ta.executor AS "executor!",
+                       ta.variant,
                        ta.worktree_deleted  AS "worktree_deleted!: bool",
                        ta.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        ta.input_tokens AS "input_tokens: i32",
@@ -267,6 +293,20 @@ impl TaskAttempt {
         Ok(())
     }
 
+    /// Clear the worktree-deleted flag once the worktree has been recreated
+    pub async fn mark_worktree_restored(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE task_attempts SET worktree_deleted = FALSE, updated_at = datetime('now') WHERE id = ?",
+        )
+        .bind(attempt_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             TaskAttempt,
@@ -276,6 +316,7 @@ impl TaskAttempt {
                        branch,
                        target_branch,
                        executor AS "executor!",
+                       variant,
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        input_tokens AS "input_tokens: i32",
@@ -301,6 +342,7 @@ impl TaskAttempt {
                        branch,
                        target_branch,
                        executor AS "executor!",
+                       variant,
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        input_tokens AS "input_tokens: i32",
@@ -427,15 +469,16 @@ impl TaskAttempt {
         // Insert the record into the database
         Ok(sqlx::query_as!(
             TaskAttempt,
-            r#"INSERT INTO task_attempts (id, task_id, container_ref, branch, target_branch, executor, worktree_deleted, setup_completed_at)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!",  worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", input_tokens as "input_tokens: i32", output_tokens as "output_tokens: i32", cache_creation_tokens as "cache_creation_tokens: i32", cache_read_tokens as "cache_read_tokens: i32", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO task_attempts (id, task_id, container_ref, branch, target_branch, executor, variant, worktree_deleted, setup_completed_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!", variant, worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", input_tokens as "input_tokens: i32", output_tokens as "output_tokens: i32", cache_creation_tokens as "cache_creation_tokens: i32", cache_read_tokens as "cache_read_tokens: i32", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             task_id,
             Option::<String>::None, // Container isn't known yet
             data.branch,
             data.base_branch, // Target branch is same as base branch during creation
             data.executor,
+            data.variant,
             false, // worktree_deleted is false during creation
             Option::<DateTime<Utc>>::None // setup_completed_at is None during creation
         )
@@ -459,6 +502,58 @@ impl TaskAttempt {
         Ok(())
     }
 
+    /// Moves an attempt under another task in a single transaction. Child tasks spawned from
+    /// the attempt keep pointing at it and are touched so their new lineage is streamed; the
+    /// source and target tasks are touched so their attempt summaries refresh.
+    ///
+    /// Returns the number of child tasks that were re-pointed.
+    pub async fn move_to_task(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        target_task_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        let source_task_id: Uuid =
+            sqlx::query_scalar("SELECT task_id FROM task_attempts WHERE id = ?")
+                .bind(attempt_id)
+                .fetch_one(&mut *tx)
+                .await?;
+
+        sqlx::query(
+            "UPDATE task_attempts SET task_id = ?, updated_at = datetime('now') WHERE id = ?",
+        )
+        .bind(target_task_id)
+        .bind(attempt_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let children = sqlx::query(
+            "UPDATE tasks SET updated_at = datetime('now') WHERE parent_task_attempt = ?",
+        )
+        .bind(attempt_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query("UPDATE tasks SET updated_at = datetime('now') WHERE id IN (?, ?)")
+            .bind(source_task_id)
+            .bind(target_task_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(children)
+    }
+
+    /// Deletes the attempt row; config, snapshots and processes go with it via cascade
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_attempts WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn resolve_container_ref(
         pool: &SqlitePool,
         container_ref: &str,
@@ -0,0 +1,44 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Key a client sent with a task creation request, mapped to the task it created
+pub struct TaskIdempotencyKey;
+
+impl TaskIdempotencyKey {
+    /// Task created earlier in the project under `key`, if any
+    pub async fn find_task_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        key: &str,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"SELECT task_id FROM forge_task_idempotency_keys
+               WHERE project_id = ? AND idempotency_key = ?"#,
+        )
+        .bind(project_id)
+        .bind(key)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Records `key` for a newly created task. Returns `false` when a concurrent request
+    /// claimed the key first.
+    pub async fn record(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        key: &str,
+        task_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"INSERT INTO forge_task_idempotency_keys (project_id, idempotency_key, task_id)
+               VALUES (?, ?, ?)
+               ON CONFLICT (project_id, idempotency_key) DO NOTHING"#,
+        )
+        .bind(project_id)
+        .bind(key)
+        .bind(task_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
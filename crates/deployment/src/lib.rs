@@ -7,6 +7,7 @@ use forge_core_db::{
     DBService,
     models::{
         execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+        forge_agent::ForgeAgent,
         project::{CreateProject, Project},
         task::{Task, TaskStatus},
         task_attempt::{TaskAttempt, TaskAttemptError},
@@ -30,6 +31,7 @@ use forge_core_services::services::{
     omni::OmniService,
     pr_monitor::PrMonitorService,
     profile_loader::ProfileCacheManager,
+    shutdown::{RunningAttemptPolicy, ShutdownCoordinator, ShutdownReport},
     worktree_manager::WorktreeError,
 };
 use forge_core_utils::{msg_store::MsgStore, sentry as sentry_utils};
@@ -114,6 +116,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn profile_cache(&self) -> &ProfileCacheManager;
 
+    fn shutdown(&self) -> &ShutdownCoordinator;
+
     async fn update_sentry_scope(&self) -> Result<(), DeploymentError> {
         let user_id = self.user_id();
         let config = self.config().read().await;
@@ -148,6 +152,36 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         }
     }
 
+    /// Clean up before the process exits, call once the server stops accepting requests.
+    /// Running executor processes are stopped or left running per `FORGE_SHUTDOWN_ATTEMPTS`.
+    async fn graceful_shutdown(&self) -> ShutdownReport {
+        self.shutdown().begin();
+
+        match RunningAttemptPolicy::from_env() {
+            RunningAttemptPolicy::Stop => match self.container().stop_running_processes().await {
+                Ok(stopped) => tracing::info!("Stopped {} running execution(s)", stopped),
+                Err(e) => tracing::error!("Failed to stop running executions: {}", e),
+            },
+            RunningAttemptPolicy::Detach => tracing::info!("Leaving running executions detached"),
+        }
+
+        self.shutdown().finish()
+    }
+
+    /// Repair drift between agent task statuses and forge_agents membership, call at startup
+    async fn reconcile_agent_tasks(&self) -> Result<(), DeploymentError> {
+        let report = ForgeAgent::reconcile(&self.db().pool, true).await?;
+        if !report.is_consistent() {
+            tracing::warn!(
+                "Repaired agent task drift: {} orphaned forge_agents rows, {} tasks with drifted status, {} unregistered agent tasks",
+                report.orphaned_rows,
+                report.status_drift.len(),
+                report.unregistered.len()
+            );
+        }
+        Ok(())
+    }
+
     /// Cleanup executions marked as running in the db, call at startup
     async fn cleanup_orphan_executions(&self) -> Result<(), DeploymentError> {
         let running_processes = ExecutionProcess::find_running(&self.db().pool).await?;
@@ -291,6 +325,7 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                         cleanup_script: None,
                         copy_files: None,
                         commit_prompt: None,
+                        clone_url: None,
                     };
                     // Ensure existing repo has a main branch if it's empty
                     if let Err(e) = self.git().ensure_main_branch_exists(&repo.path) {
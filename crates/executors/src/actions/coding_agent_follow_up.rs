@@ -9,6 +9,7 @@ use crate::{
     approvals::ExecutorApprovalService,
     executors::{BaseCodingAgent, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
     profile::{ExecutorConfigs, ExecutorProfileId},
+    safe_mode::enforce_safe_mode,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -38,6 +39,7 @@ impl Executable for CodingAgentFollowUpRequest {
         &self,
         current_dir: &Path,
         approvals: Arc<dyn ExecutorApprovalService>,
+        safe_mode: bool,
     ) -> Result<SpawnedChild, ExecutorError> {
         let executor_profile_id = self.get_executor_profile_id();
         let mut agent = ExecutorConfigs::get_cached()
@@ -45,6 +47,9 @@ impl Executable for CodingAgentFollowUpRequest {
             .ok_or(ExecutorError::UnknownExecutorType(
                 executor_profile_id.to_string(),
             ))?;
+        if safe_mode {
+            agent = enforce_safe_mode(agent, &executor_profile_id)?;
+        }
 
         agent.use_approvals(approvals.clone());
 
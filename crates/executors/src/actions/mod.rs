@@ -67,10 +67,13 @@ impl ExecutorAction {
 #[async_trait]
 #[enum_dispatch(ExecutorActionType)]
 pub trait Executable {
+    /// Spawn the action in `current_dir`. With `safe_mode`, coding agents are spawned without
+    /// any setting that bypasses their permission checks.
     async fn spawn(
         &self,
         current_dir: &Path,
         approvals: Arc<dyn ExecutorApprovalService>,
+        safe_mode: bool,
     ) -> Result<SpawnedChild, ExecutorError>;
 }
 
@@ -80,7 +83,8 @@ impl Executable for ExecutorAction {
         &self,
         current_dir: &Path,
         approvals: Arc<dyn ExecutorApprovalService>,
+        safe_mode: bool,
     ) -> Result<SpawnedChild, ExecutorError> {
-        self.typ.spawn(current_dir, approvals).await
+        self.typ.spawn(current_dir, approvals, safe_mode).await
     }
 }
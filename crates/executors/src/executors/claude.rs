@@ -62,6 +62,12 @@ pub struct ClaudeCode {
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dangerously_skip_permissions: Option<bool>,
+    #[schemars(
+        title = "MCP Servers",
+        description = "MCP server definitions (keyed by server name) loaded for this run via --mcp-config"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<HashMap<String, serde_json::Value>>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
 
@@ -113,6 +119,17 @@ impl ClaudeCode {
         apply_overrides(builder, &self.cmd)
     }
 
+    /// `--mcp-config` arguments for the MCP servers declared on this profile, if any
+    fn mcp_config_args(&self) -> Vec<String> {
+        match self.mcp_servers.as_ref().filter(|servers| !servers.is_empty()) {
+            Some(servers) => vec![
+                "--mcp-config".to_string(),
+                serde_json::json!({ "mcpServers": servers }).to_string(),
+            ],
+            None => Vec::new(),
+        }
+    }
+
     pub fn permission_mode(&self) -> PermissionMode {
         if self.plan.unwrap_or(false) {
             PermissionMode::Plan
@@ -205,7 +222,9 @@ impl ClaudeCode {
         prompt: &str,
         command_parts: CommandParts,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (program_path, args) = command_parts.into_resolved().await?;
+        let (program_path, mut args) = command_parts.into_resolved().await?;
+        // Appended after command-line splitting so the inline JSON reaches Claude verbatim
+        args.extend(self.mcp_config_args());
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
         let mut command = Command::new(program_path);
@@ -1972,6 +1991,7 @@ mod tests {
             model: None,
             append_prompt: AppendPrompt::default(),
             dangerously_skip_permissions: None,
+            mcp_servers: None,
             cmd: crate::command::CmdOverrides {
                 base_command_override: None,
                 additional_params: None,
@@ -126,6 +126,12 @@ pub struct Codex {
     pub include_plan_tool: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub include_apply_patch_tool: Option<bool>,
+    #[schemars(
+        title = "MCP Servers",
+        description = "MCP server definitions (keyed by server name) passed as a config override"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<HashMap<String, Value>>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
 
@@ -238,6 +244,13 @@ impl Codex {
             );
         }
 
+        if let Some(servers) = self.mcp_servers.as_ref().filter(|s| !s.is_empty()) {
+            overrides.insert(
+                "mcp_servers".to_string(),
+                Value::Object(servers.clone().into_iter().collect()),
+            );
+        }
+
         if overrides.is_empty() {
             None
         } else {
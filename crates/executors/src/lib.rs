@@ -5,4 +5,5 @@ pub mod executors;
 pub mod logs;
 pub mod mcp_config;
 pub mod profile;
+pub mod safe_mode;
 pub mod stdout_dup;
@@ -81,6 +81,58 @@ pub enum NormalizedEntryType {
     },
 }
 
+/// Who a conversation entry comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRole {
+    User,
+    Assistant,
+    Tool,
+    System,
+}
+
+/// Severity of a log message, from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl NormalizedEntryType {
+    pub fn role(&self) -> LogRole {
+        match self {
+            NormalizedEntryType::UserMessage | NormalizedEntryType::UserFeedback { .. } => {
+                LogRole::User
+            }
+            NormalizedEntryType::AssistantMessage | NormalizedEntryType::Thinking => {
+                LogRole::Assistant
+            }
+            NormalizedEntryType::ToolUse { .. } => LogRole::Tool,
+            NormalizedEntryType::SystemMessage
+            | NormalizedEntryType::ErrorMessage { .. }
+            | NormalizedEntryType::Loading
+            | NormalizedEntryType::NextAction { .. } => LogRole::System,
+        }
+    }
+
+    pub fn level(&self) -> LogLevel {
+        match self {
+            NormalizedEntryType::ErrorMessage { .. } => LogLevel::Error,
+            NormalizedEntryType::ToolUse {
+                status: ToolStatus::Failed | ToolStatus::TimedOut,
+                ..
+            }
+            | NormalizedEntryType::UserFeedback { .. }
+            | NormalizedEntryType::NextAction { failed: true, .. } => LogLevel::Warn,
+            NormalizedEntryType::Thinking | NormalizedEntryType::Loading => LogLevel::Debug,
+            _ => LogLevel::Info,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct NormalizedEntry {
     pub timestamp: Option<String>,
@@ -0,0 +1,228 @@
+//! Safe executor mode
+//!
+//! Strips everything that lets a coding agent act without asking for permission from the
+//! configuration it is about to be spawned with. The filter runs on the fully resolved agent,
+//! so it covers the shipped defaults, user overrides, .genie profiles and raw
+//! `additional_params` alike.
+
+use crate::{
+    executors::{CodingAgent, ExecutorError},
+    profile::ExecutorProfileId,
+};
+
+/// Profile fields that let an executor act without asking for permission
+pub const DANGEROUS_EXECUTOR_FLAGS: [&str; 5] = [
+    "dangerously_skip_permissions",
+    "dangerously_allow_all",
+    "yolo",
+    "force",
+    "allow_all_tools",
+];
+
+/// Codex sandbox mode that disables sandboxing entirely
+const UNSANDBOXED_CODEX_MODE: &str = "danger-full-access";
+
+/// Command-line switches with the same effect as [`DANGEROUS_EXECUTOR_FLAGS`]
+const DANGEROUS_CLI_FLAGS: [&str; 6] = [
+    "--dangerously-skip-permissions",
+    "--dangerously-allow-all",
+    "--dangerously-bypass-approvals-and-sandbox",
+    "--yolo",
+    "--force",
+    "--allow-all-tools",
+];
+
+/// Options whose value can switch permission checks off, with the value that does so
+const DANGEROUS_CLI_OPTIONS: [(&str, &str); 3] = [
+    ("--sandbox", UNSANDBOXED_CODEX_MODE),
+    ("-s", UNSANDBOXED_CODEX_MODE),
+    ("--approval-mode", "yolo"),
+];
+
+/// `agent` without any setting that bypasses permission checks, and the names of the settings
+/// that were dropped
+pub fn suppress_dangerous_flags(
+    agent: CodingAgent,
+) -> Result<(CodingAgent, Vec<String>), serde_json::Error> {
+    let mut json = serde_json::to_value(&agent)?;
+    let Some(fields) = json
+        .as_object_mut()
+        .and_then(|variant| variant.values_mut().next())
+        .and_then(|config| config.as_object_mut())
+    else {
+        return Ok((agent, Vec::new()));
+    };
+
+    let mut suppressed: Vec<String> = DANGEROUS_EXECUTOR_FLAGS
+        .into_iter()
+        .filter(|flag| {
+            fields
+                .remove(*flag)
+                .is_some_and(|value| value == serde_json::Value::Bool(true))
+        })
+        .map(str::to_string)
+        .collect();
+    if fields.get("sandbox").and_then(|v| v.as_str()) == Some(UNSANDBOXED_CODEX_MODE) {
+        fields.remove("sandbox");
+        suppressed.push("sandbox".to_string());
+    }
+    if let Some(params) = fields
+        .get_mut("additional_params")
+        .and_then(|params| params.as_array_mut())
+    {
+        let (kept, dropped) = filter_params(params);
+        *params = kept;
+        suppressed.extend(dropped);
+    }
+
+    if suppressed.is_empty() {
+        return Ok((agent, suppressed));
+    }
+    Ok((serde_json::from_value(json)?, suppressed))
+}
+
+/// [`suppress_dangerous_flags`] for an agent about to be spawned, logging what was dropped
+pub fn enforce_safe_mode(
+    agent: CodingAgent,
+    executor_profile_id: &ExecutorProfileId,
+) -> Result<CodingAgent, ExecutorError> {
+    let (agent, suppressed) = suppress_dangerous_flags(agent)?;
+    if !suppressed.is_empty() {
+        tracing::warn!(
+            "Safe executor mode: suppressed {} for {}",
+            suppressed.join(", "),
+            executor_profile_id
+        );
+    }
+    Ok(agent)
+}
+
+/// Splits `params` into the ones to keep and the dangerous switches found among them
+fn filter_params(params: &[serde_json::Value]) -> (Vec<serde_json::Value>, Vec<String>) {
+    let mut kept = Vec::with_capacity(params.len());
+    let mut dropped = Vec::new();
+    let mut iter = params.iter().peekable();
+    while let Some(param) = iter.next() {
+        let Some(arg) = param.as_str() else {
+            kept.push(param.clone());
+            continue;
+        };
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (arg, None),
+        };
+        if DANGEROUS_CLI_FLAGS.contains(&name) {
+            dropped.push(arg.to_string());
+            continue;
+        }
+        if let Some((_, unsafe_value)) = DANGEROUS_CLI_OPTIONS
+            .iter()
+            .find(|(option, _)| *option == name)
+        {
+            let value = match inline_value {
+                Some(value) => Some(value.to_string()),
+                None => iter.peek().and_then(|v| v.as_str()).map(str::to_string),
+            };
+            if value.as_deref() == Some(*unsafe_value) {
+                if inline_value.is_none() {
+                    iter.next();
+                }
+                dropped.push(format!("{name} {unsafe_value}"));
+                continue;
+            }
+        }
+        kept.push(param.clone());
+    }
+    (kept, dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::ExecutorConfigs;
+
+    fn dangerous_settings(agent: &CodingAgent) -> Vec<String> {
+        let json = serde_json::to_value(agent).unwrap();
+        let fields = json.as_object().unwrap().values().next().unwrap();
+        let mut found: Vec<String> = DANGEROUS_EXECUTOR_FLAGS
+            .into_iter()
+            .filter(|flag| fields.get(*flag) == Some(&serde_json::Value::Bool(true)))
+            .map(str::to_string)
+            .collect();
+        if fields.get("sandbox").and_then(|v| v.as_str()) == Some(UNSANDBOXED_CODEX_MODE) {
+            found.push("sandbox".to_string());
+        }
+        if let Some(params) = fields.get("additional_params").and_then(|p| p.as_array()) {
+            let (_, dropped) = filter_params(params);
+            found.extend(dropped);
+        }
+        found
+    }
+
+    #[test]
+    fn default_profiles_are_made_safe() {
+        let defaults = ExecutorConfigs::from_defaults();
+        let mut suppressed_any = false;
+        for executor in defaults.executors.values() {
+            for agent in executor.configurations.values() {
+                let (safe, suppressed) = suppress_dangerous_flags(agent.clone()).unwrap();
+                suppressed_any |= !suppressed.is_empty();
+                assert!(
+                    dangerous_settings(&safe).is_empty(),
+                    "{:?} still bypasses permissions",
+                    serde_json::to_value(&safe).unwrap()
+                );
+            }
+        }
+        // The shipped defaults do turn permission checks off
+        assert!(suppressed_any);
+    }
+
+    #[test]
+    fn additional_params_are_filtered() {
+        let agent: CodingAgent = serde_json::from_value(serde_json::json!({
+            "CODEX": {
+                "sandbox": "danger-full-access",
+                "additional_params": [
+                    "--dangerously-bypass-approvals-and-sandbox",
+                    "--sandbox",
+                    "danger-full-access",
+                    "-s=danger-full-access",
+                    "--sandbox",
+                    "read-only",
+                    "--model",
+                    "o3"
+                ]
+            }
+        }))
+        .unwrap();
+
+        let (safe, suppressed) = suppress_dangerous_flags(agent).unwrap();
+        assert_eq!(
+            suppressed,
+            [
+                "sandbox",
+                "--dangerously-bypass-approvals-and-sandbox",
+                "--sandbox danger-full-access",
+                "-s danger-full-access"
+            ]
+        );
+        let json = serde_json::to_value(&safe).unwrap();
+        assert_eq!(json["CODEX"]["sandbox"], serde_json::Value::Null);
+        assert_eq!(
+            json["CODEX"]["additional_params"],
+            serde_json::json!(["--sandbox", "read-only", "--model", "o3"])
+        );
+    }
+
+    #[test]
+    fn safe_profiles_are_left_alone() {
+        let agent: CodingAgent = serde_json::from_value(serde_json::json!({
+            "CLAUDE_CODE": { "plan": true, "additional_params": ["--verbose"] }
+        }))
+        .unwrap();
+        let (safe, suppressed) = suppress_dangerous_flags(agent.clone()).unwrap();
+        assert!(suppressed.is_empty());
+        assert_eq!(safe, agent);
+    }
+}
@@ -44,9 +44,13 @@ use forge_core_services::services::{
     config::Config,
     container::{ContainerError, ContainerRef, ContainerService},
     diff_stream::{self, DiffStreamHandle},
+    forge_config::ForgeConfigService,
     git::{Commit, DiffTarget, GitService},
     image::ImageService,
+    log_retention::{LogRetentionPolicy, compact_completed_process_logs},
     notification::NotificationService,
+    orphan_worktrees::{OrphanScanOptions, scan_orphaned_worktrees},
+    task_notifications::TaskNotifier,
     worktree_manager::WorktreeManager,
 };
 use forge_core_utils::{
@@ -72,9 +76,12 @@ pub struct LocalContainerService {
     image_service: ImageService,
     analytics: Option<AnalyticsContext>,
     approvals: Approvals,
+    forge_config: ForgeConfigService,
+    notifier: TaskNotifier,
 }
 
 impl LocalContainerService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         db: DBService,
         msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
@@ -83,6 +90,8 @@ impl LocalContainerService {
         image_service: ImageService,
         analytics: Option<AnalyticsContext>,
         approvals: Approvals,
+        forge_config: ForgeConfigService,
+        notifier: TaskNotifier,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
 
@@ -95,6 +104,25 @@ impl LocalContainerService {
             image_service,
             analytics,
             approvals,
+            forge_config,
+            notifier,
+        }
+    }
+
+    /// Whether coding agents for `project_id` run in safe executor mode. Read as each process
+    /// starts, so a settings change applies to the next one; safe mode is assumed when the
+    /// settings can't be read.
+    async fn safe_executor_mode(&self, project_id: Uuid) -> bool {
+        match self.forge_config.safe_executor_mode(project_id).await {
+            Ok(enabled) => enabled,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to read safe executor mode for project {}, assuming it is on: {}",
+                    project_id,
+                    e
+                );
+                true
+            }
         }
     }
 
@@ -129,12 +157,20 @@ impl LocalContainerService {
     }
 
     /// Finalize task execution by updating status to InReview and sending notifications
-    async fn finalize_task(db: &DBService, config: &Arc<RwLock<Config>>, ctx: &ExecutionContext) {
+    async fn finalize_task(
+        db: &DBService,
+        config: &Arc<RwLock<Config>>,
+        notifier: &TaskNotifier,
+        ctx: &ExecutionContext,
+    ) {
         if let Err(e) = Task::update_status(&db.pool, ctx.task.id, TaskStatus::InReview).await {
             tracing::error!("Failed to update task status to InReview: {e}");
         }
         let notify_cfg = config.read().await.notifications.clone();
         NotificationService::notify_execution_halted(notify_cfg, ctx).await;
+        notifier
+            .notify_execution_halted(ctx, TaskStatus::InReview)
+            .await;
     }
 
     /// Defensively check for externally deleted worktrees and mark them as deleted in the database
@@ -166,7 +202,8 @@ impl LocalContainerService {
         Ok(())
     }
 
-    /// Find and delete orphaned worktrees that don't correspond to any task attempts
+    /// Report orphaned worktrees that don't correspond to any task attempt or execution run,
+    /// removing them only when `FORGE_REMOVE_ORPHAN_WORKTREES` is set
     async fn cleanup_orphaned_worktrees(&self) {
         // Check if orphan cleanup is disabled via environment variable
         if std::env::var("DISABLE_WORKTREE_ORPHAN_CLEANUP").is_ok() {
@@ -176,65 +213,39 @@ impl LocalContainerService {
             return;
         }
         let worktree_base_dir = WorktreeManager::get_worktree_base_dir();
-        if !worktree_base_dir.exists() {
-            tracing::debug!(
-                "Worktree base directory {} does not exist, skipping orphan cleanup",
-                worktree_base_dir.display()
-            );
-            return;
-        }
-        let entries = match std::fs::read_dir(&worktree_base_dir) {
-            Ok(entries) => entries,
-            Err(e) => {
-                tracing::error!(
-                    "Failed to read worktree base directory {}: {}",
-                    worktree_base_dir.display(),
-                    e
-                );
-                return;
-            }
-        };
-        for entry in entries {
-            let entry = match entry {
-                Ok(entry) => entry,
+        let options = OrphanScanOptions::from_env();
+        let report =
+            match scan_orphaned_worktrees(&self.db().pool, &worktree_base_dir, options).await {
+                Ok(report) => report,
                 Err(e) => {
-                    tracing::warn!("Failed to read directory entry: {}", e);
-                    continue;
-                }
-            };
-            let path = entry.path();
-            // Only process directories
-            if !path.is_dir() {
-                continue;
-            }
-
-            let worktree_path_str = path.to_string_lossy().to_string();
-            // Check if worktree is referenced by either TaskAttempt OR ExecutionRun
-            let task_exists =
-                TaskAttempt::container_ref_exists(&self.db().pool, &worktree_path_str)
-                    .await
-                    .unwrap_or(true);
-            let run_exists =
-                ExecutionRun::container_ref_exists(&self.db().pool, &worktree_path_str)
-                    .await
-                    .unwrap_or(true);
-
-            if !task_exists && !run_exists {
-                // This is an orphaned worktree - delete it
-                tracing::info!("Found orphaned worktree: {}", worktree_path_str);
-                if let Err(e) = WorktreeManager::cleanup_worktree(&path, None).await {
                     tracing::error!(
-                        "Failed to remove orphaned worktree {}: {}",
-                        worktree_path_str,
+                        "Failed to scan worktree base directory {} for orphans: {}",
+                        worktree_base_dir.display(),
                         e
                     );
-                } else {
-                    tracing::info!(
-                        "Successfully removed orphaned worktree: {}",
-                        worktree_path_str
-                    );
+                    return;
                 }
-            }
+            };
+
+        if report.orphans.is_empty() {
+            tracing::debug!(
+                "No orphaned worktrees found in {} ({} scanned)",
+                report.base_dir,
+                report.scanned
+            );
+        } else if report.dry_run {
+            tracing::warn!(
+                "Found {} orphaned worktrees in {}; set FORGE_REMOVE_ORPHAN_WORKTREES=1 to remove them",
+                report.orphans.len(),
+                report.base_dir
+            );
+        } else {
+            tracing::info!(
+                "Removed {} of {} orphaned worktrees in {}",
+                report.orphans.iter().filter(|o| o.removed).count(),
+                report.orphans.len(),
+                report.base_dir
+            );
         }
     }
 
@@ -276,7 +287,7 @@ impl LocalContainerService {
         Ok(())
     }
 
-    pub async fn spawn_worktree_cleanup(&self) {
+    pub async fn spawn_worktree_cleanup(&self) -> JoinHandle<()> {
         let db = self.db.clone();
         let mut cleanup_interval = tokio::time::interval(tokio::time::Duration::from_secs(1800)); // 30 minutes
         self.cleanup_orphaned_worktrees().await;
@@ -294,8 +305,13 @@ impl LocalContainerService {
                     .unwrap_or_else(|e| {
                         tracing::error!("Failed to clean up expired worktree attempts: {}", e)
                     });
+                if let Err(e) =
+                    compact_completed_process_logs(&db.pool, &LogRetentionPolicy::from_env()).await
+                {
+                    tracing::error!("Failed to compact execution process logs: {}", e);
+                }
             }
-        });
+        })
     }
 
     /// Spawn a background task that polls the child process for completion and
@@ -451,12 +467,12 @@ impl LocalContainerService {
                         );
 
                         // Manually finalize task since we're bypassing normal execution flow
-                        Self::finalize_task(&db, &config, &ctx).await;
+                        Self::finalize_task(&db, &config, &container.notifier, &ctx).await;
                     }
                 }
 
                 if Self::should_finalize(&ctx) {
-                    Self::finalize_task(&db, &config, &ctx).await;
+                    Self::finalize_task(&db, &config, &container.notifier, &ctx).await;
                     // After finalization, check if a queued follow-up exists and start it
                     if let Err(e) = container.try_consume_queued_followup(&ctx).await {
                         tracing::error!(
@@ -709,6 +725,11 @@ impl ContainerService for LocalContainerService {
     fn task_attempt_to_current_dir(&self, task_attempt: &TaskAttempt) -> PathBuf {
         PathBuf::from(task_attempt.container_ref.clone().unwrap_or_default())
     }
+
+    async fn is_process_attached(&self, process_id: &Uuid) -> bool {
+        self.get_child_from_store(process_id).await.is_some()
+    }
+
     /// Create a container
     async fn create(&self, task_attempt: &TaskAttempt) -> Result<ContainerRef, ContainerError> {
         let task = task_attempt
@@ -736,13 +757,22 @@ impl ContainerService for LocalContainerService {
         .unwrap_or(true); // Default to true if query fails
 
         let container_ref_path = if use_worktree {
+            // Forked attempts already have a branch, created at the commit they fork from.
+            // Any other attempt gets a new branch, failing if its name is taken.
+            let reuse_branch = sqlx::query_scalar::<_, bool>(
+                "SELECT reuse_branch FROM forge_task_attempt_config WHERE task_attempt_id = ?",
+            )
+            .bind(task_attempt.id.to_string())
+            .fetch_optional(&self.db.pool)
+            .await?
+            .unwrap_or(false);
             // Create worktree for isolated work
             WorktreeManager::create_worktree(
                 &project.git_repo_path,
                 &task_attempt.branch,
                 &worktree_path,
                 &task_attempt.target_branch,
-                true, // create new branch
+                !reuse_branch,
             )
             .await?;
 
@@ -896,9 +926,14 @@ impl ContainerService for LocalContainerService {
                 _ => Arc::new(NoopExecutorApprovalService {}),
             };
 
+        let safe_mode = match task_attempt.parent_task(&self.db.pool).await? {
+            Some(task) => self.safe_executor_mode(task.project_id).await,
+            None => true,
+        };
+
         // Create the child and stream, add to execution tracker
         let mut spawned = executor_action
-            .spawn(&current_dir, approvals_service)
+            .spawn(&current_dir, approvals_service, safe_mode)
             .await?;
 
         self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
@@ -1225,9 +1260,11 @@ impl ContainerService for LocalContainerService {
                 _ => Arc::new(NoopExecutorApprovalService {}),
             };
 
+        let safe_mode = self.safe_executor_mode(execution_run.project_id).await;
+
         // Create the child and stream, add to execution tracker
         let mut spawned = executor_action
-            .spawn(&current_dir, approvals_service)
+            .spawn(&current_dir, approvals_service, safe_mode)
             .await?;
 
         self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
@@ -1576,10 +1613,35 @@ fn truncate_to_char_boundary(content: &str, max_len: usize) -> &str {
 
 #[cfg(test)]
 mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use forge_core_db::{
+        DBService,
+        models::{
+            project::{CreateProject, Project},
+            task::{CreateTask, Task, TaskStatus},
+            task_attempt::{CreateTaskAttempt, TaskAttempt},
+        },
+    };
+    use forge_core_executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+    use forge_core_services::services::{
+        approvals::Approvals,
+        config::Config,
+        container::ContainerService,
+        forge_config::{ForgeConfigService, SecretCipher},
+        git::GitService,
+        image::ImageService,
+        omni::{OmniConfig, OmniService},
+        task_notifications::TaskNotifier,
+        worktree_manager::WorktreeManager,
+    };
+    use tokio::sync::RwLock;
+    use uuid::Uuid;
+
+    use super::{LocalContainerService, truncate_to_char_boundary};
 
     #[test]
     fn test_truncate_to_char_boundary() {
-        use super::truncate_to_char_boundary;
 
         let input = "a".repeat(10);
         assert_eq!(truncate_to_char_boundary(&input, 7), "a".repeat(7));
@@ -1591,4 +1653,180 @@ mod tests {
         assert_eq!(truncate_to_char_boundary(input, 5), "🔥");
         assert_eq!(truncate_to_char_boundary(input, 3), "");
     }
+
+    struct Fixture {
+        _temp_dir: tempfile::TempDir,
+        db: DBService,
+        repo_path: std::path::PathBuf,
+        container: LocalContainerService,
+        task: Task,
+    }
+
+    /// A project on a fresh repository with one task, and a container service for it
+    async fn fixture() -> Fixture {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let pool = &db.pool;
+
+        let repo_path = temp_dir.path().join("repo");
+        let git = GitService::new();
+        git.initialize_repo_with_main_branch(&repo_path).unwrap();
+        git.configure_user(&repo_path, "Test User", "test@example.com")
+            .unwrap();
+        let project_id = Uuid::new_v4();
+        let create_project = CreateProject {
+            name: "Greeter".to_string(),
+            git_repo_path: repo_path.display().to_string(),
+            use_existing_repo: true,
+            setup_script: None,
+            dev_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            commit_prompt: None,
+            clone_url: None,
+        };
+        Project::create(pool, &create_project, project_id)
+            .await
+            .unwrap();
+        let create_task =
+            CreateTask::from_title_description(project_id, "Add greeting".to_string(), None);
+        let task = Task::create(pool, &create_task, Uuid::new_v4())
+            .await
+            .unwrap();
+
+        let msg_stores = Arc::new(RwLock::new(HashMap::new()));
+        let forge_config = ForgeConfigService::new(
+            pool.clone(),
+            SecretCipher::from_key_material(b"container-test-key"),
+        );
+        let container = LocalContainerService::new(
+            db.clone(),
+            msg_stores.clone(),
+            Arc::new(RwLock::new(Config::default())),
+            GitService::new(),
+            ImageService::new(pool.clone()).unwrap(),
+            None,
+            Approvals::new(msg_stores),
+            forge_config.clone(),
+            TaskNotifier::new(
+                pool.clone(),
+                forge_config,
+                Arc::new(RwLock::new(OmniService::new(OmniConfig::default()))),
+            ),
+        );
+        Fixture {
+            _temp_dir: temp_dir,
+            db,
+            repo_path,
+            container,
+            task,
+        }
+    }
+
+    async fn create_attempt(fixture: &Fixture, branch: &str) -> TaskAttempt {
+        let create_attempt = CreateTaskAttempt {
+            executor: BaseCodingAgent::ClaudeCode,
+            variant: None,
+            base_branch: "main".to_string(),
+            branch: branch.to_string(),
+        };
+        TaskAttempt::create(
+            &fixture.db.pool,
+            &create_attempt,
+            Uuid::new_v4(),
+            fixture.task.id,
+        )
+        .await
+        .unwrap()
+    }
+
+    fn worktree_path(attempt: &TaskAttempt, task: &Task) -> std::path::PathBuf {
+        WorktreeManager::get_worktree_base_dir().join(
+            LocalContainerService::dir_name_from_task_attempt(&attempt.id, &task.title),
+        )
+    }
+
+    #[tokio::test]
+    async fn failed_start_discards_the_worktree_and_restores_the_task() {
+        let fixture = fixture().await;
+        let pool = &fixture.db.pool;
+        let attempt = create_attempt(&fixture, "forge/add-greeting").await;
+
+        // The worktree is created, then the agent fails to spawn for lack of a profile
+        let result = fixture
+            .container
+            .start_attempt_or_discard(
+                &attempt,
+                ExecutorProfileId {
+                    executor: BaseCodingAgent::ClaudeCode,
+                    variant: Some("NO_SUCH_VARIANT".to_string()),
+                },
+            )
+            .await;
+        assert!(result.is_err());
+
+        assert!(!worktree_path(&attempt, &fixture.task).exists());
+        assert!(
+            !GitService::new()
+                .check_branch_exists(&fixture.repo_path, "forge/add-greeting")
+                .unwrap()
+        );
+        let task = Task::find_by_id(pool, fixture.task.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(task.status, TaskStatus::Todo);
+
+        assert!(
+            TaskAttempt::find_by_id(pool, attempt.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            TaskAttempt::fetch_all(pool, Some(task.id))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn existing_branches_are_only_reused_by_forks() {
+        let fixture = fixture().await;
+        let git = GitService::new();
+        let head = git.fork_point(&fixture.repo_path, "main", None).unwrap();
+
+        // A new attempt whose branch name is already taken doesn't adopt that branch
+        git.create_branch_at(&fixture.repo_path, "forge/taken", &head)
+            .unwrap();
+        let attempt = create_attempt(&fixture, "forge/taken").await;
+        assert!(fixture.container.create(&attempt).await.is_err());
+
+        // A fork's branch is created up front and checked out as is
+        git.create_branch_at(&fixture.repo_path, "forge/fork", &head)
+            .unwrap();
+        let fork = create_attempt(&fixture, "forge/fork").await;
+        sqlx::query(
+            "INSERT INTO forge_task_attempt_config (task_attempt_id, use_worktree, reuse_branch) \
+             VALUES (?, 1, 1)",
+        )
+        .bind(fork.id.to_string())
+        .execute(&fixture.db.pool)
+        .await
+        .unwrap();
+        let worktree = fixture.container.create(&fork).await.unwrap();
+        assert_eq!(
+            git.get_current_branch(std::path::Path::new(&worktree))
+                .unwrap(),
+            "forge/fork"
+        );
+        let fork = TaskAttempt::find_by_id(&fixture.db.pool, fork.id)
+            .await
+            .unwrap()
+            .unwrap();
+        fixture.container.delete_inner(&fork).await.unwrap();
+    }
 }
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 use async_trait::async_trait;
 use forge_core_db::DBService;
@@ -14,11 +14,13 @@ use forge_core_services::services::{
     events::EventService,
     file_search_cache::FileSearchCache,
     filesystem::FilesystemService,
-    forge_config::ForgeConfigService,
+    forge_config::{ForgeConfigService, SecretCipher},
     git::GitService,
     image::ImageService,
     omni::{OmniConfig, OmniService},
     profile_loader::ProfileCacheManager,
+    shutdown::ShutdownCoordinator,
+    task_notifications::TaskNotifier,
 };
 use forge_core_utils::{assets::config_path, msg_store::MsgStore};
 use tokio::sync::RwLock;
@@ -47,6 +49,7 @@ pub struct LocalDeployment {
     forge_config: ForgeConfigService,
     omni: Arc<RwLock<OmniService>>,
     profile_cache: ProfileCacheManager,
+    shutdown: ShutdownCoordinator,
 }
 
 #[async_trait]
@@ -76,86 +79,7 @@ impl Deployment for LocalDeployment {
         // Always save config (may have been migrated or version updated)
         save_config_to_file(&raw_config, &config_path()).await?;
 
-        let config = Arc::new(RwLock::new(raw_config));
-        let user_id = generate_user_id();
-        let analytics = AnalyticsConfig::new().map(AnalyticsService::new);
-        let git = GitService::new();
-        let msg_stores = Arc::new(RwLock::new(HashMap::new()));
-        let auth = AuthService::new();
-        let filesystem = FilesystemService::new();
-
-        // Create shared components for EventService
-        let events_msg_store = Arc::new(MsgStore::new());
-        let events_entry_count = Arc::new(RwLock::new(0));
-
-        // Create DB with event hooks
-        let db = {
-            let hook = EventService::create_hook(
-                events_msg_store.clone(),
-                events_entry_count.clone(),
-                DBService::new().await?, // Temporary DB service for the hook
-            );
-            DBService::new_with_after_connect(hook).await?
-        };
-
-        let image = ImageService::new(db.clone().pool)?;
-        {
-            let image_service = image.clone();
-            tokio::spawn(async move {
-                tracing::info!("Starting orphaned image cleanup...");
-                if let Err(e) = image_service.delete_orphaned_images().await {
-                    tracing::error!("Failed to clean up orphaned images: {}", e);
-                }
-            });
-        }
-
-        let approvals = Approvals::new(msg_stores.clone());
-
-        // We need to make analytics accessible to the ContainerService
-        // TODO: Handle this more gracefully
-        let analytics_ctx = analytics.as_ref().map(|s| AnalyticsContext {
-            user_id: user_id.clone(),
-            analytics_service: s.clone(),
-        });
-        let container = LocalContainerService::new(
-            db.clone(),
-            msg_stores.clone(),
-            config.clone(),
-            git.clone(),
-            image.clone(),
-            analytics_ctx,
-            approvals.clone(),
-        );
-        container.spawn_worktree_cleanup().await;
-
-        let events = EventService::new(db.clone(), events_msg_store, events_entry_count);
-        let drafts = DraftsService::new(db.clone(), image.clone());
-        let file_search_cache = Arc::new(FileSearchCache::new());
-
-        // Initialize forge-specific services
-        let forge_config = ForgeConfigService::new(db.pool.clone());
-        let omni = Arc::new(RwLock::new(OmniService::new(OmniConfig::default())));
-        let profile_cache = ProfileCacheManager::new();
-
-        Ok(Self {
-            config,
-            user_id,
-            db,
-            analytics,
-            msg_stores,
-            container,
-            git,
-            auth,
-            image,
-            filesystem,
-            events,
-            file_search_cache,
-            approvals,
-            drafts,
-            forge_config,
-            omni,
-            profile_cache,
-        })
+        Self::build(raw_config, None).await
     }
 
     fn user_id(&self) -> &str {
@@ -228,4 +152,115 @@ impl Deployment for LocalDeployment {
     fn profile_cache(&self) -> &ProfileCacheManager {
         &self.profile_cache
     }
+
+    fn shutdown(&self) -> &ShutdownCoordinator {
+        &self.shutdown
+    }
+}
+
+impl LocalDeployment {
+    /// A deployment on the database at `db_path` with `config`, which is never written to
+    /// the user's config file. Lets tests drive route handlers against a throwaway database.
+    pub async fn new_at(db_path: &Path, config: Config) -> Result<Self, DeploymentError> {
+        Self::build(config, Some(db_path)).await
+    }
+
+    async fn build(raw_config: Config, db_path: Option<&Path>) -> Result<Self, DeploymentError> {
+        let config = Arc::new(RwLock::new(raw_config));
+        let user_id = generate_user_id();
+        let analytics = AnalyticsConfig::new().map(AnalyticsService::new);
+        let git = GitService::new();
+        let msg_stores = Arc::new(RwLock::new(HashMap::new()));
+        let auth = AuthService::new();
+        let filesystem = FilesystemService::new();
+
+        // Create shared components for EventService
+        let events_msg_store = Arc::new(MsgStore::new());
+        let events_entry_count = Arc::new(RwLock::new(0));
+
+        // Create DB with event hooks
+        let db = match db_path {
+            Some(db_path) => {
+                let hook = EventService::create_hook(
+                    events_msg_store.clone(),
+                    events_entry_count.clone(),
+                    DBService::new_at(db_path).await?, // Temporary DB service for the hook
+                );
+                DBService::new_at_with_after_connect(db_path, hook).await?
+            }
+            None => {
+                let hook = EventService::create_hook(
+                    events_msg_store.clone(),
+                    events_entry_count.clone(),
+                    DBService::new().await?, // Temporary DB service for the hook
+                );
+                DBService::new_with_after_connect(hook).await?
+            }
+        };
+
+        let image = ImageService::new(db.clone().pool)?;
+        {
+            let image_service = image.clone();
+            tokio::spawn(async move {
+                tracing::info!("Starting orphaned image cleanup...");
+                if let Err(e) = image_service.delete_orphaned_images().await {
+                    tracing::error!("Failed to clean up orphaned images: {}", e);
+                }
+            });
+        }
+
+        let approvals = Approvals::new(msg_stores.clone());
+
+        // We need to make analytics accessible to the ContainerService
+        // TODO: Handle this more gracefully
+        let analytics_ctx = analytics.as_ref().map(|s| AnalyticsContext {
+            user_id: user_id.clone(),
+            analytics_service: s.clone(),
+        });
+        // Initialize forge-specific services
+        let forge_config = ForgeConfigService::new(db.pool.clone(), SecretCipher::load()?);
+        let omni = Arc::new(RwLock::new(OmniService::new(OmniConfig::default())));
+        let notifier = TaskNotifier::new(db.pool.clone(), forge_config.clone(), omni.clone());
+        let container = LocalContainerService::new(
+            db.clone(),
+            msg_stores.clone(),
+            config.clone(),
+            git.clone(),
+            image.clone(),
+            analytics_ctx,
+            approvals.clone(),
+            forge_config.clone(),
+            notifier,
+        );
+        let shutdown = ShutdownCoordinator::new();
+        let worktree_cleanup = container.spawn_worktree_cleanup().await;
+        shutdown.register_task("worktree_cleanup", &worktree_cleanup);
+
+        let events = EventService::new(db.clone(), events_msg_store, events_entry_count);
+        let drafts = DraftsService::new(db.clone(), image.clone());
+        let file_search_cache = Arc::new(FileSearchCache::new());
+
+        let profile_cache = ProfileCacheManager::new();
+
+        Ok(Self {
+            config,
+            user_id,
+            db,
+            analytics,
+            msg_stores,
+            container,
+            git,
+            auth,
+            image,
+            filesystem,
+            events,
+            file_search_cache,
+            approvals,
+            drafts,
+            forge_config,
+            omni,
+            profile_cache,
+            shutdown,
+        })
+    }
 }
@@ -14,6 +14,7 @@ fn generate_types_content() -> String {
         forge_core_services::services::filesystem::DirectoryListResponse::decl(),
         forge_core_db::models::project::Project::decl(),
         forge_core_db::models::project::CreateProject::decl(),
+        forge_core_server::routes::projects::ProjectCloneError::decl(),
         forge_core_db::models::project::UpdateProject::decl(),
         forge_core_db::models::project::SearchResult::decl(),
         forge_core_db::models::project::SearchMatchType::decl(),
@@ -32,6 +33,7 @@ fn generate_types_content() -> String {
         forge_core_db::models::task::TaskStatus::decl(),
         forge_core_db::models::task::Task::decl(),
         forge_core_db::models::task::TaskWithAttemptStatus::decl(),
+        forge_core_db::models::task::ProjectTaskCounts::decl(),
         forge_core_db::models::task::TaskRelationships::decl(),
         forge_core_db::models::task::CreateTask::decl(),
         forge_core_db::models::task::UpdateTask::decl(),
@@ -49,6 +51,8 @@ fn generate_types_content() -> String {
         forge_core_services::services::drafts::UpdateRetryFollowUpDraftRequest::decl(),
         forge_core_server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         forge_core_server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
+        forge_core_server::routes::task_attempts::MoveTaskAttemptRequest::decl(),
+        forge_core_server::routes::task_attempts::MoveTaskAttemptResponse::decl(),
         forge_core_server::routes::tasks::CreateAndStartTaskRequest::decl(),
         forge_core_server::routes::task_attempts::CreateGitHubPrRequest::decl(),
         forge_core_server::routes::images::ImageResponse::decl(),
@@ -91,15 +95,22 @@ fn generate_types_content() -> String {
         forge_core_executors::actions::coding_agent_initial::CodingAgentInitialRequest::decl(),
         forge_core_executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
         forge_core_server::routes::task_attempts::CreateTaskAttemptBody::decl(),
+        forge_core_server::routes::task_attempts::ForkTaskAttemptBody::decl(),
         forge_core_server::routes::task_attempts::RunAgentSetupRequest::decl(),
         forge_core_server::routes::task_attempts::RunAgentSetupResponse::decl(),
         forge_core_server::routes::task_attempts::RebaseTaskAttemptRequest::decl(),
         forge_core_server::routes::task_attempts::GitOperationError::decl(),
         forge_core_server::routes::task_attempts::ReplaceProcessRequest::decl(),
         forge_core_server::routes::task_attempts::CommitInfo::decl(),
-        forge_core_server::routes::task_attempts::BranchStatus::decl(),
+        forge_core_services::services::git::BranchStatus::decl(),
         forge_core_services::services::git::ConflictOp::decl(),
+        forge_core_services::services::git::PushResult::decl(),
+        forge_core_services::services::git::BranchCommit::decl(),
+        forge_core_server::routes::task_attempts::AttemptCommits::decl(),
         forge_core_db::models::task_attempt::TaskAttempt::decl(),
+        forge_core_server::routes::task_attempts::TaskAttemptDetail::decl(),
+        forge_core_server::routes::task_attempts::TaskAttemptListItem::decl(),
+        forge_core_db::models::attempt_profile_snapshot::AttemptProfileSnapshot::decl(),
         forge_core_db::models::execution_process::ExecutionProcess::decl(),
         forge_core_db::models::execution_process::ExecutionProcessStatus::decl(),
         forge_core_db::models::execution_process::ExecutionProcessRunReason::decl(),
@@ -1,7 +1,9 @@
+use std::time::Duration;
+
 use axum::{
     Json,
     extract::multipart::MultipartError,
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{IntoResponse, Response},
 };
 use forge_core_db::models::{
@@ -12,8 +14,8 @@ use forge_core_deployment::DeploymentError;
 use forge_core_executors::executors::ExecutorError;
 use forge_core_services::services::{
     auth::AuthError, config::ConfigError, container::ContainerError, drafts::DraftsServiceError,
-    git::GitServiceError, github_service::GitHubServiceError, image::ImageError,
-    worktree_manager::WorktreeError,
+    git::GitServiceError, git_cli::GitCliError, github_service::GitHubServiceError,
+    image::ImageError, orphan_worktrees::OrphanWorktreeError, worktree_manager::WorktreeError,
 };
 use forge_core_utils::response::ApiResponse;
 use git2::Error as Git2Error;
@@ -33,7 +35,7 @@ pub enum ApiError {
     #[error(transparent)]
     GitService(#[from] GitServiceError),
     #[error(transparent)]
-    GitHubService(#[from] GitHubServiceError),
+    GitHubService(GitHubServiceError),
     #[error(transparent)]
     Auth(#[from] AuthError),
     #[error(transparent)]
@@ -58,6 +60,54 @@ pub enum ApiError {
     Io(#[from] std::io::Error),
     #[error("Conflict: {0}")]
     Conflict(String),
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+    #[error("Too many requests: {message}")]
+    TooManyRequests {
+        message: String,
+        /// How long the client should wait, when known
+        retry_after: Option<Duration>,
+    },
+}
+
+/// Seconds clients are asked to wait before retrying a `TooManyRequests` response that
+/// doesn't know when the limit resets
+pub const RETRY_AFTER_SECS: u64 = 30;
+
+impl ApiError {
+    /// A GitHub rate limit, passing on how long GitHub asked to wait when it said
+    pub fn github_rate_limited(retry_after: Option<Duration>) -> Self {
+        ApiError::TooManyRequests {
+            message: GitHubServiceError::RateLimited.to_string(),
+            retry_after,
+        }
+    }
+
+    /// Value of the `Retry-After` header sent with a `TooManyRequests` response
+    fn retry_after_secs(retry_after: Option<Duration>) -> u64 {
+        retry_after.map_or(RETRY_AFTER_SECS, |wait| {
+            wait.as_secs() + u64::from(wait.subsec_nanos() > 0)
+        })
+    }
+}
+
+impl From<GitHubServiceError> for ApiError {
+    fn from(err: GitHubServiceError) -> Self {
+        match err {
+            // octocrab doesn't expose the reset time, so clients get the default wait
+            GitHubServiceError::RateLimited => ApiError::github_rate_limited(None),
+            err => ApiError::GitHubService(err),
+        }
+    }
+}
+
+impl From<OrphanWorktreeError> for ApiError {
+    fn from(err: OrphanWorktreeError) -> Self {
+        match err {
+            OrphanWorktreeError::Database(e) => ApiError::Database(e),
+            OrphanWorktreeError::Io(e) => ApiError::Io(e),
+        }
+    }
 }
 
 impl From<Git2Error> for ApiError {
@@ -92,11 +142,23 @@ impl IntoResponse for ApiError {
                 forge_core_services::services::git::GitServiceError::RebaseInProgress => {
                     (StatusCode::CONFLICT, "GitServiceError")
                 }
+                forge_core_services::services::git::GitServiceError::ProtectedBranch(..) => {
+                    (StatusCode::CONFLICT, "GitServiceError")
+                }
+                forge_core_services::services::git::GitServiceError::SigningKeyMissing => {
+                    (StatusCode::BAD_REQUEST, "GitServiceError")
+                }
+                forge_core_services::services::git::GitServiceError::GitCLI(
+                    GitCliError::PushRejected(_),
+                ) => (StatusCode::CONFLICT, "GitServiceError"),
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitServiceError"),
             },
             ApiError::GitHubService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError"),
             ApiError::Auth(_) => (StatusCode::INTERNAL_SERVER_ERROR, "AuthError"),
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
+            ApiError::Container(ContainerError::BranchNotFound(_)) => {
+                (StatusCode::CONFLICT, "ContainerError")
+            }
             ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
             ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
             ApiError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError"),
@@ -124,11 +186,13 @@ impl IntoResponse for ApiError {
             ApiError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "IoError"),
             ApiError::Multipart(_) => (StatusCode::BAD_REQUEST, "MultipartError"),
             ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
+            ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest"),
+            ApiError::TooManyRequests { .. } => (StatusCode::TOO_MANY_REQUESTS, "TooManyRequests"),
         };
 
         let error_message = match &self {
             ApiError::Image(img_err) => match img_err {
-                ImageError::InvalidFormat => "This file type is not supported. Please upload an image file (PNG, JPG, GIF, WebP, or BMP).".to_string(),
+                ImageError::InvalidFormat => "This file type is not supported. Please upload a PNG, JPEG, or WebP image.".to_string(),
                 ImageError::TooLarge(size, max) => format!(
                     "This image is too large ({:.1} MB). Maximum file size is {:.1} MB.",
                     *size as f64 / 1_048_576.0,
@@ -144,10 +208,22 @@ impl IntoResponse for ApiError {
                 forge_core_services::services::git::GitServiceError::RebaseInProgress => {
                     "A rebase is already in progress. Resolve conflicts or abort the rebase, then retry.".to_string()
                 }
+                forge_core_services::services::git::GitServiceError::ProtectedBranch(..) => {
+                    format!("{}", git_err)
+                }
+                forge_core_services::services::git::GitServiceError::SigningKeyMissing => {
+                    "Commit signing is enabled for this project but no signing key is configured. Set a signing key in the project settings or user.signingkey in git.".to_string()
+                }
+                forge_core_services::services::git::GitServiceError::GitCLI(
+                    GitCliError::PushRejected(_),
+                ) => "The remote branch has commits that are not in this branch. Rebase onto it, or push with force to overwrite them.".to_string(),
                 _ => format!("{}: {}", error_type, self),
             },
+            ApiError::Container(ContainerError::BranchNotFound(_)) => self.to_string(),
             ApiError::Multipart(_) => "Failed to upload file. Please ensure the file is valid and try again.".to_string(),
             ApiError::Conflict(msg) => msg.clone(),
+            ApiError::BadRequest(msg) => msg.clone(),
+            ApiError::TooManyRequests { message, .. } => message.clone(),
             ApiError::Drafts(drafts_err) => match drafts_err {
                 DraftsServiceError::Conflict(msg) => msg.clone(),
                 DraftsServiceError::Database(_) => format!("{}: {}", error_type, drafts_err),
@@ -160,6 +236,60 @@ impl IntoResponse for ApiError {
             _ => format!("{}: {}", error_type, self),
         };
         let response = ApiResponse::<()>::error(&error_message);
+        if let ApiError::TooManyRequests { retry_after, .. } = self {
+            return (
+                status_code,
+                [(
+                    header::RETRY_AFTER,
+                    Self::retry_after_secs(retry_after).to_string(),
+                )],
+                Json(response),
+            )
+                .into_response();
+        }
         (status_code, Json(response)).into_response()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn rate_limited_handler() -> Result<Json<ApiResponse<()>>, ApiError> {
+        Err(ApiError::TooManyRequests {
+            message: "Too many concurrent runs, try again later".to_string(),
+            retry_after: None,
+        })
+    }
+
+    fn retry_after(response: &Response) -> &str {
+        response
+            .headers()
+            .get(header::RETRY_AFTER)
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn too_many_requests_sets_status_and_retry_after() {
+        let response = rate_limited_handler().await.into_response();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(retry_after(&response), RETRY_AFTER_SECS.to_string());
+    }
+
+    #[test]
+    fn upstream_rate_limits_pass_their_reset_time_through() {
+        let response =
+            ApiError::github_rate_limited(Some(Duration::from_millis(41_500))).into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(retry_after(&response), "42");
+
+        let response = ApiError::from(GitHubServiceError::RateLimited).into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(retry_after(&response), RETRY_AFTER_SECS.to_string());
+        let response = ApiError::from(GitHubServiceError::TokenInvalid).into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
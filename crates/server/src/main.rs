@@ -1,3 +1,5 @@
+use std::{future::IntoFuture, time::Duration};
+
 use anyhow::{self, Error as AnyhowError};
 use forge_core_deployment::{Deployment, DeploymentError};
 use forge_core_server::{DeploymentImpl, routes};
@@ -12,6 +14,9 @@ use strip_ansi_escapes::strip;
 use thiserror::Error;
 use tracing_subscriber::{EnvFilter, prelude::*};
 
+/// How long open connections get to close once a shutdown signal arrives
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Error)]
 pub enum AutomagikForgeError {
     #[error(transparent)]
@@ -50,8 +55,10 @@ async fn main() -> Result<(), AutomagikForgeError> {
     let deployment = DeploymentImpl::new().await?;
     deployment.update_sentry_scope().await?;
     deployment.cleanup_orphan_executions().await?;
+    deployment.reconcile_agent_tasks().await?;
     deployment.backfill_before_head_commits().await?;
-    deployment.spawn_pr_monitor_service().await;
+    let pr_monitor = deployment.spawn_pr_monitor_service().await;
+    deployment.shutdown().register_task("pr_monitor", &pr_monitor);
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
@@ -68,7 +75,7 @@ async fn main() -> Result<(), AutomagikForgeError> {
         }
     });
 
-    let app_router = routes::router(deployment);
+    let app_router = routes::router(deployment.clone());
 
     let port = std::env::var("BACKEND_PORT")
         .or_else(|_| std::env::var("PORT"))
@@ -108,6 +115,55 @@ async fn main() -> Result<(), AutomagikForgeError> {
         });
     }
 
-    axum::serve(listener, app_router).await?;
+    // WebSocket and SSE handlers end their streams on this token, so the drain can finish
+    let server = axum::serve(listener, app_router)
+        .with_graceful_shutdown(deployment.shutdown().cancellation_token().cancelled_owned())
+        .into_future();
+    tokio::pin!(server);
+    tokio::select! {
+        result = &mut server => result?,
+        _ = shutdown_signal() => {
+            deployment.shutdown().begin();
+            match tokio::time::timeout(SHUTDOWN_DRAIN_TIMEOUT, &mut server).await {
+                Ok(result) => result?,
+                Err(_) => tracing::warn!(
+                    "Connections still open after {}s, shutting down anyway",
+                    SHUTDOWN_DRAIN_TIMEOUT.as_secs()
+                ),
+            }
+        }
+    }
+    deployment.graceful_shutdown().await;
     Ok(())
 }
+
+/// Resolves on Ctrl+C, or SIGTERM on unix
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    tracing::info!("Shutdown signal received, no longer accepting requests");
+}
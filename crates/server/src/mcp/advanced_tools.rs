@@ -16,7 +16,11 @@
 //! Total Advanced Tools: 49 additional tools
 
 use chrono::{DateTime, Utc};
-use forge_core_db::models::task_attempt::TaskAttempt;
+use forge_core_db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    task_attempt::TaskAttempt,
+};
+use forge_core_services::services::git::ConflictOp;
 use rmcp::schemars;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -35,24 +39,71 @@ pub struct TaskAttemptSummary {
     pub branch: String,
     pub target_branch: String,
     pub executor: String,
+    pub variant: Option<String>,
+    pub status: AttemptStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 impl TaskAttemptSummary {
-    pub fn from_task_attempt(attempt: TaskAttempt) -> Self {
+    pub fn from_task_attempt(attempt: TaskAttempt, status: AttemptStatus) -> Self {
+        let (executor, variant) = attempt.executor_and_variant();
+        let (executor, variant) = (executor.to_string(), variant.map(str::to_string));
         Self {
             id: attempt.id.to_string(),
             task_id: attempt.task_id.to_string(),
             branch: attempt.branch,
             target_branch: attempt.target_branch,
-            executor: attempt.executor,
+            executor,
+            variant,
+            status,
             created_at: attempt.created_at,
             updated_at: attempt.updated_at,
         }
     }
 }
 
+/// Lifecycle state of an attempt as reported to MCP clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AttemptStatus {
+    /// No setup script or coding agent has run yet
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    /// The latest process was killed by the user
+    Stopped,
+    /// The attempt's branch was merged directly or through a merged PR
+    Merged,
+}
+
+impl AttemptStatus {
+    /// Derives the status from the attempt's execution processes and merge state. Dev servers
+    /// are ignored since they say nothing about the agent's work.
+    pub fn from_processes(processes: &[ExecutionProcess], merged: bool) -> Self {
+        let latest = processes
+            .iter()
+            .filter(|p| !matches!(p.run_reason, ExecutionProcessRunReason::DevServer))
+            .max_by_key(|p| p.started_at);
+
+        Self::from_latest(latest.map(|p| &p.status), merged)
+    }
+
+    /// Same as [`AttemptStatus::from_processes`], given the status of the newest process that
+    /// isn't a dev server
+    pub fn from_latest(latest: Option<&ExecutionProcessStatus>, merged: bool) -> Self {
+        match latest {
+            Some(ExecutionProcessStatus::Running) => Self::Running,
+            _ if merged => Self::Merged,
+            None => Self::Pending,
+            Some(ExecutionProcessStatus::Completed) => Self::Completed,
+            Some(ExecutionProcessStatus::Failed) => Self::Failed,
+            Some(ExecutionProcessStatus::Killed) => Self::Stopped,
+        }
+    }
+}
+
 // Response types
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct StopTaskAttemptResponse {
@@ -60,9 +111,33 @@ pub struct StopTaskAttemptResponse {
     pub attempt_id: String,
 }
 
-#[derive(Debug, Serialize, schemars::JsonSchema)]
+#[derive(Debug, Serialize)]
 pub struct MergeTaskAttemptResponse {
+    /// False when nothing was merged
     pub success: bool,
+    pub attempt_id: String,
+    pub target_branch: String,
+    pub has_conflicts: bool,
+    pub conflict_op: Option<ConflictOp>,
+    /// Files that conflict with the target branch
+    pub conflicted_files: Vec<String>,
+    pub message: Option<String>,
+    pub next_steps: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResolveAttemptResponse {
+    pub attempt_id: String,
+    pub action: ResolveAction,
+    /// The rebase, merge, cherry-pick or revert that was aborted or continued
+    pub operation: ConflictOp,
+    /// False when continuing stopped on conflicts
+    pub resolved: bool,
+    /// The branch head after the operation
+    pub head_oid: Option<String>,
+    pub conflicted_files: Vec<String>,
+    pub message: Option<String>,
+    pub next_steps: Vec<String>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -79,12 +154,16 @@ pub struct GetTaskAttemptResponse {
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct ListTaskAttemptsResponse {
     pub attempts: Vec<TaskAttemptSummary>,
+    pub count: usize,
     pub applied_filters: TaskAttemptFilters,
+    #[schemars(description = "Pass as `cursor` to fetch the next page; absent on the last page")]
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct TaskAttemptFilters {
-    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub limit: u32,
 }
 
 // ============================================================================
@@ -95,8 +174,15 @@ pub struct TaskAttemptFilters {
 pub struct CreateProjectRequest {
     #[schemars(description = "Project name")]
     pub name: String,
-    #[schemars(description = "Path to git repository")]
+    #[schemars(
+        description = "Path to git repository. May be empty with clone_url to clone into the configured workspace directory"
+    )]
+    #[serde(default)]
     pub git_repo_path: String,
+    #[schemars(
+        description = "Optional remote repository URL to clone. The GitHub token is used for private repositories"
+    )]
+    pub clone_url: Option<String>,
     #[schemars(description = "Optional setup script")]
     pub setup_script: Option<String>,
     #[schemars(description = "Optional cleanup script")]
@@ -135,8 +221,14 @@ pub struct DeleteProjectRequest {
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct ListTaskAttemptsRequest {
-    #[schemars(description = "Optional project ID filter")]
-    pub project_id: Option<Uuid>,
+    #[schemars(
+        description = "Only list attempts of this task; a full id or an unambiguous prefix (at least 6 characters)"
+    )]
+    pub task_id: Option<String>,
+    #[schemars(description = "Maximum number of attempts to return (default: 20)")]
+    pub limit: Option<u32>,
+    #[schemars(description = "`next_cursor` from a previous call to continue after that page")]
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -165,8 +257,34 @@ pub struct StopTaskAttemptRequest {
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct MergeTaskAttemptRequest {
-    #[schemars(description = "Task attempt ID to merge")]
-    pub attempt_id: Uuid,
+    #[schemars(
+        description = "The ID of the task attempt, or an unambiguous prefix of it (at least 6 characters)"
+    )]
+    pub attempt_id: String,
+    #[schemars(
+        description = "Attempt the merge even when the attempt's branch status reports unresolved conflicts (default: false)"
+    )]
+    pub force: Option<bool>,
+}
+
+/// What to do with an in-progress rebase or merge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ResolveAction {
+    /// Abandon the operation and restore the branch to where it was before it started
+    Abort,
+    /// Finish the operation once the conflicted files are resolved and staged
+    Continue,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ResolveAttemptRequest {
+    #[schemars(
+        description = "The ID of the task attempt, or an unambiguous prefix of it (at least 6 characters)"
+    )]
+    pub attempt_id: String,
+    #[schemars(description = "'abort' to abandon the rebase/merge, 'continue' to finish it")]
+    pub action: ResolveAction,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -197,6 +315,10 @@ pub struct AttachPRRequest {
     pub attempt_id: Uuid,
     #[schemars(description = "PR number to attach")]
     pub pr_number: i64,
+    #[schemars(
+        description = "Replace a different PR that is already attached (default: false). Re-attaching the same PR is always a no-op"
+    )]
+    pub force: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
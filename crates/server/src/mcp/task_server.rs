@@ -1,23 +1,44 @@
 use std::{
     cmp::Ordering,
+    collections::HashMap,
     future::Future,
     path::PathBuf,
     str::FromStr,
     sync::{Arc, RwLock},
+    time::{Duration, Instant},
 };
 
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64_STANDARD};
 use forge_core_db::models::{
-    project::Project,
-    task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus, UpdateTask},
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    merge::{Merge, MergeStatus},
+    project::{CreateProject, Project},
+    task::{
+        CreateTask, ProjectTaskCounts, Task, TaskRelationships, TaskStatus, TaskWithAttemptStatus,
+        UpdateTask,
+    },
     task_attempt::TaskAttempt,
 };
 use forge_core_executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use forge_core_services::services::{
+    attempt_diff::DiffResult,
+    forge_config::ForgeProjectSettings,
+    git::{BranchCommit, BranchStatus, ConflictOp, GitBranch, GitService, PushResult},
+    image::UploadImageFormat,
+};
+use forge_core_utils::{
+    metrics::{self, MetricKind},
+    text::title_similarity,
+};
 use rmcp::{
     ErrorData, RoleServer, ServerHandler,
-    handler::server::{tool::ToolRouter, wrapper::Parameters},
+    handler::server::{
+        tool::{ToolCallContext, ToolRouter},
+        wrapper::Parameters,
+    },
     model::{
         CallToolResult, Content, Implementation, InitializeRequestParam, ProtocolVersion,
-        ServerCapabilities, ServerInfo,
+        ServerCapabilities, ServerInfo, Tool,
     },
     schemars,
     service::RequestContext,
@@ -28,11 +49,93 @@ use serde_json;
 use tracing::info;
 use uuid::Uuid;
 
-use crate::routes::{
-    execution_runs::CreateExecutionRunRequest as ApiCreateExecutionRunRequest,
-    task_attempts::CreateTaskAttemptBody,
+use crate::{
+    mcp::advanced_tools::{
+        AttemptStatus, GetBranchStatusRequest, GetTaskAttemptRequest, GetTaskAttemptResponse,
+        ListTaskAttemptsRequest, ListTaskAttemptsResponse, MergeTaskAttemptRequest,
+        MergeTaskAttemptResponse, ResolveAction, ResolveAttemptRequest, ResolveAttemptResponse,
+        TaskAttemptFilters, TaskAttemptSummary,
+    },
+    routes::{
+        execution_processes::{LogTail, LogTailQuery},
+        execution_runs::CreateExecutionRunRequest as ApiCreateExecutionRunRequest,
+        images::ImageResponse,
+        pagination::PageCursor,
+        projects::ProjectCloneError,
+        task_attempts::{
+            AttemptCommits, CreateTaskAttemptBody, ForkTaskAttemptBody, GitOperationError,
+            TaskAttemptListItem, TaskAttemptQuery,
+        },
+        tasks::{
+            CreateAndStartTaskRequest, CreatedTask, IDEMPOTENCY_KEY_HEADER, TaskMatchField,
+            TaskQuery, TaskSearchQuery, TaskSearchResults,
+        },
+    },
 };
 
+/// Error code returned when a referenced attempt does not exist (or is outside the given project)
+const ATTEMPT_NOT_FOUND: &str = "ATTEMPT_NOT_FOUND";
+/// Error code returned when an attempt's task is already finished
+const ATTEMPT_TERMINAL: &str = "ATTEMPT_TERMINAL";
+/// Error code returned when an id prefix matches several records; `suggestions` lists them
+const AMBIGUOUS_ID: &str = "AMBIGUOUS_ID";
+/// Error code returned when a `cursor` was not produced by a previous list call
+const INVALID_CURSOR: &str = "INVALID_CURSOR";
+/// Error code returned when no project has the given id or name
+const PROJECT_NOT_FOUND: &str = "PROJECT_NOT_FOUND";
+
+/// Error code returned when an image is neither an upload ID nor a base64 data URI of one of
+/// the [`UploadImageFormat::ALL`] types
+const UNSUPPORTED_IMAGE: &str = "UNSUPPORTED_IMAGE";
+
+/// Error code returned when the remote rejects the credentials used to clone it
+const CLONE_AUTH_FAILED: &str = "CLONE_AUTH_FAILED";
+
+/// Error code returned when the clone target directory already exists
+const CLONE_PATH_EXISTS: &str = "CLONE_PATH_EXISTS";
+
+/// Error code returned when cloning fails for any other reason
+const CLONE_FAILED: &str = "CLONE_FAILED";
+
+/// Error code returned when a config key is not a Forge setting; `suggestions` lists close
+/// ones from [`SETTABLE_CONFIG_KEYS`]
+const UNKNOWN_CONFIG_KEY: &str = "UNKNOWN_CONFIG_KEY";
+
+/// Error code returned when a config key is a Forge setting that `set_forge_config` may not
+/// change, such as safe executor mode or credentials
+const PROTECTED_CONFIG_KEY: &str = "PROTECTED_CONFIG_KEY";
+
+/// Error code returned when a config value does not fit the setting it is assigned to
+const INVALID_CONFIG_VALUE: &str = "INVALID_CONFIG_VALUE";
+
+/// Error code returned when `merge_attempt` is refused because the attempt has unresolved conflicts
+const MERGE_CONFLICTS_PENDING: &str = "MERGE_CONFLICTS_PENDING";
+
+/// Most tasks `tasks_create` accepts in one call
+const BATCH_CREATE_LIMIT: usize = 50;
+
+/// Matches returned by the `search` tool
+const SEARCH_TOOL_LIMIT: usize = 10;
+
+/// Least title similarity for a config key to be suggested in place of an unknown one
+const CONFIG_KEY_SUGGESTION_SIMILARITY: f64 = 0.5;
+
+/// Forge settings `set_forge_config` may change. Security settings (safe executor mode, commit
+/// signing) and anything holding credentials or a delivery target (Omni, webhooks) are left to
+/// the Forge UI.
+const SETTABLE_CONFIG_KEYS: [&str; 4] = [
+    "omni_enabled",
+    "review_on_pr",
+    "releases_repo",
+    "conversational_prefixes",
+];
+
+/// Shown in place of secrets in the config `set_forge_config` returns
+const REDACTED_SECRET: &str = "[redacted]";
+
+/// Upper bound on each request made by the `doctor` checks
+const DOCTOR_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
 const SUPPORTED_PROTOCOL_VERSIONS: [ProtocolVersion; 2] =
     [ProtocolVersion::V_2025_03_26, ProtocolVersion::V_2024_11_05];
 
@@ -44,11 +147,116 @@ pub struct CreateTaskRequest {
     pub title: String,
     #[schemars(description = "Optional description of the task")]
     pub description: Option<String>,
+    #[schemars(
+        description = "Create the task even if tasks with very similar titles already exist (default: false)"
+    )]
+    pub force: Option<bool>,
+    #[schemars(
+        description = "Images to attach: IDs of already uploaded images, or PNG/JPEG/WebP data URIs (`data:image/png;base64,...`) to upload. Attempts started for the task see them."
+    )]
+    pub images: Option<Vec<String>>,
+    #[schemars(
+        description = "Client-chosen token for this creation. Retrying with the same token returns the task it created instead of a new one"
+    )]
+    pub client_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct DuplicateTaskSummary {
+    #[schemars(description = "The ID of the existing task")]
+    pub id: String,
+    #[schemars(description = "The title of the existing task")]
+    pub title: String,
+    #[schemars(description = "Title similarity between 0 and 1")]
+    pub similarity: f64,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct CreateTaskResponse {
     pub task_id: String,
+    #[schemars(description = "Existing tasks whose titles closely match the new task's title")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub possible_duplicates: Vec<DuplicateTaskSummary>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct BatchTaskItem {
+    #[schemars(description = "The title of the task")]
+    pub title: String,
+    #[schemars(description = "Optional description of the task")]
+    pub description: Option<String>,
+    #[schemars(
+        description = "Coding agent to start the task with ('CLAUDE_CODE', 'CODEX', 'GEMINI', 'CURSOR_AGENT', 'OPENCODE'). Required when `start` is true"
+    )]
+    pub executor: Option<String>,
+    #[schemars(
+        description = "Start an attempt right after creating the task (default: true when `executor` is set)"
+    )]
+    pub start: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreateTasksRequest {
+    #[schemars(description = "The project to create the tasks in, by ID or name")]
+    pub project: String,
+    #[schemars(description = "The tasks to create, at most 50")]
+    pub tasks: Vec<BatchTaskItem>,
+    #[schemars(
+        description = "Base branch for started tasks (defaults to the project's default branch)"
+    )]
+    pub base_branch: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchTaskResult {
+    /// Position of the item in the request
+    pub index: usize,
+    pub title: String,
+    pub success: bool,
+    pub task_id: Option<String>,
+    pub started: bool,
+    /// The error this item would have returned from a single create call
+    pub error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateTasksResponse {
+    pub project_id: String,
+    pub created: usize,
+    pub failed: usize,
+    pub results: Vec<BatchTaskResult>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DuplicateTaskRequest {
+    #[schemars(
+        description = "The ID of the task to copy, or an unambiguous prefix of it (at least 6 characters)"
+    )]
+    pub task_id: String,
+    #[schemars(
+        description = "Project to create the copy in, by ID or name (defaults to the source task's project)"
+    )]
+    pub project: Option<String>,
+    #[schemars(
+        description = "Coding agent to start the copy with ('CLAUDE_CODE', 'CODEX', 'GEMINI', 'CURSOR_AGENT', 'OPENCODE'). The copy is only created when unset"
+    )]
+    pub executor: Option<String>,
+    #[schemars(description = "Optional executor variant, if needed")]
+    pub variant: Option<String>,
+    #[schemars(
+        description = "Base branch for the started copy (defaults to the project's default branch)"
+    )]
+    pub base_branch: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct DuplicateTaskResponse {
+    pub source_task_id: String,
+    pub task_id: String,
+    pub project_id: String,
+    #[schemars(description = "Number of the source task's images attached to the copy")]
+    pub images: usize,
+    pub started: bool,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -69,10 +277,14 @@ pub struct ProjectSummary {
     pub created_at: String,
     #[schemars(description = "When the project was last updated")]
     pub updated_at: String,
+    #[schemars(description = "Tasks that are in progress or have a running attempt")]
+    pub active_tasks: i64,
+    #[schemars(description = "Kanban tasks in the project, excluding agent chats")]
+    pub total_tasks: i64,
 }
 
 impl ProjectSummary {
-    fn from_project(project: Project) -> Self {
+    fn from_project(project: Project, counts: Option<&ProjectTaskCounts>) -> Self {
         Self {
             id: project.id.to_string(),
             name: project.name,
@@ -82,6 +294,8 @@ impl ProjectSummary {
             dev_script: project.dev_script,
             created_at: project.created_at.to_rfc3339(),
             updated_at: project.updated_at.to_rfc3339(),
+            active_tasks: counts.map_or(0, |c| c.active),
+            total_tasks: counts.map_or(0, |c| c.total),
         }
     }
 }
@@ -92,6 +306,74 @@ pub struct ListProjectsResponse {
     pub count: usize,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CloneProjectRequest {
+    #[schemars(
+        description = "URL of the repository to clone. The configured GitHub token is used for private repositories"
+    )]
+    pub git_url: String,
+    #[schemars(
+        description = "Directory to clone into. Defaults to a directory named after the repository in the configured workspace directory"
+    )]
+    pub target_dir: Option<String>,
+    #[schemars(description = "Project name. Defaults to the repository name")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListProjectBranchesRequest {
+    #[schemars(description = "The project whose branches to list, by ID or name")]
+    pub project: String,
+    #[schemars(
+        description = "Skip the comparison with the default branch and return only names (faster on large repositories)"
+    )]
+    pub names_only: Option<bool>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ProjectBranchSummary {
+    #[schemars(description = "Branch name; remote branches include the remote, e.g. origin/main")]
+    pub name: String,
+    #[schemars(description = "Whether this branch is checked out in the project repository")]
+    pub is_current: bool,
+    #[schemars(description = "Whether this is a remote-tracking branch")]
+    pub is_remote: bool,
+    #[schemars(description = "When the branch's latest commit was made")]
+    pub last_commit_date: String,
+    #[schemars(description = "Commits on this branch that the default branch doesn't have")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ahead: Option<usize>,
+    #[schemars(description = "Commits on the default branch that this branch doesn't have")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub behind: Option<usize>,
+    #[schemars(
+        description = "Whether every commit on this branch is already on the default branch; squash-merged branches still count as unmerged"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_merged: Option<bool>,
+}
+
+impl ProjectBranchSummary {
+    fn from_branch(branch: GitBranch) -> Self {
+        Self {
+            name: branch.name,
+            is_current: branch.is_current,
+            is_remote: branch.is_remote,
+            last_commit_date: branch.last_commit_date.to_rfc3339(),
+            ahead: branch.ahead,
+            behind: branch.behind,
+            is_merged: branch.is_merged,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ListProjectBranchesResponse {
+    pub project_id: String,
+    pub branches: Vec<ProjectBranchSummary>,
+    pub count: usize,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct ListTasksRequest {
     #[schemars(description = "The ID of the project to list tasks from")]
@@ -102,6 +384,8 @@ pub struct ListTasksRequest {
     pub status: Option<String>,
     #[schemars(description = "Maximum number of tasks to return (default: 50)")]
     pub limit: Option<i32>,
+    #[schemars(description = "`next_cursor` from a previous call to continue after that page")]
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -125,6 +409,19 @@ pub struct TaskSummary {
 }
 
 impl TaskSummary {
+    fn from_task(task: Task) -> Self {
+        Self {
+            id: task.id.to_string(),
+            title: task.title,
+            status: task.status.to_string(),
+            created_at: task.created_at.to_rfc3339(),
+            updated_at: task.updated_at.to_rfc3339(),
+            has_in_progress_attempt: None,
+            has_merged_attempt: None,
+            last_attempt_failed: None,
+        }
+    }
+
     fn from_task_with_status(task: TaskWithAttemptStatus) -> Self {
         Self {
             id: task.id.to_string(),
@@ -159,6 +456,14 @@ pub struct TaskDetails {
     pub has_merged_attempt: Option<bool>,
     #[schemars(description = "Whether the last execution attempt failed")]
     pub last_attempt_failed: Option<bool>,
+    #[schemars(description = "Number of execution attempts started for the task")]
+    pub attempts_count: Option<usize>,
+    #[schemars(description = "The task whose attempt created this task, if it is a subtask")]
+    pub parent_task_id: Option<String>,
+    #[schemars(description = "The attempt that created this task, if it is a subtask")]
+    pub parent_attempt_id: Option<String>,
+    #[schemars(description = "Number of subtasks created by the task's attempts")]
+    pub children_count: Option<usize>,
 }
 
 impl TaskDetails {
@@ -173,6 +478,10 @@ impl TaskDetails {
             has_in_progress_attempt: None,
             has_merged_attempt: None,
             last_attempt_failed: None,
+            attempts_count: None,
+            parent_task_id: None,
+            parent_attempt_id: task.parent_task_attempt.map(|id| id.to_string()),
+            children_count: None,
         }
     }
 }
@@ -183,6 +492,8 @@ pub struct ListTasksResponse {
     pub count: usize,
     pub project_id: String,
     pub applied_filters: ListTasksFilters,
+    #[schemars(description = "Pass as `cursor` to fetch the next page; absent on the last page")]
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -193,14 +504,18 @@ pub struct ListTasksFilters {
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct UpdateTaskRequest {
-    #[schemars(description = "The ID of the task to update")]
-    pub task_id: Uuid,
+    #[schemars(description = "The ID of the task to update, or an unambiguous prefix of it (at least 6 characters)")]
+    pub task_id: String,
     #[schemars(description = "New title for the task")]
     pub title: Option<String>,
     #[schemars(description = "New description for the task")]
     pub description: Option<String>,
     #[schemars(description = "New status: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'")]
     pub status: Option<String>,
+    #[schemars(
+        description = "The task's `updated_at` as last read. If the task has changed since, the update is rejected instead of overwriting those changes"
+    )]
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -210,28 +525,247 @@ pub struct UpdateTaskResponse {
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct DeleteTaskRequest {
-    #[schemars(description = "The ID of the task to delete")]
-    pub task_id: Uuid,
+    #[schemars(description = "The ID of the task to delete, or an unambiguous prefix of it (at least 6 characters)")]
+    pub task_id: String,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct StartTaskAttemptRequest {
-    #[schemars(description = "The ID of the task to start")]
-    pub task_id: Uuid,
+    #[schemars(description = "The ID of the task to start, or an unambiguous prefix of it (at least 6 characters)")]
+    pub task_id: String,
     #[schemars(
         description = "The coding agent executor to run ('CLAUDE_CODE', 'CODEX', 'GEMINI', 'CURSOR_AGENT', 'OPENCODE')"
     )]
     pub executor: String,
     #[schemars(description = "Optional executor variant, if needed")]
     pub variant: Option<String>,
-    #[schemars(description = "The base branch to use for the attempt")]
-    pub base_branch: String,
+    #[schemars(
+        description = "The base branch to use for the attempt (defaults to the project's default branch)"
+    )]
+    pub base_branch: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SetForgeConfigRequest {
+    #[schemars(
+        description = "The setting to change: 'omni_enabled', 'review_on_pr', 'releases_repo' or 'conversational_prefixes'"
+    )]
+    pub key: String,
+    #[schemars(
+        description = "The new value as JSON, e.g. true, \"owner/repo\" or [\"Claro, \"]; null clears optional settings"
+    )]
+    pub value: serde_json::Value,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct StartTaskAttemptResponse {
     pub task_id: String,
     pub attempt_id: String,
+    pub executor: String,
+    pub variant: Option<String>,
+    #[schemars(description = "Number of attempts started for the task, including this one")]
+    pub attempts_count: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ContinueAttemptRequest {
+    #[schemars(description = "The ID of the task attempt to continue, or an unambiguous prefix of it (at least 6 characters)")]
+    pub attempt_id: String,
+    #[schemars(description = "Optional project ID; the attempt must belong to this project")]
+    pub project_id: Option<Uuid>,
+    #[schemars(description = "The follow-up message to send to the coding agent")]
+    pub prompt: String,
+    #[schemars(
+        description = "Optional executor variant to switch to for this follow-up (defaults to the attempt's current variant)"
+    )]
+    pub variant: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ContinueAttemptResponse {
+    pub attempt_id: String,
+    pub execution_process_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetAttemptDiffRequest {
+    #[schemars(description = "The ID of the task attempt, or an unambiguous prefix of it (at least 6 characters)")]
+    pub attempt_id: String,
+    #[schemars(description = "Optional path (file or directory, relative to the repository root) to limit the diff to")]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GetAttemptDiffResponse {
+    pub attempt_id: String,
+    #[serde(flatten)]
+    pub diff: DiffResult,
+    pub next_steps: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ForkAttemptRequest {
+    #[schemars(description = "The ID of the task attempt to fork, or an unambiguous prefix of it (at least 6 characters)")]
+    pub attempt_id: String,
+    #[schemars(description = "Commit on the attempt's branch to start from; defaults to its tip")]
+    pub from_commit: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ForkAttemptResponse {
+    pub task_id: String,
+    pub source_attempt_id: String,
+    pub attempt_id: String,
+    pub branch: String,
+    pub target_branch: String,
+    pub from_commit: Option<String>,
+    pub next_steps: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct AttemptLogsRequest {
+    #[schemars(description = "The ID of the task attempt, or an unambiguous prefix of it (at least 6 characters)")]
+    pub attempt_id: String,
+    #[schemars(
+        description = "Number of trailing log entries to return (default 100, at most 1000)"
+    )]
+    pub tail: Option<u32>,
+    #[schemars(description = "Process to read, as listed by `processes`; defaults to the attempt's newest one")]
+    pub process_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttemptLogsResponse {
+    pub attempt_id: String,
+    /// The process the entries were read from: the attempt's newest one, ignoring dev servers
+    pub process_id: String,
+    pub run_reason: ExecutionProcessRunReason,
+    pub status: ExecutionProcessStatus,
+    #[serde(flatten)]
+    pub logs: LogTail,
+    pub next_steps: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct AttemptProcessesRequest {
+    #[schemars(description = "The ID of the task attempt, or an unambiguous prefix of it (at least 6 characters)")]
+    pub attempt_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttemptProcess {
+    pub process_id: String,
+    pub run_reason: ExecutionProcessRunReason,
+    pub status: ExecutionProcessStatus,
+    pub started_at: String,
+    pub completed_at: Option<String>,
+    pub exit_code: Option<i64>,
+}
+
+impl AttemptProcess {
+    fn from_process(process: &ExecutionProcess) -> Self {
+        Self {
+            process_id: process.id.to_string(),
+            run_reason: process.run_reason.clone(),
+            status: process.status.clone(),
+            started_at: process.started_at.to_rfc3339(),
+            completed_at: process.completed_at.map(|at| at.to_rfc3339()),
+            exit_code: process.exit_code,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttemptProcessesResponse {
+    pub attempt_id: String,
+    /// Oldest first
+    pub processes: Vec<AttemptProcess>,
+    pub next_steps: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct AttemptCommitsRequest {
+    #[schemars(description = "The ID of the task attempt, or an unambiguous prefix of it (at least 6 characters)")]
+    pub attempt_id: String,
+    #[schemars(description = "Maximum number of commits to return (default 50, at most 100)")]
+    pub limit: Option<u32>,
+    #[schemars(description = "Number of commits to skip, newest first")]
+    pub offset: Option<u32>,
+    #[schemars(
+        description = "`next_cursor` from a previous call to continue after that page; takes precedence over `offset`"
+    )]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttemptCommitsResponse {
+    pub attempt_id: String,
+    pub target_branch: String,
+    /// Newest first
+    pub commits: Vec<BranchCommit>,
+    /// Commits unique to the attempt branch across all pages
+    pub total: usize,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct StopTaskRequest {
+    #[schemars(description = "The ID of the task whose running attempts should be stopped, or an unambiguous prefix of it (at least 6 characters)")]
+    pub task_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StoppedAttempt {
+    pub attempt_id: String,
+    pub stopped: bool,
+    /// The error the stop endpoint returned for this attempt
+    pub error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StopTaskResponse {
+    pub task_id: String,
+    pub stopped: usize,
+    pub failed: usize,
+    /// One entry per attempt that was running; attempts that had already finished are skipped
+    pub attempts: Vec<StoppedAttempt>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct RebaseAttemptRequest {
+    #[schemars(description = "The ID of the task attempt, or an unambiguous prefix of it (at least 6 characters)")]
+    pub attempt_id: String,
+    #[schemars(description = "Branch to rebase onto. Defaults to the attempt's target branch; any other branch becomes the new target")]
+    pub onto: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RebaseAttemptResponse {
+    pub attempt_id: String,
+    /// False when the rebase stopped on conflicts
+    pub rebased: bool,
+    pub target_branch: String,
+    pub commits_ahead: Option<usize>,
+    pub commits_behind: Option<usize>,
+    /// Files left conflicted in the attempt's worktree
+    pub conflicted_files: Vec<String>,
+    pub next_steps: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct PushAttemptRequest {
+    #[schemars(description = "The ID of the task attempt, or an unambiguous prefix of it (at least 6 characters)")]
+    pub attempt_id: String,
+    #[schemars(description = "Overwrite the remote branch even if that drops commits from it. Defaults to false")]
+    pub force: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PushAttemptResponse {
+    pub attempt_id: String,
+    pub branch: String,
+    #[serde(flatten)]
+    pub result: PushResult,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -239,10 +773,47 @@ pub struct DeleteTaskResponse {
     pub deleted_task_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SearchTasksRequest {
+    #[schemars(
+        description = "Text to find in task titles and descriptions; close misspellings of title words also match"
+    )]
+    pub query: String,
+    #[schemars(
+        description = "Optional project to search, by ID or name. All projects are searched when omitted"
+    )]
+    pub project: Option<String>,
+    #[schemars(
+        description = "Optional status filter: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'"
+    )]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchTaskMatch {
+    #[serde(flatten)]
+    pub task: TaskSummary,
+    pub project_id: String,
+    pub matched_field: TaskMatchField,
+    /// The matched text, with the match wrapped in `**`
+    pub highlight: String,
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchTasksResponse {
+    pub query: String,
+    pub matches: Vec<SearchTaskMatch>,
+    pub count: usize,
+    /// All matching tasks, including those left out of `matches`
+    pub total_matches: usize,
+    pub next_steps: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct GetTaskRequest {
-    #[schemars(description = "The ID of the task to retrieve")]
-    pub task_id: Uuid,
+    #[schemars(description = "The ID of the task to retrieve, or an unambiguous prefix of it (at least 6 characters)")]
+    pub task_id: String,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -266,7 +837,7 @@ pub struct StartExecutionRunRequest {
     pub executor: String,
     #[schemars(description = "Optional executor variant")]
     pub variant: Option<String>,
-    #[schemars(description = "The base branch to use (defaults to 'main')")]
+    #[schemars(description = "The base branch to use (defaults to the project's default branch)")]
     pub base_branch: Option<String>,
 }
 
@@ -328,23 +899,226 @@ pub struct StopExecutionRunResponse {
     pub execution_run_id: String,
 }
 
-#[derive(Debug, Clone)]
-pub struct TaskServer {
-    client: reqwest::Client,
-    base_url: String,
-    tool_router: ToolRouter<TaskServer>,
-    negotiated_protocol_version: Arc<RwLock<ProtocolVersion>>,
+// ============================================================================
+// Diagnostics MCP Types
+// ============================================================================
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DoctorCheckStatus {
+    Pass,
+    Warn,
+    Fail,
 }
 
-impl TaskServer {
-    pub fn new(base_url: &str) -> Self {
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: DoctorCheckStatus,
+    pub message: String,
+    /// How to fix a warning or failure
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &str, message: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: DoctorCheckStatus::Pass,
+            message,
+            hint: None,
+        }
+    }
+
+    fn warn(name: &str, message: String, hint: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            status: DoctorCheckStatus::Warn,
+            message,
+            hint: Some(hint.to_string()),
+        }
+    }
+
+    fn fail(name: &str, message: String, hint: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            status: DoctorCheckStatus::Fail,
+            message,
+            hint: Some(hint.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct DoctorReport {
+    /// Worst status across all checks
+    pub status: DoctorCheckStatus,
+    pub checks: Vec<DoctorCheck>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct HealthReport {
+    pub reachable: bool,
+    pub base_url: String,
+    /// Version reported by the Forge API; absent when unreachable or on older servers
+    pub api_version: Option<String>,
+    /// MCP protocol version negotiated for this session
+    pub protocol_version: String,
+    /// Protocol versions this server speaks, newest first; older clients are downgraded to
+    /// the newest one they support
+    pub supported_protocol_versions: Vec<String>,
+    /// Round trip of the health request
+    pub latency_ms: u64,
+    pub error: Option<String>,
+    /// How to fix an unreachable API
+    pub suggestions: Vec<String>,
+}
+
+// ============================================================================
+// MCP Server Discovery Types
+// ============================================================================
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListMcpServersRequest {
+    #[schemars(description = "Optional executor to list servers for ('CLAUDE_CODE', 'CODEX', 'GEMINI', ...)")]
+    pub executor: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpServerEntry {
+    pub name: String,
+    /// Command that launches a stdio server
+    pub command: Option<String>,
+    /// Endpoint of an HTTP/SSE server
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpServerInfo {
+    pub executor: String,
+    /// The executor's MCP config file
+    pub config_path: Option<String>,
+    pub servers: Vec<McpServerEntry>,
+}
+
+impl McpServerInfo {
+    /// Parses a `/api/mcp-config` response:
+    /// `{ "mcp_config": { "servers": { "<name>": { "command": .. } | { "url": .. } } },
+    /// "config_path": ".." }`. Server configs are reduced to their command or URL since env and
+    /// headers commonly carry credentials.
+    fn from_config(executor: &str, config: &serde_json::Value) -> Self {
+        let mut servers: Vec<McpServerEntry> = config["mcp_config"]["servers"]
+            .as_object()
+            .map(|servers| {
+                servers
+                    .iter()
+                    .map(|(name, server)| McpServerEntry {
+                        name: name.clone(),
+                        command: server["command"].as_str().map(str::to_string),
+                        url: server["url"]
+                            .as_str()
+                            .or(server["httpUrl"].as_str())
+                            .map(str::to_string),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        servers.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            executor: executor.to_string(),
+            config_path: config["config_path"].as_str().map(str::to_string),
+            servers,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ListMcpServersResponse {
+    /// Executors that support MCP, with the servers configured for each
+    pub executors: Vec<McpServerInfo>,
+    /// Total number of servers across executors
+    pub count: usize,
+    /// Why the result is empty, if it is
+    pub message: Option<String>,
+}
+
+/// How `TaskServer` retries Forge API calls that fail transiently: connection errors and 5xx or
+/// 429 responses. Other 4xx responses are never retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff before the given retry (0-based), with the upper half randomized so
+    /// concurrent sessions don't retry in lockstep
+    fn delay(&self, retry: u32) -> Duration {
+        let factor = 1u32.checked_shl(retry).unwrap_or(u32::MAX);
+        let backoff = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        let half = backoff / 2;
+        half + half.mul_f64(rand::random::<f64>())
+    }
+
+    fn is_transient(status: reqwest::StatusCode) -> bool {
+        status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+    }
+}
+
+/// MCP handler for a single client session. Clones share the negotiated protocol version, so
+/// each connection should be served from its own [`TaskServer::new_session`].
+#[derive(Debug, Clone)]
+pub struct TaskServer {
+    client: reqwest::Client,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    tool_router: ToolRouter<TaskServer>,
+    negotiated_protocol_version: Arc<RwLock<ProtocolVersion>>,
+}
+
+impl TaskServer {
+    pub fn new(base_url: &str) -> Self {
         Self {
             client: reqwest::Client::new(),
             base_url: base_url.to_string(),
+            retry_policy: RetryPolicy::default(),
             tool_router: Self::tool_router(),
             negotiated_protocol_version: Arc::new(RwLock::new(Self::latest_supported_protocol())),
         }
     }
+
+    /// Returns a handler for a new client session. The HTTP client and tool router are shared,
+    /// while protocol negotiation starts over so concurrent sessions cannot overwrite each
+    /// other's negotiated version.
+    pub fn new_session(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            base_url: self.base_url.clone(),
+            retry_policy: self.retry_policy.clone(),
+            tool_router: self.tool_router.clone(),
+            negotiated_protocol_version: Arc::new(RwLock::new(Self::latest_supported_protocol())),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -352,6 +1126,7 @@ struct ApiResponseEnvelope<T> {
     success: bool,
     data: Option<T>,
     message: Option<String>,
+    error_data: Option<serde_json::Value>,
 }
 
 impl TaskServer {
@@ -377,19 +1152,116 @@ impl TaskServer {
         Self::err_value(v)
     }
 
+    /// `AMBIGUOUS_ID` error listing the matching ids so the caller can retry with one of them
+    fn ambiguous(message: Option<&str>, candidates: &[Uuid]) -> CallToolResult {
+        Self::err_value(serde_json::json!({
+            "success": false,
+            "code": AMBIGUOUS_ID,
+            "error": message.unwrap_or("The id prefix matches more than one record"),
+            "suggestions": candidates,
+        }))
+        .unwrap()
+    }
+
+    /// Error with a stable machine-readable `code` alongside the message
+    fn err_code<S: Into<String>>(code: &str, msg: S) -> CallToolResult {
+        Self::err_value(serde_json::json!({"success": false, "code": code, "error": msg.into()}))
+            .unwrap()
+    }
+
     async fn send_json<T: DeserializeOwned>(
         &self,
         rb: reqwest::RequestBuilder,
     ) -> Result<T, CallToolResult> {
-        let resp = rb
-            .send()
+        self.send_json_with(rb, None, false).await
+    }
+
+    /// Like `send_json`, but also retries non-GET requests the caller knows are safe to repeat
+    async fn send_json_retry_safe<T: DeserializeOwned>(
+        &self,
+        rb: reqwest::RequestBuilder,
+    ) -> Result<T, CallToolResult> {
+        self.send_json_with(rb, None, true).await
+    }
+
+    /// Like `send_json`, but returns `not_found` instead of the generic error on a 404
+    async fn send_json_or<T: DeserializeOwned>(
+        &self,
+        rb: reqwest::RequestBuilder,
+        not_found: Option<CallToolResult>,
+    ) -> Result<T, CallToolResult> {
+        self.send_json_with(rb, not_found, false).await
+    }
+
+    /// Sends the request, retrying transient failures per the retry policy. GETs are always
+    /// retried; other methods only when `retry_safe`.
+    async fn send_with_retry(
+        &self,
+        rb: reqwest::RequestBuilder,
+        retry_safe: bool,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let request = rb.build()?;
+        let retry_safe = retry_safe || request.method() == reqwest::Method::GET;
+        let mut attempt = 1;
+        loop {
+            let retry = retry_safe && attempt < self.retry_policy.max_attempts;
+            let Some(next) = retry.then(|| request.try_clone()).flatten() else {
+                return self.client.execute(request).await;
+            };
+            let reason = match self.client.execute(next).await {
+                Ok(resp) if !RetryPolicy::is_transient(resp.status()) => return Ok(resp),
+                Ok(resp) => format!("status {}", resp.status()),
+                Err(e) if e.is_connect() => e.to_string(),
+                Err(e) => return Err(e),
+            };
+            let delay = self.retry_policy.delay(attempt - 1);
+            tracing::warn!(
+                "{} {} failed ({}), retrying in {:.2}s",
+                request.method(),
+                request.url(),
+                reason,
+                delay.as_secs_f64()
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    async fn send_json_with<T: DeserializeOwned>(
+        &self,
+        rb: reqwest::RequestBuilder,
+        not_found: Option<CallToolResult>,
+        retry_safe: bool,
+    ) -> Result<T, CallToolResult> {
+        let resp = self
+            .send_with_retry(rb, retry_safe)
             .await
             .map_err(|e| Self::err("Failed to connect to AF API", Some(&e.to_string())).unwrap())?;
 
         if !resp.status().is_success() {
             let status = resp.status();
+            if status == reqwest::StatusCode::NOT_FOUND
+                && let Some(not_found) = not_found
+            {
+                return Err(not_found);
+            }
+            // Surface the API's own error message (e.g. ambiguous id candidates) when present
+            let body = resp
+                .json::<ApiResponseEnvelope<serde_json::Value>>()
+                .await
+                .ok();
+            if status == reqwest::StatusCode::CONFLICT
+                && let Some(body) = &body
+                && let Some(candidates) = body
+                    .error_data
+                    .as_ref()
+                    .and_then(|data| serde_json::from_value::<Vec<Uuid>>(data.clone()).ok())
+            {
+                return Err(Self::ambiguous(body.message.as_deref(), &candidates));
+            }
+            let message = body.and_then(|body| body.message);
             return Err(
-                Self::err(format!("AF API returned error status: {}", status), None).unwrap(),
+                Self::err(format!("AF API returned error status: {}", status), message).unwrap(),
             );
         }
 
@@ -402,9 +1274,154 @@ impl TaskServer {
             return Err(Self::err("AF API returned error", Some(msg)).unwrap());
         }
 
-        api_response
-            .data
-            .ok_or_else(|| Self::err("AF API response missing data field", None).unwrap())
+        match api_response.data {
+            Some(data) => Ok(data),
+            // Routes returning `()` serialize their data as null
+            None => serde_json::from_value(serde_json::Value::Null).map_err(|_| {
+                Self::err("AF API response missing data field", None).unwrap()
+            }),
+        }
+    }
+
+    /// Sends a git operation that reports conflicts as `GitOperationError` data, returning the
+    /// conflict instead of a generic error
+    async fn send_git_operation(
+        &self,
+        rb: reqwest::RequestBuilder,
+    ) -> Result<Option<GitOperationError>, CallToolResult> {
+        let resp = self
+            .send_with_retry(rb, false)
+            .await
+            .map_err(|e| Self::err("Failed to connect to AF API", Some(&e.to_string())).unwrap())?;
+        let status = resp.status();
+        let body = resp
+            .json::<ApiResponseEnvelope<serde_json::Value>>()
+            .await
+            .ok();
+
+        if let Some(operation_error) = body
+            .as_ref()
+            .filter(|body| !body.success)
+            .and_then(|body| body.error_data.clone())
+            .and_then(|data| serde_json::from_value::<GitOperationError>(data).ok())
+        {
+            return Ok(Some(operation_error));
+        }
+        match body {
+            Some(body) if status.is_success() && body.success => Ok(None),
+            body => Err(Self::err(
+                format!("AF API returned error status: {}", status),
+                body.and_then(|body| body.message),
+            )
+            .unwrap()),
+        }
+    }
+
+    /// Creates a project from a clone, reporting clone failures with a stable error code
+    async fn send_project_clone(
+        &self,
+        rb: reqwest::RequestBuilder,
+    ) -> Result<Project, CallToolResult> {
+        let resp = self
+            .send_with_retry(rb, false)
+            .await
+            .map_err(|e| Self::err("Failed to connect to AF API", Some(&e.to_string())).unwrap())?;
+        let status = resp.status();
+        let body = resp
+            .json::<ApiResponseEnvelope<Project>>()
+            .await
+            .map_err(|e| {
+                Self::err("Failed to parse AF API response", Some(&e.to_string())).unwrap()
+            })?;
+
+        if let Some(clone_error) = body
+            .error_data
+            .clone()
+            .filter(|_| !body.success)
+            .and_then(|data| serde_json::from_value::<ProjectCloneError>(data).ok())
+        {
+            let message = body.message.unwrap_or_default();
+            return Err(match clone_error {
+                ProjectCloneError::AuthFailed { .. } => Self::err_code(CLONE_AUTH_FAILED, message),
+                ProjectCloneError::PathExists { .. } => Self::err_code(CLONE_PATH_EXISTS, message),
+                ProjectCloneError::CloneFailed { .. } => Self::err_code(CLONE_FAILED, message),
+            });
+        }
+        match body.data {
+            Some(project) if status.is_success() && body.success => Ok(project),
+            _ => Err(Self::err(
+                format!("AF API returned error status: {}", status),
+                body.message,
+            )
+            .unwrap()),
+        }
+    }
+
+    /// Resolves `images` to image IDs, uploading data URIs. Every image is validated before
+    /// anything is uploaded, so a bad one leaves no orphaned uploads behind.
+    async fn upload_images(&self, images: &[String]) -> Result<Vec<Uuid>, CallToolResult> {
+        enum Image {
+            Uploaded(Uuid),
+            Data(UploadImageFormat, Vec<u8>),
+        }
+
+        let mut parsed = Vec::with_capacity(images.len());
+        for image in images.iter().map(|image| image.trim()) {
+            if let Ok(id) = Uuid::parse_str(image) {
+                parsed.push(Image::Uploaded(id));
+                continue;
+            }
+            let Some((mime, data)) = image
+                .strip_prefix("data:")
+                .and_then(|rest| rest.split_once(";base64,"))
+            else {
+                return Err(Self::err_code(
+                    UNSUPPORTED_IMAGE,
+                    "Images must be uploaded image IDs or base64 data URIs",
+                ));
+            };
+            let Some(format) = UploadImageFormat::ALL
+                .into_iter()
+                .find(|format| format.mime_type().eq_ignore_ascii_case(mime))
+            else {
+                let supported: Vec<&str> = UploadImageFormat::ALL
+                    .iter()
+                    .map(|format| format.mime_type())
+                    .collect();
+                return Err(Self::err_code(
+                    UNSUPPORTED_IMAGE,
+                    format!(
+                        "Unsupported image type '{mime}'; use one of {}",
+                        supported.join(", ")
+                    ),
+                ));
+            };
+            let bytes = BASE64_STANDARD.decode(data).map_err(|e| {
+                Self::err_code(UNSUPPORTED_IMAGE, format!("Invalid base64 image data: {e}"))
+            })?;
+            parsed.push(Image::Data(format, bytes));
+        }
+
+        let url = self.url("/api/images/upload");
+        let mut ids = Vec::with_capacity(parsed.len());
+        for image in parsed {
+            let id = match image {
+                Image::Uploaded(id) => id,
+                Image::Data(format, bytes) => {
+                    let part = reqwest::multipart::Part::bytes(bytes)
+                        .file_name(format!("image.{}", format.extension()))
+                        .mime_str(format.mime_type())
+                        .expect("static image MIME types are valid");
+                    let form = reqwest::multipart::Form::new().part("image", part);
+                    let uploaded: ImageResponse = self
+                        .send_json(self.client.post(&url).multipart(form))
+                        .await?;
+                    uploaded.id
+                }
+            };
+            ids.push(id);
+        }
+        Ok(ids)
     }
 
     fn url(&self, path: &str) -> String {
@@ -415,117 +1432,576 @@ impl TaskServer {
         )
     }
 
-    fn supported_protocol_versions() -> &'static [ProtocolVersion] {
-        &SUPPORTED_PROTOCOL_VERSIONS
+    /// Where a page starts: the position encoded in `cursor`, else `offset`
+    fn page_start(offset: Option<u32>, cursor: Option<&str>) -> Result<u32, CallToolResult> {
+        match cursor {
+            Some(cursor) => cursor.trim().parse().map_err(|_| {
+                Self::err_code(
+                    INVALID_CURSOR,
+                    format!("Cursor '{cursor}' was not returned by a previous call"),
+                )
+            }),
+            None => Ok(offset.unwrap_or(0)),
+        }
     }
 
-    fn latest_supported_protocol() -> ProtocolVersion {
-        Self::supported_protocol_versions()
-            .first()
-            .expect("supported protocols list cannot be empty")
-            .clone()
+    /// Checks that a listing `cursor` came from a previous call before it is passed on
+    fn page_cursor(cursor: Option<String>) -> Result<Option<String>, CallToolResult> {
+        match cursor {
+            Some(cursor) if PageCursor::parse(&cursor).is_none() => Err(Self::err_code(
+                INVALID_CURSOR,
+                format!("Cursor '{cursor}' was not returned by a previous call"),
+            )),
+            cursor => Ok(cursor),
+        }
     }
 
-    fn minimum_supported_protocol() -> ProtocolVersion {
-        Self::supported_protocol_versions()
-            .last()
-            .expect("supported protocols list cannot be empty")
-            .clone()
+    /// Drops the extra row fetched past `limit` and returns the cursor of the following page,
+    /// keyed on the last row kept, if there is one
+    fn finish_page<T>(
+        rows: &mut Vec<T>,
+        limit: u32,
+        key: impl Fn(&T) -> PageCursor,
+    ) -> Option<String> {
+        let has_more = limit > 0 && rows.len() > limit as usize;
+        rows.truncate(limit as usize);
+        rows.last()
+            .filter(|_| has_more)
+            .map(|last| key(last).encode())
     }
 
-    fn current_protocol_version(&self) -> ProtocolVersion {
-        self.negotiated_protocol_version
-            .read()
-            .expect("protocol negotiation lock poisoned")
-            .clone()
-    }
+    /// Finds a project by its id or its (case-insensitive) name
+    async fn resolve_project(&self, project_ref: &str) -> Result<Project, CallToolResult> {
+        let project_ref = project_ref.trim();
+        let projects: Vec<Project> = self
+            .send_json(self.client.get(self.url("/api/projects")))
+            .await?;
 
-    fn set_negotiated_protocol_version(&self, version: ProtocolVersion) {
-        let mut guard = self
-            .negotiated_protocol_version
-            .write()
-            .expect("protocol negotiation lock poisoned");
-        *guard = version;
+        if let Ok(id) = Uuid::parse_str(project_ref)
+            && let Some(project) = projects.iter().find(|p| p.id == id)
+        {
+            return Ok(project.clone());
+        }
+        let mut named: Vec<Project> = projects
+            .into_iter()
+            .filter(|p| p.name.eq_ignore_ascii_case(project_ref))
+            .collect();
+        match named.len() {
+            0 => Err(Self::err_code(
+                PROJECT_NOT_FOUND,
+                format!("Project '{project_ref}' not found"),
+            )),
+            1 => Ok(named.remove(0)),
+            _ => {
+                let ids: Vec<Uuid> = named.iter().map(|p| p.id).collect();
+                let message = format!("Several projects are named '{project_ref}'; pass an id");
+                Err(Self::ambiguous(Some(&message), &ids))
+            }
+        }
     }
 
-    fn server_info_for_version(&self, protocol_version: ProtocolVersion) -> ServerInfo {
-        ServerInfo {
-            protocol_version,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
-            server_info: Implementation {
-                name: "automagik-forge".to_string(),
-                version: "1.0.0".to_string(),
-                title: None,
-                icons: None,
-                website_url: None,
-            },
-            instructions: Some("A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. This should be provided to you. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_task_attempt', 'get_task', 'update_task', 'delete_task'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string()),
+    /// Top-level fields of the Forge config. `set_forge_config` only changes the
+    /// [`SETTABLE_CONFIG_KEYS`] among them and refuses the others as protected.
+    fn forge_config_keys() -> Vec<String> {
+        match serde_json::to_value(ForgeProjectSettings::default()) {
+            Ok(serde_json::Value::Object(fields)) => fields.keys().cloned().collect(),
+            _ => Vec::new(),
         }
     }
 
-    fn log_downgrade_if_needed(requested: &ProtocolVersion, negotiated: &ProtocolVersion) {
-        let latest = Self::latest_supported_protocol();
-        if negotiated != &latest {
-            info!(
-                requested_protocol = %requested,
-                negotiated_protocol = %negotiated,
-                latest_supported_protocol = %latest,
-                "Downgrading MCP protocol version for backward compatibility"
-            );
+    /// `settings` with the Omni API key and the webhook secret masked
+    fn redact_forge_config(mut settings: ForgeProjectSettings) -> ForgeProjectSettings {
+        if let Some(api_key) = settings
+            .omni_config
+            .as_mut()
+            .and_then(|omni| omni.api_key.as_mut())
+        {
+            *api_key = REDACTED_SECRET.to_string();
         }
+        if let Some(webhook) = settings.webhook.as_mut() {
+            webhook.secret = REDACTED_SECRET.to_string();
+        }
+        settings
     }
 
-    fn negotiate_protocol_version(
-        requested: &ProtocolVersion,
-    ) -> Result<ProtocolVersion, ErrorData> {
-        for supported in Self::supported_protocol_versions() {
-            match requested.partial_cmp(supported) {
-                Some(Ordering::Greater) | Some(Ordering::Equal) => {
-                    return Ok(supported.clone());
-                }
-                Some(Ordering::Less) => continue,
-                None => {
-                    return Err(ErrorData::invalid_params(
-                        format!(
-                            "Unable to compare requested MCP protocol version ({requested}) with supported versions"
-                        ),
-                        Some(serde_json::json!({
-                            "requested_protocol": requested.to_string(),
-                            "supported_protocols": Self::supported_protocol_versions()
-                                .iter()
-                                .map(|v| v.to_string())
-                                .collect::<Vec<_>>(),
-                        })),
-                    ));
-                }
-            }
+    /// Creates one `tasks_create` item, starting it when asked. Returns the task id and whether
+    /// an attempt was started.
+    async fn create_batch_item(
+        &self,
+        project_id: Uuid,
+        item: BatchTaskItem,
+        default_branch: &mut Option<String>,
+    ) -> Result<(Uuid, bool), CallToolResult> {
+        if item.title.trim().is_empty() {
+            return Err(Self::err("Title must not be empty.".to_string(), None).unwrap());
         }
+        let task = CreateTask::from_title_description(project_id, item.title, item.description);
 
-        Err(Self::protocol_version_too_old_error(requested))
+        if !item.start.unwrap_or(item.executor.is_some()) {
+            let created: CreatedTask = self
+                .send_json(self.client.post(self.url("/api/tasks")).json(&task))
+                .await?;
+            return Ok((created.task.id, false));
+        }
+
+        let Some(executor) = item.executor else {
+            return Err(
+                Self::err("`executor` is required to start a task.".to_string(), None).unwrap(),
+            );
+        };
+        let executor_profile_id = Self::executor_profile(&executor, None)?;
+        let base_branch = match default_branch.clone() {
+            Some(branch) => branch,
+            None => default_branch
+                .insert(self.default_branch(project_id).await?)
+                .clone(),
+        };
+        let payload = CreateAndStartTaskRequest {
+            task,
+            executor_profile_id,
+            base_branch,
+            use_worktree: None,
+        };
+        let started: TaskWithAttemptStatus = self
+            .send_json(
+                self.client
+                    .post(self.url("/api/tasks/create-and-start"))
+                    .json(&payload),
+            )
+            .await?;
+        Ok((started.task.id, true))
     }
 
-    fn protocol_version_too_old_error(requested: &ProtocolVersion) -> ErrorData {
-        let minimum = Self::minimum_supported_protocol();
-        ErrorData::invalid_params(
-            format!(
-                "Requested MCP protocol version ({requested}) is older than the supported minimum ({minimum})"
-            ),
-            Some(serde_json::json!({
-                "requested_protocol": requested.to_string(),
-                "minimum_supported_protocol": minimum.to_string(),
-                "supported_protocols": Self::supported_protocol_versions()
-                    .iter()
-                    .map(|v| v.to_string())
-                    .collect::<Vec<_>>(),
-            })),
-        )
+    /// The JSON body of an error result, as a single tool call would have returned it
+    fn error_body(result: &CallToolResult) -> serde_json::Value {
+        let text = result
+            .content
+            .first()
+            .and_then(|content| content.as_text())
+            .map(|text| text.text.clone())
+            .unwrap_or_default();
+        serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text))
     }
-}
 
-#[tool_router]
-impl TaskServer {
-    #[tool(
-        description = "Create a new task/ticket in a project. Always pass the `project_id` of the project you want to create the task in - it is required!"
+    /// Parses a user-supplied executor name such as `claude-code` and optional variant
+    fn executor_profile(
+        executor: &str,
+        variant: Option<String>,
+    ) -> Result<ExecutorProfileId, CallToolResult> {
+        let executor_trimmed = executor.trim();
+        if executor_trimmed.is_empty() {
+            return Err(Self::err("Executor must not be empty.".to_string(), None).unwrap());
+        }
+
+        let normalized_executor = executor_trimmed.replace('-', "_").to_ascii_uppercase();
+        let base_executor = BaseCodingAgent::from_str(&normalized_executor).map_err(|_| {
+            Self::err(format!("Unknown executor '{executor_trimmed}'."), None).unwrap()
+        })?;
+
+        let variant = variant.and_then(|v| {
+            let trimmed = v.trim();
+            if trimmed.is_empty() {
+                None
+            } else {
+                Some(trimmed.to_string())
+            }
+        });
+
+        Ok(ExecutorProfileId {
+            executor: base_executor,
+            variant,
+        })
+    }
+
+    /// Fetches a task by its full id or an unambiguous id prefix
+    async fn resolve_task(&self, task_ref: &str) -> Result<Task, CallToolResult> {
+        let url = self.url(&format!("/api/tasks/{}", task_ref.trim()));
+        self.send_json(self.client.get(&url)).await
+    }
+
+    /// Fetches an attempt by its full id or an unambiguous id prefix
+    async fn resolve_attempt(&self, attempt_ref: &str) -> Result<TaskAttempt, CallToolResult> {
+        let attempt_ref = attempt_ref.trim();
+        let url = self.url(&format!("/api/task-attempts/{attempt_ref}"));
+        let not_found =
+            Self::err_code(ATTEMPT_NOT_FOUND, format!("Task attempt '{attempt_ref}' not found"));
+        self.send_json_or(self.client.get(&url), Some(not_found)).await
+    }
+
+    /// Attempts started for `task_id`, if the API could be reached
+    async fn task_attempts(&self, task_id: Uuid) -> Option<Vec<TaskAttempt>> {
+        let url = self.url(&format!("/api/task-attempts?task_id={task_id}"));
+        self.send_json(self.client.get(&url)).await.ok()
+    }
+
+    /// Number of attempts started for `task_id`, if the API could be reached
+    async fn attempts_count(&self, task_id: Uuid) -> Option<usize> {
+        self.task_attempts(task_id)
+            .await
+            .map(|attempts| attempts.len())
+    }
+
+    /// Number of tasks created by `attempts`, if the API could be reached for all of them
+    async fn children_count(&self, attempts: &[TaskAttempt]) -> Option<usize> {
+        let mut count = 0;
+        for attempt in attempts {
+            let url = self.url(&format!("/api/task-attempts/{}/children", attempt.id));
+            let relationships: TaskRelationships =
+                self.send_json(self.client.get(&url)).await.ok()?;
+            count += relationships.children.len();
+        }
+        Some(count)
+    }
+
+    /// Details of `task` with its attempt count and place in the subtask tree. Lookups that
+    /// fail leave their field unset instead of failing the tool call.
+    async fn task_details(&self, task: Task) -> TaskDetails {
+        let parent_task_id = match task.parent_task_attempt {
+            Some(attempt_id) => self
+                .resolve_attempt(&attempt_id.to_string())
+                .await
+                .ok()
+                .map(|attempt| attempt.task_id.to_string()),
+            None => None,
+        };
+        let attempts = self.task_attempts(task.id).await;
+        let children_count = match &attempts {
+            Some(attempts) => self.children_count(attempts).await,
+            None => None,
+        };
+        TaskDetails {
+            attempts_count: attempts.as_ref().map(Vec::len),
+            parent_task_id,
+            children_count,
+            ..TaskDetails::from_task(task)
+        }
+    }
+
+    /// The branch attempts in `project_id` start from when none is given
+    async fn default_branch(&self, project_id: Uuid) -> Result<String, CallToolResult> {
+        let url = self.url(&format!("/api/projects/{project_id}/default-branch"));
+        self.send_json(self.client.get(&url)).await
+    }
+
+    /// Reports an attempt's status from its newest execution process and its merge state
+    async fn attempt_status(&self, attempt_id: Uuid) -> Result<AttemptStatus, CallToolResult> {
+        let url = self.url(&format!("/api/execution-processes?task_attempt_id={attempt_id}"));
+        let processes: Vec<ExecutionProcess> = self.send_json(self.client.get(&url)).await?;
+
+        // Recorded merges only; branch status would recreate a cleaned-up worktree
+        let url = self.url(&format!("/api/task-attempts/{attempt_id}/merges"));
+        let merges: Vec<Merge> = self.send_json(self.client.get(&url)).await?;
+        let merged = merges.iter().any(|merge| match merge {
+            Merge::Direct(_) => true,
+            Merge::Pr(pr) => matches!(pr.pr_info.status, MergeStatus::Merged),
+        });
+
+        Ok(AttemptStatus::from_processes(&processes, merged))
+    }
+
+    /// GETs `path` for a diagnostic check, reporting failures as text rather than tool errors
+    async fn probe(&self, path: &str) -> Result<serde_json::Value, String> {
+        let resp = self
+            .client
+            .get(self.url(path))
+            .timeout(DOCTOR_PROBE_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !resp.status().is_success() {
+            return Err(format!("{path} returned {}", resp.status()));
+        }
+        resp.json().await.map_err(|e| e.to_string())
+    }
+
+    /// Like `probe`, for endpoints wrapped in the `ApiResponse` envelope
+    async fn probe_data(&self, path: &str) -> Result<serde_json::Value, String> {
+        let body: ApiResponseEnvelope<serde_json::Value> =
+            serde_json::from_value(self.probe(path).await?).map_err(|e| e.to_string())?;
+        if !body.success {
+            return Err(body.message.unwrap_or_else(|| "Unknown error".to_string()));
+        }
+        Ok(body.data.unwrap_or_default())
+    }
+
+    async fn check_api(&self) -> DoctorCheck {
+        match self.probe_data("/api/health").await {
+            Ok(_) => DoctorCheck::pass(
+                "api",
+                format!("Forge API is reachable at {}", self.base_url),
+            ),
+            Err(e) => DoctorCheck::fail(
+                "api",
+                format!("Forge API at {} is unreachable: {e}", self.base_url),
+                "Make sure the Forge server is running and the MCP server points at its port",
+            ),
+        }
+    }
+
+    async fn check_github(&self, info: &Result<serde_json::Value, String>) -> DoctorCheck {
+        let info = match info {
+            Ok(info) => info,
+            Err(e) => {
+                return DoctorCheck::fail(
+                    "github",
+                    format!("Could not read the configuration: {e}"),
+                    "Fix the Forge API check first",
+                );
+            }
+        };
+        let github = &info["config"]["github"];
+        let has_token = ["pat", "oauth_token"]
+            .iter()
+            .any(|key| github[key].as_str().is_some_and(|t| !t.is_empty()));
+        if !has_token {
+            return DoctorCheck::fail(
+                "github",
+                "No GitHub token is configured".to_string(),
+                "Sign in with GitHub or add a personal access token in Settings",
+            );
+        }
+
+        match self.probe_data("/api/auth/github/check").await {
+            Ok(status) if status == "VALID" => {
+                DoctorCheck::pass("github", "GitHub token is valid".to_string())
+            }
+            Ok(_) => DoctorCheck::fail(
+                "github",
+                "GitHub rejected the configured token".to_string(),
+                "Sign in with GitHub again or replace the personal access token in Settings",
+            ),
+            Err(e) => DoctorCheck::warn(
+                "github",
+                format!("Could not verify the GitHub token: {e}"),
+                "Check network access to api.github.com",
+            ),
+        }
+    }
+
+    async fn check_omni(&self) -> DoctorCheck {
+        let status = match self.probe("/api/forge/omni/status").await {
+            Ok(status) => status,
+            Err(e) => {
+                return DoctorCheck::fail(
+                    "omni",
+                    format!("Could not read the Omni status: {e}"),
+                    "Fix the Forge API check first",
+                );
+            }
+        };
+        if !status["enabled"].as_bool().unwrap_or(false) {
+            return DoctorCheck::warn(
+                "omni",
+                "Omni notifications are disabled".to_string(),
+                "Enable Omni in the Forge settings to receive task notifications",
+            );
+        }
+
+        let missing = ["host", "api_key", "instance", "recipient"]
+            .into_iter()
+            .filter(|key| {
+                status["config"][key]
+                    .as_str()
+                    .is_none_or(|v| v.trim().is_empty())
+            })
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            DoctorCheck::pass("omni", "Omni is enabled and configured".to_string())
+        } else {
+            DoctorCheck::fail(
+                "omni",
+                format!("Omni is enabled but missing: {}", missing.join(", ")),
+                "Complete the Omni configuration in the Forge settings",
+            )
+        }
+    }
+
+    fn check_profiles(info: &Result<serde_json::Value, String>) -> DoctorCheck {
+        let info = match info {
+            Ok(info) => info,
+            Err(e) => {
+                return DoctorCheck::fail(
+                    "profiles",
+                    format!("Could not read the executor profiles: {e}"),
+                    "Fix the Forge API check first",
+                );
+            }
+        };
+        let count: usize = info["executors"]
+            .as_object()
+            .map(|executors| {
+                executors
+                    .values()
+                    .filter_map(|configurations| configurations.as_object())
+                    .map(|configurations| configurations.len())
+                    .sum()
+            })
+            .unwrap_or(0);
+        if count == 0 {
+            DoctorCheck::fail(
+                "profiles",
+                "No executor profiles are loaded".to_string(),
+                "Check profiles.json in the Forge config directory or reset the agent profiles",
+            )
+        } else {
+            DoctorCheck::pass("profiles", format!("{count} executor profile(s) loaded"))
+        }
+    }
+
+    async fn check_database(&self) -> DoctorCheck {
+        match self.probe_data("/api/projects").await {
+            Ok(projects) => DoctorCheck::pass(
+                "database",
+                format!(
+                    "Database is responding ({} project(s))",
+                    projects.as_array().map_or(0, Vec::len)
+                ),
+            ),
+            Err(e) => DoctorCheck::fail(
+                "database",
+                format!("Database query failed: {e}"),
+                "Check the server logs and that the Forge database file is readable",
+            ),
+        }
+    }
+
+    fn timed_tool_router(&self) -> TimedToolRouter<'_> {
+        TimedToolRouter { server: self }
+    }
+
+    /// Records a tool call locally and forwards it to the API's `/api/metrics`, since this
+    /// server runs in its own process
+    fn record_tool_timing(&self, tool: String, elapsed: Duration, failed: bool) {
+        metrics::global().record(MetricKind::Tool, &tool, elapsed, failed);
+
+        let request = self
+            .client
+            .post(self.url("/api/metrics/tools"))
+            .json(&serde_json::json!({
+                "tool": &tool,
+                "duration_ms": elapsed.as_secs_f64() * 1000.0,
+                "failed": failed,
+            }));
+        tokio::spawn(async move {
+            if let Err(e) = request.send().await {
+                tracing::debug!("Failed to report timing for tool '{}': {}", tool, e);
+            }
+        });
+    }
+
+    fn supported_protocol_versions() -> &'static [ProtocolVersion] {
+        &SUPPORTED_PROTOCOL_VERSIONS
+    }
+
+    fn latest_supported_protocol() -> ProtocolVersion {
+        Self::supported_protocol_versions()
+            .first()
+            .expect("supported protocols list cannot be empty")
+            .clone()
+    }
+
+    fn minimum_supported_protocol() -> ProtocolVersion {
+        Self::supported_protocol_versions()
+            .last()
+            .expect("supported protocols list cannot be empty")
+            .clone()
+    }
+
+    /// Protocol version negotiated with this session's client; the latest supported version
+    /// until the client has initialized
+    pub fn current_protocol_version(&self) -> ProtocolVersion {
+        self.negotiated_protocol_version
+            .read()
+            .expect("protocol negotiation lock poisoned")
+            .clone()
+    }
+
+    fn set_negotiated_protocol_version(&self, version: ProtocolVersion) {
+        let mut guard = self
+            .negotiated_protocol_version
+            .write()
+            .expect("protocol negotiation lock poisoned");
+        *guard = version;
+    }
+
+    fn server_info_for_version(&self, protocol_version: ProtocolVersion) -> ServerInfo {
+        ServerInfo {
+            protocol_version,
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            server_info: Implementation {
+                name: "automagik-forge".to_string(),
+                version: "1.0.0".to_string(),
+                title: None,
+                icons: None,
+                website_url: None,
+            },
+            instructions: Some("A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. This should be provided to you. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`. TOOLS: 'list_projects', 'list_tasks', 'search', 'create_task', 'start_task_attempt', 'get_task', 'update_task', 'delete_task'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string()),
+        }
+    }
+
+    fn log_downgrade_if_needed(requested: &ProtocolVersion, negotiated: &ProtocolVersion) {
+        let latest = Self::latest_supported_protocol();
+        if negotiated != &latest {
+            info!(
+                requested_protocol = %requested,
+                negotiated_protocol = %negotiated,
+                latest_supported_protocol = %latest,
+                "Downgrading MCP protocol version for backward compatibility"
+            );
+        }
+    }
+
+    fn negotiate_protocol_version(
+        requested: &ProtocolVersion,
+    ) -> Result<ProtocolVersion, ErrorData> {
+        for supported in Self::supported_protocol_versions() {
+            match requested.partial_cmp(supported) {
+                Some(Ordering::Greater) | Some(Ordering::Equal) => {
+                    return Ok(supported.clone());
+                }
+                Some(Ordering::Less) => continue,
+                None => {
+                    return Err(ErrorData::invalid_params(
+                        format!(
+                            "Unable to compare requested MCP protocol version ({requested}) with supported versions"
+                        ),
+                        Some(serde_json::json!({
+                            "requested_protocol": requested.to_string(),
+                            "supported_protocols": Self::supported_protocol_versions()
+                                .iter()
+                                .map(|v| v.to_string())
+                                .collect::<Vec<_>>(),
+                        })),
+                    ));
+                }
+            }
+        }
+
+        Err(Self::protocol_version_too_old_error(requested))
+    }
+
+    fn protocol_version_too_old_error(requested: &ProtocolVersion) -> ErrorData {
+        let minimum = Self::minimum_supported_protocol();
+        ErrorData::invalid_params(
+            format!(
+                "Requested MCP protocol version ({requested}) is older than the supported minimum ({minimum})"
+            ),
+            Some(serde_json::json!({
+                "requested_protocol": requested.to_string(),
+                "minimum_supported_protocol": minimum.to_string(),
+                "supported_protocols": Self::supported_protocol_versions()
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>(),
+            })),
+        )
+    }
+}
+
+#[tool_router]
+impl TaskServer {
+    #[tool(
+        description = "Create a new task/ticket in a project. Always pass the `project_id` of the project you want to create the task in - it is required!"
     )]
     async fn create_task(
         &self,
@@ -533,504 +2009,4103 @@ impl TaskServer {
             project_id,
             title,
             description,
+            force,
+            images,
+            client_token,
         }): Parameters<CreateTaskRequest>,
     ) -> Result<CallToolResult, ErrorData> {
+        let image_ids = match images.filter(|images| !images.is_empty()) {
+            Some(images) => match self.upload_images(&images).await {
+                Ok(ids) => Some(ids),
+                Err(e) => return Ok(e),
+            },
+            None => None,
+        };
+
         let url = self.url("/api/tasks");
-        let task: Task = match self
-            .send_json(
-                self.client
-                    .post(&url)
-                    .json(&CreateTask::from_title_description(
-                        project_id,
-                        title,
-                        description,
-                    )),
-            )
-            .await
-        {
+        let request = self
+            .client
+            .post(&url)
+            .query(&[("force", force.unwrap_or(false))])
+            .json(&CreateTask {
+                image_ids,
+                ..CreateTask::from_title_description(project_id, title, description)
+            });
+        let client_token = client_token.as_deref().map(str::trim);
+        let sent = match client_token.filter(|token| !token.is_empty()) {
+            // The token makes a repeated request return the same task, so it is safe to retry
+            Some(token) => {
+                self.send_json_retry_safe(request.header(IDEMPOTENCY_KEY_HEADER, token))
+                    .await
+            }
+            None => self.send_json(request).await,
+        };
+        let created: CreatedTask = match sent {
             Ok(t) => t,
             Err(e) => return Ok(e),
         };
 
-        TaskServer::success(&CreateTaskResponse {
-            task_id: task.id.to_string(),
-        })
+        TaskServer::success(&CreateTaskResponse {
+            task_id: created.task.id.to_string(),
+            possible_duplicates: created
+                .possible_duplicates
+                .into_iter()
+                .map(|d| DuplicateTaskSummary {
+                    id: d.id.to_string(),
+                    title: d.title,
+                    similarity: d.similarity,
+                })
+                .collect(),
+        })
+    }
+
+    #[tool(
+        description = "Create several tasks in a project in one call, optionally starting each with a coding agent. Every item gets its own result with `success` and, on failure, the `error` a single create would have returned, so one bad item does not stop the rest."
+    )]
+    async fn tasks_create(
+        &self,
+        Parameters(CreateTasksRequest {
+            project,
+            tasks,
+            base_branch,
+        }): Parameters<CreateTasksRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if tasks.is_empty() {
+            return Self::err("Pass at least one task to create.".to_string(), None);
+        }
+        if tasks.len() > BATCH_CREATE_LIMIT {
+            return Self::err(
+                format!("At most {BATCH_CREATE_LIMIT} tasks can be created in one call."),
+                Some(format!("got {}", tasks.len())),
+            );
+        }
+
+        let project = match self.resolve_project(&project).await {
+            Ok(project) => project,
+            Err(e) => return Ok(e),
+        };
+        // Only looked up once something is started
+        let mut default_branch = base_branch
+            .map(|b| b.trim().to_string())
+            .filter(|b| !b.is_empty());
+
+        let mut results = Vec::with_capacity(tasks.len());
+        for (index, item) in tasks.into_iter().enumerate() {
+            let title = item.title.clone();
+            let outcome = self
+                .create_batch_item(project.id, item, &mut default_branch)
+                .await;
+            results.push(match outcome {
+                Ok((task_id, started)) => BatchTaskResult {
+                    index,
+                    title,
+                    success: true,
+                    task_id: Some(task_id.to_string()),
+                    started,
+                    error: None,
+                },
+                Err(e) => BatchTaskResult {
+                    index,
+                    title,
+                    success: false,
+                    task_id: None,
+                    started: false,
+                    error: Some(Self::error_body(&e)),
+                },
+            });
+        }
+
+        let created = results.iter().filter(|r| r.success).count();
+        TaskServer::success(&CreateTasksResponse {
+            project_id: project.id.to_string(),
+            created,
+            failed: results.len() - created,
+            results,
+        })
+    }
+
+    #[tool(
+        description = "Copy a task's title, description and images into a new task, in the same or another project. Pass `executor` to start the copy right away."
+    )]
+    async fn duplicate_task(
+        &self,
+        Parameters(DuplicateTaskRequest {
+            task_id,
+            project,
+            executor,
+            variant,
+            base_branch,
+        }): Parameters<DuplicateTaskRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let executor_profile_id = match executor
+            .map(|executor| Self::executor_profile(&executor, variant))
+            .transpose()
+        {
+            Ok(profile) => profile,
+            Err(e) => return Ok(e),
+        };
+
+        let source = match self.resolve_task(&task_id).await {
+            Ok(task) => task,
+            Err(e) => return Ok(e),
+        };
+        let project_id = match project {
+            Some(project) => match self.resolve_project(&project).await {
+                Ok(project) => project.id,
+                Err(e) => return Ok(e),
+            },
+            None => source.project_id,
+        };
+
+        let url = self.url(&format!("/api/images/task/{}", source.id));
+        let images: Vec<ImageResponse> = match self.send_json(self.client.get(&url)).await {
+            Ok(images) => images,
+            Err(e) => return Ok(e),
+        };
+        let image_ids: Vec<Uuid> = images.iter().map(|image| image.id).collect();
+        let task = CreateTask {
+            image_ids: (!image_ids.is_empty()).then(|| image_ids.clone()),
+            ..CreateTask::from_title_description(
+                project_id,
+                source.title.clone(),
+                source.description.clone(),
+            )
+        };
+
+        let (task_id, started) = match executor_profile_id {
+            None => {
+                // The copy is meant to match its source, so skip the duplicate-title check
+                let request = self
+                    .client
+                    .post(self.url("/api/tasks"))
+                    .query(&[("force", true)])
+                    .json(&task);
+                match self.send_json::<CreatedTask>(request).await {
+                    Ok(created) => (created.task.id, false),
+                    Err(e) => return Ok(e),
+                }
+            }
+            Some(executor_profile_id) => {
+                let base_branch = match base_branch
+                    .map(|b| b.trim().to_string())
+                    .filter(|b| !b.is_empty())
+                {
+                    Some(branch) => branch,
+                    None => match self.default_branch(project_id).await {
+                        Ok(branch) => branch,
+                        Err(e) => return Ok(e),
+                    },
+                };
+                let payload = CreateAndStartTaskRequest {
+                    task,
+                    executor_profile_id,
+                    base_branch,
+                    use_worktree: None,
+                };
+                let request = self
+                    .client
+                    .post(self.url("/api/tasks/create-and-start"))
+                    .json(&payload);
+                match self.send_json::<TaskWithAttemptStatus>(request).await {
+                    Ok(started) => (started.task.id, true),
+                    Err(e) => return Ok(e),
+                }
+            }
+        };
+
+        TaskServer::success(&DuplicateTaskResponse {
+            source_task_id: source.id.to_string(),
+            task_id: task_id.to_string(),
+            project_id: project_id.to_string(),
+            images: image_ids.len(),
+            started,
+        })
+    }
+
+    #[tool(description = "List all the available projects")]
+    async fn list_projects(&self) -> Result<CallToolResult, ErrorData> {
+        let url = self.url("/api/projects");
+        let projects: Vec<Project> = match self.send_json(self.client.get(&url)).await {
+            Ok(ps) => ps,
+            Err(e) => return Ok(e),
+        };
+
+        // Counts are a nice-to-have; list the projects even if they can't be fetched
+        let counts_url = self.url("/api/projects/counts");
+        let counts: HashMap<Uuid, ProjectTaskCounts> = self
+            .send_json::<Vec<ProjectTaskCounts>>(self.client.get(&counts_url))
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|c| (c.project_id, c))
+            .collect();
+
+        let project_summaries: Vec<ProjectSummary> = projects
+            .into_iter()
+            .map(|project| {
+                let project_counts = counts.get(&project.id);
+                ProjectSummary::from_project(project, project_counts)
+            })
+            .collect();
+
+        let response = ListProjectsResponse {
+            count: project_summaries.len(),
+            projects: project_summaries,
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Clone a remote git repository and register it as a new project, so a GitHub repository can be onboarded without cloning it by hand. Fails with `CLONE_AUTH_FAILED`, `CLONE_PATH_EXISTS` or `CLONE_FAILED`."
+    )]
+    async fn clone_project(
+        &self,
+        Parameters(CloneProjectRequest {
+            git_url,
+            target_dir,
+            name,
+        }): Parameters<CloneProjectRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let git_url = git_url.trim().to_string();
+        let Some(repo_name) = GitService::repo_name_from_clone_url(&git_url) else {
+            return Self::err(format!("Not a repository URL: '{git_url}'"), None);
+        };
+        let name = name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or(repo_name);
+
+        let request = self
+            .client
+            .post(self.url("/api/projects"))
+            .json(&CreateProject {
+                name,
+                git_repo_path: target_dir.unwrap_or_default(),
+                use_existing_repo: false,
+                setup_script: None,
+                dev_script: None,
+                cleanup_script: None,
+                copy_files: None,
+                commit_prompt: None,
+                clone_url: Some(git_url),
+            });
+        match self.send_project_clone(request).await {
+            Ok(project) => TaskServer::success(&ProjectSummary::from_project(project, None)),
+            Err(e) => Ok(e),
+        }
+    }
+
+    #[tool(
+        description = "List a project's local and remote branches with how far each is ahead of and behind the project's default branch, and whether it is already merged. Use it to spot stale branches; pass `names_only` to skip the comparison."
+    )]
+    async fn list_project_branches(
+        &self,
+        Parameters(ListProjectBranchesRequest {
+            project,
+            names_only,
+        }): Parameters<ListProjectBranchesRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let project = match self.resolve_project(&project).await {
+            Ok(project) => project,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!("/api/projects/{}/branches", project.id));
+        let request = self
+            .client
+            .get(&url)
+            .query(&[("names_only", names_only.unwrap_or(false))]);
+        let branches: Vec<GitBranch> = match self.send_json(request).await {
+            Ok(branches) => branches,
+            Err(e) => return Ok(e),
+        };
+
+        let branches: Vec<ProjectBranchSummary> = branches
+            .into_iter()
+            .map(ProjectBranchSummary::from_branch)
+            .collect();
+        TaskServer::success(&ListProjectBranchesResponse {
+            project_id: project.id.to_string(),
+            count: branches.len(),
+            branches,
+        })
+    }
+
+    #[tool(
+        description = "List all the task/tickets in a project with optional filtering and execution status. `project_id` is required!"
+    )]
+    async fn list_tasks(
+        &self,
+        Parameters(ListTasksRequest {
+            project_id,
+            status,
+            limit,
+            cursor,
+        }): Parameters<ListTasksRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let status_filter = if let Some(ref status_str) = status {
+            match TaskStatus::from_str(status_str) {
+                Ok(s) => Some(s),
+                Err(_) => {
+                    return Self::err(
+                        "Invalid status filter. Valid values: 'todo', 'in-progress', 'in-review', 'done', 'cancelled'".to_string(),
+                        Some(status_str.to_string()),
+                    );
+                }
+            }
+        } else {
+            None
+        };
+
+        let before = match Self::page_cursor(cursor) {
+            Ok(before) => before,
+            Err(e) => return Ok(e),
+        };
+        let task_limit = limit.unwrap_or(50).max(0) as u32;
+
+        // Ask for one task past the page so we know whether another page follows
+        let query = TaskQuery {
+            project_id,
+            status: status_filter,
+            limit: Some(task_limit.saturating_add(1)),
+            before,
+        };
+        let url = self.url("/api/tasks");
+        let mut tasks: Vec<TaskWithAttemptStatus> =
+            match self.send_json(self.client.get(&url).query(&query)).await {
+                Ok(t) => t,
+                Err(e) => return Ok(e),
+            };
+        let next_cursor = Self::finish_page(&mut tasks, task_limit, |t| {
+            PageCursor::new(t.created_at, t.id)
+        });
+
+        let task_summaries: Vec<TaskSummary> = tasks
+            .into_iter()
+            .map(TaskSummary::from_task_with_status)
+            .collect();
+
+        let response = ListTasksResponse {
+            count: task_summaries.len(),
+            tasks: task_summaries,
+            project_id: project_id.to_string(),
+            applied_filters: ListTasksFilters {
+                status: status.clone(),
+                limit: task_limit as i32,
+            },
+            next_cursor,
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(description = "Start working on a task by creating and launching a new task attempt.")]
+    async fn start_task_attempt(
+        &self,
+        Parameters(StartTaskAttemptRequest {
+            task_id,
+            executor,
+            variant,
+            base_branch,
+        }): Parameters<StartTaskAttemptRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let executor_profile_id = match Self::executor_profile(&executor, variant) {
+            Ok(profile) => profile,
+            Err(e) => return Ok(e),
+        };
+
+        let task = match self.resolve_task(&task_id).await {
+            Ok(task) => task,
+            Err(e) => return Ok(e),
+        };
+
+        let base_branch = match base_branch
+            .map(|b| b.trim().to_string())
+            .filter(|b| !b.is_empty())
+        {
+            Some(branch) => branch,
+            None => match self.default_branch(task.project_id).await {
+                Ok(branch) => branch,
+                Err(e) => return Ok(e),
+            },
+        };
+
+        let payload = CreateTaskAttemptBody {
+            task_id: task.id,
+            executor_profile_id,
+            base_branch,
+            use_worktree: None, // Default to worktree execution
+        };
+
+        let url = self.url("/api/task-attempts");
+        let attempt: TaskAttempt = match self.send_json(self.client.post(&url).json(&payload)).await
+        {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+
+        let (executor, variant) = attempt.executor_and_variant();
+        let response = StartTaskAttemptResponse {
+            task_id: attempt.task_id.to_string(),
+            attempt_id: attempt.id.to_string(),
+            executor: executor.to_string(),
+            variant: variant.map(str::to_string),
+            attempts_count: self.attempts_count(attempt.task_id).await,
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Send a follow-up message to an existing task attempt so its coding agent continues working. Fails with ATTEMPT_NOT_FOUND or ATTEMPT_TERMINAL when the attempt cannot be continued."
+    )]
+    async fn continue_attempt(
+        &self,
+        Parameters(ContinueAttemptRequest {
+            attempt_id,
+            project_id,
+            prompt,
+            variant,
+        }): Parameters<ContinueAttemptRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if prompt.trim().is_empty() {
+            return Self::err("Prompt must not be empty.".to_string(), None::<String>);
+        }
+
+        let attempt = match self.resolve_attempt(&attempt_id).await {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+        let task = match self.resolve_task(&attempt.task_id.to_string()).await {
+            Ok(task) => task,
+            Err(e) => return Ok(e),
+        };
+
+        if let Some(project_id) = project_id
+            && task.project_id != project_id
+        {
+            return Ok(Self::err_code(
+                ATTEMPT_NOT_FOUND,
+                format!("Task attempt {} does not belong to project {project_id}", attempt.id),
+            ));
+        }
+
+        if matches!(
+            task.status,
+            TaskStatus::Done | TaskStatus::Cancelled | TaskStatus::Archived
+        ) {
+            return Ok(Self::err_code(
+                ATTEMPT_TERMINAL,
+                format!(
+                    "Task attempt {} cannot be continued: its task is {}",
+                    attempt.id, task.status
+                ),
+            ));
+        }
+
+        let url = self.url(&format!("/api/task-attempts/{}/follow-up", attempt.id));
+        let process: ExecutionProcess = match self
+            .send_json(
+                self.client
+                    .post(&url)
+                    .json(&serde_json::json!({ "prompt": prompt, "variant": variant })),
+            )
+            .await
+        {
+            Ok(process) => process,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&ContinueAttemptResponse {
+            attempt_id: attempt.id.to_string(),
+            execution_process_id: process.id.to_string(),
+        })
+    }
+
+    #[tool(
+        description = "Update an existing task/ticket's title, description, or status. `project_id` and `task_id` are required! `title`, `description`, and `status` are optional."
+    )]
+    async fn update_task(
+        &self,
+        Parameters(UpdateTaskRequest {
+            task_id,
+            title,
+            description,
+            status,
+            updated_at,
+        }): Parameters<UpdateTaskRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let status = if let Some(ref status_str) = status {
+            match TaskStatus::from_str(status_str) {
+                Ok(s) => Some(s),
+                Err(_) => {
+                    return Self::err(
+                        "Invalid status filter. Valid values: 'todo', 'in-progress', 'in-review', 'done', 'cancelled'".to_string(),
+                        Some(status_str.to_string()),
+                    );
+                }
+            }
+        } else {
+            None
+        };
+
+        let updated_at = match updated_at
+            .as_deref()
+            .map(chrono::DateTime::parse_from_rfc3339)
+            .transpose()
+        {
+            Ok(updated_at) => updated_at.map(|t| t.with_timezone(&chrono::Utc)),
+            Err(e) => {
+                return Self::err(
+                    "Invalid updated_at; expected an RFC 3339 timestamp".to_string(),
+                    Some(e.to_string()),
+                );
+            }
+        };
+
+        let payload = UpdateTask {
+            title,
+            description,
+            status,
+            parent_task_attempt: None,
+            image_ids: None,
+            updated_at,
+        };
+        let url = self.url(&format!("/api/tasks/{}", task_id));
+        let updated_task: Task = match self.send_json(self.client.put(&url).json(&payload)).await {
+            Ok(t) => t,
+            Err(e) => return Ok(e),
+        };
+
+        let details = self.task_details(updated_task).await;
+        let repsonse = UpdateTaskResponse { task: details };
+        TaskServer::success(&repsonse)
+    }
+
+    #[tool(
+        description = "Delete a task/ticket from a project. `project_id` and `task_id` are required!"
+    )]
+    async fn delete_task(
+        &self,
+        Parameters(DeleteTaskRequest { task_id }): Parameters<DeleteTaskRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let task = match self.resolve_task(&task_id).await {
+            Ok(task) => task,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!("/api/tasks/{}", task.id));
+        if let Err(e) = self
+            .send_json::<serde_json::Value>(self.client.delete(&url))
+            .await
+        {
+            return Ok(e);
+        }
+
+        let repsonse = DeleteTaskResponse {
+            deleted_task_id: Some(task.id.to_string()),
+        };
+
+        TaskServer::success(&repsonse)
+    }
+
+    #[tool(
+        description = "Search tasks across projects by text in their titles and descriptions. Returns the best matches first with the matched text highlighted. Use it when you don't know a task's exact title or id."
+    )]
+    async fn search(
+        &self,
+        Parameters(SearchTasksRequest {
+            query,
+            project,
+            status,
+        }): Parameters<SearchTasksRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let query = query.trim().to_string();
+        if query.is_empty() {
+            return Self::err("Search query cannot be empty".to_string(), None);
+        }
+        let status_filter = match status.as_deref().map(TaskStatus::from_str) {
+            Some(Ok(status)) => Some(status),
+            Some(Err(_)) => {
+                return Self::err(
+                    "Invalid status filter. Valid values: 'todo', 'in-progress', 'in-review', 'done', 'cancelled'".to_string(),
+                    status,
+                );
+            }
+            None => None,
+        };
+        let project_id = match project {
+            Some(project_ref) => match self.resolve_project(&project_ref).await {
+                Ok(project) => Some(project.id),
+                Err(e) => return Ok(e),
+            },
+            None => None,
+        };
+
+        let params = TaskSearchQuery {
+            q: query.clone(),
+            project_id,
+            status: status_filter,
+            limit: Some(SEARCH_TOOL_LIMIT),
+        };
+        let url = self.url("/api/tasks/search");
+        let results: TaskSearchResults =
+            match self.send_json(self.client.get(&url).query(&params)).await {
+                Ok(results) => results,
+                Err(e) => return Ok(e),
+            };
+
+        let next_steps = match results.hits.first() {
+            Some(best) => vec![
+                format!(
+                    "Call `get_task` with task_id '{}' to read the best match",
+                    best.task.id
+                ),
+                format!(
+                    "Call `start_task_attempt` with task_id '{}' to start working on it",
+                    best.task.id
+                ),
+            ],
+            None => vec![
+                "Try a shorter query, or drop the `project` and `status` filters".to_string(),
+                "Call `list_tasks` to browse a project's tasks".to_string(),
+            ],
+        };
+        let matches: Vec<SearchTaskMatch> = results
+            .hits
+            .into_iter()
+            .map(|hit| SearchTaskMatch {
+                project_id: hit.task.project_id.to_string(),
+                matched_field: hit.matched_field,
+                highlight: hit.highlight,
+                score: hit.score,
+                task: TaskSummary::from_task(hit.task),
+            })
+            .collect();
+
+        TaskServer::success(&SearchTasksResponse {
+            query,
+            count: matches.len(),
+            matches,
+            total_matches: results.total_matches,
+            next_steps,
+        })
+    }
+
+    #[tool(
+        description = "Get detailed information (like task description) about a specific task/ticket. You can use `list_tasks` to find the `task_ids` of all tasks in a project. `project_id` and `task_id` are required!"
+    )]
+    async fn get_task(
+        &self,
+        Parameters(GetTaskRequest { task_id }): Parameters<GetTaskRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url(&format!("/api/tasks/{}", task_id));
+        let task: Task = match self.send_json(self.client.get(&url)).await {
+            Ok(t) => t,
+            Err(e) => return Ok(e),
+        };
+
+        let details = self.task_details(task).await;
+        let response = GetTaskResponse { task: details };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Get the git branch status of a task attempt: commits ahead/behind its target and remote, uncommitted changes, and any in-progress rebase or merge conflicts."
+    )]
+    async fn get_branch_status(
+        &self,
+        Parameters(GetBranchStatusRequest { attempt_id }): Parameters<GetBranchStatusRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url(&format!("/api/task-attempts/{}/branch-status", attempt_id));
+        let status: BranchStatus = match self.send_json(self.client.get(&url)).await {
+            Ok(s) => s,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&status)
+    }
+
+    #[tool(
+        description = "List the commits a task attempt's branch has that its target branch doesn't (SHA, subject, author, time), newest first. Use this to review an attempt's history before merging. Results are paged: pass the returned `next_cursor` as `cursor` to fetch the next page."
+    )]
+    async fn list_attempt_commits(
+        &self,
+        Parameters(AttemptCommitsRequest {
+            attempt_id,
+            limit,
+            offset,
+            cursor,
+        }): Parameters<AttemptCommitsRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let attempt = match self.resolve_attempt(&attempt_id).await {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+        let start = match Self::page_start(offset, cursor.as_deref()) {
+            Ok(start) => start,
+            Err(e) => return Ok(e),
+        };
+        let commit_limit = limit.unwrap_or(50).min(100);
+
+        let url = self.url(&format!("/api/task-attempts/{}/commits", attempt.id));
+        let page: AttemptCommits = match self
+            .send_json(
+                self.client
+                    .get(&url)
+                    .query(&[("limit", commit_limit), ("offset", start)]),
+            )
+            .await
+        {
+            Ok(page) => page,
+            Err(e) => return Ok(e),
+        };
+        let end = start as usize + page.commits.len();
+        let next_cursor = (!page.commits.is_empty() && end < page.total).then(|| end.to_string());
+
+        TaskServer::success(&AttemptCommitsResponse {
+            attempt_id: attempt.id.to_string(),
+            target_branch: page.target_branch_name,
+            commits: page.commits,
+            total: page.total,
+            next_cursor,
+        })
+    }
+
+    #[tool(
+        description = "Get a task attempt and its current status: 'pending', 'running', 'completed', 'failed', 'stopped' (killed by the user) or 'merged'."
+    )]
+    async fn get_task_attempt(
+        &self,
+        Parameters(GetTaskAttemptRequest { attempt_id }): Parameters<GetTaskAttemptRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let attempt = match self.resolve_attempt(&attempt_id.to_string()).await {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+        let status = match self.attempt_status(attempt.id).await {
+            Ok(status) => status,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&GetTaskAttemptResponse {
+            task_attempt: TaskAttemptSummary::from_task_attempt(attempt, status),
+        })
+    }
+
+    #[tool(
+        description = "List task attempts newest first, optionally only those of one task. Results are paged: pass the returned `next_cursor` as `cursor` to fetch the next page."
+    )]
+    async fn list_task_attempts(
+        &self,
+        Parameters(ListTaskAttemptsRequest {
+            task_id,
+            limit,
+            cursor,
+        }): Parameters<ListTaskAttemptsRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let task_id = match task_id {
+            Some(task_ref) => match self.resolve_task(&task_ref).await {
+                Ok(task) => Some(task.id),
+                Err(e) => return Ok(e),
+            },
+            None => None,
+        };
+        let before = match Self::page_cursor(cursor) {
+            Ok(before) => before,
+            Err(e) => return Ok(e),
+        };
+        let attempt_limit = limit.unwrap_or(20);
+
+        let query = TaskAttemptQuery {
+            task_id,
+            limit: Some(attempt_limit.saturating_add(1)),
+            before,
+        };
+        let url = self.url("/api/task-attempts");
+        let mut attempts: Vec<TaskAttemptListItem> =
+            match self.send_json(self.client.get(&url).query(&query)).await {
+                Ok(attempts) => attempts,
+                Err(e) => return Ok(e),
+            };
+        let next_cursor = Self::finish_page(&mut attempts, attempt_limit, |item| {
+            PageCursor::new(item.attempt.created_at, item.attempt.id)
+        });
+
+        // The listing carries each attempt's process and merge state, so no request per attempt
+        let summaries: Vec<TaskAttemptSummary> = attempts
+            .into_iter()
+            .map(|item| {
+                let status =
+                    AttemptStatus::from_latest(item.latest_process_status.as_ref(), item.merged);
+                TaskAttemptSummary::from_task_attempt(item.attempt, status)
+            })
+            .collect();
+
+        TaskServer::success(&ListTaskAttemptsResponse {
+            count: summaries.len(),
+            attempts: summaries,
+            applied_filters: TaskAttemptFilters {
+                task_id,
+                limit: attempt_limit,
+            },
+            next_cursor,
+        })
+    }
+
+    #[tool(
+        description = "Show what a task attempt changed compared to its target branch: per-file additions/deletions and a unified patch capped at 64KB (`truncated` is set when files were left out). Pass `path` to limit the diff to a file or directory."
+    )]
+    async fn get_attempt_diff(
+        &self,
+        Parameters(GetAttemptDiffRequest { attempt_id, path }): Parameters<GetAttemptDiffRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let attempt = match self.resolve_attempt(&attempt_id).await {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!("/api/task-attempts/{}/diff", attempt.id));
+        let mut request = self.client.get(&url);
+        if let Some(path) = path.as_deref().filter(|p| !p.trim().is_empty()) {
+            request = request.query(&[("path", path)]);
+        }
+        let diff: DiffResult = match self.send_json(request).await {
+            Ok(diff) => diff,
+            Err(e) => return Ok(e),
+        };
+
+        let mut next_steps = vec![
+            format!(
+                "Merge the changes from the Forge UI, or POST /api/task-attempts/{}/merge",
+                attempt.id
+            ),
+            "Call `continue_attempt` with feedback to have the agent revise the changes"
+                .to_string(),
+        ];
+        if diff.truncated {
+            next_steps.push(
+                "Call `get_attempt_diff` again with `path` to see files left out of the patch"
+                    .to_string(),
+            );
+        }
+
+        TaskServer::success(&GetAttemptDiffResponse {
+            attempt_id: attempt.id.to_string(),
+            diff,
+            next_steps,
+        })
+    }
+
+    #[tool(
+        description = "Start a new attempt for the same task whose branch begins at another attempt's latest commit, or at `from_commit` on that attempt's branch, instead of at the base branch. The new attempt reuses the source attempt's executor profile and target branch."
+    )]
+    async fn fork(
+        &self,
+        Parameters(ForkAttemptRequest {
+            attempt_id,
+            from_commit,
+        }): Parameters<ForkAttemptRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let source = match self.resolve_attempt(&attempt_id).await {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!("/api/task-attempts/{}/fork", source.id));
+        let payload = ForkTaskAttemptBody {
+            from_commit: from_commit.filter(|commit| !commit.trim().is_empty()),
+        };
+        let attempt: TaskAttempt = match self.send_json(self.client.post(&url).json(&payload)).await
+        {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&ForkAttemptResponse {
+            task_id: attempt.task_id.to_string(),
+            source_attempt_id: source.id.to_string(),
+            attempt_id: attempt.id.to_string(),
+            branch: attempt.branch,
+            target_branch: attempt.target_branch,
+            from_commit: payload.from_commit,
+            next_steps: vec![
+                "Call `logs` to follow the new attempt's agent".to_string(),
+                format!(
+                    "Call `continue_attempt` on attempt {} to steer it",
+                    attempt.id
+                ),
+            ],
+        })
+    }
+
+    #[tool(
+        description = "Show the last log entries (default 100) of a task attempt's newest process, or the one given by `process_id`: the coding agent's normalized conversation (messages, tool calls, errors), or a script's stdout/stderr lines. `running` tells whether the process is still producing output; use this to debug a stuck or failed attempt."
+    )]
+    async fn logs(
+        &self,
+        Parameters(AttemptLogsRequest {
+            attempt_id,
+            tail,
+            process_id,
+        }): Parameters<AttemptLogsRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let attempt = match self.resolve_attempt(&attempt_id).await {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!(
+            "/api/execution-processes?task_attempt_id={}",
+            attempt.id
+        ));
+        let processes: Vec<ExecutionProcess> = match self.send_json(self.client.get(&url)).await {
+            Ok(processes) => processes,
+            Err(e) => return Ok(e),
+        };
+        let process = match process_id.as_deref().map(str::trim) {
+            Some(process_id) => {
+                let Some(process) = processes
+                    .into_iter()
+                    .find(|p| p.id.to_string() == process_id)
+                else {
+                    return Self::err(
+                        "The attempt has no process with that id",
+                        Some(&format!(
+                            "attempt_id: {}, process_id: {process_id}",
+                            attempt.id
+                        )),
+                    );
+                };
+                process
+            }
+            None => {
+                let Some(process) = processes
+                    .into_iter()
+                    .filter(|p| !matches!(p.run_reason, ExecutionProcessRunReason::DevServer))
+                    .max_by_key(|p| p.started_at)
+                else {
+                    return Self::err(
+                        "The attempt has not started any process yet",
+                        Some(&format!("attempt_id: {}", attempt.id)),
+                    );
+                };
+                process
+            }
+        };
+
+        let url = self.url(&format!("/api/execution-processes/{}/logs", process.id));
+        let logs: LogTail = match self
+            .send_json(self.client.get(&url).query(&LogTailQuery { tail }))
+            .await
+        {
+            Ok(logs) => logs,
+            Err(e) => return Ok(e),
+        };
+
+        let next_steps = if logs.running {
+            vec!["Call `logs` again to see newer output".to_string()]
+        } else {
+            vec![
+                "Call `continue_attempt` to send the agent a follow-up".to_string(),
+                "Call `get_attempt_diff` to review what the attempt changed".to_string(),
+            ]
+        };
+
+        TaskServer::success(&AttemptLogsResponse {
+            attempt_id: attempt.id.to_string(),
+            process_id: process.id.to_string(),
+            run_reason: process.run_reason,
+            status: process.status,
+            logs,
+            next_steps,
+        })
+    }
+
+    #[tool(
+        description = "List every process a task attempt has run (setup script, coding agent, cleanup script, dev server) with its status, start/end time and exit code. Use this to see which step of an attempt is stuck or failed, then `logs` with its `process_id`."
+    )]
+    async fn processes(
+        &self,
+        Parameters(AttemptProcessesRequest { attempt_id }): Parameters<AttemptProcessesRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let attempt = match self.resolve_attempt(&attempt_id).await {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!(
+            "/api/execution-processes?attempt_id={}",
+            attempt.id
+        ));
+        let mut processes: Vec<ExecutionProcess> = match self.send_json(self.client.get(&url)).await
+        {
+            Ok(processes) => processes,
+            Err(e) => return Ok(e),
+        };
+        processes.sort_by_key(|p| p.started_at);
+
+        let label = |process: &ExecutionProcess| match process.run_reason {
+            ExecutionProcessRunReason::SetupScript => "setup script",
+            ExecutionProcessRunReason::CleanupScript => "cleanup script",
+            ExecutionProcessRunReason::CodingAgent => "coding agent",
+            ExecutionProcessRunReason::DevServer => "dev server",
+        };
+        let next_steps = processes
+            .iter()
+            .filter_map(|process| match process.status {
+                ExecutionProcessStatus::Running => Some(format!(
+                    "Call `logs` with process_id {} to follow the running {}",
+                    process.id,
+                    label(process)
+                )),
+                ExecutionProcessStatus::Failed => Some(format!(
+                    "Call `logs` with process_id {} to see why the {} failed",
+                    process.id,
+                    label(process)
+                )),
+                _ => None,
+            })
+            .collect();
+
+        TaskServer::success(&AttemptProcessesResponse {
+            attempt_id: attempt.id.to_string(),
+            processes: processes.iter().map(AttemptProcess::from_process).collect(),
+            next_steps,
+        })
+    }
+
+    #[tool(
+        description = "Stop every running attempt of a task and report the outcome for each one. Attempts that have already finished are left alone."
+    )]
+    async fn stop_task(
+        &self,
+        Parameters(StopTaskRequest { task_id }): Parameters<StopTaskRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let task = match self.resolve_task(&task_id).await {
+            Ok(task) => task,
+            Err(e) => return Ok(e),
+        };
+
+        let query = TaskAttemptQuery {
+            task_id: Some(task.id),
+            limit: None,
+            before: None,
+        };
+        let url = self.url("/api/task-attempts");
+        let attempts: Vec<TaskAttemptListItem> =
+            match self.send_json(self.client.get(&url).query(&query)).await {
+                Ok(attempts) => attempts,
+                Err(e) => return Ok(e),
+            };
+
+        // The listing carries each attempt's process and merge state, so no request per attempt
+        let mut results = Vec::new();
+        for item in attempts {
+            let status =
+                AttemptStatus::from_latest(item.latest_process_status.as_ref(), item.merged);
+            if status != AttemptStatus::Running {
+                continue;
+            }
+            let attempt = item.attempt;
+
+            // Stopping an attempt that has just finished is a no-op, so this is safe to repeat
+            let url = self.url(&format!("/api/task-attempts/{}/stop", attempt.id));
+            let error = self
+                .send_json_retry_safe::<serde_json::Value>(self.client.post(&url))
+                .await
+                .err()
+                .map(|e| Self::error_body(&e));
+            results.push(StoppedAttempt {
+                attempt_id: attempt.id.to_string(),
+                stopped: error.is_none(),
+                error,
+            });
+        }
+
+        let stopped = results.iter().filter(|result| result.stopped).count();
+        TaskServer::success(&StopTaskResponse {
+            task_id: task.id.to_string(),
+            stopped,
+            failed: results.len() - stopped,
+            attempts: results,
+        })
+    }
+
+    #[tool(
+        description = "Rebase a task attempt's branch onto its target branch, or onto `onto` (which then becomes the target). Returns the new commits ahead/behind, or `rebased: false` with the conflicted files when the rebase stops on conflicts."
+    )]
+    async fn rebase_attempt(
+        &self,
+        Parameters(RebaseAttemptRequest { attempt_id, onto }): Parameters<RebaseAttemptRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let attempt = match self.resolve_attempt(&attempt_id).await {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!("/api/task-attempts/{}/rebase", attempt.id));
+        let payload = serde_json::json!({
+            "new_base_branch": onto.filter(|branch| !branch.trim().is_empty()),
+        });
+        let rebase = self
+            .send_json::<()>(self.client.post(&url).json(&payload))
+            .await;
+
+        // Conflicts are reported as a failed rebase; the branch status says which files
+        let url = self.url(&format!("/api/task-attempts/{}/branch-status", attempt.id));
+        let status: BranchStatus = match self.send_json(self.client.get(&url)).await {
+            Ok(status) => status,
+            Err(e) => return Ok(rebase.err().unwrap_or(e)),
+        };
+        let conflicted = status.is_rebase_in_progress || !status.conflicted_files.is_empty();
+        if let Err(e) = rebase
+            && !conflicted
+        {
+            return Ok(e);
+        }
+
+        let next_steps = if conflicted {
+            vec![
+                "Call `continue_attempt` asking the agent to resolve the conflicts and finish the \
+                 rebase"
+                    .to_string(),
+                "Or call `resolve` with action 'abort' to restore the branch".to_string(),
+            ]
+        } else {
+            vec![
+                "Call `get_attempt_diff` to review the rebased changes".to_string(),
+                format!(
+                    "Merge from the Forge UI, or POST /api/task-attempts/{}/merge",
+                    attempt.id
+                ),
+            ]
+        };
+
+        TaskServer::success(&RebaseAttemptResponse {
+            attempt_id: attempt.id.to_string(),
+            rebased: !conflicted,
+            target_branch: status.target_branch_name,
+            commits_ahead: status.commits_ahead,
+            commits_behind: status.commits_behind,
+            conflicted_files: status.conflicted_files,
+            next_steps,
+        })
+    }
+
+    #[tool(
+        description = "Push a task attempt's branch to the remote. Reports the remote commit and whether the push was a fast-forward. A push that would drop remote commits is refused unless `force` is set."
+    )]
+    async fn push_attempt(
+        &self,
+        Parameters(PushAttemptRequest { attempt_id, force }): Parameters<PushAttemptRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let attempt = match self.resolve_attempt(&attempt_id).await {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!("/api/task-attempts/{}/push", attempt.id));
+        let request = self
+            .client
+            .post(&url)
+            .query(&[("force", force.unwrap_or(false))]);
+        let result: PushResult = match self.send_json(request).await {
+            Ok(result) => result,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&PushAttemptResponse {
+            attempt_id: attempt.id.to_string(),
+            branch: attempt.branch,
+            result,
+        })
+    }
+
+    #[tool(
+        description = "Merge a task attempt's branch into its target branch. When the merge would conflict nothing is merged: `has_conflicts` is set and `conflicted_files` lists the files to resolve. An attempt whose branch status already shows unresolved conflicts is refused unless `force` is set."
+    )]
+    async fn merge_attempt(
+        &self,
+        Parameters(MergeTaskAttemptRequest { attempt_id, force }): Parameters<
+            MergeTaskAttemptRequest,
+        >,
+    ) -> Result<CallToolResult, ErrorData> {
+        let attempt = match self.resolve_attempt(&attempt_id).await {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+
+        if !force.unwrap_or(false) {
+            let status_url = self.url(&format!("/api/task-attempts/{}/branch-status", attempt.id));
+            // The merge reports conflicts itself, so a status that can't be read doesn't block it
+            if let Ok(status) = self
+                .send_json::<BranchStatus>(self.client.get(&status_url))
+                .await
+                && (status.is_rebase_in_progress
+                    || status.conflict_op.is_some()
+                    || !status.conflicted_files.is_empty())
+            {
+                return Self::err_value(serde_json::json!({
+                    "success": false,
+                    "code": MERGE_CONFLICTS_PENDING,
+                    "error": "The attempt has unresolved conflicts; nothing was merged",
+                    "attempt_id": attempt.id,
+                    "conflict_op": status.conflict_op,
+                    "conflicted_files": status.conflicted_files,
+                    "next_steps": [
+                        "Resolve the conflicts with `continue_attempt`, then call `resolve` with \
+                         action 'continue'; or call `resolve` with action 'abort'",
+                        "Once the worktree is clean, call `rebase_attempt` to replay the attempt \
+                         onto the target branch and merge again",
+                        "Or call `merge_attempt` with force=true to try the merge anyway",
+                    ],
+                }));
+            }
+        }
+
+        let url = self.url(&format!("/api/task-attempts/{}/merge", attempt.id));
+        let conflict = match self.send_git_operation(self.client.post(&url)).await {
+            Ok(conflict) => conflict,
+            Err(e) => return Ok(e),
+        };
+
+        let mut response = MergeTaskAttemptResponse {
+            success: conflict.is_none(),
+            attempt_id: attempt.id.to_string(),
+            target_branch: attempt.target_branch,
+            has_conflicts: false,
+            conflict_op: None,
+            conflicted_files: Vec::new(),
+            message: None,
+            next_steps: Vec::new(),
+        };
+        match conflict {
+            None => {
+                response
+                    .next_steps
+                    .push("The task was moved to done; call `get_task` to confirm".to_string());
+            }
+            Some(GitOperationError::MergeConflicts {
+                message,
+                op,
+                conflicted_files,
+            }) => {
+                response.has_conflicts = true;
+                response.conflict_op = Some(op);
+                response.conflicted_files = conflicted_files;
+                response.message = Some(message);
+                response.next_steps = vec![
+                    "Call `rebase_attempt` to replay the attempt onto the target branch, then \
+                     `continue_attempt` asking the agent to resolve the conflicts"
+                        .to_string(),
+                    "Or resolve the conflicted files manually in the attempt's worktree, commit, \
+                     and call `merge_attempt` again"
+                        .to_string(),
+                ];
+            }
+            Some(GitOperationError::RebaseInProgress) => {
+                response.message = Some("A rebase is in progress in the attempt's worktree".into());
+                response.next_steps = vec![
+                    "Resolve the conflicts with `continue_attempt`, then call `resolve` with \
+                     action 'continue'; or call `resolve` with action 'abort'"
+                        .to_string(),
+                ];
+            }
+        }
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Abort or continue a rebase or merge that stopped on conflicts in a task attempt's worktree. 'abort' restores the branch to its pre-operation commit; 'continue' finishes the operation once the conflicted files are resolved and staged, or lists the files still in conflict."
+    )]
+    async fn resolve(
+        &self,
+        Parameters(ResolveAttemptRequest { attempt_id, action }): Parameters<ResolveAttemptRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let attempt = match self.resolve_attempt(&attempt_id).await {
+            Ok(attempt) => attempt,
+            Err(e) => return Ok(e),
+        };
+
+        let status_url = self.url(&format!("/api/task-attempts/{}/branch-status", attempt.id));
+        let status: BranchStatus = match self.send_json(self.client.get(&status_url)).await {
+            Ok(status) => status,
+            Err(e) => return Ok(e),
+        };
+        let Some(op) = status.conflict_op else {
+            return TaskServer::err(
+                "No rebase or merge is in progress for this attempt",
+                Some(&format!("attempt_id: {}", attempt.id)),
+            );
+        };
+
+        let mut conflict = None;
+        match action {
+            ResolveAction::Abort => {
+                let path = match op {
+                    ConflictOp::Rebase => "rebase-abort",
+                    ConflictOp::Merge => "merge-abort",
+                    ConflictOp::CherryPick | ConflictOp::Revert => "conflicts/abort",
+                };
+                let url = self.url(&format!("/api/task-attempts/{}/{path}", attempt.id));
+                if let Err(e) = self.send_json::<()>(self.client.post(&url)).await {
+                    return Ok(e);
+                }
+            }
+            ResolveAction::Continue => {
+                let url = self.url(&format!("/api/task-attempts/{}/continue", attempt.id));
+                conflict = match self.send_git_operation(self.client.post(&url)).await {
+                    Ok(conflict) => conflict,
+                    Err(e) => return Ok(e),
+                };
+            }
+        }
+
+        let status: BranchStatus = match self.send_json(self.client.get(&status_url)).await {
+            Ok(status) => status,
+            Err(e) => return Ok(e),
+        };
+        let mut response = ResolveAttemptResponse {
+            attempt_id: attempt.id.to_string(),
+            action,
+            operation: op,
+            resolved: conflict.is_none(),
+            head_oid: status.head_oid,
+            conflicted_files: status.conflicted_files,
+            message: None,
+            next_steps: Vec::new(),
+        };
+        match conflict {
+            Some(GitOperationError::MergeConflicts {
+                message,
+                conflicted_files,
+                ..
+            }) => {
+                response.conflicted_files = conflicted_files;
+                response.message = Some(message);
+                response.next_steps = vec![
+                    "Call `continue_attempt` asking the agent to resolve and stage the conflicted \
+                     files, then call `resolve` with action 'continue' again"
+                        .to_string(),
+                    "Or call `resolve` with action 'abort' to restore the branch".to_string(),
+                ];
+            }
+            Some(GitOperationError::RebaseInProgress) => {
+                response.message = Some("A rebase is still in progress".into());
+            }
+            None => {
+                response
+                    .next_steps
+                    .push("Call `get_attempt_diff` to review the branch".to_string());
+            }
+        }
+
+        TaskServer::success(&response)
+    }
+
+    // =========================================================================
+    // ExecutionRun Tools - Lightweight executor invocation without Task overhead
+    // =========================================================================
+
+    #[tool(
+        description = "Start a lightweight execution run. Unlike task attempts, runs don't require creating a task first. Ideal for serverless micro-tasks like generating commit messages or PR descriptions."
+    )]
+    async fn start_execution_run(
+        &self,
+        Parameters(StartExecutionRunRequest {
+            project_id,
+            prompt,
+            executor,
+            variant,
+            base_branch,
+        }): Parameters<StartExecutionRunRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let executor_profile_id = match Self::executor_profile(&executor, variant) {
+            Ok(profile) => profile,
+            Err(e) => return Ok(e),
+        };
+
+        let payload = ApiCreateExecutionRunRequest {
+            project_id,
+            prompt,
+            executor_profile_id,
+            base_branch,
+        };
+
+        let url = self.url("/api/execution-runs");
+
+        // Response contains { execution_run, execution_process }
+        #[derive(Deserialize)]
+        struct ApiExecutionRunResponse {
+            execution_run: forge_core_db::models::execution_run::ExecutionRun,
+        }
+
+        let resp: ApiExecutionRunResponse =
+            match self.send_json(self.client.post(&url).json(&payload)).await {
+                Ok(r) => r,
+                Err(e) => return Ok(e),
+            };
+
+        let response = StartExecutionRunResponse {
+            execution_run_id: resp.execution_run.id.to_string(),
+            project_id: resp.execution_run.project_id.to_string(),
+            branch: resp.execution_run.branch,
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(description = "List execution runs, optionally filtered by project_id")]
+    async fn list_execution_runs(
+        &self,
+        Parameters(ListExecutionRunsRequest { project_id }): Parameters<ListExecutionRunsRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = match project_id {
+            Some(pid) => self.url(&format!("/api/execution-runs?project_id={}", pid)),
+            None => self.url("/api/execution-runs"),
+        };
+
+        let runs: Vec<forge_core_db::models::execution_run::ExecutionRun> =
+            match self.send_json(self.client.get(&url)).await {
+                Ok(r) => r,
+                Err(e) => return Ok(e),
+            };
+
+        let summaries: Vec<ExecutionRunSummary> = runs
+            .into_iter()
+            .map(|r| ExecutionRunSummary {
+                id: r.id.to_string(),
+                project_id: r.project_id.to_string(),
+                branch: r.branch,
+                target_branch: r.target_branch,
+                executor: r.executor,
+                created_at: r.created_at.to_rfc3339(),
+            })
+            .collect();
+
+        let response = ListExecutionRunsResponse {
+            count: summaries.len(),
+            runs: summaries,
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(description = "Get details of a specific execution run")]
+    async fn get_execution_run(
+        &self,
+        Parameters(GetExecutionRunRequest { execution_run_id }): Parameters<GetExecutionRunRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url(&format!("/api/execution-runs/{}", execution_run_id));
+
+        let run: forge_core_db::models::execution_run::ExecutionRun =
+            match self.send_json(self.client.get(&url)).await {
+                Ok(r) => r,
+                Err(e) => return Ok(e),
+            };
+
+        let response = GetExecutionRunResponse {
+            id: run.id.to_string(),
+            project_id: run.project_id.to_string(),
+            branch: run.branch,
+            target_branch: run.target_branch,
+            executor: run.executor,
+            prompt: run.prompt,
+            created_at: run.created_at.to_rfc3339(),
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(description = "Stop a running execution run")]
+    async fn stop_execution_run(
+        &self,
+        Parameters(StopExecutionRunRequest { execution_run_id }): Parameters<
+            StopExecutionRunRequest,
+        >,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url(&format!("/api/execution-runs/{}/stop", execution_run_id));
+
+        // Stopping an already stopped run is a no-op, so this is safe to repeat
+        if let Err(e) = self
+            .send_json_retry_safe::<serde_json::Value>(self.client.post(&url))
+            .await
+        {
+            return Ok(e);
+        }
+
+        let response = StopExecutionRunResponse {
+            stopped: true,
+            execution_run_id: execution_run_id.to_string(),
+        };
+
+        TaskServer::success(&response)
+    }
+
+    // =========================================================================
+    // Diagnostics
+    // =========================================================================
+
+    #[tool(
+        description = "List the MCP servers configured for each coding agent executor, optionally for a single `executor`. Only server names and their command or URL are returned."
+    )]
+    async fn list_mcp_servers(
+        &self,
+        Parameters(ListMcpServersRequest { executor }): Parameters<ListMcpServersRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let executors: Vec<String> =
+            match executor.as_deref().map(str::trim).filter(|e| !e.is_empty()) {
+                Some(executor) => {
+                    let normalized = executor.replace('-', "_").to_ascii_uppercase();
+                    match BaseCodingAgent::from_str(&normalized) {
+                        Ok(agent) => vec![serde_json::to_value(agent)
+                            .ok()
+                            .and_then(|v| v.as_str().map(str::to_string))
+                            .unwrap_or(normalized)],
+                        Err(_) => {
+                            return Self::err(
+                                format!("Unknown executor '{executor}'."),
+                                None::<String>,
+                            );
+                        }
+                    }
+                }
+                None => {
+                    let info: serde_json::Value =
+                        match self.send_json(self.client.get(self.url("/api/info"))).await {
+                            Ok(info) => info,
+                            Err(e) => return Ok(e),
+                        };
+                    let mut executors: Vec<String> = info["executors"]
+                        .as_object()
+                        .map(|executors| executors.keys().cloned().collect())
+                        .unwrap_or_default();
+                    executors.sort();
+                    executors
+                }
+            };
+
+        let mut infos = Vec::new();
+        for executor in &executors {
+            let url = self.url(&format!("/api/mcp-config?executor={executor}"));
+            // Executors without MCP support answer with an error and are left out
+            if let Ok(config) = self
+                .send_json::<serde_json::Value>(self.client.get(&url))
+                .await
+            {
+                infos.push(McpServerInfo::from_config(executor, &config));
+            }
+        }
+
+        let count = infos.iter().map(|info| info.servers.len()).sum();
+        let message = if infos.is_empty() {
+            Some(format!(
+                "None of the executors ({}) support MCP servers",
+                executors.join(", ")
+            ))
+        } else if count == 0 {
+            Some(
+                "No MCP servers are configured; add them in the Forge settings under MCP Servers"
+                    .to_string(),
+            )
+        } else {
+            None
+        };
+
+        TaskServer::success(&ListMcpServersResponse {
+            executors: infos,
+            count,
+            message,
+        })
+    }
+
+    #[tool(
+        description = "Check that Forge is set up correctly: API reachability, GitHub token, Omni configuration, loaded executor profiles and database health. Each check reports pass/warn/fail with a hint on how to fix it."
+    )]
+    async fn doctor(&self) -> Result<CallToolResult, ErrorData> {
+        let info = self.probe_data("/api/info").await;
+        let checks = vec![
+            self.check_api().await,
+            self.check_github(&info).await,
+            self.check_omni().await,
+            Self::check_profiles(&info),
+            self.check_database().await,
+        ];
+        let status = checks
+            .iter()
+            .map(|check| check.status)
+            .max()
+            .unwrap_or(DoctorCheckStatus::Pass);
+
+        TaskServer::success(&DoctorReport { status, checks })
+    }
+
+    #[tool(
+        description = "Change one global Forge setting and persist it: omni_enabled, review_on_pr, releases_repo or conversational_prefixes. Security settings and credentials can only be changed in Forge itself. Other settings keep their current values. Returns the updated config with secrets redacted."
+    )]
+    async fn set_forge_config(
+        &self,
+        Parameters(SetForgeConfigRequest { key, value }): Parameters<SetForgeConfigRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let key = key.trim();
+        if !SETTABLE_CONFIG_KEYS.contains(&key) {
+            if Self::forge_config_keys().iter().any(|known| known == key) {
+                return Ok(Self::err_code(
+                    PROTECTED_CONFIG_KEY,
+                    format!("'{key}' can only be changed in Forge's settings"),
+                ));
+            }
+            let mut close: Vec<(f64, &str)> = SETTABLE_CONFIG_KEYS
+                .iter()
+                .map(|known| (title_similarity(key, known), *known))
+                .filter(|(similarity, _)| *similarity >= CONFIG_KEY_SUGGESTION_SIMILARITY)
+                .collect();
+            close.sort_by(|a, b| b.0.total_cmp(&a.0));
+            let suggestions: Vec<&str> = if close.is_empty() {
+                SETTABLE_CONFIG_KEYS.to_vec()
+            } else {
+                close.into_iter().map(|(_, known)| known).collect()
+            };
+            return Self::err_value(serde_json::json!({
+                "success": false,
+                "code": UNKNOWN_CONFIG_KEY,
+                "error": format!("'{key}' is not a Forge setting"),
+                "suggestions": suggestions,
+            }));
+        }
+
+        let url = self.url("/api/forge/config");
+        let mut config: serde_json::Value = match self.send_json(self.client.get(&url)).await {
+            Ok(config) => config,
+            Err(e) => return Ok(e),
+        };
+        config[key] = value;
+        if let Err(e) = serde_json::from_value::<ForgeProjectSettings>(config.clone()) {
+            return Ok(Self::err_code(
+                INVALID_CONFIG_VALUE,
+                format!("Invalid value for '{key}': {e}"),
+            ));
+        }
+
+        let updated: ForgeProjectSettings =
+            match self.send_json(self.client.put(&url).json(&config)).await {
+                Ok(updated) => updated,
+                Err(e) => return Ok(e),
+            };
+        TaskServer::success(&Self::redact_forge_config(updated))
+    }
+
+    #[tool(
+        description = "Check that the Forge API this server talks to is live. Returns whether it is reachable, its version, the negotiated MCP protocol version and the round-trip latency, with suggestions when it can't be reached. Call this first if other tools fail to connect."
+    )]
+    async fn health(&self) -> Result<CallToolResult, ErrorData> {
+        let started = Instant::now();
+        let result = self.probe_data("/api/health").await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        let (api_version, error) = match result {
+            Ok(data) => (data["version"].as_str().map(str::to_string), None),
+            Err(e) => (None, Some(e)),
+        };
+        let suggestions = if error.is_some() {
+            vec![
+                "Make sure the Forge server is running".to_string(),
+                format!(
+                    "Check that {} is the Forge API address; set FORGE_BACKEND_URL, or BACKEND_PORT/PORT, when launching the MCP server to change it",
+                    self.base_url
+                ),
+                "Check that no firewall or proxy blocks local connections to that port".to_string(),
+            ]
+        } else {
+            Vec::new()
+        };
+
+        TaskServer::success(&HealthReport {
+            reachable: error.is_none(),
+            base_url: self.base_url.clone(),
+            api_version,
+            protocol_version: self.current_protocol_version().to_string(),
+            supported_protocol_versions: Self::supported_protocol_versions()
+                .iter()
+                .map(|v| v.to_string())
+                .collect(),
+            latency_ms,
+            error,
+            suggestions,
+        })
+    }
+}
+
+/// [`TaskServer::tool_router`] with every call timed, for `#[tool_handler]` to dispatch through
+struct TimedToolRouter<'a> {
+    server: &'a TaskServer,
+}
+
+impl TimedToolRouter<'_> {
+    async fn call(
+        &self,
+        context: ToolCallContext<'_, TaskServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let tool = context.name().to_string();
+        let started = Instant::now();
+        let result = self.server.tool_router.call(context).await;
+        let failed = !matches!(&result, Ok(r) if r.is_error != Some(true));
+        self.server
+            .record_tool_timing(tool, started.elapsed(), failed);
+        result
+    }
+
+    fn list_all(&self) -> Vec<Tool> {
+        self.server.tool_router.list_all()
+    }
+}
+
+#[tool_handler(router = self.timed_tool_router())]
+impl ServerHandler for TaskServer {
+    #[allow(clippy::manual_async_fn)]
+    fn initialize(
+        &self,
+        request: InitializeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> impl Future<Output = Result<ServerInfo, ErrorData>> + Send + '_ {
+        async move {
+            if context.peer.peer_info().is_none() {
+                context.peer.set_peer_info(request.clone());
+            }
+
+            let requested_version = request.protocol_version.clone();
+            let negotiated_version = match Self::negotiate_protocol_version(&requested_version) {
+                Ok(version) => version,
+                Err(error) => return Err(error),
+            };
+
+            Self::log_downgrade_if_needed(&requested_version, &negotiated_version);
+            self.set_negotiated_protocol_version(negotiated_version.clone());
+
+            Ok(self.server_info_for_version(negotiated_version))
+        }
+    }
+
+    /// Returns server info that reflects this session's negotiated protocol version so
+    /// any follow-up responses stay aligned with the handshake.
+    fn get_info(&self) -> ServerInfo {
+        let protocol_version = self.current_protocol_version();
+        self.server_info_for_version(protocol_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+    use axum::{
+        Json, Router,
+        extract::{Path, Query, RawQuery},
+        http::StatusCode,
+        response::IntoResponse,
+        routing::{get, post},
+    };
+    use chrono::Utc;
+    use forge_core_db::models::execution_process::{
+        ExecutionProcessRunReason, ExecutionProcessStatus, ExecutorActionField,
+    };
+    use forge_core_services::services::forge_config::{OmniConfig, WebhookConfig};
+    use forge_core_utils::response::ApiResponse;
+    use rmcp::{
+        RoleClient, ServiceExt,
+        model::{ClientInfo, ErrorCode},
+        service::RunningService,
+    };
+
+    use super::*;
+
+    fn custom_protocol_version(version: &str) -> ProtocolVersion {
+        serde_json::from_str::<ProtocolVersion>(&format!("\"{version}\"")).unwrap()
+    }
+
+    #[test]
+    fn client_requesting_latest_version_receives_latest() {
+        let negotiated =
+            TaskServer::negotiate_protocol_version(&ProtocolVersion::V_2025_03_26).unwrap();
+        assert_eq!(negotiated, ProtocolVersion::V_2025_03_26);
+    }
+
+    #[test]
+    fn client_requesting_older_version_negotiates_down() {
+        let negotiated =
+            TaskServer::negotiate_protocol_version(&ProtocolVersion::V_2024_11_05).unwrap();
+        assert_eq!(negotiated, ProtocolVersion::V_2024_11_05);
+    }
+
+    #[test]
+    fn client_requesting_newer_version_falls_back_to_latest() {
+        let version = custom_protocol_version("2026-01-01");
+        let negotiated = TaskServer::negotiate_protocol_version(&version).unwrap();
+        assert_eq!(negotiated, ProtocolVersion::V_2025_03_26);
+    }
+
+    #[test]
+    fn client_requesting_too_old_version_receives_error() {
+        let version = custom_protocol_version("2023-01-01");
+        let error = TaskServer::negotiate_protocol_version(&version).unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn get_info_reflects_negotiated_version() {
+        let server = TaskServer::new("http://example.com");
+        server.set_negotiated_protocol_version(ProtocolVersion::V_2024_11_05);
+        let info = server.get_info();
+        assert_eq!(info.protocol_version, ProtocolVersion::V_2024_11_05);
+    }
+
+    /// Runs the MCP handshake for `session` against a client requesting `requested`
+    async fn initialize_session(
+        session: TaskServer,
+        requested: ProtocolVersion,
+    ) -> (RunningService<RoleServer, TaskServer>, RunningService<RoleClient, ClientInfo>) {
+        let (server_io, client_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move { session.serve(server_io).await });
+        let client = ClientInfo {
+            protocol_version: requested,
+            ..Default::default()
+        }
+        .serve(client_io)
+        .await
+        .unwrap();
+        (server.await.unwrap().unwrap(), client)
+    }
+
+    #[tokio::test]
+    async fn concurrent_sessions_negotiate_independently() {
+        let server = TaskServer::new("http://example.com");
+        let legacy = server.new_session();
+        let current = server.new_session();
+
+        let (_legacy_server, legacy_client) =
+            initialize_session(legacy.clone(), ProtocolVersion::V_2024_11_05).await;
+        let (_current_server, current_client) =
+            initialize_session(current.clone(), ProtocolVersion::V_2025_03_26).await;
+
+        assert_eq!(
+            legacy_client.peer_info().unwrap().protocol_version,
+            ProtocolVersion::V_2024_11_05
+        );
+        assert_eq!(
+            current_client.peer_info().unwrap().protocol_version,
+            ProtocolVersion::V_2025_03_26
+        );
+        assert_eq!(legacy.get_info().protocol_version, ProtocolVersion::V_2024_11_05);
+        assert_eq!(current.get_info().protocol_version, ProtocolVersion::V_2025_03_26);
+        assert_eq!(server.get_info().protocol_version, TaskServer::latest_supported_protocol());
+    }
+
+    fn test_task(status: TaskStatus) -> Task {
+        Task {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            title: "Add greeting".to_string(),
+            description: None,
+            status,
+            parent_task_attempt: None,
+            dev_server_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn test_attempt(task: &Task) -> TaskAttempt {
+        TaskAttempt {
+            id: Uuid::new_v4(),
+            task_id: task.id,
+            container_ref: None,
+            branch: "forge/add-greeting".to_string(),
+            target_branch: "main".to_string(),
+            executor: "CLAUDE_CODE".to_string(),
+            variant: None,
+            worktree_deleted: false,
+            setup_completed_at: None,
+            input_tokens: None,
+            output_tokens: None,
+            cache_creation_tokens: None,
+            cache_read_tokens: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// `attempt` as `/api/task-attempts` lists it, before any process has run
+    fn list_item(attempt: TaskAttempt) -> TaskAttemptListItem {
+        TaskAttemptListItem {
+            attempt,
+            latest_process_status: None,
+            merged: false,
+        }
+    }
+
+    /// Serves `app` as a stub Forge API on a free local port and returns its base URL
+    async fn serve(app: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}")
+    }
+
+    /// A `TaskServer` whose Forge API is the stub `app`
+    async fn stub_server(app: Router) -> TaskServer {
+        TaskServer::new(&serve(app).await)
+    }
+
+    /// Handler that always answers with `value` as a successful API response
+    fn respond<T>(value: T) -> impl Fn() -> std::future::Ready<Json<ApiResponse<T>>> + Clone
+    where
+        T: Serialize + Clone + Send + Sync + 'static,
+    {
+        move || std::future::ready(Json(ApiResponse::success(value.clone())))
+    }
+
+    /// JSON body of a tool result, which must not be an error
+    fn success_body(result: &CallToolResult) -> serde_json::Value {
+        assert_ne!(result.is_error, Some(true));
+        result_body(result)
+    }
+
+    /// JSON body of a tool result, error or not
+    fn result_body(result: &CallToolResult) -> serde_json::Value {
+        serde_json::from_str(&result.content[0].as_text().unwrap().text).unwrap()
+    }
+
+    fn test_process(
+        run_reason: ExecutionProcessRunReason,
+        status: ExecutionProcessStatus,
+        minutes_ago: i64,
+    ) -> ExecutionProcess {
+        let started_at = Utc::now() - chrono::Duration::minutes(minutes_ago);
+        ExecutionProcess {
+            id: Uuid::new_v4(),
+            task_attempt_id: Some(Uuid::new_v4()),
+            execution_run_id: None,
+            run_reason,
+            executor_action: sqlx::types::Json(ExecutorActionField::Other(serde_json::json!({}))),
+            before_head_commit: None,
+            after_head_commit: None,
+            status,
+            exit_code: None,
+            dropped: false,
+            started_at,
+            completed_at: None,
+            created_at: started_at,
+            updated_at: started_at,
+        }
+    }
+
+    #[test]
+    fn attempt_status_follows_newest_process_and_merge_state() {
+        use ExecutionProcessRunReason::*;
+        use ExecutionProcessStatus::*;
+
+        assert_eq!(AttemptStatus::from_processes(&[], false), AttemptStatus::Pending);
+
+        let setup_then_agent = [
+            test_process(SetupScript, Completed, 10),
+            test_process(CodingAgent, Running, 5),
+        ];
+        assert_eq!(
+            AttemptStatus::from_processes(&setup_then_agent, false),
+            AttemptStatus::Running
+        );
+        // A follow-up running on a merged attempt is still live
+        assert_eq!(
+            AttemptStatus::from_processes(&setup_then_agent, true),
+            AttemptStatus::Running
+        );
+
+        let failed_setup = [test_process(SetupScript, Failed, 3)];
+        assert_eq!(AttemptStatus::from_processes(&failed_setup, false), AttemptStatus::Failed);
+
+        let stopped = [
+            test_process(CodingAgent, Completed, 30),
+            test_process(CodingAgent, Killed, 2),
+        ];
+        assert_eq!(AttemptStatus::from_processes(&stopped, false), AttemptStatus::Stopped);
+
+        // Dev servers don't reflect the agent's work
+        let agent_then_dev_server = [
+            test_process(CodingAgent, Completed, 20),
+            test_process(DevServer, Running, 1),
+        ];
+        assert_eq!(
+            AttemptStatus::from_processes(&agent_then_dev_server, false),
+            AttemptStatus::Completed
+        );
+        assert_eq!(
+            AttemptStatus::from_processes(&agent_then_dev_server, true),
+            AttemptStatus::Merged
+        );
+        assert_eq!(
+            serde_json::to_value(AttemptStatus::Stopped).unwrap(),
+            serde_json::json!("stopped")
+        );
+    }
+
+    #[tokio::test]
+    async fn attempt_status_reads_recorded_merges() {
+        use forge_core_db::models::merge::DirectMerge;
+
+        let attempt_id = Uuid::new_v4();
+        let merge = Merge::Direct(DirectMerge {
+            id: Uuid::new_v4(),
+            task_attempt_id: attempt_id,
+            merge_commit: "abc1234".to_string(),
+            target_branch_name: "main".to_string(),
+            created_at: Utc::now(),
+        });
+        let processes = Router::new().route(
+            "/api/execution-processes",
+            get(|| {
+                let process = test_process(
+                    ExecutionProcessRunReason::CodingAgent,
+                    ExecutionProcessStatus::Completed,
+                    5,
+                );
+                std::future::ready(Json(ApiResponse::success(vec![process])))
+            }),
+        );
+
+        let server = stub_server(
+            processes
+                .clone()
+                .route("/api/task-attempts/{id}/merges", get(respond(vec![merge]))),
+        )
+        .await;
+        assert_eq!(
+            server.attempt_status(attempt_id).await.unwrap(),
+            AttemptStatus::Merged
+        );
+
+        // A merge state that can't be read is an error, not "unmerged"
+        let server = stub_server(processes).await;
+        assert!(server.attempt_status(attempt_id).await.is_err());
+    }
+
+    /// Serves just enough of the Forge API for `continue_attempt`, counting follow-up requests
+    async fn spawn_mock_api(
+        task: Task,
+        attempt: TaskAttempt,
+        follow_ups: Arc<AtomicUsize>,
+    ) -> String {
+        let app = Router::new()
+            .route("/api/tasks/{id}", get(respond(task.clone())))
+            .route(
+                "/api/task-attempts/{id}",
+                get(move |Path(id): Path<String>| {
+                    let attempt = attempt.clone();
+                    async move {
+                        if id == attempt.id.to_string() {
+                            Json(ApiResponse::<TaskAttempt>::success(attempt)).into_response()
+                        } else {
+                            StatusCode::NOT_FOUND.into_response()
+                        }
+                    }
+                }),
+            )
+            .route(
+                "/api/task-attempts/{id}/follow-up",
+                post(move || {
+                    follow_ups.fetch_add(1, AtomicOrdering::SeqCst);
+                    async { StatusCode::INTERNAL_SERVER_ERROR }
+                }),
+            );
+
+        serve(app).await
+    }
+
+    fn error_code(result: &CallToolResult) -> String {
+        assert_eq!(result.is_error, Some(true));
+        result_body(result)["code"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn continue_attempt_rejects_unknown_or_foreign_attempts() {
+        let task = test_task(TaskStatus::InProgress);
+        let attempt = test_attempt(&task);
+        let follow_ups = Arc::new(AtomicUsize::new(0));
+        let base_url = spawn_mock_api(task, attempt.clone(), follow_ups.clone()).await;
+        let server = TaskServer::new(&base_url);
+
+        let missing = server
+            .continue_attempt(Parameters(ContinueAttemptRequest {
+                attempt_id: Uuid::new_v4().to_string(),
+                project_id: None,
+                prompt: "Keep going".to_string(),
+                variant: None,
+            }))
+            .await
+            .unwrap();
+        assert_eq!(error_code(&missing), ATTEMPT_NOT_FOUND);
+
+        let foreign = server
+            .continue_attempt(Parameters(ContinueAttemptRequest {
+                attempt_id: attempt.id.to_string(),
+                project_id: Some(Uuid::new_v4()),
+                prompt: "Keep going".to_string(),
+                variant: None,
+            }))
+            .await
+            .unwrap();
+        assert_eq!(error_code(&foreign), ATTEMPT_NOT_FOUND);
+
+        assert_eq!(follow_ups.load(AtomicOrdering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn continue_attempt_rejects_completed_attempts() {
+        let task = test_task(TaskStatus::Done);
+        let attempt = test_attempt(&task);
+        let project_id = task.project_id;
+        let follow_ups = Arc::new(AtomicUsize::new(0));
+        let base_url = spawn_mock_api(task, attempt.clone(), follow_ups.clone()).await;
+        let server = TaskServer::new(&base_url);
+
+        let result = server
+            .continue_attempt(Parameters(ContinueAttemptRequest {
+                attempt_id: attempt.id.to_string(),
+                project_id: Some(project_id),
+                prompt: "One more thing".to_string(),
+                variant: None,
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(error_code(&result), ATTEMPT_TERMINAL);
+        assert_eq!(follow_ups.load(AtomicOrdering::SeqCst), 0);
+    }
+
+    /// Serves the endpoints used by `doctor` for a setup where GitHub was never connected
+    #[tokio::test]
+    async fn start_task_attempt_defaults_to_project_default_branch() {
+        let task = test_task(TaskStatus::Todo);
+        let attempt = test_attempt(&task);
+        let requested_base = Arc::new(std::sync::Mutex::new(None::<String>));
+        let captured = requested_base.clone();
+        let app = Router::new()
+            .route("/api/tasks/{id}", get(respond(task.clone())))
+            .route("/api/projects/{id}/default-branch", get(respond("master".to_string())))
+            .route(
+                "/api/task-attempts",
+                post(move |Json(body): Json<serde_json::Value>| {
+                    *captured.lock().unwrap() = body["base_branch"].as_str().map(str::to_string);
+                    let attempt = attempt.clone();
+                    async move { Json(ApiResponse::<TaskAttempt>::success(attempt)) }
+                }),
+            );
+        let server = stub_server(app).await;
+
+        let result = server
+            .start_task_attempt(Parameters(StartTaskAttemptRequest {
+                task_id: Uuid::new_v4().to_string(),
+                executor: "claude-code".to_string(),
+                variant: None,
+                base_branch: None,
+            }))
+            .await
+            .unwrap();
+        assert_ne!(result.is_error, Some(true));
+        assert_eq!(requested_base.lock().unwrap().as_deref(), Some("master"));
+
+        // An explicit base branch is passed through untouched
+        server
+            .start_task_attempt(Parameters(StartTaskAttemptRequest {
+                task_id: Uuid::new_v4().to_string(),
+                executor: "claude-code".to_string(),
+                variant: None,
+                base_branch: Some("develop".to_string()),
+            }))
+            .await
+            .unwrap();
+        assert_eq!(requested_base.lock().unwrap().as_deref(), Some("develop"));
+    }
+
+    #[tokio::test]
+    async fn list_tasks_follows_cursors_across_pages() {
+        let tasks: Vec<TaskWithAttemptStatus> = (0..120)
+            .map(|_| TaskWithAttemptStatus {
+                task: test_task(TaskStatus::Todo),
+                has_in_progress_attempt: false,
+                has_merged_attempt: false,
+                last_attempt_failed: false,
+                executor: "CLAUDE_CODE".to_string(),
+                attempt_count: 0,
+            })
+            .collect();
+        let tasks = Arc::new(std::sync::Mutex::new(tasks));
+        let newest_first = |tasks: &mut Vec<TaskWithAttemptStatus>| {
+            tasks.sort_by(|a, b| (b.created_at, b.id).cmp(&(a.created_at, a.id)))
+        };
+        newest_first(&mut tasks.lock().unwrap());
+        let expected: Vec<String> = tasks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|t| t.id.to_string())
+            .collect();
+        let listed = tasks.clone();
+        let app = Router::new().route(
+            "/api/tasks",
+            get(move |Query(query): Query<TaskQuery>| {
+                let before = query
+                    .before
+                    .as_deref()
+                    .map(|c| PageCursor::parse(c).unwrap());
+                let page: Vec<TaskWithAttemptStatus> = listed
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|t| before.is_none_or(|b| (t.created_at, t.id) < (b.created_at, b.id)))
+                    .take(query.limit.map_or(usize::MAX, |limit| limit as usize))
+                    .cloned()
+                    .collect();
+                async move { Json(ApiResponse::<Vec<TaskWithAttemptStatus>>::success(page)) }
+            }),
+        );
+        let server = stub_server(app).await;
+        let list = |cursor: Option<String>| {
+            server.list_tasks(Parameters(ListTasksRequest {
+                project_id: Uuid::new_v4(),
+                status: None,
+                limit: Some(50),
+                cursor,
+            }))
+        };
+
+        let mut seen = Vec::new();
+        let mut page_sizes = Vec::new();
+        let mut cursor = None;
+        loop {
+            let result = list(cursor).await.unwrap();
+            let body = success_body(&result);
+            let ids = body["tasks"].as_array().unwrap();
+            page_sizes.push(ids.len());
+            seen.extend(ids.iter().map(|t| t["id"].as_str().unwrap().to_string()));
+            cursor = body["next_cursor"].as_str().map(str::to_string);
+            if cursor.is_none() {
+                break;
+            }
+
+            // A task created between two calls is newer than the cursor and shifts nothing
+            let mut tasks = tasks.lock().unwrap();
+            let newer = TaskWithAttemptStatus {
+                task: Task {
+                    created_at: Utc::now() + chrono::Duration::days(1),
+                    ..test_task(TaskStatus::Todo)
+                },
+                ..tasks[0].clone()
+            };
+            tasks.push(newer);
+            newest_first(&mut tasks);
+        }
+        assert_eq!(page_sizes, vec![50, 50, 20]);
+        assert_eq!(seen, expected);
+
+        let invalid = list(Some("not-a-cursor".to_string())).await.unwrap();
+        assert_eq!(error_code(&invalid), INVALID_CURSOR);
     }
 
-    #[tool(description = "List all the available projects")]
-    async fn list_projects(&self) -> Result<CallToolResult, ErrorData> {
-        let url = self.url("/api/projects");
-        let projects: Vec<Project> = match self.send_json(self.client.get(&url)).await {
-            Ok(ps) => ps,
-            Err(e) => return Ok(e),
+    #[tokio::test]
+    async fn list_task_attempts_reads_status_from_the_listing() {
+        let task = test_task(TaskStatus::InProgress);
+        let running = TaskAttemptListItem {
+            latest_process_status: Some(ExecutionProcessStatus::Running),
+            ..list_item(test_attempt(&task))
         };
-
-        let project_summaries: Vec<ProjectSummary> = projects
-            .into_iter()
-            .map(ProjectSummary::from_project)
-            .collect();
-
-        let response = ListProjectsResponse {
-            count: project_summaries.len(),
-            projects: project_summaries,
+        let merged = TaskAttemptListItem {
+            latest_process_status: Some(ExecutionProcessStatus::Completed),
+            merged: true,
+            ..list_item(test_attempt(&task))
+        };
+        let pending = list_item(test_attempt(&task));
+        let listing = vec![running.clone(), merged.clone(), pending.clone()];
+        let queries = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = queries.clone();
+        // No per-attempt process or merge routes: the listing must be enough
+        let app = Router::new().route(
+            "/api/task-attempts",
+            get(move |Query(query): Query<HashMap<String, String>>| {
+                recorded.lock().unwrap().push(query.clone());
+                let skip = usize::from(query.contains_key("before")) * 2;
+                let page: Vec<TaskAttemptListItem> = listing
+                    .iter()
+                    .skip(skip)
+                    .take(query["limit"].parse().unwrap())
+                    .cloned()
+                    .collect();
+                async move { Json(ApiResponse::success(page)) }
+            }),
+        );
+        let server = stub_server(app).await;
+        let list = |cursor: Option<String>| {
+            server.list_task_attempts(Parameters(ListTaskAttemptsRequest {
+                task_id: None,
+                limit: Some(2),
+                cursor,
+            }))
         };
+        let first = success_body(&list(None).await.unwrap());
+        let statuses: Vec<&str> = first["attempts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|attempt| attempt["status"].as_str().unwrap())
+            .collect();
+        assert_eq!(statuses, ["running", "merged"]);
+        let cursor = first["next_cursor"].as_str().unwrap().to_string();
+        assert_eq!(
+            PageCursor::parse(&cursor),
+            Some(PageCursor::new(
+                merged.attempt.created_at,
+                merged.attempt.id
+            ))
+        );
 
-        TaskServer::success(&response)
+        let second = success_body(&list(Some(cursor.clone())).await.unwrap());
+        assert_eq!(second["attempts"][0]["id"], pending.attempt.id.to_string());
+        assert_eq!(second["attempts"][0]["status"], "pending");
+        assert!(second["next_cursor"].is_null());
+        assert_eq!(queries.lock().unwrap()[1]["before"], cursor);
     }
 
-    #[tool(
-        description = "List all the task/tickets in a project with optional filtering and execution status. `project_id` is required!"
-    )]
-    async fn list_tasks(
-        &self,
-        Parameters(ListTasksRequest {
-            project_id,
-            status,
-            limit,
-        }): Parameters<ListTasksRequest>,
-    ) -> Result<CallToolResult, ErrorData> {
-        let status_filter = if let Some(ref status_str) = status {
-            match TaskStatus::from_str(status_str) {
-                Ok(s) => Some(s),
-                Err(_) => {
-                    return Self::err(
-                        "Invalid status filter. Valid values: 'todo', 'in-progress', 'in-review', 'done', 'cancelled'".to_string(),
-                        Some(status_str.to_string()),
-                    );
-                }
-            }
-        } else {
-            None
-        };
+    #[tokio::test]
+    async fn task_details_count_started_attempts() {
+        let task = test_task(TaskStatus::Todo);
+        let attempts = Arc::new(std::sync::Mutex::new(Vec::<TaskAttempt>::new()));
+        let (listed, started) = (attempts.clone(), attempts.clone());
+        let started_task = task.clone();
+        let app = Router::new()
+            .route("/api/tasks/{id}", get(respond(task.clone())))
+            .route(
+                "/api/task-attempts",
+                get(move || {
+                    let attempts = listed.lock().unwrap().clone();
+                    async move { Json(ApiResponse::<Vec<TaskAttempt>>::success(attempts)) }
+                })
+                .post(move || {
+                    let attempt = test_attempt(&started_task);
+                    started.lock().unwrap().push(attempt.clone());
+                    async move { Json(ApiResponse::<TaskAttempt>::success(attempt)) }
+                }),
+            );
+        let server = stub_server(app).await;
 
-        let url = self.url(&format!("/api/tasks?project_id={}", project_id));
-        let all_tasks: Vec<TaskWithAttemptStatus> =
-            match self.send_json(self.client.get(&url)).await {
-                Ok(t) => t,
-                Err(e) => return Ok(e),
-            };
+        let before = success_body(
+            &server
+                .get_task(Parameters(GetTaskRequest {
+                    task_id: task.id.to_string(),
+                }))
+                .await
+                .unwrap(),
+        );
+        assert_eq!(before["task"]["attempts_count"], 0);
 
-        let task_limit = limit.unwrap_or(50).max(0) as usize;
-        let filtered = all_tasks.into_iter().filter(|t| {
-            if let Some(ref want) = status_filter {
-                &t.status == want
-            } else {
-                true
-            }
-        });
-        let limited: Vec<TaskWithAttemptStatus> = filtered.take(task_limit).collect();
+        for expected in 1..=2 {
+            let started = success_body(
+                &server
+                    .start_task_attempt(Parameters(StartTaskAttemptRequest {
+                        task_id: task.id.to_string(),
+                        executor: "CLAUDE_CODE".to_string(),
+                        variant: None,
+                        base_branch: Some("main".to_string()),
+                    }))
+                    .await
+                    .unwrap(),
+            );
+            assert_eq!(started["attempts_count"], expected);
+        }
 
-        let task_summaries: Vec<TaskSummary> = limited
-            .into_iter()
-            .map(TaskSummary::from_task_with_status)
-            .collect();
+        let after = success_body(
+            &server
+                .get_task(Parameters(GetTaskRequest {
+                    task_id: task.id.to_string(),
+                }))
+                .await
+                .unwrap(),
+        );
+        assert_eq!(after["task"]["attempts_count"], 2);
+    }
 
-        let response = ListTasksResponse {
-            count: task_summaries.len(),
-            tasks: task_summaries,
-            project_id: project_id.to_string(),
-            applied_filters: ListTasksFilters {
-                status: status.clone(),
-                limit: task_limit as i32,
+    #[tokio::test]
+    async fn task_details_link_subtasks_to_their_parent() {
+        let parent = test_task(TaskStatus::InProgress);
+        let parent_attempt = test_attempt(&parent);
+        let child = Task {
+            parent_task_attempt: Some(parent_attempt.id),
+            ..test_task(TaskStatus::Todo)
+        };
+        let tasks = HashMap::from([(parent.id, parent.clone()), (child.id, child.clone())]);
+        let (attempt, listed, relationships) = (
+            parent_attempt.clone(),
+            parent_attempt.clone(),
+            TaskRelationships {
+                parent_task: Some(parent.clone()),
+                current_attempt: parent_attempt.clone(),
+                children: vec![child.clone()],
             },
+        );
+        let app = Router::new()
+            .route(
+                "/api/tasks/{id}",
+                get(move |Path(id): Path<Uuid>| {
+                    let task = tasks[&id].clone();
+                    async move { Json(ApiResponse::<Task>::success(task)) }
+                }),
+            )
+            .route(
+                "/api/task-attempts",
+                get(move |Query(query): Query<HashMap<String, String>>| {
+                    let attempts = if query["task_id"] == listed.task_id.to_string() {
+                        vec![listed.clone()]
+                    } else {
+                        Vec::new()
+                    };
+                    async move { Json(ApiResponse::<Vec<TaskAttempt>>::success(attempts)) }
+                }),
+            )
+            .route("/api/task-attempts/{id}", get(respond(attempt.clone())))
+            .route("/api/task-attempts/{id}/children", get(respond(relationships.clone())));
+        let server = stub_server(app).await;
+        let details = |task: &Task| {
+            let task_id = task.id.to_string();
+            let server = server.clone();
+            async move {
+                let result = server
+                    .get_task(Parameters(GetTaskRequest { task_id }))
+                    .await
+                    .unwrap();
+                let body = success_body(&result);
+                body["task"].clone()
+            }
         };
 
-        TaskServer::success(&response)
+        let child_details = details(&child).await;
+        assert_eq!(child_details["parent_task_id"], parent.id.to_string());
+        assert_eq!(
+            child_details["parent_attempt_id"],
+            parent_attempt.id.to_string()
+        );
+        assert_eq!(child_details["children_count"], 0);
+
+        let parent_details = details(&parent).await;
+        assert!(parent_details["parent_task_id"].is_null());
+        assert!(parent_details["parent_attempt_id"].is_null());
+        assert_eq!(parent_details["children_count"], 1);
+        assert_eq!(parent_details["attempts_count"], 1);
     }
 
-    #[tool(description = "Start working on a task by creating and launching a new task attempt.")]
-    async fn start_task_attempt(
-        &self,
-        Parameters(StartTaskAttemptRequest {
-            task_id,
-            executor,
-            variant,
-            base_branch,
-        }): Parameters<StartTaskAttemptRequest>,
-    ) -> Result<CallToolResult, ErrorData> {
-        let base_branch = base_branch.trim().to_string();
-        if base_branch.is_empty() {
-            return Self::err("Base branch must not be empty.".to_string(), None::<String>);
-        }
+    #[tokio::test]
+    async fn continue_attempt_forwards_variant_and_surfaces_unknown_ones() {
+        let task = test_task(TaskStatus::InProgress);
+        let attempt = test_attempt(&task);
+        let app = Router::new()
+            .route("/api/tasks/{id}", get(respond(task.clone())))
+            .route("/api/task-attempts/{id}", get(respond(attempt.clone())))
+            .route(
+                "/api/task-attempts/{id}/follow-up",
+                post(|Json(body): Json<serde_json::Value>| async move {
+                    if body["variant"] == "PLAN" {
+                        let process = test_process(
+                            ExecutionProcessRunReason::CodingAgent,
+                            ExecutionProcessStatus::Running,
+                            0,
+                        );
+                        Json(ApiResponse::<ExecutionProcess>::success(process)).into_response()
+                    } else {
+                        (
+                            StatusCode::BAD_REQUEST,
+                            Json(ApiResponse::<()>::error(
+                                "Unknown variant 'TURBO' for executor CLAUDE_CODE. \
+                                 Valid variants: GENIE, PLAN",
+                            )),
+                        )
+                            .into_response()
+                    }
+                }),
+            );
+        let server = stub_server(app).await;
+        let follow_up = |variant: &str| {
+            server.continue_attempt(Parameters(ContinueAttemptRequest {
+                attempt_id: attempt.id.to_string(),
+                project_id: None,
+                prompt: "Plan the next step".to_string(),
+                variant: Some(variant.to_string()),
+            }))
+        };
 
-        let executor_trimmed = executor.trim();
-        if executor_trimmed.is_empty() {
-            return Self::err("Executor must not be empty.".to_string(), None::<String>);
-        }
+        let accepted = follow_up("PLAN").await.unwrap();
+        assert_ne!(accepted.is_error, Some(true));
 
-        let normalized_executor = executor_trimmed.replace('-', "_").to_ascii_uppercase();
-        let base_executor = match BaseCodingAgent::from_str(&normalized_executor) {
-            Ok(exec) => exec,
-            Err(_) => {
-                return Self::err(
-                    format!("Unknown executor '{executor_trimmed}'."),
-                    None::<String>,
-                );
-            }
-        };
+        let rejected = follow_up("TURBO").await.unwrap();
+        assert_eq!(rejected.is_error, Some(true));
+        let text = &rejected.content[0].as_text().unwrap().text;
+        assert!(text.contains("Valid variants: GENIE, PLAN"));
+    }
 
-        let variant = variant.and_then(|v| {
-            let trimmed = v.trim();
-            if trimmed.is_empty() {
-                None
+    /// Answers like the id-prefix middleware: the full id or its first 8 characters find the
+    /// record, `ambiguous` matches both candidates, anything else is not found
+    fn prefix_lookup<T: Clone + Serialize + Send + Sync + 'static>(
+        id: Uuid,
+        record: T,
+        ambiguous: &'static str,
+        candidates: Vec<Uuid>,
+    ) -> impl Fn(Path<String>) -> std::future::Ready<axum::response::Response>
+    + Clone
+    + Send
+    + Sync
+    + 'static {
+        move |Path(reference): Path<String>| {
+            let response = if reference == id.to_string() || reference == id.to_string()[..8] {
+                Json(ApiResponse::<T>::success(record.clone())).into_response()
+            } else if reference == ambiguous {
+                (
+                    StatusCode::CONFLICT,
+                    Json(ApiResponse::<(), Vec<Uuid>>::error_with_message_and_data(
+                        &format!("id prefix '{reference}' is ambiguous"),
+                        candidates.clone(),
+                    )),
+                )
+                    .into_response()
             } else {
-                Some(trimmed.to_string())
-            }
-        });
+                StatusCode::NOT_FOUND.into_response()
+            };
+            std::future::ready(response)
+        }
+    }
 
-        let executor_profile_id = ExecutorProfileId {
-            executor: base_executor,
-            variant,
+    #[tokio::test]
+    async fn resolvers_report_zero_one_or_many_matches() {
+        let task = test_task(TaskStatus::Todo);
+        let attempt = test_attempt(&task);
+        let (other_task, other_attempt) = (Uuid::new_v4(), Uuid::new_v4());
+        let app = Router::new()
+            .route(
+                "/api/tasks/{id}",
+                get(prefix_lookup(
+                    task.id,
+                    task.clone(),
+                    "aaaaaa",
+                    vec![task.id, other_task],
+                )),
+            )
+            .route(
+                "/api/task-attempts/{id}",
+                get(prefix_lookup(
+                    attempt.id,
+                    attempt.clone(),
+                    "bbbbbb",
+                    vec![attempt.id, other_attempt],
+                )),
+            );
+        let server = stub_server(app).await;
+        let suggestions = |result: &CallToolResult| -> Vec<Uuid> {
+            serde_json::from_value(result_body(result)["suggestions"].clone()).unwrap()
         };
 
-        let payload = CreateTaskAttemptBody {
-            task_id,
-            executor_profile_id,
-            base_branch,
-            use_worktree: None, // Default to worktree execution
-        };
+        // Exactly one match, by full id or prefix
+        assert_eq!(server.resolve_task(&task.id.to_string()).await.unwrap().id, task.id);
+        assert_eq!(server.resolve_task(&task.id.to_string()[..8]).await.unwrap().id, task.id);
+        assert_eq!(
+            server.resolve_attempt(&attempt.id.to_string()[..8]).await.unwrap().id,
+            attempt.id
+        );
 
-        let url = self.url("/api/task-attempts");
-        let attempt: TaskAttempt = match self.send_json(self.client.post(&url).json(&payload)).await
-        {
-            Ok(attempt) => attempt,
-            Err(e) => return Ok(e),
-        };
+        // No match
+        let missing_task = server.resolve_task("cccccc").await.unwrap_err();
+        assert_eq!(missing_task.is_error, Some(true));
+        assert_ne!(error_code(&missing_task), AMBIGUOUS_ID);
+        let missing_attempt = server.resolve_attempt("cccccc").await.unwrap_err();
+        assert_eq!(error_code(&missing_attempt), ATTEMPT_NOT_FOUND);
 
-        let response = StartTaskAttemptResponse {
-            task_id: attempt.task_id.to_string(),
-            attempt_id: attempt.id.to_string(),
+        // Several matches come back as suggestions
+        let ambiguous_task = server.resolve_task("aaaaaa").await.unwrap_err();
+        assert_eq!(error_code(&ambiguous_task), AMBIGUOUS_ID);
+        assert_eq!(suggestions(&ambiguous_task), [task.id, other_task]);
+        let ambiguous_attempt = server.resolve_attempt("bbbbbb").await.unwrap_err();
+        assert_eq!(error_code(&ambiguous_attempt), AMBIGUOUS_ID);
+        assert_eq!(suggestions(&ambiguous_attempt), [attempt.id, other_attempt]);
+    }
+
+    /// Serves an attempt whose rebase either applies cleanly or stops on a conflict in
+    /// `src/lib.rs`, recording the branch each rebase was asked to go onto
+    async fn spawn_rebase_mock_api(
+        attempt: TaskAttempt,
+        conflict: bool,
+        onto: Arc<std::sync::Mutex<Option<String>>>,
+    ) -> String {
+        let id = attempt.id;
+        let (behind, conflicted_files) = if conflict {
+            (1, vec!["src/lib.rs"])
+        } else {
+            (0, Vec::new())
         };
+        let status = serde_json::json!({
+            "commits_ahead": 2,
+            "commits_behind": behind,
+            "has_uncommitted_changes": false,
+            "head_oid": null,
+            "uncommitted_count": 0,
+            "untracked_count": 0,
+            "target_branch_name": "main",
+            "remote_commits_behind": null,
+            "remote_commits_ahead": null,
+            "merges": [],
+            "is_rebase_in_progress": conflict,
+            "conflict_op": conflict.then_some("rebase"),
+            "conflicted_files": conflicted_files,
+        });
+        let app = Router::new()
+            .route(
+                "/api/task-attempts/{id}",
+                get(prefix_lookup(id, attempt, "bbbbbb", Vec::new())),
+            )
+            .route(
+                "/api/task-attempts/{id}/rebase",
+                post(move |Json(body): Json<serde_json::Value>| {
+                    *onto.lock().unwrap() = body["new_base_branch"].as_str().map(String::from);
+                    let response = if conflict {
+                        ApiResponse::<(), GitOperationError>::error_with_data(
+                            GitOperationError::MergeConflicts {
+                                message: "Rebase stopped on conflicts".to_string(),
+                                op: ConflictOp::Rebase,
+                                conflicted_files: conflicted_files
+                                    .iter()
+                                    .map(|file| file.to_string())
+                                    .collect(),
+                            },
+                        )
+                    } else {
+                        ApiResponse::success(())
+                    };
+                    std::future::ready(Json(response))
+                }),
+            )
+            .route(
+                "/api/task-attempts/{id}/branch-status",
+                get(move || {
+                    let status = status.clone();
+                    async move { Json(ApiResponse::<serde_json::Value>::success(status)) }
+                }),
+            );
+        serve(app).await
+    }
 
-        TaskServer::success(&response)
+    #[tokio::test]
+    async fn rebase_attempt_reports_counts_or_conflicted_files() {
+        let task = test_task(TaskStatus::InReview);
+        let attempt = test_attempt(&task);
+        let onto = Arc::new(std::sync::Mutex::new(None));
+        let base_url = spawn_rebase_mock_api(attempt.clone(), false, onto.clone()).await;
+        let server = TaskServer::new(&base_url);
+        let clean = server
+            .rebase_attempt(Parameters(RebaseAttemptRequest {
+                attempt_id: attempt.id.to_string()[..8].to_string(),
+                onto: Some("release".to_string()),
+            }))
+            .await
+            .unwrap();
+        let clean = success_body(&clean);
+        assert_eq!(clean["rebased"], true);
+        assert_eq!(clean["commits_ahead"], 2);
+        assert_eq!(clean["commits_behind"], 0);
+        assert_eq!(clean["conflicted_files"], serde_json::json!([]));
+        assert_eq!(onto.lock().unwrap().as_deref(), Some("release"));
+
+        let onto = Arc::new(std::sync::Mutex::new(None));
+        let base_url = spawn_rebase_mock_api(attempt.clone(), true, onto.clone()).await;
+        let server = TaskServer::new(&base_url);
+        let conflicting = server
+            .rebase_attempt(Parameters(RebaseAttemptRequest {
+                attempt_id: attempt.id.to_string(),
+                onto: None,
+            }))
+            .await
+            .unwrap();
+        let conflicting = success_body(&conflicting);
+        assert_eq!(conflicting["rebased"], false);
+        assert_eq!(
+            conflicting["conflicted_files"],
+            serde_json::json!(["src/lib.rs"])
+        );
+        assert_eq!(conflicting["commits_behind"], 1);
+        assert_eq!(*onto.lock().unwrap(), None);
     }
 
-    #[tool(
-        description = "Update an existing task/ticket's title, description, or status. `project_id` and `task_id` are required! `title`, `description`, and `status` are optional."
-    )]
-    async fn update_task(
-        &self,
-        Parameters(UpdateTaskRequest {
-            task_id,
-            title,
-            description,
-            status,
-        }): Parameters<UpdateTaskRequest>,
-    ) -> Result<CallToolResult, ErrorData> {
-        let status = if let Some(ref status_str) = status {
-            match TaskStatus::from_str(status_str) {
-                Ok(s) => Some(s),
-                Err(_) => {
-                    return Self::err(
-                        "Invalid status filter. Valid values: 'todo', 'in-progress', 'in-review', 'done', 'cancelled'".to_string(),
-                        Some(status_str.to_string()),
-                    );
-                }
-            }
+    /// Serves an attempt whose merge either succeeds or conflicts in `src/lib.rs`. With
+    /// `pending_conflict` its branch status shows a merge already stopped on that file.
+    /// Every merge request is counted in `merges`.
+    async fn spawn_merge_mock_api(
+        attempt: TaskAttempt,
+        conflict: bool,
+        pending_conflict: bool,
+        merges: Arc<AtomicUsize>,
+    ) -> String {
+        let id = attempt.id;
+        let conflicted_files: Vec<&str> = if pending_conflict {
+            vec!["src/lib.rs"]
         } else {
-            None
+            Vec::new()
         };
+        let status = serde_json::json!({
+            "commits_ahead": 2,
+            "commits_behind": 0,
+            "has_uncommitted_changes": pending_conflict,
+            "head_oid": null,
+            "uncommitted_count": 0,
+            "untracked_count": 0,
+            "target_branch_name": "main",
+            "remote_commits_behind": null,
+            "remote_commits_ahead": null,
+            "merges": [],
+            "is_rebase_in_progress": false,
+            "conflict_op": pending_conflict.then_some("merge"),
+            "conflicted_files": conflicted_files,
+        });
+        let app = Router::new()
+            .route(
+                "/api/task-attempts/{id}",
+                get(prefix_lookup(id, attempt, "bbbbbb", Vec::new())),
+            )
+            .route(
+                "/api/task-attempts/{id}/branch-status",
+                get(move || {
+                    let status = status.clone();
+                    async move { Json(ApiResponse::<serde_json::Value>::success(status)) }
+                }),
+            )
+            .route(
+                "/api/task-attempts/{id}/merge",
+                post(move || {
+                    merges.fetch_add(1, AtomicOrdering::SeqCst);
+                    let response = if conflict {
+                        ApiResponse::<(), GitOperationError>::error_with_data(
+                            GitOperationError::MergeConflicts {
+                                message: "Merging conflicts in 1 file(s)".to_string(),
+                                op: ConflictOp::Merge,
+                                conflicted_files: vec!["src/lib.rs".to_string()],
+                            },
+                        )
+                    } else {
+                        ApiResponse::success(())
+                    };
+                    std::future::ready(Json(response))
+                }),
+            );
+        serve(app).await
+    }
 
-        let payload = UpdateTask {
-            title,
-            description,
-            status,
-            parent_task_attempt: None,
-            image_ids: None,
-        };
-        let url = self.url(&format!("/api/tasks/{}", task_id));
-        let updated_task: Task = match self.send_json(self.client.put(&url).json(&payload)).await {
-            Ok(t) => t,
-            Err(e) => return Ok(e),
+    #[tokio::test]
+    async fn merge_attempt_reports_success_or_conflicted_files() {
+        let task = test_task(TaskStatus::InReview);
+        let attempt = test_attempt(&task);
+        let merge = |base_url: String| {
+            let attempt_id = attempt.id.to_string();
+            async move {
+                let result = TaskServer::new(&base_url)
+                    .merge_attempt(Parameters(MergeTaskAttemptRequest {
+                        attempt_id,
+                        force: None,
+                    }))
+                    .await
+                    .unwrap();
+                success_body(&result)
+            }
         };
+        let merges = Arc::new(AtomicUsize::new(0));
 
-        let details = TaskDetails::from_task(updated_task);
-        let repsonse = UpdateTaskResponse { task: details };
-        TaskServer::success(&repsonse)
+        let clean =
+            merge(spawn_merge_mock_api(attempt.clone(), false, false, merges.clone()).await).await;
+        assert_eq!(clean["success"], true);
+        assert_eq!(clean["has_conflicts"], false);
+        assert_eq!(clean["conflicted_files"], serde_json::json!([]));
+        assert_eq!(clean["target_branch"], "main");
+
+        let conflicting =
+            merge(spawn_merge_mock_api(attempt.clone(), true, false, merges.clone()).await).await;
+        assert_eq!(conflicting["success"], false);
+        assert_eq!(conflicting["has_conflicts"], true);
+        assert_eq!(conflicting["conflict_op"], "merge");
+        assert_eq!(
+            conflicting["conflicted_files"],
+            serde_json::json!(["src/lib.rs"])
+        );
+        assert!(
+            conflicting["next_steps"][0]
+                .as_str()
+                .unwrap()
+                .contains("rebase_attempt")
+        );
+        assert_eq!(merges.load(AtomicOrdering::SeqCst), 2);
     }
 
-    #[tool(
-        description = "Delete a task/ticket from a project. `project_id` and `task_id` are required!"
-    )]
-    async fn delete_task(
-        &self,
-        Parameters(DeleteTaskRequest { task_id }): Parameters<DeleteTaskRequest>,
-    ) -> Result<CallToolResult, ErrorData> {
-        let url = self.url(&format!("/api/tasks/{}", task_id));
-        if let Err(e) = self
-            .send_json::<serde_json::Value>(self.client.delete(&url))
-            .await
-        {
-            return Ok(e);
-        }
+    #[tokio::test]
+    async fn merge_attempt_refuses_pending_conflicts_unless_forced() {
+        let task = test_task(TaskStatus::InReview);
+        let attempt = test_attempt(&task);
+        let merges = Arc::new(AtomicUsize::new(0));
+        let base_url = spawn_merge_mock_api(attempt.clone(), true, true, merges.clone()).await;
+        let server = TaskServer::new(&base_url);
+        let merge = |force: Option<bool>| {
+            server.merge_attempt(Parameters(MergeTaskAttemptRequest {
+                attempt_id: attempt.id.to_string(),
+                force,
+            }))
+        };
 
-        let repsonse = DeleteTaskResponse {
-            deleted_task_id: Some(task_id.to_string()),
+        let refused = merge(None).await.unwrap();
+        assert_eq!(error_code(&refused), MERGE_CONFLICTS_PENDING);
+        let body = result_body(&refused);
+        assert_eq!(body["conflict_op"], "merge");
+        assert_eq!(body["conflicted_files"], serde_json::json!(["src/lib.rs"]));
+        let next_steps = body["next_steps"].to_string();
+        assert!(next_steps.contains("resolve"));
+        assert!(next_steps.contains("rebase_attempt"));
+        assert_eq!(merges.load(AtomicOrdering::SeqCst), 0);
+
+        // Forcing skips the check and reports the merge's own outcome
+        let forced = merge(Some(true)).await.unwrap();
+        let body = success_body(&forced);
+        assert_eq!(body["success"], false);
+        assert_eq!(body["has_conflicts"], true);
+        assert_eq!(merges.load(AtomicOrdering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn create_task_uploads_data_uris_and_attaches_images() {
+        let task = test_task(TaskStatus::Todo);
+        let project_id = task.project_id;
+        let uploaded_id = Uuid::new_v4();
+        let existing_id = Uuid::new_v4();
+        let uploads = Arc::new(std::sync::Mutex::new(Vec::<(String, Vec<u8>)>::new()));
+        let created = Arc::new(std::sync::Mutex::new(Vec::<serde_json::Value>::new()));
+        let app = Router::new()
+            .route(
+                "/api/images/upload",
+                post({
+                    let uploads = uploads.clone();
+                    move |mut multipart: axum::extract::Multipart| {
+                        let uploads = uploads.clone();
+                        async move {
+                            let field = multipart.next_field().await.unwrap().unwrap();
+                            assert_eq!(field.name(), Some("image"));
+                            let mime = field.content_type().unwrap_or_default().to_string();
+                            let data = field.bytes().await.unwrap().to_vec();
+                            uploads.lock().unwrap().push((mime.clone(), data));
+                            Json(ApiResponse::success(ImageResponse {
+                                id: uploaded_id,
+                                file_path: ".forge-images/image.png".to_string(),
+                                original_name: "image.png".to_string(),
+                                mime_type: Some(mime),
+                                size_bytes: 3,
+                                hash: "hash".to_string(),
+                                created_at: Utc::now(),
+                                updated_at: Utc::now(),
+                            }))
+                        }
+                    }
+                }),
+            )
+            .route(
+                "/api/tasks",
+                post({
+                    let created = created.clone();
+                    move |Json(body): Json<serde_json::Value>| {
+                        created.lock().unwrap().push(body);
+                        std::future::ready(Json(ApiResponse::success(CreatedTask {
+                            task: task.clone(),
+                            possible_duplicates: Vec::new(),
+                        })))
+                    }
+                }),
+            );
+        let server = stub_server(app).await;
+        let create = |images: Vec<String>| {
+            server.create_task(Parameters(CreateTaskRequest {
+                project_id,
+                title: "Match the mockup".to_string(),
+                description: None,
+                force: None,
+                images: Some(images),
+                client_token: None,
+            }))
         };
 
-        TaskServer::success(&repsonse)
+        let result = create(vec![
+            format!("data:image/png;base64,{}", BASE64_STANDARD.encode(b"png")),
+            existing_id.to_string(),
+            format!("data:image/webp;base64,{}", BASE64_STANDARD.encode(b"RIFF")),
+        ])
+        .await
+        .unwrap();
+        assert_ne!(result.is_error, Some(true));
+        assert_eq!(
+            *uploads.lock().unwrap(),
+            vec![
+                ("image/png".to_string(), b"png".to_vec()),
+                ("image/webp".to_string(), b"RIFF".to_vec()),
+            ]
+        );
+        assert_eq!(
+            created.lock().unwrap()[0]["image_ids"],
+            serde_json::json!([uploaded_id, existing_id, uploaded_id])
+        );
+
+        // Unsupported types are rejected before anything is uploaded or created
+        let result = create(vec![
+            format!("data:image/png;base64,{}", BASE64_STANDARD.encode(b"png")),
+            "data:image/svg+xml;base64,PHN2Zy8+".to_string(),
+        ])
+        .await
+        .unwrap();
+        assert_eq!(error_code(&result), UNSUPPORTED_IMAGE);
+        assert_eq!(uploads.lock().unwrap().len(), 2);
+        assert_eq!(created.lock().unwrap().len(), 1);
     }
 
-    #[tool(
-        description = "Get detailed information (like task description) about a specific task/ticket. You can use `list_tasks` to find the `task_ids` of all tasks in a project. `project_id` and `task_id` are required!"
-    )]
-    async fn get_task(
-        &self,
-        Parameters(GetTaskRequest { task_id }): Parameters<GetTaskRequest>,
-    ) -> Result<CallToolResult, ErrorData> {
-        let url = self.url(&format!("/api/tasks/{}", task_id));
-        let task: Task = match self.send_json(self.client.get(&url)).await {
-            Ok(t) => t,
-            Err(e) => return Ok(e),
+    #[tokio::test]
+    async fn duplicate_task_copies_description_and_images_into_a_new_task() {
+        let source = Task {
+            description: Some("Say hello in the CLI".to_string()),
+            ..test_task(TaskStatus::Done)
+        };
+        let image_ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let created = Arc::new(std::sync::Mutex::new(
+            Vec::<(String, serde_json::Value)>::new(),
+        ));
+        let image = |id: Uuid| ImageResponse {
+            id,
+            file_path: format!(".forge-images/{id}.png"),
+            original_name: "mockup.png".to_string(),
+            mime_type: Some("image/png".to_string()),
+            size_bytes: 3,
+            hash: id.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let copy_of = |body: &serde_json::Value| Task {
+            id: Uuid::new_v4(),
+            project_id: body["project_id"].as_str().unwrap().parse().unwrap(),
+            title: body["title"].as_str().unwrap().to_string(),
+            description: body["description"].as_str().map(str::to_string),
+            ..test_task(TaskStatus::Todo)
+        };
+        let app = Router::new()
+            .route("/api/tasks/{id}", get(respond(source.clone())))
+            .route(
+                "/api/images/task/{task_id}",
+                get(move || {
+                    std::future::ready(Json(ApiResponse::success(image_ids.map(image).to_vec())))
+                }),
+            )
+            .route("/api/projects/{id}/default-branch", get(respond("main".to_string())))
+            .route(
+                "/api/tasks",
+                post({
+                    let created = created.clone();
+                    move |RawQuery(query): RawQuery, Json(body): Json<serde_json::Value>| {
+                        let task = copy_of(&body);
+                        created
+                            .lock()
+                            .unwrap()
+                            .push((query.unwrap_or_default(), body));
+                        std::future::ready(Json(ApiResponse::success(CreatedTask {
+                            task,
+                            possible_duplicates: Vec::new(),
+                        })))
+                    }
+                }),
+            )
+            .route(
+                "/api/tasks/create-and-start",
+                post({
+                    let created = created.clone();
+                    move |Json(body): Json<serde_json::Value>| {
+                        let task = copy_of(&body["task"]);
+                        created.lock().unwrap().push(("start".to_string(), body));
+                        std::future::ready(Json(ApiResponse::success(TaskWithAttemptStatus {
+                            task,
+                            has_in_progress_attempt: true,
+                            has_merged_attempt: false,
+                            last_attempt_failed: false,
+                            executor: "CODEX".to_string(),
+                            attempt_count: 1,
+                        })))
+                    }
+                }),
+            );
+        let server = stub_server(app).await;
+        let duplicate = |executor: Option<&str>| {
+            server.duplicate_task(Parameters(DuplicateTaskRequest {
+                task_id: source.id.to_string(),
+                project: None,
+                executor: executor.map(str::to_string),
+                variant: None,
+                base_branch: None,
+            }))
         };
 
-        let details = TaskDetails::from_task(task);
-        let response = GetTaskResponse { task: details };
+        let result = duplicate(None).await.unwrap();
+        let body = success_body(&result);
+        assert_eq!(body["source_task_id"], source.id.to_string());
+        assert_ne!(body["task_id"], source.id.to_string());
+        assert_eq!(body["project_id"], source.project_id.to_string());
+        assert_eq!(body["images"], 2);
+        assert_eq!(body["started"], false);
+        {
+            let created = created.lock().unwrap();
+            let (query, task) = &created[0];
+            assert_eq!(query, "force=true");
+            assert_eq!(task["title"], "Add greeting");
+            assert_eq!(task["description"], "Say hello in the CLI");
+            assert_eq!(task["image_ids"], serde_json::json!(image_ids));
+        }
 
-        TaskServer::success(&response)
+        let result = duplicate(Some("codex")).await.unwrap();
+        let body = success_body(&result);
+        assert_eq!(body["started"], true);
+        assert_ne!(body["task_id"], source.id.to_string());
+        let created = created.lock().unwrap();
+        let (kind, payload) = &created[1];
+        assert_eq!(kind, "start");
+        assert_eq!(payload["executor_profile_id"]["executor"], "CODEX");
+        assert_eq!(payload["base_branch"], "main");
+        assert_eq!(payload["task"]["description"], "Say hello in the CLI");
+        assert_eq!(payload["task"]["image_ids"], serde_json::json!(image_ids));
     }
 
-    // =========================================================================
-    // ExecutionRun Tools - Lightweight executor invocation without Task overhead
-    // =========================================================================
+    #[tokio::test]
+    async fn set_forge_config_persists_known_keys_and_rejects_unknown_ones() {
+        let stored = Arc::new(std::sync::Mutex::new(
+            serde_json::to_value(ForgeProjectSettings {
+                releases_repo: Some("acme/forge".to_string()),
+                omni_config: Some(OmniConfig {
+                    api_key: Some("omni-test-key".to_string()),
+                    ..Default::default()
+                }),
+                webhook: Some(WebhookConfig {
+                    url: "https://hooks.example.com/forge".to_string(),
+                    secret: "webhook-test-secret".to_string(),
+                    events: Default::default(),
+                }),
+                ..Default::default()
+            })
+            .unwrap(),
+        ));
+        let puts = Arc::new(AtomicUsize::new(0));
+        let app = Router::new().route(
+            "/api/forge/config",
+            get({
+                let stored = stored.clone();
+                move || {
+                    let config = stored.lock().unwrap().clone();
+                    std::future::ready(Json(ApiResponse::<serde_json::Value>::success(config)))
+                }
+            })
+            .put({
+                let stored = stored.clone();
+                let puts = puts.clone();
+                move |Json(body): Json<ForgeProjectSettings>| {
+                    puts.fetch_add(1, AtomicOrdering::SeqCst);
+                    *stored.lock().unwrap() = serde_json::to_value(&body).unwrap();
+                    std::future::ready(Json(ApiResponse::success(body)))
+                }
+            }),
+        );
+        let server = stub_server(app).await;
+        let set = |key: &str, value: serde_json::Value| {
+            server.set_forge_config(Parameters(SetForgeConfigRequest {
+                key: key.to_string(),
+                value,
+            }))
+        };
 
-    #[tool(
-        description = "Start a lightweight execution run. Unlike task attempts, runs don't require creating a task first. Ideal for serverless micro-tasks like generating commit messages or PR descriptions."
-    )]
-    async fn start_execution_run(
-        &self,
-        Parameters(StartExecutionRunRequest {
-            project_id,
-            prompt,
-            executor,
-            variant,
-            base_branch,
-        }): Parameters<StartExecutionRunRequest>,
-    ) -> Result<CallToolResult, ErrorData> {
-        let executor_trimmed = executor.trim();
-        if executor_trimmed.is_empty() {
-            return Self::err("Executor must not be empty.".to_string(), None::<String>);
+        let result = set("review_on_pr", serde_json::json!(false)).await.unwrap();
+        let body = success_body(&result);
+        assert_eq!(body["review_on_pr"], false);
+        // Secrets are never echoed back
+        assert_eq!(body["omni_config"]["api_key"], REDACTED_SECRET);
+        assert_eq!(body["webhook"]["secret"], REDACTED_SECRET);
+        {
+            let stored = stored.lock().unwrap();
+            assert_eq!(stored["review_on_pr"], false);
+            // Settings other than the one being set are kept
+            assert_eq!(stored["releases_repo"], "acme/forge");
+            assert_eq!(stored["omni_config"]["api_key"], "omni-test-key");
+            assert_eq!(stored["webhook"]["secret"], "webhook-test-secret");
+        }
+
+        let result = set("review_on_prr", serde_json::json!(true)).await.unwrap();
+        assert_eq!(error_code(&result), UNKNOWN_CONFIG_KEY);
+        let body = result_body(&result);
+        assert_eq!(body["suggestions"][0], "review_on_pr");
+
+        for protected in [
+            "safe_executor_mode",
+            "sign_commits",
+            "webhook",
+            "omni_config",
+        ] {
+            let result = set(protected, serde_json::json!(null)).await.unwrap();
+            assert_eq!(error_code(&result), PROTECTED_CONFIG_KEY, "{protected}");
         }
 
-        let normalized_executor = executor_trimmed.replace('-', "_").to_ascii_uppercase();
-        let base_executor = match BaseCodingAgent::from_str(&normalized_executor) {
-            Ok(exec) => exec,
-            Err(_) => {
-                return Self::err(
-                    format!("Unknown executor '{executor_trimmed}'."),
-                    None::<String>,
-                );
-            }
+        let result = set("omni_enabled", serde_json::json!("yes")).await.unwrap();
+        assert_eq!(error_code(&result), INVALID_CONFIG_VALUE);
+
+        assert_eq!(puts.load(AtomicOrdering::SeqCst), 1);
+        assert_eq!(
+            stored.lock().unwrap()["safe_executor_mode"],
+            serde_json::Value::Null
+        );
+    }
+
+    #[tokio::test]
+    async fn fork_starts_a_new_attempt_from_the_source_attempt() {
+        let task = test_task(TaskStatus::InProgress);
+        let source = test_attempt(&task);
+        let forked = TaskAttempt {
+            id: Uuid::new_v4(),
+            branch: "forge/add-greeting-fork".to_string(),
+            ..source.clone()
+        };
+        let forked_id = forked.id;
+        let captured = Arc::new(std::sync::Mutex::new(None));
+        let app = Router::new()
+            .route(
+                "/api/task-attempts/{id}",
+                get(prefix_lookup(
+                    source.id,
+                    source.clone(),
+                    "bbbbbb",
+                    Vec::new(),
+                )),
+            )
+            .route(
+                "/api/task-attempts/{id}/fork",
+                post({
+                    let captured = captured.clone();
+                    move |Path(id): Path<Uuid>, Json(body): Json<serde_json::Value>| {
+                        *captured.lock().unwrap() = Some((id, body));
+                        std::future::ready(Json(ApiResponse::success(forked.clone())))
+                    }
+                }),
+            );
+
+        let result = stub_server(app)
+            .await
+            .fork(Parameters(ForkAttemptRequest {
+                attempt_id: source.id.to_string()[..8].to_string(),
+                from_commit: Some("abc1234".to_string()),
+            }))
+            .await
+            .unwrap();
+
+        let body = success_body(&result);
+        assert_eq!(body["source_attempt_id"], source.id.to_string());
+        assert_eq!(body["attempt_id"], forked_id.to_string());
+        assert_eq!(body["branch"], "forge/add-greeting-fork");
+        assert_eq!(body["target_branch"], "main");
+        let (forked_from, request) = captured.lock().unwrap().take().unwrap();
+        assert_eq!(forked_from, source.id);
+        assert_eq!(request["from_commit"], "abc1234");
+    }
+
+    #[tokio::test]
+    async fn processes_lists_each_step_of_an_attempt() {
+        let task = test_task(TaskStatus::InProgress);
+        let attempt = test_attempt(&task);
+        let setup = ExecutionProcess {
+            exit_code: Some(0),
+            completed_at: Some(Utc::now() - chrono::Duration::minutes(9)),
+            ..test_process(
+                ExecutionProcessRunReason::SetupScript,
+                ExecutionProcessStatus::Completed,
+                10,
+            )
         };
+        let agent = test_process(
+            ExecutionProcessRunReason::CodingAgent,
+            ExecutionProcessStatus::Running,
+            8,
+        );
+        let requested_attempt = Arc::new(std::sync::Mutex::new(None::<String>));
+        let captured = requested_attempt.clone();
+        let app = Router::new()
+            .route("/api/task-attempts/{id}", get(respond(attempt.clone())))
+            .route(
+                "/api/execution-processes",
+                get({
+                    let processes = vec![agent.clone(), setup.clone()];
+                    move |Query(query): Query<HashMap<String, String>>| {
+                        *captured.lock().unwrap() = query.get("attempt_id").cloned();
+                        std::future::ready(Json(ApiResponse::success(processes.clone())))
+                    }
+                }),
+            );
+        let server = stub_server(app).await;
 
-        let variant = variant.and_then(|v| {
-            let trimmed = v.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
-        });
+        let result = server
+            .processes(Parameters(AttemptProcessesRequest {
+                attempt_id: attempt.id.to_string(),
+            }))
+            .await
+            .unwrap();
 
-        let executor_profile_id = ExecutorProfileId {
-            executor: base_executor,
-            variant,
+        let body = success_body(&result);
+        assert_eq!(
+            requested_attempt.lock().unwrap().as_deref(),
+            Some(attempt.id.to_string().as_str())
+        );
+        let processes = body["processes"].as_array().unwrap();
+        assert_eq!(processes.len(), 2);
+        assert_eq!(processes[0]["process_id"], setup.id.to_string());
+        assert_eq!(processes[0]["run_reason"], "setupscript");
+        assert_eq!(processes[0]["status"], "completed");
+        assert_eq!(processes[0]["exit_code"], 0);
+        assert!(processes[0]["completed_at"].is_string());
+        assert_eq!(processes[1]["process_id"], agent.id.to_string());
+        assert_eq!(processes[1]["run_reason"], "codingagent");
+        assert_eq!(processes[1]["status"], "running");
+        assert!(processes[1]["completed_at"].is_null());
+        assert_eq!(
+            body["next_steps"],
+            serde_json::json!([format!(
+                "Call `logs` with process_id {} to follow the running coding agent",
+                agent.id
+            )])
+        );
+    }
+
+    #[tokio::test]
+    async fn list_attempt_commits_pages_through_the_attempt_branch() {
+        let task = test_task(TaskStatus::InReview);
+        let attempt = test_attempt(&task);
+        let commit = |n: usize| BranchCommit {
+            sha: format!("{n:040x}"),
+            subject: format!("commit {n}"),
+            author_name: Some("Forge".to_string()),
+            author_email: Some("forge@example.com".to_string()),
+            committed_at: Utc::now(),
         };
+        let branch_commits: Vec<BranchCommit> = (0..3).rev().map(commit).collect();
+        let app = Router::new()
+            .route("/api/task-attempts/{id}", get(respond(attempt.clone())))
+            .route(
+                "/api/task-attempts/{id}/commits",
+                get(move |Query(query): Query<HashMap<String, usize>>| {
+                    let offset = query.get("offset").copied().unwrap_or(0);
+                    let limit = query.get("limit").copied().unwrap_or(50);
+                    let page = AttemptCommits {
+                        commits: branch_commits
+                            .iter()
+                            .skip(offset)
+                            .take(limit)
+                            .cloned()
+                            .collect(),
+                        total: branch_commits.len(),
+                        target_branch_name: "main".to_string(),
+                    };
+                    std::future::ready(Json(ApiResponse::success(page)))
+                }),
+            );
+        let server = stub_server(app).await;
 
-        let payload = ApiCreateExecutionRunRequest {
-            project_id,
-            prompt,
-            executor_profile_id,
-            base_branch,
+        let list = |cursor: Option<String>| {
+            server.list_attempt_commits(Parameters(AttemptCommitsRequest {
+                attempt_id: attempt.id.to_string(),
+                limit: Some(2),
+                offset: None,
+                cursor,
+            }))
+        };
+        let first = success_body(&list(None).await.unwrap());
+        assert_eq!(first["target_branch"], "main");
+        assert_eq!(first["total"], 3);
+        let subjects = |page: &serde_json::Value| -> Vec<String> {
+            page["commits"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|c| c["subject"].as_str().unwrap().to_string())
+                .collect()
         };
+        assert_eq!(subjects(&first), ["commit 2", "commit 1"]);
+        assert_eq!(first["next_cursor"], "2");
 
-        let url = self.url("/api/execution-runs");
+        let second = success_body(&list(Some("2".to_string())).await.unwrap());
+        assert_eq!(subjects(&second), ["commit 0"]);
+        assert!(second["next_cursor"].is_null());
+    }
 
-        // Response contains { execution_run, execution_process }
-        #[derive(Deserialize)]
-        struct ApiExecutionRunResponse {
-            execution_run: forge_core_db::models::execution_run::ExecutionRun,
-        }
+    #[tokio::test]
+    async fn logs_tails_the_newest_non_dev_server_process() {
+        use forge_core_executors::logs::{NormalizedEntry, NormalizedEntryType};
 
-        let resp: ApiExecutionRunResponse =
-            match self.send_json(self.client.post(&url).json(&payload)).await {
-                Ok(r) => r,
-                Err(e) => return Ok(e),
-            };
+        use crate::routes::execution_processes::LogEntry;
 
-        let response = StartExecutionRunResponse {
-            execution_run_id: resp.execution_run.id.to_string(),
-            project_id: resp.execution_run.project_id.to_string(),
-            branch: resp.execution_run.branch,
-        };
+        let task = test_task(TaskStatus::InProgress);
+        let attempt = test_attempt(&task);
+        let agent = test_process(
+            ExecutionProcessRunReason::CodingAgent,
+            ExecutionProcessStatus::Running,
+            5,
+        );
+        let dev_server = test_process(
+            ExecutionProcessRunReason::DevServer,
+            ExecutionProcessStatus::Running,
+            1,
+        );
+        let agent_id = agent.id;
+        let app = Router::new()
+            .route(
+                "/api/task-attempts/{id}",
+                get(prefix_lookup(
+                    attempt.id,
+                    attempt.clone(),
+                    "bbbbbb",
+                    Vec::new(),
+                )),
+            )
+            .route(
+                "/api/execution-processes",
+                get(move || {
+                    let processes = vec![agent.clone(), dev_server.clone()];
+                    std::future::ready(Json(ApiResponse::success(processes)))
+                }),
+            )
+            .route(
+                "/api/execution-processes/{id}/logs",
+                get(
+                    move |Path(id): Path<Uuid>, Query(query): Query<LogTailQuery>| {
+                        assert_eq!(id, agent_id);
+                        let total_entries = 300;
+                        let tail = query.tail.unwrap_or(100) as usize;
+                        let entries = (total_entries - tail..total_entries)
+                            .map(|i| {
+                                LogEntry::NormalizedEntry(NormalizedEntry {
+                                    timestamp: None,
+                                    entry_type: NormalizedEntryType::AssistantMessage,
+                                    content: format!("entry {i}"),
+                                    metadata: None,
+                                })
+                            })
+                            .collect();
+                        std::future::ready(Json(ApiResponse::success(LogTail {
+                            entries,
+                            total_entries,
+                            truncated: true,
+                            running: true,
+                        })))
+                    },
+                ),
+            );
+        let server = stub_server(app).await;
 
-        TaskServer::success(&response)
+        let result = server
+            .logs(Parameters(AttemptLogsRequest {
+                attempt_id: attempt.id.to_string()[..8].to_string(),
+                tail: Some(20),
+                process_id: None,
+            }))
+            .await
+            .unwrap();
+
+        let body = success_body(&result);
+        assert_eq!(body["process_id"], agent_id.to_string());
+        assert_eq!(body["run_reason"], "codingagent");
+        assert_eq!(body["total_entries"], 300);
+        assert_eq!(body["truncated"], true);
+        assert_eq!(body["running"], true);
+        assert_eq!(body["entries"].as_array().unwrap().len(), 20);
+        assert_eq!(body["entries"][0]["content"]["content"], "entry 280");
+        assert_eq!(body["entries"][19]["content"]["content"], "entry 299");
+        assert_eq!(body["entries"][19]["type"], "NORMALIZED_ENTRY");
     }
 
-    #[tool(description = "List execution runs, optionally filtered by project_id")]
-    async fn list_execution_runs(
-        &self,
-        Parameters(ListExecutionRunsRequest { project_id }): Parameters<ListExecutionRunsRequest>,
-    ) -> Result<CallToolResult, ErrorData> {
-        let url = match project_id {
-            Some(pid) => self.url(&format!("/api/execution-runs?project_id={}", pid)),
-            None => self.url("/api/execution-runs"),
-        };
+    #[tokio::test]
+    async fn stop_task_stops_every_running_attempt() {
+        let task = test_task(TaskStatus::InProgress);
+        let running: Vec<TaskAttempt> = (0..3).map(|_| test_attempt(&task)).collect();
+        let finished = test_attempt(&task);
+        let running_ids: Vec<Uuid> = running.iter().map(|attempt| attempt.id).collect();
+        let mut attempts = running.clone();
+        attempts.push(finished.clone());
+        let stop_requests = Arc::new(std::sync::Mutex::new(Vec::<Uuid>::new()));
+        let recorded = stop_requests.clone();
+        let app = Router::new()
+            .route("/api/tasks/{id}", get(respond(task.clone())))
+            .route(
+                "/api/task-attempts",
+                get(move || {
+                    // Statuses come from the listing; no per-attempt route is served
+                    let attempts: Vec<TaskAttemptListItem> = attempts
+                        .iter()
+                        .cloned()
+                        .map(|attempt| TaskAttemptListItem {
+                            latest_process_status: Some(if attempt.id == finished.id {
+                                ExecutionProcessStatus::Completed
+                            } else {
+                                ExecutionProcessStatus::Running
+                            }),
+                            ..list_item(attempt)
+                        })
+                        .collect();
+                    async move { Json(ApiResponse::success(attempts)) }
+                }),
+            )
+            .route(
+                "/api/task-attempts/{id}/stop",
+                post(move |Path(id): Path<Uuid>| {
+                    recorded.lock().unwrap().push(id);
+                    std::future::ready(Json(ApiResponse::<()>::success(())))
+                }),
+            );
+        let server = stub_server(app).await;
 
-        let runs: Vec<forge_core_db::models::execution_run::ExecutionRun> =
-            match self.send_json(self.client.get(&url)).await {
-                Ok(r) => r,
-                Err(e) => return Ok(e),
-            };
+        let result = server
+            .stop_task(Parameters(StopTaskRequest {
+                task_id: task.id.to_string(),
+            }))
+            .await
+            .unwrap();
 
-        let summaries: Vec<ExecutionRunSummary> = runs
-            .into_iter()
-            .map(|r| ExecutionRunSummary {
-                id: r.id.to_string(),
-                project_id: r.project_id.to_string(),
-                branch: r.branch,
-                target_branch: r.target_branch,
-                executor: r.executor,
-                created_at: r.created_at.to_rfc3339(),
+        let body = success_body(&result);
+        assert_eq!(body["stopped"], 3);
+        assert_eq!(body["failed"], 0);
+        let reported: Vec<String> = body["attempts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|attempt| {
+                assert_eq!(attempt["stopped"], true);
+                attempt["attempt_id"].as_str().unwrap().to_string()
             })
             .collect();
+        let expected: Vec<String> = running_ids.iter().map(Uuid::to_string).collect();
+        assert_eq!(reported, expected);
+        assert_eq!(*stop_requests.lock().unwrap(), running_ids);
+    }
 
-        let response = ListExecutionRunsResponse {
-            count: summaries.len(),
-            runs: summaries,
+    /// Serves one project named "forge" and records every create and create-and-start body
+    async fn spawn_batch_mock_api(
+        project_id: Uuid,
+        bodies: Arc<std::sync::Mutex<Vec<(&'static str, serde_json::Value)>>>,
+    ) -> String {
+        let project = Project {
+            id: project_id,
+            name: "forge".to_string(),
+            git_repo_path: PathBuf::from("/tmp/forge"),
+            setup_script: None,
+            dev_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            commit_prompt: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let task_from = |body: &serde_json::Value| Task {
+            title: body["title"].as_str().unwrap().to_string(),
+            ..test_task(TaskStatus::Todo)
         };
+        let started_bodies = bodies.clone();
+        let app = Router::new()
+            .route(
+                "/api/projects",
+                get(move || std::future::ready(Json(ApiResponse::success(vec![project.clone()])))),
+            )
+            .route("/api/projects/{id}/default-branch", get(respond("main".to_string())))
+            .route(
+                "/api/tasks",
+                post(move |Json(body): Json<serde_json::Value>| {
+                    let task = task_from(&body);
+                    bodies.lock().unwrap().push(("create", body));
+                    std::future::ready(Json(ApiResponse::success(CreatedTask {
+                        task,
+                        possible_duplicates: Vec::new(),
+                    })))
+                }),
+            )
+            .route(
+                "/api/tasks/create-and-start",
+                post(move |Json(body): Json<serde_json::Value>| {
+                    let task = task_from(&body["task"]);
+                    started_bodies.lock().unwrap().push(("start", body));
+                    std::future::ready(Json(ApiResponse::success(TaskWithAttemptStatus {
+                        task,
+                        has_in_progress_attempt: true,
+                        has_merged_attempt: false,
+                        last_attempt_failed: false,
+                        executor: "CLAUDE_CODE".to_string(),
+                        attempt_count: 1,
+                    })))
+                }),
+            );
+        serve(app).await
+    }
 
-        TaskServer::success(&response)
+    fn batch_item(title: &str, executor: Option<&str>) -> BatchTaskItem {
+        BatchTaskItem {
+            title: title.to_string(),
+            description: None,
+            executor: executor.map(str::to_string),
+            start: None,
+        }
     }
 
-    #[tool(description = "Get details of a specific execution run")]
-    async fn get_execution_run(
-        &self,
-        Parameters(GetExecutionRunRequest { execution_run_id }): Parameters<GetExecutionRunRequest>,
-    ) -> Result<CallToolResult, ErrorData> {
-        let url = self.url(&format!("/api/execution-runs/{}", execution_run_id));
+    async fn create_batch(base_url: &str, tasks: Vec<BatchTaskItem>) -> serde_json::Value {
+        let result = TaskServer::new(base_url)
+            .tasks_create(Parameters(CreateTasksRequest {
+                project: "forge".to_string(),
+                tasks,
+                base_branch: None,
+            }))
+            .await
+            .unwrap();
+        success_body(&result)
+    }
 
-        let run: forge_core_db::models::execution_run::ExecutionRun =
-            match self.send_json(self.client.get(&url)).await {
-                Ok(r) => r,
-                Err(e) => return Ok(e),
-            };
+    #[tokio::test]
+    async fn tasks_create_creates_and_starts_every_item() {
+        let project_id = Uuid::new_v4();
+        let bodies = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let base_url = spawn_batch_mock_api(project_id, bodies.clone()).await;
 
-        let response = GetExecutionRunResponse {
-            id: run.id.to_string(),
-            project_id: run.project_id.to_string(),
-            branch: run.branch,
-            target_branch: run.target_branch,
-            executor: run.executor,
-            prompt: run.prompt,
-            created_at: run.created_at.to_rfc3339(),
-        };
+        let body = create_batch(
+            &base_url,
+            vec![
+                batch_item("Write the spec", None),
+                batch_item("Implement the parser", Some("claude-code")),
+            ],
+        )
+        .await;
 
-        TaskServer::success(&response)
+        assert_eq!(body["project_id"], project_id.to_string());
+        assert_eq!(body["created"], 2);
+        assert_eq!(body["failed"], 0);
+        assert_eq!(body["results"][0]["success"], true);
+        assert_eq!(body["results"][0]["started"], false);
+        assert_eq!(body["results"][1]["started"], true);
+        assert_eq!(body["results"][1]["title"], "Implement the parser");
+
+        let bodies = bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies[0].0, "create");
+        assert_eq!(bodies[0].1["project_id"], project_id.to_string());
+        assert_eq!(bodies[1].0, "start");
+        assert_eq!(
+            bodies[1].1["executor_profile_id"]["executor"],
+            "CLAUDE_CODE"
+        );
+        assert_eq!(bodies[1].1["base_branch"], "main");
     }
 
-    #[tool(description = "Stop a running execution run")]
-    async fn stop_execution_run(
-        &self,
-        Parameters(StopExecutionRunRequest { execution_run_id }): Parameters<
-            StopExecutionRunRequest,
-        >,
-    ) -> Result<CallToolResult, ErrorData> {
-        let url = self.url(&format!("/api/execution-runs/{}/stop", execution_run_id));
+    #[tokio::test]
+    async fn tasks_create_reports_an_invalid_executor_without_stopping_the_batch() {
+        let bodies = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let base_url = spawn_batch_mock_api(Uuid::new_v4(), bodies.clone()).await;
 
-        if let Err(e) = self
-            .send_json::<serde_json::Value>(self.client.post(&url))
-            .await
-        {
-            return Ok(e);
-        }
+        let body = create_batch(
+            &base_url,
+            vec![
+                batch_item("First", Some("codex")),
+                batch_item("Second", Some("not-an-agent")),
+                batch_item("Third", None),
+            ],
+        )
+        .await;
 
-        let response = StopExecutionRunResponse {
-            stopped: true,
-            execution_run_id: execution_run_id.to_string(),
+        assert_eq!(body["created"], 2);
+        assert_eq!(body["failed"], 1);
+        let failed = &body["results"][1];
+        assert_eq!(failed["success"], false);
+        assert_eq!(failed["task_id"], serde_json::Value::Null);
+        assert!(
+            failed["error"]["error"]
+                .as_str()
+                .unwrap()
+                .contains("Unknown executor 'not-an-agent'")
+        );
+        assert_eq!(body["results"][2]["success"], true);
+        // The invalid item never reached the API
+        let titles: Vec<_> = bodies
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(kind, body)| match *kind {
+                "start" => body["task"]["title"].clone(),
+                _ => body["title"].clone(),
+            })
+            .collect();
+        assert_eq!(titles, ["First", "Third"]);
+    }
+
+    #[tokio::test]
+    async fn resolve_abort_uses_the_operation_specific_endpoint() {
+        let task = test_task(TaskStatus::InReview);
+        let attempt = test_attempt(&task);
+        let aborts: [Arc<AtomicUsize>; 2] = Default::default();
+        let [rebase_aborts, merge_aborts] = aborts.clone();
+        let in_progress = {
+            let rebase_aborts = rebase_aborts.clone();
+            move || rebase_aborts.load(AtomicOrdering::SeqCst) == 0
+        };
+        let app = Router::new()
+            .route(
+                "/api/task-attempts/{id}",
+                get(prefix_lookup(
+                    attempt.id,
+                    attempt.clone(),
+                    "bbbbbb",
+                    Vec::new(),
+                )),
+            )
+            .route(
+                "/api/task-attempts/{id}/branch-status",
+                get(move || {
+                    let rebasing = in_progress();
+                    std::future::ready(Json(ApiResponse::<serde_json::Value>::success(
+                        serde_json::json!({
+                            "commits_ahead": 1,
+                            "commits_behind": 1,
+                            "has_uncommitted_changes": false,
+                            "head_oid": if rebasing { "0000000" } else { "1234567" },
+                            "uncommitted_count": 0,
+                            "untracked_count": 0,
+                            "target_branch_name": "main",
+                            "remote_commits_behind": null,
+                            "remote_commits_ahead": null,
+                            "merges": [],
+                            "is_rebase_in_progress": rebasing,
+                            "conflict_op": rebasing.then_some("rebase"),
+                            "conflicted_files": if rebasing { vec!["src/lib.rs"] } else { vec![] },
+                        }),
+                    )))
+                }),
+            )
+            .route(
+                "/api/task-attempts/{id}/rebase-abort",
+                post(move || {
+                    rebase_aborts.fetch_add(1, AtomicOrdering::SeqCst);
+                    std::future::ready(Json(ApiResponse::success(())))
+                }),
+            )
+            .route(
+                "/api/task-attempts/{id}/merge-abort",
+                post(move || {
+                    merge_aborts.fetch_add(1, AtomicOrdering::SeqCst);
+                    std::future::ready(Json(ApiResponse::success(())))
+                }),
+            );
+        let server = stub_server(app).await;
+        let abort = || {
+            server.resolve(Parameters(ResolveAttemptRequest {
+                attempt_id: attempt.id.to_string(),
+                action: ResolveAction::Abort,
+            }))
         };
 
-        TaskServer::success(&response)
-    }
-}
+        let result = abort().await.unwrap();
+        let body = success_body(&result);
+        assert_eq!(body["operation"], "rebase");
+        assert_eq!(body["resolved"], true);
+        assert_eq!(body["head_oid"], "1234567");
+        assert_eq!(body["conflicted_files"], serde_json::json!([]));
+        assert_eq!(aborts[0].load(AtomicOrdering::SeqCst), 1);
+        assert_eq!(aborts[1].load(AtomicOrdering::SeqCst), 0);
 
-#[tool_handler]
-impl ServerHandler for TaskServer {
-    #[allow(clippy::manual_async_fn)]
-    fn initialize(
-        &self,
-        request: InitializeRequestParam,
-        context: RequestContext<RoleServer>,
-    ) -> impl Future<Output = Result<ServerInfo, ErrorData>> + Send + '_ {
-        async move {
-            if context.peer.peer_info().is_none() {
-                context.peer.set_peer_info(request.clone());
-            }
+        // Nothing left to abort
+        assert_eq!(abort().await.unwrap().is_error, Some(true));
+        assert_eq!(aborts[0].load(AtomicOrdering::SeqCst), 1);
+    }
 
-            let requested_version = request.protocol_version.clone();
-            let negotiated_version = match Self::negotiate_protocol_version(&requested_version) {
-                Ok(version) => version,
-                Err(error) => return Err(error),
+    /// Fails the first `failures` calls with `status`, then succeeds with `body`
+    fn flaky<T: Clone + Serialize + Send + Sync + 'static>(
+        calls: Arc<AtomicUsize>,
+        failures: usize,
+        status: StatusCode,
+        body: T,
+    ) -> impl Fn() -> std::future::Ready<axum::response::Response>
+    + Clone
+    + Send
+    + Sync
+    + 'static {
+        move || {
+            let response = if calls.fetch_add(1, AtomicOrdering::SeqCst) < failures {
+                status.into_response()
+            } else {
+                Json(ApiResponse::<T>::success(body.clone())).into_response()
             };
+            std::future::ready(response)
+        }
+    }
 
-            Self::log_downgrade_if_needed(&requested_version, &negotiated_version);
-            self.set_negotiated_protocol_version(negotiated_version.clone());
+    #[tokio::test]
+    async fn transient_failures_are_retried_only_when_safe() {
+        let task = test_task(TaskStatus::Todo);
+        let counters: [Arc<AtomicUsize>; 4] = Default::default();
+        let [gets, stops, posts, rejected] = counters.clone();
+        let app = Router::new()
+            .route(
+                "/api/tasks/{id}",
+                get(flaky(gets, 2, StatusCode::SERVICE_UNAVAILABLE, task.clone())),
+            )
+            .route(
+                "/api/execution-runs/{id}/stop",
+                post(flaky(stops, 2, StatusCode::TOO_MANY_REQUESTS, ())),
+            )
+            .route(
+                "/api/tasks",
+                post(flaky(posts, 2, StatusCode::SERVICE_UNAVAILABLE, ())),
+            )
+            .route(
+                "/api/projects",
+                get(flaky(rejected, 2, StatusCode::BAD_REQUEST, ())),
+            );
+        let server = stub_server(app).await.with_retry_policy(RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        });
+        let calls = |i: usize| counters[i].load(AtomicOrdering::SeqCst);
 
-            Ok(self.server_info_for_version(negotiated_version))
-        }
+        // GETs recover after two 503s
+        assert_eq!(server.resolve_task(&task.id.to_string()).await.unwrap().id, task.id);
+        assert_eq!(calls(0), 3);
+
+        // Stopping a run is marked retry-safe, so it rides out rate limiting
+        let stopped = server
+            .stop_execution_run(Parameters(StopExecutionRunRequest {
+                execution_run_id: Uuid::new_v4(),
+            }))
+            .await
+            .unwrap();
+        assert_ne!(stopped.is_error, Some(true));
+        assert_eq!(calls(1), 3);
+
+        // Other POSTs and 4xx responses fail on the first attempt
+        let created = server
+            .send_json::<serde_json::Value>(server.client.post(server.url("/api/tasks")))
+            .await;
+        assert!(created.is_err());
+        assert_eq!(calls(2), 1);
+        assert_eq!(server.list_projects().await.unwrap().is_error, Some(true));
+        assert_eq!(calls(3), 1);
     }
 
-    /// Returns server info that reflects the currently negotiated protocol version so
-    /// any follow-up responses stay aligned with the handshake.
-    fn get_info(&self) -> ServerInfo {
-        let protocol_version = self.current_protocol_version();
-        self.server_info_for_version(protocol_version)
+    async fn spawn_mock_api_without_github() -> String {
+        let info = serde_json::json!({
+            "config": {"github": {"pat": null, "oauth_token": null, "username": null}},
+            "executors": {
+                "CLAUDE_CODE": {"DEFAULT": {"CLAUDE_CODE": {}}, "PLAN": {"CLAUDE_CODE": {}}},
+            },
+        });
+        let omni_status = serde_json::json!({
+            "enabled": true,
+            "config": {
+                "enabled": true,
+                "host": "https://omni.example.com",
+                "api_key": "omni-key",
+                "instance": "forge",
+                "recipient": "+15550000000",
+            },
+        });
+        let app = Router::new()
+            .route("/api/health", get(respond("OK".to_string())))
+            .route(
+                "/api/info",
+                get(move || {
+                    let info = info.clone();
+                    async move { Json(ApiResponse::<serde_json::Value>::success(info)) }
+                }),
+            )
+            .route(
+                "/api/forge/omni/status",
+                get(move || {
+                    let omni_status = omni_status.clone();
+                    async move { Json(omni_status) }
+                }),
+            )
+            .route(
+                "/api/projects",
+                get(|| async { Json(ApiResponse::<Vec<Project>>::success(Vec::new())) }),
+            );
+
+        serve(app).await
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use rmcp::model::ErrorCode;
+    #[test]
+    fn mcp_servers_are_parsed_from_config_without_secrets() {
+        let config = serde_json::json!({
+            "mcp_config": {
+                "servers": {
+                    "forge": {
+                        "command": "npx",
+                        "args": ["-y", "automagik-forge", "--mcp"],
+                        "env": { "FORGE_TOKEN": "secret" }
+                    },
+                    "context7": {
+                        "url": "https://mcp.context7.com/mcp",
+                        "headers": { "Authorization": "Bearer secret" }
+                    }
+                },
+                "servers_path": ["mcpServers"],
+                "template": {},
+                "preconfigured": {},
+                "is_toml_config": false
+            },
+            "config_path": "/home/dev/.claude.json"
+        });
 
-    use super::*;
+        let info = McpServerInfo::from_config("CLAUDE_CODE", &config);
+        assert_eq!(info.executor, "CLAUDE_CODE");
+        assert_eq!(info.config_path.as_deref(), Some("/home/dev/.claude.json"));
+        let names: Vec<_> = info.servers.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["context7", "forge"]);
+        assert_eq!(info.servers[0].url.as_deref(), Some("https://mcp.context7.com/mcp"));
+        assert_eq!(info.servers[1].command.as_deref(), Some("npx"));
+        assert!(!serde_json::to_string(&info).unwrap().contains("secret"));
 
-    fn custom_protocol_version(version: &str) -> ProtocolVersion {
-        serde_json::from_str::<ProtocolVersion>(&format!("\"{version}\"")).unwrap()
+        let empty = serde_json::json!({
+            "mcp_config": { "servers": {}, "servers_path": ["mcp_servers"] },
+            "config_path": "/home/dev/.codex/config.toml"
+        });
+        assert!(McpServerInfo::from_config("CODEX", &empty).servers.is_empty());
     }
 
-    #[test]
-    fn client_requesting_latest_version_receives_latest() {
-        let negotiated =
-            TaskServer::negotiate_protocol_version(&ProtocolVersion::V_2025_03_26).unwrap();
-        assert_eq!(negotiated, ProtocolVersion::V_2025_03_26);
+    #[tokio::test]
+    async fn health_reports_a_live_api() {
+        let app = Router::new().route("/api/health", get(crate::routes::health::health_check));
+        let server = stub_server(app).await;
+        server.set_negotiated_protocol_version(ProtocolVersion::V_2024_11_05);
+
+        let report = success_body(&server.health().await.unwrap());
+        assert_eq!(report["reachable"], true);
+        assert_eq!(report["api_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            report["protocol_version"],
+            ProtocolVersion::V_2024_11_05.to_string()
+        );
+        assert!(report["latency_ms"].is_u64());
+        assert!(report["error"].is_null());
+        assert_eq!(report["suggestions"], serde_json::json!([]));
     }
 
-    #[test]
-    fn client_requesting_older_version_negotiates_down() {
-        let negotiated =
-            TaskServer::negotiate_protocol_version(&ProtocolVersion::V_2024_11_05).unwrap();
-        assert_eq!(negotiated, ProtocolVersion::V_2024_11_05);
+    #[tokio::test]
+    async fn health_reports_a_dead_api_with_suggestions() {
+        // Bind and release a port so nothing is listening on it
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let server = TaskServer::new(&base_url);
+
+        let report = success_body(&server.health().await.unwrap());
+        assert_eq!(report["reachable"], false);
+        assert!(report["api_version"].is_null());
+        assert!(report["error"].is_string());
+        let suggestions = report["suggestions"].as_array().unwrap();
+        assert!(!suggestions.is_empty());
+        assert!(
+            suggestions
+                .iter()
+                .any(|s| s.as_str().unwrap().contains(&base_url))
+        );
     }
 
-    #[test]
-    fn client_requesting_newer_version_falls_back_to_latest() {
-        let version = custom_protocol_version("2026-01-01");
-        let negotiated = TaskServer::negotiate_protocol_version(&version).unwrap();
-        assert_eq!(negotiated, ProtocolVersion::V_2025_03_26);
+    #[tokio::test]
+    async fn health_reports_a_downgraded_protocol_version() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let session = TaskServer::new(&base_url).new_session();
+
+        // Older than the latest supported version, newer than the one below it
+        let (_server, _client) =
+            initialize_session(session.clone(), custom_protocol_version("2025-01-15")).await;
+        assert_eq!(
+            session.current_protocol_version(),
+            ProtocolVersion::V_2024_11_05
+        );
+
+        let report = success_body(&session.health().await.unwrap());
+        assert_eq!(report["protocol_version"], "2024-11-05");
+        assert_eq!(
+            report["supported_protocol_versions"],
+            serde_json::json!(["2025-03-26", "2024-11-05"])
+        );
     }
 
-    #[test]
-    fn client_requesting_too_old_version_receives_error() {
-        let version = custom_protocol_version("2023-01-01");
-        let error = TaskServer::negotiate_protocol_version(&version).unwrap_err();
-        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    #[tokio::test]
+    async fn doctor_flags_missing_github_token() {
+        let server = TaskServer::new(&spawn_mock_api_without_github().await);
+
+        let result = server.doctor().await.unwrap();
+        let report = success_body(&result);
+
+        assert_eq!(report["status"], "fail");
+        let checks = report["checks"].as_array().unwrap();
+        assert_eq!(checks.len(), 5);
+        for check in checks {
+            if check["name"] == "github" {
+                assert_eq!(check["status"], "fail");
+                assert!(check["hint"].is_string());
+            } else {
+                assert_eq!(check["status"], "pass", "{check}");
+            }
+        }
     }
 
-    #[test]
-    fn get_info_reflects_negotiated_version() {
-        let server = TaskServer::new("http://example.com");
-        server.set_negotiated_protocol_version(ProtocolVersion::V_2024_11_05);
-        let info = server.get_info();
-        assert_eq!(info.protocol_version, ProtocolVersion::V_2024_11_05);
+    #[tokio::test]
+    async fn clone_project_registers_the_clone_and_reports_clone_errors() {
+        let bodies = Arc::new(std::sync::Mutex::new(Vec::<serde_json::Value>::new()));
+        let app = Router::new().route(
+            "/api/projects",
+            post({
+                let bodies = bodies.clone();
+                move |Json(body): Json<serde_json::Value>| {
+                    bodies.lock().unwrap().push(body.clone());
+                    let response = if body["clone_url"] == "https://github.com/acme/private.git" {
+                        Json(
+                            ApiResponse::<Project, ProjectCloneError>::error_with_message_and_data(
+                                "Failed to clone https://github.com/acme/private.git: authentication required",
+                                ProjectCloneError::AuthFailed {
+                                    message: "authentication required".to_string(),
+                                },
+                            ),
+                        )
+                    } else {
+                        Json(ApiResponse::success(Project {
+                            id: Uuid::new_v4(),
+                            name: body["name"].as_str().unwrap().to_string(),
+                            git_repo_path: PathBuf::from("/workspace/widgets"),
+                            setup_script: None,
+                            dev_script: None,
+                            cleanup_script: None,
+                            copy_files: None,
+                            commit_prompt: None,
+                            created_at: Utc::now(),
+                            updated_at: Utc::now(),
+                        }))
+                    };
+                    std::future::ready(response)
+                }
+            }),
+        );
+        let server = stub_server(app).await;
+        let clone = |git_url: &str| {
+            server.clone_project(Parameters(CloneProjectRequest {
+                git_url: git_url.to_string(),
+                target_dir: None,
+                name: None,
+            }))
+        };
+
+        let result = clone(" https://github.com/acme/widgets.git ")
+            .await
+            .unwrap();
+        let project = success_body(&result);
+        assert_eq!(project["name"], "widgets");
+        assert_eq!(project["git_repo_path"], "/workspace/widgets");
+        {
+            let bodies = bodies.lock().unwrap();
+            assert_eq!(
+                bodies[0]["clone_url"],
+                "https://github.com/acme/widgets.git"
+            );
+            assert_eq!(bodies[0]["git_repo_path"], "");
+            assert_eq!(bodies[0]["use_existing_repo"], false);
+        }
+
+        let result = clone("https://github.com/acme/private.git").await.unwrap();
+        assert_eq!(error_code(&result), CLONE_AUTH_FAILED);
+    }
+
+    #[cfg(feature = "cloud")]
+    #[tokio::test]
+    async fn clone_project_clones_a_fixture_repo_and_registers_it() {
+        use forge_core_deployment::Deployment;
+        use forge_core_services::services::config::Config;
+        use git2::{Repository, Signature};
+
+        use crate::DeploymentImpl;
+
+        let root = tempfile::TempDir::new().unwrap();
+        let workspace = tempfile::TempDir::new().unwrap();
+        let deployment = DeploymentImpl::new_at(
+            &root.path().join("db.sqlite"),
+            Config {
+                workspace_dir: Some(workspace.path().to_string_lossy().to_string()),
+                ..Config::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let remote = root.path().join("widgets.git");
+        let repo = Repository::init_bare(&remote).unwrap();
+        let signature = Signature::now("Forge Test", "test@example.com").unwrap();
+        let blob = repo.blob(b"# Widgets\n").unwrap();
+        let mut builder = repo.treebuilder(None).unwrap();
+        builder.insert("README.md", blob, 0o100644).unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        repo.commit(
+            Some("refs/heads/main"),
+            &signature,
+            &signature,
+            "Initial commit",
+            &tree,
+            &[],
+        )
+        .unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+
+        let app = Router::new()
+            .nest("/api", crate::routes::projects::router(&deployment))
+            .with_state(deployment.clone());
+        let server = stub_server(app).await;
+        let clone = || {
+            server.clone_project(Parameters(CloneProjectRequest {
+                git_url: remote.to_string_lossy().to_string(),
+                target_dir: None,
+                name: None,
+            }))
+        };
+
+        let result = clone().await.unwrap();
+        let summary = success_body(&result);
+        assert_eq!(summary["name"], "widgets");
+
+        let checkout = workspace.path().join("widgets");
+        assert_eq!(
+            std::fs::read_to_string(checkout.join("README.md")).unwrap(),
+            "# Widgets\n"
+        );
+        let projects = Project::find_all(&deployment.db().pool).await.unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].git_repo_path, checkout);
+        assert_eq!(summary["id"], projects[0].id.to_string());
+
+        // A second clone would overwrite the first checkout
+        let result = clone().await.unwrap();
+        assert_eq!(error_code(&result), CLONE_PATH_EXISTS);
+        assert_eq!(
+            Project::find_all(&deployment.db().pool)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
     }
 }
@@ -0,0 +1,29 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use forge_core_utils::metrics::{self, MetricKind};
+
+/// Records the latency of every request under its matched route, so ids in paths don't
+/// create a series per resource
+pub async fn record_route_metrics(req: Request, next: Next) -> Response {
+    let route = format!(
+        "{} {}",
+        req.method(),
+        req.extensions()
+            .get::<MatchedPath>()
+            .map_or("unmatched", MatchedPath::as_str)
+    );
+    let started = Instant::now();
+    let response = next.run(req).await;
+    metrics::global().record(
+        MetricKind::Route,
+        &route,
+        started.elapsed(),
+        response.status().is_server_error(),
+    );
+    response
+}
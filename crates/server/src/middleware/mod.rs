@@ -1,3 +1,5 @@
+pub mod metrics;
 pub mod model_loaders;
 
+pub use metrics::*;
 pub use model_loaders::*;
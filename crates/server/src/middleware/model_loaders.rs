@@ -1,17 +1,67 @@
 use axum::{
+    Json,
     extract::{Path, Request, State},
     http::StatusCode,
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
 use forge_core_db::models::{
-    execution_process::ExecutionProcess, execution_run::ExecutionRun, project::Project, tag::Tag,
-    task::Task, task_attempt::TaskAttempt,
+    execution_process::ExecutionProcess,
+    execution_run::ExecutionRun,
+    id_prefix::{IdResolution, MIN_ID_PREFIX_LEN},
+    project::Project,
+    tag::Tag,
+    task::Task,
+    task_attempt::TaskAttempt,
 };
 use forge_core_deployment::Deployment;
+use forge_core_utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::DeploymentImpl;
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Turns the resolution of a path segment holding a full UUID or a UUID
+/// prefix into an id, or into the response to return when it does not
+/// identify exactly one row.
+fn resolve_path_id(
+    kind: &str,
+    reference: &str,
+    resolution: Result<IdResolution, sqlx::Error>,
+) -> Result<Uuid, Response> {
+    match resolution {
+        Ok(IdResolution::Found(id)) => Ok(id),
+        Ok(IdResolution::NotFound) => {
+            tracing::warn!("{} {} not found", kind, reference);
+            Err(StatusCode::NOT_FOUND.into_response())
+        }
+        Ok(IdResolution::Invalid) => Err(ApiError::BadRequest(format!(
+            "Invalid {kind} id '{reference}': expected a UUID or a prefix of at least {MIN_ID_PREFIX_LEN} hex characters"
+        ))
+        .into_response()),
+        Ok(IdResolution::Ambiguous(candidates)) => {
+            let message = format!(
+                "{kind} id prefix '{reference}' is ambiguous; candidates: {}",
+                candidates
+                    .iter()
+                    .map(Uuid::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            // Candidates also go in `error_data` so clients can offer them without parsing
+            Err((
+                StatusCode::CONFLICT,
+                Json(ApiResponse::<(), Vec<Uuid>>::error_with_message_and_data(
+                    &message, candidates,
+                )),
+            )
+                .into_response())
+        }
+        Err(e) => {
+            tracing::error!("Failed to resolve {} {}: {}", kind, reference, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+        }
+    }
+}
 
 pub async fn load_project_middleware(
     State(deployment): State<DeploymentImpl>,
@@ -42,20 +92,23 @@ pub async fn load_project_middleware(
 
 pub async fn load_task_middleware(
     State(deployment): State<DeploymentImpl>,
-    Path(task_id): Path<Uuid>,
+    Path(task_ref): Path<String>,
     request: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
+) -> Result<Response, Response> {
+    let resolution = Task::resolve_id(&deployment.db().pool, &task_ref).await;
+    let task_id = resolve_path_id("Task", &task_ref, resolution)?;
+
     // Load the task and validate it belongs to the project
     let task = match Task::find_by_id(&deployment.db().pool, task_id).await {
         Ok(Some(task)) => task,
         Ok(None) => {
             tracing::warn!("Task {} not found", task_id);
-            return Err(StatusCode::NOT_FOUND);
+            return Err(StatusCode::NOT_FOUND.into_response());
         }
         Err(e) => {
             tracing::error!("Failed to fetch task {}: {}", task_id, e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
         }
     };
 
@@ -69,20 +122,23 @@ pub async fn load_task_middleware(
 
 pub async fn load_task_attempt_middleware(
     State(deployment): State<DeploymentImpl>,
-    Path(task_attempt_id): Path<Uuid>,
+    Path(task_attempt_ref): Path<String>,
     mut request: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
+) -> Result<Response, Response> {
+    let resolution = TaskAttempt::resolve_id(&deployment.db().pool, &task_attempt_ref).await;
+    let task_attempt_id = resolve_path_id("TaskAttempt", &task_attempt_ref, resolution)?;
+
     // Load the TaskAttempt from the database
     let attempt = match TaskAttempt::find_by_id(&deployment.db().pool, task_attempt_id).await {
         Ok(Some(a)) => a,
         Ok(None) => {
             tracing::warn!("TaskAttempt {} not found", task_attempt_id);
-            return Err(StatusCode::NOT_FOUND);
+            return Err(StatusCode::NOT_FOUND.into_response());
         }
         Err(e) => {
             tracing::error!("Failed to fetch TaskAttempt {}: {}", task_attempt_id, e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
         }
     };
 
@@ -0,0 +1,48 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use forge_core_db::models::activity::{ActivityEvent, ActivityQuery};
+use forge_core_deployment::Deployment;
+use forge_core_utils::response::ApiResponse;
+use serde::Deserialize;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_ACTIVITY_LIMIT: i64 = 50;
+const MAX_ACTIVITY_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityParams {
+    pub limit: Option<i64>,
+    pub since: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub include_agents: bool,
+}
+
+/// Recent task, attempt and execution run events across all projects, newest first.
+/// Agent chat tasks are left out unless `include_agents=true`.
+pub async fn get_activity(
+    State(deployment): State<DeploymentImpl>,
+    Query(params): Query<ActivityParams>,
+) -> Result<ResponseJson<ApiResponse<Vec<ActivityEvent>>>, ApiError> {
+    let limit = params.limit.unwrap_or(DEFAULT_ACTIVITY_LIMIT);
+    if limit <= 0 {
+        return Err(ApiError::BadRequest("limit must be a positive number".to_string()));
+    }
+
+    let query = ActivityQuery {
+        limit: limit.min(MAX_ACTIVITY_LIMIT),
+        since: params.since,
+        include_agents: params.include_agents,
+    };
+    let events = ActivityEvent::find_recent(&deployment.db().pool, &query).await?;
+    Ok(ResponseJson(ApiResponse::success(events)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/activity", get(get_activity))
+}
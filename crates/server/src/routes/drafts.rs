@@ -24,8 +24,10 @@ pub async fn stream_project_drafts_ws(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<DraftsQuery>,
 ) -> impl IntoResponse {
+    let shutdown = deployment.shutdown().cancellation_token();
     ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_project_drafts_ws(socket, deployment, query.project_id).await {
+        let handler = handle_project_drafts_ws(socket, deployment, query.project_id);
+        if let Some(Err(e)) = shutdown.run_until_cancelled(handler).await {
             tracing::warn!("drafts WS closed: {}", e);
         }
     })
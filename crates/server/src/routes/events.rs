@@ -8,7 +8,7 @@ use axum::{
     routing::get,
 };
 use forge_core_deployment::Deployment;
-use futures_util::TryStreamExt;
+use futures_util::{StreamExt, TryStreamExt};
 
 use crate::DeploymentImpl;
 
@@ -16,8 +16,11 @@ pub async fn events(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<Sse<impl futures_util::Stream<Item = Result<Event, BoxError>>>, axum::http::StatusCode>
 {
-    // Ask the container service for a combined "history + live" stream
-    let stream = deployment.stream_events().await;
+    // Ask the container service for a combined "history + live" stream, ending it on shutdown
+    let stream = deployment
+        .stream_events()
+        .await
+        .take_until(deployment.shutdown().cancellation_token().cancelled_owned());
     Ok(Sse::new(stream.map_err(|e| -> BoxError { e.into() })).keep_alive(KeepAlive::default()))
 }
 
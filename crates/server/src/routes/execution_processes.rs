@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use anyhow;
 use axum::{
     Extension, Router,
@@ -10,19 +12,21 @@ use axum::{
     routing::{get, post},
 };
 use forge_core_db::models::execution_process::{
-    ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus,
+    ExecutionProcess, ExecutionProcessError, ExecutionProcessRunReason, ExecutionProcessStatus,
 };
 use forge_core_deployment::Deployment;
+use forge_core_executors::logs::NormalizedEntry;
 use forge_core_services::services::container::ContainerService;
 use forge_core_utils::{log_msg::LogMsg, response::ApiResponse};
-use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use serde::Deserialize;
+use futures_util::{SinkExt, Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_execution_process_middleware};
 
 #[derive(Debug, Deserialize)]
 pub struct ExecutionProcessQuery {
+    #[serde(alias = "attempt_id")]
     pub task_attempt_id: Uuid,
     /// If true, include soft-deleted (dropped) processes in results/stream
     #[serde(default)]
@@ -65,8 +69,10 @@ pub async fn stream_raw_logs_ws(
             ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
         })?;
 
+    let shutdown = deployment.shutdown().cancellation_token();
     Ok(ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_raw_logs_ws(socket, deployment, exec_id).await {
+        let handler = handle_raw_logs_ws(socket, deployment, exec_id);
+        if let Some(Err(e)) = shutdown.run_until_cancelled(handler).await {
             tracing::warn!("raw logs WS closed: {}", e);
         }
     }))
@@ -134,6 +140,141 @@ async fn handle_raw_logs_ws(
     Ok(())
 }
 
+/// Entries returned by `/logs` when no `tail` is given
+const DEFAULT_LOG_TAIL: usize = 100;
+const MAX_LOG_TAIL: usize = 1000;
+/// How long `/logs` waits for logs read back from the database to be normalized
+const LOG_TAIL_DEADLINE: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LogTailQuery {
+    /// Number of trailing entries to return (default 100, at most 1000)
+    pub tail: Option<u32>,
+}
+
+/// An entry of a process's log, in the shape `/normalized-logs/ws` uses for conversation entries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", tag = "type", content = "content")]
+pub enum LogEntry {
+    NormalizedEntry(NormalizedEntry),
+    /// A line of output from a process that isn't normalized, such as a setup script
+    Stdout(String),
+    Stderr(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogTail {
+    pub entries: Vec<LogEntry>,
+    /// Entries the process has produced so far, including those not returned
+    pub total_entries: usize,
+    /// True when older entries were dropped to honour `tail`
+    pub truncated: bool,
+    /// True while the process can still produce output
+    pub running: bool,
+}
+
+/// The last entries of a process's log: the normalized conversation of a coding agent, or the
+/// output lines of a script. A running process is read from a snapshot of what it has logged
+/// so far, so the request never waits on new output.
+pub async fn get_log_tail(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<LogTailQuery>,
+) -> Result<ResponseJson<ApiResponse<LogTail>>, ApiError> {
+    let container = deployment.container();
+    let normalized = execution_process.run_reason == ExecutionProcessRunReason::CodingAgent;
+    let stream = match container.get_msg_store_by_id(&execution_process.id).await {
+        Some(store) => {
+            let history = store.get_history().into_iter().map(Ok::<_, std::io::Error>);
+            Some(futures_util::stream::iter(history).boxed())
+        }
+        None if normalized => {
+            container
+                .stream_normalized_logs(&execution_process.id)
+                .await
+        }
+        None => container.stream_raw_logs(&execution_process.id).await,
+    }
+    .ok_or_else(|| ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound))?;
+    let tail = query
+        .tail
+        .map_or(DEFAULT_LOG_TAIL, |tail| tail as usize)
+        .clamp(1, MAX_LOG_TAIL);
+    let running = execution_process.status == ExecutionProcessStatus::Running;
+
+    Ok(ResponseJson(ApiResponse::success(
+        collect_log_tail(stream, normalized, tail, running, LOG_TAIL_DEADLINE).await,
+    )))
+}
+
+/// Reads `stream` until `Finished`, the end of the stream or `deadline`, and keeps the last
+/// `tail` entries: those of the conversation its patches build when `normalized`, otherwise
+/// its output lines with ANSI escapes removed
+async fn collect_log_tail(
+    mut stream: impl Stream<Item = Result<LogMsg, std::io::Error>> + Unpin,
+    normalized: bool,
+    tail: usize,
+    running: bool,
+    deadline: Duration,
+) -> LogTail {
+    let mut conversation = serde_json::json!({ "entries": [] });
+    let mut lines = Vec::new();
+    let replay = async {
+        while let Some(msg) = stream.next().await {
+            match msg {
+                Ok(LogMsg::JsonPatch(patch)) if normalized => {
+                    if let Err(e) = json_patch::patch(&mut conversation, &patch) {
+                        tracing::warn!("skipping log patch that doesn't apply: {}", e);
+                    }
+                }
+                Ok(LogMsg::Stdout(content)) if !normalized => lines.extend(
+                    strip_ansi_escapes::strip_str(&content)
+                        .lines()
+                        .map(|line| LogEntry::Stdout(line.to_string())),
+                ),
+                Ok(LogMsg::Stderr(content)) if !normalized => lines.extend(
+                    strip_ansi_escapes::strip_str(&content)
+                        .lines()
+                        .map(|line| LogEntry::Stderr(line.to_string())),
+                ),
+                Ok(LogMsg::Finished) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("log stream error: {}", e);
+                    break;
+                }
+            }
+        }
+    };
+    if tokio::time::timeout(deadline, replay).await.is_err() {
+        tracing::warn!(
+            "reading logs took longer than {:?}; returning the entries so far",
+            deadline
+        );
+    }
+
+    let mut entries: Vec<LogEntry> = if normalized {
+        // Diffs and other non-log entries don't decode and are left out
+        conversation["entries"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| serde_json::from_value(entry.clone()).ok())
+            .collect()
+    } else {
+        lines
+    };
+    let total_entries = entries.len();
+    let entries = entries.split_off(total_entries.saturating_sub(tail));
+
+    LogTail {
+        truncated: total_entries > entries.len(),
+        entries,
+        total_entries,
+        running,
+    }
+}
+
 pub async fn stream_normalized_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -150,8 +291,10 @@ pub async fn stream_normalized_logs_ws(
     // Convert the error type to anyhow::Error and turn TryStream -> Stream<Result<_, _>>
     let stream = stream.err_into::<anyhow::Error>().into_stream();
 
+    let shutdown = deployment.shutdown().cancellation_token();
     Ok(ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_normalized_logs_ws(socket, stream).await {
+        let handler = handle_normalized_logs_ws(socket, stream);
+        if let Some(Err(e)) = shutdown.run_until_cancelled(handler).await {
             tracing::warn!("normalized logs WS closed: {}", e);
         }
     }))
@@ -197,15 +340,15 @@ pub async fn stream_execution_processes_ws(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ExecutionProcessQuery>,
 ) -> impl IntoResponse {
+    let shutdown = deployment.shutdown().cancellation_token();
     ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_execution_processes_ws(
+        let handler = handle_execution_processes_ws(
             socket,
             deployment,
             query.task_attempt_id,
             query.show_soft_deleted.unwrap_or(false),
-        )
-        .await
-        {
+        );
+        if let Some(Err(e)) = shutdown.run_until_cancelled(handler).await {
             tracing::warn!("execution processes WS closed: {}", e);
         }
     })
@@ -252,6 +395,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/", get(get_execution_process_by_id))
         .route("/stop", post(stop_execution_process))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
+        .route("/logs", get(get_log_tail))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
         .layer(from_fn_with_state(
             deployment.clone(),
@@ -265,3 +409,98 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     Router::new().nest("/execution-processes", task_attempts_router)
 }
+
+#[cfg(test)]
+mod tests {
+    use forge_core_executors::logs::{NormalizedEntryType, utils::patch::ConversationPatch};
+    use futures_util::stream;
+
+    use super::*;
+
+    fn message(content: String) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::AssistantMessage,
+            content,
+            metadata: None,
+        }
+    }
+
+    fn conversation(count: usize) -> Vec<Result<LogMsg, std::io::Error>> {
+        (0..count)
+            .flat_map(|i| {
+                [
+                    Ok(LogMsg::Stdout(format!("raw {i}\n"))),
+                    Ok(LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+                        i,
+                        message(format!("entry {i}")),
+                    ))),
+                ]
+            })
+            .collect()
+    }
+
+    fn content(entry: &LogEntry) -> &str {
+        match entry {
+            LogEntry::NormalizedEntry(entry) => &entry.content,
+            LogEntry::Stdout(line) | LogEntry::Stderr(line) => line,
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_the_last_normalized_entries() {
+        let mut msgs = conversation(250);
+        msgs.push(Ok(LogMsg::JsonPatch(ConversationPatch::replace(
+            249,
+            message("entry 249, edited".to_string()),
+        ))));
+        msgs.push(Ok(LogMsg::Finished));
+
+        let tail =
+            collect_log_tail(stream::iter(msgs), true, 50, false, Duration::from_secs(5)).await;
+
+        assert_eq!(tail.total_entries, 250);
+        assert!(tail.truncated);
+        assert!(!tail.running);
+        assert_eq!(tail.entries.len(), 50);
+        assert_eq!(content(&tail.entries[0]), "entry 200");
+        assert_eq!(content(&tail.entries[48]), "entry 248");
+        assert_eq!(content(&tail.entries[49]), "entry 249, edited");
+        assert!(
+            tail.entries
+                .iter()
+                .all(|entry| matches!(entry, LogEntry::NormalizedEntry(_)))
+        );
+    }
+
+    #[tokio::test]
+    async fn scripts_return_their_output_lines() {
+        let msgs = vec![
+            Ok(LogMsg::Stdout(
+                "\u{1b}[32minstalling\u{1b}[0m\ndone\n".to_string(),
+            )),
+            Ok(LogMsg::Stderr("warn a\n".to_string())),
+            Ok(LogMsg::Finished),
+        ];
+
+        let tail =
+            collect_log_tail(stream::iter(msgs), false, 2, false, Duration::from_secs(5)).await;
+
+        assert_eq!(tail.total_entries, 3);
+        assert!(tail.truncated);
+        assert!(matches!(&tail.entries[0], LogEntry::Stdout(line) if line == "done"));
+        assert!(matches!(&tail.entries[1], LogEntry::Stderr(line) if line == "warn a"));
+    }
+
+    #[tokio::test]
+    async fn stream_that_never_finishes_is_cut_off_at_the_deadline() {
+        let live = stream::iter(conversation(120)).chain(stream::pending());
+
+        let tail = collect_log_tail(live, true, 100, true, Duration::from_millis(20)).await;
+
+        assert!(tail.running);
+        assert_eq!(tail.total_entries, 120);
+        assert_eq!(tail.entries.len(), 100);
+        assert_eq!(content(&tail.entries[99]), "entry 119");
+    }
+}
@@ -9,7 +9,9 @@ use axum::{
     routing::{get, post},
 };
 use forge_core_db::models::{
-    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    execution_process::{
+        ExecutionProcess, ExecutionProcessError, ExecutionProcessRunReason, ExecutionProcessStatus,
+    },
     execution_run::{CreateExecutionRun, ExecutionRun},
     project::Project,
 };
@@ -19,10 +21,13 @@ use forge_core_executors::{
         ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
         coding_agent_initial::CodingAgentInitialRequest,
     },
+    logs::{LogLevel, LogRole, utils::patch::extract_normalized_entry_from_patch},
     profile::ExecutorProfileId,
 };
-use forge_core_services::services::container::ContainerService;
-use forge_core_utils::response::ApiResponse;
+use forge_core_services::services::container::{
+    ContainerService, RunCleanupOptions, RunResumeState, reconcile_run_process,
+};
+use forge_core_utils::{log_msg::LogMsg, response::ApiResponse};
 use serde::{Deserialize, Serialize};
 use sqlx::Error as SqlxError;
 use ts_rs_forge::TS;
@@ -30,6 +35,33 @@ use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_execution_run_middleware};
 
+/// Default upper bound on prompt size (in bytes) for a single execution run.
+const DEFAULT_MAX_PROMPT_LENGTH: usize = 100_000;
+
+/// Maximum prompt length, overridable via `FORGE_MAX_PROMPT_LENGTH`.
+fn max_prompt_length() -> usize {
+    std::env::var("FORGE_MAX_PROMPT_LENGTH")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_MAX_PROMPT_LENGTH)
+}
+
+/// Trim the prompt and reject empty or oversized input before an executor is launched.
+fn validate_prompt(prompt: &str, max_length: usize) -> Result<String, ApiError> {
+    let trimmed = prompt.trim();
+    if trimmed.is_empty() {
+        return Err(ApiError::BadRequest("Prompt must not be empty".to_string()));
+    }
+    if trimmed.len() > max_length {
+        return Err(ApiError::BadRequest(format!(
+            "Prompt is too long ({} bytes). Maximum allowed is {max_length} bytes",
+            trimmed.len()
+        )));
+    }
+    Ok(trimmed.to_string())
+}
+
 // ============================================================================
 // Request/Response Types
 // ============================================================================
@@ -53,6 +85,27 @@ pub struct FollowUpRequest {
     pub variant: Option<String>,
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct CleanupExecutionRunRequest {
+    /// Delete the run's local branch along with its worktree (default: true)
+    pub delete_branch: Option<bool>,
+    /// Also delete the branch from the remote (default: false)
+    pub delete_remote_branch: Option<bool>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct CleanupExecutionRunResponse {
+    pub worktree_removed: bool,
+    pub local_branch_deleted: bool,
+    pub remote_branch_deleted: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResumeExecutionRunResponse {
+    pub state: RunResumeState,
+    pub execution_process: ExecutionProcess,
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct ExecutionRunResponse {
     pub execution_run: ExecutionRun,
@@ -88,6 +141,8 @@ pub async fn create_execution_run(
 ) -> Result<ResponseJson<ApiResponse<ExecutionRunResponse>>, ApiError> {
     let pool = &deployment.db().pool;
 
+    let prompt = validate_prompt(&payload.prompt, max_prompt_length())?;
+
     // Validate project exists and get git_repo_path for profile loading
     let project = Project::find_by_id(pool, payload.project_id)
         .await?
@@ -111,8 +166,13 @@ pub async fn create_execution_run(
             .await;
     }
 
-    // Determine base branch (defaults to "main")
-    let base_branch = payload.base_branch.unwrap_or_else(|| "main".to_string());
+    // Determine base branch (defaults to the repository's default branch)
+    let base_branch = match payload.base_branch.filter(|b| !b.trim().is_empty()) {
+        Some(branch) => branch,
+        None => deployment
+            .git()
+            .get_default_branch_name(&project.git_repo_path)?,
+    };
 
     // Generate branch name for the run
     let run_id = Uuid::new_v4();
@@ -123,7 +183,7 @@ pub async fn create_execution_run(
         executor: payload.executor_profile_id.executor,
         variant: payload.executor_profile_id.variant.clone(),
         base_branch: base_branch.clone(),
-        prompt: payload.prompt.clone(),
+        prompt,
     };
 
     let execution_run =
@@ -216,14 +276,145 @@ pub async fn follow_up(
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
+/// Server-side filters for the execution run log stream
+#[derive(Debug, Default, Deserialize)]
+pub struct RunLogsQuery {
+    /// Only send conversation entries from this role. Roles only exist on normalized entries,
+    /// so this streams those instead of raw stdout/stderr.
+    pub role: Option<LogRole>,
+    /// Drop messages below this level; raw stdout counts as info and stderr as warn
+    pub min_level: Option<LogLevel>,
+}
+
+impl RunLogsQuery {
+    /// Whether `msg` passes the filters. Session ids and the finished marker always do; patches
+    /// without a conversation entry have no role, so they only pass when no role is requested.
+    fn allows(&self, msg: &LogMsg) -> bool {
+        let (role, level) = match msg {
+            LogMsg::Stdout(_) => (None, LogLevel::Info),
+            LogMsg::Stderr(_) => (None, LogLevel::Warn),
+            LogMsg::JsonPatch(patch) => match extract_normalized_entry_from_patch(patch) {
+                Some((_, entry)) => (Some(entry.entry_type.role()), entry.entry_type.level()),
+                None => (None, LogLevel::Info),
+            },
+            LogMsg::SessionId(_) | LogMsg::Finished => return true,
+        };
+        self.role.is_none_or(|wanted| role == Some(wanted))
+            && self.min_level.is_none_or(|min_level| level >= min_level)
+    }
+}
+
+/// Applies a [`RunLogsQuery`] to a log stream. Conversation patches address entries by index,
+/// so the patches that pass are rewritten to the entry's position among the entries the client
+/// actually received; otherwise every dropped entry would shift the client's array out of step.
+#[derive(Debug)]
+struct RunLogsFilter {
+    query: RunLogsQuery,
+    /// Source index of every entry sent to the client, in client order
+    sent: Vec<usize>,
+}
+
+impl RunLogsFilter {
+    fn new(query: RunLogsQuery) -> Self {
+        Self {
+            query,
+            sent: Vec::new(),
+        }
+    }
+
+    /// The message to send in place of `msg`, if any
+    fn apply(&mut self, msg: LogMsg) -> Option<LogMsg> {
+        let allowed = self.query.allows(&msg);
+        let LogMsg::JsonPatch(patch) = &msg else {
+            return allowed.then_some(msg);
+        };
+        let Some((op, index, value)) = single_entry_op(patch) else {
+            return allowed.then_some(msg);
+        };
+
+        match op.as_str() {
+            "add" => {
+                // Adding into the source array shifts every later entry up by one
+                for sent in self.sent.iter_mut().filter(|sent| **sent >= index) {
+                    *sent += 1;
+                }
+                if !allowed {
+                    return None;
+                }
+                let position = self.sent.partition_point(|sent| *sent < index);
+                self.sent.insert(position, index);
+                entry_patch("add", position, value)
+            }
+            "replace" => match self.sent.binary_search(&index) {
+                Ok(position) if allowed => entry_patch("replace", position, value),
+                // The entry changed into one the client doesn't want
+                Ok(position) => {
+                    self.sent.remove(position);
+                    entry_patch("remove", position, None)
+                }
+                Err(position) if allowed => {
+                    self.sent.insert(position, index);
+                    entry_patch("add", position, value)
+                }
+                Err(_) => None,
+            },
+            "remove" => {
+                let removed = self.sent.binary_search(&index).ok();
+                if let Some(position) = removed {
+                    self.sent.remove(position);
+                }
+                for sent in self.sent.iter_mut().filter(|sent| **sent > index) {
+                    *sent -= 1;
+                }
+                removed.and_then(|position| entry_patch("remove", position, None))
+            }
+            _ => allowed.then_some(msg),
+        }
+    }
+}
+
+/// The operation, entry index and value of a patch made of a single `/entries/N` operation
+fn single_entry_op(
+    patch: &json_patch::Patch,
+) -> Option<(String, usize, Option<serde_json::Value>)> {
+    let value = serde_json::to_value(patch).ok()?;
+    let [op] = value.as_array()?.as_slice() else {
+        return None;
+    };
+    let index = op
+        .get("path")?
+        .as_str()?
+        .strip_prefix("/entries/")?
+        .parse::<usize>()
+        .ok()?;
+    Some((
+        op.get("op")?.as_str()?.to_string(),
+        index,
+        op.get("value").cloned(),
+    ))
+}
+
+fn entry_patch(op: &str, index: usize, value: Option<serde_json::Value>) -> Option<LogMsg> {
+    let mut operation = serde_json::json!({ "op": op, "path": format!("/entries/{index}") });
+    if let Some(value) = value {
+        operation["value"] = value;
+    }
+    serde_json::from_value(serde_json::json!([operation]))
+        .ok()
+        .map(LogMsg::JsonPatch)
+}
+
 /// Stream logs for an execution run via WebSocket
 pub async fn stream_logs_ws(
     ws: WebSocketUpgrade,
     Extension(execution_run): Extension<ExecutionRun>,
     State(deployment): State<DeploymentImpl>,
+    Query(filter): Query<RunLogsQuery>,
 ) -> impl IntoResponse {
+    let shutdown = deployment.shutdown().cancellation_token();
     ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_logs_ws(socket, deployment, execution_run).await {
+        let handler = handle_logs_ws(socket, deployment, execution_run, filter);
+        if let Some(Err(e)) = shutdown.run_until_cancelled(handler).await {
             tracing::warn!("Execution run logs WS closed: {}", e);
         }
     })
@@ -233,17 +424,24 @@ async fn handle_logs_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
     execution_run: ExecutionRun,
+    filter: RunLogsQuery,
 ) -> anyhow::Result<()> {
-    use forge_core_utils::log_msg::LogMsg;
-    use futures_util::{SinkExt, StreamExt, TryStreamExt};
+    use futures_util::{SinkExt, StreamExt, TryStreamExt, future};
 
-    let stream = deployment
-        .container()
-        .stream_raw_logs_for_run(&execution_run.id)
-        .await
-        .ok_or_else(|| anyhow::anyhow!("No active process for execution run"))?;
+    let container = deployment.container();
+    let stream = if filter.role.is_some() {
+        container
+            .stream_normalized_logs_for_run(&execution_run.id)
+            .await
+    } else {
+        container.stream_raw_logs_for_run(&execution_run.id).await
+    }
+    .ok_or_else(|| anyhow::anyhow!("No active process for execution run"))?;
 
-    let mut stream = stream.map_ok(|msg: LogMsg| msg.to_ws_message_unchecked());
+    let mut filter = RunLogsFilter::new(filter);
+    let mut stream = stream
+        .try_filter_map(move |msg| future::ready(Ok(filter.apply(msg))))
+        .map_ok(|msg: LogMsg| msg.to_ws_message_unchecked());
 
     let (mut sender, mut receiver) = socket.split();
 
@@ -273,6 +471,46 @@ async fn handle_logs_ws(
     Ok(())
 }
 
+/// Re-attach to an execution run, e.g. after a server restart. A process this server still runs
+/// keeps streaming; one that was orphaned is marked failed so its logs stream from the database.
+pub async fn resume_execution_run(
+    Extension(execution_run): Extension<ExecutionRun>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ResumeExecutionRunResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let process = ExecutionProcess::find_latest_by_execution_run_and_run_reason(
+        pool,
+        execution_run.id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?
+    .ok_or(ExecutionProcessError::ExecutionProcessNotFound)?;
+
+    let attached = deployment
+        .container()
+        .is_process_attached(&process.id)
+        .await;
+    let state = reconcile_run_process(pool, &process, attached).await?;
+    if state == RunResumeState::Orphaned {
+        tracing::info!(
+            "Marked orphaned process {} of execution run {} as failed",
+            process.id,
+            execution_run.id
+        );
+    }
+
+    let execution_process = ExecutionProcess::find_by_id(pool, process.id)
+        .await?
+        .ok_or(ExecutionProcessError::ExecutionProcessNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(
+        ResumeExecutionRunResponse {
+            state,
+            execution_process,
+        },
+    )))
+}
+
 /// Stop an execution run
 pub async fn stop_execution_run(
     Extension(execution_run): Extension<ExecutionRun>,
@@ -307,6 +545,39 @@ pub async fn stop_execution_run(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Remove an execution run's worktree and optionally its local/remote branch
+pub async fn cleanup_execution_run(
+    Extension(execution_run): Extension<ExecutionRun>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CleanupExecutionRunRequest>,
+) -> Result<ResponseJson<ApiResponse<CleanupExecutionRunResponse>>, ApiError> {
+    let delete_remote_branch = payload.delete_remote_branch.unwrap_or(false);
+    let github_token = if delete_remote_branch {
+        deployment.config().read().await.github.token()
+    } else {
+        None
+    };
+
+    let options = RunCleanupOptions {
+        delete_branch: payload.delete_branch.unwrap_or(true),
+        delete_remote_branch,
+        github_token,
+    };
+
+    let outcome = deployment
+        .container()
+        .cleanup_run(&execution_run, options)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        CleanupExecutionRunResponse {
+            worktree_removed: outcome.worktree_removed,
+            local_branch_deleted: outcome.local_branch_deleted,
+            remote_branch_deleted: outcome.remote_branch_deleted,
+        },
+    )))
+}
+
 /// Get execution processes for a run
 pub async fn get_execution_run_processes(
     Extension(execution_run): Extension<ExecutionRun>,
@@ -331,8 +602,10 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/", get(get_execution_run))
         .route("/follow-up", post(follow_up))
         .route("/logs/ws", get(stream_logs_ws))
+        .route("/resume", post(resume_execution_run))
         .route("/stop", post(stop_execution_run))
         .route("/processes", get(get_execution_run_processes))
+        .route("/cleanup", post(cleanup_execution_run))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_execution_run_middleware,
@@ -344,3 +617,188 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     Router::new().nest("/execution-runs", execution_runs_router)
 }
+
+#[cfg(test)]
+mod tests {
+    use forge_core_executors::logs::{
+        NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch,
+    };
+
+    use super::*;
+
+    #[test]
+    fn empty_prompt_is_rejected() {
+        let err = validate_prompt("   \n\t ", 100).unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest(msg) if msg.contains("empty")));
+    }
+
+    #[test]
+    fn oversized_prompt_is_rejected_with_limit() {
+        let prompt = "a".repeat(101);
+        let err = validate_prompt(&prompt, 100).unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest(msg) if msg.contains("100")));
+    }
+
+    #[test]
+    fn valid_prompt_is_trimmed() {
+        let prompt = validate_prompt("  write a commit message  \n", 100).unwrap();
+        assert_eq!(prompt, "write a commit message");
+    }
+
+    fn entry(index: usize, entry_type: NormalizedEntryType, content: &str) -> LogMsg {
+        LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+            index,
+            NormalizedEntry {
+                timestamp: None,
+                entry_type,
+                content: content.to_string(),
+                metadata: None,
+            },
+        ))
+    }
+
+    fn conversation() -> Vec<LogMsg> {
+        vec![
+            LogMsg::SessionId("session-1".to_string()),
+            entry(0, NormalizedEntryType::SystemMessage, "model: sonnet"),
+            entry(1, NormalizedEntryType::UserMessage, "fix the login bug"),
+            entry(2, NormalizedEntryType::Thinking, "looking at auth.rs"),
+            entry(
+                3,
+                NormalizedEntryType::AssistantMessage,
+                "The redirect is wrong",
+            ),
+            entry(
+                4,
+                NormalizedEntryType::ErrorMessage {
+                    error_type: NormalizedEntryError::Other,
+                },
+                "rate limited",
+            ),
+            LogMsg::Finished,
+        ]
+    }
+
+    fn filter(query: &str) -> RunLogsQuery {
+        let uri: axum::http::Uri = format!("/logs/ws?{query}").parse().unwrap();
+        Query::<RunLogsQuery>::try_from_uri(&uri).unwrap().0
+    }
+
+    fn contents(filter: RunLogsQuery, messages: Vec<LogMsg>) -> Vec<String> {
+        let mut filter = RunLogsFilter::new(filter);
+        messages
+            .into_iter()
+            .filter_map(|msg| filter.apply(msg))
+            .map(|msg| match msg {
+                LogMsg::JsonPatch(patch) => extract_normalized_entry_from_patch(&patch)
+                    .map(|(_, entry)| entry.content)
+                    .unwrap(),
+                LogMsg::Stdout(line) | LogMsg::Stderr(line) => line,
+                other => other.name().to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn role_filter_keeps_only_that_roles_entries() {
+        assert_eq!(
+            contents(filter("role=assistant"), conversation()),
+            vec![
+                "session_id",
+                "looking at auth.rs",
+                "The redirect is wrong",
+                "finished"
+            ]
+        );
+    }
+
+    #[test]
+    fn min_level_filter_drops_quieter_messages() {
+        assert_eq!(
+            contents(filter("role=assistant&min_level=info"), conversation()),
+            vec!["session_id", "The redirect is wrong", "finished"]
+        );
+        assert_eq!(
+            contents(filter("min_level=warn"), conversation()),
+            vec!["session_id", "rate limited", "finished"]
+        );
+
+        let raw = vec![
+            LogMsg::Stdout("compiling".to_string()),
+            LogMsg::Stderr("warning: unused import".to_string()),
+        ];
+        assert_eq!(
+            contents(filter("min_level=warn"), raw.clone()),
+            vec!["warning: unused import"]
+        );
+        assert_eq!(contents(filter(""), raw).len(), 2);
+    }
+
+    /// Applies `messages` to a fresh client document and returns the entries' contents
+    fn client_entries(messages: impl IntoIterator<Item = LogMsg>) -> Vec<String> {
+        let mut document = serde_json::json!({ "entries": [] });
+        for msg in messages {
+            if let LogMsg::JsonPatch(patch) = msg {
+                json_patch::patch(&mut document, &patch).unwrap();
+            }
+        }
+        document["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["content"]["content"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn filtered_patches_apply_cleanly_to_the_client_document() {
+        let replace = |index, entry_type, content: &str| {
+            LogMsg::JsonPatch(ConversationPatch::replace(
+                index,
+                NormalizedEntry {
+                    timestamp: None,
+                    entry_type,
+                    content: content.to_string(),
+                    metadata: None,
+                },
+            ))
+        };
+        let mut source = conversation();
+        source.extend([
+            entry(5, NormalizedEntryType::AssistantMessage, "Fixed it"),
+            replace(
+                3,
+                NormalizedEntryType::AssistantMessage,
+                "The redirect was wrong",
+            ),
+            // An entry that only becomes wanted once it is replaced
+            replace(0, NormalizedEntryType::AssistantMessage, "Starting"),
+            // And one that stops being wanted
+            replace(2, NormalizedEntryType::SystemMessage, "looked at auth.rs"),
+            LogMsg::JsonPatch(ConversationPatch::remove(4)),
+            entry(4, NormalizedEntryType::AssistantMessage, "Retrying"),
+        ]);
+
+        // Unfiltered, the client ends up with the source document
+        let everything = client_entries(source.clone());
+        assert_eq!(
+            everything,
+            [
+                "Starting",
+                "fix the login bug",
+                "looked at auth.rs",
+                "The redirect was wrong",
+                "Retrying",
+                "Fixed it"
+            ]
+        );
+
+        // Filtered, it ends up with exactly the source's assistant entries, in order
+        let mut filter = RunLogsFilter::new(filter("role=assistant"));
+        let filtered = client_entries(source.into_iter().filter_map(|msg| filter.apply(msg)));
+        assert_eq!(
+            filtered,
+            ["Starting", "The redirect was wrong", "Retrying", "Fixed it"]
+        );
+    }
+}
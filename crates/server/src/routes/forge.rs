@@ -6,24 +6,50 @@
 //! - Project branch status and git operations
 //! - GitHub releases
 //! - Agent task management
+//! - Orphaned worktree detection
+
+use std::{
+    path::PathBuf,
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
 
 use axum::{
     Json, Router,
-    extract::{Path, Query, State},
+    extract::{
+        Path, Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
     http::StatusCode,
+    response::IntoResponse,
     routing::{get, post},
 };
-use forge_core_db::models::project::Project;
+use chrono::{DateTime, Utc};
+use forge_core_db::models::{
+    forge_agent::{AgentConsistencyReport, ForgeAgent},
+    project::Project,
+    project_fetch_state::ProjectFetchState,
+};
 use forge_core_deployment::Deployment;
 use forge_core_executors::profile::ExecutorConfigs;
 use forge_core_services::services::{
+    commit_validator::{CommitValidator, ValidationWarning},
     forge_config::ForgeProjectSettings,
-    omni::{OmniConfig, OmniInstance, OmniService},
+    git::{BranchStatus, GitService},
+    github_http::{Conditional, GitHubHttpClient, GitHubHttpError},
+    omni::{
+        InvalidOmniHost, OmniConfig, OmniInstance, OmniNotification, OmniService,
+        RecipientType, RecipientValidation, normalize_omni_host,
+    },
+    orphan_worktrees::{OrphanScanOptions, OrphanWorktreeReport, scan_orphaned_worktrees},
+    profile_loader::{GenieProfileLoader, ProfileValidationIssue, ProfilesReloaded},
+    worktree_manager::WorktreeManager,
 };
 use forge_core_utils::response::ApiResponse;
+use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
-use sqlx::Row;
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
@@ -49,18 +75,39 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/forge/projects/{project_id}/profiles",
             get(get_project_profiles),
         )
+        .route(
+            "/forge/projects/{project_id}/profiles/validate",
+            get(validate_project_profiles),
+        )
+        .route(
+            "/forge/projects/{project_id}/profiles/stream/ws",
+            get(stream_project_profiles_ws),
+        )
         // Omni routes
         .route("/forge/omni/status", get(get_omni_status))
         .route("/forge/omni/instances", get(list_omni_instances))
         .route("/forge/omni/validate", post(validate_omni_config))
+        .route("/forge/omni/validate-recipient", post(validate_omni_recipient))
         .route("/forge/omni/notifications", get(list_omni_notifications))
         // GitHub releases
         .route("/forge/releases", get(get_github_releases))
+        .route("/forge/releases/status", get(get_github_releases_status))
         // Agent management
         .route(
             "/forge/agents",
             get(get_forge_agents).post(create_forge_agent),
         )
+        .route(
+            "/forge/agents/consistency",
+            get(get_agent_consistency).post(repair_agent_consistency),
+        )
+        // Worktree maintenance
+        .route(
+            "/forge/worktrees/orphans",
+            get(get_orphaned_worktrees).post(remove_orphaned_worktrees),
+        )
+        // Commit messages
+        .route("/forge/commit/validate", post(validate_commit_message))
         .with_state(deployment.clone())
 }
 
@@ -82,10 +129,29 @@ async fn get_forge_config(
         })
 }
 
+/// Normalizes the Omni host of incoming settings before they are persisted
+fn normalize_omni_settings(
+    mut settings: ForgeProjectSettings,
+) -> Result<ForgeProjectSettings, InvalidOmniHost> {
+    settings.omni_config = settings.omni_config.map(OmniConfig::normalized).transpose()?;
+    Ok(settings)
+}
+
 async fn update_forge_config(
     State(deployment): State<DeploymentImpl>,
     Json(settings): Json<ForgeProjectSettings>,
 ) -> Result<Json<ApiResponse<ForgeProjectSettings>>, StatusCode> {
+    let settings = match normalize_omni_settings(settings) {
+        Ok(settings) => settings,
+        Err(e) => return Ok(Json(ApiResponse::error(&e.to_string()))),
+    };
+    if let Some(repo) = &settings.releases_repo
+        && !is_owner_repo(repo)
+    {
+        return Ok(Json(ApiResponse::error(&format!(
+            "releases_repo must look like owner/repo, got '{repo}'"
+        ))));
+    }
     deployment
         .forge_config()
         .set_global_settings(&settings)
@@ -126,6 +192,10 @@ async fn update_project_settings(
     State(deployment): State<DeploymentImpl>,
     Json(settings): Json<ForgeProjectSettings>,
 ) -> Result<Json<ApiResponse<ForgeProjectSettings>>, StatusCode> {
+    let settings = match normalize_omni_settings(settings) {
+        Ok(settings) => settings,
+        Err(e) => return Ok(Json(ApiResponse::error(&e.to_string()))),
+    };
     deployment
         .forge_config()
         .set_forge_settings(project_id, &settings)
@@ -145,13 +215,83 @@ async fn update_project_settings(
 #[derive(Deserialize)]
 struct BranchStatusQuery {
     base: Option<String>,
+    /// Fetch only this many commits of each remote branch instead of its full history. The
+    /// next fetch without a depth restores the full history.
+    depth: Option<u32>,
+}
+
+/// Fetches `origin`, truncating the history of fetched branches to `depth` commits when given.
+/// Without a depth, a repository left shallow by an earlier fetch gets its full history back,
+/// so sync counts become known again.
+fn fetch_origin(repo_path: &std::path::Path, depth: Option<u32>) -> bool {
+    let mut fetch = std::process::Command::new("git");
+    fetch.current_dir(repo_path).args(["fetch", "origin"]);
+    match depth.filter(|depth| *depth > 0) {
+        Some(depth) => {
+            fetch.arg(format!("--depth={depth}"));
+        }
+        None if is_shallow(repo_path) => {
+            fetch.arg("--unshallow");
+        }
+        None => {}
+    }
+    fetch.output().is_ok_and(|output| output.status.success())
+}
+
+fn is_shallow(repo_path: &std::path::Path) -> bool {
+    std::process::Command::new("git")
+        .current_dir(repo_path)
+        .args(["rev-parse", "--is-shallow-repository"])
+        .output()
+        .is_ok_and(|output| output.status.success() && output.stdout.trim_ascii() == b"true")
+}
+
+/// Counts the commits `upstream` has that `branch` lacks, and the other way round.
+///
+/// A shallow fetch can cut the history between the two, in which case `rev-list` would count
+/// every commit down to the shallow boundary. Without a merge base inside the fetched history
+/// both counts are reported as unknown instead.
+fn behind_ahead(
+    repo_path: &std::path::Path,
+    upstream: &str,
+    branch: &str,
+) -> (Option<usize>, Option<usize>) {
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .current_dir(repo_path)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    if is_shallow(repo_path) && git(&["merge-base", upstream, branch]).is_none() {
+        return (None, None);
+    }
+
+    let range = format!("{upstream}...{branch}");
+    match git(&["rev-list", "--left-right", "--count", &range]) {
+        Some(counts) => {
+            let parts: Vec<&str> = counts.split_whitespace().collect();
+            if parts.len() == 2 {
+                (
+                    parts[0].parse::<usize>().ok(),
+                    parts[1].parse::<usize>().ok(),
+                )
+            } else {
+                (None, None)
+            }
+        }
+        None => (None, None),
+    }
 }
 
 async fn get_project_branch_status(
     Path(project_id): Path<Uuid>,
     Query(query): Query<BranchStatusQuery>,
     State(deployment): State<DeploymentImpl>,
-) -> Result<Json<ApiResponse<Value>>, StatusCode> {
+) -> Result<Json<ApiResponse<BranchStatus>>, StatusCode> {
     use std::process::Command;
 
     let project = match Project::find_by_id(&deployment.db().pool, project_id).await {
@@ -181,36 +321,25 @@ async fn get_project_branch_status(
 
     let target_branch = query.base.as_deref().unwrap_or("main");
 
-    // Fetch from remote
-    let _ = Command::new("git")
-        .current_dir(&project.git_repo_path)
-        .args(["fetch", "origin"])
-        .output();
+    // Fetch from remote, remembering when it last worked so the UI can show staleness
+    let fetched = fetch_origin(&project.git_repo_path, query.depth);
+    let pool = &deployment.db().pool;
+    if fetched
+        && let Err(e) = ProjectFetchState::record_fetch(pool, project_id, Utc::now()).await
+    {
+        tracing::warn!("Failed to record fetch time for project {}: {}", project_id, e);
+    }
+    let last_fetch_at = ProjectFetchState::last_fetch_at(pool, project_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to load fetch time for project {}: {}", project_id, e);
+            None
+        });
 
     // Compare against remote tracking branch
     let remote_branch = format!("origin/{target_branch}");
-    let commits_behind_ahead_output = Command::new("git")
-        .current_dir(&project.git_repo_path)
-        .args([
-            "rev-list",
-            "--left-right",
-            "--count",
-            &format!("{remote_branch}...{current_branch}"),
-        ])
-        .output();
-
-    let (commits_behind, commits_ahead) = match commits_behind_ahead_output {
-        Ok(output) if output.status.success() => {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            let parts: Vec<&str> = output_str.split_whitespace().collect();
-            if parts.len() == 2 {
-                (parts[0].parse::<i32>().ok(), parts[1].parse::<i32>().ok())
-            } else {
-                (None, None)
-            }
-        }
-        _ => (None, None),
-    };
+    let (commits_behind, commits_ahead) =
+        behind_ahead(&project.git_repo_path, &remote_branch, &current_branch);
 
     // Get remote commits behind/ahead
     let upstream_output = Command::new("git")
@@ -221,28 +350,11 @@ async fn get_project_branch_status(
     let (remote_commits_behind, remote_commits_ahead) = match upstream_output {
         Ok(output) if output.status.success() => {
             let remote_tracking_branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            let remote_commits_output = Command::new("git")
-                .current_dir(&project.git_repo_path)
-                .args([
-                    "rev-list",
-                    "--left-right",
-                    "--count",
-                    &format!("{remote_tracking_branch}...{current_branch}"),
-                ])
-                .output();
-
-            match remote_commits_output {
-                Ok(output) if output.status.success() => {
-                    let output_str = String::from_utf8_lossy(&output.stdout);
-                    let parts: Vec<&str> = output_str.split_whitespace().collect();
-                    if parts.len() == 2 {
-                        (parts[0].parse::<i32>().ok(), parts[1].parse::<i32>().ok())
-                    } else {
-                        (None, None)
-                    }
-                }
-                _ => (None, None),
-            }
+            behind_ahead(
+                &project.git_repo_path,
+                &remote_tracking_branch,
+                &current_branch,
+            )
         }
         _ => (None, None),
     };
@@ -260,12 +372,12 @@ async fn get_project_branch_status(
             let uncommitted = status_lines.iter().filter(|l| !l.starts_with("??")).count();
             let untracked = status_lines.iter().filter(|l| l.starts_with("??")).count();
             (
-                !status_lines.is_empty(),
-                Some(uncommitted as i32),
-                Some(untracked as i32),
+                Some(!status_lines.is_empty()),
+                Some(uncommitted),
+                Some(untracked),
             )
         }
-        _ => (false, None, None),
+        _ => (Some(false), None, None),
     };
 
     // Get HEAD commit OID
@@ -281,21 +393,22 @@ async fn get_project_branch_status(
         _ => None,
     };
 
-    let response = json!({
-        "commits_behind": commits_behind,
-        "commits_ahead": commits_ahead,
-        "has_uncommitted_changes": has_uncommitted_changes,
-        "head_oid": head_oid,
-        "uncommitted_count": uncommitted_count,
-        "untracked_count": untracked_count,
-        "target_branch_name": target_branch,
-        "remote_commits_behind": remote_commits_behind,
-        "remote_commits_ahead": remote_commits_ahead,
-        "merges": [],
-        "is_rebase_in_progress": false,
-        "conflict_op": null,
-        "conflicted_files": []
-    });
+    let response = BranchStatus {
+        commits_behind,
+        commits_ahead,
+        has_uncommitted_changes,
+        head_oid,
+        uncommitted_count,
+        untracked_count,
+        target_branch_name: target_branch.to_string(),
+        remote_commits_behind,
+        remote_commits_ahead,
+        merges: Vec::new(),
+        is_rebase_in_progress: false,
+        conflict_op: None,
+        conflicted_files: Vec::new(),
+        last_fetch_at,
+    };
 
     Ok(Json(ApiResponse::success(response)))
 }
@@ -353,14 +466,8 @@ async fn post_project_pull(
         project.git_repo_path
     );
 
-    let pull_output = Command::new("git")
-        .current_dir(&project.git_repo_path)
-        .args(["pull", "--rebase", "origin", &current_branch])
-        .output();
-
-    match pull_output {
-        Ok(output) if output.status.success() => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
+    match pull_rebase(deployment.git(), &project.git_repo_path, &current_branch) {
+        Ok(PullOutcome::Pulled(stdout)) => {
             tracing::info!(
                 "Successfully pulled updates for project {}: {}",
                 project_id,
@@ -371,31 +478,31 @@ async fn post_project_pull(
                 "message": format!("Successfully pulled updates from origin/{}", current_branch)
             })))
         }
-        Ok(output) => {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-
-            if stderr.contains("conflict") || stderr.contains("Cannot rebase") {
-                tracing::warn!(
-                    "Git pull conflict for project {}: {} {}",
-                    project_id,
-                    stdout,
-                    stderr
-                );
-                Ok(Json(json!({
-                    "success": false,
-                    "message": "Cannot pull: working tree has conflicts or uncommitted changes. Please resolve manually.",
-                    "details": stderr.to_string()
-                })))
-            } else {
-                tracing::error!(
-                    "Git pull failed for project {}: {} {}",
-                    project_id,
-                    stdout,
-                    stderr
-                );
-                Err(StatusCode::INTERNAL_SERVER_ERROR)
-            }
+        Ok(PullOutcome::Conflicted(files)) => {
+            tracing::warn!(
+                "Rebase onto origin/{} stopped on conflicts for project {}: {:?}",
+                current_branch,
+                project_id,
+                files
+            );
+            Ok(Json(json!({
+                "success": false,
+                "message": "Rebase stopped on conflicts. Resolve the listed files and continue the rebase, or abort it.",
+                "rebase_in_progress": true,
+                "conflicted_files": files
+            })))
+        }
+        Ok(PullOutcome::Refused(stderr)) => {
+            tracing::warn!("Git pull refused for project {}: {}", project_id, stderr);
+            Ok(Json(json!({
+                "success": false,
+                "message": "Cannot pull: working tree has conflicts or uncommitted changes. Please resolve manually.",
+                "details": stderr
+            })))
+        }
+        Ok(PullOutcome::Failed(output)) => {
+            tracing::error!("Git pull failed for project {}: {}", project_id, output);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
         Err(e) => {
             tracing::error!(
@@ -408,6 +515,44 @@ async fn post_project_pull(
     }
 }
 
+#[derive(Debug, PartialEq)]
+enum PullOutcome {
+    Pulled(String),
+    /// The rebase started but stopped on these files; it is left in progress for the user
+    Conflicted(Vec<String>),
+    /// Git refused to start, e.g. because of uncommitted changes
+    Refused(String),
+    Failed(String),
+}
+
+/// `git pull --rebase` the checked-out branch. A diverged branch is rebased rather than
+/// refused, and a rebase that stops on conflicts reports the conflicted files.
+fn pull_rebase(
+    git: &GitService,
+    repo_path: &std::path::Path,
+    branch: &str,
+) -> std::io::Result<PullOutcome> {
+    let output = std::process::Command::new("git")
+        .current_dir(repo_path)
+        .args(["pull", "--rebase", "origin", branch])
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if output.status.success() {
+        return Ok(PullOutcome::Pulled(stdout));
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let files = git.get_conflicted_files(repo_path).unwrap_or_default();
+    if !files.is_empty() {
+        return Ok(PullOutcome::Conflicted(files));
+    }
+    if stderr.contains("conflict") || stderr.contains("Cannot rebase") {
+        Ok(PullOutcome::Refused(stderr))
+    } else {
+        Ok(PullOutcome::Failed(format!("{stdout} {stderr}")))
+    }
+}
+
 /// Get executor profiles for a specific project (per-workspace)
 async fn get_project_profiles(
     State(deployment): State<DeploymentImpl>,
@@ -425,8 +570,24 @@ async fn get_project_profiles(
             StatusCode::NOT_FOUND
         })?;
 
-    // Lazy registration: ensure cache exists and project is registered
-    // This enables hot-reload for projects that haven't created tasks yet
+    register_profile_cache(&deployment, &project).await;
+
+    // Now get profiles (will work because project is registered)
+    let profiles = deployment
+        .profile_cache()
+        .get_profiles_for_project(project_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get profiles for project {}: {}", project_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(ApiResponse::success(profiles)))
+}
+
+/// Lazy registration: ensure the project's profile cache exists and the project is registered.
+/// This enables hot-reload for projects that haven't created tasks yet
+async fn register_profile_cache(deployment: &DeploymentImpl, project: &Project) {
     if let Ok(_cache) = deployment
         .profile_cache()
         .get_or_create(project.git_repo_path.clone())
@@ -434,21 +595,99 @@ async fn get_project_profiles(
     {
         deployment
             .profile_cache()
-            .register_project(project_id, project.git_repo_path.clone())
+            .register_project(project.id, project.git_repo_path.clone())
             .await;
     }
+}
 
-    // Now get profiles (will work because project is registered)
-    let profiles = deployment
-        .profile_cache()
-        .get_profiles_for_project(project_id)
+/// Stream a project's profile reloads so the UI can refresh its executor/variant lists
+async fn stream_project_profiles_ws(
+    ws: WebSocketUpgrade,
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let project = Project::find_by_id(&deployment.db().pool, project_id)
         .await
         .map_err(|e| {
-            tracing::error!("Failed to get profiles for project {}: {}", project_id, e);
+            tracing::error!("Failed to find project {}: {}", project_id, e);
             StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
 
-    Ok(Json(ApiResponse::success(profiles)))
+    // Subscribe before registering so a reload triggered by registration isn't missed
+    let events = deployment.profile_cache().subscribe();
+    register_profile_cache(&deployment, &project).await;
+
+    let shutdown = deployment.shutdown().cancellation_token();
+    Ok(ws.on_upgrade(move |socket| async move {
+        let handler = handle_profiles_ws(socket, events, project.git_repo_path);
+        if let Some(Err(e)) = shutdown.run_until_cancelled(handler).await {
+            tracing::warn!("profiles WS closed: {}", e);
+        }
+    }))
+}
+
+async fn handle_profiles_ws(
+    socket: WebSocket,
+    mut events: broadcast::Receiver<ProfilesReloaded>,
+    workspace_root: PathBuf,
+) -> anyhow::Result<()> {
+    let (mut sender, mut receiver) = socket.split();
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                match event {
+                    Ok(event) if event.workspace_root == workspace_root => {
+                        let json = serde_json::to_string(&event)?;
+                        if sender.send(Message::Text(json.into())).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!("profiles WS skipped {} reload events", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+            msg = receiver.next() => {
+                if msg.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Report .genie agent files in a project that fail to load, without reloading profiles
+async fn validate_project_profiles(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<ProfileValidationIssue>>>, StatusCode> {
+    let project = Project::find_by_id(&deployment.db().pool, project_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to find project {}: {}", project_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let issues = tokio::task::spawn_blocking(move || {
+        GenieProfileLoader::new(project.git_repo_path).validate_profiles()
+    })
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "Profile validation for project {} panicked: {}",
+            project_id,
+            e
+        );
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(ApiResponse::success(issues)))
 }
 
 // ============================================================================
@@ -487,63 +726,117 @@ async fn list_omni_instances(
     }
 }
 
+/// Default and maximum page sizes for the Omni notification history
+const DEFAULT_NOTIFICATIONS_LIMIT: u32 = 50;
+const MAX_NOTIFICATIONS_LIMIT: u32 = 200;
+
+#[derive(Debug, Default, Deserialize)]
+struct OmniNotificationsQuery {
+    limit: Option<u32>,
+    /// `next_cursor` from a previous page; only older notifications are returned
+    before: Option<String>,
+    /// Only notifications with this delivery status (e.g. `sent`, `failed`)
+    status: Option<String>,
+}
+
+/// Position of a notification in the newest-first listing. Serialized as
+/// `<created_at>|<id>` so rows created in the same second still page deterministically.
+struct NotificationCursor {
+    created_at: DateTime<Utc>,
+    id: String,
+}
+
+impl NotificationCursor {
+    fn parse(cursor: &str) -> Option<Self> {
+        let (created_at, id) = cursor.rsplit_once('|')?;
+        let created_at = DateTime::parse_from_rfc3339(created_at).ok()?;
+        (!id.is_empty()).then(|| Self {
+            created_at: created_at.with_timezone(&Utc),
+            id: id.to_string(),
+        })
+    }
+
+    fn encode(&self) -> String {
+        format!(
+            "{}|{}",
+            self.created_at
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            self.id
+        )
+    }
+}
+
+/// One page of Omni notifications, newest first, with the cursor of the next page if any
+async fn fetch_omni_notifications(
+    pool: &sqlx::SqlitePool,
+    limit: u32,
+    before: Option<&NotificationCursor>,
+    status: Option<&str>,
+) -> Result<(Vec<OmniNotification>, Option<String>), sqlx::Error> {
+    // One extra row tells whether there is a next page
+    let query = format!(
+        r#"SELECT {}
+             FROM forge_omni_notifications
+            WHERE (?1 IS NULL OR status = ?1)
+              AND (?2 IS NULL
+                   OR datetime(created_at, 'subsec') < datetime(?2, 'subsec')
+                   OR (datetime(created_at, 'subsec') = datetime(?2, 'subsec') AND id < ?3))
+            ORDER BY datetime(created_at, 'subsec') DESC, id DESC
+            LIMIT ?4"#,
+        OmniNotification::COLUMNS
+    );
+    let mut notifications = sqlx::query_as::<_, OmniNotification>(&query)
+        .bind(status)
+        .bind(before.map(|cursor| cursor.created_at))
+        .bind(before.map(|cursor| cursor.id.as_str()))
+        .bind(i64::from(limit) + 1)
+        .fetch_all(pool)
+        .await?;
+
+    let next_cursor = if notifications.len() > limit as usize {
+        notifications.truncate(limit as usize);
+        notifications.last().map(|last| {
+            NotificationCursor {
+                created_at: last.created_at,
+                id: last.id.clone(),
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+
+    Ok((notifications, next_cursor))
+}
+
 async fn list_omni_notifications(
     State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OmniNotificationsQuery>,
 ) -> Result<Json<Value>, StatusCode> {
-    let rows = sqlx::query(
-        r#"SELECT
-                id,
-                task_id,
-                notification_type,
-                status,
-                message,
-                error_message,
-                sent_at,
-                created_at,
-                metadata
-           FROM forge_omni_notifications
-          ORDER BY created_at DESC
-          LIMIT 50"#,
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_NOTIFICATIONS_LIMIT)
+        .clamp(1, MAX_NOTIFICATIONS_LIMIT);
+    let before = match query.before.as_deref() {
+        Some(cursor) => Some(NotificationCursor::parse(cursor).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+
+    let (notifications, next_cursor) = fetch_omni_notifications(
+        &deployment.db().pool,
+        limit,
+        before.as_ref(),
+        query.status.as_deref(),
     )
-    .fetch_all(&deployment.db().pool)
     .await
     .map_err(|error| {
         tracing::error!("Failed to fetch Omni notifications: {}", error);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    let mut notifications = Vec::with_capacity(rows.len());
-
-    for row in rows {
-        let metadata = match row.try_get::<Option<String>, _>("metadata") {
-            Ok(Some(raw)) => serde_json::from_str::<Value>(&raw).ok(),
-            _ => None,
-        };
-
-        let record = json!({
-            "id": row.try_get::<String, _>("id").unwrap_or_default(),
-            "task_id": row.try_get::<Option<String>, _>("task_id").unwrap_or(None),
-            "notification_type": row
-                .try_get::<String, _>("notification_type")
-                .unwrap_or_else(|_| "unknown".to_string()),
-            "status": row
-                .try_get::<String, _>("status")
-                .unwrap_or_else(|_| "pending".to_string()),
-            "message": row.try_get::<Option<String>, _>("message").unwrap_or(None),
-            "error_message": row
-                .try_get::<Option<String>, _>("error_message")
-                .unwrap_or(None),
-            "sent_at": row.try_get::<Option<String>, _>("sent_at").unwrap_or(None),
-            "created_at": row
-                .try_get::<String, _>("created_at")
-                .unwrap_or_else(|_| chrono::Utc::now().to_rfc3339()),
-            "metadata": metadata,
-        });
-
-        notifications.push(record);
-    }
-
-    Ok(Json(json!({ "notifications": notifications })))
+    Ok(Json(
+        json!({ "notifications": notifications, "next_cursor": next_cursor }),
+    ))
 }
 
 #[derive(Debug, Deserialize)]
@@ -563,13 +856,26 @@ async fn validate_omni_config(
     State(_deployment): State<DeploymentImpl>,
     Json(req): Json<ValidateOmniRequest>,
 ) -> Result<Json<ValidateOmniResponse>, StatusCode> {
+    let host = match normalize_omni_host(&req.host) {
+        Ok(host) => host,
+        Err(e) => {
+            return Ok(Json(ValidateOmniResponse {
+                valid: false,
+                instances: vec![],
+                error: Some(e.to_string()),
+            }));
+        }
+    };
+
     let temp_config = OmniConfig {
         enabled: false,
-        host: Some(req.host),
+        host: Some(host),
         api_key: Some(req.api_key),
         instance: None,
         recipient: None,
         recipient_type: None,
+        throttle_window_secs: None,
+        events: Default::default(),
     };
 
     let temp_service = OmniService::new(temp_config);
@@ -587,11 +893,39 @@ async fn validate_omni_config(
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct ValidateRecipientRequest {
+    instance: String,
+    recipient: String,
+    recipient_type: RecipientType,
+}
+
+/// Checks a recipient against the configured Omni host without sending anything
+async fn validate_omni_recipient(
+    State(deployment): State<DeploymentImpl>,
+    Json(req): Json<ValidateRecipientRequest>,
+) -> Result<Json<RecipientValidation>, StatusCode> {
+    let omni = deployment.omni().read().await;
+    match omni
+        .validate_recipient(&req.instance, &req.recipient, &req.recipient_type)
+        .await
+    {
+        Ok(validation) => Ok(Json(validation)),
+        Err(e) => {
+            tracing::error!("Failed to validate Omni recipient: {}", e);
+            Ok(Json(RecipientValidation {
+                reachable: false,
+                detail: "Failed to connect to Omni service".to_string(),
+            }))
+        }
+    }
+}
+
 // ============================================================================
 // GitHub releases endpoint
 // ============================================================================
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct GitHubRelease {
     tag_name: String,
     name: String,
@@ -602,51 +936,185 @@ struct GitHubRelease {
     html_url: String,
 }
 
-async fn get_github_releases() -> Result<Json<ApiResponse<Vec<GitHubRelease>>>, StatusCode> {
-    let client = reqwest::Client::new();
+const GITHUB_API_URL: &str = "https://api.github.com";
+const DEFAULT_RELEASES_REPO: &str = "automagik-dev/automagik-forge";
+
+/// Whether `repo` has the `owner/repo` form used in GitHub API paths
+fn is_owner_repo(repo: &str) -> bool {
+    repo.split_once('/').is_some_and(|(owner, name)| {
+        [owner, name].iter().all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+    })
+}
 
-    match client
-        .get("https://api.github.com/repos/automagik-dev/automagik-forge/releases")
-        .header("User-Agent", "automagik-forge")
-        .header("Accept", "application/vnd.github+json")
-        .send()
-        .await
-    {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.json::<Vec<GitHubRelease>>().await {
-                    Ok(releases) => Ok(Json(ApiResponse::success(releases))),
-                    Err(e) => {
-                        tracing::error!("Failed to parse GitHub releases: {}", e);
-                        Err(StatusCode::INTERNAL_SERVER_ERROR)
+/// How long fetched releases are served before GitHub is asked again
+const RELEASES_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+static RELEASES_CACHE: LazyLock<ReleasesCache> =
+    LazyLock::new(|| ReleasesCache::new(RELEASES_CACHE_TTL));
+
+#[derive(Debug, Serialize)]
+struct GitHubReleases {
+    releases: Vec<GitHubRelease>,
+    /// When GitHub last confirmed these releases
+    fetched_at: DateTime<Utc>,
+    /// GitHub could not be reached, so an expired cache entry was served
+    stale: bool,
+}
+
+struct CachedReleases {
+    repo: String,
+    releases: Vec<GitHubRelease>,
+    etag: Option<String>,
+    fetched_at: DateTime<Utc>,
+    checked_at: Instant,
+}
+
+impl CachedReleases {
+    fn response(&self, stale: bool) -> GitHubReleases {
+        GitHubReleases {
+            releases: self.releases.clone(),
+            fetched_at: self.fetched_at,
+            stale,
+        }
+    }
+}
+
+/// Releases kept in memory so UI polling doesn't spend the GitHub rate limit. Once the TTL
+/// passes the entry is revalidated with its ETag, and it is served stale if GitHub fails.
+struct ReleasesCache {
+    ttl: Duration,
+    entry: std::sync::Mutex<Option<CachedReleases>>,
+}
+
+impl ReleasesCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: std::sync::Mutex::new(None),
+        }
+    }
+
+    async fn get(
+        &self,
+        client: &GitHubHttpClient,
+        api_url: &str,
+        repo: &str,
+    ) -> Result<GitHubReleases, GitHubHttpError> {
+        // The lock is released before the request, so a slow GitHub doesn't hold up callers
+        // that could be served from the cache
+        let etag = {
+            let entry = self.entry.lock().unwrap();
+            match entry.as_ref().filter(|cached| cached.repo == repo) {
+                Some(cached) if cached.checked_at.elapsed() < self.ttl => {
+                    return Ok(cached.response(false));
+                }
+                Some(cached) => cached.etag.clone(),
+                None => None,
+            }
+        };
+
+        let url = format!("{api_url}/repos/{repo}/releases");
+        let result = client
+            .get_json_conditional::<Vec<GitHubRelease>>(&url, etag.as_deref())
+            .await;
+
+        let mut entry = self.entry.lock().unwrap();
+        match result {
+            Ok(Conditional::Modified { value, etag }) => {
+                let cached = entry.insert(CachedReleases {
+                    repo: repo.to_string(),
+                    releases: value,
+                    etag,
+                    fetched_at: Utc::now(),
+                    checked_at: Instant::now(),
+                });
+                Ok(cached.response(false))
+            }
+            Ok(Conditional::NotModified) => {
+                match entry.as_mut().filter(|cached| cached.repo == repo) {
+                    Some(cached) => {
+                        cached.fetched_at = Utc::now();
+                        cached.checked_at = Instant::now();
+                        Ok(cached.response(false))
                     }
+                    // Another repo's releases replaced the entry during the request
+                    None => Err(GitHubHttpError::Status { status: 304 }),
                 }
-            } else {
-                tracing::error!("GitHub API returned error: {}", response.status());
-                Err(StatusCode::BAD_GATEWAY)
             }
+            Err(e) => match entry.as_ref().filter(|cached| cached.repo == repo) {
+                Some(cached) => {
+                    tracing::warn!("Serving cached GitHub releases after refresh failed: {}", e);
+                    Ok(cached.response(true))
+                }
+                None => Err(e),
+            },
+        }
+    }
+}
+
+/// Releases of the configured repo, from the cache when it is fresh
+async fn load_github_releases(
+    deployment: &DeploymentImpl,
+) -> Result<GitHubReleases, axum::response::Response> {
+    let repo = match deployment.forge_config().get_global_settings().await {
+        Ok(settings) => settings.releases_repo.filter(|repo| is_owner_repo(repo)),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to load forge config, using the default releases repo: {}",
+                e
+            );
+            None
+        }
+    }
+    .unwrap_or_else(|| DEFAULT_RELEASES_REPO.to_string());
+    let token = deployment.config().read().await.github.token();
+
+    let client = GitHubHttpClient::new().with_token(token);
+    match RELEASES_CACHE.get(&client, GITHUB_API_URL, &repo).await {
+        Ok(releases) => Ok(releases),
+        Err(GitHubHttpError::RateLimited { retry_after }) => {
+            tracing::warn!(
+                "GitHub rate limit hit while fetching releases (resets in {:?})",
+                retry_after
+            );
+            // Clients wait as long as GitHub asked before trying again
+            Err(ApiError::github_rate_limited(retry_after).into_response())
+        }
+        Err(GitHubHttpError::Request(e)) if e.is_decode() => {
+            tracing::error!("Failed to parse GitHub releases: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR.into_response())
         }
         Err(e) => {
             tracing::error!("Failed to fetch GitHub releases: {}", e);
-            Err(StatusCode::BAD_GATEWAY)
+            Err(StatusCode::BAD_GATEWAY.into_response())
         }
     }
 }
 
+async fn get_github_releases(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Json<ApiResponse<Vec<GitHubRelease>>>, axum::response::Response> {
+    let releases = load_github_releases(&deployment).await?;
+    Ok(Json(ApiResponse::success(releases.releases)))
+}
+
+/// The releases together with when GitHub last confirmed them and whether they are stale
+async fn get_github_releases_status(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Json<ApiResponse<GitHubReleases>>, axum::response::Response> {
+    let releases = load_github_releases(&deployment).await?;
+    Ok(Json(ApiResponse::success(releases)))
+}
+
 // ============================================================================
 // Agent management endpoints
 // ============================================================================
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
-struct ForgeAgent {
-    id: Uuid,
-    project_id: Uuid,
-    agent_type: String,
-    task_id: Uuid,
-    created_at: String,
-    updated_at: String,
-}
-
 #[derive(Debug, Deserialize)]
 struct GetForgeAgentsParams {
     project_id: Uuid,
@@ -721,3 +1189,548 @@ async fn create_forge_agent(
 
     Ok(Json(ApiResponse::success(agent)))
 }
+
+/// Reports drift between agent task statuses and `forge_agents` membership without changing it
+async fn get_agent_consistency(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Json<ApiResponse<AgentConsistencyReport>>, ApiError> {
+    let report = ForgeAgent::reconcile(&deployment.db().pool, false).await?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+async fn repair_agent_consistency(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Json<ApiResponse<AgentConsistencyReport>>, ApiError> {
+    let report = ForgeAgent::reconcile(&deployment.db().pool, true).await?;
+    if report.repaired {
+        tracing::info!(
+            "Repaired agent task drift: {} orphaned rows, {} drifted statuses, {} unregistered tasks",
+            report.orphaned_rows,
+            report.status_drift.len(),
+            report.unregistered.len()
+        );
+    }
+    Ok(Json(ApiResponse::success(report)))
+}
+
+// ============================================================================
+// Worktree maintenance endpoints
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct OrphanWorktreesQuery {
+    /// Only consider orphans untouched for at least this many seconds
+    min_age_secs: Option<u64>,
+}
+
+impl OrphanWorktreesQuery {
+    fn scan_options(&self, dry_run: bool) -> OrphanScanOptions {
+        let defaults = OrphanScanOptions::default();
+        OrphanScanOptions {
+            min_age: self
+                .min_age_secs
+                .map(std::time::Duration::from_secs)
+                .unwrap_or(defaults.min_age),
+            dry_run,
+        }
+    }
+}
+
+/// Reports worktree directories that no attempt or execution run references, without removing them
+async fn get_orphaned_worktrees(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OrphanWorktreesQuery>,
+) -> Result<Json<ApiResponse<OrphanWorktreeReport>>, ApiError> {
+    let report = scan_orphaned_worktrees(
+        &deployment.db().pool,
+        &WorktreeManager::get_worktree_base_dir(),
+        query.scan_options(true),
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+async fn remove_orphaned_worktrees(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OrphanWorktreesQuery>,
+) -> Result<Json<ApiResponse<OrphanWorktreeReport>>, ApiError> {
+    let report = scan_orphaned_worktrees(
+        &deployment.db().pool,
+        &WorktreeManager::get_worktree_base_dir(),
+        query.scan_options(false),
+    )
+    .await?;
+    tracing::info!(
+        "Removed {} of {} orphaned worktrees in {}",
+        report.orphans.iter().filter(|o| o.removed).count(),
+        report.orphans.len(),
+        report.base_dir
+    );
+    Ok(Json(ApiResponse::success(report)))
+}
+
+// ============================================================================
+// Commit message endpoints
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct ValidateCommitMessageRequest {
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CommitMessageValidation {
+    warnings: Vec<ValidationWarning>,
+    follows_conventional_commits: bool,
+}
+
+/// Checks a commit message the same way merges do, so clients can fix it beforehand
+async fn validate_commit_message(
+    Json(req): Json<ValidateCommitMessageRequest>,
+) -> Json<ApiResponse<CommitMessageValidation>> {
+    Json(ApiResponse::success(CommitMessageValidation {
+        warnings: CommitValidator::validate(&req.message),
+        follows_conventional_commits: CommitValidator::follows_conventional_commits(
+            &req.message,
+        ),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    use axum::{body::Body, http::Request};
+    use forge_core_services::services::github_http::GitHubRetryPolicy;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn validate(message: &str) -> Value {
+        let app = Router::new().route("/forge/commit/validate", post(validate_commit_message));
+        let response = app
+            .oneshot(
+                Request::post("/forge/commit/validate")
+                    .header("content-type", "application/json")
+                    .body(Body::from(json!({ "message": message }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice::<Value>(&body).unwrap()["data"].clone()
+    }
+
+    #[tokio::test]
+    async fn commit_validation_flags_conversational_messages() {
+        let bad = validate("Perfect! Let me add the login page").await;
+        assert_eq!(bad["follows_conventional_commits"], false);
+        let errors: Vec<&Value> = bad["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|w| w["severity"] == "error")
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0]["message"]
+                .as_str()
+                .unwrap()
+                .contains("conversational")
+        );
+
+        let good = validate("feat: add login page (#12)").await;
+        assert_eq!(good["follows_conventional_commits"], true);
+        assert_eq!(good["warnings"], json!([]));
+    }
+
+    fn git(dir: &std::path::Path, args: &[&str]) {
+        let output = std::process::Command::new("git")
+            .current_dir(dir)
+            .args([
+                "-c",
+                "user.name=Forge",
+                "-c",
+                "user.email=forge@example.com",
+            ])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn shallow_fetch_succeeds_and_reports_unknown_sync_counts() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let origin = temp.path().join("origin");
+        std::fs::create_dir(&origin).unwrap();
+        git(&origin, &["init", "-b", "main"]);
+        git(&origin, &["commit", "--allow-empty", "-m", "initial"]);
+
+        let url = format!("file://{}", origin.display());
+        for clone in ["full", "shallow"] {
+            git(temp.path(), &["clone", &url, clone]);
+        }
+        for n in 1..=3 {
+            git(
+                &origin,
+                &["commit", "--allow-empty", "-m", &format!("upstream {n}")],
+            );
+        }
+
+        let full = temp.path().join("full");
+        assert!(fetch_origin(&full, None));
+        assert_eq!(
+            behind_ahead(&full, "origin/main", "main"),
+            (Some(3), Some(0))
+        );
+
+        // The fetch itself still succeeds, so the fetch time is recorded as usual
+        let shallow = temp.path().join("shallow");
+        assert!(fetch_origin(&shallow, Some(1)));
+        assert!(shallow.join(".git/shallow").exists());
+        // The local branch is no longer connected to the fetched tip, so counting would be wrong
+        assert_eq!(behind_ahead(&shallow, "origin/main", "main"), (None, None));
+
+        // A full fetch afterwards doesn't leave the repository shallow for good
+        assert!(fetch_origin(&shallow, None));
+        assert!(!shallow.join(".git/shallow").exists());
+        assert_eq!(
+            behind_ahead(&shallow, "origin/main", "main"),
+            (Some(3), Some(0))
+        );
+    }
+
+    /// Requests seen by the mock releases API: repo, Authorization and If-None-Match
+    type SeenRequests = Arc<Mutex<Vec<(String, Option<String>, Option<String>)>>>;
+
+    /// Mock GitHub releases API that answers with an ETag and honours If-None-Match. Once
+    /// `failing` is set it answers 500.
+    async fn mock_releases_api(failing: Arc<AtomicBool>) -> (String, SeenRequests) {
+        let seen = SeenRequests::default();
+        let recorded = seen.clone();
+        let app = Router::new().route(
+            "/repos/{owner}/{repo}/releases",
+            get(
+                move |Path((owner, repo)): Path<(String, String)>,
+                      headers: axum::http::HeaderMap| async move {
+                    let header = |name: &str| {
+                        headers
+                            .get(name)
+                            .and_then(|v| v.to_str().ok())
+                            .map(str::to_string)
+                    };
+                    let if_none_match = header("if-none-match");
+                    recorded.lock().unwrap().push((
+                        format!("{owner}/{repo}"),
+                        header("authorization"),
+                        if_none_match.clone(),
+                    ));
+                    if failing.load(Ordering::SeqCst) {
+                        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                    }
+                    if if_none_match.as_deref() == Some("\"v1\"") {
+                        return StatusCode::NOT_MODIFIED.into_response();
+                    }
+                    (
+                        [("etag", "\"v1\"")],
+                        Json(json!([{
+                            "tag_name": "v1.0.0",
+                            "name": "Fork release",
+                            "body": null,
+                            "prerelease": false,
+                            "created_at": "2025-01-01T00:00:00Z",
+                            "published_at": null,
+                            "html_url": "https://github.com/acme/forge-fork/releases/tag/v1.0.0"
+                        }])),
+                    )
+                        .into_response()
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{addr}"), seen)
+    }
+
+    const FORK: &str = "acme/forge-fork";
+
+    fn releases_client() -> GitHubHttpClient {
+        GitHubHttpClient::with_policy(GitHubRetryPolicy {
+            max_retries: 0,
+            ..Default::default()
+        })
+        .with_token(Some("ghp_test".to_string()))
+    }
+
+    #[tokio::test]
+    async fn releases_use_the_configured_repo_and_token() {
+        let (api_url, seen) = mock_releases_api(Default::default()).await;
+        let cache = ReleasesCache::new(RELEASES_CACHE_TTL);
+
+        let releases = cache
+            .get(&releases_client(), &api_url, "acme/forge-fork")
+            .await
+            .unwrap();
+
+        assert_eq!(releases.releases.len(), 1);
+        assert_eq!(releases.releases[0].tag_name, "v1.0.0");
+        assert_eq!(
+            seen.lock().unwrap().clone(),
+            vec![(
+                "acme/forge-fork".to_string(),
+                Some("Bearer ghp_test".to_string()),
+                None
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn rapid_release_requests_hit_github_once() {
+        let (api_url, seen) = mock_releases_api(Default::default()).await;
+        let cache = ReleasesCache::new(RELEASES_CACHE_TTL);
+        let client = releases_client();
+
+        let first = cache.get(&client, &api_url, FORK).await.unwrap();
+        let second = cache.get(&client, &api_url, FORK).await.unwrap();
+
+        assert_eq!(seen.lock().unwrap().len(), 1);
+        assert_eq!(second.releases[0].tag_name, first.releases[0].tag_name);
+        assert_eq!(second.fetched_at, first.fetched_at);
+        assert!(!second.stale);
+
+        // Another repo is not served from this repo's entry
+        cache.get(&client, &api_url, "acme/other").await.unwrap();
+        assert_eq!(seen.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn expired_releases_are_revalidated_and_served_stale_on_failure() {
+        let failing = Arc::new(AtomicBool::new(false));
+        let (api_url, seen) = mock_releases_api(failing.clone()).await;
+        let cache = ReleasesCache::new(Duration::ZERO);
+        let client = releases_client();
+
+        cache.get(&client, &api_url, FORK).await.unwrap();
+        // The expired entry is checked with its ETag and kept on a 304
+        let revalidated = cache.get(&client, &api_url, FORK).await.unwrap();
+        assert_eq!(revalidated.releases[0].tag_name, "v1.0.0");
+        assert!(!revalidated.stale);
+        assert_eq!(seen.lock().unwrap()[1].2.as_deref(), Some("\"v1\""));
+
+        failing.store(true, Ordering::SeqCst);
+        let stale = cache.get(&client, &api_url, FORK).await.unwrap();
+        assert!(stale.stale);
+        assert_eq!(stale.fetched_at, revalidated.fetched_at);
+        assert_eq!(stale.releases[0].tag_name, "v1.0.0");
+    }
+
+    #[tokio::test]
+    async fn a_slow_refresh_does_not_hold_up_cached_reads() {
+        let (api_url, _) = mock_releases_api(Default::default()).await;
+        let cache = Arc::new(ReleasesCache::new(RELEASES_CACHE_TTL));
+        cache.get(&releases_client(), &api_url, FORK).await.unwrap();
+
+        // A GitHub that takes the request and never answers
+        let received = Arc::new(tokio::sync::Notify::new());
+        let app = Router::new().route(
+            "/repos/{owner}/{repo}/releases",
+            get({
+                let received = received.clone();
+                move || async move {
+                    received.notify_one();
+                    std::future::pending::<StatusCode>().await
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let hanging_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let slow = tokio::spawn({
+            let cache = cache.clone();
+            async move {
+                cache
+                    .get(&releases_client(), &hanging_url, "acme/other")
+                    .await
+            }
+        });
+        received.notified().await;
+
+        let cached = tokio::time::timeout(
+            Duration::from_secs(5),
+            cache.get(&releases_client(), &api_url, FORK),
+        )
+        .await
+        .expect("cached read waited for the slow refresh")
+        .unwrap();
+        assert_eq!(cached.releases[0].tag_name, "v1.0.0");
+        assert!(!cached.stale);
+        slow.abort();
+    }
+
+    #[test]
+    fn releases_repo_must_be_owner_slash_repo() {
+        assert!(is_owner_repo(DEFAULT_RELEASES_REPO));
+        assert!(is_owner_repo("acme/forge.fork_2"));
+        for repo in ["acme", "acme/", "/forge", "acme/forge/extra"] {
+            assert!(!is_owner_repo(repo), "{repo} should be rejected");
+        }
+    }
+
+    /// Clone an origin, then commit `upstream` on origin and `local` in the clone so that
+    /// the two histories diverge. Each change is a (file, content) pair.
+    fn diverged_clone(
+        temp: &tempfile::TempDir,
+        upstream: (&str, &str),
+        local: (&str, &str),
+    ) -> PathBuf {
+        let origin = temp.path().join("origin");
+        std::fs::create_dir(&origin).unwrap();
+        git(&origin, &["init", "-b", "main"]);
+        std::fs::write(origin.join("shared.txt"), "base\n").unwrap();
+        git(&origin, &["add", "."]);
+        git(&origin, &["commit", "-m", "initial"]);
+
+        let url = format!("file://{}", origin.display());
+        git(temp.path(), &["clone", &url, "local"]);
+        let clone = temp.path().join("local");
+        // The pull runs without the helper's -c overrides, so the rebase needs an identity
+        git(&clone, &["config", "user.name", "Forge"]);
+        git(&clone, &["config", "user.email", "forge@example.com"]);
+
+        for (dir, (file, content)) in [(&origin, upstream), (&clone, local)] {
+            std::fs::write(dir.join(file), content).unwrap();
+            git(dir, &["add", "."]);
+            git(dir, &["commit", "-m", &format!("change {file}")]);
+        }
+        clone
+    }
+
+    #[test]
+    fn pull_rebases_a_diverged_branch() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let clone = diverged_clone(
+            &temp,
+            ("shared.txt", "upstream\n"),
+            ("local.txt", "local\n"),
+        );
+
+        let outcome = pull_rebase(&GitService::new(), &clone, "main").unwrap();
+        assert!(matches!(outcome, PullOutcome::Pulled(_)), "{outcome:?}");
+        assert_eq!(
+            std::fs::read_to_string(clone.join("shared.txt")).unwrap(),
+            "upstream\n"
+        );
+        assert_eq!(
+            behind_ahead(&clone, "origin/main", "main"),
+            (Some(0), Some(1))
+        );
+    }
+
+    #[test]
+    fn pull_reports_conflicted_files_when_the_rebase_stops() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let clone = diverged_clone(
+            &temp,
+            ("shared.txt", "upstream\n"),
+            ("shared.txt", "local\n"),
+        );
+
+        let outcome = pull_rebase(&GitService::new(), &clone, "main").unwrap();
+        assert_eq!(
+            outcome,
+            PullOutcome::Conflicted(vec!["shared.txt".to_string()])
+        );
+        assert!(clone.join(".git/rebase-merge").exists());
+    }
+
+    async fn notifications_pool() -> sqlx::SqlitePool {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+        // Two notifications share each timestamp, so paging has to break ties on id
+        let rows = [
+            ("n0", "sent", "2025-01-01 10:00:00"),
+            ("n1", "failed", "2025-01-01 10:00:00"),
+            ("n2", "sent", "2025-01-02 10:00:00"),
+            ("n3", "failed", "2025-01-02 10:00:00"),
+            ("n4", "sent", "2025-01-03 10:00:00"),
+        ];
+        for (id, status, created_at) in rows {
+            sqlx::query(
+                "INSERT INTO forge_omni_notifications (id, notification_type, recipient, message, status, created_at)
+                 VALUES (?, 'task_completed', 'ops', 'Task finished', ?, ?)",
+            )
+            .bind(id)
+            .bind(status)
+            .bind(created_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        pool
+    }
+
+    async fn notification_page(
+        pool: &sqlx::SqlitePool,
+        limit: u32,
+        before: Option<&str>,
+        status: Option<&str>,
+    ) -> (Vec<String>, Option<String>) {
+        let before = before.map(|cursor| NotificationCursor::parse(cursor).unwrap());
+        let (notifications, next_cursor) =
+            fetch_omni_notifications(pool, limit, before.as_ref(), status)
+                .await
+                .unwrap();
+        let ids = notifications.into_iter().map(|n| n.id).collect();
+        (ids, next_cursor)
+    }
+
+    #[tokio::test]
+    async fn notifications_page_newest_first_until_exhausted() {
+        let pool = notifications_pool().await;
+
+        let (ids, cursor) = notification_page(&pool, 2, None, None).await;
+        assert_eq!(ids, ["n4", "n3"]);
+        let (ids, cursor) = notification_page(&pool, 2, cursor.as_deref(), None).await;
+        assert_eq!(ids, ["n2", "n1"]);
+        let (ids, cursor) = notification_page(&pool, 2, cursor.as_deref(), None).await;
+        assert_eq!(ids, ["n0"]);
+        assert!(cursor.is_none());
+
+        let (ids, cursor) = notification_page(&pool, 50, None, None).await;
+        assert_eq!(ids.len(), 5);
+        assert!(cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn notifications_filter_by_status() {
+        let pool = notifications_pool().await;
+
+        let (ids, cursor) = notification_page(&pool, 1, None, Some("failed")).await;
+        assert_eq!(ids, ["n3"]);
+        let (ids, cursor) = notification_page(&pool, 1, cursor.as_deref(), Some("failed")).await;
+        assert_eq!(ids, ["n1"]);
+        assert!(cursor.is_none());
+    }
+
+    #[test]
+    fn notification_cursors_must_name_a_position() {
+        let cursor = NotificationCursor::parse("2025-01-01T10:00:00.000Z|n1").unwrap();
+        assert_eq!(cursor.encode(), "2025-01-01T10:00:00.000Z|n1");
+        assert!(NotificationCursor::parse("n1").is_none());
+        assert!(NotificationCursor::parse("|n1").is_none());
+        assert!(NotificationCursor::parse("2025-01-01 10:00:00|n1").is_none());
+    }
+}
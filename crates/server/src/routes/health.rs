@@ -1,6 +1,16 @@
 use axum::response::Json;
 use forge_core_utils::response::ApiResponse;
+use serde::Serialize;
 
-pub async fn health_check() -> Json<ApiResponse<String>> {
-    Json(ApiResponse::success("OK".to_string()))
+#[derive(Debug, Serialize)]
+pub struct HealthInfo {
+    pub status: &'static str,
+    pub version: &'static str,
+}
+
+pub async fn health_check() -> Json<ApiResponse<HealthInfo>> {
+    Json(ApiResponse::success(HealthInfo {
+        status: "OK",
+        version: env!("CARGO_PKG_VERSION"),
+    }))
 }
@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use axum::{
+    Json, Router,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use forge_core_utils::{
+    metrics::{self, MetricKind, MetricsSnapshot},
+    response::ApiResponse,
+};
+use serde::Deserialize;
+use ts_rs_forge::TS;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Longest tool name accepted by `/metrics/tools`
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// A tool call timed by the MCP server, which runs in its own process
+#[derive(Debug, Deserialize, TS)]
+pub struct ToolTimingSample {
+    pub tool: String,
+    pub duration_ms: f64,
+    #[serde(default)]
+    pub failed: bool,
+}
+
+pub async fn get_metrics() -> ResponseJson<ApiResponse<MetricsSnapshot>> {
+    ResponseJson(ApiResponse::success(metrics::global().snapshot()))
+}
+
+pub async fn record_tool_timing(
+    Json(sample): Json<ToolTimingSample>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let valid_name = sample.tool.len() <= MAX_TOOL_NAME_LEN
+        && !sample.tool.is_empty()
+        && sample
+            .tool
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid_name {
+        return Err(ApiError::BadRequest(format!(
+            "Tool names are 1-{MAX_TOOL_NAME_LEN} letters, digits, '_' or '-'"
+        )));
+    }
+    let elapsed = Duration::try_from_secs_f64(sample.duration_ms / 1000.0).map_err(|_| {
+        ApiError::BadRequest(format!("Invalid duration_ms: {}", sample.duration_ms))
+    })?;
+
+    metrics::global().record(MetricKind::Tool, &sample.tool, elapsed, sample.failed);
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/metrics", get(get_metrics))
+        .route("/metrics/tools", post(record_tool_timing))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        middleware::from_fn,
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::middleware::record_route_metrics;
+
+    #[tokio::test]
+    async fn metrics_endpoint_reports_timed_routes() {
+        let app = Router::new()
+            .route("/metrics-test/{id}", get(|| async { "pong" }))
+            .route("/metrics", get(get_metrics))
+            .layer(from_fn(record_route_metrics));
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/metrics-test/42").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        let response = app
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        let route = &body["data"]["routes"]["GET /metrics-test/{id}"];
+        assert!(route["count"].as_u64().unwrap() >= 1);
+        assert!(route["total_ms"].as_f64().unwrap() > 0.0);
+        assert_eq!(
+            route["buckets"].as_array().unwrap().len(),
+            metrics::LATENCY_BUCKETS_MS.len() + 1
+        );
+    }
+
+    #[tokio::test]
+    async fn tool_timings_with_bad_names_or_durations_are_rejected() {
+        let app = Router::new().route("/metrics/tools", post(record_tool_timing));
+        let post_sample = |sample: serde_json::Value| {
+            let app = app.clone();
+            async move {
+                app.oneshot(
+                    Request::post("/metrics/tools")
+                        .header("content-type", "application/json")
+                        .body(Body::from(sample.to_string()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+                .status()
+            }
+        };
+
+        let long_name = "t".repeat(MAX_TOOL_NAME_LEN + 1);
+        for sample in [
+            serde_json::json!({ "tool": "list_tasks", "duration_ms": -1.0 }),
+            serde_json::json!({ "tool": "list_tasks", "duration_ms": 1e300 }),
+            serde_json::json!({ "tool": long_name, "duration_ms": 5.0 }),
+            serde_json::json!({ "tool": "", "duration_ms": 5.0 }),
+            serde_json::json!({ "tool": "list tasks\n", "duration_ms": 5.0 }),
+        ] {
+            assert_eq!(
+                post_sample(sample.clone()).await,
+                StatusCode::BAD_REQUEST,
+                "{sample}"
+            );
+        }
+
+        assert!(
+            post_sample(serde_json::json!({ "tool": "timed_tool", "duration_ms": 12.5 }))
+                .await
+                .is_success()
+        );
+        let snapshot = metrics::global().snapshot();
+        let timed = &snapshot.tools["timed_tool"];
+        assert_eq!(timed.count, 1);
+        assert_eq!(timed.buckets[2], 1);
+    }
+}
@@ -1,11 +1,12 @@
 use axum::{
     Router,
-    middleware::from_fn_with_state,
+    middleware::{from_fn, from_fn_with_state},
     routing::{IntoMakeService, get},
 };
 
-use crate::DeploymentImpl;
+use crate::{DeploymentImpl, middleware::record_route_metrics};
 
+pub mod activity;
 pub mod approvals;
 pub mod auth;
 pub mod config;
@@ -19,6 +20,8 @@ pub mod execution_processes;
 pub mod execution_runs;
 pub mod health;
 pub mod images;
+pub mod metrics;
+pub mod pagination;
 pub mod projects;
 pub mod tags;
 pub mod task_attempts;
@@ -28,6 +31,7 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
     // Create routers with different middleware layers
     let base_routes = Router::new()
         .route("/health", get(health::health_check))
+        .merge(activity::router())
         .merge(config::router())
         .merge(containers::router(&deployment))
         .merge(projects::router(&deployment))
@@ -42,7 +46,9 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(events::router(&deployment))
         .merge(approvals::router())
         .merge(forge::router(&deployment))
+        .merge(metrics::router())
         .nest("/images", images::routes())
+        .layer(from_fn(record_route_metrics))
         .layer(from_fn_with_state(
             deployment.clone(),
             auth::sentry_user_context_middleware,
@@ -0,0 +1,74 @@
+//! Keyset cursors for newest-first listings
+//!
+//! A cursor names the last row of a page by its `(created_at, id)` key, so the next page
+//! starts right after that row no matter how many rows were inserted or deleted in between.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+
+/// The `(created_at, id)` key of the last row of a page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl PageCursor {
+    pub fn new(created_at: DateTime<Utc>, id: Uuid) -> Self {
+        Self { created_at, id }
+    }
+
+    /// Reads a cursor produced by [`PageCursor::encode`]
+    pub fn parse(cursor: &str) -> Option<Self> {
+        let (created_at, id) = cursor.trim().rsplit_once('|')?;
+        let created_at = DateTime::parse_from_rfc3339(created_at).ok()?;
+        Some(Self {
+            created_at: created_at.with_timezone(&Utc),
+            id: Uuid::parse_str(id).ok()?,
+        })
+    }
+
+    /// Opaque form handed to clients. The timestamp keeps its full precision so that it
+    /// compares equal to the stored one.
+    pub fn encode(&self) -> String {
+        format!(
+            "{}|{}",
+            self.created_at.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            self.id
+        )
+    }
+
+    /// Parses the `before` query parameter of a listing
+    pub fn from_query(before: Option<&str>) -> Result<Option<Self>, ApiError> {
+        before
+            .map(|cursor| {
+                Self::parse(cursor)
+                    .ok_or_else(|| ApiError::BadRequest(format!("Invalid cursor '{cursor}'")))
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursors_round_trip() {
+        let created_at = DateTime::parse_from_rfc3339("2025-01-01T10:00:00.123456Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let cursor = PageCursor::new(created_at, Uuid::new_v4());
+        assert_eq!(PageCursor::parse(&cursor.encode()), Some(cursor));
+
+        assert_eq!(PageCursor::parse("50"), None);
+        assert_eq!(PageCursor::parse("2025-01-01T10:00:00Z|not-a-uuid"), None);
+        assert!(matches!(
+            PageCursor::from_query(Some("50")),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert_eq!(PageCursor::from_query(None).unwrap(), None);
+    }
+}
@@ -2,23 +2,29 @@ use std::path::Path;
 
 use axum::{
     Extension, Json, Router,
-    extract::{Query, State},
+    extract::{Path as AxumPath, Query, State},
     http::StatusCode,
     middleware::from_fn_with_state,
     response::Json as ResponseJson,
-    routing::{get, post},
+    routing::{delete, get, post},
 };
-use forge_core_db::models::project::{
-    CreateProject, Project, ProjectError, SearchMatchType, SearchResult, UpdateProject,
+use forge_core_db::models::{
+    project::{
+        CreateProject, Project, ProjectError, SearchMatchType, SearchResult, UpdateProject,
+    },
+    task::{ProjectTaskCounts, Task},
 };
 use forge_core_deployment::Deployment;
 use forge_core_services::services::{
     file_ranker::FileRanker,
     file_search_cache::{CacheError, SearchMode, SearchQuery},
-    git::GitBranch,
+    git::{GitBranch, GitService, GitServiceError},
+    github_service::GitHubServiceError,
 };
 use forge_core_utils::{path::expand_tilde, response::ApiResponse};
 use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use ts_rs_forge::TS;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
@@ -30,24 +36,142 @@ pub async fn get_projects(
     Ok(ResponseJson(ApiResponse::success(projects)))
 }
 
+/// Active and total task counts for every project
+pub async fn get_project_task_counts(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectTaskCounts>>>, ApiError> {
+    let counts = Task::count_by_project(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(counts)))
+}
+
 pub async fn get_project(
     Extension(project): Extension<Project>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
     Ok(ResponseJson(ApiResponse::success(project)))
 }
 
+#[derive(serde::Deserialize)]
+pub struct ProjectBranchesQuery {
+    /// Skip the ahead/behind comparison with the default branch
+    #[serde(default)]
+    names_only: bool,
+}
+
 pub async fn get_project_branches(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ProjectBranchesQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<GitBranch>>>, ApiError> {
-    let branches = deployment.git().get_all_branches(&project.git_repo_path)?;
+    let mut branches = deployment.git().get_all_branches(&project.git_repo_path)?;
+    if !query.names_only {
+        deployment
+            .git()
+            .annotate_branch_sync(&project.git_repo_path, &mut branches)?;
+    }
     Ok(ResponseJson(ApiResponse::success(branches)))
 }
 
+#[derive(serde::Deserialize)]
+pub struct RemoteBranchPath {
+    branch_name: String,
+}
+
+/// Delete a branch from the project's remote. The default branch and the branch checked out
+/// in the project repository are refused with a conflict.
+pub async fn delete_project_remote_branch(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    AxumPath(RemoteBranchPath { branch_name }): AxumPath<RemoteBranchPath>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let github_config = deployment.config().read().await.github.clone();
+    let Some(github_token) = github_config.token() else {
+        return Err(GitHubServiceError::TokenInvalid.into());
+    };
+    deployment
+        .git()
+        .delete_remote_branch(&project.git_repo_path, &branch_name, &github_token)?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Branch new attempts should start from when the caller doesn't pick one
+pub async fn get_project_default_branch(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<String>>, ApiError> {
+    let branch = deployment
+        .git()
+        .get_default_branch_name(&project.git_repo_path)?;
+    Ok(ResponseJson(ApiResponse::success(branch)))
+}
+
+/// Why cloning a new project's repository failed. `auth_failed` means the remote rejected
+/// the credentials, or none are configured for a private repository.
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum ProjectCloneError {
+    AuthFailed { message: String },
+    PathExists { path: String },
+    CloneFailed { message: String },
+}
+
+impl From<GitServiceError> for ProjectCloneError {
+    fn from(e: GitServiceError) -> Self {
+        if e.is_auth_failure() {
+            ProjectCloneError::AuthFailed {
+                message: e.to_string(),
+            }
+        } else {
+            ProjectCloneError::CloneFailed {
+                message: e.to_string(),
+            }
+        }
+    }
+}
+
+/// Clone `url` into `path` for a new project, removing any partial checkout on failure
+#[cfg(feature = "cloud")]
+async fn clone_project_repository(
+    deployment: &DeploymentImpl,
+    url: &str,
+    path: &Path,
+) -> Result<(), (String, ProjectCloneError)> {
+    tracing::info!("Cloning {} into {}", url, path.display());
+    let token = deployment.config().read().await.github.token();
+    let (clone_from, clone_to) = (url.to_string(), path.to_path_buf());
+    let result = tokio::task::spawn_blocking(move || {
+        GitService::clone_repository(&clone_from, &clone_to, token.as_deref()).map(|_| ())
+    })
+    .await;
+    let error = match result {
+        Ok(Ok(())) => return Ok(()),
+        Ok(Err(e)) => (e.to_string(), ProjectCloneError::from(e)),
+        Err(e) => (
+            e.to_string(),
+            ProjectCloneError::CloneFailed {
+                message: e.to_string(),
+            },
+        ),
+    };
+    // Don't leave a partial checkout behind
+    let _ = std::fs::remove_dir_all(path);
+    Err(error)
+}
+
+#[cfg(not(feature = "cloud"))]
+async fn clone_project_repository(
+    _deployment: &DeploymentImpl,
+    _url: &str,
+    _path: &Path,
+) -> Result<(), (String, ProjectCloneError)> {
+    let message = "cloning repositories requires the cloud feature".to_string();
+    Err((message.clone(), ProjectCloneError::CloneFailed { message }))
+}
+
 pub async fn create_project(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateProject>,
-) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<Project, ProjectCloneError>>, ApiError> {
     let id = Uuid::new_v4();
     let CreateProject {
         name,
@@ -57,10 +181,33 @@ pub async fn create_project(
         cleanup_script,
         copy_files,
         commit_prompt,
-        use_existing_repo,
+        mut use_existing_repo,
+        clone_url,
     } = payload;
     tracing::debug!("Creating project '{}'", name);
 
+    let clone_url = clone_url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty());
+    // Cloned repos go to the given path, or a directory named after the repo in the workspace
+    let git_repo_path = match &clone_url {
+        Some(url) if git_repo_path.trim().is_empty() => {
+            let workspace_dir = deployment.config().read().await.workspace_dir.clone();
+            let (Some(workspace_dir), Some(repo_name)) =
+                (workspace_dir, GitService::repo_name_from_clone_url(url))
+            else {
+                return Ok(ResponseJson(ApiResponse::error(
+                    "A git repository path is required when no workspace directory is configured",
+                )));
+            };
+            expand_tilde(&workspace_dir)
+                .join(repo_name)
+                .to_string_lossy()
+                .to_string()
+        }
+        _ => git_repo_path,
+    };
+
     // Validate and setup git repository
     let path = std::path::absolute(expand_tilde(&git_repo_path))?;
     // Check if git repo path is already used by another project
@@ -80,6 +227,29 @@ pub async fn create_project(
         }
     }
 
+    if let Some(url) = &clone_url {
+        if path.exists() {
+            return Ok(ResponseJson(ApiResponse::error_with_message_and_data(
+                &format!(
+                    "Cannot clone into {}: the path already exists",
+                    path.display()
+                ),
+                ProjectCloneError::PathExists {
+                    path: path.to_string_lossy().to_string(),
+                },
+            )));
+        }
+
+        if let Err((message, error)) = clone_project_repository(&deployment, url, &path).await {
+            tracing::error!("Failed to clone {}: {}", url, message);
+            return Ok(ResponseJson(ApiResponse::error_with_message_and_data(
+                &format!("Failed to clone {url}: {message}"),
+                error,
+            )));
+        }
+        use_existing_repo = true;
+    }
+
     if use_existing_repo {
         // For existing repos, validate that the path exists and is a git repository
         if !path.exists() {
@@ -145,6 +315,7 @@ pub async fn create_project(
             cleanup_script,
             copy_files,
             commit_prompt,
+            clone_url: clone_url.clone(),
         },
         id,
     )
@@ -160,7 +331,7 @@ pub async fn create_project(
                         "use_existing_repo": use_existing_repo,
                         "has_setup_script": project.setup_script.is_some(),
                         "has_dev_script": project.dev_script.is_some(),
-                        "trigger": "manual",
+                        "trigger": if clone_url.is_some() { "clone" } else { "manual" },
                     }),
                 )
                 .await;
@@ -515,6 +686,11 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             get(get_project).put(update_project).delete(delete_project),
         )
         .route("/branches", get(get_project_branches))
+        .route(
+            "/branches/{*branch_name}",
+            delete(delete_project_remote_branch),
+        )
+        .route("/default-branch", get(get_project_default_branch))
         .route("/search", get(search_project_files))
         .route("/open-editor", post(open_project_in_editor))
         .layer(from_fn_with_state(
@@ -524,7 +700,128 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let projects_router = Router::new()
         .route("/", get(get_projects).post(create_project))
+        .route("/counts", get(get_project_task_counts))
         .nest("/{id}", project_id_router);
 
     Router::new().nest("/projects", projects_router)
 }
+
+#[cfg(test)]
+mod tests {
+    use forge_core_services::services::config::Config;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    async fn test_deployment(root: &Path, workspace_dir: &Path) -> DeploymentImpl {
+        let config = Config {
+            workspace_dir: Some(workspace_dir.to_string_lossy().to_string()),
+            ..Config::default()
+        };
+        DeploymentImpl::new_at(&root.join("db.sqlite"), config)
+            .await
+            .unwrap()
+    }
+
+    fn clone_payload(clone_url: &str) -> CreateProject {
+        CreateProject {
+            name: "Widgets".to_string(),
+            git_repo_path: String::new(),
+            use_existing_repo: false,
+            setup_script: None,
+            dev_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            commit_prompt: None,
+            clone_url: Some(clone_url.to_string()),
+        }
+    }
+
+    #[cfg(feature = "cloud")]
+    #[tokio::test]
+    async fn create_project_clones_the_url_into_the_workspace() {
+        use git2::{Repository, Signature};
+
+        let root = TempDir::new().unwrap();
+        let workspace = TempDir::new().unwrap();
+        let deployment = test_deployment(root.path(), workspace.path()).await;
+
+        let remote = root.path().join("widgets.git");
+        let repo = Repository::init_bare(&remote).unwrap();
+        let signature = Signature::now("Forge Test", "test@example.com").unwrap();
+        let blob = repo.blob(b"# Cloned project\n").unwrap();
+        let mut builder = repo.treebuilder(None).unwrap();
+        builder.insert("README.md", blob, 0o100644).unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        repo.commit(
+            Some("refs/heads/main"),
+            &signature,
+            &signature,
+            "Initial commit",
+            &tree,
+            &[],
+        )
+        .unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+
+        let ResponseJson(response) = create_project(
+            State(deployment.clone()),
+            Json(clone_payload(&remote.to_string_lossy())),
+        )
+        .await
+        .unwrap();
+        assert!(response.is_success(), "{:?}", response.message());
+        let project = response.into_data().unwrap();
+
+        let checkout = workspace.path().join("widgets");
+        assert_eq!(project.git_repo_path, checkout);
+        assert_eq!(
+            std::fs::read_to_string(checkout.join("README.md")).unwrap(),
+            "# Cloned project\n"
+        );
+        let stored = Project::find_by_id(&deployment.db().pool, project.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.git_repo_path, checkout);
+
+        // Cloning over the existing checkout is refused without touching it
+        let ResponseJson(response) = create_project(
+            State(deployment.clone()),
+            Json(CreateProject {
+                git_repo_path: checkout.to_string_lossy().to_string(),
+                ..clone_payload(&remote.to_string_lossy())
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(!response.is_success());
+        assert!(checkout.join("README.md").exists());
+    }
+
+    #[cfg(not(feature = "cloud"))]
+    #[tokio::test]
+    async fn create_project_refuses_clone_urls_without_the_cloud_feature() {
+        let root = TempDir::new().unwrap();
+        let workspace = TempDir::new().unwrap();
+        let deployment = test_deployment(root.path(), workspace.path()).await;
+
+        let ResponseJson(response) = create_project(
+            State(deployment.clone()),
+            Json(clone_payload("https://github.com/acme/widgets.git")),
+        )
+        .await
+        .unwrap();
+        assert!(!response.is_success());
+        let body = serde_json::to_value(&response).unwrap();
+        assert_eq!(body["error_data"]["type"], "clone_failed");
+
+        assert!(!workspace.path().join("widgets").exists());
+        assert!(
+            Project::find_all(&deployment.db().pool)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
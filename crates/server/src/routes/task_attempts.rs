@@ -8,12 +8,13 @@ use axum::{
         Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
+    http::{StatusCode, header},
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
     routing::{get, post},
 };
 use forge_core_db::models::{
+    attempt_profile_snapshot::AttemptProfileSnapshot,
     draft::{Draft, DraftType},
     execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
     executor_session::ExecutorSession,
@@ -22,7 +23,7 @@ use forge_core_db::models::{
     task::{Task, TaskRelationships, TaskStatus},
     task_attempt::{CreateTaskAttempt, TaskAttempt, TaskAttemptError},
 };
-use forge_core_deployment::Deployment;
+use forge_core_deployment::{Deployment, DeploymentError};
 use forge_core_executors::{
     actions::{
         ExecutorAction, ExecutorActionType,
@@ -30,19 +31,22 @@ use forge_core_executors::{
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
     executors::{CodingAgent, ExecutorError},
-    profile::{ExecutorConfigs, ExecutorProfileId},
+    profile::{ExecutorConfigs, ExecutorProfileId, canonical_variant_key},
 };
 use forge_core_services::services::{
-    commit_message_generator::CommitMessageGenerator,
+    attempt_diff::{DiffResult, MAX_DIFF_PATCH_BYTES},
+    attempt_export::{AttemptExportBundle, load_transcript_turns},
+    commit_message_generator::{CoAuthor, CommitMessageGenerator},
     commit_validator::{CommitValidator, WarningSeverity},
     container::ContainerService,
-    git::{ConflictOp, WorktreeResetOptions},
+    git::{BranchCommit, BranchStatus, ConflictOp, DiffTarget, PushResult, WorktreeResetOptions},
     github_service::{CreatePrRequest, GitHubService, GitHubServiceError},
+    pr_monitor::review_task_for_new_pr,
 };
-use forge_core_utils::response::ApiResponse;
+use forge_core_utils::{diff::Diff, response::ApiResponse};
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
-use sqlx::Error as SqlxError;
+use sqlx::{Error as SqlxError, FromRow};
 use ts_rs_forge::TS;
 use uuid::Uuid;
 
@@ -50,7 +54,11 @@ use crate::{
     DeploymentImpl,
     error::ApiError,
     middleware::load_task_attempt_middleware,
-    routes::task_attempts::util::{ensure_worktree_path, handle_images_for_prompt},
+    routes::{
+        pagination::PageCursor,
+        task_attempts::util::{ensure_worktree_path, handle_images_for_prompt},
+        tasks::ensure_executor_allowed,
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -63,7 +71,12 @@ pub struct RebaseTaskAttemptRequest {
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum GitOperationError {
-    MergeConflicts { message: String, op: ConflictOp },
+    MergeConflicts {
+        message: String,
+        op: ConflictOp,
+        #[serde(default)]
+        conflicted_files: Vec<String>,
+    },
     RebaseInProgress,
 }
 
@@ -104,9 +117,28 @@ pub struct FollowUpResponse {
     pub created_new_attempt: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TaskAttemptQuery {
     pub task_id: Option<Uuid>,
+    /// Maximum number of attempts to return; all remaining attempts when absent
+    pub limit: Option<u32>,
+    /// Only return attempts older than this cursor, the key of the last attempt of a previous
+    /// page
+    pub before: Option<String>,
+}
+
+/// An attempt in a listing, with the state its status is derived from so that clients don't
+/// need a request per attempt to show it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
+pub struct TaskAttemptListItem {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    #[sqlx(flatten)]
+    pub attempt: TaskAttempt,
+    /// Status of the newest setup script, cleanup script or coding agent process
+    pub latest_process_status: Option<ExecutionProcessStatus>,
+    /// Whether the branch was merged directly or through a merged PR
+    pub merged: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -115,20 +147,85 @@ pub struct DiffStreamQuery {
     pub stats_only: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct DiffQuery {
+    /// Only include changes under this path, relative to the repository root
+    pub path: Option<String>,
+}
+
+/// One newest-first page of attempts, keyed on (created_at, id) so that attempts created
+/// between two requests can't shift rows across pages. Process and merge state are read for
+/// the page only.
+const TASK_ATTEMPTS_PAGE_QUERY: &str = r#"SELECT ta.id, ta.task_id, ta.container_ref, ta.branch, ta.target_branch,
+       ta.executor, ta.variant, ta.worktree_deleted, ta.setup_completed_at,
+       ta.input_tokens, ta.output_tokens, ta.cache_creation_tokens, ta.cache_read_tokens,
+       ta.created_at, ta.updated_at,
+       (SELECT ep.status
+          FROM execution_processes ep
+         WHERE ep.task_attempt_id = ta.id
+           AND ep.run_reason != 'devserver'
+           AND ep.dropped = FALSE
+         ORDER BY ep.started_at DESC
+         LIMIT 1) AS latest_process_status,
+       EXISTS (SELECT 1
+                 FROM merges m
+                WHERE m.task_attempt_id = ta.id
+                  AND (m.merge_type = 'direct' OR m.pr_status = 'merged')) AS merged
+  FROM task_attempts ta
+ WHERE (?1 IS NULL OR ta.task_id = ?1)
+   AND (?2 IS NULL
+        OR datetime(ta.created_at, 'subsec') < datetime(?2, 'subsec')
+        OR (datetime(ta.created_at, 'subsec') = datetime(?2, 'subsec') AND ta.id < ?3))
+ ORDER BY datetime(ta.created_at, 'subsec') DESC, ta.id DESC
+ LIMIT ?4"#;
+
 pub async fn get_task_attempts(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskAttemptQuery>,
-) -> Result<ResponseJson<ApiResponse<Vec<TaskAttempt>>>, ApiError> {
-    let pool = &deployment.db().pool;
-    let attempts = TaskAttempt::fetch_all(pool, query.task_id).await?;
+) -> Result<ResponseJson<ApiResponse<Vec<TaskAttemptListItem>>>, ApiError> {
+    let before = PageCursor::from_query(query.before.as_deref())?;
+    let attempts =
+        fetch_attempt_page(&deployment.db().pool, query.task_id, before, query.limit).await?;
     Ok(ResponseJson(ApiResponse::success(attempts)))
 }
 
+async fn fetch_attempt_page(
+    pool: &sqlx::SqlitePool,
+    task_id: Option<Uuid>,
+    before: Option<PageCursor>,
+    limit: Option<u32>,
+) -> Result<Vec<TaskAttemptListItem>, SqlxError> {
+    // SQLite treats a negative LIMIT as unbounded
+    sqlx::query_as::<_, TaskAttemptListItem>(TASK_ATTEMPTS_PAGE_QUERY)
+        .bind(task_id)
+        .bind(before.map(|cursor| cursor.created_at))
+        .bind(before.map(|cursor| cursor.id))
+        .bind(limit.map_or(-1, i64::from))
+        .fetch_all(pool)
+        .await
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct TaskAttemptDetail {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub attempt: TaskAttempt,
+    /// Executor configuration the attempt was started with; absent for attempts started
+    /// before snapshots were recorded
+    pub profile_snapshot: Option<AttemptProfileSnapshot>,
+}
+
 pub async fn get_task_attempt(
     Extension(task_attempt): Extension<TaskAttempt>,
-    State(_deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
-    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttemptDetail>>, ApiError> {
+    let profile_snapshot =
+        AttemptProfileSnapshot::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(TaskAttemptDetail {
+        attempt: task_attempt,
+        profile_snapshot,
+    })))
 }
 
 #[derive(Debug, Serialize, Deserialize, ts_rs_forge::TS)]
@@ -161,11 +258,41 @@ pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTaskAttemptBody>,
 ) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    if deployment.shutdown().is_shutting_down() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The server is shutting down and not starting new attempts",
+        )));
+    }
     let executor_profile_id = payload.get_executor_profile_id();
     let task = Task::find_by_id(&deployment.db().pool, payload.task_id)
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
+    let task_attempt = start_new_attempt(
+        &deployment,
+        &task,
+        executor_profile_id,
+        payload.base_branch.clone(),
+        payload.use_worktree,
+        None,
+    )
+    .await?;
+
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
+/// Creates an attempt for `task` and starts its executor. With a `fork_point` the attempt's
+/// branch starts at that commit instead of at `base_branch`.
+async fn start_new_attempt(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    executor_profile_id: ExecutorProfileId,
+    base_branch: String,
+    use_worktree: Option<bool>,
+    fork_point: Option<&str>,
+) -> Result<TaskAttempt, ApiError> {
+    ensure_executor_allowed(deployment, task.project_id, executor_profile_id.executor).await?;
+
     // Load workspace-specific .genie profiles and inject into global cache just-in-time
     let project = task
         .parent_project(&deployment.db().pool)
@@ -195,41 +322,40 @@ pub async fn create_task_attempt(
         .git_branch_from_task_attempt(&attempt_id, &task.title)
         .await;
 
-    let mut task_attempt = TaskAttempt::create(
-        &deployment.db().pool,
-        &CreateTaskAttempt {
-            executor: executor_profile_id.executor,
-            base_branch: payload.base_branch.clone(),
-            branch: git_branch_name.clone(),
-        },
+    // Create the fork's branch first so a failure leaves no attempt behind
+    if let Some(commit) = fork_point {
+        deployment
+            .git()
+            .create_branch_at(&project.git_repo_path, &git_branch_name, commit)?;
+    }
+    let create_attempt = CreateTaskAttempt {
+        executor: executor_profile_id.executor,
+        variant: executor_profile_id.variant.clone(),
+        base_branch,
+        branch: git_branch_name.clone(),
+    };
+    let task_attempt = match insert_attempt(
+        deployment,
+        &create_attempt,
         attempt_id,
-        payload.task_id,
+        task.id,
+        use_worktree,
+        fork_point.is_some(),
     )
-    .await?;
-
-    // Store executor with variant for filtering (executor:variant format)
-    if let Some(variant) = &executor_profile_id.variant {
-        let executor_with_variant = format!("{}:{}", executor_profile_id.executor, variant);
-        sqlx::query(
-            "UPDATE task_attempts SET executor = ?, updated_at = datetime('now') WHERE id = ?",
-        )
-        .bind(&executor_with_variant)
-        .bind(attempt_id.to_string())
-        .execute(&deployment.db().pool)
-        .await?;
-        task_attempt.executor = executor_with_variant;
-    }
-
-    // Insert worktree config if explicitly specified (defaults to true when not present)
-    if let Some(use_worktree) = payload.use_worktree {
-        sqlx::query(
-            "INSERT INTO forge_task_attempt_config (task_attempt_id, use_worktree) VALUES (?, ?)",
-        )
-        .bind(attempt_id.to_string())
-        .bind(use_worktree)
-        .execute(&deployment.db().pool)
-        .await?;
-    }
+    .await
+    {
+        Ok(task_attempt) => task_attempt,
+        Err(err) => {
+            if fork_point.is_some()
+                && let Err(e) = deployment
+                    .git()
+                    .delete_local_branch(&project.git_repo_path, &git_branch_name)
+            {
+                tracing::warn!("Failed to delete fork branch {}: {}", git_branch_name, e);
+            }
+            return Err(err);
+        }
+    };
 
     if let Err(err) = deployment
         .container()
@@ -253,6 +379,96 @@ pub async fn create_task_attempt(
 
     tracing::info!("Created attempt for task {}", task.id);
 
+    Ok(task_attempt)
+}
+
+/// Inserts the attempt row and its worktree config. `reuse_branch` marks an attempt whose
+/// branch already exists, so its worktree is checked out on that branch.
+async fn insert_attempt(
+    deployment: &DeploymentImpl,
+    create_attempt: &CreateTaskAttempt,
+    attempt_id: Uuid,
+    task_id: Uuid,
+    use_worktree: Option<bool>,
+    reuse_branch: bool,
+) -> Result<TaskAttempt, ApiError> {
+    let task_attempt =
+        TaskAttempt::create(&deployment.db().pool, create_attempt, attempt_id, task_id).await?;
+
+    // Insert worktree config if specified (worktrees are used when not present)
+    if use_worktree.is_some() || reuse_branch {
+        sqlx::query(
+            "INSERT INTO forge_task_attempt_config (task_attempt_id, use_worktree, reuse_branch) \
+             VALUES (?, ?, ?)",
+        )
+        .bind(attempt_id.to_string())
+        .bind(use_worktree.unwrap_or(true))
+        .bind(reuse_branch)
+        .execute(&deployment.db().pool)
+        .await?;
+    }
+    Ok(task_attempt)
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, TS)]
+pub struct ForkTaskAttemptBody {
+    /// Commit of the source attempt's branch to start from; defaults to its tip
+    pub from_commit: Option<String>,
+}
+
+/// Start a new attempt for the same task whose branch begins at the source attempt's tip, or at
+/// `from_commit`. The new attempt keeps the source's target branch and executor profile.
+pub async fn fork_task_attempt(
+    Extension(source): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ForkTaskAttemptBody>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    if deployment.shutdown().is_shutting_down() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The server is shutting down and not starting new attempts",
+        )));
+    }
+    let pool = &deployment.db().pool;
+    let task = source
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let from_commit = payload
+        .from_commit
+        .as_deref()
+        .map(str::trim)
+        .filter(|commit| !commit.is_empty());
+    let fork_point = deployment
+        .git()
+        .fork_point(&project.git_repo_path, &source.branch, from_commit)
+        .map_err(|e| match from_commit {
+            Some(commit) => ApiError::BadRequest(format!("Cannot fork from '{commit}': {e}")),
+            None => e.into(),
+        })?;
+    let executor_profile_id =
+        ExecutionProcess::latest_executor_profile_for_attempt(pool, source.id).await?;
+
+    let task_attempt = start_new_attempt(
+        &deployment,
+        &task,
+        executor_profile_id,
+        source.target_branch.clone(),
+        None,
+        Some(&fork_point),
+    )
+    .await?;
+    tracing::info!(
+        "Forked attempt {} from attempt {} at {}",
+        task_attempt.id,
+        source.id,
+        fork_point
+    );
+
     Ok(ResponseJson(ApiResponse::success(task_attempt)))
 }
 
@@ -295,6 +511,32 @@ pub struct CreateFollowUpAttempt {
     pub perform_git_reset: Option<bool>,
 }
 
+/// Rejects a follow-up whose variant doesn't exist for the attempt's executor, listing the
+/// variants that do
+fn validate_follow_up_variant(
+    configs: &ExecutorConfigs,
+    executor_profile_id: &ExecutorProfileId,
+) -> Result<(), ApiError> {
+    let Some(variant) = &executor_profile_id.variant else {
+        return Ok(());
+    };
+    if configs.get_coding_agent(executor_profile_id).is_some() {
+        return Ok(());
+    }
+
+    let mut variants: Vec<&str> = configs
+        .executors
+        .get(&executor_profile_id.executor)
+        .map(|executor| executor.configurations.keys().map(String::as_str).collect())
+        .unwrap_or_default();
+    variants.sort_unstable();
+    Err(ApiError::BadRequest(format!(
+        "Unknown variant '{variant}' for executor {}. Valid variants: {}",
+        executor_profile_id.executor,
+        variants.join(", ")
+    )))
+}
+
 pub async fn follow_up(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
@@ -312,10 +554,11 @@ pub async fn follow_up(
     )
     .await?;
 
+    let variant_override = payload.variant.as_deref().map(canonical_variant_key);
     let executor_profile_id = ExecutorProfileId {
         executor: initial_executor_profile_id.executor,
-        variant: payload
-            .variant
+        variant: variant_override
+            .clone()
             .or(initial_executor_profile_id.variant.clone()),
     };
 
@@ -349,6 +592,11 @@ pub async fn follow_up(
             .await;
     }
 
+    // Only an explicitly requested variant is checked; an inherited one already ran
+    if variant_override.is_some() {
+        validate_follow_up_variant(&ExecutorConfigs::get_cached(), &executor_profile_id)?;
+    }
+
     // If retry settings provided, perform replace-logic before proceeding
     if let Some(proc_id) = payload.retry_process_id {
         let pool = &deployment.db().pool;
@@ -599,10 +847,10 @@ pub async fn stream_task_attempt_diff_ws(
     State(deployment): State<DeploymentImpl>,
 ) -> impl IntoResponse {
     let stats_only = params.stats_only;
+    let shutdown = deployment.shutdown().cancellation_token();
     ws.on_upgrade(move |socket| async move {
-        if let Err(e) =
-            handle_task_attempt_diff_ws(socket, deployment, task_attempt, stats_only).await
-        {
+        let handler = handle_task_attempt_diff_ws(socket, deployment, task_attempt, stats_only);
+        if let Some(Err(e)) = shutdown.run_until_cancelled(handler).await {
             tracing::warn!("diff WS closed: {}", e);
         }
     })
@@ -715,12 +963,21 @@ pub async fn compare_commit_to_head(
     })))
 }
 
+#[derive(Debug, Default, Deserialize, TS)]
+pub struct MergeAttemptRequest {
+    /// People credited on the merge commit with `Co-authored-by` trailers
+    #[serde(default)]
+    pub co_authors: Vec<CoAuthor>,
+}
+
 #[axum::debug_handler]
 pub async fn merge_task_attempt(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    payload: Option<Json<MergeAttemptRequest>>,
+) -> Result<ResponseJson<ApiResponse<(), GitOperationError>>, ApiError> {
     let pool = &deployment.db().pool;
+    let request = payload.map(|Json(request)| request).unwrap_or_default();
 
     let task = task_attempt
         .parent_task(pool)
@@ -728,6 +985,28 @@ pub async fn merge_task_attempt(
         .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
     let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
 
+    // Check for conflicts up front so callers learn which files clash, rather than only that
+    // the target branch has moved on
+    let conflicted_files = deployment.git().merge_conflicted_files(
+        &ctx.project.git_repo_path,
+        &ctx.task_attempt.branch,
+        &ctx.task_attempt.target_branch,
+    )?;
+    if !conflicted_files.is_empty() {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            GitOperationError::MergeConflicts {
+                message: format!(
+                    "Merging '{}' into '{}' conflicts in {} file(s)",
+                    ctx.task_attempt.branch,
+                    ctx.task_attempt.target_branch,
+                    conflicted_files.len()
+                ),
+                op: ConflictOp::Merge,
+                conflicted_files,
+            },
+        )));
+    }
+
     let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
     let worktree_path = worktree_path_buf.as_path();
 
@@ -742,8 +1021,15 @@ pub async fn merge_task_attempt(
                 .next_back() // Get most recent commit message
         });
 
+    let conversational_prefixes = deployment
+        .forge_config()
+        .conversational_prefixes(ctx.project.id)
+        .await
+        .map_err(DeploymentError::Other)?;
+
     // Generate high-quality commit message
-    let commit_message_generator = CommitMessageGenerator::new();
+    let commit_message_generator =
+        CommitMessageGenerator::new().with_conversational_prefixes(conversational_prefixes.clone());
     let commit_message = commit_message_generator
         .generate(
             &ctx.task.title,
@@ -751,6 +1037,8 @@ pub async fn merge_task_attempt(
             None, // TODO: github_issue_number not yet on Task model
             executor_commit_message.as_deref(),
             worktree_path,
+            &ctx.task_attempt.target_branch,
+            &request.co_authors,
         )
         .unwrap_or_else(|_| {
             // Final fallback: just use task title
@@ -758,7 +1046,8 @@ pub async fn merge_task_attempt(
         });
 
     // Validate commit message quality
-    let validation_warnings = CommitValidator::validate(&commit_message);
+    let validation_warnings =
+        CommitValidator::validate_with_prefixes(&commit_message, &conversational_prefixes);
 
     // Log warnings
     for warning in &validation_warnings {
@@ -780,12 +1069,27 @@ pub async fn merge_task_attempt(
         tracing::info!("Commit message does not follow conventional commits format");
     }
 
+    let forge_settings = deployment
+        .forge_config()
+        .get_forge_settings(ctx.project.id)
+        .await
+        .map_err(DeploymentError::Other)?;
+    let signing = if forge_settings.sign_commits {
+        Some(deployment.git().commit_signing(
+            &ctx.project.git_repo_path,
+            forge_settings.signing_key.as_deref(),
+        )?)
+    } else {
+        None
+    };
+
     let merge_commit_id = deployment.git().merge_changes(
         &ctx.project.git_repo_path,
         worktree_path,
         &ctx.task_attempt.branch,
         &ctx.task_attempt.target_branch,
         &commit_message,
+        signing.as_ref(),
     )?;
 
     Merge::create_direct(
@@ -811,10 +1115,18 @@ pub async fn merge_task_attempt(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PushQuery {
+    /// Overwrite the remote branch even when that drops commits from it
+    #[serde(default)]
+    pub force: bool,
+}
+
 pub async fn push_task_attempt_branch(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    Query(query): Query<PushQuery>,
+) -> Result<ResponseJson<ApiResponse<PushResult>>, ApiError> {
     let github_config = deployment.config().read().await.github.clone();
     let Some(github_token) = github_config.token() else {
         return Err(GitHubServiceError::TokenInvalid.into());
@@ -825,10 +1137,13 @@ pub async fn push_task_attempt_branch(
 
     let ws_path = ensure_worktree_path(&deployment, &task_attempt).await?;
 
-    deployment
-        .git()
-        .push_to_github(&ws_path, &task_attempt.branch, &github_token)?;
-    Ok(ResponseJson(ApiResponse::success(())))
+    let result = deployment.git().push_branch(
+        &ws_path,
+        &task_attempt.branch,
+        &github_token,
+        query.force,
+    )?;
+    Ok(ResponseJson(ApiResponse::success(result)))
 }
 
 pub async fn create_github_pr(
@@ -932,6 +1247,12 @@ pub async fn create_github_pr(
                 tracing::error!("Failed to update task attempt PR status: {}", e);
             }
 
+            match review_task_for_new_pr(pool, deployment.forge_config(), &task).await {
+                Ok(true) => tracing::info!("Moved task {} to in-review for its new PR", task.id),
+                Ok(false) => {}
+                Err(e) => tracing::error!("Failed to move task {} to in-review: {}", task.id, e),
+            }
+
             // Auto-open PR in browser
             if let Err(e) = forge_core_utils::browser::open_browser(&pr_info.url).await {
                 tracing::warn!("Failed to open PR in browser: {}", e);
@@ -1036,24 +1357,14 @@ pub async fn open_task_attempt_in_editor(
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
-pub struct BranchStatus {
-    pub commits_behind: Option<usize>,
-    pub commits_ahead: Option<usize>,
-    pub has_uncommitted_changes: Option<bool>,
-    pub head_oid: Option<String>,
-    pub uncommitted_count: Option<usize>,
-    pub untracked_count: Option<usize>,
-    pub target_branch_name: String,
-    pub remote_commits_behind: Option<usize>,
-    pub remote_commits_ahead: Option<usize>,
-    pub merges: Vec<Merge>,
-    /// True if a `git rebase` is currently in progress in this worktree
-    pub is_rebase_in_progress: bool,
-    /// Current conflict operation if any
-    pub conflict_op: Option<ConflictOp>,
-    /// List of files currently in conflicted (unmerged) state
-    pub conflicted_files: Vec<String>,
+/// The attempt's recorded merges. Unlike `/branch-status` this only reads the database, so it
+/// never recreates a worktree that was cleaned up.
+pub async fn get_task_attempt_merges(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Merge>>>, ApiError> {
+    let merges = Merge::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(merges)))
 }
 
 pub async fn get_task_attempt_branch_status(
@@ -1172,10 +1483,159 @@ pub async fn get_task_attempt_branch_status(
         is_rebase_in_progress,
         conflict_op,
         conflicted_files,
+        last_fetch_at: None,
     };
     Ok(ResponseJson(ApiResponse::success(branch_status)))
 }
 
+/// Most commits returned by one page of `GET /task-attempts/{id}/commits`
+const MAX_COMMITS_PAGE: u32 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct AttemptCommitsQuery {
+    /// Maximum number of commits to return (default 50, at most 100)
+    pub limit: Option<u32>,
+    /// Number of commits to skip, newest first
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct AttemptCommits {
+    /// Commits on the attempt branch that its target branch doesn't have, newest first
+    pub commits: Vec<BranchCommit>,
+    /// How many such commits there are across all pages
+    pub total: usize,
+    pub target_branch_name: String,
+}
+
+pub async fn get_task_attempt_commits(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<AttemptCommitsQuery>,
+) -> Result<ResponseJson<ApiResponse<AttemptCommits>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
+    let limit = query.limit.unwrap_or(50).min(MAX_COMMITS_PAGE);
+    let (commits, total) = deployment.git().branch_commits(
+        &ctx.project.git_repo_path,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+        query.offset.unwrap_or(0) as usize,
+        limit as usize,
+    )?;
+
+    Ok(ResponseJson(ApiResponse::success(AttemptCommits {
+        commits,
+        total,
+        target_branch_name: task_attempt.target_branch,
+    })))
+}
+
+/// Downloadable bundle of an attempt's transcript, diff, branch status and metadata.
+/// Diff and branch status are best-effort so an attempt whose worktree or branch is gone
+/// can still be exported.
+pub async fn export_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<impl IntoResponse, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let turns = load_transcript_turns(pool, task_attempt.id).await?;
+
+    let diffs = match attempt_diffs(&deployment, &task, &task_attempt, None).await {
+        Ok(diffs) => diffs,
+        Err(e) => {
+            tracing::warn!("Exporting attempt {} without diff: {}", task_attempt.id, e);
+            Vec::new()
+        }
+    };
+
+    let branch_status = match get_task_attempt_branch_status(
+        Extension(task_attempt.clone()),
+        State(deployment.clone()),
+    )
+    .await
+    {
+        Ok(ResponseJson(response)) => response.into_data(),
+        Err(e) => {
+            tracing::warn!(
+                "Exporting attempt {} without branch status: {}",
+                task_attempt.id,
+                e
+            );
+            None
+        }
+    };
+
+    let bundle = AttemptExportBundle::new(&task, &task_attempt, &turns, &diffs, branch_status);
+    let disposition = format!("attachment; filename=\"attempt-{}.json\"", task_attempt.id);
+
+    Ok((
+        [(header::CONTENT_DISPOSITION, disposition)],
+        ResponseJson(ApiResponse::success(bundle)),
+    ))
+}
+
+/// Unified diff of an attempt's changes against its target branch, with per-file line counts
+pub async fn get_task_attempt_diff(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<DiffQuery>,
+) -> Result<ResponseJson<ApiResponse<DiffResult>>, ApiError> {
+    let task = task_attempt
+        .parent_task(&deployment.db().pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let path = query
+        .path
+        .as_deref()
+        .map(|p| p.trim().trim_matches('/'))
+        .filter(|p| !p.is_empty());
+
+    let diffs = attempt_diffs(&deployment, &task, &task_attempt, path).await?;
+    Ok(ResponseJson(ApiResponse::success(DiffResult::from_diffs(
+        &diffs,
+        MAX_DIFF_PATCH_BYTES,
+    ))))
+}
+
+async fn attempt_diffs(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    task_attempt: &TaskAttempt,
+    path: Option<&str>,
+) -> Result<Vec<Diff>, ApiError> {
+    let project = task
+        .parent_project(&deployment.db().pool)
+        .await?
+        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
+    let worktree_path = ensure_worktree_path(deployment, task_attempt).await?;
+    let base_commit = deployment.git().get_base_commit(
+        &project.git_repo_path,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+    )?;
+
+    let path_filter = path.map(|p| [p]);
+
+    Ok(deployment.git().get_diffs(
+        DiffTarget::Worktree {
+            worktree_path: &worktree_path,
+            base_commit: &base_commit,
+        },
+        path_filter.as_ref().map(|paths| paths.as_slice()),
+    )?)
+}
+
 #[derive(serde::Deserialize, Debug, TS)]
 pub struct ChangeTargetBranchRequest {
     pub new_target_branch: String,
@@ -1312,6 +1772,10 @@ pub async fn rebase_task_attempt(
                 GitOperationError::MergeConflicts {
                     message: msg,
                     op: ConflictOp::Rebase,
+                    conflicted_files: deployment
+                        .git()
+                        .get_conflicted_files(worktree_path)
+                        .unwrap_or_default(),
                 },
             ))),
             GitServiceError::RebaseInProgress => Ok(ResponseJson(ApiResponse::<
@@ -1352,6 +1816,70 @@ pub async fn abort_conflicts_task_attempt(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Abort the in-progress rebase. Unlike `/conflicts/abort`, this always runs `git rebase
+/// --abort`, so the branch returns to the commit it was on before the rebase started.
+pub async fn rebase_abort_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    if deployment.git().detect_conflict_op(&worktree_path)? != Some(ConflictOp::Rebase) {
+        return Err(ApiError::Conflict("No rebase is in progress".to_string()));
+    }
+    deployment.git().abort_rebase(&worktree_path)?;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Abort the in-progress merge, restoring the branch and worktree to their pre-merge state
+pub async fn merge_abort_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    if deployment.git().detect_conflict_op(&worktree_path)? != Some(ConflictOp::Merge) {
+        return Err(ApiError::Conflict("No merge is in progress".to_string()));
+    }
+    deployment.git().abort_merge(&worktree_path)?;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Continue the in-progress rebase, merge, cherry-pick or revert after its conflicts were
+/// resolved and staged. Returns the operation that was continued, or the files still in
+/// conflict.
+pub async fn continue_task_attempt_operation(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ConflictOp, GitOperationError>>, ApiError> {
+    let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let worktree_path = worktree_path_buf.as_path();
+
+    let Some(op) = deployment.git().detect_conflict_op(worktree_path)? else {
+        return Err(ApiError::Conflict(
+            "No rebase, merge, cherry-pick or revert is in progress".to_string(),
+        ));
+    };
+    use forge_core_services::services::git::GitServiceError;
+    let result = deployment
+        .git()
+        .continue_operation(worktree_path, op.clone());
+    match result {
+        Ok(()) => Ok(ResponseJson(ApiResponse::success(op))),
+        Err(GitServiceError::MergeConflicts(message)) => Ok(ResponseJson(
+            ApiResponse::error_with_data(GitOperationError::MergeConflicts {
+                message,
+                op,
+                conflicted_files: deployment
+                    .git()
+                    .get_conflicted_files(worktree_path)
+                    .unwrap_or_default(),
+            }),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
 #[derive(serde::Deserialize)]
 pub struct DeleteFileQuery {
     file_path: String,
@@ -1524,31 +2052,69 @@ pub async fn stop_task_attempt_execution(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[derive(Debug, Default, Deserialize, TS)]
+pub struct AttachPrRequest {
+    /// PR to attach; defaults to the first PR found for the attempt's branch
+    pub pr_number: Option<i64>,
+    /// Replace a different PR that is already attached
+    #[serde(default)]
+    pub force: bool,
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct AttachPrResponse {
     pub pr_attached: bool,
     pub pr_url: Option<String>,
     pub pr_number: Option<i64>,
     pub pr_status: Option<MergeStatus>,
+    /// Set when nothing changed because the PR was already attached
+    pub note: Option<String>,
+}
+
+/// Decides whether an attach request keeps the PR already attached to the attempt (`false`)
+/// or replaces it (`true`). A different PR is only replaced when the request is forced.
+fn should_replace_attached_pr(
+    attached: &PrMerge,
+    request: &AttachPrRequest,
+) -> Result<bool, ApiError> {
+    let attached_number = attached.pr_info.number;
+    match request.pr_number {
+        None => Ok(false),
+        Some(number) if number == attached_number => Ok(false),
+        Some(_) if request.force => Ok(true),
+        Some(number) => Err(ApiError::Conflict(format!(
+            "PR #{attached_number} is already attached to this attempt; attach PR #{number} with force to replace it"
+        ))),
+    }
 }
 
 pub async fn attach_existing_pr(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
+    payload: Option<Json<AttachPrRequest>>,
 ) -> Result<ResponseJson<ApiResponse<AttachPrResponse>>, ApiError> {
     let pool = &deployment.db().pool;
-
-    // Check if PR already attached
-    if let Some(Merge::Pr(pr_merge)) =
-        Merge::find_latest_by_task_attempt_id(pool, task_attempt.id).await?
-    {
-        return Ok(ResponseJson(ApiResponse::success(AttachPrResponse {
-            pr_attached: true,
-            pr_url: Some(pr_merge.pr_info.url.clone()),
-            pr_number: Some(pr_merge.pr_info.number),
-            pr_status: Some(pr_merge.pr_info.status.clone()),
-        })));
-    }
+    let request = payload.map(|Json(request)| request).unwrap_or_default();
+
+    // Reconcile with a PR that is already attached
+    let replaced = match Merge::find_latest_by_task_attempt_id(pool, task_attempt.id).await? {
+        Some(Merge::Pr(pr_merge)) => {
+            if !should_replace_attached_pr(&pr_merge, &request)? {
+                return Ok(ResponseJson(ApiResponse::success(AttachPrResponse {
+                    pr_attached: true,
+                    pr_url: Some(pr_merge.pr_info.url.clone()),
+                    pr_number: Some(pr_merge.pr_info.number),
+                    pr_status: Some(pr_merge.pr_info.status.clone()),
+                    note: Some(format!(
+                        "PR #{} is already attached to this attempt",
+                        pr_merge.pr_info.number
+                    )),
+                })));
+            }
+            Some(pr_merge)
+        }
+        _ => None,
+    };
 
     // Get GitHub token
     let github_config = deployment.config().read().await.github.clone();
@@ -1569,13 +2135,30 @@ pub async fn attach_existing_pr(
         .git()
         .get_github_repo_info(&project.git_repo_path)?;
 
-    // List all PRs for branch (open, closed, and merged)
-    let prs = github_service
-        .list_all_prs_for_branch(&repo_info, &task_attempt.branch)
-        .await?;
+    let pr_info = match request.pr_number {
+        Some(pr_number) => Some(github_service.update_pr_status(&repo_info, pr_number).await?),
+        None => {
+            // List all PRs for branch (open, closed, and merged)
+            let prs = github_service
+                .list_all_prs_for_branch(&repo_info, &task_attempt.branch)
+                .await?;
+
+            // Take the first PR (prefer open, but also accept merged/closed)
+            prs.into_iter().next()
+        }
+    };
+
+    if let Some(pr_info) = pr_info {
+        if let Some(replaced) = replaced {
+            tracing::info!(
+                "Replacing PR #{} with PR #{} on attempt {}",
+                replaced.pr_info.number,
+                pr_info.number,
+                task_attempt.id
+            );
+            Merge::delete(pool, replaced.id).await?;
+        }
 
-    // Take the first PR (prefer open, but also accept merged/closed)
-    if let Some(pr_info) = prs.into_iter().next() {
         // Save PR info to database
         let merge = Merge::create_pr(
             pool,
@@ -1607,6 +2190,7 @@ pub async fn attach_existing_pr(
             pr_url: Some(pr_info.url),
             pr_number: Some(pr_info.number),
             pr_status: Some(pr_info.status),
+            note: None,
         })))
     } else {
         Ok(ResponseJson(ApiResponse::success(AttachPrResponse {
@@ -1614,14 +2198,94 @@ pub async fn attach_existing_pr(
             pr_url: None,
             pr_number: None,
             pr_status: None,
+            note: None,
         })))
     }
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct MoveTaskAttemptRequest {
+    pub target_task_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct MoveTaskAttemptResponse {
+    pub attempt: TaskAttempt,
+    pub previous_task_id: Uuid,
+    /// Child tasks spawned from the attempt, which now follow it to the new task
+    pub child_tasks: u64,
+}
+
+/// Checks that an attempt under `source` may be moved to `target`
+fn validate_attempt_move(source: &Task, target: &Task, running: bool) -> Result<(), ApiError> {
+    if source.id == target.id {
+        return Err(ApiError::BadRequest(
+            "Attempt already belongs to this task".to_string(),
+        ));
+    }
+    if source.project_id != target.project_id {
+        return Err(ApiError::BadRequest(
+            "Attempts can only be moved to a task in the same project".to_string(),
+        ));
+    }
+    if running {
+        return Err(ApiError::Conflict(
+            "Attempt has running processes; stop it before moving it".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reassigns an attempt (and the child tasks spawned from it) to another task
+pub async fn move_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<MoveTaskAttemptRequest>,
+) -> Result<ResponseJson<ApiResponse<MoveTaskAttemptResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let source = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let target = Task::find_by_id(pool, payload.target_task_id)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+
+    let running = ExecutionProcess::find_by_task_attempt_id(pool, task_attempt.id, false)
+        .await?
+        .iter()
+        .any(|p| matches!(p.status, ExecutionProcessStatus::Running));
+    validate_attempt_move(&source, &target, running)?;
+
+    let child_tasks = TaskAttempt::move_to_task(pool, task_attempt.id, target.id).await?;
+    let attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_moved",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "from_task_id": source.id.to_string(),
+                "to_task_id": target.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(MoveTaskAttemptResponse {
+        attempt,
+        previous_task_id: source.id,
+        child_tasks,
+    })))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempt_id_router = Router::new()
         .route("/", get(get_task_attempt))
         .route("/follow-up", post(follow_up))
+        .route("/fork", post(fork_task_attempt))
         .route("/run-agent-setup", post(run_agent_setup))
         .route(
             "/draft",
@@ -1635,11 +2299,18 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/commit-compare", get(compare_commit_to_head))
         .route("/start-dev-server", post(start_dev_server))
         .route("/branch-status", get(get_task_attempt_branch_status))
+        .route("/merges", get(get_task_attempt_merges))
+        .route("/commits", get(get_task_attempt_commits))
+        .route("/export", get(export_task_attempt))
+        .route("/diff", get(get_task_attempt_diff))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
         .route("/merge", post(merge_task_attempt))
         .route("/push", post(push_task_attempt_branch))
         .route("/rebase", post(rebase_task_attempt))
         .route("/conflicts/abort", post(abort_conflicts_task_attempt))
+        .route("/rebase-abort", post(rebase_abort_task_attempt))
+        .route("/merge-abort", post(merge_abort_task_attempt))
+        .route("/continue", post(continue_task_attempt_operation))
         .route("/pr", post(create_github_pr))
         .route("/pr/attach", post(attach_existing_pr))
         .route("/open-editor", post(open_task_attempt_in_editor))
@@ -1647,6 +2318,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/children", get(get_task_attempt_children))
         .route("/stop", post(stop_task_attempt_execution))
         .route("/change-target-branch", post(change_target_branch))
+        .route("/move", post(move_task_attempt))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_task_attempt_middleware,
@@ -1658,3 +2330,234 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     Router::new().nest("/task-attempts", task_attempts_router)
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use forge_core_executors::{executors::BaseCodingAgent, profile::GENIE_VARIANT};
+
+    use super::*;
+
+    #[test]
+    fn follow_up_variant_must_exist_for_the_executor() {
+        let configs = ExecutorConfigs::from_defaults();
+        let profile = |variant: Option<&str>| ExecutorProfileId {
+            executor: BaseCodingAgent::ClaudeCode,
+            variant: variant.map(canonical_variant_key),
+        };
+
+        assert!(validate_follow_up_variant(&configs, &profile(None)).is_ok());
+        assert!(validate_follow_up_variant(&configs, &profile(Some("genie"))).is_ok());
+
+        let Err(ApiError::BadRequest(message)) =
+            validate_follow_up_variant(&configs, &profile(Some("no-such-variant")))
+        else {
+            panic!("unknown variant was accepted");
+        };
+        assert!(message.contains("'NO_SUCH_VARIANT'"));
+        assert!(message.contains(GENIE_VARIANT));
+    }
+
+    fn attached_pr(number: i64) -> PrMerge {
+        PrMerge {
+            id: Uuid::new_v4(),
+            task_attempt_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            target_branch_name: "main".to_string(),
+            pr_info: PullRequestInfo {
+                number,
+                url: format!("https://github.com/acme/widgets/pull/{number}"),
+                status: MergeStatus::Open,
+                merged_at: None,
+                merge_commit_sha: None,
+            },
+        }
+    }
+
+    #[test]
+    fn reattaching_the_same_pr_keeps_it() {
+        let attached = attached_pr(42);
+        for request in [
+            AttachPrRequest::default(),
+            AttachPrRequest {
+                pr_number: Some(42),
+                force: false,
+            },
+            AttachPrRequest {
+                pr_number: Some(42),
+                force: true,
+            },
+        ] {
+            assert!(!should_replace_attached_pr(&attached, &request).unwrap());
+        }
+    }
+
+    #[test]
+    fn attaching_a_different_pr_conflicts_without_force() {
+        let attached = attached_pr(42);
+        let request = AttachPrRequest {
+            pr_number: Some(43),
+            force: false,
+        };
+        let err = should_replace_attached_pr(&attached, &request).unwrap_err();
+        assert!(matches!(err, ApiError::Conflict(msg) if msg.contains("#42")));
+
+        let forced = AttachPrRequest {
+            pr_number: Some(43),
+            force: true,
+        };
+        assert!(should_replace_attached_pr(&attached, &forced).unwrap());
+    }
+
+    fn task_in(project_id: Uuid) -> Task {
+        Task {
+            id: Uuid::new_v4(),
+            project_id,
+            title: "Task".to_string(),
+            description: None,
+            status: TaskStatus::Todo,
+            parent_task_attempt: None,
+            dev_server_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn attempt_moves_are_limited_to_idle_attempts_in_the_same_project() {
+        let project_id = Uuid::new_v4();
+        let source = task_in(project_id);
+        let target = task_in(project_id);
+
+        assert!(validate_attempt_move(&source, &target, false).is_ok());
+        assert!(matches!(
+            validate_attempt_move(&source, &source, false),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            validate_attempt_move(&source, &task_in(Uuid::new_v4()), false),
+            Err(ApiError::BadRequest(msg)) if msg.contains("same project")
+        ));
+        assert!(matches!(
+            validate_attempt_move(&source, &target, true),
+            Err(ApiError::Conflict(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn attempt_pages_follow_cursors_and_carry_status() {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+        let project_id = Uuid::new_v4();
+        let project = forge_core_db::models::project::CreateProject {
+            name: "Paging".to_string(),
+            git_repo_path: format!("/tmp/paging-{project_id}"),
+            use_existing_repo: true,
+            setup_script: None,
+            dev_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            commit_prompt: None,
+            clone_url: None,
+        };
+        Project::create(&pool, &project, project_id).await.unwrap();
+        let data = forge_core_db::models::task::CreateTask::from_title_description(
+            project_id,
+            "Paging".to_string(),
+            None,
+        );
+        let task_id = Task::create(&pool, &data, Uuid::new_v4()).await.unwrap().id;
+        let create_attempt = |n: usize| {
+            let create = CreateTaskAttempt {
+                executor: BaseCodingAgent::ClaudeCode,
+                variant: None,
+                base_branch: "main".to_string(),
+                branch: format!("forge/attempt-{n}"),
+            };
+            let pool = pool.clone();
+            async move {
+                TaskAttempt::create(&pool, &create, Uuid::new_v4(), task_id)
+                    .await
+                    .unwrap()
+            }
+        };
+        let mut created = Vec::new();
+        for n in 0..25 {
+            created.push(create_attempt(n).await);
+        }
+
+        let all = fetch_attempt_page(&pool, Some(task_id), None, None)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 25);
+
+        let mut paged = Vec::new();
+        let mut before = None;
+        loop {
+            let page = fetch_attempt_page(&pool, Some(task_id), before, Some(10))
+                .await
+                .unwrap();
+            let Some(last) = page.last() else { break };
+            before = Some(PageCursor::new(last.attempt.created_at, last.attempt.id));
+            paged.extend(page.iter().map(|item| item.attempt.id));
+
+            // An attempt started while paging doesn't push earlier rows into later pages
+            let newer = create_attempt(100 + paged.len()).await;
+            sqlx::query(
+                "UPDATE task_attempts SET created_at = datetime('now', '+1 day') WHERE id = ?",
+            )
+            .bind(newer.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        assert_eq!(
+            paged,
+            all.iter().map(|item| item.attempt.id).collect::<Vec<_>>()
+        );
+
+        // Dev servers and dropped processes don't count towards the status
+        let attempt = &created[0];
+        for (run_reason, status, dropped) in [
+            ("codingagent", "failed", false),
+            ("codingagent", "running", true),
+            ("devserver", "running", false),
+        ] {
+            sqlx::query(
+                "INSERT INTO execution_processes \
+                 (id, task_attempt_id, run_reason, executor_action, status, dropped) \
+                 VALUES (?, ?, ?, '{}', ?, ?)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(attempt.id)
+            .bind(run_reason)
+            .bind(status)
+            .bind(dropped)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        Merge::create_direct(&pool, attempt.id, "main", "abc123")
+            .await
+            .unwrap();
+
+        let items = fetch_attempt_page(&pool, Some(task_id), None, None)
+            .await
+            .unwrap();
+        let item = items
+            .iter()
+            .find(|item| item.attempt.id == attempt.id)
+            .unwrap();
+        assert_eq!(
+            item.latest_process_status,
+            Some(ExecutionProcessStatus::Failed)
+        );
+        assert!(item.merged);
+        let untouched = items
+            .iter()
+            .find(|item| item.attempt.id == created[1].id)
+            .unwrap();
+        assert_eq!(untouched.latest_process_status, None);
+        assert!(!untouched.merged);
+    }
+}
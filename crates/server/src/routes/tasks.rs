@@ -1,4 +1,4 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{ops::Range, path::PathBuf, sync::Arc};
 
 use anyhow;
 use axum::{
@@ -7,34 +7,57 @@ use axum::{
         Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
     routing::{get, post},
 };
 use forge_core_db::models::{
+    forge_agent::{AGENT_CHAT_TYPE, AgentRegistered, ForgeAgent},
     image::TaskImage,
     project::Project,
     task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus, UpdateTask},
     task_attempt::{CreateTaskAttempt, TaskAttempt},
+    task_idempotency_key::TaskIdempotencyKey,
 };
-use forge_core_deployment::Deployment;
-use forge_core_executors::profile::ExecutorProfileId;
-use forge_core_services::services::container::{
-    ContainerService, WorktreeCleanupData, cleanup_worktrees_direct,
+use forge_core_deployment::{Deployment, DeploymentError};
+use forge_core_executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use forge_core_services::services::{
+    container::{
+        ContainerService, WorktreeCleanupData, cleanup_worktrees_direct, lock_task_worktrees,
+        restore_task_worktrees,
+    },
+    git::GitBranch,
 };
-use forge_core_utils::response::ApiResponse;
+use forge_core_utils::{log_msg::LogMsg, response::ApiResponse, text::title_similarity};
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use sqlx::Error as SqlxError;
 use ts_rs_forge::TS;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_task_middleware};
+use crate::{
+    DeploymentImpl, error::ApiError, middleware::load_task_middleware,
+    routes::pagination::PageCursor,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TaskQuery {
     pub project_id: Uuid,
+    /// Only return tasks with this status
+    pub status: Option<TaskStatus>,
+    /// Maximum number of tasks to return; all remaining tasks when absent
+    pub limit: Option<u32>,
+    /// Only return tasks older than this cursor, the key of the last task of a previous page
+    pub before: Option<String>,
+}
+
+/// Window into the newest-first task list of a project
+#[derive(Debug, Clone, Copy, Default)]
+struct TaskPage {
+    status: Option<TaskStatus>,
+    limit: Option<u32>,
+    before: Option<PageCursor>,
 }
 
 /// Get kanban tasks (excludes agent tasks)
@@ -45,68 +68,103 @@ pub async fn get_tasks(
 ) -> Result<ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>, ApiError> {
     // Kanban endpoint always excludes agent tasks
     // Agent tasks have their own dedicated endpoint
-    let tasks = get_kanban_tasks(&deployment.db().pool, query.project_id).await?;
+    let page = TaskPage {
+        status: query.status,
+        limit: query.limit,
+        before: PageCursor::from_query(query.before.as_deref())?,
+    };
+    let tasks = get_kanban_tasks(&deployment.db().pool, query.project_id, page).await?;
     Ok(ResponseJson(ApiResponse::success(tasks)))
 }
 
+/// Kanban tasks with their attempt status. Attempt and process attributes are aggregated in
+/// one grouped pass per table over the requested page only, rather than through correlated
+/// subqueries evaluated for every task.
+const KANBAN_TASKS_QUERY: &str = r#"WITH page AS (
+  SELECT t.id, t.project_id, t.title, t.description, t.status,
+         t.parent_task_attempt, t.dev_server_id, t.created_at, t.updated_at
+    FROM tasks t
+   WHERE t.project_id = ?1
+     AND t.id NOT IN (SELECT task_id FROM forge_agents)
+     AND (?2 IS NULL OR t.status = ?2)
+     AND (?3 IS NULL
+          OR datetime(t.created_at, 'subsec') < datetime(?3, 'subsec')
+          OR (datetime(t.created_at, 'subsec') = datetime(?3, 'subsec') AND t.id < ?4))
+   ORDER BY datetime(t.created_at, 'subsec') DESC, t.id DESC
+   LIMIT ?5
+),
+ranked_attempts AS (
+  SELECT ta.task_id,
+         ta.executor,
+         ROW_NUMBER() OVER (PARTITION BY ta.task_id ORDER BY ta.created_at DESC) AS recency
+    FROM task_attempts ta
+    JOIN page p ON p.id = ta.task_id
+),
+attempt_stats AS (
+  SELECT task_id,
+         COUNT(*)                                     AS attempt_count,
+         MAX(CASE WHEN recency = 1 THEN executor END) AS executor
+    FROM ranked_attempts
+   GROUP BY task_id
+),
+ranked_processes AS (
+  SELECT ta.task_id,
+         ep.status,
+         ROW_NUMBER() OVER (PARTITION BY ta.task_id ORDER BY ep.created_at DESC) AS recency
+    FROM task_attempts ta
+    JOIN page p ON p.id = ta.task_id
+    JOIN execution_processes ep ON ep.task_attempt_id = ta.id
+   WHERE ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+),
+process_stats AS (
+  SELECT task_id,
+         MAX(status = 'running')                            AS has_in_progress_attempt,
+         MAX(recency = 1 AND status IN ('failed','killed')) AS last_attempt_failed
+    FROM ranked_processes
+   GROUP BY task_id
+)
+SELECT
+  p.id                            AS "id",
+  p.project_id                    AS "project_id",
+  p.title,
+  p.description,
+  p.status                        AS "status",
+  p.parent_task_attempt           AS "parent_task_attempt",
+  p.dev_server_id                 AS "dev_server_id",
+  p.created_at                    AS "created_at",
+  p.updated_at                    AS "updated_at",
+  COALESCE(ps.has_in_progress_attempt, 0) AS has_in_progress_attempt,
+  COALESCE(ps.last_attempt_failed, 0)     AS last_attempt_failed,
+  a.executor                      AS executor,
+  COALESCE(a.attempt_count, 0)    AS attempt_count
+FROM page p
+LEFT JOIN attempt_stats a  ON a.task_id = p.id
+LEFT JOIN process_stats ps ON ps.task_id = p.id
+ORDER BY datetime(p.created_at, 'subsec') DESC, p.id DESC"#;
+
 /// Get kanban tasks (excludes agent tasks in forge_agents table)
 async fn get_kanban_tasks(
     pool: &sqlx::SqlitePool,
     project_id: Uuid,
+    page: TaskPage,
 ) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
-    let query_str = r#"SELECT
-  t.id                            AS "id",
-  t.project_id                    AS "project_id",
-  t.title,
-  t.description,
-  t.status                        AS "status",
-  t.parent_task_attempt           AS "parent_task_attempt",
-  t.dev_server_id                 AS "dev_server_id",
-  t.created_at                    AS "created_at",
-  t.updated_at                    AS "updated_at",
-
-  CASE WHEN EXISTS (
-    SELECT 1
-      FROM task_attempts ta
-      JOIN execution_processes ep
-        ON ep.task_attempt_id = ta.id
-     WHERE ta.task_id       = t.id
-       AND ep.status        = 'running'
-       AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
-     LIMIT 1
-  ) THEN 1 ELSE 0 END            AS has_in_progress_attempt,
-
-  CASE WHEN (
-    SELECT ep.status
-      FROM task_attempts ta
-      JOIN execution_processes ep
-        ON ep.task_attempt_id = ta.id
-     WHERE ta.task_id       = t.id
-     AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
-     ORDER BY ep.created_at DESC
-     LIMIT 1
-  ) IN ('failed','killed') THEN 1 ELSE 0 END
-                                 AS last_attempt_failed,
-
-  ( SELECT ta.executor
-      FROM task_attempts ta
-      WHERE ta.task_id = t.id
-     ORDER BY ta.created_at DESC
-      LIMIT 1
-    )                               AS executor,
-
-  ( SELECT COUNT(*)
-      FROM task_attempts ta
-      WHERE ta.task_id = t.id
-    )                               AS attempt_count
-
-FROM tasks t
-WHERE t.project_id = ?
-  AND t.id NOT IN (SELECT task_id FROM forge_agents)
-ORDER BY t.created_at DESC"#;
+    fetch_kanban_tasks(pool, KANBAN_TASKS_QUERY, project_id, page).await
+}
 
+async fn fetch_kanban_tasks(
+    pool: &sqlx::SqlitePool,
+    query_str: &str,
+    project_id: Uuid,
+    page: TaskPage,
+) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
+    // Pages are keyed on (created_at, id) so tasks created between two requests can't shift
+    // rows across pages; SQLite treats a negative LIMIT as unbounded
     let rows = sqlx::query(query_str)
         .bind(project_id)
+        .bind(page.status)
+        .bind(page.before.map(|cursor| cursor.created_at))
+        .bind(page.before.map(|cursor| cursor.id))
+        .bind(page.limit.map_or(-1, i64::from))
         .fetch_all(pool)
         .await?;
 
@@ -174,19 +232,19 @@ pub async fn stream_tasks_ws(
 }
 
 /// Handle kanban WebSocket (excludes agent tasks)
-/// Uses a cache with periodic refresh to minimize DB queries
+/// Uses a cache, refreshed periodically and whenever an agent is registered, to minimize DB queries
 async fn handle_kanban_tasks_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
     project_id: Uuid,
 ) -> anyhow::Result<()> {
-    use std::{collections::HashSet, sync::Arc, time::Duration};
+    use std::{collections::HashSet, sync::Arc};
 
-    use forge_core_utils::log_msg::LogMsg;
-    use serde_json::json;
     use tokio::sync::RwLock;
 
     let pool = deployment.db().pool.clone();
+    // Subscribe before the initial query so no registration falls between the two
+    let registrations = ForgeAgent::subscribe_registrations();
 
     // Batch query for all agent task IDs at initialization
     // CRITICAL: Fail early if DB query fails - empty cache would leak agent tasks to kanban
@@ -211,180 +269,313 @@ async fn handle_kanban_tasks_ws(
         Arc::new(RwLock::new(agent_tasks.into_iter().collect()))
     };
 
-    // Spawn background task to refresh agent task IDs periodically
-    let refresh_cache = agent_task_ids.clone();
-    let refresh_pool = pool.clone();
-    let refresh_project_id = project_id;
-    let refresh_task_handle = tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(30));
-        loop {
-            interval.tick().await;
+    let raw_stream = deployment.events().stream_tasks_raw(project_id).await?;
 
-            match sqlx::query_scalar::<_, Uuid>(
-                "SELECT task_id FROM forge_agents fa
-                 INNER JOIN tasks t ON fa.task_id = t.id
-                 WHERE t.project_id = ?",
-            )
-            .bind(refresh_project_id)
-            .fetch_all(&refresh_pool)
-            .await
-            {
-                Ok(tasks) => {
-                    let mut cache = refresh_cache.write().await;
-                    cache.clear();
-                    cache.extend(tasks);
-                    tracing::trace!(
-                        "Refreshed agent task cache for project {}: {} tasks",
-                        refresh_project_id,
-                        cache.len()
-                    );
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to refresh agent task cache for project {}: {}",
-                        refresh_project_id,
-                        e
-                    );
+    // Keep the agent task cache fresh: periodically, and as soon as an agent is registered
+    let refresh_task_handle = tokio::spawn(refresh_agent_task_cache(
+        agent_task_ids.clone(),
+        pool.clone(),
+        project_id,
+        registrations,
+        kanban_agent_refresh_interval(),
+    ));
+    deployment
+        .shutdown()
+        .register_task("kanban_agent_cache_refresh", &refresh_task_handle);
+
+    // End the stream on shutdown so the refresher is aborted below
+    let stream = filter_kanban_stream(
+        raw_stream.take_until(deployment.shutdown().cancellation_token().cancelled_owned()),
+        agent_task_ids,
+        pool,
+        kanban_snapshot_page_size(),
+    )
+    .map_ok(|msg| msg.to_ws_message_unchecked());
+
+    futures_util::pin_mut!(stream);
+
+    let (mut sender, mut receiver) = socket.split();
+
+    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(msg) => {
+                if sender.send(msg).await.is_err() {
+                    break;
                 }
             }
+            Err(e) => {
+                tracing::error!("stream error: {}", e);
+                break;
+            }
         }
-    });
+    }
 
-    // Get the raw stream and filter out agent tasks
-    let stream = deployment
-        .events()
-        .stream_tasks_raw(project_id)
-        .await?
-        .filter_map(move |msg_result| {
-            let agent_task_ids = agent_task_ids.clone();
-            let pool = pool.clone();
-            async move {
-                match msg_result {
-                    Ok(LogMsg::JsonPatch(patch)) => {
-                        if let Some(patch_op) = patch.0.first() {
-                            // Handle direct task patches
-                            if patch_op.path().starts_with("/tasks/") {
-                                match patch_op {
-                                    json_patch::PatchOperation::Add(op) => {
-                                        if let Ok(task_with_status) =
-                                            serde_json::from_value::<TaskWithAttemptStatus>(
-                                                op.value.clone(),
-                                            )
-                                        {
-                                            let task_id = task_with_status.task.id;
-                                            // Filter by forge_agents cache OR by task status
-                                            // The status check is a backup for race conditions
-                                            if is_agent_task(&agent_task_ids, &pool, task_id).await
-                                                || task_with_status.task.status == TaskStatus::Agent
-                                            {
-                                                return None;
-                                            }
-                                            return Some(Ok(LogMsg::JsonPatch(patch)));
-                                        }
-                                    }
-                                    json_patch::PatchOperation::Replace(op) => {
-                                        if let Ok(task_with_status) =
-                                            serde_json::from_value::<TaskWithAttemptStatus>(
-                                                op.value.clone(),
-                                            )
+    refresh_task_handle.abort();
+
+    Ok(())
+}
+
+/// Drops agent tasks from a raw task stream: the cache (or, on a miss, `forge_agents`) decides
+/// membership, with the `agent` status as a backup. The initial snapshot is filtered as a
+/// whole and then split into pages of `snapshot_page_size` tasks.
+fn filter_kanban_stream(
+    raw: impl futures_util::Stream<Item = Result<LogMsg, std::io::Error>>,
+    agent_task_ids: Arc<tokio::sync::RwLock<std::collections::HashSet<Uuid>>>,
+    pool: sqlx::SqlitePool,
+    snapshot_page_size: usize,
+) -> impl futures_util::Stream<Item = Result<LogMsg, std::io::Error>> {
+    use serde_json::json;
+
+    raw.filter_map(move |msg_result| {
+        let agent_task_ids = agent_task_ids.clone();
+        let pool = pool.clone();
+        async move {
+            match msg_result {
+                Ok(LogMsg::JsonPatch(patch)) => {
+                    if let Some(patch_op) = patch.0.first() {
+                        // Handle direct task patches
+                        if patch_op.path().starts_with("/tasks/") {
+                            match patch_op {
+                                json_patch::PatchOperation::Add(op) => {
+                                    if let Ok(task_with_status) =
+                                        serde_json::from_value::<TaskWithAttemptStatus>(
+                                            op.value.clone(),
+                                        )
+                                    {
+                                        let task_id = task_with_status.task.id;
+                                        // Filter by forge_agents cache OR by task status
+                                        // The status check is a backup for race conditions
+                                        if is_agent_task(&agent_task_ids, &pool, task_id).await
+                                            || task_with_status.task.status == TaskStatus::Agent
                                         {
-                                            let task_id = task_with_status.task.id;
-                                            // Filter by forge_agents cache OR by task status
-                                            // The status check is a backup for race conditions
-                                            if is_agent_task(&agent_task_ids, &pool, task_id).await
-                                                || task_with_status.task.status == TaskStatus::Agent
-                                            {
-                                                return None;
-                                            }
-                                            return Some(Ok(LogMsg::JsonPatch(patch)));
+                                            return None;
                                         }
-                                    }
-                                    json_patch::PatchOperation::Remove(_) => {
                                         return Some(Ok(LogMsg::JsonPatch(patch)));
                                     }
-                                    _ => {}
                                 }
-                            }
-                            // Handle initial snapshot
-                            else if patch_op.path() == "/tasks"
-                                && let json_patch::PatchOperation::Replace(op) = patch_op
-                                && let Some(tasks_obj) = op.value.as_object()
-                            {
-                                let mut filtered_tasks = serde_json::Map::new();
-                                for (task_id_str, task_value) in tasks_obj {
+                                json_patch::PatchOperation::Replace(op) => {
                                     if let Ok(task_with_status) =
                                         serde_json::from_value::<TaskWithAttemptStatus>(
-                                            task_value.clone(),
+                                            op.value.clone(),
                                         )
                                     {
                                         let task_id = task_with_status.task.id;
                                         // Filter by forge_agents cache OR by task status
                                         // The status check is a backup for race conditions
-                                        let is_agent =
-                                            is_agent_task(&agent_task_ids, &pool, task_id).await
-                                                || task_with_status.task.status
-                                                    == TaskStatus::Agent;
-                                        if !is_agent {
-                                            filtered_tasks.insert(
-                                                task_id_str.to_string(),
-                                                task_value.clone(),
-                                            );
+                                        if is_agent_task(&agent_task_ids, &pool, task_id).await
+                                            || task_with_status.task.status == TaskStatus::Agent
+                                        {
+                                            return None;
                                         }
+                                        return Some(Ok(LogMsg::JsonPatch(patch)));
                                     }
                                 }
-
-                                let filtered_patch = json!([{
-                                    "op": "replace",
-                                    "path": "/tasks",
-                                    "value": filtered_tasks
-                                }]);
-                                return match serde_json::from_value(filtered_patch) {
-                                    Ok(patch) => Some(Ok(LogMsg::JsonPatch(patch))),
-                                    Err(e) => {
-                                        tracing::error!(
-                                            "Failed to deserialize filtered patch: {}",
-                                            e
-                                        );
-                                        Some(Err(std::io::Error::new(
-                                            std::io::ErrorKind::InvalidData,
-                                            "Patch deserialization failed",
-                                        )))
+                                json_patch::PatchOperation::Remove(_) => {
+                                    return Some(Ok(LogMsg::JsonPatch(patch)));
+                                }
+                                _ => {}
+                            }
+                        }
+                        // Handle initial snapshot
+                        else if patch_op.path() == "/tasks"
+                            && let json_patch::PatchOperation::Replace(op) = patch_op
+                            && let Some(tasks_obj) = op.value.as_object()
+                        {
+                            let mut filtered_tasks = serde_json::Map::new();
+                            for (task_id_str, task_value) in tasks_obj {
+                                if let Ok(task_with_status) =
+                                    serde_json::from_value::<TaskWithAttemptStatus>(
+                                        task_value.clone(),
+                                    )
+                                {
+                                    let task_id = task_with_status.task.id;
+                                    // Filter by forge_agents cache OR by task status
+                                    // The status check is a backup for race conditions
+                                    let is_agent = is_agent_task(&agent_task_ids, &pool, task_id)
+                                        .await
+                                        || task_with_status.task.status == TaskStatus::Agent;
+                                    if !is_agent {
+                                        filtered_tasks
+                                            .insert(task_id_str.to_string(), task_value.clone());
                                     }
-                                };
+                                }
                             }
+
+                            let filtered_patch = json!([{
+                                "op": "replace",
+                                "path": "/tasks",
+                                "value": filtered_tasks
+                            }]);
+                            return match serde_json::from_value(filtered_patch) {
+                                Ok(patch) => Some(Ok(LogMsg::JsonPatch(patch))),
+                                Err(e) => {
+                                    tracing::error!("Failed to deserialize filtered patch: {}", e);
+                                    Some(Err(std::io::Error::new(
+                                        std::io::ErrorKind::InvalidData,
+                                        "Patch deserialization failed",
+                                    )))
+                                }
+                            };
                         }
-                        Some(Ok(LogMsg::JsonPatch(patch)))
                     }
-                    Ok(other) => Some(Ok(other)),
-                    Err(e) => Some(Err(e)),
+                    Some(Ok(LogMsg::JsonPatch(patch)))
                 }
+                Ok(other) => Some(Ok(other)),
+                Err(e) => Some(Err(e)),
             }
-        })
-        .map_ok(|msg| msg.to_ws_message_unchecked());
-
-    futures_util::pin_mut!(stream);
-
-    let (mut sender, mut receiver) = socket.split();
+        }
+    })
+    .flat_map(move |item| {
+        // Split the (filtered) initial snapshot into pages so large boards render
+        // progressively; every other message passes through untouched
+        let items: Vec<_> = match item {
+            Ok(msg) => paginate_tasks_snapshot(msg, snapshot_page_size)
+                .into_iter()
+                .map(Ok)
+                .collect(),
+            Err(e) => vec![Err(e)],
+        };
+        futures_util::stream::iter(items)
+    })
+}
 
-    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+/// Default interval between full reloads of the kanban's agent task cache
+const DEFAULT_KANBAN_AGENT_REFRESH_SECS: u64 = 30;
+
+/// Interval between full reloads of the kanban's agent task cache, overridable via
+/// `FORGE_KANBAN_AGENT_REFRESH_SECS`.
+fn kanban_agent_refresh_interval() -> std::time::Duration {
+    let secs = std::env::var("FORGE_KANBAN_AGENT_REFRESH_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_KANBAN_AGENT_REFRESH_SECS);
+    std::time::Duration::from_secs(secs)
+}
 
-    while let Some(item) = stream.next().await {
-        match item {
-            Ok(msg) => {
-                if sender.send(msg).await.is_err() {
-                    break;
+/// Keeps `cache` in sync with the project's `forge_agents` rows. Newly registered agent tasks
+/// are added as soon as they are announced on `registrations`; the full reload every `period`
+/// (or after missed announcements) also drops tasks that stopped being agents.
+async fn refresh_agent_task_cache(
+    cache: Arc<tokio::sync::RwLock<std::collections::HashSet<Uuid>>>,
+    pool: sqlx::SqlitePool,
+    project_id: Uuid,
+    mut registrations: tokio::sync::broadcast::Receiver<AgentRegistered>,
+    period: std::time::Duration,
+) {
+    use tokio::sync::broadcast::error::RecvError;
+
+    let mut interval = tokio::time::interval(period);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            event = registrations.recv() => match event {
+                Ok(event) => {
+                    if event.project_id == project_id {
+                        cache.write().await.insert(event.task_id);
+                    }
+                    continue;
                 }
+                // Announcements were dropped, so reload everything now
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return,
+            },
+        }
+
+        match sqlx::query_scalar::<_, Uuid>(
+            "SELECT task_id FROM forge_agents fa
+             INNER JOIN tasks t ON fa.task_id = t.id
+             WHERE t.project_id = ?",
+        )
+        .bind(project_id)
+        .fetch_all(&pool)
+        .await
+        {
+            Ok(tasks) => {
+                let mut cache = cache.write().await;
+                cache.clear();
+                cache.extend(tasks);
+                tracing::trace!(
+                    "Refreshed agent task cache for project {}: {} tasks",
+                    project_id,
+                    cache.len()
+                );
             }
             Err(e) => {
-                tracing::error!("stream error: {}", e);
-                break;
+                tracing::warn!(
+                    "Failed to refresh agent task cache for project {}: {}",
+                    project_id,
+                    e
+                );
             }
         }
     }
+}
 
-    refresh_task_handle.abort();
+/// Default number of tasks per page of the kanban WebSocket initial snapshot
+const DEFAULT_KANBAN_SNAPSHOT_PAGE_SIZE: usize = 100;
 
-    Ok(())
+/// Page size for the kanban initial snapshot, overridable via `FORGE_KANBAN_SNAPSHOT_PAGE_SIZE`.
+fn kanban_snapshot_page_size() -> usize {
+    std::env::var("FORGE_KANBAN_SNAPSHOT_PAGE_SIZE")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|size| *size > 0)
+        .unwrap_or(DEFAULT_KANBAN_SNAPSHOT_PAGE_SIZE)
+}
+
+/// Splits a `/tasks` replace snapshot into pages of at most `page_size` tasks.
+///
+/// The first page replaces `/tasks` (so the client still starts from a clean board) and each
+/// following page is a single patch of `add` operations. Tasks keep the snapshot's order
+/// across pages. Any other message, or a snapshot that already fits in one page, is returned
+/// as-is.
+fn paginate_tasks_snapshot(msg: LogMsg, page_size: usize) -> Vec<LogMsg> {
+    use serde_json::json;
+
+    let page_size = page_size.max(1);
+    let tasks = match &msg {
+        LogMsg::JsonPatch(patch) if patch.0.len() == 1 && patch.0[0].path() == "/tasks" => {
+            match &patch.0[0] {
+                json_patch::PatchOperation::Replace(op) => match op.value.as_object() {
+                    Some(tasks) if tasks.len() > page_size => tasks,
+                    _ => return vec![msg],
+                },
+                _ => return vec![msg],
+            }
+        }
+        _ => return vec![msg],
+    };
+
+    let entries: Vec<(&String, &serde_json::Value)> = tasks.iter().collect();
+    let mut pages = Vec::new();
+    for (index, page) in entries.chunks(page_size).enumerate() {
+        let patch = if index == 0 {
+            let first_page: serde_json::Map<String, serde_json::Value> = page
+                .iter()
+                .map(|(id, task)| ((*id).clone(), (*task).clone()))
+                .collect();
+            json!([{ "op": "replace", "path": "/tasks", "value": first_page }])
+        } else {
+            serde_json::Value::Array(
+                page.iter()
+                    .map(|(id, task)| {
+                        json!({ "op": "add", "path": format!("/tasks/{id}"), "value": task })
+                    })
+                    .collect(),
+            )
+        };
+        match serde_json::from_value(patch) {
+            Ok(patch) => pages.push(LogMsg::JsonPatch(patch)),
+            Err(e) => {
+                tracing::error!("Failed to build kanban snapshot page: {}", e);
+                return vec![msg];
+            }
+        }
+    }
+    pages
 }
 
 /// Check if a task is an agent task using cache with DB fallback
@@ -429,11 +620,343 @@ pub async fn get_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
+/// Titles at least this similar to an existing task's title are reported as duplicates
+const DUPLICATE_TITLE_SIMILARITY: f64 = 0.85;
+
+/// Results returned by `search_tasks` unless the caller asks for a different number
+const SEARCH_RESULT_LIMIT: usize = 20;
+/// Upper bound on `limit` for `search_tasks`
+const SEARCH_RESULT_MAX: usize = 100;
+/// Title words at least this similar to the query count as a (misspelled) match
+const FUZZY_TITLE_SIMILARITY: f64 = 0.75;
+/// Characters of context kept on each side of a match in a highlight
+const HIGHLIGHT_CONTEXT: usize = 40;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TaskSearchQuery {
+    pub q: String,
+    pub project_id: Option<Uuid>,
+    pub status: Option<TaskStatus>,
+    pub limit: Option<usize>,
+}
+
+/// Task field a search query matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskMatchField {
+    Title,
+    Description,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskSearchHit {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub task: Task,
+    pub matched_field: TaskMatchField,
+    /// The matched field with the match wrapped in `**`, cut down to the text around it
+    pub highlight: String,
+    /// Relevance between 0.0 and 1.0; title matches always outrank description matches
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct TaskSearchResults {
+    pub hits: Vec<TaskSearchHit>,
+    /// Number of matching tasks before `limit` was applied
+    pub total_matches: usize,
+}
+
+/// Search task titles and descriptions across projects, best matches first
+pub async fn search_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskSearchQuery>,
+) -> Result<ResponseJson<ApiResponse<TaskSearchResults>>, ApiError> {
+    let q = query.q.trim();
+    if q.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Search query cannot be empty".to_string(),
+        ));
+    }
+
+    let tasks =
+        Task::find_searchable(&deployment.db().pool, query.project_id, query.status).await?;
+    let mut hits = rank_tasks(q, tasks);
+    let total_matches = hits.len();
+    let limit = query.limit.unwrap_or(SEARCH_RESULT_LIMIT);
+    hits.truncate(limit.min(SEARCH_RESULT_MAX));
+
+    Ok(ResponseJson(ApiResponse::success(TaskSearchResults {
+        hits,
+        total_matches,
+    })))
+}
+
+/// Tasks matching `query`, best first. Exact (case-insensitive) title matches rank highest,
+/// then titles that nearly match, then descriptions containing the query; ties go to the most
+/// recently updated task.
+fn rank_tasks(query: &str, tasks: Vec<Task>) -> Vec<TaskSearchHit> {
+    let mut hits: Vec<TaskSearchHit> = tasks
+        .into_iter()
+        .filter_map(|task| {
+            let (matched_field, score, highlight) = match_task(query, &task)?;
+            Some(TaskSearchHit {
+                task,
+                matched_field,
+                highlight,
+                score,
+            })
+        })
+        .collect();
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.task.updated_at.cmp(&a.task.updated_at))
+    });
+    hits
+}
+
+fn match_task(query: &str, task: &Task) -> Option<(TaskMatchField, f64, String)> {
+    if let Some(range) = find_ignore_case(&task.title, query) {
+        // A query covering more of the title is a better match
+        let coverage = range.len() as f64 / task.title.len() as f64;
+        let score = 0.9 + 0.1 * coverage;
+        return Some((TaskMatchField::Title, score, highlight(&task.title, range)));
+    }
+
+    if let Some((range, similarity)) = fuzzy_title_match(&task.title, query) {
+        let score = 0.7 * similarity;
+        return Some((TaskMatchField::Title, score, highlight(&task.title, range)));
+    }
+
+    let description = task.description.as_deref()?;
+    let range = find_ignore_case(description, query)?;
+    Some((
+        TaskMatchField::Description,
+        0.5,
+        highlight(description, range),
+    ))
+}
+
+/// Byte range of the first case-insensitive occurrence of `needle` in `haystack`
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<Range<usize>> {
+    let needle: Vec<char> = needle.chars().collect();
+    if needle.is_empty() {
+        return None;
+    }
+    haystack.char_indices().find_map(|(start, _)| {
+        let mut chars = haystack[start..].char_indices();
+        for expected in &needle {
+            let (_, c) = chars.next()?;
+            if !c.to_lowercase().eq(expected.to_lowercase()) {
+                return None;
+            }
+        }
+        let end = chars
+            .next()
+            .map_or(haystack.len(), |(offset, _)| start + offset);
+        Some(start..end)
+    })
+}
+
+/// The run of title words, as many as the query has, that is most similar to the query
+fn fuzzy_title_match(title: &str, query: &str) -> Option<(Range<usize>, f64)> {
+    let words: Vec<Range<usize>> = title
+        .split_whitespace()
+        .map(|word| {
+            let start = word.as_ptr() as usize - title.as_ptr() as usize;
+            start..start + word.len()
+        })
+        .collect();
+    let width = query
+        .split_whitespace()
+        .count()
+        .clamp(1, words.len().max(1));
+
+    words
+        .windows(width)
+        .map(|window| {
+            let range = window[0].start..window[width - 1].end;
+            let similarity = title_similarity(query, &title[range.clone()]);
+            (range, similarity)
+        })
+        .filter(|(_, similarity)| *similarity >= FUZZY_TITLE_SIMILARITY)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// `text` with `range` wrapped in `**`, keeping only `HIGHLIGHT_CONTEXT` characters around it
+fn highlight(text: &str, range: Range<usize>) -> String {
+    let start = text[..range.start]
+        .char_indices()
+        .rev()
+        .nth(HIGHLIGHT_CONTEXT - 1)
+        .map_or(0, |(i, _)| i);
+    let end = text[range.end..]
+        .char_indices()
+        .nth(HIGHLIGHT_CONTEXT)
+        .map_or(text.len(), |(i, _)| range.end + i);
+
+    format!(
+        "{}{}**{}**{}{}",
+        if start > 0 { "…" } else { "" },
+        &text[start..range.start],
+        &text[range.clone()],
+        &text[range.end..end],
+        if end < text.len() { "…" } else { "" },
+    )
+}
+
+/// How `create_task` treats tasks whose titles closely match existing ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateCheckMode {
+    /// Skip the check
+    Off,
+    /// Create the task and report the possible duplicates alongside it
+    #[default]
+    Warn,
+    /// Refuse to create the task unless `force` is set
+    Strict,
+}
+
+impl DuplicateCheckMode {
+    /// Default mode, overridable via `FORGE_TASK_DUPLICATE_CHECK`.
+    fn from_env() -> Self {
+        match std::env::var("FORGE_TASK_DUPLICATE_CHECK")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "off" => Self::Off,
+            "strict" => Self::Strict,
+            _ => Self::Warn,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTaskParams {
+    /// Create the task even if possible duplicates exist
+    #[serde(default)]
+    pub force: bool,
+    pub duplicate_check: Option<DuplicateCheckMode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DuplicateTask {
+    pub id: Uuid,
+    pub title: String,
+    pub similarity: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct CreatedTask {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub task: Task,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub possible_duplicates: Vec<DuplicateTask>,
+}
+
+/// Existing tasks in the project whose titles closely match `title`, most similar first
+async fn find_duplicate_tasks(
+    pool: &sqlx::SqlitePool,
+    project_id: Uuid,
+    title: &str,
+) -> Result<Vec<DuplicateTask>, ApiError> {
+    let mut duplicates: Vec<DuplicateTask> = Task::find_titles_by_project_id(pool, project_id)
+        .await?
+        .into_iter()
+        .filter_map(|(id, existing_title)| {
+            let similarity = title_similarity(title, &existing_title);
+            (similarity >= DUPLICATE_TITLE_SIMILARITY).then_some(DuplicateTask {
+                id,
+                title: existing_title,
+                similarity,
+            })
+        })
+        .collect();
+    duplicates.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    Ok(duplicates)
+}
+
+/// Header a client sends so that retrying a task creation returns the task it created
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// The request's idempotency key, if it sent a non-empty one
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, ApiError> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    let key = value
+        .to_str()
+        .map_err(|_| ApiError::BadRequest(format!("{IDEMPOTENCY_KEY_HEADER} must be ASCII")))?
+        .trim();
+    if key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "{IDEMPOTENCY_KEY_HEADER} must be at most {MAX_IDEMPOTENCY_KEY_LEN} characters"
+        )));
+    }
+    Ok((!key.is_empty()).then(|| key.to_string()))
+}
+
+/// Task created earlier in the project under `key`
+async fn find_idempotent_task(
+    pool: &sqlx::SqlitePool,
+    project_id: Uuid,
+    key: Option<&str>,
+) -> Result<Option<Task>, ApiError> {
+    let Some(key) = key else {
+        return Ok(None);
+    };
+    match TaskIdempotencyKey::find_task_id(pool, project_id, key).await? {
+        Some(task_id) => Ok(Task::find_by_id(pool, task_id).await?),
+        None => Ok(None),
+    }
+}
+
+/// Ties `key` to the newly created `task`. When a concurrent request with the same key won,
+/// the new task is deleted and the winner's task id is returned instead.
+async fn claim_idempotency_key(
+    pool: &sqlx::SqlitePool,
+    task: &Task,
+    key: Option<&str>,
+) -> Result<Option<Uuid>, ApiError> {
+    let Some(key) = key else {
+        return Ok(None);
+    };
+    if TaskIdempotencyKey::record(pool, task.project_id, key, task.id).await? {
+        return Ok(None);
+    }
+    Task::delete(pool, task.id).await?;
+    let winner = TaskIdempotencyKey::find_task_id(pool, task.project_id, key)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    Ok(Some(winner))
+}
+
 pub async fn create_task(
     State(deployment): State<DeploymentImpl>,
+    Query(params): Query<CreateTaskParams>,
+    headers: HeaderMap,
     Json(payload): Json<CreateTask>,
-) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<CreatedTask>>, ApiError> {
     let id = Uuid::new_v4();
+    let idempotency_key = idempotency_key(&headers)?;
+    if let Some(task) = find_idempotent_task(
+        &deployment.db().pool,
+        payload.project_id,
+        idempotency_key.as_deref(),
+    )
+    .await?
+    {
+        tracing::debug!("Returning task {} for a repeated create request", task.id);
+        return Ok(ResponseJson(ApiResponse::success(CreatedTask {
+            task,
+            possible_duplicates: Vec::new(),
+        })));
+    }
 
     tracing::debug!(
         "Creating task '{}' in project {}",
@@ -441,7 +964,45 @@ pub async fn create_task(
         payload.project_id
     );
 
+    let mode = params
+        .duplicate_check
+        .unwrap_or_else(DuplicateCheckMode::from_env);
+    let possible_duplicates = if mode == DuplicateCheckMode::Off || params.force {
+        Vec::new()
+    } else {
+        find_duplicate_tasks(&deployment.db().pool, payload.project_id, &payload.title).await?
+    };
+
+    if !possible_duplicates.is_empty() {
+        let summary = possible_duplicates
+            .iter()
+            .map(|d| format!("'{}' ({})", d.title, d.id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if mode == DuplicateCheckMode::Strict {
+            return Err(ApiError::Conflict(format!(
+                "Possible duplicate tasks: {summary}. Pass force=true to create it anyway."
+            )));
+        }
+        tracing::warn!(
+            "Task '{}' looks like a duplicate of {}",
+            payload.title,
+            summary
+        );
+    }
+
     let task = Task::create(&deployment.db().pool, &payload, id).await?;
+    if let Some(winner) =
+        claim_idempotency_key(&deployment.db().pool, &task, idempotency_key.as_deref()).await?
+    {
+        let task = Task::find_by_id(&deployment.db().pool, winner)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        return Ok(ResponseJson(ApiResponse::success(CreatedTask {
+            task,
+            possible_duplicates: Vec::new(),
+        })));
+    }
 
     if let Some(image_ids) = &payload.image_ids {
         TaskImage::associate_many_dedup(&deployment.db().pool, task.id, image_ids).await?;
@@ -459,24 +1020,130 @@ pub async fn create_task(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(task)))
+    Ok(ResponseJson(ApiResponse::success(CreatedTask {
+        task,
+        possible_duplicates,
+    })))
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct CreateAndStartTaskRequest {
+    pub task: CreateTask,
+    pub executor_profile_id: ExecutorProfileId,
+    pub base_branch: String,
+    /// Whether to use a git worktree for isolation (default: true)
+    pub use_worktree: Option<bool>,
+}
+
+/// Most branches named in the error for an unknown base branch
+const MAX_LISTED_BRANCHES: usize = 20;
+
+/// Rejects a base branch missing from `branches`, naming the branches that do exist.
+/// Remote branches match by their full name, e.g. `origin/main`.
+fn check_base_branch(branches: &[GitBranch], base_branch: &str) -> Result<(), ApiError> {
+    if branches.iter().any(|branch| branch.name == base_branch) {
+        return Ok(());
+    }
+
+    let mut available = branches
+        .iter()
+        .take(MAX_LISTED_BRANCHES)
+        .map(|branch| branch.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if branches.len() > MAX_LISTED_BRANCHES {
+        available.push_str(&format!(
+            " and {} more",
+            branches.len() - MAX_LISTED_BRANCHES
+        ));
+    }
+    Err(ApiError::BadRequest(format!(
+        "Base branch '{base_branch}' does not exist; available branches: {available}"
+    )))
+}
+
+/// Ensures `base_branch` exists in the project's repository, locally or on a remote,
+/// before a worktree is created from it
+pub(crate) async fn ensure_base_branch_exists(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    base_branch: &str,
+) -> Result<(), ApiError> {
+    let project = Project::find_by_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let branches = deployment.git().get_all_branches(&project.git_repo_path)?;
+    check_base_branch(&branches, base_branch)
+}
+
+/// Rejects an executor that is missing from the project's executor allow-list
+fn check_executor_allowed(
+    allowed: Option<&[BaseCodingAgent]>,
+    executor: BaseCodingAgent,
+) -> Result<(), ApiError> {
+    match allowed {
+        Some(allowed) if !allowed.contains(&executor) => {
+            let allowed = allowed
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(ApiError::BadRequest(format!(
+                "Executor {executor} is not allowed for this project; allowed executors: {allowed}"
+            )))
+        }
+        _ => Ok(()),
+    }
 }
 
-#[derive(Debug, Deserialize, TS)]
-pub struct CreateAndStartTaskRequest {
-    pub task: CreateTask,
-    pub executor_profile_id: ExecutorProfileId,
-    pub base_branch: String,
-    /// Whether to use a git worktree for isolation (default: true)
-    pub use_worktree: Option<bool>,
+/// Ensures the project permits starting attempts with `executor`
+pub(crate) async fn ensure_executor_allowed(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    executor: BaseCodingAgent,
+) -> Result<(), ApiError> {
+    let allowed = deployment
+        .forge_config()
+        .allowed_executors(project_id)
+        .await
+        .map_err(DeploymentError::Other)?;
+    check_executor_allowed(allowed.as_deref(), executor)
 }
 
 pub async fn create_task_and_start(
     State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
     Json(payload): Json<CreateAndStartTaskRequest>,
 ) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
+    let idempotency_key = idempotency_key(&headers)?;
+    if let Some(task) = find_idempotent_task(
+        &deployment.db().pool,
+        payload.task.project_id,
+        idempotency_key.as_deref(),
+    )
+    .await?
+    {
+        tracing::debug!("Returning task {} for a repeated create request", task.id);
+        let task = Task::find_by_id_with_attempt_status(&deployment.db().pool, task.id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        return Ok(ResponseJson(ApiResponse::success(task)));
+    }
+
+    ensure_executor_allowed(
+        &deployment,
+        payload.task.project_id,
+        payload.executor_profile_id.executor,
+    )
+    .await?;
+
     let task_id = Uuid::new_v4();
     let use_worktree = payload.use_worktree.unwrap_or(true);
+    // Agent chats run in the project checkout, so only worktrees are created from base_branch
+    if use_worktree {
+        ensure_base_branch_exists(&deployment, payload.task.project_id, &payload.base_branch)
+            .await?;
+    }
 
     // Set initial status based on use_worktree to avoid race condition with WebSocket broadcasts.
     // Agent tasks (use_worktree: false) must be created with status 'agent' from the start,
@@ -493,23 +1160,36 @@ pub async fn create_task_and_start(
         initial_status,
     )
     .await?;
+    if let Some(winner) =
+        claim_idempotency_key(&deployment.db().pool, &task, idempotency_key.as_deref()).await?
+    {
+        let task = Task::find_by_id_with_attempt_status(&deployment.db().pool, winner)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        return Ok(ResponseJson(ApiResponse::success(task)));
+    }
 
     if let Some(image_ids) = &payload.task.image_ids {
         TaskImage::associate_many(&deployment.db().pool, task.id, image_ids).await?;
     }
 
-    // If non-worktree task (e.g., agent chat), register in forge_agents to hide from kanban
-    if !use_worktree {
-        sqlx::query(
-            r#"INSERT INTO forge_agents (id, project_id, agent_type, task_id, created_at, updated_at)
-               VALUES (?, ?, 'genie_chat', ?, datetime('now'), datetime('now'))"#,
+    // If non-worktree task (e.g., agent chat), register in forge_agents to hide from kanban.
+    // Status is already set to 'agent' at task creation time above.
+    if !use_worktree
+        && !ForgeAgent::register_task(
+            &deployment.db().pool,
+            task.project_id,
+            task.id,
+            AGENT_CHAT_TYPE,
         )
-        .bind(Uuid::new_v4().to_string())
-        .bind(task.project_id.to_string())
-        .bind(task.id.to_string())
-        .execute(&deployment.db().pool)
-        .await?;
-        // Note: Status is already set to 'agent' at task creation time above
+        .await?
+    {
+        tracing::warn!(
+            "Project {} already has a {} agent; task {} was not registered",
+            task.project_id,
+            AGENT_CHAT_TYPE,
+            task.id
+        );
     }
 
     deployment
@@ -552,10 +1232,11 @@ pub async fn create_task_and_start(
         .git_branch_from_task_attempt(&attempt_id, &task.title)
         .await;
 
-    let mut task_attempt = TaskAttempt::create(
+    let task_attempt = TaskAttempt::create(
         &deployment.db().pool,
         &CreateTaskAttempt {
             executor: payload.executor_profile_id.executor,
+            variant: payload.executor_profile_id.variant.clone(),
             base_branch: payload.base_branch,
             branch: git_branch_name,
         },
@@ -564,19 +1245,6 @@ pub async fn create_task_and_start(
     )
     .await?;
 
-    // Store executor with variant for filtering (executor:variant format)
-    if let Some(variant) = &payload.executor_profile_id.variant {
-        let executor_with_variant = format!("{}:{}", payload.executor_profile_id.executor, variant);
-        sqlx::query(
-            "UPDATE task_attempts SET executor = ?, updated_at = datetime('now') WHERE id = ?",
-        )
-        .bind(&executor_with_variant)
-        .bind(attempt_id.to_string())
-        .execute(&deployment.db().pool)
-        .await?;
-        task_attempt.executor = executor_with_variant;
-    }
-
     // Insert worktree config if explicitly specified (defaults to true when not present)
     if let Some(use_worktree) = payload.use_worktree {
         sqlx::query(
@@ -588,12 +1256,31 @@ pub async fn create_task_and_start(
         .await?;
     }
 
-    let is_attempt_running = deployment
-        .container()
-        .start_attempt(&task_attempt, payload.executor_profile_id.clone())
-        .await
-        .inspect_err(|err| tracing::error!("Failed to start task attempt: {}", err))
-        .is_ok();
+    // Run the start on its own task so a dropped request can't interrupt the rollback
+    let deployment_clone = deployment.clone();
+    let attempt = task_attempt.clone();
+    let executor_profile_id = payload.executor_profile_id.clone();
+    let is_attempt_running = match tokio::spawn(async move {
+        deployment_clone
+            .container()
+            .start_attempt_or_discard(&attempt, executor_profile_id)
+            .await
+    })
+    .await
+    {
+        Ok(Ok(_)) => true,
+        // The task is back in its initial status
+        Ok(Err(err)) => {
+            tracing::error!("Failed to start task attempt, discarded it: {}", err);
+            false
+        }
+        Err(err) => {
+            tracing::error!("Task attempt start was aborted: {}", err);
+            // The start may have already moved the task to in-progress
+            Task::update_status(&deployment.db().pool, task.id, initial_status).await?;
+            false
+        }
+    };
     deployment
         .track_if_analytics_allowed(
             "task_attempt_started",
@@ -617,7 +1304,7 @@ pub async fn create_task_and_start(
         has_merged_attempt: false,
         last_attempt_failed: false,
         executor: task_attempt.executor,
-        attempt_count: 1, // First attempt for a newly created task
+        attempt_count: i64::from(is_attempt_running), // A failed first attempt is discarded
     })))
 }
 
@@ -646,8 +1333,58 @@ pub async fn update_task(
         description,
         status,
         parent_task_attempt,
+        payload.updated_at,
     )
-    .await?;
+    .await?
+    .ok_or_else(|| {
+        ApiError::Conflict(
+            "The task was changed by someone else since it was loaded. Reload it and try again."
+                .to_string(),
+        )
+    })?;
+
+    // Bring the attempt worktrees back once the task has left Archived, so a stale update
+    // never touches them. Attempts whose branch is gone keep no worktree, which doesn't stop
+    // the rest of the task from being restored; any other failure keeps the task archived.
+    if existing_task.status == TaskStatus::Archived && status != TaskStatus::Archived {
+        let restore =
+            match restore_task_worktrees(&deployment.db().pool, deployment.git(), task.id).await {
+                Ok(restore) => restore,
+                Err(e) => {
+                    Task::update_status_from(
+                        &deployment.db().pool,
+                        task.id,
+                        status,
+                        TaskStatus::Archived,
+                    )
+                    .await?;
+                    return Err(e.into());
+                }
+            };
+        for (attempt_id, reason) in &restore.skipped {
+            tracing::warn!(
+                "Could not restore the worktree of attempt {} of task {}: {}",
+                attempt_id,
+                task.id,
+                reason
+            );
+        }
+    }
+
+    // Keep forge_agents membership in step with the agent status
+    if status != existing_task.status {
+        if status == TaskStatus::Agent {
+            ForgeAgent::register_task(
+                &deployment.db().pool,
+                task.project_id,
+                task.id,
+                AGENT_CHAT_TYPE,
+            )
+            .await?;
+        } else if existing_task.status == TaskStatus::Agent {
+            ForgeAgent::unregister_task(&deployment.db().pool, task.id).await?;
+        }
+    }
 
     if let Some(image_ids) = &payload.image_ids {
         TaskImage::delete_by_task_id(&deployment.db().pool, task.id).await?;
@@ -715,6 +1452,9 @@ pub async fn delete_task(
         total_children_affected += children_affected;
     }
 
+    // Drop the agent registration explicitly rather than relying on the FK cascade
+    ForgeAgent::unregister_task(&mut *tx, task.id).await?;
+
     // Delete task from database (FK CASCADE will handle task_attempts)
     let rows_affected = Task::delete(&mut *tx, task.id).await?;
 
@@ -777,6 +1517,8 @@ fn handle_task_archive(deployment: &DeploymentImpl, task_id: Uuid) {
     tokio::spawn(async move {
         let span = tracing::info_span!("archive_task_worktree_cleanup", task_id = %task_id);
         let _enter = span.enter();
+        // An unarchive restoring the worktrees either finished or waits for this cleanup
+        let _guard = lock_task_worktrees(task_id).await;
 
         // Fetch task
         let task = match Task::find_by_id(&deployment.db().pool, task_id).await {
@@ -786,6 +1528,10 @@ fn handle_task_archive(deployment: &DeploymentImpl, task_id: Uuid) {
                 return;
             }
         };
+        if task.status != TaskStatus::Archived {
+            tracing::debug!("Task {} was unarchived before its cleanup ran", task_id);
+            return;
+        }
 
         // Fetch all attempts
         let attempts = match TaskAttempt::fetch_all(&deployment.db().pool, Some(task_id)).await {
@@ -866,6 +1612,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let inner = Router::new()
         .route("/", get(get_tasks).post(create_task))
+        .route("/search", get(search_tasks))
         .route("/stream/ws", get(stream_tasks_ws))
         .route("/create-and-start", post(create_task_and_start))
         .nest("/{task_id}", task_id_router);
@@ -873,3 +1620,764 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     // mount under /projects/:project_id/tasks
     Router::new().nest("/tasks", inner)
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn large_snapshot_is_split_into_bounded_ordered_pages() {
+        let tasks: serde_json::Map<String, serde_json::Value> = (0..500)
+            .map(|i| {
+                let id = Uuid::new_v4().to_string();
+                (id.clone(), json!({ "id": id, "title": format!("Task {i}") }))
+            })
+            .collect();
+        let snapshot: json_patch::Patch =
+            serde_json::from_value(json!([{ "op": "replace", "path": "/tasks", "value": tasks }]))
+                .unwrap();
+
+        let pages = paginate_tasks_snapshot(LogMsg::JsonPatch(snapshot), 100);
+        assert_eq!(pages.len(), 5);
+
+        let mut board = json!({ "tasks": {} });
+        let mut received = Vec::new();
+        for (index, page) in pages.into_iter().enumerate() {
+            let LogMsg::JsonPatch(patch) = page else {
+                panic!("expected a JSON patch page");
+            };
+            if index == 0 {
+                assert_eq!(patch.0.len(), 1);
+                let json_patch::PatchOperation::Replace(op) = &patch.0[0] else {
+                    panic!("first page must replace /tasks");
+                };
+                let first_page = op.value.as_object().unwrap();
+                assert_eq!(first_page.len(), 100);
+                received.extend(first_page.keys().cloned());
+            } else {
+                assert_eq!(patch.0.len(), 100);
+                for op in &patch.0 {
+                    let json_patch::PatchOperation::Add(op) = op else {
+                        panic!("later pages must add tasks");
+                    };
+                    received.push(op.value["id"].as_str().unwrap().to_string());
+                }
+            }
+            json_patch::patch(&mut board, &patch).unwrap();
+        }
+
+        assert_eq!(received, tasks.keys().cloned().collect::<Vec<_>>());
+        assert_eq!(board["tasks"], serde_json::Value::Object(tasks));
+    }
+
+    #[test]
+    fn executor_allow_list_rejects_unlisted_executors() {
+        let allowed = [BaseCodingAgent::ClaudeCode, BaseCodingAgent::Codex];
+
+        assert!(check_executor_allowed(Some(&allowed), BaseCodingAgent::Codex).is_ok());
+        assert!(check_executor_allowed(None, BaseCodingAgent::Gemini).is_ok());
+
+        let Err(ApiError::BadRequest(message)) =
+            check_executor_allowed(Some(&allowed), BaseCodingAgent::Gemini)
+        else {
+            panic!("an unlisted executor should be rejected");
+        };
+        assert!(message.contains("GEMINI"));
+        assert!(message.contains("CLAUDE_CODE, CODEX"));
+    }
+
+    #[test]
+    fn unknown_base_branch_is_rejected_with_the_available_branches() {
+        use forge_core_services::services::git::GitService;
+
+        let root = tempfile::TempDir::new().unwrap();
+        let repo_path = root.path().join("repo");
+        let git = GitService::new();
+        git.initialize_repo_with_main_branch(&repo_path).unwrap();
+        let repo = git2::Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        repo.reference(
+            "refs/remotes/origin/release",
+            head,
+            false,
+            "test remote branch",
+        )
+        .unwrap();
+
+        let branches = git.get_all_branches(&repo_path).unwrap();
+        assert!(check_base_branch(&branches, "main").is_ok());
+        assert!(check_base_branch(&branches, "origin/release").is_ok());
+
+        let Err(ApiError::BadRequest(message)) = check_base_branch(&branches, "mian") else {
+            panic!("a missing base branch should be rejected");
+        };
+        assert!(message.contains("'mian' does not exist"));
+        assert!(message.contains("main"));
+        assert!(message.contains("origin/release"));
+    }
+
+    #[test]
+    fn small_snapshots_and_updates_pass_through() {
+        let snapshot: json_patch::Patch = serde_json::from_value(
+            json!([{ "op": "replace", "path": "/tasks", "value": { "a": {}, "b": {} } }]),
+        )
+        .unwrap();
+        assert_eq!(
+            paginate_tasks_snapshot(LogMsg::JsonPatch(snapshot), 100).len(),
+            1
+        );
+
+        let update: json_patch::Patch =
+            serde_json::from_value(json!([{ "op": "add", "path": "/tasks/a", "value": {} }]))
+                .unwrap();
+        assert_eq!(paginate_tasks_snapshot(LogMsg::JsonPatch(update), 1).len(), 1);
+    }
+
+    fn task(title: &str, description: Option<&str>, updated_minutes_ago: i64) -> Task {
+        let updated_at = chrono::Utc::now() - chrono::Duration::minutes(updated_minutes_ago);
+        Task {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            title: title.to_string(),
+            description: description.map(str::to_string),
+            status: TaskStatus::Todo,
+            parent_task_attempt: None,
+            dev_server_id: None,
+            created_at: updated_at,
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn search_ranks_title_hits_above_description_hits() {
+        let long_description = format!("{} the login form should show an error", "x".repeat(60));
+        let tasks = vec![
+            task("Refactor session storage", Some(&long_description), 0),
+            task("Add dark mode", None, 0),
+            task("Fix Login button crash", None, 5),
+            task("Fix lgin redirect", None, 0),
+            task("Login", None, 10),
+        ];
+
+        let hits = rank_tasks("login", tasks);
+        let titles: Vec<&str> = hits.iter().map(|h| h.task.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Login",
+                "Fix Login button crash",
+                "Fix lgin redirect",
+                "Refactor session storage",
+            ]
+        );
+
+        assert_eq!(hits[1].matched_field, TaskMatchField::Title);
+        assert_eq!(hits[1].highlight, "Fix **Login** button crash");
+        assert_eq!(hits[2].highlight, "Fix **lgin** redirect");
+        assert_eq!(hits[3].matched_field, TaskMatchField::Description);
+        assert!(hits[3].highlight.starts_with('…'));
+        assert!(hits[3].highlight.contains("the **login** form"));
+    }
+
+    #[test]
+    fn search_breaks_score_ties_by_most_recent_update() {
+        let tasks = vec![
+            task("Older", Some("Update the changelog"), 30),
+            task("Newer", Some("Changelog entry for the release"), 1),
+        ];
+
+        let hits = rank_tasks("CHANGELOG", tasks);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].task.title, "Newer");
+        assert_eq!(hits[0].highlight, "**Changelog** entry for the release");
+    }
+
+    async fn project_with_tasks(count: usize) -> (sqlx::SqlitePool, Uuid) {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+
+        let project_id = Uuid::new_v4();
+        let project = forge_core_db::models::project::CreateProject {
+            name: "Paging".to_string(),
+            git_repo_path: format!("/tmp/paging-{project_id}"),
+            use_existing_repo: true,
+            setup_script: None,
+            dev_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            commit_prompt: None,
+            clone_url: None,
+        };
+        Project::create(&pool, &project, project_id).await.unwrap();
+
+        for i in 0..count {
+            let data = CreateTask::from_title_description(project_id, format!("Task {i}"), None);
+            let status = if i % 3 == 0 {
+                TaskStatus::Done
+            } else {
+                TaskStatus::Todo
+            };
+            Task::create_with_status(&pool, &data, Uuid::new_v4(), status)
+                .await
+                .unwrap();
+        }
+        (pool, project_id)
+    }
+
+    #[tokio::test]
+    async fn registered_agent_tasks_enter_the_kanban_cache_without_polling() {
+        let (pool, project_id) = project_with_tasks(1).await;
+        let cache = Arc::new(tokio::sync::RwLock::new(std::collections::HashSet::new()));
+        let data = CreateTask::from_title_description(project_id, "Agent chat".to_string(), None);
+        let agent = Task::create_with_status(&pool, &data, Uuid::new_v4(), TaskStatus::Agent)
+            .await
+            .unwrap();
+
+        // An hour-long period leaves only the startup reload, which runs before registration
+        let refresh = tokio::spawn(refresh_agent_task_cache(
+            cache.clone(),
+            pool.clone(),
+            project_id,
+            ForgeAgent::subscribe_registrations(),
+            std::time::Duration::from_secs(3600),
+        ));
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!cache.read().await.contains(&agent.id));
+
+        ForgeAgent::register_task(&pool, project_id, agent.id, AGENT_CHAT_TYPE)
+            .await
+            .unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            while !cache.read().await.contains(&agent.id) {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("registered agent task should be hidden without waiting for the poll");
+        refresh.abort();
+    }
+
+    #[tokio::test]
+    async fn kanban_stream_drops_registered_agent_tasks() {
+        let (pool, project_id) = project_with_tasks(0).await;
+        let mut tasks = Vec::new();
+        for title in ["Agent chat", "Kanban task"] {
+            // Both start as plain tasks so only forge_agents membership tells them apart
+            let data = CreateTask::from_title_description(project_id, title.to_string(), None);
+            let task = Task::create_with_status(&pool, &data, Uuid::new_v4(), TaskStatus::Todo)
+                .await
+                .unwrap();
+            tasks.push(
+                Task::find_by_id_with_attempt_status(&pool, task.id)
+                    .await
+                    .unwrap()
+                    .unwrap(),
+            );
+        }
+        let (agent, regular) = (tasks[0].id, tasks[1].id);
+
+        let cache = Arc::new(tokio::sync::RwLock::new(std::collections::HashSet::new()));
+        let refresh = tokio::spawn(refresh_agent_task_cache(
+            cache.clone(),
+            pool.clone(),
+            project_id,
+            ForgeAgent::subscribe_registrations(),
+            std::time::Duration::from_secs(3600),
+        ));
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        ForgeAgent::register_task(&pool, project_id, agent, AGENT_CHAT_TYPE)
+            .await
+            .unwrap();
+
+        let snapshot: serde_json::Map<String, serde_json::Value> = tasks
+            .iter()
+            .map(|task| (task.id.to_string(), serde_json::to_value(task).unwrap()))
+            .collect();
+        let mut raw = vec![json!([{ "op": "replace", "path": "/tasks", "value": snapshot }])];
+        for task in &tasks {
+            raw.push(json!([{
+                "op": "add",
+                "path": format!("/tasks/{}", task.id),
+                "value": serde_json::to_value(task).unwrap()
+            }]));
+        }
+        let raw = futures_util::stream::iter(
+            raw.into_iter()
+                .map(|patch| Ok(LogMsg::JsonPatch(serde_json::from_value(patch).unwrap()))),
+        );
+
+        let messages: Vec<LogMsg> = filter_kanban_stream(raw, cache, pool, 100)
+            .try_collect()
+            .await
+            .unwrap();
+        refresh.abort();
+
+        // Apply the stream to a client board, as the frontend does
+        let mut board = json!({ "tasks": {} });
+        for message in messages {
+            let LogMsg::JsonPatch(patch) = message else {
+                panic!("expected JSON patches only");
+            };
+            json_patch::patch(&mut board, &patch).unwrap();
+        }
+        let shown: Vec<&String> = board["tasks"].as_object().unwrap().keys().collect();
+        assert_eq!(shown, vec![&regular.to_string()]);
+    }
+
+    /// The correlated-subquery kanban query that `KANBAN_TASKS_QUERY` replaced
+    const LEGACY_KANBAN_TASKS_QUERY: &str = r#"SELECT
+  t.id                            AS "id",
+  t.project_id                    AS "project_id",
+  t.title,
+  t.description,
+  t.status                        AS "status",
+  t.parent_task_attempt           AS "parent_task_attempt",
+  t.dev_server_id                 AS "dev_server_id",
+  t.created_at                    AS "created_at",
+  t.updated_at                    AS "updated_at",
+
+  CASE WHEN EXISTS (
+    SELECT 1
+      FROM task_attempts ta
+      JOIN execution_processes ep
+        ON ep.task_attempt_id = ta.id
+     WHERE ta.task_id       = t.id
+       AND ep.status        = 'running'
+       AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+     LIMIT 1
+  ) THEN 1 ELSE 0 END            AS has_in_progress_attempt,
+
+  CASE WHEN (
+    SELECT ep.status
+      FROM task_attempts ta
+      JOIN execution_processes ep
+        ON ep.task_attempt_id = ta.id
+     WHERE ta.task_id       = t.id
+     AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+     ORDER BY ep.created_at DESC
+     LIMIT 1
+  ) IN ('failed','killed') THEN 1 ELSE 0 END
+                                 AS last_attempt_failed,
+
+  ( SELECT ta.executor
+      FROM task_attempts ta
+      WHERE ta.task_id = t.id
+     ORDER BY ta.created_at DESC
+      LIMIT 1
+    )                               AS executor,
+
+  ( SELECT COUNT(*)
+      FROM task_attempts ta
+      WHERE ta.task_id = t.id
+    )                               AS attempt_count
+
+FROM tasks t
+WHERE t.project_id = ?1
+  AND t.id NOT IN (SELECT task_id FROM forge_agents)
+  AND (?2 IS NULL OR t.status = ?2)
+  AND (?3 IS NULL
+       OR datetime(t.created_at, 'subsec') < datetime(?3, 'subsec')
+       OR (datetime(t.created_at, 'subsec') = datetime(?3, 'subsec') AND t.id < ?4))
+ORDER BY datetime(t.created_at, 'subsec') DESC, t.id DESC
+LIMIT ?5"#;
+
+    #[tokio::test]
+    async fn kanban_query_matches_the_correlated_subquery_version() {
+        let (pool, project_id) = project_with_tasks(12).await;
+        let tasks = get_kanban_tasks(&pool, project_id, TaskPage::default())
+            .await
+            .unwrap();
+        ForgeAgent::register_task(&pool, project_id, tasks[11].task.id, AGENT_CHAT_TYPE)
+            .await
+            .unwrap();
+
+        // Task i gets i % 4 attempts; each attempt runs a setup script, a coding agent and a
+        // dev server whose statuses vary so every flag takes both values across the board
+        let statuses = ["running", "completed", "failed", "killed"];
+        let mut clock = 0;
+        for (i, task) in tasks.iter().enumerate() {
+            for n in 0..i % 4 {
+                let executor = [BaseCodingAgent::ClaudeCode, BaseCodingAgent::Codex][n % 2];
+                let attempt = TaskAttempt::create(
+                    &pool,
+                    &CreateTaskAttempt {
+                        executor,
+                        variant: None,
+                        base_branch: "main".to_string(),
+                        branch: format!("forge/{i}-{n}"),
+                    },
+                    Uuid::new_v4(),
+                    task.task.id,
+                )
+                .await
+                .unwrap();
+                clock += 1;
+                sqlx::query(
+                    "UPDATE task_attempts SET created_at = datetime('2025-01-01', ? || ' seconds') \
+                     WHERE id = ?",
+                )
+                .bind(clock)
+                .bind(attempt.id)
+                .execute(&pool)
+                .await
+                .unwrap();
+
+                for (k, run_reason) in ["setupscript", "codingagent", "devserver"]
+                    .into_iter()
+                    .enumerate()
+                {
+                    clock += 1;
+                    sqlx::query(
+                        "INSERT INTO execution_processes \
+                         (id, task_attempt_id, run_reason, executor_action, status, created_at) \
+                         VALUES (?, ?, ?, '{}', ?, datetime('2025-01-01', ? || ' seconds'))",
+                    )
+                    .bind(Uuid::new_v4())
+                    .bind(attempt.id)
+                    .bind(run_reason)
+                    .bind(statuses[(i + n + k) % statuses.len()])
+                    .bind(clock)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+                }
+            }
+        }
+
+        let pages = [
+            TaskPage::default(),
+            TaskPage {
+                status: Some(TaskStatus::Todo),
+                limit: None,
+                before: None,
+            },
+            TaskPage {
+                status: None,
+                limit: Some(4),
+                before: Some(PageCursor::new(tasks[2].created_at, tasks[2].id)),
+            },
+        ];
+        for page in pages {
+            let expected = fetch_kanban_tasks(&pool, LEGACY_KANBAN_TASKS_QUERY, project_id, page)
+                .await
+                .unwrap();
+            let actual = get_kanban_tasks(&pool, project_id, page).await.unwrap();
+            assert_eq!(
+                serde_json::to_value(&actual).unwrap(),
+                serde_json::to_value(&expected).unwrap()
+            );
+        }
+
+        let all = get_kanban_tasks(&pool, project_id, TaskPage::default())
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 11);
+        assert!(all.iter().any(|t| t.has_in_progress_attempt));
+        assert!(all.iter().any(|t| t.last_attempt_failed));
+        assert!(all.iter().any(|t| t.attempt_count == 3));
+    }
+
+    #[tokio::test]
+    async fn kanban_pages_are_stable_and_do_not_overlap() {
+        let (pool, project_id) = project_with_tasks(120).await;
+        let page = |status, limit, before| TaskPage {
+            status,
+            limit: Some(limit),
+            before,
+        };
+
+        let all = get_kanban_tasks(&pool, project_id, TaskPage::default())
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 120);
+
+        let mut paged = Vec::new();
+        let mut cursors = Vec::new();
+        let mut before = None;
+        loop {
+            let tasks = get_kanban_tasks(&pool, project_id, page(None, 50, before))
+                .await
+                .unwrap();
+            assert!(tasks.len() <= 50);
+            let Some(last) = tasks.last() else { break };
+            before = Some(PageCursor::new(last.created_at, last.id));
+            cursors.push(before);
+            paged.extend(tasks.into_iter().map(|t| t.id));
+
+            // A task created while paging shifted every later row by one under offset paging
+            let create_task =
+                CreateTask::from_title_description(project_id, "Created while paging".into(), None);
+            let created = Task::create(&pool, &create_task, Uuid::new_v4())
+                .await
+                .unwrap();
+            sqlx::query("UPDATE tasks SET created_at = datetime('now', '+1 day') WHERE id = ?")
+                .bind(created.id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        assert_eq!(paged, all.iter().map(|t| t.id).collect::<Vec<_>>());
+
+        // Tasks created within the same second still come back in the same order
+        let again = get_kanban_tasks(&pool, project_id, page(None, 50, cursors[0]))
+            .await
+            .unwrap();
+        assert_eq!(
+            again.iter().map(|t| t.id).collect::<Vec<_>>(),
+            paged[50..100]
+        );
+
+        let done = get_kanban_tasks(&pool, project_id, page(Some(TaskStatus::Done), 100, None))
+            .await
+            .unwrap();
+        assert_eq!(done.len(), 40);
+        assert!(done.iter().all(|t| t.status == TaskStatus::Done));
+    }
+
+    #[test]
+    fn idempotency_key_is_trimmed_and_bounded() {
+        let headers = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(IDEMPOTENCY_KEY_HEADER, value.parse().unwrap());
+            headers
+        };
+
+        assert_eq!(idempotency_key(&HeaderMap::new()).unwrap(), None);
+        assert_eq!(idempotency_key(&headers("   ")).unwrap(), None);
+        assert_eq!(
+            idempotency_key(&headers(" retry-1 ")).unwrap().as_deref(),
+            Some("retry-1")
+        );
+        assert!(matches!(
+            idempotency_key(&headers(&"k".repeat(MAX_IDEMPOTENCY_KEY_LEN + 1))),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    async fn idempotency_deployment(root: &std::path::Path) -> (DeploymentImpl, Uuid) {
+        let deployment = DeploymentImpl::new_at(&root.join("db.sqlite"), Default::default())
+            .await
+            .unwrap();
+        let project_id = Uuid::new_v4();
+        let project = forge_core_db::models::project::CreateProject {
+            name: "Idempotency".to_string(),
+            git_repo_path: root.join("repo").display().to_string(),
+            use_existing_repo: true,
+            setup_script: None,
+            dev_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            commit_prompt: None,
+            clone_url: None,
+        };
+        Project::create(&deployment.db().pool, &project, project_id)
+            .await
+            .unwrap();
+        (deployment, project_id)
+    }
+
+    async fn create_with_key(
+        deployment: &DeploymentImpl,
+        project_id: Uuid,
+        key: &str,
+    ) -> Result<Task, ApiError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(IDEMPOTENCY_KEY_HEADER, key.parse().unwrap());
+        let ResponseJson(response) = create_task(
+            State(deployment.clone()),
+            Query(CreateTaskParams {
+                force: true,
+                duplicate_check: None,
+            }),
+            headers,
+            Json(CreateTask::from_title_description(
+                project_id,
+                "Add greeting".to_string(),
+                None,
+            )),
+        )
+        .await?;
+        Ok(response.into_data().unwrap().task)
+    }
+
+    #[tokio::test]
+    async fn repeated_create_requests_return_the_first_task() {
+        let root = tempfile::TempDir::new().unwrap();
+        let (deployment, project_id) = idempotency_deployment(root.path()).await;
+        let pool = &deployment.db().pool;
+
+        let first = create_with_key(&deployment, project_id, " retry-1 ")
+            .await
+            .unwrap();
+        let second = create_with_key(&deployment, project_id, "retry-1")
+            .await
+            .unwrap();
+        assert_eq!(first.id, second.id);
+        let other = create_with_key(&deployment, project_id, "retry-2")
+            .await
+            .unwrap();
+        assert_ne!(first.id, other.id);
+        assert!(matches!(
+            create_with_key(
+                &deployment,
+                project_id,
+                &"k".repeat(MAX_IDEMPOTENCY_KEY_LEN + 1)
+            )
+            .await,
+            Err(ApiError::BadRequest(_))
+        ));
+
+        // The create-and-start route shares the keys, and a replay starts nothing
+        let mut headers = HeaderMap::new();
+        headers.insert(IDEMPOTENCY_KEY_HEADER, "retry-1".parse().unwrap());
+        let ResponseJson(response) = create_task_and_start(
+            State(deployment.clone()),
+            headers,
+            Json(CreateAndStartTaskRequest {
+                task: CreateTask::from_title_description(
+                    project_id,
+                    "Add greeting".to_string(),
+                    None,
+                ),
+                executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+                base_branch: "main".to_string(),
+                use_worktree: None,
+            }),
+        )
+        .await
+        .unwrap();
+        let replayed = response.into_data().unwrap();
+        assert_eq!(replayed.task.id, first.id);
+        assert_eq!(replayed.attempt_count, 0);
+
+        let tasks = Task::find_titles_by_project_id(pool, project_id)
+            .await
+            .unwrap();
+        assert_eq!(tasks.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn concurrent_create_requests_with_one_key_create_one_task() {
+        let root = tempfile::TempDir::new().unwrap();
+        let (deployment, project_id) = idempotency_deployment(root.path()).await;
+
+        let created = futures_util::future::join_all(
+            (0..8).map(|_| create_with_key(&deployment, project_id, "retry-1")),
+        )
+        .await;
+        let ids: std::collections::HashSet<Uuid> =
+            created.into_iter().map(|task| task.unwrap().id).collect();
+        assert_eq!(ids.len(), 1);
+
+        let tasks = Task::find_titles_by_project_id(&deployment.db().pool, project_id)
+            .await
+            .unwrap();
+        assert_eq!(tasks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn a_request_that_loses_the_key_deletes_its_task() {
+        let root = tempfile::TempDir::new().unwrap();
+        let (deployment, project_id) = idempotency_deployment(root.path()).await;
+        let pool = &deployment.db().pool;
+
+        let winner = create_with_key(&deployment, project_id, "retry-1")
+            .await
+            .unwrap();
+        // A concurrent request that created its task before the winner recorded the key
+        let data = CreateTask::from_title_description(project_id, "Add greeting".to_string(), None);
+        let loser = Task::create(pool, &data, Uuid::new_v4()).await.unwrap();
+
+        assert_eq!(
+            claim_idempotency_key(pool, &loser, Some("retry-1"))
+                .await
+                .unwrap(),
+            Some(winner.id)
+        );
+        assert!(Task::find_by_id(pool, loser.id).await.unwrap().is_none());
+        assert_eq!(
+            claim_idempotency_key(pool, &loser, None).await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn a_stale_update_keeps_an_archived_task_and_its_worktrees_deleted() {
+        let root = tempfile::TempDir::new().unwrap();
+        let (deployment, project_id) = idempotency_deployment(root.path()).await;
+        let pool = &deployment.db().pool;
+        let repo_path = root.path().join("repo");
+        deployment
+            .git()
+            .initialize_repo_with_main_branch(&repo_path)
+            .unwrap();
+        deployment
+            .git()
+            .create_branch(&repo_path, "forge/archived")
+            .unwrap();
+
+        let data = CreateTask::from_title_description(project_id, "Archived".to_string(), None);
+        let task = Task::create(pool, &data, Uuid::new_v4()).await.unwrap();
+        let task = Task::update(
+            pool,
+            task.id,
+            project_id,
+            task.title,
+            None,
+            TaskStatus::Archived,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let attempt = TaskAttempt::create(
+            pool,
+            &CreateTaskAttempt {
+                executor: BaseCodingAgent::ClaudeCode,
+                variant: None,
+                base_branch: "main".to_string(),
+                branch: "forge/archived".to_string(),
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+        let worktree_path = root.path().join("worktree");
+        TaskAttempt::update_container_ref(pool, attempt.id, &worktree_path.to_string_lossy())
+            .await
+            .unwrap();
+        TaskAttempt::mark_worktree_deleted(pool, attempt.id)
+            .await
+            .unwrap();
+
+        let stale = task.updated_at - chrono::Duration::seconds(1);
+        let result = update_task(
+            Extension(task.clone()),
+            State(deployment.clone()),
+            Json(UpdateTask {
+                title: None,
+                description: None,
+                status: Some(TaskStatus::Todo),
+                parent_task_attempt: None,
+                image_ids: None,
+                updated_at: Some(stale),
+            }),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiError::Conflict(_))));
+
+        let task = Task::find_by_id(pool, task.id).await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Archived);
+        let attempt = TaskAttempt::find_by_id(pool, attempt.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(attempt.worktree_deleted);
+        assert!(!worktree_path.exists());
+    }
+}
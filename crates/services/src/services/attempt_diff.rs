@@ -0,0 +1,71 @@
+//! Compact summary of an attempt's changes for agents deciding whether to merge: per-file line
+//! counts plus a size-capped unified patch.
+
+use forge_core_utils::diff::{Diff, DiffChangeKind, compute_line_change_counts};
+use serde::{Deserialize, Serialize};
+use ts_rs_forge::TS;
+
+use super::{attempt_export::render_diff, git::GitService};
+
+/// Upper bound on the size of the patch returned with a `DiffResult`
+pub const MAX_DIFF_PATCH_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DiffFileStat {
+    pub path: String,
+    pub change: DiffChangeKind,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DiffResult {
+    /// Every changed file, including those left out of `patch`
+    pub files: Vec<DiffFileStat>,
+    pub additions: usize,
+    pub deletions: usize,
+    /// Unified diff of the changes, capped at `MAX_DIFF_PATCH_BYTES`
+    pub patch: String,
+    /// True when files were left out of `patch` to stay under the cap
+    pub truncated: bool,
+}
+
+impl DiffResult {
+    pub fn from_diffs(diffs: &[Diff], max_patch_bytes: usize) -> Self {
+        let files: Vec<DiffFileStat> = diffs
+            .iter()
+            .map(|diff| {
+                let (additions, deletions) = match (diff.additions, diff.deletions) {
+                    (Some(additions), Some(deletions)) => (additions, deletions),
+                    _ => line_counts(diff.old_content.as_deref(), diff.new_content.as_deref()),
+                };
+                DiffFileStat {
+                    path: GitService::diff_path(diff),
+                    change: diff.change.clone(),
+                    additions,
+                    deletions,
+                }
+            })
+            .collect();
+        let (patch, truncated) = render_diff(diffs, max_patch_bytes);
+
+        Self {
+            additions: files.iter().map(|f| f.additions).sum(),
+            deletions: files.iter().map(|f| f.deletions).sum(),
+            files,
+            patch,
+            truncated,
+        }
+    }
+}
+
+fn line_counts(old: Option<&str>, new: Option<&str>) -> (usize, usize) {
+    let non_empty = |content: Option<&str>| content.filter(|c| !c.is_empty());
+    match (non_empty(old), non_empty(new)) {
+        (None, Some(new)) => (new.lines().count(), 0),
+        (Some(old), None) => (0, old.lines().count()),
+        (Some(old), Some(new)) => compute_line_change_counts(old, new),
+        // Binary files carry no contents; empty files have no lines
+        (None, None) => (0, 0),
+    }
+}
@@ -0,0 +1,211 @@
+//! Builds a self-contained export of a task attempt (transcript, diff, branch status and
+//! metadata) that can be handed off for review or support.
+
+use chrono::{DateTime, Utc};
+use forge_core_db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    executor_session::ExecutorSession,
+    task::Task,
+    task_attempt::TaskAttempt,
+};
+use forge_core_executors::actions::ExecutorActionType;
+use forge_core_utils::diff::{Diff, create_unified_diff};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs_forge::TS;
+use uuid::Uuid;
+
+use super::git::BranchStatus;
+
+/// Upper bound on the size of the unified diff included in an export
+pub const MAX_EXPORT_DIFF_BYTES: usize = 512 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct AttemptExportMetadata {
+    pub attempt_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub task_description: Option<String>,
+    pub executor: String,
+    pub branch: String,
+    pub target_branch: String,
+    pub attempt_created_at: DateTime<Utc>,
+    pub exported_at: DateTime<Utc>,
+}
+
+/// One coding agent turn of an attempt
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TranscriptTurn {
+    pub execution_process_id: Uuid,
+    pub status: ExecutionProcessStatus,
+    pub exit_code: Option<i64>,
+    pub prompt: Option<String>,
+    /// Final assistant message reported by the executor
+    pub summary: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct AttemptExportBundle {
+    pub metadata: AttemptExportMetadata,
+    /// Markdown transcript of the attempt's coding agent turns
+    pub transcript: String,
+    /// Unified diff of the attempt's changes against its base
+    pub diff: String,
+    /// True when files were left out of `diff` to stay under the size cap
+    pub diff_truncated: bool,
+    pub branch_status: Option<BranchStatus>,
+}
+
+impl AttemptExportBundle {
+    pub fn new(
+        task: &Task,
+        attempt: &TaskAttempt,
+        turns: &[TranscriptTurn],
+        diffs: &[Diff],
+        branch_status: Option<BranchStatus>,
+    ) -> Self {
+        let (diff, diff_truncated) = render_diff(diffs, MAX_EXPORT_DIFF_BYTES);
+        Self {
+            metadata: AttemptExportMetadata {
+                attempt_id: attempt.id,
+                task_id: task.id,
+                task_title: task.title.clone(),
+                task_description: task.description.clone(),
+                executor: attempt.executor.clone(),
+                branch: attempt.branch.clone(),
+                target_branch: attempt.target_branch.clone(),
+                attempt_created_at: attempt.created_at,
+                exported_at: Utc::now(),
+            },
+            transcript: render_transcript(task, attempt, turns),
+            diff,
+            diff_truncated,
+            branch_status,
+        }
+    }
+}
+
+/// Loads the coding agent turns of an attempt in the order they ran. The prompt comes from the
+/// executor session when one was recorded, otherwise from the process's executor action.
+pub async fn load_transcript_turns(
+    pool: &SqlitePool,
+    attempt_id: Uuid,
+) -> Result<Vec<TranscriptTurn>, sqlx::Error> {
+    let processes = ExecutionProcess::find_by_task_attempt_id(pool, attempt_id, false).await?;
+
+    let mut turns = Vec::new();
+    for process in processes {
+        if process.run_reason != ExecutionProcessRunReason::CodingAgent {
+            continue;
+        }
+
+        let session = ExecutorSession::find_by_execution_process_id(pool, process.id).await?;
+        let (session_prompt, summary) = session
+            .map(|s| (s.prompt, s.summary))
+            .unwrap_or_default();
+        let prompt = session_prompt.or_else(|| {
+            process
+                .executor_action()
+                .ok()
+                .and_then(|action| match &action.typ {
+                    ExecutorActionType::CodingAgentInitialRequest(request) => {
+                        Some(request.prompt.clone())
+                    }
+                    ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                        Some(request.prompt.clone())
+                    }
+                    ExecutorActionType::ScriptRequest(_) => None,
+                })
+        });
+
+        turns.push(TranscriptTurn {
+            execution_process_id: process.id,
+            status: process.status,
+            exit_code: process.exit_code,
+            prompt,
+            summary,
+            started_at: process.started_at,
+            completed_at: process.completed_at,
+        });
+    }
+
+    Ok(turns)
+}
+
+pub fn render_transcript(task: &Task, attempt: &TaskAttempt, turns: &[TranscriptTurn]) -> String {
+    let mut out = format!("# {}\n\n", task.title);
+    if let Some(description) = task.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        out.push_str(description.trim());
+        out.push_str("\n\n");
+    }
+    out.push_str(&format!(
+        "- Attempt: `{}`\n- Executor: {}\n- Branch: `{}` → `{}`\n",
+        attempt.id, attempt.executor, attempt.branch, attempt.target_branch
+    ));
+
+    if turns.is_empty() {
+        out.push_str("\n_No coding agent turns were recorded for this attempt._\n");
+        return out;
+    }
+
+    for (index, turn) in turns.iter().enumerate() {
+        out.push_str(&format!("\n## Turn {} ({:?}", index + 1, turn.status));
+        if let Some(code) = turn.exit_code {
+            out.push_str(&format!(", exit code {code}"));
+        }
+        out.push_str(&format!(")\n\n_Started {}_\n", turn.started_at.to_rfc3339()));
+
+        out.push_str("\n### Prompt\n\n");
+        out.push_str(turn.prompt.as_deref().unwrap_or("_Not recorded_").trim());
+        out.push('\n');
+
+        out.push_str("\n### Response\n\n");
+        out.push_str(turn.summary.as_deref().unwrap_or("_Not recorded_").trim());
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Renders diffs as a single unified diff, leaving out whole files once `max_bytes` would be
+/// exceeded. Returns the diff and whether anything was left out.
+pub fn render_diff(diffs: &[Diff], max_bytes: usize) -> (String, bool) {
+    let mut out = String::new();
+    let mut omitted = 0usize;
+
+    for diff in diffs {
+        let path = diff
+            .new_path
+            .as_deref()
+            .or(diff.old_path.as_deref())
+            .unwrap_or("unknown");
+        let rendered = if diff.content_omitted {
+            format!("--- a/{path}\n+++ b/{path}\n# content omitted (file too large)\n")
+        } else {
+            create_unified_diff(
+                path,
+                diff.old_content.as_deref().unwrap_or_default(),
+                diff.new_content.as_deref().unwrap_or_default(),
+            )
+        };
+
+        if out.len() + rendered.len() > max_bytes {
+            omitted += 1;
+            continue;
+        }
+        out.push_str(&rendered);
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+
+    if omitted > 0 {
+        out.push_str(&format!(
+            "# {omitted} file(s) omitted: diff exceeds {max_bytes} bytes\n"
+        ));
+    }
+
+    (out, omitted > 0)
+}
@@ -1,6 +1,13 @@
-use std::path::Path;
+use std::{collections::HashSet, path::Path};
 
+use serde::Deserialize;
 use thiserror::Error;
+use ts_rs_forge::TS;
+
+use super::{
+    commit_validator::{CommitValidator, extra_prefixes, strip_prefix_ignore_case},
+    git_cli::GitCli,
+};
 
 #[derive(Error, Debug)]
 pub enum CommitMessageError {
@@ -11,60 +18,387 @@ pub enum CommitMessageError {
     InvalidFormat,
 }
 
-/// Service for generating high-quality conventional commit messages
-pub struct CommitMessageGenerator;
+/// A file changed on the branch being committed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    pub path: String,
+    /// True when the file does not exist on the base branch
+    pub added: bool,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// Someone credited on a commit with a `Co-authored-by` trailer
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, TS)]
+pub struct CoAuthor {
+    pub name: String,
+    pub email: String,
+}
+
+impl CoAuthor {
+    /// Parse a `Co-authored-by: Name <email>` trailer line
+    fn from_trailer(line: &str) -> Option<Self> {
+        let (key, value) = line.trim().split_once(':')?;
+        if !key.trim().eq_ignore_ascii_case(CO_AUTHOR_TRAILER) {
+            return None;
+        }
+        let (name, email) = value.trim().strip_suffix('>')?.split_once('<')?;
+        let (name, email) = (name.trim(), email.trim());
+        if name.is_empty() || email.is_empty() {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            email: email.to_string(),
+        })
+    }
 
-impl Default for CommitMessageGenerator {
-    fn default() -> Self {
-        Self
+    fn trailer(&self) -> String {
+        format!(
+            "{CO_AUTHOR_TRAILER}: {} <{}>",
+            self.name.trim(),
+            self.email.trim()
+        )
     }
 }
 
+const CO_AUTHOR_TRAILER: &str = "Co-authored-by";
+/// Area name for files at the repository root
+const ROOT_AREA: &str = "root";
+/// Directories whose children are separate packages, so areas go one level deeper
+const WORKSPACE_DIRS: [&str; 4] = ["crates", "packages", "apps", "libs"];
+/// Areas listed in a diff-based commit body before the rest are summarized
+const MAX_BODY_AREAS: usize = 5;
+
+/// Service for generating high-quality conventional commit messages
+#[derive(Default)]
+pub struct CommitMessageGenerator {
+    /// Conversational openers stripped from titles on top of the built-in ones
+    conversational_prefixes: Vec<String>,
+}
+
 impl CommitMessageGenerator {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Also strip and reject messages opening with one of `prefixes`, matched ignoring case
+    pub fn with_conversational_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.conversational_prefixes = prefixes;
+        self
     }
 
     /// Generate a commit message from task context
     ///
     /// Priority order:
     /// 1. Use executor-generated commit message (if available)
-    /// 2. Generate from diff analysis
+    /// 2. Generate from analysis of the worktree's diff against `base_branch`
     /// 3. Fallback to sanitized task title
+    ///
+    /// `co_authors` are credited with `Co-authored-by` trailers whichever message is chosen.
+    #[allow(clippy::too_many_arguments)]
     pub fn generate(
         &self,
         task_title: &str,
         task_description: Option<&str>,
         github_issue: Option<u32>,
         executor_commit_message: Option<&str>,
-        _worktree_path: &Path,
+        worktree_path: &Path,
+        base_branch: &str,
+        co_authors: &[CoAuthor],
     ) -> Result<String, CommitMessageError> {
         // Priority 1: Use executor-generated commit message
         if let Some(msg) = executor_commit_message
-            && Self::is_valid_commit_message(msg)
+            && Self::is_valid_commit_message(msg, &self.conversational_prefixes)
         {
-            return Ok(msg.to_string());
+            return Ok(Self::append_co_authors(
+                msg.trim_end().to_string(),
+                co_authors,
+            ));
         }
 
-        // Priority 2: TODO - Analyze diff and generate (future enhancement)
-        // This would call commit-suggester agent or use a lightweight model
+        // Priority 2: Analyze the diff against the base branch
+        match Self::changed_files(worktree_path, base_branch) {
+            Ok(files) => {
+                if let Some(message) = Self::compose_from_diff(
+                    task_title,
+                    github_issue,
+                    &files,
+                    &self.conversational_prefixes,
+                ) {
+                    return Ok(Self::append_co_authors(message, co_authors));
+                }
+            }
+            Err(e) => tracing::debug!("Skipping diff-based commit message: {}", e),
+        }
 
         // Priority 3: Sanitize task title and construct message
         Ok(Self::sanitize_and_format(
             task_title,
             task_description,
             github_issue,
+            co_authors,
+            &self.conversational_prefixes,
         ))
     }
 
+    /// Append a `Co-authored-by` trailer per co-author, after a blank line as git expects.
+    /// Co-authors are matched by email, so anyone already credited in `message` is skipped.
+    fn append_co_authors(message: String, co_authors: &[CoAuthor]) -> String {
+        let mut credited: HashSet<String> = message
+            .lines()
+            .filter_map(CoAuthor::from_trailer)
+            .map(|author| author.email.to_lowercase())
+            .collect();
+        let trailers: Vec<String> = co_authors
+            .iter()
+            .filter(|author| !author.name.trim().is_empty() && !author.email.trim().is_empty())
+            .filter(|author| credited.insert(author.email.trim().to_lowercase()))
+            .map(CoAuthor::trailer)
+            .collect();
+        if trailers.is_empty() {
+            return message;
+        }
+
+        // Extend an existing trailer block rather than starting a second one
+        let ends_with_trailers = message.rsplit_once("\n\n").is_some_and(|(_, block)| {
+            block
+                .lines()
+                .all(|line| CoAuthor::from_trailer(line).is_some())
+        });
+        let separator = if ends_with_trailers { "\n" } else { "\n\n" };
+        format!("{message}{separator}{}", trailers.join("\n"))
+    }
+
+    /// Files changed in the worktree since it diverged from `base_branch`
+    fn changed_files(
+        worktree_path: &Path,
+        base_branch: &str,
+    ) -> Result<Vec<ChangedFile>, CommitMessageError> {
+        let git = GitCli::new();
+        let merge_base = git
+            .git(worktree_path, ["merge-base", "HEAD", base_branch])
+            .map_err(|e| CommitMessageError::GitError(e.to_string()))?;
+        let out = git
+            .git(
+                worktree_path,
+                [
+                    "-c",
+                    "core.quotepath=false",
+                    "diff",
+                    "--numstat",
+                    "--summary",
+                    "--no-renames",
+                    merge_base.trim(),
+                ],
+            )
+            .map_err(|e| CommitMessageError::GitError(e.to_string()))?;
+        Ok(Self::parse_numstat_summary(&out))
+    }
+
+    /// Parse `git diff --numstat --summary` output. Binary files count as zero lines.
+    fn parse_numstat_summary(out: &str) -> Vec<ChangedFile> {
+        let mut created = HashSet::new();
+        let mut files = Vec::new();
+        for line in out.lines() {
+            // Summary lines look like ` create mode 100644 path/to/file`
+            if let Some(rest) = line.trim_start().strip_prefix("create mode ") {
+                if let Some((_, path)) = rest.split_once(' ') {
+                    created.insert(path.to_string());
+                }
+                continue;
+            }
+            let mut parts = line.splitn(3, '\t');
+            if let (Some(additions), Some(deletions), Some(path)) =
+                (parts.next(), parts.next(), parts.next())
+            {
+                files.push(ChangedFile {
+                    path: path.to_string(),
+                    added: false,
+                    additions: additions.parse().unwrap_or(0),
+                    deletions: deletions.parse().unwrap_or(0),
+                });
+            }
+        }
+        for file in &mut files {
+            file.added = created.contains(&file.path);
+        }
+        files
+    }
+
+    /// Build a conventional commit from the task title and the changed files: the type and
+    /// scope come from the diff, the body lists the touched areas
+    fn compose_from_diff(
+        title: &str,
+        github_issue: Option<u32>,
+        files: &[ChangedFile],
+        extra: &[String],
+    ) -> Option<String> {
+        let summary = Self::sanitize_title_with_prefixes(title, extra);
+        if files.is_empty() || summary.is_empty() {
+            return None;
+        }
+
+        let areas = Self::areas(files);
+        let mut subject = if CommitValidator::follows_conventional_commits(&summary) {
+            summary
+        } else {
+            let scope = match areas.as_slice() {
+                [(area, ..)] if area != ROOT_AREA => {
+                    format!("({})", area.rsplit('/').next().unwrap_or(area))
+                }
+                _ => String::new(),
+            };
+            let mut chars = summary.chars();
+            let summary = match (chars.next(), chars.next()) {
+                // Keep acronyms like "OAuth" or "API" intact
+                (Some(first), Some(second)) if !second.is_uppercase() => {
+                    first.to_lowercase().chain(summary.chars().skip(1)).collect()
+                }
+                _ => summary,
+            };
+            let subject = format!("{}{scope}: {summary}", Self::infer_commit_type(files));
+            subject.chars().take(72).collect::<String>().trim().to_string()
+        };
+        if let Some(issue) = github_issue {
+            subject = format!("{subject} (#{issue})");
+        }
+
+        let mut body: Vec<String> = areas
+            .iter()
+            .take(MAX_BODY_AREAS)
+            .map(|(area, count, additions, deletions)| {
+                let noun = if *count == 1 { "file" } else { "files" };
+                format!("- {area}: {count} {noun} (+{additions}/-{deletions})")
+            })
+            .collect();
+        if areas.len() > MAX_BODY_AREAS {
+            body.push(format!("- {} more areas", areas.len() - MAX_BODY_AREAS));
+        }
+
+        Some(format!("{subject}\n\n{}", body.join("\n")))
+    }
+
+    /// Conventional commit type for a set of changed files
+    fn infer_commit_type(files: &[ChangedFile]) -> &'static str {
+        let source: Vec<&ChangedFile> = files
+            .iter()
+            .filter(|f| {
+                !Self::is_docs_path(&f.path)
+                    && !Self::is_test_path(&f.path)
+                    && !Self::is_chore_path(&f.path)
+            })
+            .collect();
+
+        if source.is_empty() {
+            return if files.iter().all(|f| Self::is_docs_path(&f.path)) {
+                "docs"
+            } else if files.iter().any(|f| Self::is_test_path(&f.path)) {
+                "test"
+            } else {
+                "chore"
+            };
+        }
+
+        let additions: usize = source.iter().map(|f| f.additions).sum();
+        let deletions: usize = source.iter().map(|f| f.deletions).sum();
+        if source.iter().any(|f| f.added) {
+            "feat"
+        } else if deletions >= additions {
+            // Reshaping or trimming existing code rather than adding behaviour
+            "refactor"
+        } else {
+            "fix"
+        }
+    }
+
+    /// Changed files grouped by area with file count and line stats, largest first
+    fn areas(files: &[ChangedFile]) -> Vec<(String, usize, usize, usize)> {
+        let mut areas: Vec<(String, usize, usize, usize)> = Vec::new();
+        for file in files {
+            let area = Self::area_of(&file.path);
+            match areas.iter_mut().find(|(name, ..)| *name == area) {
+                Some((_, count, additions, deletions)) => {
+                    *count += 1;
+                    *additions += file.additions;
+                    *deletions += file.deletions;
+                }
+                None => areas.push((area, 1, file.additions, file.deletions)),
+            }
+        }
+        areas.sort_by(|a, b| (b.2 + b.3).cmp(&(a.2 + a.3)).then_with(|| a.0.cmp(&b.0)));
+        areas
+    }
+
+    /// First directory of a path, or the first two for workspace layouts (`crates/foo`)
+    fn area_of(path: &str) -> String {
+        let parts: Vec<&str> = path.split('/').collect();
+        match parts.as_slice() {
+            [group, member, _, ..] if WORKSPACE_DIRS.contains(group) => {
+                format!("{group}/{member}")
+            }
+            [dir, _, ..] => dir.to_string(),
+            _ => ROOT_AREA.to_string(),
+        }
+    }
+
+    fn file_name(path: &str) -> &str {
+        path.rsplit('/').next().unwrap_or(path)
+    }
+
+    fn is_docs_path(path: &str) -> bool {
+        let name = Self::file_name(path).to_ascii_lowercase();
+        path.starts_with("docs/")
+            || path.starts_with("doc/")
+            || [".md", ".mdx", ".rst", ".adoc"]
+                .iter()
+                .any(|ext| name.ends_with(ext))
+            || ["readme", "changelog", "license"]
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+    }
+
+    fn is_test_path(path: &str) -> bool {
+        let name = Self::file_name(path);
+        let stem = name.split('.').next().unwrap_or(name);
+        path.split('/')
+            .any(|part| matches!(part, "tests" | "test" | "__tests__" | "spec"))
+            || name.contains(".test.")
+            || name.contains(".spec.")
+            || stem.starts_with("test_")
+            || stem.ends_with("_test")
+            || stem.ends_with("_spec")
+    }
+
+    fn is_chore_path(path: &str) -> bool {
+        let name = Self::file_name(path);
+        path.starts_with(".github/")
+            || matches!(
+                name,
+                "Cargo.toml"
+                    | "Cargo.lock"
+                    | "package.json"
+                    | "package-lock.json"
+                    | "pnpm-lock.yaml"
+                    | "yarn.lock"
+                    | "Makefile"
+                    | "Dockerfile"
+                    | ".gitignore"
+                    | "rust-toolchain.toml"
+                    | "rustfmt.toml"
+            )
+    }
+
     /// Sanitize task title and format as conventional commit
     fn sanitize_and_format(
         title: &str,
         description: Option<&str>,
         github_issue: Option<u32>,
+        co_authors: &[CoAuthor],
+        extra: &[String],
     ) -> String {
         // Remove conversational AI prefixes and clean up
-        let cleaned = Self::sanitize_title(title);
+        let cleaned = Self::sanitize_title_with_prefixes(title, extra);
 
         // Build commit message
         let mut message = cleaned;
@@ -83,11 +417,25 @@ impl CommitMessageGenerator {
             }
         }
 
-        message
+        // Co-authors credited in the description keep their trailers even when the description
+        // itself is trimmed or dropped
+        let credited: Vec<CoAuthor> = description
+            .into_iter()
+            .flat_map(str::lines)
+            .filter_map(CoAuthor::from_trailer)
+            .chain(co_authors.iter().cloned())
+            .collect();
+        Self::append_co_authors(message, &credited)
     }
 
     /// Sanitize task title - remove conversational crud
+    #[cfg(test)]
     fn sanitize_title(raw_title: &str) -> String {
+        Self::sanitize_title_with_prefixes(raw_title, &[])
+    }
+
+    /// Sanitize task title, also stripping `extra` prefixes. Prefixes match ignoring case.
+    fn sanitize_title_with_prefixes(raw_title: &str, extra: &[String]) -> String {
         let conversational_prefixes = [
             "Perfect! Let me ",
             "Perfect! ",
@@ -108,10 +456,10 @@ impl CommitMessageGenerator {
 
         let mut cleaned = raw_title.trim();
 
-        // Remove conversational prefixes
-        for prefix in &conversational_prefixes {
-            if let Some(stripped) = cleaned.strip_prefix(prefix) {
-                cleaned = stripped;
+        // Remove conversational prefixes, configured ones first so they can be more specific
+        for prefix in extra_prefixes(extra).chain(conversational_prefixes) {
+            if let Some(stripped) = strip_prefix_ignore_case(cleaned, prefix) {
+                cleaned = stripped.trim_start();
                 break;
             }
         }
@@ -135,8 +483,10 @@ impl CommitMessageGenerator {
             .lines()
             .filter(|line| {
                 let trimmed = line.trim();
-                // Skip markdown tables
-                !trimmed.starts_with('|')
+                // Co-author trailers are re-added below the body
+                CoAuthor::from_trailer(trimmed).is_none()
+                    // Skip markdown tables
+                    && !trimmed.starts_with('|')
                     && !trimmed.starts_with("---")
                     // Skip section headers with excessive formatting
                     && !trimmed.starts_with("## ")
@@ -150,7 +500,7 @@ impl CommitMessageGenerator {
     }
 
     /// Validate commit message format
-    fn is_valid_commit_message(msg: &str) -> bool {
+    fn is_valid_commit_message(msg: &str, extra: &[String]) -> bool {
         if msg.is_empty() {
             return false;
         }
@@ -170,10 +520,12 @@ impl CommitMessageGenerator {
         let first_line = msg.lines().next().unwrap_or("");
 
         // Reject if starts with conversational pattern
-        for pattern in &conversational_patterns {
-            if first_line.starts_with(pattern) {
-                return false;
-            }
+        if CommitValidator::has_conversational_pattern(first_line, extra)
+            || conversational_patterns
+                .into_iter()
+                .any(|pattern| strip_prefix_ignore_case(first_line, pattern).is_some())
+        {
+            return false;
         }
 
         // Basic sanity checks
@@ -205,6 +557,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sanitize_title_ignores_case_and_strips_custom_prefixes() {
+        assert_eq!(
+            CommitMessageGenerator::sanitize_title("PERFECT! let me add the login page"),
+            "add the login page"
+        );
+
+        let prefixes = vec!["Claro, vou ".to_string(), "Listo! ".to_string()];
+        assert_eq!(
+            CommitMessageGenerator::sanitize_title_with_prefixes(
+                "claro, vou criar a página de login",
+                &prefixes
+            ),
+            "criar a página de login"
+        );
+        assert_eq!(
+            CommitMessageGenerator::sanitize_title_with_prefixes("listo! add login", &prefixes),
+            "add login"
+        );
+        // Built-in prefixes still apply alongside custom ones
+        assert_eq!(
+            CommitMessageGenerator::sanitize_title_with_prefixes("Let me add login", &prefixes),
+            "add login"
+        );
+        assert!(!CommitMessageGenerator::is_valid_commit_message(
+            "Claro, vou criar a página de login",
+            &prefixes
+        ));
+    }
+
     #[test]
     fn test_sanitize_title_takes_first_line() {
         assert_eq!(
@@ -243,22 +625,29 @@ mod tests {
     #[test]
     fn test_is_valid_commit_message() {
         assert!(CommitMessageGenerator::is_valid_commit_message(
-            "feat: add new feature"
+            "feat: add new feature",
+            &[]
         ));
 
         assert!(!CommitMessageGenerator::is_valid_commit_message(
-            "Perfect! Let me help you"
+            "Perfect! Let me help you",
+            &[]
         ));
 
-        assert!(!CommitMessageGenerator::is_valid_commit_message(""));
+        assert!(!CommitMessageGenerator::is_valid_commit_message("", &[]));
 
-        assert!(!CommitMessageGenerator::is_valid_commit_message("abc")); // Too short
+        assert!(!CommitMessageGenerator::is_valid_commit_message("abc", &[])); // Too short
     }
 
     #[test]
     fn test_sanitize_and_format_with_issue() {
-        let result =
-            CommitMessageGenerator::sanitize_and_format("implement OAuth login", None, Some(123));
+        let result = CommitMessageGenerator::sanitize_and_format(
+            "implement OAuth login",
+            None,
+            Some(123),
+            &[],
+            &[],
+        );
 
         assert_eq!(result, "implement OAuth login (#123)");
     }
@@ -269,6 +658,8 @@ mod tests {
             "add user authentication",
             Some("This feature adds OAuth support\nWith Google integration"),
             None,
+            &[],
+            &[],
         );
 
         assert!(result.contains("add user authentication"));
@@ -276,6 +667,144 @@ mod tests {
         assert!(result.contains("With Google integration"));
     }
 
+    fn co_author(name: &str, email: &str) -> CoAuthor {
+        CoAuthor {
+            name: name.to_string(),
+            email: email.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_co_author_trailers_are_appended_once() {
+        let co_authors = [
+            co_author("Ada Lovelace", "ada@example.com"),
+            co_author("Ada L.", "ADA@example.com"),
+            co_author("Grace Hopper", "grace@example.com"),
+        ];
+        let message = CommitMessageGenerator::sanitize_and_format(
+            "add login page",
+            Some("Adds the login form\nCo-authored-by: Grace Hopper <grace@example.com>"),
+            None,
+            &co_authors,
+            &[],
+        );
+
+        assert_eq!(
+            message,
+            "add login page\n\nAdds the login form\n\n\
+             Co-authored-by: Grace Hopper <grace@example.com>\n\
+             Co-authored-by: Ada Lovelace <ada@example.com>"
+        );
+        // Crediting the same people again leaves the message untouched
+        assert_eq!(
+            CommitMessageGenerator::append_co_authors(message.clone(), &co_authors),
+            message
+        );
+
+        // An existing trailer block is extended rather than repeated
+        let message = CommitMessageGenerator::append_co_authors(
+            "feat: add login page\n\nCo-authored-by: Grace Hopper <grace@example.com>".into(),
+            &co_authors,
+        );
+        assert_eq!(
+            message,
+            "feat: add login page\n\n\
+             Co-authored-by: Grace Hopper <grace@example.com>\n\
+             Co-authored-by: Ada Lovelace <ada@example.com>"
+        );
+    }
+
+    fn changed(path: &str, added: bool, additions: usize, deletions: usize) -> ChangedFile {
+        ChangedFile {
+            path: path.to_string(),
+            added,
+            additions,
+            deletions,
+        }
+    }
+
+    #[test]
+    fn test_parse_numstat_summary_marks_created_files() {
+        let out = "12\t0\tsrc/greeter.rs\n3\t1\tsrc/lib.rs\n-\t-\tassets/logo.png\n \
+                   create mode 100644 src/greeter.rs\n create mode 100644 assets/logo.png\n";
+
+        assert_eq!(
+            CommitMessageGenerator::parse_numstat_summary(out),
+            vec![
+                changed("src/greeter.rs", true, 12, 0),
+                changed("src/lib.rs", false, 3, 1),
+                changed("assets/logo.png", true, 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_infer_commit_type_from_diff() {
+        let infer = CommitMessageGenerator::infer_commit_type;
+
+        // A new source file is a feature
+        assert_eq!(
+            infer(&[
+                changed("crates/services/src/services/greeter.rs", true, 40, 0),
+                changed("crates/services/src/services/mod.rs", false, 1, 0),
+                changed("crates/services/tests/greeter.rs", true, 25, 0),
+            ]),
+            "feat"
+        );
+        // Only tests touched
+        assert_eq!(
+            infer(&[
+                changed("crates/services/tests/git_ops_safety.rs", false, 30, 2),
+                changed("frontend/src/utils/date.test.ts", true, 12, 0),
+            ]),
+            "test"
+        );
+        // Only docs touched
+        assert_eq!(
+            infer(&[
+                changed("README.md", false, 4, 1),
+                changed("docs/setup.md", true, 20, 0),
+            ]),
+            "docs"
+        );
+        // Edits to existing code: growing is a fix, shrinking is a refactor
+        assert_eq!(infer(&[changed("src/lib.rs", false, 5, 1)]), "fix");
+        assert_eq!(infer(&[changed("src/lib.rs", false, 10, 30)]), "refactor");
+        assert_eq!(
+            infer(&[changed("Cargo.toml", false, 1, 1), changed("README.md", false, 1, 0)]),
+            "chore"
+        );
+    }
+
+    #[test]
+    fn test_compose_from_diff_adds_scope_and_area_summary() {
+        let files = [
+            changed("crates/services/src/services/greeter.rs", true, 40, 0),
+            changed("crates/services/src/services/mod.rs", false, 1, 0),
+        ];
+        let message =
+            CommitMessageGenerator::compose_from_diff("Add greeter service", Some(7), &files, &[])
+                .unwrap();
+
+        assert_eq!(
+            message,
+            "feat(services): add greeter service (#7)\n\n- crates/services: 2 files (+41/-0)"
+        );
+        assert!(CommitValidator::follows_conventional_commits(&message));
+
+        // Titles that already carry a type are kept, and acronyms stay intact
+        let docs = [changed("README.md", false, 2, 0)];
+        let message =
+            CommitMessageGenerator::compose_from_diff("fix: typo", None, &docs, &[]).unwrap();
+        assert!(message.starts_with("fix: typo\n\n- root: 1 file"));
+        let message =
+            CommitMessageGenerator::compose_from_diff("OAuth setup guide", None, &docs, &[])
+                .unwrap();
+        assert!(message.starts_with("docs: OAuth setup guide"));
+
+        assert!(CommitMessageGenerator::compose_from_diff("Anything", None, &[], &[]).is_none());
+    }
+
     #[test]
     fn test_sanitize_description_filters_markdown_tables() {
         let desc = "| Column 1 | Column 2 |\n|----------|----------|\n| Value 1  | Value 2  |\nRegular text here";
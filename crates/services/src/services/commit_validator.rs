@@ -1,26 +1,60 @@
+use serde::Serialize;
+
 /// Commit message validator for quality assurance
 pub struct CommitValidator;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ValidationWarning {
     pub message: String,
     pub severity: WarningSeverity,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum WarningSeverity {
     Info,
     Warning,
     Error,
 }
 
+/// `text` without a leading `prefix`, compared case-insensitively
+pub(crate) fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let mut chars = text.char_indices();
+    let mut end = 0;
+    for expected in prefix.chars() {
+        let (i, actual) = chars.next()?;
+        if !actual.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+        end = i + actual.len_utf8();
+    }
+    Some(&text[end..])
+}
+
+/// Configured prefixes worth matching; blank entries would match every message
+pub(crate) fn extra_prefixes(extra: &[String]) -> impl Iterator<Item = &str> {
+    extra
+        .iter()
+        .map(|p| p.trim_start())
+        .filter(|p| !p.trim().is_empty())
+}
+
 impl CommitValidator {
     /// Validate commit message and return warnings (if any)
     pub fn validate(commit_message: &str) -> Vec<ValidationWarning> {
+        Self::validate_with_prefixes(commit_message, &[])
+    }
+
+    /// Like [`Self::validate`], also flagging messages that open with one of
+    /// `conversational_prefixes`
+    pub fn validate_with_prefixes(
+        commit_message: &str,
+        conversational_prefixes: &[String],
+    ) -> Vec<ValidationWarning> {
         let mut warnings = Vec::new();
 
         // Check for conversational patterns (ERROR level)
-        if Self::has_conversational_pattern(commit_message) {
+        if Self::has_conversational_pattern(commit_message, conversational_prefixes) {
             warnings.push(ValidationWarning {
                 message: "Commit message contains conversational AI patterns (e.g., 'Perfect!', 'Let me')".to_string(),
                 severity: WarningSeverity::Error,
@@ -102,8 +136,8 @@ impl CommitValidator {
             .any(|prefix| first_line.starts_with(prefix))
     }
 
-    /// Check for conversational patterns
-    fn has_conversational_pattern(msg: &str) -> bool {
+    /// Check for conversational patterns, built-in or `extra`, ignoring case
+    pub(crate) fn has_conversational_pattern(msg: &str, extra: &[String]) -> bool {
         let conversational_patterns = [
             "Perfect!",
             "Good, I",
@@ -117,11 +151,12 @@ impl CommitValidator {
             "Great!",
         ];
 
-        let first_line = msg.lines().next().unwrap_or("");
+        let first_line = msg.lines().next().unwrap_or("").trim_start();
 
         conversational_patterns
-            .iter()
-            .any(|pattern| first_line.starts_with(pattern))
+            .into_iter()
+            .chain(extra_prefixes(extra))
+            .any(|pattern| strip_prefix_ignore_case(first_line, pattern).is_some())
     }
 
     /// Check if message has GitHub issue reference
@@ -159,6 +194,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_conversational_patterns_ignore_case() {
+        assert!(CommitValidator::has_conversational_pattern(
+            "PERFECT! the login page",
+            &[]
+        ));
+        assert!(CommitValidator::has_conversational_pattern(
+            "let me add the login page",
+            &[]
+        ));
+        assert!(!CommitValidator::has_conversational_pattern(
+            "feat: let me in",
+            &[]
+        ));
+    }
+
+    #[test]
+    fn test_validate_with_custom_prefixes() {
+        let prefixes = vec!["Claro, vou".to_string(), "  ".to_string()];
+        let errors = |msg: &str| {
+            CommitValidator::validate_with_prefixes(msg, &prefixes)
+                .into_iter()
+                .filter(|w| w.severity == WarningSeverity::Error)
+                .count()
+        };
+
+        assert_eq!(errors("claro, vou criar a página de login"), 1);
+        assert_eq!(errors("ÉTAPE: ignored"), 0);
+        // Blank prefixes are ignored rather than matching everything
+        assert_eq!(errors("feat: add login page (#12)"), 0);
+        assert!(
+            CommitValidator::validate("Claro, vou criar a página")
+                .iter()
+                .all(|w| w.severity != WarningSeverity::Error)
+        );
+    }
+
     #[test]
     fn test_validate_long_subject() {
         let msg = "a".repeat(100);
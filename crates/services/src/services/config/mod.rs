@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
@@ -14,27 +14,67 @@ pub enum ConfigError {
     ValidationError(String),
 }
 
-pub type Config = versions::v7::Config;
-pub type NotificationConfig = versions::v7::NotificationConfig;
-pub type EditorConfig = versions::v7::EditorConfig;
-pub type ThemeMode = versions::v7::ThemeMode;
-pub type SoundFile = versions::v7::SoundFile;
-pub type EditorType = versions::v7::EditorType;
-pub type GitHubConfig = versions::v7::GitHubConfig;
-pub type UiLanguage = versions::v7::UiLanguage;
-pub type ShowcaseState = versions::v7::ShowcaseState;
-
-/// Will always return config, trying old schemas or eventually returning default
+pub type Config = versions::v8::Config;
+pub type NotificationConfig = versions::v8::NotificationConfig;
+pub type EditorConfig = versions::v8::EditorConfig;
+pub type ThemeMode = versions::v8::ThemeMode;
+pub type SoundFile = versions::v8::SoundFile;
+pub type EditorType = versions::v8::EditorType;
+pub type GitHubConfig = versions::v8::GitHubConfig;
+pub type UiLanguage = versions::v8::UiLanguage;
+pub type ShowcaseState = versions::v8::ShowcaseState;
+
+/// Will always return config, trying old schemas, then recovering field by field,
+/// or eventually returning default. The original file is backed up to `<path>.bak`
+/// whenever any of its settings are dropped.
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
-    match std::fs::read_to_string(config_path) {
-        Ok(raw_config) => Config::from(raw_config),
+    let raw_config = match std::fs::read_to_string(config_path) {
+        Ok(raw_config) => raw_config,
         Err(_) => {
             tracing::info!("No config file found, creating one");
+            return Config::default();
+        }
+    };
+
+    let parse_error = match Config::parse(&raw_config) {
+        Ok(config) => return config,
+        Err(e) => e,
+    };
+    tracing::warn!("Failed to parse config: {}, attempting recovery", parse_error);
+    backup_config_file(config_path);
+
+    match Config::from_lenient(&raw_config) {
+        Some((config, reset_fields)) => {
+            if !reset_fields.is_empty() {
+                tracing::warn!(
+                    "Config recovered; reset unparseable fields to defaults: {}",
+                    reset_fields.join(", ")
+                );
+            }
+            config
+        }
+        None => {
+            tracing::warn!("Config is unrecoverable, using default");
             Config::default()
         }
     }
 }
 
+/// Path of the backup written before a config with unparseable fields is replaced
+fn config_backup_path(config_path: &Path) -> PathBuf {
+    let mut backup = config_path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+fn backup_config_file(config_path: &Path) {
+    let backup_path = config_backup_path(config_path);
+    match std::fs::copy(config_path, &backup_path) {
+        Ok(_) => tracing::info!("Original config backed up to {:?}", backup_path),
+        Err(e) => tracing::warn!("Failed to back up config to {:?}: {}", backup_path, e),
+    }
+}
+
 /// Saves the config to the given path
 pub async fn save_config_to_file(
     config: &Config,
@@ -5,3 +5,228 @@ pub(super) mod v4;
 pub(super) mod v5;
 pub(super) mod v6;
 pub(super) mod v7;
+pub(super) mod v8;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Recovers a config that fails to parse as a whole by deserializing it
+/// field by field. Fields whose value does not fit the current schema are
+/// reset to their default and reported in the returned list; fields that
+/// are missing take their default silently. Returns `None` when the input
+/// is not a JSON object at all.
+pub(super) fn from_lenient<T>(raw_config: &str) -> Option<(T, Vec<String>)>
+where
+    T: Default + Serialize + DeserializeOwned,
+{
+    let raw = serde_json::from_str::<serde_json::Value>(raw_config).ok()?;
+    let raw = raw.as_object()?;
+    let serde_json::Value::Object(mut merged) = serde_json::to_value(T::default()).ok()? else {
+        return None;
+    };
+
+    let mut reset_fields = Vec::new();
+    let field_names: Vec<String> = merged.keys().cloned().collect();
+    for field in field_names {
+        if field == "config_version" {
+            continue;
+        }
+        let Some(value) = raw.get(&field) else {
+            continue;
+        };
+
+        let mut candidate = merged.clone();
+        candidate.insert(field.clone(), value.clone());
+        if serde_json::from_value::<T>(serde_json::Value::Object(candidate)).is_ok() {
+            merged.insert(field, value.clone());
+        } else {
+            reset_fields.push(field);
+        }
+    }
+
+    let config = serde_json::from_value::<T>(serde_json::Value::Object(merged)).ok()?;
+    Some((config, reset_fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_core_executors::executors::BaseCodingAgent;
+    use serde_json::{Value, json};
+
+    use super::{v6, v7, v8};
+
+    /// Fields that `v7::Config::from_previous_version` deliberately does not
+    /// carry over from v6: the version marker itself plus settings introduced
+    /// in v7, which start from their defaults.
+    const V7_INTENTIONAL_DEFAULTS: &[&str] = &[
+        "config_version",
+        "contact_email_opt_in",
+        "contact_username_opt_in",
+        "git_branch_prefix",
+        "showcases",
+    ];
+
+    /// Fields that `v8::Config::from_previous_version` deliberately does not
+    /// carry over from v7.
+    const V8_INTENTIONAL_DEFAULTS: &[&str] = &["config_version", "default_variant_per_executor"];
+
+    /// A v6 config where every field differs from `v6::Config::default()`, so a
+    /// field that silently resets during migration shows up as a mismatch.
+    fn populated_v6_config() -> Value {
+        json!({
+            "config_version": "v6",
+            "theme": "DARK",
+            "executor_profile": { "executor": "CODEX", "variant": "HIGH" },
+            "disclaimer_acknowledged": true,
+            "onboarding_acknowledged": true,
+            "github_login_acknowledged": true,
+            "telemetry_acknowledged": true,
+            "notifications": {
+                "sound_enabled": false,
+                "push_enabled": false,
+                "sound_file": "PHONE_VIBRATION"
+            },
+            "editor": {
+                "editor_type": "ZED",
+                "custom_command": "zed --wait",
+                "remote_ssh_host": "devbox",
+                "remote_ssh_user": "forge"
+            },
+            "github": {
+                "pat": "ghp_test",
+                "oauth_token": "gho_test",
+                "username": "octocat",
+                "primary_email": "octocat@example.com",
+                "default_pr_base": "develop"
+            },
+            "analytics_enabled": false,
+            "workspace_dir": "/tmp/forge-workspace",
+            "last_app_version": "0.7.3",
+            "show_release_notes": true,
+            "language": "JA"
+        })
+    }
+
+    /// Asserts that every field of `before` survives into `after` unchanged,
+    /// except for whitelisted ones, and that any field new in `after` is
+    /// whitelisted as well.
+    fn assert_fields_carried_forward(before: &Value, after: &Value, whitelist: &[&str]) {
+        let before = before.as_object().expect("config should serialize to an object");
+        let after = after.as_object().expect("config should serialize to an object");
+
+        for (key, old_value) in before {
+            if whitelist.contains(&key.as_str()) {
+                continue;
+            }
+            assert_eq!(
+                after.get(key),
+                Some(old_value),
+                "field `{key}` was not carried forward by the migration"
+            );
+        }
+
+        for key in after.keys() {
+            assert!(
+                before.contains_key(key) || whitelist.contains(&key.as_str()),
+                "field `{key}` is new in the migrated config; add it to the whitelist if defaulting it is intended"
+            );
+        }
+    }
+
+    #[test]
+    fn v6_fixture_is_fully_populated() {
+        let fixture: v6::Config = serde_json::from_value(populated_v6_config()).unwrap();
+        let fixture = serde_json::to_value(fixture).unwrap();
+        let defaults = serde_json::to_value(v6::Config::default()).unwrap();
+
+        for (key, default_value) in defaults.as_object().unwrap() {
+            assert_ne!(
+                fixture.get(key),
+                Some(default_value),
+                "fixture field `{key}` matches its default and cannot detect a reset"
+            );
+        }
+    }
+
+    #[test]
+    fn v6_to_v7_migration_preserves_fields() {
+        let before: v6::Config = serde_json::from_value(populated_v6_config()).unwrap();
+        let before = serde_json::to_value(before).unwrap();
+
+        let migrated = v7::Config::from(before.to_string());
+        assert_eq!(migrated.config_version, "v7");
+
+        let after = serde_json::to_value(&migrated).unwrap();
+        assert_fields_carried_forward(&before, &after, V7_INTENTIONAL_DEFAULTS);
+    }
+
+    #[test]
+    fn v7_config_loads_without_resetting_fields() {
+        let mut config = v7::Config::from(populated_v6_config().to_string());
+        config.contact_email_opt_in = Some(true);
+        config.contact_username_opt_in = Some(false);
+        config.git_branch_prefix = "feature".to_string();
+        config.showcases.seen_features = vec!["kanban".to_string()];
+
+        let before = serde_json::to_value(&config).unwrap();
+        let after = serde_json::to_value(v7::Config::from(before.to_string())).unwrap();
+
+        assert_fields_carried_forward(&before, &after, &[]);
+    }
+
+    #[test]
+    fn v7_to_v8_migration_preserves_fields() {
+        let mut config = v7::Config::from(populated_v6_config().to_string());
+        config.contact_email_opt_in = Some(true);
+        config.contact_username_opt_in = Some(false);
+        config.git_branch_prefix = "feature".to_string();
+        config.showcases.seen_features = vec!["kanban".to_string()];
+        let before = serde_json::to_value(&config).unwrap();
+
+        let migrated = v8::Config::from(before.to_string());
+        assert_eq!(migrated.config_version, "v8");
+        assert!(migrated.default_variant_per_executor.is_empty());
+
+        let after = serde_json::to_value(&migrated).unwrap();
+        assert_fields_carried_forward(&before, &after, V8_INTENTIONAL_DEFAULTS);
+    }
+
+    #[test]
+    fn v8_config_round_trips_default_variants() {
+        let mut config = v8::Config::from(populated_v6_config().to_string());
+        config
+            .default_variant_per_executor
+            .insert(BaseCodingAgent::Codex, "HIGH".to_string());
+
+        let before = serde_json::to_value(&config).unwrap();
+        let after = serde_json::to_value(v8::Config::from(before.to_string())).unwrap();
+
+        assert_fields_carried_forward(&before, &after, &[]);
+    }
+
+    #[test]
+    fn lenient_parsing_resets_only_the_broken_fields() {
+        let mut raw = serde_json::to_value(v8::Config::from(populated_v6_config().to_string()))
+            .unwrap();
+        raw["theme"] = json!("NOT_A_THEME");
+        raw["git_branch_prefix"] = json!(42);
+
+        let (config, mut reset) = super::from_lenient::<v8::Config>(&raw.to_string()).unwrap();
+        reset.sort();
+        assert_eq!(reset, ["git_branch_prefix", "theme"]);
+        let defaults = serde_json::to_value(v8::Config::default()).unwrap();
+        let after = serde_json::to_value(&config).unwrap();
+        assert_eq!(after["theme"], defaults["theme"]);
+        assert_eq!(after["workspace_dir"], raw["workspace_dir"]);
+
+        assert!(super::from_lenient::<v8::Config>("[1, 2]").is_none());
+    }
+
+    #[test]
+    fn v6_color_themes_migrate_to_system() {
+        let mut raw = populated_v6_config();
+        raw["theme"] = json!("PURPLE");
+
+        let migrated = serde_json::to_value(v7::Config::from(raw.to_string())).unwrap();
+        assert_eq!(migrated["theme"], json!("SYSTEM"));
+    }
+}
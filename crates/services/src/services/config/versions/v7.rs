@@ -107,19 +107,25 @@ impl Config {
     }
 }
 
-impl From<String> for Config {
-    fn from(raw_config: String) -> Self {
-        if let Ok(config) = serde_json::from_str::<Config>(&raw_config)
+impl Config {
+    /// Parses a v7 config, migrating it from an older schema when needed
+    pub fn parse(raw_config: &str) -> Result<Self, Error> {
+        if let Ok(config) = serde_json::from_str::<Config>(raw_config)
             && config.config_version == "v7"
         {
-            return config;
+            return Ok(config);
         }
 
-        match Self::from_previous_version(&raw_config) {
-            Ok(config) => {
-                tracing::info!("Config upgraded to v7");
-                config
-            }
+        let config = Self::from_previous_version(raw_config)?;
+        tracing::info!("Config upgraded to v7");
+        Ok(config)
+    }
+}
+
+impl From<String> for Config {
+    fn from(raw_config: String) -> Self {
+        match Self::parse(&raw_config) {
+            Ok(config) => config,
             Err(e) => {
                 tracing::warn!("Config migration failed: {}, using default", e);
                 Self::default()
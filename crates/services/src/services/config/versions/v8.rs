@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use anyhow::Error;
+use forge_core_executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use serde::{Deserialize, Serialize};
+use ts_rs_forge::TS;
+pub use v7::{
+    EditorConfig, EditorType, GitHubConfig, NotificationConfig, ShowcaseState, SoundFile,
+    ThemeMode, UiLanguage,
+};
+
+use crate::services::config::versions::{self, v7};
+
+fn default_git_branch_prefix() -> String {
+    "forge".to_string()
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct Config {
+    pub config_version: String,
+    pub theme: ThemeMode,
+    pub executor_profile: ExecutorProfileId,
+    pub disclaimer_acknowledged: bool,
+    pub onboarding_acknowledged: bool,
+    pub github_login_acknowledged: bool,
+    pub telemetry_acknowledged: bool,
+    pub notifications: NotificationConfig,
+    pub editor: EditorConfig,
+    pub github: GitHubConfig,
+    pub analytics_enabled: Option<bool>,
+    pub contact_email_opt_in: Option<bool>,
+    pub contact_username_opt_in: Option<bool>,
+    pub workspace_dir: Option<String>,
+    pub last_app_version: Option<String>,
+    pub show_release_notes: bool,
+    #[serde(default)]
+    pub language: UiLanguage,
+    #[serde(default = "default_git_branch_prefix")]
+    pub git_branch_prefix: String,
+    #[serde(default)]
+    pub showcases: ShowcaseState,
+    /// Variant to use for each executor when none is chosen explicitly
+    #[serde(default)]
+    pub default_variant_per_executor: HashMap<BaseCodingAgent, String>,
+}
+
+impl Config {
+    pub fn from_previous_version(raw_config: &str) -> Result<Self, Error> {
+        let old_config = v7::Config::parse(raw_config)?;
+
+        Ok(Self {
+            config_version: "v8".to_string(),
+            theme: old_config.theme,
+            executor_profile: old_config.executor_profile,
+            disclaimer_acknowledged: old_config.disclaimer_acknowledged,
+            onboarding_acknowledged: old_config.onboarding_acknowledged,
+            github_login_acknowledged: old_config.github_login_acknowledged,
+            telemetry_acknowledged: old_config.telemetry_acknowledged,
+            notifications: old_config.notifications,
+            editor: old_config.editor,
+            github: old_config.github,
+            analytics_enabled: old_config.analytics_enabled,
+            contact_email_opt_in: old_config.contact_email_opt_in,
+            contact_username_opt_in: old_config.contact_username_opt_in,
+            workspace_dir: old_config.workspace_dir,
+            last_app_version: old_config.last_app_version,
+            show_release_notes: old_config.show_release_notes,
+            language: old_config.language,
+            git_branch_prefix: old_config.git_branch_prefix,
+            showcases: old_config.showcases,
+            default_variant_per_executor: HashMap::new(),
+        })
+    }
+}
+
+impl Config {
+    /// Parses a v8 config, migrating it from an older schema when needed
+    pub fn parse(raw_config: &str) -> Result<Self, Error> {
+        if let Ok(config) = serde_json::from_str::<Config>(raw_config)
+            && config.config_version == "v8"
+        {
+            return Ok(config);
+        }
+
+        let config = Self::from_previous_version(raw_config)?;
+        tracing::info!("Config upgraded to v8");
+        Ok(config)
+    }
+
+    /// Recovers a config that fails to parse as a whole; see `versions::from_lenient`
+    pub fn from_lenient(raw_config: &str) -> Option<(Self, Vec<String>)> {
+        versions::from_lenient(raw_config)
+    }
+}
+
+impl From<String> for Config {
+    fn from(raw_config: String) -> Self {
+        match Self::parse(&raw_config) {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!("Config migration failed: {}, using default", e);
+                Self::default()
+            }
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            config_version: "v8".to_string(),
+            theme: ThemeMode::System,
+            executor_profile: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+            disclaimer_acknowledged: false,
+            onboarding_acknowledged: false,
+            github_login_acknowledged: false,
+            telemetry_acknowledged: false,
+            notifications: NotificationConfig::default(),
+            editor: EditorConfig::default(),
+            github: GitHubConfig::default(),
+            analytics_enabled: None,
+            contact_email_opt_in: None,
+            contact_username_opt_in: None,
+            workspace_dir: None,
+            last_app_version: None,
+            show_release_notes: false,
+            language: UiLanguage::default(),
+            git_branch_prefix: default_git_branch_prefix(),
+            showcases: ShowcaseState::default(),
+            default_variant_per_executor: HashMap::new(),
+        }
+    }
+}
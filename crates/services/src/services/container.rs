@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 use anyhow::{Error as AnyhowError, anyhow};
@@ -9,6 +9,7 @@ use async_trait::async_trait;
 use forge_core_db::{
     DBService,
     models::{
+        attempt_profile_snapshot::AttemptProfileSnapshot,
         execution_process::{
             CreateExecutionProcess, ExecutionContext, ExecutionProcess, ExecutionProcessRunReason,
             ExecutionProcessStatus,
@@ -16,6 +17,7 @@ use forge_core_db::{
         execution_process_logs::ExecutionProcessLogs,
         execution_run::ExecutionRun,
         executor_session::{CreateExecutorSession, ExecutorSession},
+        project::Project,
         task::{Task, TaskStatus},
         task_attempt::{TaskAttempt, TaskAttemptError},
     },
@@ -37,18 +39,65 @@ use forge_core_utils::{
     text::{git_branch_id, short_uuid},
 };
 use futures::{StreamExt, future};
-use sqlx::Error as SqlxError;
+use serde::Serialize;
+use sqlx::{Error as SqlxError, SqlitePool};
 use thiserror::Error;
-use tokio::{sync::RwLock, task::JoinHandle};
+use tokio::{
+    sync::{OwnedMutexGuard, RwLock},
+    task::JoinHandle,
+};
 use uuid::Uuid;
 
 use crate::services::{
     git::{GitService, GitServiceError},
     image::ImageService,
+    log_retention::{LogRetentionPolicy, truncate_process_logs},
     worktree_manager::{WorktreeError, WorktreeManager},
 };
 pub type ContainerRef = String;
 
+// Serializes the archive cleanup and the restore of each task's worktrees
+lazy_static::lazy_static! {
+    static ref TASK_WORKTREE_LOCKS: Mutex<HashMap<Uuid, Arc<tokio::sync::Mutex<()>>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Holds a task's worktree lock; the task's lock entry is dropped with the last holder
+pub struct TaskWorktreeGuard {
+    task_id: Uuid,
+    guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl Drop for TaskWorktreeGuard {
+    fn drop(&mut self) {
+        // Release the mutex first so its only remaining reference is the map's when nobody
+        // else holds or waits for it. Waiters clone it under the map lock, so this can't race.
+        self.guard.take();
+        let mut locks = TASK_WORKTREE_LOCKS.lock().unwrap();
+        if locks
+            .get(&self.task_id)
+            .is_some_and(|lock| Arc::strong_count(lock) == 1)
+        {
+            locks.remove(&self.task_id);
+        }
+    }
+}
+
+/// Waits until no other archive cleanup or restore is working on `task_id`'s worktrees. Hold
+/// the guard until the worktrees and their `worktree_deleted` flags agree again.
+pub async fn lock_task_worktrees(task_id: Uuid) -> TaskWorktreeGuard {
+    let lock = TASK_WORKTREE_LOCKS
+        .lock()
+        .unwrap()
+        .entry(task_id)
+        .or_default()
+        .clone();
+    TaskWorktreeGuard {
+        task_id,
+        guard: Some(lock.lock_owned().await),
+    }
+}
+
 /// Data needed for background worktree cleanup (doesn't require DB access)
 #[derive(Debug, Clone)]
 pub struct WorktreeCleanupData {
@@ -83,6 +132,195 @@ pub async fn cleanup_worktrees_direct(data: &[WorktreeCleanupData]) -> Result<()
     Ok(())
 }
 
+/// Outcome of [`restore_task_worktrees`]
+#[derive(Debug, Default)]
+pub struct WorktreeRestore {
+    /// Attempts whose worktree was recreated
+    pub restored: Vec<Uuid>,
+    /// Attempts left without a worktree, with the reason
+    pub skipped: Vec<(Uuid, String)>,
+}
+
+/// Recreate the worktrees removed when a task was archived from each attempt's branch, and
+/// clear their `worktree_deleted` flag. An attempt whose branch is gone or whose worktree
+/// can't be recreated is skipped and reported, so one lost branch doesn't keep the task
+/// archived.
+pub async fn restore_task_worktrees(
+    pool: &SqlitePool,
+    git: &GitService,
+    task_id: Uuid,
+) -> Result<WorktreeRestore, ContainerError> {
+    let _guard = lock_task_worktrees(task_id).await;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let attempts: Vec<TaskAttempt> = TaskAttempt::fetch_all(pool, Some(task_id))
+        .await?
+        .into_iter()
+        .filter(|attempt| attempt.worktree_deleted && attempt.container_ref.is_some())
+        .collect();
+
+    let mut outcome = WorktreeRestore::default();
+    for attempt in attempts {
+        if !git.check_branch_exists(&project.git_repo_path, &attempt.branch)? {
+            outcome.skipped.push((
+                attempt.id,
+                ContainerError::BranchNotFound(attempt.branch).to_string(),
+            ));
+            continue;
+        }
+        // Attempts without a worktree run in the project repository itself
+        if let Some(container_ref) = &attempt.container_ref
+            && Path::new(container_ref) != project.git_repo_path
+            && let Err(e) = WorktreeManager::ensure_worktree_exists(
+                &project.git_repo_path,
+                &attempt.branch,
+                Path::new(container_ref),
+            )
+            .await
+        {
+            outcome.skipped.push((attempt.id, e.to_string()));
+            continue;
+        }
+        TaskAttempt::mark_worktree_restored(pool, attempt.id).await?;
+        outcome.restored.push(attempt.id);
+    }
+    Ok(outcome)
+}
+
+/// Branch handling when an execution run's worktree is cleaned up
+#[derive(Debug, Clone, Default)]
+pub struct RunCleanupOptions {
+    /// Delete the run's local branch after removing the worktree
+    pub delete_branch: bool,
+    /// Also delete the branch from the remote (opt-in, requires a GitHub token)
+    pub delete_remote_branch: bool,
+    pub github_token: Option<String>,
+}
+
+/// Data needed to clean up an execution run's worktree and branch (doesn't require DB access)
+#[derive(Debug, Clone)]
+pub struct RunCleanupData {
+    pub run_id: Uuid,
+    pub worktree_path: Option<PathBuf>,
+    pub git_repo_path: PathBuf,
+    pub branch: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RunCleanupOutcome {
+    pub worktree_removed: bool,
+    pub local_branch_deleted: bool,
+    pub remote_branch_deleted: bool,
+    /// Every requested branch deletion succeeded (a local branch that was already gone counts)
+    pub branch_cleanup_complete: bool,
+}
+
+/// Remove an execution run's worktree and, optionally, its local/remote branch.
+/// The worktree must be removed first, since git refuses to delete a checked-out branch.
+/// A missing GitHub token for a remote deletion is reported before anything is removed.
+pub async fn cleanup_run_direct(
+    git: &GitService,
+    data: &RunCleanupData,
+    options: &RunCleanupOptions,
+) -> Result<RunCleanupOutcome, ContainerError> {
+    let remote_token = if options.delete_branch && options.delete_remote_branch {
+        Some(
+            options
+                .github_token
+                .as_deref()
+                .ok_or(GitServiceError::TokenUnavailable)?,
+        )
+    } else {
+        None
+    };
+    let mut outcome = RunCleanupOutcome::default();
+
+    if let Some(worktree_path) = &data.worktree_path {
+        WorktreeManager::cleanup_worktree(worktree_path, Some(&data.git_repo_path)).await?;
+        outcome.worktree_removed = true;
+    }
+
+    if !options.delete_branch {
+        return Ok(outcome);
+    }
+
+    outcome.local_branch_deleted = git.delete_local_branch(&data.git_repo_path, &data.branch)?;
+
+    if let Some(token) = remote_token {
+        match git.delete_remote_branch(&data.git_repo_path, &data.branch, token) {
+            Ok(()) => outcome.remote_branch_deleted = true,
+            Err(e) => tracing::warn!(
+                "Failed to delete remote branch {} for execution run {}: {}",
+                data.branch,
+                data.run_id,
+                e
+            ),
+        }
+    }
+    outcome.branch_cleanup_complete = remote_token.is_none() || outcome.remote_branch_deleted;
+
+    Ok(outcome)
+}
+
+/// Stores the coding agent configuration `executor_profile_id` resolves to in `configs`, the
+/// same lookup the executor uses when it spawns. Returns false if the profile doesn't resolve.
+pub async fn snapshot_attempt_profile(
+    pool: &SqlitePool,
+    task_attempt_id: Uuid,
+    executor_profile_id: &ExecutorProfileId,
+    configs: &ExecutorConfigs,
+) -> Result<bool, ContainerError> {
+    let Some(agent) = configs.get_coding_agent(executor_profile_id) else {
+        return Ok(false);
+    };
+    let config = serde_json::to_value(&agent).map_err(|e| ContainerError::Other(e.into()))?;
+    AttemptProfileSnapshot::create(
+        pool,
+        task_attempt_id,
+        &executor_profile_id.to_string(),
+        &config,
+    )
+    .await?;
+    Ok(true)
+}
+
+/// What resuming an execution run found for its latest coding agent process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunResumeState {
+    /// Still running under this server; logs keep streaming live
+    Attached,
+    /// Marked running, but the process did not survive a server restart; now marked failed
+    Orphaned,
+    /// Already finished; logs are served from the database
+    Finished,
+}
+
+/// Reconcile a run's process with whether this server still holds it. A process the database
+/// marks running that isn't `attached` was orphaned by a restart, so it is marked failed and
+/// log streams fall back to the stored logs instead of waiting for output that never comes.
+pub async fn reconcile_run_process(
+    pool: &SqlitePool,
+    process: &ExecutionProcess,
+    attached: bool,
+) -> Result<RunResumeState, ContainerError> {
+    if process.status != ExecutionProcessStatus::Running {
+        return Ok(RunResumeState::Finished);
+    }
+    if attached {
+        return Ok(RunResumeState::Attached);
+    }
+    ExecutionProcess::update_completion(pool, process.id, ExecutionProcessStatus::Failed, None)
+        .await?;
+    Ok(RunResumeState::Orphaned)
+}
+
 #[derive(Debug, Error)]
 pub enum ContainerError {
     #[error(transparent)]
@@ -95,6 +333,8 @@ pub enum ContainerError {
     Worktree(#[from] WorktreeError),
     #[error("Io error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("Cannot restore the worktree: branch {0} no longer exists")]
+    BranchNotFound(String),
     #[error("Failed to kill process: {0}")]
     KillFailed(std::io::Error),
     #[error(transparent)]
@@ -150,6 +390,24 @@ pub trait ContainerService {
         Ok(())
     }
 
+    /// Stop every running execution process, returning how many were stopped
+    async fn stop_running_processes(&self) -> Result<usize, ContainerError> {
+        let processes = ExecutionProcess::find_running(&self.db().pool).await?;
+        let mut stopped = 0;
+        for process in processes {
+            match self
+                .stop_execution(&process, ExecutionProcessStatus::Killed)
+                .await
+            {
+                Ok(()) => stopped += 1,
+                Err(e) => {
+                    tracing::warn!("Failed to stop execution process {}: {}", process.id, e)
+                }
+            }
+        }
+        Ok(stopped)
+    }
+
     fn cleanup_action(&self, cleanup_script: Option<String>) -> Option<Box<ExecutorAction>> {
         cleanup_script.map(|script| {
             Box::new(ExecutorAction::new(
@@ -228,6 +486,11 @@ pub trait ContainerService {
         map.get(uuid).cloned()
     }
 
+    /// Whether this server is still running the execution process and collecting its output
+    async fn is_process_attached(&self, process_id: &Uuid) -> bool {
+        self.get_msg_store_by_id(process_id).await.is_some()
+    }
+
     async fn git_branch_prefix(&self) -> String;
 
     async fn git_branch_from_task_attempt(&self, attempt_id: &Uuid, task_title: &str) -> String {
@@ -330,11 +593,14 @@ pub trait ContainerService {
             // Create temporary store and populate
             // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization)
             let temp_store = Arc::new(MsgStore::new());
+            // Elision markers from log truncation are not executor output, so the normalizers
+            // never see them
             for msg in raw_messages {
                 if matches!(
                     msg,
                     LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
-                ) {
+                ) && !ExecutionProcessLogs::is_elision_marker(&msg)
+                {
                     temp_store.push(msg);
                 }
             }
@@ -438,6 +704,8 @@ pub trait ContainerService {
 
             if let Some(store) = store {
                 let mut stream = store.history_plus_stream();
+                let retention = LogRetentionPolicy::from_env();
+                let mut stored_bytes = 0usize;
 
                 while let Some(Ok(msg)) = stream.next().await {
                     match &msg {
@@ -460,6 +728,28 @@ pub trait ContainerService {
                                             execution_id,
                                             e
                                         );
+                                    } else {
+                                        stored_bytes += jsonl_line_with_newline.len();
+                                    }
+
+                                    // Keep the stored log bounded: cut it back to head + tail
+                                    // once it outgrows the cap
+                                    if stored_bytes > retention.max_bytes {
+                                        match truncate_process_logs(
+                                            &db.pool,
+                                            execution_id,
+                                            retention.truncate_to(),
+                                        )
+                                        .await
+                                        {
+                                            Ok(Some(size)) => stored_bytes = size,
+                                            Ok(None) => {}
+                                            Err(e) => tracing::error!(
+                                                "Failed to truncate logs for execution {}: {}",
+                                                execution_id,
+                                                e
+                                            ),
+                                        }
                                     }
                                 }
                                 Err(e) => {
@@ -532,6 +822,22 @@ pub trait ContainerService {
         );
         let prompt = ImageService::canonicalise_image_paths(&task.to_prompt(), &worktree_path);
 
+        // Pin the resolved profile so the run can be audited after the profile changes
+        if let Err(e) = snapshot_attempt_profile(
+            &self.db().pool,
+            task_attempt.id,
+            &executor_profile_id,
+            &ExecutorConfigs::get_cached(),
+        )
+        .await
+        {
+            tracing::warn!(
+                "Failed to snapshot executor profile for attempt {}: {}",
+                task_attempt.id,
+                e
+            );
+        }
+
         let cleanup_action = self.cleanup_action(project.cleanup_script);
 
         // Choose whether to execute the setup_script or coding agent first
@@ -577,6 +883,96 @@ pub trait ContainerService {
         Ok(execution_process)
     }
 
+    /// Start a freshly created attempt, deleting it again (worktree, new branch and attempt row)
+    /// and putting its task back in the status it had if the start fails, so no
+    /// half-initialised attempt is left behind
+    async fn start_attempt_or_discard(
+        &self,
+        task_attempt: &TaskAttempt,
+        executor_profile_id: ExecutorProfileId,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let task = task_attempt
+            .parent_task(&self.db().pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let repo_path = task
+            .parent_project(&self.db().pool)
+            .await
+            .ok()
+            .flatten()
+            .map(|project| project.git_repo_path);
+        // Forks start on a branch that already exists; only a branch the start creates is ours
+        let branch_existed = repo_path.as_ref().is_none_or(|repo_path| {
+            self.git()
+                .check_branch_exists(repo_path, &task_attempt.branch)
+                .unwrap_or(true)
+        });
+        let err = match self.start_attempt(task_attempt, executor_profile_id).await {
+            Ok(execution_process) => return Ok(execution_process),
+            Err(err) => err,
+        };
+
+        // `create` may have recorded a worktree before the failure
+        let latest = TaskAttempt::find_by_id(&self.db().pool, task_attempt.id)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| task_attempt.clone());
+        if repo_path.is_none() {
+            tracing::warn!(
+                "Could not load the project of discarded attempt {}",
+                task_attempt.id
+            );
+        }
+        // Attempts without a worktree run in the project repository itself, which must stay.
+        // Without the project, only a checkout in the worktree directory is known to be ours.
+        let is_worktree = |container_ref: &Path| match &repo_path {
+            Some(repo_path) => container_ref != repo_path,
+            None => container_ref.starts_with(WorktreeManager::get_worktree_base_dir()),
+        };
+        if let Some(container_ref) = &latest.container_ref
+            && is_worktree(Path::new(container_ref))
+            && let Err(e) = self.delete(&latest).await
+        {
+            tracing::warn!(
+                "Failed to clean up container for discarded attempt {}: {}",
+                task_attempt.id,
+                e
+            );
+        }
+        // Git refuses to delete a checked-out branch, so this follows the worktree removal
+        if !branch_existed
+            && let Some(repo_path) = &repo_path
+            && let Err(e) = self
+                .git()
+                .delete_local_branch(repo_path, &task_attempt.branch)
+        {
+            tracing::warn!(
+                "Failed to delete branch {} of discarded attempt {}: {}",
+                task_attempt.branch,
+                task_attempt.id,
+                e
+            );
+        }
+        if let Err(e) = TaskAttempt::delete(&self.db().pool, task_attempt.id).await {
+            tracing::error!(
+                "Failed to delete discarded attempt {}: {}",
+                task_attempt.id,
+                e
+            );
+        }
+        // The start may have already moved the task to in-progress or in-review
+        if let Err(e) = Task::update_status(&self.db().pool, task.id, task.status).await {
+            tracing::error!(
+                "Failed to restore the status of task {} after discarding attempt {}: {}",
+                task.id,
+                task_attempt.id,
+                e
+            );
+        }
+        Err(err)
+    }
+
     async fn start_execution(
         &self,
         task_attempt: &TaskAttempt,
@@ -1007,6 +1403,66 @@ pub trait ContainerService {
         executor_action: &ExecutorAction,
     ) -> Result<(), ContainerError>;
 
+    /// Remove an execution run's worktree and (optionally) its branch, recording both in the DB.
+    /// The branch is only recorded as deleted once every requested deletion succeeded, and a
+    /// recorded deletion is only skipped when no remote deletion is asked for.
+    async fn cleanup_run(
+        &self,
+        execution_run: &ExecutionRun,
+        options: RunCleanupOptions,
+    ) -> Result<RunCleanupOutcome, ContainerError> {
+        let project = Project::find_by_id(&self.db().pool, execution_run.project_id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+
+        let worktree_path = if execution_run.worktree_deleted {
+            None
+        } else {
+            execution_run.container_ref.as_ref().map(PathBuf::from)
+        };
+        let branch_already_deleted =
+            ExecutionRun::is_branch_deleted(&self.db().pool, execution_run.id).await?;
+
+        // Deleting the local branch again is a no-op, so a run whose branch was recorded as
+        // deleted is only revisited to delete the remote branch
+        let options = RunCleanupOptions {
+            delete_branch: options.delete_branch
+                && (!branch_already_deleted || options.delete_remote_branch),
+            ..options
+        };
+
+        let data = RunCleanupData {
+            run_id: execution_run.id,
+            worktree_path,
+            git_repo_path: project.git_repo_path,
+            branch: execution_run.branch.clone(),
+        };
+
+        let outcome = match cleanup_run_direct(self.git(), &data, &options).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                // The worktree may be gone even though a later step failed
+                if data
+                    .worktree_path
+                    .as_ref()
+                    .is_some_and(|path| !path.exists())
+                {
+                    ExecutionRun::mark_worktree_deleted(&self.db().pool, execution_run.id).await?;
+                }
+                return Err(e);
+            }
+        };
+
+        if outcome.worktree_removed {
+            ExecutionRun::mark_worktree_deleted(&self.db().pool, execution_run.id).await?;
+        }
+        if options.delete_branch && outcome.branch_cleanup_complete {
+            ExecutionRun::mark_branch_deleted(&self.db().pool, execution_run.id).await?;
+        }
+
+        Ok(outcome)
+    }
+
     /// Stream raw logs for an execution run
     async fn stream_raw_logs_for_run(
         &self,
@@ -1024,4 +1480,21 @@ pub trait ContainerService {
 
         self.stream_raw_logs(&process.id).await
     }
+
+    /// Stream normalized conversation entries for an execution run
+    async fn stream_normalized_logs_for_run(
+        &self,
+        execution_run_id: &Uuid,
+    ) -> Option<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>> {
+        let process = ExecutionProcess::find_latest_by_execution_run_and_run_reason(
+            &self.db().pool,
+            *execution_run_id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await
+        .ok()
+        .flatten()?;
+
+        self.stream_normalized_logs(&process.id).await
+    }
 }
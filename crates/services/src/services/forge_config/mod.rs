@@ -3,14 +3,19 @@
 //! This module contains forge-specific configuration functionality.
 //! For Task 2, this focuses on project-level config management and Omni integration.
 
+pub mod secret;
 pub mod service;
 pub mod types;
 
 // Re-export Omni config for compatibility
+pub use secret::SecretCipher;
 pub use service::ForgeConfigService;
 pub use types::*;
 
-pub use super::omni::{OmniConfig, RecipientType};
+pub use super::{
+    omni::{OmniConfig, RecipientType},
+    webhook::WebhookConfig,
+};
 // Re-export upstream config primitives so downstream code can switch to forge-config without churn
 pub use crate::services::config::{
     Config, ConfigError, EditorConfig, EditorType, GitHubConfig, NotificationConfig, SoundFile,
@@ -0,0 +1,173 @@
+//! At-rest encryption for secrets kept in forge settings (currently the Omni `api_key`).
+//!
+//! Values are sealed with AES-256-GCM. The key is derived from `FORGE_MASTER_KEY` when set,
+//! otherwise from a random secret file created next to the database on first use.
+
+use std::{fs, io::Write, path::Path};
+
+use anyhow::{Context, Result, anyhow};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use forge_core_utils::assets::asset_dir;
+use ring::{
+    aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey},
+    rand::{SecureRandom, SystemRandom},
+};
+use sha2::{Digest, Sha256};
+
+const MASTER_KEY_ENV: &str = "FORGE_MASTER_KEY";
+const SECRET_FILE_NAME: &str = "forge_secret.key";
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+#[derive(Clone)]
+pub struct SecretCipher {
+    key: [u8; 32],
+}
+
+impl std::fmt::Debug for SecretCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretCipher").finish_non_exhaustive()
+    }
+}
+
+impl SecretCipher {
+    /// Load the cipher from `FORGE_MASTER_KEY`, falling back to the local secret file
+    pub fn load() -> Result<Self> {
+        if let Ok(master_key) = std::env::var(MASTER_KEY_ENV)
+            && !master_key.is_empty()
+        {
+            return Ok(Self::from_key_material(master_key.as_bytes()));
+        }
+        Self::from_secret_file(&asset_dir().join(SECRET_FILE_NAME))
+    }
+
+    /// Read the secret file at `path`, creating it with fresh random bytes if it is missing
+    pub fn from_secret_file(path: &Path) -> Result<Self> {
+        if path.exists() {
+            let material = fs::read(path)
+                .with_context(|| format!("failed to read secret file {}", path.display()))?;
+            return Ok(Self::from_key_material(&material));
+        }
+
+        let mut material = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut material)
+            .map_err(|_| anyhow!("failed to generate secret key"))?;
+        let encoded = STANDARD.encode(material);
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(path)
+            .with_context(|| format!("failed to create secret file {}", path.display()))?;
+        file.write_all(encoded.as_bytes())?;
+
+        Ok(Self::from_key_material(encoded.as_bytes()))
+    }
+
+    pub fn from_key_material(material: &[u8]) -> Self {
+        Self {
+            key: Sha256::digest(material).into(),
+        }
+    }
+
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(ENCRYPTED_PREFIX)
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| anyhow!("failed to generate nonce"))?;
+
+        let mut in_out = plaintext.as_bytes().to_vec();
+        self.sealing_key()?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| anyhow!("failed to encrypt secret"))?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&in_out);
+        Ok(format!("{ENCRYPTED_PREFIX}{}", STANDARD.encode(payload)))
+    }
+
+    /// Decrypt a stored value. Values without the encryption prefix predate at-rest encryption
+    /// and are returned unchanged.
+    pub fn decrypt(&self, stored: &str) -> Result<String> {
+        let Some(encoded) = stored.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+
+        let payload = STANDARD
+            .decode(encoded)
+            .context("encrypted secret is not valid base64")?;
+        if payload.len() < NONCE_LEN {
+            return Err(anyhow!("encrypted secret is truncated"));
+        }
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| anyhow!("encrypted secret has an invalid nonce"))?;
+
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = self
+            .sealing_key()?
+            .open_in_place(nonce, Aad::empty(), &mut in_out)
+            .map_err(|_| anyhow!("failed to decrypt secret; was the master key changed?"))?;
+
+        Ok(String::from_utf8(plaintext.to_vec())?)
+    }
+
+    fn sealing_key(&self) -> Result<LessSafeKey> {
+        let key = UnboundKey::new(&AES_256_GCM, &self.key)
+            .map_err(|_| anyhow!("invalid secret key length"))?;
+        Ok(LessSafeKey::new(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypts_and_decrypts_round_trip() {
+        let cipher = SecretCipher::from_key_material(b"test-master-key");
+
+        let sealed = cipher.encrypt("omni-api-key").unwrap();
+        assert!(SecretCipher::is_encrypted(&sealed));
+        assert!(!sealed.contains("omni-api-key"));
+        assert_ne!(sealed, cipher.encrypt("omni-api-key").unwrap());
+        assert_eq!(cipher.decrypt(&sealed).unwrap(), "omni-api-key");
+
+        // Plaintext written before encryption was introduced passes through
+        assert_eq!(cipher.decrypt("legacy-key").unwrap(), "legacy-key");
+
+        let other = SecretCipher::from_key_material(b"another-key");
+        assert!(other.decrypt(&sealed).is_err());
+    }
+
+    #[test]
+    fn secret_file_is_created_once_and_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SECRET_FILE_NAME);
+
+        let first = SecretCipher::from_secret_file(&path).unwrap();
+        let sealed = first.encrypt("value").unwrap();
+        let second = SecretCipher::from_secret_file(&path).unwrap();
+        assert_eq!(second.decrypt(&sealed).unwrap(), "value");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
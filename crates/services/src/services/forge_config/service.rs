@@ -1,20 +1,25 @@
 use anyhow::Result;
+use forge_core_executors::executors::BaseCodingAgent;
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
-use super::types::{ForgeProjectSettings, ProjectConfig};
-use crate::services::omni::OmniConfig;
+use super::{
+    secret::SecretCipher,
+    types::{ForgeProjectSettings, ProjectConfig},
+};
+use crate::services::{omni::OmniConfig, webhook::WebhookConfig};
 
 #[derive(Clone)]
 pub struct ForgeConfigService {
     pool: SqlitePool,
+    cipher: SecretCipher,
 }
 
 impl ForgeConfigService {
     pub const GLOBAL_PROJECT_ID: Uuid = Uuid::nil();
 
-    pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+    pub fn new(pool: SqlitePool, cipher: SecretCipher) -> Self {
+        Self { pool, cipher }
     }
 
     pub async fn get_project_config(&self, project_id: Uuid) -> Result<Option<ProjectConfig>> {
@@ -73,7 +78,10 @@ impl ForgeConfigService {
             && let Some(forge_config) = config.forge_config
             && let Ok(settings) = serde_json::from_value::<ForgeProjectSettings>(forge_config)
         {
-            return Ok(settings);
+            if self.has_plaintext_secret(&settings) {
+                self.set_forge_settings(project_id, &settings).await?;
+            }
+            return Ok(self.decrypt_secrets(settings));
         }
 
         Ok(ForgeProjectSettings::default())
@@ -84,7 +92,7 @@ impl ForgeConfigService {
         project_id: Uuid,
         settings: &ForgeProjectSettings,
     ) -> Result<()> {
-        let forge_config_value = serde_json::to_value(settings)?;
+        let forge_config_value = serde_json::to_value(self.encrypt_secrets(settings)?)?;
 
         // Get existing config or create new one
         let mut config = self
@@ -101,6 +109,74 @@ impl ForgeConfigService {
         self.set_project_config(&config).await
     }
 
+    /// Whether safe executor mode applies to a project: its own setting wins, then the global
+    /// one, and safe mode is off when neither is set
+    pub async fn safe_executor_mode(&self, project_id: Uuid) -> Result<bool> {
+        if let Some(enabled) = self.get_forge_settings(project_id).await?.safe_executor_mode {
+            return Ok(enabled);
+        }
+        Ok(self
+            .get_global_settings()
+            .await?
+            .safe_executor_mode
+            .unwrap_or(false))
+    }
+
+    /// Whether opening a PR moves a task of `project_id` to in-review: its own setting wins,
+    /// then the global one, and the transition is on when neither is set
+    pub async fn review_on_pr(&self, project_id: Uuid) -> Result<bool> {
+        if let Some(enabled) = self.get_forge_settings(project_id).await?.review_on_pr {
+            return Ok(enabled);
+        }
+        Ok(self
+            .get_global_settings()
+            .await?
+            .review_on_pr
+            .unwrap_or(true))
+    }
+
+    /// Conversational prefixes configured globally plus those configured for `project_id`
+    pub async fn conversational_prefixes(&self, project_id: Uuid) -> Result<Vec<String>> {
+        let mut prefixes = self.get_global_settings().await?.conversational_prefixes;
+        for prefix in self
+            .get_forge_settings(project_id)
+            .await?
+            .conversational_prefixes
+        {
+            if !prefixes.contains(&prefix) {
+                prefixes.push(prefix);
+            }
+        }
+        Ok(prefixes)
+    }
+
+    /// Executors a project may start attempts with, read from its `custom_executors` list.
+    /// `None` means no allow-list is configured and every executor is permitted.
+    pub async fn allowed_executors(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Option<Vec<BaseCodingAgent>>> {
+        let Some(custom_executors) = self
+            .get_project_config(project_id)
+            .await?
+            .and_then(|config| config.custom_executors)
+        else {
+            return Ok(None);
+        };
+
+        match serde_json::from_value::<Vec<BaseCodingAgent>>(custom_executors) {
+            Ok(allowed) => Ok(Some(allowed)),
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring unreadable custom_executors for project {}: {}",
+                    project_id,
+                    e
+                );
+                Ok(None)
+            }
+        }
+    }
+
     pub async fn get_global_settings(&self) -> Result<ForgeProjectSettings> {
         // Read from forge_global_settings table
         let row: Option<(String,)> =
@@ -111,7 +187,10 @@ impl ForgeConfigService {
         if let Some((config_str,)) = row
             && let Ok(settings) = serde_json::from_str::<ForgeProjectSettings>(&config_str)
         {
-            return Ok(settings);
+            if self.has_plaintext_secret(&settings) {
+                self.set_global_settings(&settings).await?;
+            }
+            return Ok(self.decrypt_secrets(settings));
         }
 
         Ok(ForgeProjectSettings::default())
@@ -119,7 +198,7 @@ impl ForgeConfigService {
 
     pub async fn set_global_settings(&self, settings: &ForgeProjectSettings) -> Result<()> {
         // Write to forge_global_settings table
-        let config_json = serde_json::to_string(settings)?;
+        let config_json = serde_json::to_string(&self.encrypt_secrets(settings)?)?;
 
         sqlx::query(
             "INSERT INTO forge_global_settings (id, forge_config) VALUES (1, ?)
@@ -142,6 +221,7 @@ impl ForgeConfigService {
             && let Some(value) = project_config.forge_config.clone()
             && let Ok(project_settings) = serde_json::from_value::<ForgeProjectSettings>(value)
         {
+            let project_settings = self.decrypt_secrets(project_settings);
             let mut project_omni = project_settings
                 .omni_config
                 .unwrap_or_else(|| config.clone());
@@ -151,6 +231,75 @@ impl ForgeConfigService {
 
         Ok(config)
     }
+
+    /// The webhook task notifications are POSTed to: the project's own, else the global one
+    pub async fn effective_webhook_config(
+        &self,
+        project_id: Option<Uuid>,
+    ) -> Result<Option<WebhookConfig>> {
+        if let Some(project_id) = project_id
+            && let Some(webhook) = self.get_forge_settings(project_id).await?.webhook
+        {
+            return Ok(Some(webhook));
+        }
+        Ok(self.get_global_settings().await?.webhook)
+    }
+
+    /// Seal the Omni api_key and webhook secret before they are written; the rest of the
+    /// settings stay readable JSON
+    fn encrypt_secrets(&self, settings: &ForgeProjectSettings) -> Result<ForgeProjectSettings> {
+        let mut settings = settings.clone();
+        if let Some(omni) = settings.omni_config.as_mut()
+            && let Some(api_key) = omni.api_key.as_mut()
+            && !SecretCipher::is_encrypted(api_key)
+        {
+            *api_key = self.cipher.encrypt(api_key)?;
+        }
+        if let Some(webhook) = settings.webhook.as_mut()
+            && !SecretCipher::is_encrypted(&webhook.secret)
+        {
+            webhook.secret = self.cipher.encrypt(&webhook.secret)?;
+        }
+        Ok(settings)
+    }
+
+    /// Open stored secrets. An api_key that no longer decrypts (e.g. the master key changed) is
+    /// dropped so the rest of the settings still load and the user can enter it again; a
+    /// webhook whose secret can't be read is dropped as a whole rather than sent unsigned.
+    fn decrypt_secrets(&self, mut settings: ForgeProjectSettings) -> ForgeProjectSettings {
+        if let Some(omni) = settings.omni_config.as_mut()
+            && let Some(stored) = omni.api_key.take()
+        {
+            match self.cipher.decrypt(&stored) {
+                Ok(api_key) => omni.api_key = Some(api_key),
+                Err(e) => tracing::warn!("Discarding unreadable Omni api_key: {e}"),
+            }
+        }
+        if let Some(mut webhook) = settings.webhook.take() {
+            match self.cipher.decrypt(&webhook.secret) {
+                Ok(secret) => {
+                    webhook.secret = secret;
+                    settings.webhook = Some(webhook);
+                }
+                Err(e) => tracing::warn!("Discarding webhook with unreadable secret: {e}"),
+            }
+        }
+        settings
+    }
+
+    /// Settings written before at-rest encryption hold their secrets in plaintext
+    fn has_plaintext_secret(&self, settings: &ForgeProjectSettings) -> bool {
+        let plaintext_api_key = settings
+            .omni_config
+            .as_ref()
+            .and_then(|omni| omni.api_key.as_deref())
+            .is_some_and(|api_key| !SecretCipher::is_encrypted(api_key));
+        let plaintext_webhook_secret = settings
+            .webhook
+            .as_ref()
+            .is_some_and(|webhook| !SecretCipher::is_encrypted(&webhook.secret));
+        plaintext_api_key || plaintext_webhook_secret
+    }
 }
 
 // Helper struct for database queries
@@ -166,6 +315,17 @@ mod tests {
     use super::*;
     use crate::services::omni::{OmniConfig, RecipientType};
 
+    fn test_cipher() -> SecretCipher {
+        SecretCipher::from_key_material(b"forge-config-test-key")
+    }
+
+    async fn stored_global_config(pool: &SqlitePool) -> String {
+        sqlx::query_scalar("SELECT forge_config FROM forge_global_settings WHERE id = 1")
+            .fetch_one(pool)
+            .await
+            .expect("should read stored global settings")
+    }
+
     async fn setup_pool() -> SqlitePool {
         let pool = SqlitePool::connect("sqlite::memory:")
             .await
@@ -208,7 +368,7 @@ mod tests {
     #[tokio::test]
     async fn round_trips_global_settings() {
         let pool = setup_pool().await;
-        let service = ForgeConfigService::new(pool);
+        let service = ForgeConfigService::new(pool, test_cipher());
 
         // defaults
         let mut settings = service
@@ -226,6 +386,8 @@ mod tests {
             instance: Some("forge".into()),
             recipient: Some("+14155552671".into()),
             recipient_type: Some(RecipientType::PhoneNumber),
+            throttle_window_secs: None,
+            events: Default::default(),
         });
 
         service
@@ -248,7 +410,7 @@ mod tests {
     #[tokio::test]
     async fn project_overrides_effective_omni_config() {
         let pool = setup_pool().await;
-        let service = ForgeConfigService::new(pool);
+        let service = ForgeConfigService::new(pool, test_cipher());
 
         let project_id = Uuid::new_v4();
 
@@ -261,7 +423,16 @@ mod tests {
                 instance: Some("global".into()),
                 recipient: Some("global-recipient".into()),
                 recipient_type: Some(RecipientType::PhoneNumber),
+                throttle_window_secs: None,
+                events: Default::default(),
             }),
+            webhook: None,
+            safe_executor_mode: None,
+            review_on_pr: None,
+            sign_commits: false,
+            signing_key: None,
+            releases_repo: None,
+            conversational_prefixes: Vec::new(),
         };
         service
             .set_global_settings(&global)
@@ -277,7 +448,16 @@ mod tests {
                 instance: Some("project".into()),
                 recipient: Some("project-recipient".into()),
                 recipient_type: Some(RecipientType::UserId),
+                throttle_window_secs: None,
+                events: Default::default(),
             }),
+            webhook: None,
+            safe_executor_mode: None,
+            review_on_pr: None,
+            sign_commits: false,
+            signing_key: None,
+            releases_repo: None,
+            conversational_prefixes: Vec::new(),
         };
         service
             .set_forge_settings(project_id, &project)
@@ -296,6 +476,239 @@ mod tests {
         assert!(matches!(config.recipient_type, Some(RecipientType::UserId)));
     }
 
+    #[tokio::test]
+    async fn project_overrides_global_safe_executor_mode() {
+        let pool = setup_pool().await;
+        let service = ForgeConfigService::new(pool, test_cipher());
+
+        let trusted_project = Uuid::new_v4();
+        let other_project = Uuid::new_v4();
+
+        assert!(!service.safe_executor_mode(other_project).await.unwrap());
+
+        service
+            .set_global_settings(&ForgeProjectSettings {
+                safe_executor_mode: Some(true),
+                ..Default::default()
+            })
+            .await
+            .expect("global settings should persist");
+        service
+            .set_forge_settings(
+                trusted_project,
+                &ForgeProjectSettings {
+                    safe_executor_mode: Some(false),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("project settings should persist");
+
+        assert!(service.safe_executor_mode(other_project).await.unwrap());
+        assert!(!service.safe_executor_mode(trusted_project).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn conversational_prefixes_combine_global_and_project_lists() {
+        let pool = setup_pool().await;
+        let service = ForgeConfigService::new(pool, test_cipher());
+        let project_id = Uuid::new_v4();
+
+        service
+            .set_global_settings(&ForgeProjectSettings {
+                conversational_prefixes: vec!["Claro, ".into(), "Vou ".into()],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        service
+            .set_forge_settings(
+                project_id,
+                &ForgeProjectSettings {
+                    conversational_prefixes: vec!["Vou ".into(), "Listo! ".into()],
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            service.conversational_prefixes(project_id).await.unwrap(),
+            ["Claro, ", "Vou ", "Listo! "]
+        );
+        assert_eq!(
+            service
+                .conversational_prefixes(Uuid::new_v4())
+                .await
+                .unwrap(),
+            ["Claro, ", "Vou "]
+        );
+    }
+
+    #[tokio::test]
+    async fn allowed_executors_reads_project_allow_list() {
+        let pool = setup_pool().await;
+        let service = ForgeConfigService::new(pool, test_cipher());
+        let restricted = Uuid::new_v4();
+        let unrestricted = Uuid::new_v4();
+
+        service
+            .set_project_config(&ProjectConfig {
+                project_id: restricted,
+                custom_executors: Some(serde_json::json!(["CLAUDE_CODE", "CODEX"])),
+                forge_config: None,
+            })
+            .await
+            .unwrap();
+
+        let allowed = service
+            .allowed_executors(restricted)
+            .await
+            .unwrap()
+            .expect("allow-list should be configured");
+        assert!(allowed.contains(&BaseCodingAgent::ClaudeCode));
+        assert!(allowed.contains(&BaseCodingAgent::Codex));
+        assert!(!allowed.contains(&BaseCodingAgent::Gemini));
+
+        assert!(
+            service
+                .allowed_executors(unrestricted)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn api_key_is_encrypted_at_rest() {
+        let pool = setup_pool().await;
+        let service = ForgeConfigService::new(pool.clone(), test_cipher());
+        let project_id = Uuid::new_v4();
+
+        let settings = ForgeProjectSettings {
+            omni_enabled: true,
+            omni_config: Some(OmniConfig {
+                api_key: Some("super-secret-key".into()),
+                host: Some("https://omni.test".into()),
+                ..Default::default()
+            }),
+            webhook: None,
+            safe_executor_mode: None,
+            review_on_pr: None,
+            sign_commits: false,
+            signing_key: None,
+            releases_repo: None,
+            conversational_prefixes: Vec::new(),
+        };
+        service.set_global_settings(&settings).await.unwrap();
+        service
+            .set_forge_settings(project_id, &settings)
+            .await
+            .unwrap();
+
+        let global_row = stored_global_config(&pool).await;
+        let project_row: String = sqlx::query_scalar(
+            "SELECT forge_config FROM forge_project_settings WHERE project_id = ?",
+        )
+        .bind(project_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        for row in [&global_row, &project_row] {
+            assert!(!row.contains("super-secret-key"));
+            assert!(row.contains("https://omni.test"));
+        }
+
+        let global = service.get_global_settings().await.unwrap();
+        let project = service.get_forge_settings(project_id).await.unwrap();
+        for loaded in [global, project] {
+            assert_eq!(
+                loaded.omni_config.unwrap().api_key.as_deref(),
+                Some("super-secret-key")
+            );
+        }
+        let effective = service
+            .effective_omni_config(Some(project_id))
+            .await
+            .unwrap();
+        assert_eq!(effective.api_key.as_deref(), Some("super-secret-key"));
+    }
+
+    #[tokio::test]
+    async fn webhook_secret_is_encrypted_and_project_webhook_wins() {
+        let pool = setup_pool().await;
+        let service = ForgeConfigService::new(pool.clone(), test_cipher());
+        let project_id = Uuid::new_v4();
+        let webhook = |url: &str, secret: &str| WebhookConfig {
+            url: url.into(),
+            secret: secret.into(),
+            events: Default::default(),
+        };
+
+        service
+            .set_global_settings(&ForgeProjectSettings {
+                webhook: Some(webhook("https://hooks.test/global", "global-secret")),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        service
+            .set_forge_settings(
+                project_id,
+                &ForgeProjectSettings {
+                    webhook: Some(webhook("https://hooks.test/project", "project-secret")),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let global_row = stored_global_config(&pool).await;
+        assert!(!global_row.contains("global-secret"));
+        assert!(global_row.contains("https://hooks.test/global"));
+
+        let project = service
+            .effective_webhook_config(Some(project_id))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(project.url, "https://hooks.test/project");
+        assert_eq!(project.secret, "project-secret");
+
+        let global = service
+            .effective_webhook_config(Some(Uuid::new_v4()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(global.url, "https://hooks.test/global");
+        assert_eq!(global.secret, "global-secret");
+    }
+
+    #[tokio::test]
+    async fn plaintext_api_key_is_migrated_on_first_read() {
+        let pool = setup_pool().await;
+        sqlx::query("UPDATE forge_global_settings SET forge_config = ? WHERE id = 1")
+            .bind(r#"{"omni_enabled":true,"omni_config":{"enabled":true,"api_key":"legacy-key"}}"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let service = ForgeConfigService::new(pool.clone(), test_cipher());
+
+        let settings = service.get_global_settings().await.unwrap();
+        assert_eq!(
+            settings.omni_config.unwrap().api_key.as_deref(),
+            Some("legacy-key")
+        );
+
+        let stored = stored_global_config(&pool).await;
+        assert!(!stored.contains("legacy-key"));
+        let reloaded = service.get_global_settings().await.unwrap();
+        assert_eq!(
+            reloaded.omni_config.unwrap().api_key.as_deref(),
+            Some("legacy-key")
+        );
+    }
+
     #[tokio::test]
     async fn forge_global_settings_singleton_constraint() {
         let pool = setup_pool().await;
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use ts_rs_forge::TS;
 use uuid::Uuid;
 
-use crate::services::omni::OmniConfig;
+use crate::services::{omni::OmniConfig, webhook::WebhookConfig};
 
 /// Project-level configuration stored in auxiliary tables
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -21,4 +21,31 @@ pub struct ForgeProjectSettings {
     pub omni_enabled: bool,
     #[serde(default)]
     pub omni_config: Option<OmniConfig>,
+    /// Signed JSON POSTs for the same task events Omni notifies on
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
+    /// Spawn coding agents without permission-bypassing settings (yolo,
+    /// dangerously_skip_permissions, ...), whichever profile or additional_params set them. Set
+    /// globally to turn safe mode on; set per project to override the global value.
+    #[serde(default)]
+    pub safe_executor_mode: Option<bool>,
+    /// Move an in-progress task to in-review when a PR is opened for one of its attempts. Set
+    /// globally or per project (the project wins); on when neither is set.
+    #[serde(default)]
+    pub review_on_pr: Option<bool>,
+    /// Sign this project's merge commits, as GPG or SSH signatures following git's `gpg.format`
+    #[serde(default)]
+    pub sign_commits: bool,
+    /// Key passed to `git commit -S`; git's own `user.signingkey` is used when unset
+    #[serde(default)]
+    pub signing_key: Option<String>,
+    /// `owner/repo` whose GitHub releases feed the changelog. Global only; defaults to
+    /// automagik-dev/automagik-forge.
+    #[serde(default)]
+    pub releases_repo: Option<String>,
+    /// Extra conversational openers (e.g. "Claro, vou ") stripped from generated commit titles
+    /// and flagged by the commit validator, on top of the built-in English ones. Global and
+    /// project lists are combined.
+    #[serde(default)]
+    pub conversational_prefixes: Vec<String>,
 }
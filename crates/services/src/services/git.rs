@@ -1,6 +1,7 @@
 use std::{collections::HashMap, path::Path};
 
 use chrono::{DateTime, Utc};
+use forge_core_db::models::merge::Merge;
 use forge_core_utils::diff::{Diff, DiffChangeKind, FileDiffDetails};
 use git2::{
     BranchType, Delta, DiffFindOptions, DiffOptions, Error as GitError, Reference, Remote,
@@ -12,7 +13,9 @@ use ts_rs_forge::TS;
 
 // Import for file ranking functionality
 use super::file_ranker::FileStat;
-use super::git_cli::{ChangeType, GitCli, GitCliError, StatusDiffEntry, StatusDiffOptions};
+use super::git_cli::{
+    ChangeType, CommitSigning, GitCli, GitCliError, PushKind, StatusDiffEntry, StatusDiffOptions,
+};
 use crate::services::github_service::GitHubRepoInfo;
 
 #[derive(Debug, Error)]
@@ -37,7 +40,27 @@ pub enum GitServiceError {
     TokenUnavailable,
     #[error("Rebase in progress; resolve or abort it before retrying")]
     RebaseInProgress,
+    #[error("Refusing to delete branch {0}: {1}")]
+    ProtectedBranch(String, String),
+    #[error("Commit signing is enabled but no signing key is configured")]
+    SigningKeyMissing,
+}
+
+impl GitServiceError {
+    /// Whether the remote rejected our credentials, or none were available for it
+    pub fn is_auth_failure(&self) -> bool {
+        match self {
+            GitServiceError::Git(e) => {
+                e.code() == git2::ErrorCode::Auth
+                    || (matches!(e.class(), git2::ErrorClass::Http | git2::ErrorClass::Ssh)
+                        && e.message().to_ascii_lowercase().contains("auth"))
+            }
+            GitServiceError::TokenUnavailable => true,
+            _ => false,
+        }
+    }
 }
+
 /// Service for managing Git operations in task execution workflows
 #[derive(Clone)]
 pub struct GitService {}
@@ -56,13 +79,75 @@ pub enum ConflictOp {
     Revert,
 }
 
-#[derive(Debug, Serialize, TS)]
+/// Ahead/behind, worktree and conflict state of a branch relative to its target.
+///
+/// Returned by both the task attempt and the project branch-status endpoints, and
+/// deserialized by the MCP client, so all three agree on a single wire format.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BranchStatus {
+    pub commits_behind: Option<usize>,
+    pub commits_ahead: Option<usize>,
+    pub has_uncommitted_changes: Option<bool>,
+    pub head_oid: Option<String>,
+    pub uncommitted_count: Option<usize>,
+    pub untracked_count: Option<usize>,
+    pub target_branch_name: String,
+    pub remote_commits_behind: Option<usize>,
+    pub remote_commits_ahead: Option<usize>,
+    pub merges: Vec<Merge>,
+    /// True if a `git rebase` is currently in progress in this worktree
+    pub is_rebase_in_progress: bool,
+    /// Current conflict operation if any
+    pub conflict_op: Option<ConflictOp>,
+    /// List of files currently in conflicted (unmerged) state
+    pub conflicted_files: Vec<String>,
+    /// When the project's remote was last fetched successfully; only reported for projects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub last_fetch_at: Option<DateTime<Utc>>,
+}
+
+/// Where a pushed branch ended up on the remote
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct PushResult {
+    /// Commit the remote branch points to after the push
+    pub remote_sha: String,
+    /// False when a forced push rewrote the remote branch's history
+    pub fast_forward: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct GitBranch {
     pub name: String,
     pub is_current: bool,
     pub is_remote: bool,
     #[ts(type = "Date")]
     pub last_commit_date: DateTime<Utc>,
+    /// Commits on this branch that the default branch doesn't have; set by
+    /// `annotate_branch_sync`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub ahead: Option<usize>,
+    /// Commits on the default branch that this branch doesn't have
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub behind: Option<usize>,
+    /// Every commit on this branch is already on the default branch. Squash-merged branches
+    /// keep their own commits, so they still count as unmerged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub is_merged: Option<bool>,
+}
+
+/// A commit on an attempt branch that its target branch doesn't have
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BranchCommit {
+    pub sha: String,
+    pub subject: String,
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    #[ts(type = "Date")]
+    pub committed_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone)]
@@ -171,6 +256,26 @@ impl GitService {
         Ok(())
     }
 
+    /// Resolve how merge commits in this repository should be signed. An explicit key wins;
+    /// otherwise git's own `user.signingkey` must be set, so signing never silently falls back
+    /// to unsigned commits.
+    pub fn commit_signing(
+        &self,
+        repo_path: &Path,
+        signing_key: Option<&str>,
+    ) -> Result<CommitSigning, GitServiceError> {
+        if let Some(key) = signing_key.map(str::trim).filter(|key| !key.is_empty()) {
+            return Ok(CommitSigning {
+                key: Some(key.to_string()),
+            });
+        }
+        let repo = self.open_repo(repo_path)?;
+        match repo.config()?.get_string("user.signingkey") {
+            Ok(key) if !key.trim().is_empty() => Ok(CommitSigning { key: None }),
+            _ => Err(GitServiceError::SigningKeyMissing),
+        }
+    }
+
     /// Get a signature for libgit2 commits with a safe fallback identity.
     fn signature_with_fallback<'a>(
         &self,
@@ -760,7 +865,8 @@ impl GitService {
         Ok(None)
     }
 
-    /// Merge changes from a task branch into the base branch.
+    /// Merge changes from a task branch into the base branch. With `signing`, the squash
+    /// commit is created by the git CLI so it carries a signature.
     pub fn merge_changes(
         &self,
         base_worktree_path: &Path,
@@ -768,6 +874,7 @@ impl GitService {
         task_branch_name: &str,
         base_branch_name: &str,
         commit_message: &str,
+        signing: Option<&CommitSigning>,
     ) -> Result<String, GitServiceError> {
         // Open the repositories
         let task_repo = self.open_repo(task_worktree_path)?;
@@ -811,6 +918,7 @@ impl GitService {
                         base_branch_name,
                         task_branch_name,
                         commit_message,
+                        signing,
                     )
                     .map_err(|e| {
                         GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
@@ -844,6 +952,7 @@ impl GitService {
                     &signature,
                     commit_message,
                     base_branch_name,
+                    signing,
                 )?;
 
                 // Update the task branch to the new squash commit so follow-up
@@ -1082,6 +1191,52 @@ impl GitService {
         Ok((ahead, behind))
     }
 
+    /// Commits on `branch_name` that `base_branch_name` doesn't have, newest first, skipping
+    /// `offset` and returning at most `limit`. Also returns how many such commits there are.
+    pub fn branch_commits(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        base_branch_name: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<BranchCommit>, usize), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let tip = Self::find_branch(&repo, branch_name)?
+            .get()
+            .peel_to_commit()?
+            .id();
+        let base = Self::find_branch(&repo, base_branch_name)?
+            .get()
+            .peel_to_commit()?
+            .id();
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(tip)?;
+        revwalk.hide(base)?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        let oids = revwalk.collect::<Result<Vec<_>, _>>()?;
+
+        let commits = oids
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|oid| {
+                let commit = repo.find_commit(*oid)?;
+                let author = commit.author();
+                Ok(BranchCommit {
+                    sha: oid.to_string(),
+                    subject: commit.summary().unwrap_or("(no subject)").to_string(),
+                    author_name: author.name().map(|s| s.to_string()),
+                    author_email: author.email().map(|s| s.to_string()),
+                    committed_at: DateTime::from_timestamp(commit.time().seconds(), 0)
+                        .unwrap_or_else(Utc::now),
+                })
+            })
+            .collect::<Result<Vec<_>, GitServiceError>>()?;
+        Ok((commits, oids.len()))
+    }
+
     /// Return (uncommitted_tracked_changes, untracked_files) counts in worktree
     pub fn get_worktree_change_counts(
         &self,
@@ -1203,6 +1358,44 @@ impl GitService {
         Ok(())
     }
 
+    /// Resolve the commit a new branch forked from `branch_name` should start at: the branch tip,
+    /// or `from_commit` when given, which must be the tip or one of its ancestors.
+    pub fn fork_point(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        from_commit: Option<&str>,
+    ) -> Result<String, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let tip = Self::find_branch(&repo, branch_name)?
+            .into_reference()
+            .peel_to_commit()?;
+        let Some(from_commit) = from_commit else {
+            return Ok(tip.id().to_string());
+        };
+
+        let commit = repo.revparse_single(from_commit)?.peel_to_commit()?;
+        if commit.id() != tip.id() && !repo.graph_descendant_of(tip.id(), commit.id())? {
+            return Err(GitServiceError::InvalidRepository(format!(
+                "{from_commit} is not a commit of branch {branch_name}"
+            )));
+        }
+        Ok(commit.id().to_string())
+    }
+
+    /// Create a local branch at `commit`, failing if the branch already exists
+    pub fn create_branch_at(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        commit: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let commit = repo.find_commit(git2::Oid::from_str(commit)?)?;
+        repo.branch(branch_name, &commit, false)?;
+        Ok(())
+    }
+
     /// Checkout a local branch in the given working tree
     pub fn checkout_branch(
         &self,
@@ -1295,6 +1488,9 @@ impl GitService {
                     is_current: name == current_branch,
                     is_remote: false,
                     last_commit_date,
+                    ahead: None,
+                    behind: None,
+                    is_merged: None,
                 });
             }
         }
@@ -1312,6 +1508,9 @@ impl GitService {
                         is_current: false,
                         is_remote: true,
                         last_commit_date,
+                        ahead: None,
+                        behind: None,
+                        is_merged: None,
                     });
                 }
             }
@@ -1332,6 +1531,44 @@ impl GitService {
         Ok(branches)
     }
 
+    /// Fill in ahead/behind and merged state for each branch, compared with the repository's
+    /// default branch (local, else its remote-tracking branch). Branches that can't be
+    /// compared are left without counts.
+    pub fn annotate_branch_sync(
+        &self,
+        repo_path: &Path,
+        branches: &mut [GitBranch],
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let default_branch = self.get_default_branch_name(repo_path)?;
+        let remote = self.default_remote_name(&repo);
+        let Some(base) = [
+            format!("refs/heads/{default_branch}"),
+            format!("refs/remotes/{remote}/{default_branch}"),
+        ]
+        .iter()
+        .find_map(|refname| repo.refname_to_id(refname).ok()) else {
+            return Ok(());
+        };
+
+        for branch in branches {
+            let refname = if branch.is_remote {
+                format!("refs/remotes/{}", branch.name)
+            } else {
+                format!("refs/heads/{}", branch.name)
+            };
+            let Ok(tip) = repo.refname_to_id(&refname) else {
+                continue;
+            };
+            if let Ok((ahead, behind)) = repo.graph_ahead_behind(tip, base) {
+                branch.ahead = Some(ahead);
+                branch.behind = Some(behind);
+                branch.is_merged = Some(ahead == 0);
+            }
+        }
+        Ok(())
+    }
+
     /// Perform a squash merge of task branch into base branch, but fail on conflicts
     fn perform_squash_merge(
         &self,
@@ -1341,6 +1578,7 @@ impl GitService {
         signature: &git2::Signature,
         commit_message: &str,
         base_branch_name: &str,
+        signing: Option<&CommitSigning>,
     ) -> Result<git2::Oid, GitServiceError> {
         // In-memory merge to detect conflicts without touching the working tree
         let mut merge_opts = git2::MergeOptions::new();
@@ -1361,14 +1599,29 @@ impl GitService {
         let tree = repo.find_tree(tree_id)?;
 
         // Create a squash commit: use merged tree with base_commit as sole parent
-        let squash_commit_id = repo.commit(
-            None,           // Don't update any reference yet
-            signature,      // Author
-            signature,      // Committer
-            commit_message, // Custom message
-            &tree,          // Merged tree content
-            &[base_commit], // Single parent: base branch commit
-        )?;
+        let squash_commit_id = match signing {
+            // libgit2 can't sign, so let the CLI write the commit object
+            Some(signing) => {
+                let repo_path = repo.workdir().unwrap_or(repo.path());
+                self.ensure_cli_commit_identity(repo_path)?;
+                let sha = GitCli::new().commit_tree_signed(
+                    repo_path,
+                    &tree_id.to_string(),
+                    &base_commit.id().to_string(),
+                    commit_message,
+                    signing,
+                )?;
+                git2::Oid::from_str(&sha)?
+            }
+            None => repo.commit(
+                None,           // Don't update any reference yet
+                signature,      // Author
+                signature,      // Committer
+                commit_message, // Custom message
+                &tree,          // Merged tree content
+                &[base_commit], // Single parent: base branch commit
+            )?,
+        };
 
         // Update the base branch reference to point to the new commit
         let refname = format!("refs/heads/{base_branch_name}");
@@ -1548,6 +1801,40 @@ impl GitService {
         })
     }
 
+    /// Files that would conflict if `branch_name` were merged into `base_branch_name`. The
+    /// merge happens in memory, so no ref, index or worktree is touched.
+    pub fn merge_conflicted_files(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        base_branch_name: &str,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let task_commit = Self::find_branch(&repo, branch_name)?
+            .get()
+            .peel_to_commit()?;
+        let base_commit = Self::find_branch(&repo, base_branch_name)?
+            .get()
+            .peel_to_commit()?;
+
+        let mut merge_opts = git2::MergeOptions::new();
+        merge_opts.find_renames(true);
+        let index = repo.merge_commits(&base_commit, &task_commit, Some(&merge_opts))?;
+        if !index.has_conflicts() {
+            return Ok(Vec::new());
+        }
+
+        let mut files: Vec<String> = index
+            .conflicts()?
+            .filter_map(Result::ok)
+            .filter_map(|conflict| conflict.our.or(conflict.their).or(conflict.ancestor))
+            .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+            .collect();
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+
     /// Abort an in-progress rebase in this worktree (no-op if none).
     pub fn abort_rebase(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
         let git = GitCli::new();
@@ -1556,6 +1843,51 @@ impl GitService {
         })
     }
 
+    /// Abort an in-progress merge in this worktree (no-op if none), restoring the pre-merge state.
+    pub fn abort_merge(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+        git.abort_merge(worktree_path).map_err(|e| {
+            GitServiceError::InvalidRepository(format!("git merge --abort failed: {e}"))
+        })
+    }
+
+    /// Continue the in-progress `op` once its conflicts are resolved and staged. Reports
+    /// `MergeConflicts` while unmerged files remain, including when a rebase stops again on a
+    /// later commit.
+    pub fn continue_operation(
+        &self,
+        worktree_path: &Path,
+        op: ConflictOp,
+    ) -> Result<(), GitServiceError> {
+        let conflicted = self.get_conflicted_files(worktree_path)?;
+        if !conflicted.is_empty() {
+            return Err(GitServiceError::MergeConflicts(format!(
+                "Resolve and stage the conflicted files before continuing: {}",
+                conflicted.join(", ")
+            )));
+        }
+
+        self.ensure_cli_commit_identity(worktree_path)?;
+        let git = GitCli::new();
+        let (command, result) = match op {
+            ConflictOp::Rebase => ("rebase", git.continue_rebase(worktree_path)),
+            ConflictOp::Merge => ("merge", git.continue_merge(worktree_path)),
+            ConflictOp::CherryPick => ("cherry-pick", git.continue_cherry_pick(worktree_path)),
+            ConflictOp::Revert => ("revert", git.continue_revert(worktree_path)),
+        };
+        result.map_err(|e| {
+            let conflicted = self.get_conflicted_files(worktree_path).unwrap_or_default();
+            if conflicted.is_empty() {
+                GitServiceError::InvalidRepository(format!("git {command} --continue failed: {e}"))
+            } else {
+                GitServiceError::MergeConflicts(format!(
+                    "The {command} stopped on new conflicts: {}",
+                    conflicted.join(", ")
+                ))
+            }
+        })
+    }
+
     pub fn abort_conflicts(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
         let git = GitCli::new();
         if git.is_rebase_in_progress(worktree_path).unwrap_or(false) {
@@ -1657,10 +1989,23 @@ impl GitService {
         Ok(commit_id.to_string())
     }
 
-    /// Get the default branch name for the repository
+    /// Get the default branch name for the repository: the branch the remote's HEAD points at
+    /// (`git symbolic-ref refs/remotes/origin/HEAD`), else the checked-out branch, else `main`
     pub fn get_default_branch_name(&self, repo_path: &Path) -> Result<String, GitServiceError> {
         let repo = self.open_repo(repo_path)?;
 
+        let remote_prefix = format!("refs/remotes/{}/", self.default_remote_name(&repo));
+        if let Ok(remote_head) = repo.find_reference(&format!("{remote_prefix}HEAD"))
+            && let Some(branch) = remote_head
+                .symbolic_target()
+                .and_then(|target| target.strip_prefix(&remote_prefix))
+        {
+            return Ok(branch.to_string());
+        }
+
+        if repo.head_detached().unwrap_or(false) {
+            return Ok("main".to_string());
+        }
         match repo.head() {
             Ok(head_ref) => Ok(head_ref.shorthand().unwrap_or("main").to_string()),
             Err(e)
@@ -1735,6 +2080,19 @@ impl GitService {
         branch_name: &str,
         github_token: &str,
     ) -> Result<(), GitServiceError> {
+        self.push_branch(worktree_path, branch_name, github_token, false)
+            .map(|_| ())
+    }
+
+    /// Push a branch to the default remote. Without `force`, a push that would drop commits
+    /// from the remote branch fails with a push-rejected error.
+    pub fn push_branch(
+        &self,
+        worktree_path: &Path,
+        branch_name: &str,
+        github_token: &str,
+        force: bool,
+    ) -> Result<PushResult, GitServiceError> {
         let repo = Repository::open(worktree_path)?;
         self.check_worktree_clean(&repo)?;
 
@@ -1747,16 +2105,24 @@ impl GitService {
             .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))?;
         let https_url = self.convert_to_https_url(remote_url);
         let git_cli = GitCli::new();
-        if let Err(e) =
-            git_cli.push_with_token(worktree_path, &https_url, branch_name, github_token)
-        {
-            tracing::error!("Push to GitHub failed: {}", e);
-            return Err(e.into());
-        }
+        let kind = match git_cli.push_branch_with_token(
+            worktree_path,
+            &https_url,
+            branch_name,
+            github_token,
+            force,
+        ) {
+            Ok(kind) => kind,
+            Err(e) => {
+                tracing::error!("Push to GitHub failed: {}", e);
+                return Err(e.into());
+            }
+        };
 
         let mut branch = Self::find_branch(&repo, branch_name)?;
+        let branch_target = branch.get().target();
         if !branch.get().is_remote() {
-            if let Some(branch_target) = branch.get().target() {
+            if let Some(branch_target) = branch_target {
                 let remote_ref = format!("refs/remotes/{remote_name}/{branch_name}");
                 repo.reference(
                     &remote_ref,
@@ -1768,6 +2134,68 @@ impl GitService {
             branch.set_upstream(Some(&format!("{remote_name}/{branch_name}")))?;
         }
 
+        Ok(PushResult {
+            remote_sha: branch_target.map(|oid| oid.to_string()).unwrap_or_default(),
+            fast_forward: kind != PushKind::Forced,
+        })
+    }
+
+    /// Delete a local branch. Returns `Ok(false)` if the branch did not exist.
+    pub fn delete_local_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+    ) -> Result<bool, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        if repo.find_branch(branch_name, BranchType::Local).is_err() {
+            return Ok(false);
+        }
+        let git_cli = GitCli::new();
+        git_cli.delete_branch(repo_path, branch_name, true)?;
+        Ok(true)
+    }
+
+    /// Delete a branch from the default remote and drop its remote-tracking ref. The
+    /// repository's default branch and its checked-out branch are never deleted.
+    pub fn delete_remote_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        github_token: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        if self.get_default_branch_name(repo_path)? == branch_name {
+            return Err(GitServiceError::ProtectedBranch(
+                branch_name.to_string(),
+                "it is the default branch".to_string(),
+            ));
+        }
+        let checked_out = !repo.head_detached().unwrap_or(false)
+            && repo
+                .head()
+                .is_ok_and(|head| head.shorthand() == Some(branch_name));
+        if checked_out {
+            return Err(GitServiceError::ProtectedBranch(
+                branch_name.to_string(),
+                "it is checked out in the project repository".to_string(),
+            ));
+        }
+
+        let remote_name = self.default_remote_name(&repo);
+        let remote = repo.find_remote(&remote_name)?;
+        let remote_url = remote
+            .url()
+            .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))?;
+        let https_url = self.convert_to_https_url(remote_url);
+
+        let git_cli = GitCli::new();
+        git_cli.delete_remote_branch_with_token(repo_path, &https_url, branch_name, github_token)?;
+
+        if let Ok(mut tracking) =
+            repo.find_reference(&format!("refs/remotes/{remote_name}/{branch_name}"))
+        {
+            tracking.delete()?;
+        }
         Ok(())
     }
 
@@ -1846,6 +2274,23 @@ impl GitService {
         self.fetch_from_remote(repo, github_token, remote, &refspec)
     }
 
+    /// Directory name for a checkout of `clone_url`: its last path segment without `.git`
+    pub fn repo_name_from_clone_url(clone_url: &str) -> Option<String> {
+        let name = clone_url
+            .trim()
+            .trim_end_matches('/')
+            .rsplit(['/', ':', '\\'])
+            .next()?
+            .trim_end_matches(".git");
+        (!name.is_empty() && name != "." && name != "..").then(|| name.to_string())
+    }
+
+    /// Whether `url` is an HTTPS GitHub URL, the only kind of remote the GitHub token is sent to
+    pub fn is_github_https_url(url: &str) -> bool {
+        url.get(..19)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("https://github.com/"))
+    }
+
     /// Clone a repository to the specified directory
     #[cfg(feature = "cloud")]
     pub fn clone_repository(
@@ -1859,10 +2304,16 @@ impl GitService {
             std::fs::create_dir_all(parent)?;
         }
 
-        // Set up callbacks for authentication if token is provided
+        // Set up callbacks for authentication if token is provided. The token is a GitHub
+        // credential, so it's never offered to any other host.
         let mut callbacks = RemoteCallbacks::new();
-        if let Some(token) = token {
-            callbacks.credentials(|_url, username_from_url, _allowed_types| {
+        if let Some(token) = token.filter(|_| Self::is_github_https_url(clone_url)) {
+            callbacks.credentials(|url, username_from_url, _allowed_types| {
+                if !Self::is_github_https_url(url) {
+                    return Err(git2::Error::from_str(
+                        "Refusing to send the GitHub token to a non-GitHub remote",
+                    ));
+                }
                 Cred::userpass_plaintext(username_from_url.unwrap_or("git"), token)
             });
         } else {
@@ -1883,6 +2334,25 @@ impl GitService {
             });
         }
 
+        // Log progress at every quarter of the received objects
+        let mut last_quarter = 0;
+        callbacks.transfer_progress(move |stats| {
+            let total = stats.total_objects();
+            if total > 0 {
+                let quarter = stats.received_objects() * 4 / total;
+                if quarter > last_quarter {
+                    last_quarter = quarter;
+                    tracing::info!(
+                        "Cloning {}: received {}/{} objects",
+                        clone_url,
+                        stats.received_objects(),
+                        total
+                    );
+                }
+            }
+            true
+        });
+
         // Set up fetch options with our callbacks
         let mut fetch_opts = FetchOptions::new();
         fetch_opts.remote_callbacks(callbacks);
@@ -44,6 +44,23 @@ pub enum GitCliError {
 #[derive(Clone, Default)]
 pub struct GitCli;
 
+/// Signing applied to commits Forge creates. Without a key, git signs with the repository's
+/// `user.signingkey`; `gpg.format` decides between GPG and SSH signatures.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitSigning {
+    pub key: Option<String>,
+}
+
+impl CommitSigning {
+    /// The `-S` argument for `git commit` and `git commit-tree`
+    pub fn flag(&self) -> String {
+        match &self.key {
+            Some(key) => format!("-S{key}"),
+            None => "-S".to_string(),
+        }
+    }
+}
+
 /// Parsed change type from `git diff --name-status` output
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ChangeType {
@@ -65,6 +82,16 @@ pub struct StatusDiffEntry {
     pub old_path: Option<String>,
 }
 
+/// How a pushed ref moved, from the flag column of `git push --porcelain`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushKind {
+    FastForward,
+    /// The remote ref was rewritten, dropping commits it had
+    Forced,
+    NewBranch,
+    UpToDate,
+}
+
 /// Parsed worktree entry from `git worktree list --porcelain`
 #[derive(Debug, Clone)]
 pub struct WorktreeEntry {
@@ -334,7 +361,52 @@ impl GitCli {
         branch: &str,
         token: &str,
     ) -> Result<(), GitCliError> {
-        let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+        self.push_branch_with_token(repo_path, remote_url, branch, token, false)
+            .map(|_| ())
+    }
+
+    /// Push a branch like `push_with_token` and report how the remote ref moved. Without
+    /// `force`, pushes that would drop remote commits are rejected with `PushRejected`.
+    pub fn push_branch_with_token(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        branch: &str,
+        token: &str,
+        force: bool,
+    ) -> Result<PushKind, GitCliError> {
+        let force_prefix = if force { "+" } else { "" };
+        let refspec = format!("{force_prefix}refs/heads/{branch}:refs/heads/{branch}");
+        let auth_header = self.build_auth_header(token);
+        let envs = self.build_token_env(&auth_header);
+
+        let args = [
+            OsString::from("-c"),
+            OsString::from("credential.helper="),
+            OsString::from("--config-env"),
+            OsString::from("http.extraHeader=GIT_HTTP_EXTRAHEADER"),
+            OsString::from("push"),
+            OsString::from("--porcelain"),
+            OsString::from(remote_url),
+            OsString::from(refspec),
+        ];
+
+        match self.git_with_env(repo_path, args, &envs) {
+            Ok(out) => Ok(Self::parse_push_kind(&out)),
+            Err(GitCliError::CommandFailed(msg)) => Err(self.classify_cli_error(msg)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Delete a remote branch by pushing an empty refspec, authenticating with an HTTPS token.
+    pub fn delete_remote_branch_with_token(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        branch: &str,
+        token: &str,
+    ) -> Result<(), GitCliError> {
+        let refspec = format!(":refs/heads/{branch}");
         let auth_header = self.build_auth_header(token);
         let envs = self.build_token_env(&auth_header);
 
@@ -355,6 +427,17 @@ impl GitCli {
         }
     }
 
+    /// Delete a local branch (`git branch -d`, or `-D` when `force` is set).
+    pub fn delete_branch(
+        &self,
+        repo_path: &Path,
+        branch: &str,
+        force: bool,
+    ) -> Result<(), GitCliError> {
+        let flag = if force { "-D" } else { "-d" };
+        self.git(repo_path, ["branch", flag, branch]).map(|_| ())
+    }
+
     // Parse `git diff --name-status` output into structured entries.
     // Handles rename/copy scores like `R100` by matching the first letter.
     fn parse_name_status(output: &str) -> Vec<StatusDiffEntry> {
@@ -527,11 +610,15 @@ impl GitCli {
         base_branch: &str,
         from_branch: &str,
         message: &str,
+        signing: Option<&CommitSigning>,
     ) -> Result<String, GitCliError> {
         self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
         self.git(repo_path, ["merge", "--squash", "--no-commit", from_branch])
             .map(|_| ())?;
-        self.git(repo_path, ["commit", "-m", message]).map(|_| ())?;
+        let mut args = vec!["commit".to_string()];
+        args.extend(signing.map(CommitSigning::flag));
+        args.extend(["-m".to_string(), message.to_string()]);
+        self.git(repo_path, args).map(|_| ())?;
         let sha = self
             .git(repo_path, ["rev-parse", "HEAD"])?
             .trim()
@@ -539,6 +626,31 @@ impl GitCli {
         Ok(sha)
     }
 
+    /// Create a signed commit object for `tree` on top of `parent` without touching any ref or
+    /// worktree. Returns the new commit sha.
+    pub fn commit_tree_signed(
+        &self,
+        repo_path: &Path,
+        tree: &str,
+        parent: &str,
+        message: &str,
+        signing: &CommitSigning,
+    ) -> Result<String, GitCliError> {
+        let out = self.git(
+            repo_path,
+            [
+                "commit-tree",
+                tree,
+                "-p",
+                parent,
+                "-m",
+                message,
+                &signing.flag(),
+            ],
+        )?;
+        Ok(out.trim().to_string())
+    }
+
     /// Update a ref to a specific sha in the repo.
     pub fn update_ref(
         &self,
@@ -572,6 +684,44 @@ impl GitCli {
         self.git(worktree_path, ["revert", "--abort"]).map(|_| ())
     }
 
+    /// Continue an in-progress rebase whose conflicts have been resolved and staged.
+    pub fn continue_rebase(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["rebase", "--continue"],
+            &self.no_editor_env(),
+        )
+        .map(|_| ())
+    }
+
+    /// Conclude an in-progress merge whose conflicts have been resolved and staged.
+    pub fn continue_merge(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["merge", "--continue"],
+            &self.no_editor_env(),
+        )
+        .map(|_| ())
+    }
+
+    pub fn continue_cherry_pick(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["cherry-pick", "--continue"],
+            &self.no_editor_env(),
+        )
+        .map(|_| ())
+    }
+
+    pub fn continue_revert(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["revert", "--continue"],
+            &self.no_editor_env(),
+        )
+        .map(|_| ())
+    }
+
     /// List files currently in a conflicted (unmerged) state in the worktree.
     pub fn get_conflicted_files(&self, worktree_path: &Path) -> Result<Vec<String>, GitCliError> {
         // `--diff-filter=U` lists paths with unresolved conflicts
@@ -589,6 +739,20 @@ impl GitCli {
 
 // Private methods
 impl GitCli {
+    /// Reads the ref status line (`<flag>\t<from>:<to>\t<summary>`) of porcelain push output
+    fn parse_push_kind(out: &str) -> PushKind {
+        let flag = out
+            .lines()
+            .find(|line| line.contains("\trefs/"))
+            .and_then(|line| line.chars().next());
+        match flag {
+            Some('+') => PushKind::Forced,
+            Some('*') => PushKind::NewBranch,
+            Some('=') => PushKind::UpToDate,
+            _ => PushKind::FastForward,
+        }
+    }
+
     fn classify_cli_error(&self, msg: String) -> GitCliError {
         let lower = msg.to_ascii_lowercase();
         if lower.contains("authentication failed")
@@ -624,6 +788,11 @@ impl GitCli {
         ]
     }
 
+    /// Accept git's prepared commit messages instead of opening an editor
+    fn no_editor_env(&self) -> Vec<(OsString, OsString)> {
+        vec![(OsString::from("GIT_EDITOR"), OsString::from("true"))]
+    }
+
     /// Ensure `git` is available on PATH
     fn ensure_available(&self) -> Result<(), GitCliError> {
         let git = resolve_executable_path_blocking("git").ok_or(GitCliError::NotAvailable)?;
@@ -0,0 +1,333 @@
+//! Retry handling shared by GitHub API calls (releases, pull requests).
+//!
+//! Every attempt first takes a token from a process-wide bucket so bursts of calls stay
+//! under GitHub's secondary rate limits. Transient failures are retried with jittered
+//! exponential backoff so clients that failed together don't retry in lockstep. Rate limits
+//! are waited out when GitHub says how long to wait (`Retry-After` / `X-RateLimit-Reset`),
+//! and anything longer than the policy allows is surfaced as a rate-limit error instead of
+//! a generic failure. Requests that create something are never retried.
+
+use std::{
+    future::Future,
+    sync::{Arc, LazyLock, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::{
+    StatusCode,
+    header::{ETAG, HeaderMap, IF_NONE_MATCH},
+};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+const USER_AGENT: &str = "automagik-forge";
+
+/// Default request rate to GitHub, overridable via `FORGE_GITHUB_REQUESTS_PER_SECOND`.
+const DEFAULT_REQUESTS_PER_SECOND: f64 = 5.0;
+
+static SHARED_RATE_LIMITER: LazyLock<Arc<GitHubRateLimiter>> = LazyLock::new(|| {
+    let requests_per_second = std::env::var("FORGE_GITHUB_REQUESTS_PER_SECOND")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .unwrap_or(DEFAULT_REQUESTS_PER_SECOND);
+    Arc::new(GitHubRateLimiter::new(requests_per_second))
+});
+
+/// Token bucket pacing requests to GitHub. Up to one second's worth of requests may go out
+/// at once; after that callers wait their turn at the configured rate.
+#[derive(Debug)]
+pub struct GitHubRateLimiter {
+    requests_per_second: f64,
+    bucket: Mutex<TokenBucket>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    /// Negative when callers are already queued for future tokens
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl GitHubRateLimiter {
+    /// A rate that isn't positive disables limiting
+    pub fn new(requests_per_second: f64) -> Self {
+        Self {
+            requests_per_second,
+            bucket: Mutex::new(TokenBucket {
+                tokens: Self::capacity(requests_per_second),
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// The limiter shared by every GitHub call in the process
+    pub fn shared() -> Arc<Self> {
+        SHARED_RATE_LIMITER.clone()
+    }
+
+    fn capacity(requests_per_second: f64) -> f64 {
+        requests_per_second.max(1.0)
+    }
+
+    /// Waits until a request may be sent
+    pub async fn acquire(&self) {
+        if self.requests_per_second <= 0.0 {
+            return;
+        }
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap();
+            let now = Instant::now();
+            let refill =
+                now.duration_since(bucket.refilled_at).as_secs_f64() * self.requests_per_second;
+            bucket.tokens = (bucket.tokens + refill).min(Self::capacity(self.requests_per_second));
+            bucket.refilled_at = now;
+            // Reserve a token now, even if it only becomes available later, so waiting
+            // callers are served in order
+            bucket.tokens -= 1.0;
+            (bucket.tokens < 0.0)
+                .then(|| Duration::from_secs_f64(-bucket.tokens / self.requests_per_second))
+        };
+        if let Some(wait) = wait {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GitHubRetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: usize,
+    pub min_delay: Duration,
+    pub max_delay: Duration,
+    /// Longest rate-limit reset worth waiting for; longer resets fail straight away
+    pub max_rate_limit_wait: Duration,
+    /// Paces every attempt, retries included
+    pub rate_limiter: Arc<GitHubRateLimiter>,
+}
+
+impl Default for GitHubRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            min_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            max_rate_limit_wait: Duration::from_secs(60),
+            rate_limiter: GitHubRateLimiter::shared(),
+        }
+    }
+}
+
+impl GitHubRetryPolicy {
+    /// Delay before retry number `attempt`: a random point in the upper half of the
+    /// exponential backoff
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        let factor = 1u32.checked_shl(attempt as u32).unwrap_or(u32::MAX);
+        let delay = self.min_delay.saturating_mul(factor).min(self.max_delay);
+        delay.mul_f64(0.5 + rand::random::<f64>() / 2.0)
+    }
+}
+
+/// How a failed GitHub call should be handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Transient failure; retry after the given delay, or the policy's backoff
+    Retry(Option<Duration>),
+    /// Rate limited; wait until the limit resets if known and short enough
+    RateLimited(Option<Duration>),
+    /// Permanent failure
+    Stop,
+}
+
+pub trait GitHubRetry {
+    fn retry_decision(&self) -> RetryDecision;
+}
+
+/// Runs `op` until it succeeds, fails permanently, or the policy gives up.
+pub async fn with_github_retry<T, E, F, Fut>(policy: &GitHubRetryPolicy, mut op: F) -> Result<T, E>
+where
+    E: GitHubRetry + std::fmt::Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 0;
+    loop {
+        policy.rate_limiter.acquire().await;
+        let err = match op().await {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        if attempt >= policy.max_retries {
+            return Err(err);
+        }
+        let delay = match err.retry_decision() {
+            RetryDecision::Stop => return Err(err),
+            RetryDecision::Retry(hint) => hint.unwrap_or_else(|| policy.backoff(attempt)),
+            RetryDecision::RateLimited(Some(wait)) if wait <= policy.max_rate_limit_wait => wait,
+            RetryDecision::RateLimited(_) => return Err(err),
+        };
+        tracing::warn!(
+            "GitHub API call failed, retrying after {:.2}s: {}",
+            delay.as_secs_f64(),
+            err
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum GitHubHttpError {
+    #[error("GitHub API rate limit exceeded")]
+    RateLimited { retry_after: Option<Duration> },
+    #[error("GitHub API responded with status {status}")]
+    Status { status: u16 },
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+}
+
+impl GitHubRetry for GitHubHttpError {
+    fn retry_decision(&self) -> RetryDecision {
+        match self {
+            Self::RateLimited { retry_after } => RetryDecision::RateLimited(*retry_after),
+            Self::Status { status } if *status >= 500 => RetryDecision::Retry(None),
+            Self::Status { .. } => RetryDecision::Stop,
+            Self::Request(err) if err.is_timeout() || err.is_connect() => {
+                RetryDecision::Retry(None)
+            }
+            Self::Request(_) => RetryDecision::Stop,
+        }
+    }
+}
+
+/// How long GitHub asks us to wait, if the response is a rate-limit rejection.
+///
+/// Returns `None` for responses that are not rate limited, and `Some(None)` when the response
+/// is rate limited but carries no usable reset hint.
+pub fn rate_limit_wait(
+    status: StatusCode,
+    headers: &HeaderMap,
+    now: SystemTime,
+) -> Option<Option<Duration>> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    let retry_after = header("retry-after").map(Duration::from_secs);
+    let exhausted = header("x-ratelimit-remaining") == Some(0);
+
+    let limited = status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::FORBIDDEN && (retry_after.is_some() || exhausted));
+    if !limited {
+        return None;
+    }
+
+    let reset_wait = header("x-ratelimit-reset").map(|reset| {
+        let now = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        Duration::from_secs(reset.saturating_sub(now))
+    });
+    Some(retry_after.or(reset_wait))
+}
+
+/// Outcome of a conditional GET
+#[derive(Debug)]
+pub enum Conditional<T> {
+    /// The resource still matches the ETag that was sent
+    NotModified,
+    Modified {
+        value: T,
+        etag: Option<String>,
+    },
+}
+
+/// Plain HTTP client for the public GitHub REST endpoints that don't go through octocrab
+#[derive(Debug, Clone, Default)]
+pub struct GitHubHttpClient {
+    client: reqwest::Client,
+    policy: GitHubRetryPolicy,
+    token: Option<String>,
+}
+
+impl GitHubHttpClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_policy(policy: GitHubRetryPolicy) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            policy,
+            token: None,
+        }
+    }
+
+    /// Authenticate requests with `token`, which lifts the low anonymous rate limit
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// GETs `url` and decodes the JSON body, retrying 5xx responses and waiting out short
+    /// rate limits.
+    pub async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, GitHubHttpError> {
+        Ok(self.send(url, None).await?.json::<T>().await?)
+    }
+
+    /// Like [`Self::get_json`], but sends `If-None-Match` with a previously returned ETag so an
+    /// unchanged resource comes back as a bodyless 304.
+    pub async fn get_json_conditional<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        etag: Option<&str>,
+    ) -> Result<Conditional<T>, GitHubHttpError> {
+        let response = self.send(url, etag).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(Conditional::NotModified);
+        }
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        Ok(Conditional::Modified {
+            value: response.json::<T>().await?,
+            etag,
+        })
+    }
+
+    async fn send(
+        &self,
+        url: &str,
+        etag: Option<&str>,
+    ) -> Result<reqwest::Response, GitHubHttpError> {
+        with_github_retry(&self.policy, || async {
+            let mut request = self
+                .client
+                .get(url)
+                .header("User-Agent", USER_AGENT)
+                .header("Accept", "application/vnd.github+json");
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            }
+            if let Some(etag) = etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            let response = request.send().await?;
+
+            let status = response.status();
+            let rate_limited = rate_limit_wait(status, response.headers(), SystemTime::now());
+            if let Some(retry_after) = rate_limited {
+                return Err(GitHubHttpError::RateLimited { retry_after });
+            }
+            if !status.is_success() && status != StatusCode::NOT_MODIFIED {
+                return Err(GitHubHttpError::Status {
+                    status: status.as_u16(),
+                });
+            }
+            Ok(response)
+        })
+        .await
+    }
+}
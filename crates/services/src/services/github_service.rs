@@ -1,6 +1,3 @@
-use std::time::Duration;
-
-use backon::{ExponentialBuilder, Retryable};
 use forge_core_db::models::merge::{MergeStatus, PullRequestInfo};
 use octocrab::{Octocrab, OctocrabBuilder, models::IssueState};
 use regex::Regex;
@@ -9,7 +6,11 @@ use thiserror::Error;
 use tracing::info;
 use ts_rs_forge::TS;
 
-use crate::services::{git::GitServiceError, git_cli::GitCliError};
+use crate::services::{
+    git::GitServiceError,
+    git_cli::GitCliError,
+    github_http::{GitHubRetry, GitHubRetryPolicy, RetryDecision, with_github_retry},
+};
 
 #[derive(Debug, Error, Serialize, Deserialize, TS)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -34,6 +35,8 @@ pub enum GitHubServiceError {
     InsufficientPermissions,
     #[error("GitHub repository not found or no access")]
     RepoNotFoundOrNoAccess,
+    #[error("GitHub API rate limit exceeded. Try again later.")]
+    RateLimited,
     #[ts(skip)]
     #[serde(skip)]
     #[error(transparent)]
@@ -49,6 +52,8 @@ impl From<octocrab::Error> for GitHubServiceError {
                 if status == 401 || msg.contains("bad credentials") || msg.contains("token expired")
                 {
                     GitHubServiceError::TokenInvalid
+                } else if status == 429 || (status == 403 && msg.contains("rate limit")) {
+                    GitHubServiceError::RateLimited
                 } else if status == 403 {
                     GitHubServiceError::InsufficientPermissions
                 } else {
@@ -107,6 +112,7 @@ impl GitHubServiceError {
             GitHubServiceError::TokenInvalid
                 | GitHubServiceError::InsufficientPermissions
                 | GitHubServiceError::RepoNotFoundOrNoAccess
+                | GitHubServiceError::RateLimited
         )
     }
 
@@ -115,6 +121,17 @@ impl GitHubServiceError {
     }
 }
 
+impl GitHubRetry for GitHubServiceError {
+    fn retry_decision(&self) -> RetryDecision {
+        match self {
+            // octocrab doesn't expose the response headers, so the reset time is unknown
+            GitHubServiceError::RateLimited => RetryDecision::RateLimited(None),
+            err if err.should_retry() => RetryDecision::Retry(None),
+            _ => RetryDecision::Stop,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GitHubRepoInfo {
     pub owner: String,
@@ -180,36 +197,29 @@ impl GitHubService {
         Ok(())
     }
 
-    /// Create a pull request on GitHub
+    /// Create a pull request on GitHub. The lookups before it are retried, but the create
+    /// request is sent only once: retrying it after a lost response could open a second PR.
     pub async fn create_pr(
         &self,
         repo_info: &GitHubRepoInfo,
         request: &CreatePrRequest,
     ) -> Result<PullRequestInfo, GitHubServiceError> {
-        (|| async { self.create_pr_internal(repo_info, request).await })
-            .retry(
-                &ExponentialBuilder::default()
-                    .with_min_delay(Duration::from_secs(1))
-                    .with_max_delay(Duration::from_secs(30))
-                    .with_max_times(3)
-                    .with_jitter(),
-            )
-            .when(|e| e.should_retry())
-            .notify(|err: &GitHubServiceError, dur: Duration| {
-                tracing::warn!(
-                    "GitHub API call failed, retrying after {:.2}s: {}",
-                    dur.as_secs_f64(),
-                    err
-                );
-            })
-            .await
+        let policy = GitHubRetryPolicy::default();
+        with_github_retry(&policy, || async {
+            self.check_pr_refs(repo_info, request).await
+        })
+        .await?;
+
+        policy.rate_limiter.acquire().await;
+        self.send_create_pr(repo_info, request).await
     }
 
-    async fn create_pr_internal(
+    /// Checks that the repository and both branches of the PR exist
+    async fn check_pr_refs(
         &self,
         repo_info: &GitHubRepoInfo,
         request: &CreatePrRequest,
-    ) -> Result<PullRequestInfo, GitHubServiceError> {
+    ) -> Result<(), GitHubServiceError> {
         // Verify repository access
         self.client
             .repos(&repo_info.owner, &repo_info.repo_name)
@@ -264,8 +274,14 @@ impl GitHubService {
                 )),
                 other => other,
             })?;
+        Ok(())
+    }
 
-        // Create the pull request
+    async fn send_create_pr(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitHubServiceError> {
         let pr_info = self
             .client
             .pulls(&repo_info.owner, &repo_info.repo_name)
@@ -298,7 +314,7 @@ impl GitHubService {
         repo_info: &GitHubRepoInfo,
         pr_number: i64,
     ) -> Result<PullRequestInfo, GitHubServiceError> {
-        (|| async {
+        with_github_retry(&GitHubRetryPolicy::default(), || async {
             self.client
                 .pulls(&repo_info.owner, &repo_info.repo_name)
                 .get(pr_number as u64)
@@ -312,21 +328,6 @@ impl GitHubService {
                     other => other,
                 })
         })
-        .retry(
-            &ExponentialBuilder::default()
-                .with_min_delay(Duration::from_secs(1))
-                .with_max_delay(Duration::from_secs(30))
-                .with_max_times(3)
-                .with_jitter(),
-        )
-        .when(|err| err.should_retry())
-        .notify(|err: &GitHubServiceError, dur: Duration| {
-            tracing::warn!(
-                "GitHub API call failed, retrying after {:.2}s: {}",
-                dur.as_secs_f64(),
-                err
-            );
-        })
         .await
     }
 
@@ -359,25 +360,10 @@ impl GitHubService {
         repo_info: &GitHubRepoInfo,
         branch_name: &str,
     ) -> Result<Vec<PullRequestInfo>, GitHubServiceError> {
-        (|| async {
+        with_github_retry(&GitHubRetryPolicy::default(), || async {
             self.list_all_prs_for_branch_internal(repo_info, branch_name)
                 .await
         })
-        .retry(
-            &ExponentialBuilder::default()
-                .with_min_delay(Duration::from_secs(1))
-                .with_max_delay(Duration::from_secs(30))
-                .with_max_times(3)
-                .with_jitter(),
-        )
-        .when(|e| e.should_retry())
-        .notify(|err: &GitHubServiceError, dur: Duration| {
-            tracing::warn!(
-                "GitHub API call failed, retrying after {:.2}s: {}",
-                dur.as_secs_f64(),
-                err
-            );
-        })
         .await
     }
 
@@ -414,23 +400,10 @@ impl GitHubService {
         &self,
         page: u8,
     ) -> Result<Vec<RepositoryInfo>, GitHubServiceError> {
-        (|| async { self.list_repositories_internal(page).await })
-            .retry(
-                &ExponentialBuilder::default()
-                    .with_min_delay(Duration::from_secs(1))
-                    .with_max_delay(Duration::from_secs(30))
-                    .with_max_times(3)
-                    .with_jitter(),
-            )
-            .when(|err| err.should_retry())
-            .notify(|err: &GitHubServiceError, dur: Duration| {
-                tracing::warn!(
-                    "GitHub API call failed, retrying after {:.2}s: {}",
-                    dur.as_secs_f64(),
-                    err
-                );
-            })
-            .await
+        with_github_retry(&GitHubRetryPolicy::default(), || async {
+            self.list_repositories_internal(page).await
+        })
+        .await
     }
 
     #[cfg(feature = "cloud")]
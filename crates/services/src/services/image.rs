@@ -30,6 +30,161 @@ pub enum ImageError {
     ResponseBuildError(String),
 }
 
+/// Image formats accepted for upload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadImageFormat {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl UploadImageFormat {
+    /// Every accepted format
+    pub const ALL: [Self; 3] = [Self::Png, Self::Jpeg, Self::Webp];
+
+    /// Detects the format from the file signature
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(Self::Jpeg)
+        } else if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+            Some(Self::Webp)
+        } else {
+            None
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Webp => "image/webp",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::Webp => "webp",
+        }
+    }
+}
+
+/// Checks an upload against the size limit and the accepted formats
+pub fn validate_upload(data: &[u8], max_size_bytes: u64) -> Result<UploadImageFormat, ImageError> {
+    let file_size = data.len() as u64;
+    if file_size > max_size_bytes {
+        return Err(ImageError::TooLarge(file_size, max_size_bytes));
+    }
+
+    UploadImageFormat::detect(data).ok_or(ImageError::InvalidFormat)
+}
+
+/// Removes EXIF metadata (camera details, GPS position, ...) from an image
+pub fn strip_exif(data: &[u8], format: UploadImageFormat) -> Result<Vec<u8>, ImageError> {
+    match format {
+        UploadImageFormat::Png => strip_png_exif(data),
+        UploadImageFormat::Jpeg => strip_jpeg_exif(data),
+        UploadImageFormat::Webp => strip_webp_exif(data),
+    }
+    .ok_or(ImageError::InvalidFormat)
+}
+
+/// Drops `eXIf` chunks, stopping at `IEND`
+fn strip_png_exif(data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = data.get(..8)?.to_vec();
+    let mut pos = 8;
+    while pos < data.len() {
+        let len = u32::from_be_bytes(data.get(pos..pos + 4)?.try_into().ok()?) as usize;
+        let chunk_type = data.get(pos + 4..pos + 8)?;
+        let end = pos.checked_add(12)?.checked_add(len)?;
+        let chunk = data.get(pos..end)?;
+        if chunk_type != b"eXIf" {
+            out.extend_from_slice(chunk);
+        }
+        if chunk_type == b"IEND" {
+            break;
+        }
+        pos = end;
+    }
+    Some(out)
+}
+
+/// Drops APP1 segments carrying EXIF data; everything from the start of scan
+/// onwards is copied unchanged
+fn strip_jpeg_exif(data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = data.get(..2)?.to_vec();
+    let mut pos = 2;
+    while pos < data.len() {
+        if data[pos] != 0xFF {
+            return None;
+        }
+        let marker = *data.get(pos + 1)?;
+        match marker {
+            // Fill byte before a marker
+            0xFF => {
+                pos += 1;
+            }
+            // Start of scan: entropy-coded data follows
+            0xDA => {
+                out.extend_from_slice(&data[pos..]);
+                break;
+            }
+            // End of image: anything trailing it is dropped
+            0xD9 => {
+                out.extend_from_slice(&data[pos..pos + 2]);
+                break;
+            }
+            // Markers without a length field
+            0x01 | 0xD0..=0xD8 => {
+                out.extend_from_slice(&data[pos..pos + 2]);
+                pos += 2;
+            }
+            _ => {
+                let len = u16::from_be_bytes(data.get(pos + 2..pos + 4)?.try_into().ok()?);
+                let end = pos + 2 + len as usize;
+                let segment = data.get(pos..end)?;
+                let is_exif = marker == 0xE1 && segment.get(4..10) == Some(&b"Exif\0\0"[..]);
+                if !is_exif {
+                    out.extend_from_slice(segment);
+                }
+                pos = end;
+            }
+        }
+    }
+    Some(out)
+}
+
+/// Drops the `EXIF` chunk, clears the EXIF flag in `VP8X` and fixes up the
+/// RIFF size
+fn strip_webp_exif(data: &[u8]) -> Option<Vec<u8>> {
+    const VP8X_EXIF_FLAG: u8 = 0x08;
+
+    let mut out = data.get(..12)?.to_vec();
+    let mut pos = 12;
+    while pos < data.len() {
+        let fourcc = data.get(pos..pos + 4)?;
+        let len = u32::from_le_bytes(data.get(pos + 4..pos + 8)?.try_into().ok()?) as usize;
+        let padded = len + (len & 1);
+        let end = pos.checked_add(8)?.checked_add(padded)?.min(data.len());
+        let chunk = data.get(pos..end)?;
+        if fourcc == b"VP8X" {
+            let start = out.len();
+            out.extend_from_slice(chunk);
+            *out.get_mut(start + 8)? &= !VP8X_EXIF_FLAG;
+        } else if fourcc != b"EXIF" {
+            out.extend_from_slice(chunk);
+        }
+        pos = end;
+    }
+
+    let riff_size = u32::try_from(out.len() - 8).ok()?;
+    out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(out)
+}
+
 #[derive(Clone)]
 pub struct ImageService {
     cache_dir: PathBuf,
@@ -48,38 +203,19 @@ impl ImageService {
         })
     }
 
+    /// Validates and stores an uploaded image. The format is detected from the
+    /// file contents rather than the filename, and EXIF metadata is stripped
+    /// before the image is hashed and written to the cache.
     pub async fn store_image(
         &self,
         data: &[u8],
         original_filename: &str,
     ) -> Result<Image, ImageError> {
+        let format = validate_upload(data, self.max_size_bytes)?;
+        let data = strip_exif(data, format)?;
         let file_size = data.len() as u64;
 
-        if file_size > self.max_size_bytes {
-            return Err(ImageError::TooLarge(file_size, self.max_size_bytes));
-        }
-
-        let hash = format!("{:x}", Sha256::digest(data));
-
-        // Extract extension from original filename
-        let extension = Path::new(original_filename)
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("png");
-
-        let mime_type = match extension.to_lowercase().as_str() {
-            "png" => Some("image/png".to_string()),
-            "jpg" | "jpeg" => Some("image/jpeg".to_string()),
-            "gif" => Some("image/gif".to_string()),
-            "webp" => Some("image/webp".to_string()),
-            "bmp" => Some("image/bmp".to_string()),
-            "svg" => Some("image/svg+xml".to_string()),
-            _ => None,
-        };
-
-        if mime_type.is_none() {
-            return Err(ImageError::InvalidFormat);
-        }
+        let hash = format!("{:x}", Sha256::digest(&data));
 
         let existing_image = Image::find_by_hash(&self.pool, &hash).await?;
 
@@ -88,16 +224,16 @@ impl ImageService {
             return Ok(existing);
         }
 
-        let new_filename = format!("{}.{}", Uuid::new_v4(), extension);
+        let new_filename = format!("{}.{}", Uuid::new_v4(), format.extension());
         let cached_path = self.cache_dir.join(&new_filename);
-        fs::write(&cached_path, data)?;
+        fs::write(&cached_path, &data)?;
 
         let image = Image::create(
             &self.pool,
             &CreateImage {
                 file_path: new_filename,
                 original_name: original_filename.to_string(),
-                mime_type,
+                mime_type: Some(format.mime_type().to_string()),
                 size_bytes: file_size as i64,
                 hash,
             },
@@ -234,3 +370,82 @@ impl ImageService {
         .into_owned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(chunk_type);
+        chunk.extend_from_slice(data);
+        // CRC is not checked by the stripper
+        chunk.extend_from_slice(&[0, 0, 0, 0]);
+        chunk
+    }
+
+    fn png_with_exif() -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend(png_chunk(b"IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]));
+        png.extend(png_chunk(b"eXIf", b"MM\0*gps-coordinates"));
+        png.extend(png_chunk(b"IDAT", &[0x78, 0x9C, 0x63, 0x00, 0x00]));
+        png.extend(png_chunk(b"IEND", &[]));
+        png
+    }
+
+    #[test]
+    fn accepts_valid_png_and_strips_exif() {
+        let png = png_with_exif();
+
+        let format = validate_upload(&png, 1024).unwrap();
+        assert_eq!(format, UploadImageFormat::Png);
+        assert_eq!(format.mime_type(), "image/png");
+
+        let stripped = strip_exif(&png, format).unwrap();
+        assert!(stripped.len() < png.len());
+        assert!(!stripped.windows(4).any(|w| w == b"eXIf"));
+        assert!(stripped.windows(4).any(|w| w == b"IDAT"));
+        assert!(stripped.ends_with(&png_chunk(b"IEND", &[])));
+    }
+
+    #[test]
+    fn rejects_oversized_upload() {
+        let png = png_with_exif();
+
+        let err = validate_upload(&png, 16).unwrap_err();
+        assert!(matches!(err, ImageError::TooLarge(size, 16) if size == png.len() as u64));
+    }
+
+    #[test]
+    fn rejects_disallowed_types() {
+        let gif = b"GIF89a\x01\x00\x01\x00\x00\x00\x00;".to_vec();
+        let svg = b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>".to_vec();
+        let scripted_svg = b"<?xml version=\"1.0\"?><svg><script>alert(1)</script></svg>".to_vec();
+        let exe = b"MZ\x90\x00\x03\x00\x00\x00".to_vec();
+
+        for data in [gif, svg, scripted_svg, exe] {
+            assert!(matches!(
+                validate_upload(&data, 1024),
+                Err(ImageError::InvalidFormat)
+            ));
+        }
+    }
+
+    #[test]
+    fn strips_exif_segment_from_jpeg() {
+        let mut jpeg = vec![0xFF, 0xD8];
+        // APP1 with EXIF payload
+        jpeg.extend_from_slice(&[0xFF, 0xE1, 0x00, 0x0A]);
+        jpeg.extend_from_slice(b"Exif\0\0GP");
+        // APP0 (JFIF) is kept
+        jpeg.extend_from_slice(&[0xFF, 0xE0, 0x00, 0x07]);
+        jpeg.extend_from_slice(b"JFIF\0");
+        // Start of scan, scan data, end of image
+        jpeg.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]);
+
+        let stripped = strip_exif(&jpeg, UploadImageFormat::Jpeg).unwrap();
+        assert!(!stripped.windows(4).any(|w| w == b"Exif"));
+        assert!(stripped.windows(4).any(|w| w == b"JFIF"));
+        assert!(stripped.ends_with(&[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]));
+    }
+}
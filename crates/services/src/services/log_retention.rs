@@ -0,0 +1,98 @@
+//! Size limits for stored execution process logs.
+//!
+//! Logs of a running process are capped at `max_bytes` as they are written; once a process
+//! has been finished for `compact_after_days`, its logs are compacted further to
+//! `compacted_bytes`. Both keep the head and tail of the log around an elision marker.
+
+use chrono::{Duration, Utc};
+use forge_core_db::models::execution_process_logs::ExecutionProcessLogs;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogRetentionPolicy {
+    pub max_bytes: usize,
+    pub compacted_bytes: usize,
+    pub compact_after_days: i64,
+}
+
+impl Default for LogRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_bytes: 16 * 1024 * 1024,
+            compacted_bytes: 1024 * 1024,
+            compact_after_days: 30,
+        }
+    }
+}
+
+impl LogRetentionPolicy {
+    /// Defaults, overridable via `FORGE_PROCESS_LOG_MAX_BYTES`,
+    /// `FORGE_PROCESS_LOG_COMPACTED_BYTES` and `FORGE_PROCESS_LOG_RETENTION_DAYS`.
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr + PartialOrd + Default>(name: &str) -> Option<T> {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<T>().ok())
+                .filter(|v| *v > T::default())
+        }
+
+        let defaults = Self::default();
+        Self {
+            max_bytes: var("FORGE_PROCESS_LOG_MAX_BYTES").unwrap_or(defaults.max_bytes),
+            compacted_bytes: var("FORGE_PROCESS_LOG_COMPACTED_BYTES")
+                .unwrap_or(defaults.compacted_bytes),
+            compact_after_days: var("FORGE_PROCESS_LOG_RETENTION_DAYS")
+                .unwrap_or(defaults.compact_after_days),
+        }
+    }
+
+    /// Size logs are cut back to once they exceed `max_bytes`, leaving room to append before
+    /// the next truncation
+    pub fn truncate_to(&self) -> usize {
+        self.max_bytes / 4 * 3
+    }
+}
+
+/// Truncates the stored logs of one process to `max_bytes`. Returns the new size if anything
+/// was dropped.
+pub async fn truncate_process_logs(
+    pool: &SqlitePool,
+    execution_id: Uuid,
+    max_bytes: usize,
+) -> Result<Option<usize>, sqlx::Error> {
+    let Some(record) = ExecutionProcessLogs::find_by_execution_id(pool, execution_id).await?
+    else {
+        return Ok(None);
+    };
+    let Some(truncated) = ExecutionProcessLogs::truncate_jsonl(&record.logs, max_bytes) else {
+        return Ok(None);
+    };
+    ExecutionProcessLogs::replace_logs(pool, execution_id, &truncated).await?;
+    Ok(Some(truncated.len()))
+}
+
+/// Compacts the logs of processes that finished more than `compact_after_days` ago. Returns
+/// how many processes were compacted.
+pub async fn compact_completed_process_logs(
+    pool: &SqlitePool,
+    policy: &LogRetentionPolicy,
+) -> Result<usize, sqlx::Error> {
+    let cutoff = Utc::now() - Duration::days(policy.compact_after_days);
+    let candidates =
+        ExecutionProcessLogs::find_compactable(pool, cutoff, policy.compacted_bytes as i64).await?;
+
+    let mut compacted = 0;
+    for execution_id in candidates {
+        if truncate_process_logs(pool, execution_id, policy.compacted_bytes)
+            .await?
+            .is_some()
+        {
+            compacted += 1;
+        }
+    }
+    if compacted > 0 {
+        tracing::info!("Compacted logs of {} finished execution process(es)", compacted);
+    }
+    Ok(compacted)
+}
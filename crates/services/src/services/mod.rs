@@ -1,5 +1,7 @@
 pub mod analytics;
 pub mod approvals;
+pub mod attempt_diff;
+pub mod attempt_export;
 pub mod auth;
 pub mod commit_message_generator;
 pub mod commit_validator;
@@ -15,10 +17,16 @@ pub mod filesystem_watcher;
 pub mod forge_config;
 pub mod git;
 pub mod git_cli;
+pub mod github_http;
 pub mod github_service;
 pub mod image;
+pub mod log_retention;
 pub mod notification;
 pub mod omni;
+pub mod orphan_worktrees;
 pub mod pr_monitor;
 pub mod profile_loader;
+pub mod shutdown;
+pub mod task_notifications;
+pub mod webhook;
 pub mod worktree_manager;
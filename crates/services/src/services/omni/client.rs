@@ -1,7 +1,23 @@
 use anyhow::Result;
+use reqwest::StatusCode;
 
 use super::types::{InstancesResponse, OmniInstance, SendTextRequest, SendTextResponse};
 
+/// A non-success response from the Omni API
+#[derive(Debug, thiserror::Error)]
+#[error("Omni API returned {status}: {body}")]
+pub struct OmniApiError {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+impl OmniApiError {
+    /// Whether the request may succeed if sent again (server errors and rate limiting)
+    pub fn is_transient(&self) -> bool {
+        self.status.is_server_error() || self.status == StatusCode::TOO_MANY_REQUESTS
+    }
+}
+
 pub struct OmniClient {
     base_url: String,
     api_key: Option<String>,
@@ -9,9 +25,11 @@ pub struct OmniClient {
 }
 
 impl OmniClient {
+    /// Trailing slashes are stripped from `base_url` so request paths can be appended to it.
+    /// Hosts should already be validated with `normalize_omni_host`.
     pub fn new(base_url: String, api_key: Option<String>) -> Self {
         Self {
-            base_url,
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
             api_key,
             client: reqwest::Client::new(),
         }
@@ -63,7 +81,7 @@ impl OmniClient {
                         .await
                         .unwrap_or_else(|_| "Unknown error".to_string());
                     tracing::error!("Omni API error response: {}", text);
-                    return Err(anyhow::anyhow!("Omni API returned {status}: {text}"));
+                    return Err(OmniApiError { status, body: text }.into());
                 }
                 resp.json().await?
             }
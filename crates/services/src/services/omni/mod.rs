@@ -4,11 +4,14 @@
 //! Provides notification services for task completion and status updates.
 
 pub mod client;
+pub mod render;
 pub mod service;
+pub mod throttle;
 pub mod types;
 
-pub use client::OmniClient;
-pub use service::OmniService;
+pub use client::{OmniApiError, OmniClient};
+pub use render::render_for_channel;
+pub use service::{NotificationDispatch, NotificationRetryPolicy, OmniService};
 pub use types::*;
 
 #[cfg(test)]
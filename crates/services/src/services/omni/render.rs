@@ -0,0 +1,218 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Channel types whose clients render markdown themselves.
+const MARKDOWN_CHANNELS: &[&str] = &["discord", "slack"];
+
+static LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"!?\[([^\]]*)\]\(([^)\s]+)\)").unwrap());
+static STRONG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\*\*([^*]+)\*\*|__([^_]+)__").unwrap());
+static INLINE_CODE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`([^`]+)`").unwrap());
+static HEADING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^#{1,6}\s+(.*?)\s*#*$").unwrap());
+static TABLE_SEPARATOR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\|?\s*:?-+:?\s*(\|\s*:?-+:?\s*)*\|?$").unwrap());
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ChannelFormat {
+    /// Sent as is
+    Markdown,
+    /// WhatsApp's own `*bold*` is kept for headings and strong text
+    WhatsApp,
+    /// Everything is flattened to plain text
+    Plain,
+}
+
+impl ChannelFormat {
+    fn for_channel(channel_type: &str) -> Self {
+        let channel_type = channel_type.trim().to_ascii_lowercase();
+        if MARKDOWN_CHANNELS.contains(&channel_type.as_str()) {
+            ChannelFormat::Markdown
+        } else if channel_type == "whatsapp" {
+            ChannelFormat::WhatsApp
+        } else {
+            ChannelFormat::Plain
+        }
+    }
+
+    fn emphasize(self, text: &str) -> String {
+        match self {
+            ChannelFormat::WhatsApp => format!("*{text}*"),
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// Renders markdown `text` for an Omni channel (`OmniInstance::channel_type`). Channels
+/// that display markdown get it unchanged; others get it flattened: code fences become
+/// indented text, links become `text (url)` and tables become `header: value` lines.
+/// Unknown channel types are treated as plain text.
+pub fn render_for_channel(text: &str, channel_type: &str) -> String {
+    let format = ChannelFormat::for_channel(channel_type);
+    if format == ChannelFormat::Markdown {
+        return text.to_string();
+    }
+
+    let mut out = Vec::new();
+    let mut lines = text.lines().peekable();
+    let mut in_fence = false;
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            out.push(format!("    {line}").trim_end().to_string());
+            continue;
+        }
+
+        if is_table_row(trimmed) && lines.peek().is_some_and(|next| is_separator(next)) {
+            lines.next();
+            let headers = table_cells(trimmed);
+            let mut first = true;
+            while let Some(row) = lines.next_if(|next| is_table_row(next.trim())) {
+                if !first {
+                    out.push(String::new());
+                }
+                first = false;
+                for (i, cell) in table_cells(row.trim()).iter().enumerate() {
+                    let value = render_inline(cell, format);
+                    match headers.get(i).filter(|header| !header.is_empty()) {
+                        Some(header) => {
+                            out.push(format!("{}: {value}", render_inline(header, format)))
+                        }
+                        None => out.push(value),
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(captures) = HEADING.captures(trimmed) {
+            out.push(format.emphasize(&render_inline(&captures[1], format)));
+        } else if let Some(item) = trimmed
+            .strip_prefix("* ")
+            .or_else(|| trimmed.strip_prefix("+ "))
+        {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            out.push(format!("{indent}- {}", render_inline(item, format)));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            out.push(render_inline(quote.trim_start(), format));
+        } else {
+            out.push(render_inline(line, format));
+        }
+    }
+
+    out.join("\n")
+}
+
+fn is_table_row(line: &str) -> bool {
+    line.starts_with('|') && line.len() > 1
+}
+
+fn is_separator(line: &str) -> bool {
+    TABLE_SEPARATOR.is_match(line.trim())
+}
+
+fn table_cells(row: &str) -> Vec<String> {
+    let row = row.strip_prefix('|').unwrap_or(row);
+    let row = row.strip_suffix('|').unwrap_or(row);
+    row.split('|').map(|cell| cell.trim().to_string()).collect()
+}
+
+fn render_inline(text: &str, format: ChannelFormat) -> String {
+    let text = INLINE_CODE.replace_all(text, "$1");
+    let text = LINK.replace_all(&text, |captures: &regex::Captures| {
+        let (label, url) = (&captures[1], &captures[2]);
+        if label.is_empty() || label == url {
+            url.to_string()
+        } else {
+            format!("{label} ({url})")
+        }
+    });
+    let text = STRONG.replace_all(&text, |captures: &regex::Captures| {
+        let inner = captures
+            .get(1)
+            .or_else(|| captures.get(2))
+            .unwrap()
+            .as_str();
+        format.emphasize(inner)
+    });
+    text.into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMIT_SUMMARY: &str = "## Fix login\n\
+        See [the PR](https://github.com/acme/app/pull/7) for **details**.\n\
+        \n\
+        ```rust\n\
+        fn main() {}\n\
+        ```\n\
+        * updated `auth.rs`";
+
+    #[test]
+    fn markdown_channels_are_left_alone() {
+        assert_eq!(
+            render_for_channel(COMMIT_SUMMARY, "discord"),
+            COMMIT_SUMMARY
+        );
+        assert_eq!(render_for_channel(COMMIT_SUMMARY, "Slack"), COMMIT_SUMMARY);
+    }
+
+    #[test]
+    fn plain_channels_get_flattened_text() {
+        assert_eq!(
+            render_for_channel(COMMIT_SUMMARY, "sms"),
+            "Fix login\n\
+             See the PR (https://github.com/acme/app/pull/7) for details.\n\
+             \n    fn main() {}\n\
+             - updated auth.rs"
+        );
+    }
+
+    #[test]
+    fn whatsapp_keeps_its_own_bold() {
+        assert_eq!(
+            render_for_channel(COMMIT_SUMMARY, "whatsapp"),
+            "*Fix login*\n\
+             See the PR (https://github.com/acme/app/pull/7) for *details*.\n\
+             \n    fn main() {}\n\
+             - updated auth.rs"
+        );
+    }
+
+    #[test]
+    fn tables_become_key_value_lines() {
+        let table = "Results:\n\
+            | Check | Status |\n\
+            |-------|:------:|\n\
+            | build | passed |\n\
+            | `lint` | [failed](https://ci.example.com/1) |\n\
+            Done";
+        assert_eq!(
+            render_for_channel(table, "telegram"),
+            "Results:\n\
+             Check: build\nStatus: passed\n\
+             \n\
+             Check: lint\nStatus: failed (https://ci.example.com/1)\n\
+             Done"
+        );
+    }
+
+    #[test]
+    fn bare_links_and_pipes_outside_tables_are_kept() {
+        assert_eq!(
+            render_for_channel(
+                "[https://a.example](https://a.example) | not a table",
+                "sms"
+            ),
+            "https://a.example | not a table"
+        );
+        assert_eq!(render_for_channel("| lonely row |", ""), "| lonely row |");
+    }
+}
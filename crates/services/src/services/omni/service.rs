@@ -1,11 +1,57 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
 use anyhow::Result;
+use serde_json::json;
+use sqlx::SqlitePool;
+use uuid::Uuid;
 
-use super::client::OmniClient;
+use super::{
+    client::{OmniApiError, OmniClient},
+    render::render_for_channel,
+    throttle::{DEFAULT_THROTTLE_WINDOW_SECS, NotificationThrottle, ThrottleDecision},
+};
 pub use super::types::*;
 
+/// Result of dispatching a task notification through the event filter and throttle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationDispatch {
+    Disabled,
+    Unsubscribed,
+    Sent { notification_id: String },
+    Suppressed { suppressed_count: u32 },
+}
+
+/// Bounded exponential backoff for delivering a recorded notification. Only transient
+/// failures (5xx, 429, connection errors) are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotificationRetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for NotificationRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl NotificationRetryPolicy {
+    /// Delay before the attempt following `attempt` (1-based)
+    fn delay(&self, attempt: u32) -> Duration {
+        self.base_delay * 2u32.saturating_pow(attempt.saturating_sub(1))
+    }
+}
+
 pub struct OmniService {
     config: OmniConfig,
     pub client: OmniClient,
+    throttle: NotificationThrottle,
+    retry_policy: NotificationRetryPolicy,
+    /// `channel_type` of each instance messages have been sent through
+    channel_types: Mutex<HashMap<String, String>>,
 }
 
 impl OmniService {
@@ -13,16 +59,25 @@ impl OmniService {
         let mut service = Self {
             config: OmniConfig::default(),
             client: OmniClient::new(String::new(), None),
+            throttle: NotificationThrottle::new(),
+            retry_policy: NotificationRetryPolicy::default(),
+            channel_types: Mutex::new(HashMap::new()),
         };
         service.apply_config(config);
         service
     }
 
+    pub fn with_retry_policy(mut self, retry_policy: NotificationRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     pub fn apply_config(&mut self, config: OmniConfig) {
         self.client = OmniClient::new(
             config.host.clone().unwrap_or_default(),
             config.api_key.clone(),
         );
+        self.channel_types.lock().unwrap().clear();
         self.config = config;
     }
 
@@ -59,28 +114,255 @@ impl OmniService {
             task_title
         );
 
-        let message = format!(
+        let message = Self::format_task_message(task_title, task_status, task_url);
+        self.send_message(instance, recipient, message).await
+    }
+
+    /// Sends a task notification unless its event isn't subscribed to or an identical
+    /// `(task_id, notification_type)` notification was sent within the configured
+    /// throttle window. Each notification
+    /// is recorded in `forge_omni_notifications` as `pending` and moves to `sent` or
+    /// `failed` once delivery (including retries) settles; suppressed duplicates bump
+    /// `suppressed_count` in the metadata of the sent row.
+    pub async fn dispatch_task_notification(
+        &self,
+        pool: &SqlitePool,
+        task_id: Uuid,
+        notification_type: &str,
+        task_title: &str,
+        task_status: &str,
+        task_url: Option<&str>,
+    ) -> Result<NotificationDispatch> {
+        if !self.config.enabled {
+            tracing::debug!("Omni notifications disabled");
+            return Ok(NotificationDispatch::Disabled);
+        }
+
+        if !self.config.notifies(notification_type) {
+            tracing::debug!(
+                "Skipping '{}' Omni notification for task {}: event not subscribed",
+                notification_type,
+                task_id
+            );
+            return Ok(NotificationDispatch::Unsubscribed);
+        }
+
+        if let ThrottleDecision::Suppress {
+            notification_id,
+            suppressed_count,
+        } = self
+            .throttle
+            .check(task_id, notification_type, self.throttle_window())
+        {
+            tracing::debug!(
+                "Suppressing duplicate '{}' Omni notification for task {} ({} suppressed)",
+                notification_type,
+                task_id,
+                suppressed_count
+            );
+            sqlx::query(
+                r#"UPDATE forge_omni_notifications
+                      SET metadata = json_set(COALESCE(metadata, '{}'), '$.suppressed_count', ?)
+                    WHERE id = ?"#,
+            )
+            .bind(suppressed_count)
+            .bind(&notification_id)
+            .execute(pool)
+            .await?;
+            return Ok(NotificationDispatch::Suppressed { suppressed_count });
+        }
+
+        let instance = self
+            .config
+            .instance
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No Omni instance configured"))?;
+        let recipient = self
+            .config
+            .recipient
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No recipient configured"))?;
+
+        let message = Self::format_task_message(task_title, task_status, task_url);
+        let notification_id = Uuid::new_v4().to_string();
+        sqlx::query(
+            r#"INSERT INTO forge_omni_notifications
+                   (id, task_id, notification_type, recipient, message, status, metadata)
+               VALUES (?, ?, ?, ?, ?, 'pending', ?)"#,
+        )
+        .bind(&notification_id)
+        .bind(task_id)
+        .bind(notification_type)
+        .bind(recipient)
+        .bind(&message)
+        .bind(json!({ "suppressed_count": 0 }).to_string())
+        .execute(pool)
+        .await?;
+
+        self.deliver(pool, &notification_id, instance, recipient, &message)
+            .await?;
+        self.throttle.record_sent(task_id, notification_type, notification_id.clone());
+        Ok(NotificationDispatch::Sent { notification_id })
+    }
+
+    /// Re-sends a notification whose delivery failed, through the currently configured
+    /// instance. Attempts keep counting from where the original delivery stopped.
+    pub async fn retry_notification(&self, pool: &SqlitePool, notification_id: &str) -> Result<()> {
+        if !self.config.enabled {
+            return Err(anyhow::anyhow!("Omni notifications are disabled"));
+        }
+
+        let row: Option<(Option<String>, String, String)> = sqlx::query_as(
+            "SELECT status, recipient, message FROM forge_omni_notifications WHERE id = ?",
+        )
+        .bind(notification_id)
+        .fetch_optional(pool)
+        .await?;
+        let Some((status, recipient, message)) = row else {
+            return Err(anyhow::anyhow!(
+                "Omni notification {notification_id} not found"
+            ));
+        };
+        if status.as_deref() != Some("failed") {
+            return Err(anyhow::anyhow!(
+                "Omni notification {notification_id} is {}; only failed notifications can be retried",
+                status.as_deref().unwrap_or("pending")
+            ));
+        }
+
+        let instance = self
+            .config
+            .instance
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No Omni instance configured"))?;
+
+        self.deliver(pool, notification_id, instance, &recipient, &message)
+            .await
+    }
+
+    /// Sends `message`, retrying transient failures per the retry policy, and records every
+    /// attempt on the notification row. The row stays `pending` while retries remain.
+    async fn deliver(
+        &self,
+        pool: &SqlitePool,
+        notification_id: &str,
+        instance: &str,
+        recipient: &str,
+        message: &str,
+    ) -> Result<()> {
+        let mut attempt = 1;
+        loop {
+            let result = self
+                .send_message(instance, recipient, message.to_string())
+                .await;
+            let will_retry = result
+                .as_ref()
+                .is_err_and(|e| attempt < self.retry_policy.max_attempts && Self::is_transient(e));
+            let (status, error_message) = match &result {
+                Ok(()) => ("sent", None),
+                Err(e) if will_retry => ("pending", Some(e.to_string())),
+                Err(e) => ("failed", Some(e.to_string())),
+            };
+
+            sqlx::query(
+                r#"UPDATE forge_omni_notifications
+                      SET status = ?, error_message = ?, sent_at = ?, attempts = attempts + 1
+                    WHERE id = ?"#,
+            )
+            .bind(status)
+            .bind(error_message)
+            .bind(result.is_ok().then(chrono::Utc::now))
+            .bind(notification_id)
+            .execute(pool)
+            .await?;
+
+            if !will_retry {
+                return result;
+            }
+
+            let delay = self.retry_policy.delay(attempt);
+            tracing::warn!(
+                "Omni notification {} failed on attempt {}, retrying in {:?}",
+                notification_id,
+                attempt,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    fn is_transient(error: &anyhow::Error) -> bool {
+        if let Some(api_error) = error.downcast_ref::<OmniApiError>() {
+            return api_error.is_transient();
+        }
+        error
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_connect() || e.is_timeout())
+    }
+
+    fn throttle_window(&self) -> Duration {
+        Duration::from_secs(
+            self.config
+                .throttle_window_secs
+                .unwrap_or(DEFAULT_THROTTLE_WINDOW_SECS),
+        )
+    }
+
+    fn format_task_message(
+        task_title: &str,
+        task_status: &str,
+        task_url: Option<&str>,
+    ) -> String {
+        format!(
             "🎯 Task Complete: {}\n\n\
              Status: {}\n\
              {}",
             task_title,
             task_status,
             task_url.map(|u| format!("URL: {u}")).unwrap_or_default()
-        );
+        )
+    }
+
+    /// The `channel_type` of `instance`, looked up once per configured host. When Omni
+    /// can't be queried the message is rendered as plain text, which every channel shows.
+    async fn channel_type(&self, instance: &str) -> String {
+        if let Some(channel_type) = self.channel_types.lock().unwrap().get(instance) {
+            return channel_type.clone();
+        }
+
+        match self.client.list_instances().await {
+            Ok(instances) => {
+                let mut channel_types = self.channel_types.lock().unwrap();
+                channel_types.extend(
+                    instances
+                        .into_iter()
+                        .map(|i| (i.instance_name, i.channel_type)),
+                );
+                channel_types.get(instance).cloned().unwrap_or_default()
+            }
+            Err(e) => {
+                tracing::debug!("Could not look up Omni channel for '{}': {}", instance, e);
+                String::new()
+            }
+        }
+    }
 
+    async fn send_message(&self, instance: &str, recipient: &str, message: String) -> Result<()> {
+        let message = render_for_channel(&message, &self.channel_type(instance).await);
         let request = match self.config.recipient_type {
             Some(RecipientType::PhoneNumber) => SendTextRequest {
-                phone_number: Some(recipient.clone()),
+                phone_number: Some(recipient.to_string()),
                 user_id: None,
                 text: message,
             },
             Some(RecipientType::UserId) => SendTextRequest {
                 phone_number: None,
-                user_id: Some(recipient.clone()),
+                user_id: Some(recipient.to_string()),
                 text: message,
             },
             None => SendTextRequest {
-                phone_number: Some(recipient.clone()),
+                phone_number: Some(recipient.to_string()),
                 user_id: None,
                 text: message,
             },
@@ -101,4 +383,59 @@ impl OmniService {
     pub async fn list_instances(&self) -> Result<Vec<OmniInstance>> {
         self.client.list_instances().await
     }
+
+    /// Dry run of a send: checks that `instance` exists on the Omni host and is healthy
+    /// and that `recipient` is well formed for `recipient_type`. Nothing is delivered or
+    /// recorded. Errors only when the Omni host itself can't be queried.
+    pub async fn validate_recipient(
+        &self,
+        instance: &str,
+        recipient: &str,
+        recipient_type: &RecipientType,
+    ) -> Result<RecipientValidation> {
+        let recipient = recipient.trim();
+        if let Err(reason) = Self::check_recipient(recipient, recipient_type) {
+            return Ok(RecipientValidation::unreachable(reason));
+        }
+
+        let instances = self.client.list_instances().await?;
+        let Some(found) = instances.iter().find(|i| i.instance_name == instance) else {
+            return Ok(RecipientValidation::unreachable(format!(
+                "Omni instance '{instance}' does not exist"
+            )));
+        };
+        if !found.is_healthy {
+            return Ok(RecipientValidation::unreachable(format!(
+                "Omni instance '{instance}' is not healthy (status: {})",
+                found.status
+            )));
+        }
+
+        Ok(RecipientValidation::reachable(format!(
+            "Would send to {recipient} via {}",
+            found.display_name
+        )))
+    }
+
+    fn check_recipient(recipient: &str, recipient_type: &RecipientType) -> Result<(), String> {
+        match recipient_type {
+            RecipientType::PhoneNumber => {
+                let digits = recipient.strip_prefix('+').unwrap_or(recipient);
+                if (7..=15).contains(&digits.len()) && digits.bytes().all(|b| b.is_ascii_digit()) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "'{recipient}' is not a phone number; expected 7 to 15 digits"
+                    ))
+                }
+            }
+            RecipientType::UserId => {
+                if !recipient.is_empty() && !recipient.contains(char::is_whitespace) {
+                    Ok(())
+                } else {
+                    Err(format!("'{recipient}' is not a valid user id"))
+                }
+            }
+        }
+    }
 }
@@ -7,7 +7,14 @@ use wiremock::{
     matchers::{header, method, path},
 };
 
-use super::{client::OmniClient, types::SendTextRequest};
+use super::{
+    client::OmniClient,
+    service::{NotificationDispatch, NotificationRetryPolicy, OmniService},
+    types::{
+        NotificationEvent, OmniConfig, OmniNotification, RecipientType, RecipientValidation,
+        SendTextRequest,
+    },
+};
 
 // NOTE: All API keys and secrets in this test file are fake test values only.
 // They are used solely for testing HTTP header functionality and are not real credentials.
@@ -253,6 +260,38 @@ async fn test_list_instances_success() {
     assert_eq!(instances[1].channel_type, "discord");
 }
 
+/// Test that a trailing slash on the host doesn't produce `//` in request paths
+#[tokio::test]
+async fn test_trailing_slash_host_is_normalized() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/instances/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "channels": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let service = OmniService::new(
+        OmniConfig {
+            host: Some(format!("{}/", mock_server.uri())),
+            ..Default::default()
+        }
+        .normalized()
+        .expect("host with a trailing slash should be valid"),
+    );
+    assert_eq!(service.config().host, Some(mock_server.uri()));
+
+    let client = OmniClient::new(format!("{}//", mock_server.uri()), None);
+    let instances = client
+        .list_instances()
+        .await
+        .expect("Should list instances despite the trailing slash");
+    assert!(instances.is_empty());
+}
+
 /// Test list_instances with API key
 #[tokio::test]
 async fn test_list_instances_with_api_key() {
@@ -348,3 +387,466 @@ async fn test_send_text_request_body_format() {
 
     assert!(response.success);
 }
+
+/// Test that repeated notifications for the same task and type are throttled
+#[tokio::test]
+async fn test_dispatch_throttles_duplicate_notifications() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/instance/forge/send-text"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "message_id": "msg_throttled",
+            "status": "sent",
+            "error": null
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let pool = setup_notifications_pool().await;
+
+    let service = OmniService::new(OmniConfig {
+        enabled: true,
+        host: Some(mock_server.uri()),
+        api_key: None,
+        instance: Some("forge".to_string()),
+        recipient: Some("1234567890".to_string()),
+        recipient_type: Some(RecipientType::PhoneNumber),
+        throttle_window_secs: Some(600),
+        events: Default::default(),
+    });
+    let task_id = uuid::Uuid::new_v4();
+
+    let mut outcomes = Vec::new();
+    for _ in 0..3 {
+        let outcome = service
+            .dispatch_task_notification(&pool, task_id, "failed", "Flaky task", "failed", None)
+            .await
+            .expect("dispatch should succeed");
+        outcomes.push(outcome);
+    }
+
+    assert!(matches!(outcomes[0], NotificationDispatch::Sent { .. }));
+    assert_eq!(
+        outcomes[1],
+        NotificationDispatch::Suppressed {
+            suppressed_count: 1
+        }
+    );
+    assert_eq!(
+        outcomes[2],
+        NotificationDispatch::Suppressed {
+            suppressed_count: 2
+        }
+    );
+
+    let rows: Vec<(String, Option<String>)> =
+        sqlx::query_as("SELECT status, metadata FROM forge_omni_notifications")
+            .fetch_all(&pool)
+            .await
+            .expect("should load notifications");
+    assert_eq!(rows.len(), 1, "only the first notification should be recorded");
+    assert_eq!(rows[0].0, "sent");
+
+    let metadata: serde_json::Value =
+        serde_json::from_str(rows[0].1.as_deref().expect("metadata should be set")).unwrap();
+    assert_eq!(metadata["suppressed_count"], 2);
+}
+
+async fn setup_notifications_pool() -> sqlx::SqlitePool {
+    let pool = sqlx::SqlitePool::connect("sqlite::memory:")
+        .await
+        .expect("failed to create in-memory sqlite pool");
+    sqlx::query(
+        r#"CREATE TABLE forge_omni_notifications (
+                id TEXT PRIMARY KEY,
+                task_id TEXT,
+                notification_type TEXT NOT NULL,
+                recipient TEXT NOT NULL,
+                message TEXT NOT NULL,
+                sent_at DATETIME,
+                status TEXT DEFAULT 'pending',
+                error_message TEXT,
+                metadata TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                attempts INTEGER NOT NULL DEFAULT 0
+            )"#,
+    )
+    .execute(&pool)
+    .await
+    .expect("failed to create forge_omni_notifications table for tests");
+    pool
+}
+
+fn retrying_service(host: String) -> OmniService {
+    OmniService::new(OmniConfig {
+        enabled: true,
+        host: Some(host),
+        api_key: None,
+        instance: Some("forge".to_string()),
+        recipient: Some("1234567890".to_string()),
+        recipient_type: Some(RecipientType::PhoneNumber),
+        throttle_window_secs: None,
+        events: Default::default(),
+    })
+    .with_retry_policy(NotificationRetryPolicy {
+        max_attempts: 3,
+        base_delay: std::time::Duration::ZERO,
+    })
+}
+
+fn sent_response() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "success": true,
+        "message_id": "msg_retry",
+        "status": "sent",
+        "error": null
+    }))
+}
+
+async fn notification_row(
+    pool: &sqlx::SqlitePool,
+    id: &str,
+) -> (String, Option<String>, i64, Option<String>) {
+    sqlx::query_as(
+        "SELECT status, error_message, attempts, sent_at FROM forge_omni_notifications WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .expect("notification row should exist")
+}
+
+/// Test that a transient 503 is retried and the notification ends up sent
+#[tokio::test]
+async fn test_dispatch_retries_transient_failures() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/instance/forge/send-text"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("Service temporarily unavailable"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/instance/forge/send-text"))
+        .respond_with(sent_response())
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let pool = setup_notifications_pool().await;
+    let service = retrying_service(mock_server.uri());
+
+    let outcome = service
+        .dispatch_task_notification(&pool, uuid::Uuid::new_v4(), "done", "Task", "done", None)
+        .await
+        .expect("dispatch should succeed after a retry");
+    let NotificationDispatch::Sent { notification_id } = outcome else {
+        panic!("expected the notification to be sent, got {outcome:?}");
+    };
+
+    let (status, error_message, attempts, sent_at) =
+        notification_row(&pool, &notification_id).await;
+    assert_eq!(status, "sent");
+    assert_eq!(attempts, 2);
+    assert!(error_message.is_none());
+    assert!(sent_at.is_some());
+}
+
+/// Test that client errors are not retried automatically but can be re-sent on demand
+#[tokio::test]
+async fn test_retry_notification_resends_failed_rows() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/instance/forge/send-text"))
+        .respond_with(ResponseTemplate::new(400).set_body_string("Bad request"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/instance/forge/send-text"))
+        .respond_with(sent_response())
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let pool = setup_notifications_pool().await;
+    let service = retrying_service(mock_server.uri());
+
+    let result = service
+        .dispatch_task_notification(&pool, uuid::Uuid::new_v4(), "done", "Task", "done", None)
+        .await;
+    assert!(result.is_err(), "a 400 should fail without retrying");
+
+    let (notification_id,): (String,) = sqlx::query_as("SELECT id FROM forge_omni_notifications")
+        .fetch_one(&pool)
+        .await
+        .expect("failed notification should be recorded");
+    let (status, error_message, attempts, sent_at) =
+        notification_row(&pool, &notification_id).await;
+    assert_eq!(status, "failed");
+    assert_eq!(attempts, 1);
+    assert!(error_message.unwrap().contains("400"));
+    assert!(sent_at.is_none());
+
+    service
+        .retry_notification(&pool, &notification_id)
+        .await
+        .expect("retry should send the notification");
+
+    let (status, error_message, attempts, _) = notification_row(&pool, &notification_id).await;
+    assert_eq!(status, "sent");
+    assert_eq!(attempts, 2);
+    assert!(error_message.is_none());
+
+    let err = service
+        .retry_notification(&pool, &notification_id)
+        .await
+        .expect_err("sent notifications should not be retried");
+    assert!(err.to_string().contains("only failed notifications"));
+}
+
+/// Test that events missing from the subscribed set are not sent or recorded
+#[tokio::test]
+async fn test_dispatch_skips_unsubscribed_events() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/instance/forge/send-text"))
+        .respond_with(sent_response())
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let pool = setup_notifications_pool().await;
+    let mut service = retrying_service(mock_server.uri());
+    let mut config = service.config().clone();
+    config.events = [NotificationEvent::TaskFailed].into();
+    service.apply_config(config);
+    let task_id = uuid::Uuid::new_v4();
+
+    let completed = service
+        .dispatch_task_notification(&pool, task_id, "task_completed", "Task", "done", None)
+        .await
+        .expect("unsubscribed dispatch should not fail");
+    assert_eq!(completed, NotificationDispatch::Unsubscribed);
+
+    let failed = service
+        .dispatch_task_notification(&pool, task_id, "task_failed", "Task", "failed", None)
+        .await
+        .expect("subscribed dispatch should succeed");
+    assert!(matches!(failed, NotificationDispatch::Sent { .. }));
+
+    let notification_types: Vec<(String,)> =
+        sqlx::query_as("SELECT notification_type FROM forge_omni_notifications")
+            .fetch_all(&pool)
+            .await
+            .expect("should load notifications");
+    assert_eq!(notification_types, vec![("task_failed".to_string(),)]);
+}
+
+/// Test that stored notification rows map onto typed fields, tolerating malformed ones
+#[tokio::test]
+async fn test_notification_rows_map_to_typed_model() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/instance/forge/send-text"))
+        .respond_with(sent_response())
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let pool = setup_notifications_pool().await;
+    let service = retrying_service(mock_server.uri());
+    let task_id = uuid::Uuid::new_v4();
+    let outcome = service
+        .dispatch_task_notification(&pool, task_id, "task_completed", "Task", "done", None)
+        .await
+        .expect("dispatch should succeed");
+    let NotificationDispatch::Sent { notification_id } = outcome else {
+        panic!("expected the notification to be sent, got {outcome:?}");
+    };
+
+    // A row whose task id and metadata can't be decoded must not break the listing
+    sqlx::query(
+        r#"INSERT INTO forge_omni_notifications
+               (id, task_id, notification_type, recipient, message, status, metadata,
+                created_at)
+           VALUES ('malformed', 'task-1', 'task_failed', '1234567890', 'failed', NULL,
+                   '{not json', '2025-01-01 09:30:00')"#,
+    )
+    .execute(&pool)
+    .await
+    .expect("should seed a malformed notification");
+
+    let query = format!(
+        "SELECT {} FROM forge_omni_notifications ORDER BY created_at DESC",
+        OmniNotification::COLUMNS
+    );
+    let notifications: Vec<OmniNotification> = sqlx::query_as(&query)
+        .fetch_all(&pool)
+        .await
+        .expect("rows should map to OmniNotification");
+    assert_eq!(notifications.len(), 2);
+
+    let sent = &notifications[0];
+    assert_eq!(sent.id, notification_id);
+    assert_eq!(sent.task_id, Some(task_id));
+    assert_eq!(sent.status, "sent");
+    assert_eq!(sent.attempts, 1);
+    assert_eq!(
+        sent.metadata,
+        Some(serde_json::json!({ "suppressed_count": 0 }))
+    );
+    assert!(sent.sent_at.is_some());
+
+    let malformed = &notifications[1];
+    assert_eq!(malformed.id, "malformed");
+    assert_eq!(malformed.task_id, None);
+    assert_eq!(malformed.status, "pending");
+    assert_eq!(malformed.sent_at, None);
+    assert_eq!(malformed.metadata, None);
+    assert_eq!(
+        malformed.created_at.to_rfc3339(),
+        "2025-01-01T09:30:00+00:00"
+    );
+
+    let json = serde_json::to_value(sent).expect("notification should serialize");
+    assert_eq!(json["task_id"], task_id.to_string());
+    assert_eq!(json["metadata"]["suppressed_count"], 0);
+}
+
+fn instances_response() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "channels": [
+            {
+                "instance_name": "forge",
+                "channel_type": "whatsapp",
+                "display_name": "WhatsApp - forge",
+                "status": "connected",
+                "is_healthy": true
+            },
+            {
+                "instance_name": "offline",
+                "channel_type": "discord",
+                "display_name": "Discord - offline",
+                "status": "disconnected",
+                "is_healthy": false
+            }
+        ]
+    }))
+}
+
+/// Test that a dry run reports a reachable recipient without sending a message
+#[tokio::test]
+async fn test_validate_recipient_reachable() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/instances/"))
+        .respond_with(instances_response())
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/instance/forge/send-text"))
+        .respond_with(sent_response())
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let service = retrying_service(mock_server.uri());
+    let validation = service
+        .validate_recipient("forge", " +1234567890 ", &RecipientType::PhoneNumber)
+        .await
+        .expect("validation should reach Omni");
+    assert_eq!(
+        validation,
+        RecipientValidation {
+            reachable: true,
+            detail: "Would send to +1234567890 via WhatsApp - forge".to_string(),
+        }
+    );
+}
+
+/// Test that a dry run reports why a recipient can't be reached
+#[tokio::test]
+async fn test_validate_recipient_unreachable() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/instances/"))
+        .respond_with(instances_response())
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let service = retrying_service(mock_server.uri());
+
+    let unknown = service
+        .validate_recipient("missing", "user_abc123", &RecipientType::UserId)
+        .await
+        .expect("validation should reach Omni");
+    assert!(!unknown.reachable);
+    assert!(unknown.detail.contains("does not exist"));
+
+    let unhealthy = service
+        .validate_recipient("offline", "user_abc123", &RecipientType::UserId)
+        .await
+        .expect("validation should reach Omni");
+    assert!(!unhealthy.reachable);
+    assert!(unhealthy.detail.contains("disconnected"));
+
+    // Malformed recipients are rejected before Omni is queried
+    let malformed = service
+        .validate_recipient("forge", "call me", &RecipientType::PhoneNumber)
+        .await
+        .expect("malformed recipients are not an error");
+    assert!(!malformed.reachable);
+    assert!(malformed.detail.contains("not a phone number"));
+}
+
+/// Test that messages are rendered for the channel of the instance they go through
+#[tokio::test]
+async fn test_dispatch_renders_markdown_for_the_channel() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/instances/"))
+        .respond_with(instances_response())
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/instance/forge/send-text"))
+        .and(wiremock::matchers::body_partial_json(serde_json::json!({
+            "text": "🎯 Task Complete: Ship *v2* (see docs (https://docs.example.com))\n\nStatus: done"
+        })))
+        .respond_with(sent_response())
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let pool = setup_notifications_pool().await;
+    let service = retrying_service(mock_server.uri());
+    for _ in 0..2 {
+        let dispatch = service
+            .dispatch_task_notification(
+                &pool,
+                uuid::Uuid::new_v4(),
+                "task_completed",
+                "Ship **v2** (see [docs](https://docs.example.com))",
+                "done",
+                None,
+            )
+            .await
+            .expect("dispatch should succeed");
+        assert!(matches!(dispatch, NotificationDispatch::Sent { .. }));
+    }
+}
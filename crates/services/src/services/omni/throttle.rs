@@ -0,0 +1,123 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use uuid::Uuid;
+
+/// Window used when the Omni config does not set `throttle_window_secs`.
+pub const DEFAULT_THROTTLE_WINDOW_SECS: u64 = 300;
+
+/// Outcome of checking a notification against the throttle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThrottleDecision {
+    /// No identical notification was sent within the window.
+    Send,
+    /// An identical notification was already sent within the window.
+    Suppress {
+        /// Row id of the notification that was sent.
+        notification_id: String,
+        /// Number of duplicates suppressed so far, including this one.
+        suppressed_count: u32,
+    },
+}
+
+struct ThrottleEntry {
+    sent_at: Instant,
+    notification_id: String,
+    suppressed_count: u32,
+}
+
+/// Tracks recently sent notifications per `(task_id, notification_type)` so a
+/// flapping task does not produce one message per transition.
+#[derive(Default)]
+pub struct NotificationThrottle {
+    entries: Mutex<HashMap<(Uuid, String), ThrottleEntry>>,
+}
+
+impl NotificationThrottle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks whether a notification may be sent, counting it as suppressed
+    /// when an identical one went out within `window`.
+    pub fn check(
+        &self,
+        task_id: Uuid,
+        notification_type: &str,
+        window: Duration,
+    ) -> ThrottleDecision {
+        let mut entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+        entries.retain(|_, entry| now.duration_since(entry.sent_at) < window);
+
+        match entries.get_mut(&(task_id, notification_type.to_string())) {
+            Some(entry) => {
+                entry.suppressed_count += 1;
+                ThrottleDecision::Suppress {
+                    notification_id: entry.notification_id.clone(),
+                    suppressed_count: entry.suppressed_count,
+                }
+            }
+            None => ThrottleDecision::Send,
+        }
+    }
+
+    /// Records a notification that was actually sent, opening a new window.
+    pub fn record_sent(&self, task_id: Uuid, notification_type: &str, notification_id: String) {
+        self.entries.lock().unwrap().insert(
+            (task_id, notification_type.to_string()),
+            ThrottleEntry {
+                sent_at: Instant::now(),
+                notification_id,
+                suppressed_count: 0,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suppresses_duplicates_within_window() {
+        let throttle = NotificationThrottle::new();
+        let task_id = Uuid::new_v4();
+        let window = Duration::from_secs(60);
+
+        assert_eq!(
+            throttle.check(task_id, "failed", window),
+            ThrottleDecision::Send
+        );
+        throttle.record_sent(task_id, "failed", "n1".to_string());
+
+        assert_eq!(
+            throttle.check(task_id, "failed", window),
+            ThrottleDecision::Suppress {
+                notification_id: "n1".to_string(),
+                suppressed_count: 1,
+            }
+        );
+        // A different notification type for the same task is not throttled.
+        assert_eq!(
+            throttle.check(task_id, "completed", window),
+            ThrottleDecision::Send
+        );
+    }
+
+    #[test]
+    fn sends_again_after_window_elapses() {
+        let throttle = NotificationThrottle::new();
+        let task_id = Uuid::new_v4();
+
+        throttle.record_sent(task_id, "failed", "n1".to_string());
+
+        assert_eq!(
+            throttle.check(task_id, "failed", Duration::ZERO),
+            ThrottleDecision::Send
+        );
+    }
+}
@@ -1,5 +1,12 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, Row, sqlite::SqliteRow};
+use thiserror::Error;
 use ts_rs_forge::TS;
+use uuid::Uuid;
 
 /// Local Omni recipient type options.
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
@@ -8,6 +15,46 @@ pub enum RecipientType {
     UserId,
 }
 
+/// Events a user can subscribe to Omni notifications for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+pub enum NotificationEvent {
+    TaskCompleted,
+    TaskFailed,
+    AttemptMerged,
+    PrCreated,
+}
+
+impl NotificationEvent {
+    /// The `notification_type` recorded for this event.
+    pub fn notification_type(self) -> &'static str {
+        match self {
+            NotificationEvent::TaskCompleted => "task_completed",
+            NotificationEvent::TaskFailed => "task_failed",
+            NotificationEvent::AttemptMerged => "attempt_merged",
+            NotificationEvent::PrCreated => "pr_created",
+        }
+    }
+
+    pub fn from_notification_type(notification_type: &str) -> Option<Self> {
+        [
+            NotificationEvent::TaskCompleted,
+            NotificationEvent::TaskFailed,
+            NotificationEvent::AttemptMerged,
+            NotificationEvent::PrCreated,
+        ]
+        .into_iter()
+        .find(|event| event.notification_type() == notification_type)
+    }
+
+    /// Whether a subscription to `events` covers `notification_type`. An empty set
+    /// subscribes to every event, and types that aren't a [`NotificationEvent`] always pass.
+    pub fn subscribed(events: &HashSet<NotificationEvent>, notification_type: &str) -> bool {
+        events.is_empty()
+            || Self::from_notification_type(notification_type)
+                .is_none_or(|event| events.contains(&event))
+    }
+}
+
 /// Forge-scoped Omni configuration payload.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
 pub struct OmniConfig {
@@ -17,6 +64,135 @@ pub struct OmniConfig {
     pub instance: Option<String>,
     pub recipient: Option<String>,
     pub recipient_type: Option<RecipientType>,
+    /// Seconds during which duplicate notifications for the same task and
+    /// notification type are suppressed. Defaults to five minutes when unset.
+    #[serde(default)]
+    pub throttle_window_secs: Option<u64>,
+    /// Events to notify on. Empty means every event, which is how configs saved before
+    /// event filtering behave.
+    #[serde(default)]
+    pub events: HashSet<NotificationEvent>,
+}
+
+/// An Omni host that can't be used as the base of request URLs.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid Omni host '{host}': {reason}")]
+pub struct InvalidOmniHost {
+    pub host: String,
+    pub reason: &'static str,
+}
+
+/// Normalizes an Omni host into a request base URL: surrounding whitespace and
+/// trailing slashes are removed, and an http(s) scheme and host name are required.
+pub fn normalize_omni_host(host: &str) -> Result<String, InvalidOmniHost> {
+    let invalid = |reason| InvalidOmniHost {
+        host: host.to_string(),
+        reason,
+    };
+    let normalized = host.trim().trim_end_matches('/');
+    let url = reqwest::Url::parse(normalized)
+        .map_err(|_| invalid("expected a URL such as https://omni.example.com"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid("the URL must start with http:// or https://"));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(invalid("the URL has no host name"));
+    }
+    Ok(normalized.to_string())
+}
+
+impl OmniConfig {
+    /// Whether a notification of `notification_type` should be sent. Types that don't
+    /// correspond to a [`NotificationEvent`] are never filtered.
+    pub fn notifies(&self, notification_type: &str) -> bool {
+        NotificationEvent::subscribed(&self.events, notification_type)
+    }
+
+    /// Returns the config with its host normalized; a blank host is treated as unset.
+    pub fn normalized(mut self) -> Result<Self, InvalidOmniHost> {
+        self.host = match self.host.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(host) => Some(normalize_omni_host(host)?),
+        };
+        Ok(self)
+    }
+}
+
+/// A notification recorded in `forge_omni_notifications`.
+#[derive(Clone, Debug, Serialize)]
+pub struct OmniNotification {
+    pub id: String,
+    pub task_id: Option<Uuid>,
+    pub notification_type: String,
+    pub status: String,
+    pub message: String,
+    pub error_message: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub metadata: Option<Value>,
+    pub attempts: i64,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for OmniNotification {
+    /// History is best-effort: a task id or metadata that can't be decoded reads as unset
+    /// instead of failing the whole listing.
+    fn from_row(row: &'r SqliteRow) -> Result<Self, sqlx::Error> {
+        let task_id = row
+            .try_get::<Option<Uuid>, _>("task_id")
+            .unwrap_or_else(|_| {
+                row.try_get::<Option<String>, _>("task_id")
+                    .ok()
+                    .flatten()
+                    .and_then(|id| Uuid::parse_str(&id).ok())
+            });
+        let metadata = row
+            .try_get::<Option<String>, _>("metadata")
+            .ok()
+            .flatten()
+            .and_then(|raw| serde_json::from_str(&raw).ok());
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            task_id,
+            notification_type: row.try_get("notification_type")?,
+            status: row.try_get("status")?,
+            message: row.try_get("message")?,
+            error_message: row.try_get("error_message")?,
+            sent_at: row.try_get("sent_at")?,
+            created_at: row.try_get("created_at")?,
+            metadata,
+            attempts: row.try_get("attempts")?,
+        })
+    }
+}
+
+impl OmniNotification {
+    /// Columns to select into an [`OmniNotification`]; a missing status reads as `pending`.
+    pub const COLUMNS: &str = "id, task_id, notification_type, COALESCE(status, 'pending') AS status, message, error_message, sent_at, created_at, metadata, attempts";
+}
+
+/// Whether Omni could deliver to a recipient, determined without sending a message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RecipientValidation {
+    pub reachable: bool,
+    /// Where a message would go, or why it can't be delivered
+    pub detail: String,
+}
+
+impl RecipientValidation {
+    pub(super) fn reachable(detail: String) -> Self {
+        Self {
+            reachable: true,
+            detail,
+        }
+    }
+
+    pub(super) fn unreachable(detail: String) -> Self {
+        Self {
+            reachable: false,
+            detail,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -95,15 +271,69 @@ mod tests {
             instance: None,
             recipient: None,
             recipient_type: None,
+            throttle_window_secs: None,
+            events: HashSet::new(),
         };
 
         assert!(!config.enabled);
+        assert!(config.notifies("task_completed"));
         assert_eq!(config.host, Some("https://omni.example.com".to_string()));
         assert!(config.instance.is_none());
         assert!(config.recipient.is_none());
         assert!(config.recipient_type.is_none());
     }
 
+    #[test]
+    fn test_schemeless_host_is_rejected() {
+        let err = normalize_omni_host("omni.example.com").unwrap_err();
+        assert_eq!(err.host, "omni.example.com");
+        assert!(err.to_string().contains("Invalid Omni host"));
+
+        assert!(normalize_omni_host("ftp://omni.example.com").is_err());
+
+        let config = OmniConfig {
+            host: Some("omni.example.com:8882".to_string()),
+            ..Default::default()
+        };
+        assert!(config.normalized().is_err());
+    }
+
+    #[test]
+    fn test_host_is_normalized() {
+        assert_eq!(
+            normalize_omni_host("  https://omni.example.com/// ").unwrap(),
+            "https://omni.example.com"
+        );
+        assert_eq!(
+            normalize_omni_host("http://localhost:8882/omni/").unwrap(),
+            "http://localhost:8882/omni"
+        );
+
+        let blank = OmniConfig {
+            host: Some("  ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(blank.normalized().unwrap().host, None);
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let config = OmniConfig {
+            events: HashSet::from([NotificationEvent::TaskFailed, NotificationEvent::PrCreated]),
+            ..Default::default()
+        };
+        assert!(config.notifies("task_failed"));
+        assert!(config.notifies("pr_created"));
+        assert!(!config.notifies("task_completed"));
+        assert!(!config.notifies("attempt_merged"));
+        // Ad-hoc notification types aren't subscribable, so they always go out
+        assert!(config.notifies("custom"));
+
+        let legacy: OmniConfig = serde_json::from_str(r#"{"enabled":true}"#).unwrap();
+        assert!(legacy.events.is_empty());
+        assert!(legacy.notifies("task_completed"));
+    }
+
     #[test]
     fn test_send_text_request_serialization() {
         let req = SendTextRequest {
@@ -0,0 +1,157 @@
+//! Detects worktree directories left on disk that no task attempt or execution run references,
+//! typically after a crash between creating a worktree and recording it.
+
+use std::{
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use forge_core_db::models::{execution_run::ExecutionRun, task_attempt::TaskAttempt};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs_forge::TS;
+
+use super::worktree_manager::WorktreeManager;
+
+/// Unreferenced directories younger than this are left alone, since a worktree exists briefly
+/// before its attempt records the `container_ref`.
+pub const DEFAULT_ORPHAN_MIN_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Error)]
+pub enum OrphanWorktreeError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OrphanScanOptions {
+    /// Minimum time since an orphan was last modified
+    pub min_age: Duration,
+    /// Report orphans without removing them
+    pub dry_run: bool,
+}
+
+impl Default for OrphanScanOptions {
+    fn default() -> Self {
+        Self {
+            min_age: DEFAULT_ORPHAN_MIN_AGE,
+            dry_run: true,
+        }
+    }
+}
+
+impl OrphanScanOptions {
+    /// Options for the startup scan: `FORGE_ORPHAN_WORKTREE_MIN_AGE_SECS` overrides the age
+    /// threshold and `FORGE_REMOVE_ORPHAN_WORKTREES=1` turns off the dry run.
+    pub fn from_env() -> Self {
+        let min_age = std::env::var("FORGE_ORPHAN_WORKTREE_MIN_AGE_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_ORPHAN_MIN_AGE);
+        let remove = std::env::var("FORGE_REMOVE_ORPHAN_WORKTREES")
+            .is_ok_and(|v| matches!(v.trim(), "1" | "true" | "yes"));
+        Self {
+            min_age,
+            dry_run: !remove,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct OrphanedWorktree {
+    pub path: String,
+    /// Seconds since the directory was last modified
+    pub age_secs: u64,
+    /// Whether this scan removed the directory
+    pub removed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct OrphanWorktreeReport {
+    pub base_dir: String,
+    pub dry_run: bool,
+    pub min_age_secs: u64,
+    /// Number of directories examined
+    pub scanned: usize,
+    /// Unreferenced directories older than `min_age_secs`
+    pub orphans: Vec<OrphanedWorktree>,
+    /// Unreferenced directories skipped for being younger than `min_age_secs`
+    pub too_recent: usize,
+}
+
+/// Walks `base_dir` and reports directories that are not the `container_ref` of any task
+/// attempt or execution run, removing them unless `options.dry_run` is set.
+pub async fn scan_orphaned_worktrees(
+    pool: &SqlitePool,
+    base_dir: &Path,
+    options: OrphanScanOptions,
+) -> Result<OrphanWorktreeReport, OrphanWorktreeError> {
+    let mut report = OrphanWorktreeReport {
+        base_dir: base_dir.to_string_lossy().to_string(),
+        dry_run: options.dry_run,
+        min_age_secs: options.min_age.as_secs(),
+        scanned: 0,
+        orphans: Vec::new(),
+        too_recent: 0,
+    };
+    if !base_dir.exists() {
+        return Ok(report);
+    }
+
+    let now = SystemTime::now();
+    let mut entries = std::fs::read_dir(base_dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for path in entries {
+        if !path.is_dir() {
+            continue;
+        }
+        report.scanned += 1;
+
+        let path_str = path.to_string_lossy().to_string();
+        if TaskAttempt::container_ref_exists(pool, &path_str).await?
+            || ExecutionRun::container_ref_exists(pool, &path_str).await?
+        {
+            continue;
+        }
+
+        let age = std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .unwrap_or_default();
+        if age < options.min_age {
+            report.too_recent += 1;
+            continue;
+        }
+
+        let removed = if options.dry_run {
+            false
+        } else {
+            match WorktreeManager::cleanup_worktree(&path, None).await {
+                Ok(()) => {
+                    tracing::info!("Removed orphaned worktree: {}", path_str);
+                    true
+                }
+                Err(e) => {
+                    tracing::error!("Failed to remove orphaned worktree {}: {}", path_str, e);
+                    false
+                }
+            }
+        };
+
+        report.orphans.push(OrphanedWorktree {
+            path: path_str,
+            age_secs: age.as_secs(),
+            removed,
+        });
+    }
+
+    Ok(report)
+}
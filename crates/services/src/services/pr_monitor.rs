@@ -9,7 +9,7 @@ use forge_core_db::{
     },
 };
 use serde_json::json;
-use sqlx::error::Error as SqlxError;
+use sqlx::{SqlitePool, error::Error as SqlxError};
 use thiserror::Error;
 use tokio::{sync::RwLock, time::interval};
 use tracing::{debug, error, info, warn};
@@ -17,6 +17,7 @@ use tracing::{debug, error, info, warn};
 use crate::services::{
     analytics::AnalyticsContext,
     config::Config,
+    forge_config::ForgeConfigService,
     github_service::{GitHubRepoInfo, GitHubService, GitHubServiceError},
 };
 
@@ -32,6 +33,22 @@ enum PrMonitorError {
     Sqlx(#[from] SqlxError),
 }
 
+/// Moves `task` to in-review after a PR was opened for one of its attempts, when it is in
+/// progress and its project hasn't turned `review_on_pr` off. Returns whether it moved.
+pub async fn review_task_for_new_pr(
+    pool: &SqlitePool,
+    forge_config: &ForgeConfigService,
+    task: &Task,
+) -> anyhow::Result<bool> {
+    if !forge_config.review_on_pr(task.project_id).await? {
+        return Ok(false);
+    }
+    Ok(
+        Task::update_status_from(pool, task.id, TaskStatus::InProgress, TaskStatus::InReview)
+            .await?,
+    )
+}
+
 /// Service to monitor GitHub PRs and update task status when they are merged
 pub struct PrMonitorService {
     db: DBService,
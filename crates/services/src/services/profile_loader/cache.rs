@@ -1,8 +1,11 @@
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
-    sync::Arc,
-    time::Duration,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
 };
 
 /// Profile Cache with Hot-Reload Support
@@ -11,10 +14,63 @@ use std::{
 use anyhow::Result;
 use forge_core_executors::profile::ExecutorConfigs;
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
-use tokio::sync::RwLock;
+use serde::Serialize;
+use tokio::sync::{RwLock, broadcast};
 use uuid::Uuid;
 
-use super::genie_profiles::GenieProfileLoader;
+use super::genie_profiles::{GenieProfileLoader, agent_file_stem};
+
+/// Default reload interval when file watching is unavailable.
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 30;
+
+/// Poll interval used as a fallback, overridable via `FORGE_PROFILE_POLL_INTERVAL_SECS`.
+fn poll_interval_from_env() -> Duration {
+    let secs = std::env::var("FORGE_PROFILE_POLL_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_POLL_INTERVAL_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Timing of the .genie file watcher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileCacheConfig {
+    /// Quiet period after the last relevant file event before profiles are reloaded, so a
+    /// burst of edits causes a single reload
+    pub debounce: Duration,
+
+    /// How often the watcher thread wakes up to check whether the debounce has elapsed
+    pub poll_interval: Duration,
+}
+
+impl Default for ProfileCacheConfig {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_millis(500),
+            poll_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Published after each successful reload so clients can refresh their executor/variant lists
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProfilesReloaded {
+    pub workspace_root: PathBuf,
+    pub variant_count: usize,
+}
+
+/// Buffered reload events per subscriber; slow subscribers skip ahead to the latest
+const RELOAD_EVENT_CAPACITY: usize = 64;
+
+/// How a cache picks up changes to the .genie folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadMode {
+    /// Filesystem notifications
+    Watching,
+    /// Periodic reloads, used when the filesystem cannot be watched (NFS, some containers)
+    Polling(Duration),
+}
 
 /// Cached profiles for a workspace with hot-reload support
 #[derive(Clone)]
@@ -27,6 +83,18 @@ pub struct ProfileCache {
 
     /// Last known profile count for change detection
     last_count: Arc<RwLock<usize>>,
+
+    /// Reload interval used when watching fails
+    poll_interval: Duration,
+
+    /// Watcher debounce and wake-up timing
+    config: ProfileCacheConfig,
+
+    /// Number of successful reloads since the cache was created
+    reload_count: Arc<AtomicU64>,
+
+    /// Where reload events are published, if anyone listens
+    reload_events: Option<broadcast::Sender<ProfilesReloaded>>,
 }
 
 impl ProfileCache {
@@ -38,9 +106,31 @@ impl ProfileCache {
                 executors: HashMap::new(),
             })),
             last_count: Arc::new(RwLock::new(0)),
+            poll_interval: poll_interval_from_env(),
+            config: ProfileCacheConfig::default(),
+            reload_count: Arc::new(AtomicU64::new(0)),
+            reload_events: None,
         }
     }
 
+    /// Override the watcher timing
+    pub fn with_config(mut self, config: ProfileCacheConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Override the fallback poll interval
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Publish a [`ProfilesReloaded`] event on `sender` after every successful reload
+    pub fn with_reload_events(mut self, sender: broadcast::Sender<ProfilesReloaded>) -> Self {
+        self.reload_events = Some(sender);
+        self
+    }
+
     /// Load profiles initially
     pub async fn initialize(&self) -> Result<()> {
         let profiles = self.load_profiles_now()?;
@@ -63,6 +153,11 @@ impl ProfileCache {
         self.profiles.read().await.clone()
     }
 
+    /// Number of successful reloads since the cache was created
+    pub fn reload_count(&self) -> u64 {
+        self.reload_count.load(Ordering::SeqCst)
+    }
+
     /// Reload profiles from disk
     pub async fn reload(&self) -> Result<()> {
         let old_count = *self.last_count.read().await;
@@ -77,6 +172,14 @@ impl ProfileCache {
             *profiles_guard = new_profiles;
             *count_guard = new_count;
         }
+        self.reload_count.fetch_add(1, Ordering::SeqCst);
+        if let Some(sender) = &self.reload_events {
+            // No receivers is fine: nobody is streaming profile changes right now
+            let _ = sender.send(ProfilesReloaded {
+                workspace_root: self.workspace_root.clone(),
+                variant_count: new_count,
+            });
+        }
 
         if new_count != old_count {
             tracing::info!(
@@ -92,8 +195,11 @@ impl ProfileCache {
         Ok(())
     }
 
-    /// Start watching for file changes
-    pub fn start_watching(self: Arc<Self>) -> Result<()> {
+    /// Start watching for file changes.
+    ///
+    /// Watch setup failures are not fatal: the cache keeps serving the profiles it already
+    /// loaded and falls back to reloading them every `poll_interval`.
+    pub fn start_watching(self: Arc<Self>) -> ReloadMode {
         let genie_path = self.workspace_root.join(".genie");
 
         tracing::debug!("start_watching called for {:?}", self.workspace_root);
@@ -110,6 +216,22 @@ impl ProfileCache {
             self.workspace_root.clone()
         };
 
+        let (tx, rx) = std::sync::mpsc::channel();
+        let watcher = match Self::create_watcher(&watch_path, tx) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                tracing::warn!(
+                    "Cannot watch {:?} for profile changes ({}); polling every {:?} instead",
+                    watch_path,
+                    e,
+                    self.poll_interval
+                );
+                let poll_interval = self.poll_interval;
+                self.start_polling();
+                return ReloadMode::Polling(poll_interval);
+            }
+        };
+
         // Clone for the watcher thread
         let cache = self.clone();
 
@@ -121,9 +243,7 @@ impl ProfileCache {
         std::thread::spawn(move || {
             tracing::debug!("File watcher thread started");
             let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                if let Err(e) = cache.watch_loop(&watch_path, &genie_path, runtime) {
-                    tracing::error!("File watcher error: {}", e);
-                }
+                cache.watch_loop(watcher, rx, &genie_path, runtime);
             }));
 
             if let Err(panic) = result {
@@ -132,22 +252,14 @@ impl ProfileCache {
         });
 
         tracing::debug!("File watcher thread spawned");
-        Ok(())
+        ReloadMode::Watching
     }
 
-    /// Watch loop (runs in separate thread)
-    /// watch_path: what we're currently watching (workspace root or .genie)
-    /// genie_path: the .genie folder path (may not exist yet)
-    fn watch_loop(
-        &self,
+    /// Create a watcher for `watch_path` that forwards events to `tx`
+    fn create_watcher(
         watch_path: &Path,
-        genie_path: &Path,
-        runtime: tokio::runtime::Handle,
-    ) -> Result<()> {
-        tracing::debug!("watch_loop entered for {:?}", watch_path);
-
-        let (tx, rx) = std::sync::mpsc::channel();
-
+        tx: std::sync::mpsc::Sender<Event>,
+    ) -> notify::Result<RecommendedWatcher> {
         tracing::debug!("Creating RecommendedWatcher...");
         let mut watcher = RecommendedWatcher::new(
             move |res: Result<Event, notify::Error>| {
@@ -157,24 +269,60 @@ impl ProfileCache {
             },
             Config::default(),
         )?;
-        tracing::debug!("RecommendedWatcher created");
 
         tracing::debug!("Starting to watch {:?}", watch_path);
         watcher.watch(watch_path, RecursiveMode::Recursive)?;
+        Ok(watcher)
+    }
+
+    /// Reload profiles every `poll_interval` (fallback when watching is unavailable)
+    fn start_polling(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval_at(
+                tokio::time::Instant::now() + self.poll_interval,
+                self.poll_interval,
+            );
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.reload().await {
+                    tracing::warn!(
+                        "Failed to poll profiles for {:?}, will retry: {}",
+                        self.workspace_root,
+                        e
+                    );
+                }
+            }
+        });
+    }
 
+    /// Watch loop (runs in separate thread)
+    /// watcher: kept alive for as long as the loop runs
+    /// genie_path: the .genie folder path (may not exist yet)
+    fn watch_loop(
+        &self,
+        _watcher: RecommendedWatcher,
+        rx: std::sync::mpsc::Receiver<Event>,
+        genie_path: &Path,
+        runtime: tokio::runtime::Handle,
+    ) {
         tracing::debug!("File watcher started for {:?}", genie_path);
 
-        // Debounce: collect events for a short period before reloading
-        let debounce_duration = Duration::from_millis(500);
-        let mut last_reload = std::time::Instant::now();
+        // Debounce: wait until events have been quiet for a while before reloading
+        let ProfileCacheConfig {
+            debounce,
+            poll_interval,
+        } = self.config;
+        let mut last_event = Instant::now();
+        let mut last_reload = Instant::now();
         let mut pending_reload = false;
 
         loop {
-            match rx.recv_timeout(Duration::from_millis(100)) {
+            match rx.recv_timeout(poll_interval) {
                 Ok(event) => {
                     // Check if it's a relevant event
                     if self.is_relevant_event(&event) {
                         pending_reload = true;
+                        last_event = Instant::now();
 
                         tracing::debug!(
                             "Detected change in .genie: {:?}",
@@ -184,7 +332,10 @@ impl ProfileCache {
                 }
                 Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
                     // Check if we should reload
-                    if pending_reload && last_reload.elapsed() > debounce_duration {
+                    if pending_reload
+                        && last_event.elapsed() >= debounce
+                        && last_reload.elapsed() >= debounce
+                    {
                         tracing::info!("Detected .genie changes, reloading profiles...");
 
                         // Reload using the passed-in runtime handle
@@ -198,7 +349,7 @@ impl ProfileCache {
                             }
                         }
                         // Always update last_reload to prevent tight retry loop on errors
-                        last_reload = std::time::Instant::now();
+                        last_reload = Instant::now();
                     }
                 }
                 Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
@@ -207,8 +358,6 @@ impl ProfileCache {
                 }
             }
         }
-
-        Ok(())
     }
 
     /// Check if event is relevant for profile reload
@@ -220,8 +369,8 @@ impl ProfileCache {
                     if p.file_name().and_then(|n| n.to_str()) == Some(".genie") {
                         return true;
                     }
-                    // Detect .md file changes inside .genie
-                    if p.extension().and_then(|e| e.to_str()) == Some("md") {
+                    // Detect agent file changes inside .genie
+                    if agent_file_stem(p).is_some() {
                         return p
                             .ancestors()
                             .any(|a| a.file_name().and_then(|n| n.to_str()) == Some(".genie"));
@@ -283,6 +432,12 @@ pub struct ProfileCacheManager {
 
     /// Project ID -> workspace path mapping
     project_paths: Arc<RwLock<HashMap<Uuid, PathBuf>>>,
+
+    /// Watcher timing for caches created by this manager
+    config: ProfileCacheConfig,
+
+    /// Reload events from every cache created by this manager
+    reload_events: broadcast::Sender<ProfilesReloaded>,
 }
 
 impl Default for ProfileCacheManager {
@@ -296,9 +451,22 @@ impl ProfileCacheManager {
         Self {
             caches_by_path: Arc::new(RwLock::new(HashMap::new())),
             project_paths: Arc::new(RwLock::new(HashMap::new())),
+            config: ProfileCacheConfig::default(),
+            reload_events: broadcast::channel(RELOAD_EVENT_CAPACITY).0,
         }
     }
 
+    /// Receive a [`ProfilesReloaded`] event whenever any managed cache reloads
+    pub fn subscribe(&self) -> broadcast::Receiver<ProfilesReloaded> {
+        self.reload_events.subscribe()
+    }
+
+    /// Use `config` for the watcher of every cache created from now on
+    pub fn with_config(mut self, config: ProfileCacheConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     /// Get or create cache for a workspace
     pub async fn get_or_create(&self, workspace_root: PathBuf) -> Result<Arc<ProfileCache>> {
         tracing::debug!(
@@ -317,15 +485,19 @@ impl ProfileCacheManager {
 
         // Create new cache
         tracing::debug!("Creating new ProfileCache for {:?}", workspace_root);
-        let cache = Arc::new(ProfileCache::new(workspace_root.clone()));
+        let cache = Arc::new(
+            ProfileCache::new(workspace_root.clone())
+                .with_config(self.config)
+                .with_reload_events(self.reload_events.clone()),
+        );
 
         tracing::debug!("Initializing ProfileCache...");
         cache.initialize().await?;
 
-        // Start file watcher
+        // Start file watcher (falls back to polling if the filesystem can't be watched)
         tracing::debug!("Starting file watcher...");
-        cache.clone().start_watching()?;
-        tracing::debug!("File watcher started successfully");
+        let mode = cache.clone().start_watching();
+        tracing::debug!("Profile reload mode for {:?}: {:?}", workspace_root, mode);
 
         // Store cache
         self.caches_by_path
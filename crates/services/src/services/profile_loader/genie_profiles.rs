@@ -17,6 +17,22 @@ use forge_core_executors::{
 use serde::{Deserialize, Serialize};
 use serde_yaml_ng as serde_yaml;
 
+/// Suffixes of structured agent files, which hold the frontmatter fields directly
+const STRUCTURED_AGENT_SUFFIXES: [&str; 3] = [".agent.json", ".agent.yaml", ".agent.yml"];
+
+/// Agent name for a file the loader reads (Markdown or structured), without its suffix
+pub(super) fn agent_file_stem(path: &Path) -> Option<&str> {
+    let file_name = path.file_name()?.to_str()?;
+    file_name
+        .strip_suffix(".md")
+        .or_else(|| {
+            STRUCTURED_AGENT_SUFFIXES
+                .iter()
+                .find_map(|suffix| file_name.strip_suffix(suffix))
+        })
+        .filter(|stem| !stem.is_empty())
+}
+
 /// Represents the new frontmatter schema with genie.* and forge.* namespaces
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentFrontmatter {
@@ -47,6 +63,25 @@ pub struct AgentFrontmatter {
     pub forge: ForgeConfigMap,
 }
 
+/// Split Markdown into its frontmatter YAML and body, if it has frontmatter
+fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let front_matter_regex =
+        regex::Regex::new(r"^---\r?\n([\s\S]*?)\r?\n---\r?\n([\s\S]*)$").unwrap();
+    let captures = front_matter_regex.captures(content)?;
+    Some((captures.get(1)?.as_str(), captures.get(2)?.as_str()))
+}
+
+/// A `.agent.json`/`.agent.yaml` file: the frontmatter fields plus the instructions that
+/// a Markdown agent keeps below its frontmatter
+#[derive(Debug, Deserialize)]
+struct StructuredAgent {
+    #[serde(flatten)]
+    metadata: AgentFrontmatter,
+
+    #[serde(default)]
+    instructions: String,
+}
+
 /// Orchestration settings (genie.* namespace)
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GenieConfig {
@@ -126,6 +161,7 @@ where
                 "claude_code_router",
                 "plan",
                 "approvals",
+                "mcp_servers",
             ];
 
             // Check if any keys match known executor names (uppercase convention)
@@ -210,6 +246,9 @@ pub struct ForgeConfig {
 
     /// Approval settings
     pub approvals: Option<serde_json::Value>,
+
+    /// MCP server definitions keyed by server name (CLAUDE_CODE, CODEX)
+    pub mcp_servers: Option<HashMap<String, serde_json::Value>>,
 }
 
 /// Represents a discovered agent file
@@ -247,6 +286,19 @@ pub struct Collective {
     pub context_file: PathBuf,
 }
 
+/// Why an agent file fails to produce profiles
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProfileValidationIssue {
+    /// Agent file path, relative to the workspace root
+    pub file_path: String,
+
+    /// Offending field, e.g. `genie.executor` or `forge`; `frontmatter` when the file
+    /// can't be parsed at all
+    pub field: String,
+
+    pub message: String,
+}
+
 /// Main entry point for discovering .genie folders and loading profiles
 pub struct GenieProfileLoader {
     workspace_root: PathBuf,
@@ -281,17 +333,24 @@ impl GenieProfileLoader {
             collectives.iter().map(|c| &c.id).collect::<Vec<_>>()
         );
 
-        // Step 3: Scan agent/neuron files
-        let agent_files = self.scan_agent_files(&genie_root, &collectives)?;
+        // Step 3: Scan agent/neuron files, in path order so variant collisions resolve the
+        // same way on every load
+        let mut agent_files = self.scan_agent_files(&genie_root, &collectives)?;
+        agent_files.sort_by(|a, b| a.file_path.cmp(&b.file_path));
         tracing::info!("Found {} agent/neuron files", agent_files.len());
 
         // Step 4: Parse and generate profiles (one per executor)
         let mut executor_configs: HashMap<BaseCodingAgent, ExecutorConfig> = HashMap::new();
+        let mut variant_sources: HashMap<(BaseCodingAgent, String), PathBuf> = HashMap::new();
 
         for file in agent_files {
             match self.parse_and_generate_profiles(&file, &collectives) {
-                Ok(profiles) => {
+                Ok(mut profiles) => {
+                    Self::disambiguate_variants(&file, &mut profiles, &variant_sources);
                     for (executor, variant_name, config) in profiles {
+                        variant_sources
+                            .insert((executor, variant_name.clone()), file.file_path.clone());
+
                         // Get or create executor config
                         let executor_config =
                             executor_configs
@@ -323,6 +382,167 @@ impl GenieProfileLoader {
         })
     }
 
+    /// Report agent files that `load_profiles` would skip, and why. Nothing is cached or
+    /// loaded, so this is safe to call while profiles are in use.
+    pub fn validate_profiles(&self) -> Vec<ProfileValidationIssue> {
+        let genie_root = self.workspace_root.join(".genie");
+        if !genie_root.exists() {
+            return Vec::new();
+        }
+
+        let agent_files = self
+            .discover_collectives(&genie_root)
+            .and_then(|collectives| {
+                let files = self.scan_agent_files(&genie_root, &collectives)?;
+                Ok((collectives, files))
+            });
+        let (collectives, agent_files) = match agent_files {
+            Ok(found) => found,
+            Err(e) => {
+                return vec![ProfileValidationIssue {
+                    file_path: ".genie".to_string(),
+                    field: "frontmatter".to_string(),
+                    message: format!("{e:#}"),
+                }];
+            }
+        };
+
+        agent_files
+            .iter()
+            .flat_map(|file| self.validate_agent_file(file, &collectives))
+            .collect()
+    }
+
+    fn validate_agent_file(
+        &self,
+        file: &AgentFile,
+        collectives: &[Collective],
+    ) -> Vec<ProfileValidationIssue> {
+        let issue = |field: &str, message: String| ProfileValidationIssue {
+            file_path: file
+                .file_path
+                .strip_prefix(&self.workspace_root)
+                .unwrap_or(&file.file_path)
+                .display()
+                .to_string(),
+            field: field.to_string(),
+            message,
+        };
+
+        let document = match Self::read_agent_document(&file.file_path) {
+            Ok(Some(document)) => document,
+            // Markdown without frontmatter loads with default metadata
+            Ok(None) => return Vec::new(),
+            Err(e) => return vec![issue("frontmatter", format!("{e:#}"))],
+        };
+
+        let mut issues = Vec::new();
+        if let Some(executor) = document
+            .get("genie")
+            .and_then(|genie| genie.get("executor"))
+        {
+            let names = match executor {
+                serde_json::Value::String(name) => vec![Some(name.as_str())],
+                serde_json::Value::Array(names) => names.iter().map(|n| n.as_str()).collect(),
+                _ => vec![None],
+            };
+            if names.is_empty() {
+                issues.push(issue(
+                    "genie.executor",
+                    "executor array cannot be empty".to_string(),
+                ));
+            }
+            for name in names {
+                match name {
+                    Some(name) if name.parse::<BaseCodingAgent>().is_ok() => {}
+                    Some(name) => issues.push(issue(
+                        "genie.executor",
+                        format!("Unknown executor '{name}'"),
+                    )),
+                    None => issues.push(issue(
+                        "genie.executor",
+                        "executor must be a name or a list of names".to_string(),
+                    )),
+                }
+            }
+        }
+        if let Some(forge) = document.get("forge")
+            && let Err(e) = deserialize_forge_config(forge.clone())
+        {
+            issues.push(issue("forge", e.to_string()));
+        }
+
+        // Anything the field checks don't cover still surfaces when building the profiles
+        if issues.is_empty()
+            && let Err(e) = self.parse_and_generate_profiles(file, collectives)
+        {
+            issues.push(issue("frontmatter", format!("{e:#}")));
+        }
+        issues
+    }
+
+    /// An agent file's metadata as untyped JSON, or `None` for Markdown without frontmatter
+    fn read_agent_document(path: &Path) -> Result<Option<serde_json::Value>> {
+        let content = fs::read_to_string(path).context(format!("Failed to read file: {path:?}"))?;
+
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let document = if file_name.ends_with(".md") {
+            let Some((front_matter_yaml, _)) = split_frontmatter(&content) else {
+                return Ok(None);
+            };
+            serde_yaml::from_str(front_matter_yaml).context("Failed to parse frontmatter YAML")?
+        } else if file_name.ends_with(".json") {
+            serde_json::from_str(&content).context("Failed to parse agent JSON")?
+        } else {
+            serde_yaml::from_str(&content).context("Failed to parse agent YAML")?
+        };
+        Ok(Some(document))
+    }
+
+    /// Rename a file's variants when another agent file already produced the same variant
+    /// for one of its executors. The collective name, then the file stem, then a counter is
+    /// appended, so both profiles load under distinct names.
+    fn disambiguate_variants(
+        file: &AgentFile,
+        profiles: &mut [(BaseCodingAgent, String, CodingAgent)],
+        variant_sources: &HashMap<(BaseCodingAgent, String), PathBuf>,
+    ) {
+        let conflict = |variant: &str| {
+            profiles
+                .iter()
+                .find_map(|(executor, _, _)| variant_sources.get(&(*executor, variant.to_string())))
+        };
+
+        let Some(variant) = profiles.first().map(|(_, variant, _)| variant.clone()) else {
+            return;
+        };
+        let Some(existing) = conflict(&variant) else {
+            return;
+        };
+
+        let stem = agent_file_stem(&file.file_path).unwrap_or("agent");
+        let renamed = file
+            .collective
+            .iter()
+            .map(String::as_str)
+            .chain([stem])
+            .map(|suffix| canonical_variant_key(format!("{variant}_{suffix}")))
+            .chain((2..).map(|n| format!("{variant}_{n}")))
+            .find(|candidate| conflict(candidate).is_none())
+            .expect("variant candidates are unbounded");
+
+        tracing::warn!(
+            "Variant {} from {} is already defined by {}; loading it as {}",
+            variant,
+            file.file_path.display(),
+            existing.display(),
+            renamed
+        );
+        for (_, variant_name, _) in profiles.iter_mut() {
+            *variant_name = renamed.clone();
+        }
+    }
+
     /// Discover collectives (directories with AGENTS.md marker)
     fn discover_collectives(&self, genie_root: &Path) -> Result<Vec<Collective>> {
         let mut collectives = Vec::new();
@@ -413,7 +633,7 @@ impl GenieProfileLoader {
         Ok(files)
     }
 
-    /// Scan a directory for .md files recursively
+    /// Scan a directory for .md and .agent.json/.agent.yaml files recursively
     fn scan_directory(
         dir: &Path,
         collective: Option<String>,
@@ -461,18 +681,12 @@ impl GenieProfileLoader {
                 continue;
             }
 
-            // Only process .md files
-            if path.extension().and_then(|s| s.to_str()) != Some("md") {
+            // Only process agent files
+            let Some(name) = agent_file_stem(&path).map(str::to_string) else {
                 continue;
-            }
+            };
 
             // Skip README files
-            let name = path
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("unknown")
-                .to_string();
-
             if name.eq_ignore_ascii_case("README") || name.eq_ignore_ascii_case("AGENTS") {
                 tracing::debug!("Skipping documentation file: {}", path.display());
                 continue;
@@ -502,12 +716,7 @@ impl GenieProfileLoader {
         file: &AgentFile,
         collectives: &[Collective],
     ) -> Result<Vec<(BaseCodingAgent, String, CodingAgent)>> {
-        // Read file content
-        let content = fs::read_to_string(&file.file_path)
-            .context(format!("Failed to read file: {:?}", file.file_path))?;
-
-        // Extract frontmatter and body
-        let (metadata, instructions) = self.extract_frontmatter(&content)?;
+        let (metadata, instructions) = self.read_agent_file(&file.file_path)?;
 
         // Load collective context if applicable
         let collective_context = if let Some(coll_id) = &file.collective {
@@ -556,12 +765,26 @@ impl GenieProfileLoader {
         Ok(profiles)
     }
 
+    /// Read an agent file's metadata and instructions
+    fn read_agent_file(&self, path: &Path) -> Result<(AgentFrontmatter, String)> {
+        let content = fs::read_to_string(path).context(format!("Failed to read file: {path:?}"))?;
+
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if file_name.ends_with(".md") {
+            return self.extract_frontmatter(&content);
+        }
+
+        let agent: StructuredAgent = if file_name.ends_with(".json") {
+            serde_json::from_str(&content).context("Failed to parse agent JSON")?
+        } else {
+            serde_yaml::from_str(&content).context("Failed to parse agent YAML")?
+        };
+        Ok((agent.metadata, agent.instructions.trim().to_string()))
+    }
+
     /// Extract frontmatter and markdown body from content
     fn extract_frontmatter(&self, content: &str) -> Result<(AgentFrontmatter, String)> {
-        let front_matter_regex =
-            regex::Regex::new(r"^---\r?\n([\s\S]*?)\r?\n---\r?\n([\s\S]*)$").unwrap();
-
-        let Some(captures) = front_matter_regex.captures(content) else {
+        let Some((front_matter_yaml, body)) = split_frontmatter(content) else {
             // No frontmatter, return minimal metadata
             return Ok((
                 AgentFrontmatter {
@@ -577,8 +800,7 @@ impl GenieProfileLoader {
             ));
         };
 
-        let front_matter_yaml = &captures[1];
-        let body = captures[2].trim().to_string();
+        let body = body.trim().to_string();
 
         let metadata: AgentFrontmatter =
             serde_yaml::from_str(front_matter_yaml).context("Failed to parse frontmatter YAML")?;
@@ -685,6 +907,17 @@ impl GenieProfileLoader {
             if let Some(approvals) = &config.approvals {
                 base_json["approvals"] = approvals.clone();
             }
+            if let Some(servers) = &config.mcp_servers {
+                if matches!(executor, BaseCodingAgent::ClaudeCode | BaseCodingAgent::Codex) {
+                    base_json["mcp_servers"] = serde_json::json!(servers);
+                } else {
+                    tracing::warn!(
+                        "Ignoring mcp_servers in profile {}: not supported by {}",
+                        metadata.name,
+                        executor
+                    );
+                }
+            }
         }
 
         // Construct the executor-specific config
@@ -6,8 +6,10 @@
 mod cache;
 mod genie_profiles;
 
-pub use cache::{ProfileCache, ProfileCacheManager};
+pub use cache::{
+    ProfileCache, ProfileCacheConfig, ProfileCacheManager, ProfilesReloaded, ReloadMode,
+};
 pub use genie_profiles::{
     AgentFile, AgentFrontmatter, AgentType, Collective, ForgeConfig, ForgeConfigMap, GenieConfig,
-    GenieProfileLoader,
+    GenieProfileLoader, ProfileValidationIssue,
 };
@@ -0,0 +1,106 @@
+//! Coordinated cleanup when the server is asked to stop (SIGTERM/SIGINT): long-lived streams are
+//! cancelled so the server can drain, and background loops are aborted instead of being dropped
+//! with the runtime.
+
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use tokio::task::{AbortHandle, JoinHandle};
+use tokio_util::sync::CancellationToken;
+
+/// What happens to executor processes that are still running at shutdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunningAttemptPolicy {
+    /// Kill the processes and mark them as killed
+    Stop,
+    /// Leave the processes running; they are reconciled on the next start
+    Detach,
+}
+
+impl RunningAttemptPolicy {
+    /// Reads `FORGE_SHUTDOWN_ATTEMPTS` (`stop` or `detach`), defaulting to `Stop`.
+    pub fn from_env() -> Self {
+        match std::env::var("FORGE_SHUTDOWN_ATTEMPTS") {
+            Ok(v) if v.trim().eq_ignore_ascii_case("detach") => Self::Detach,
+            _ => Self::Stop,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Background tasks that were still running and got aborted
+    pub aborted_tasks: usize,
+}
+
+/// Tracks long-lived background tasks so they can be stopped together on shutdown.
+#[derive(Clone, Default)]
+pub struct ShutdownCoordinator {
+    shutting_down: Arc<AtomicBool>,
+    streams: CancellationToken,
+    tasks: Arc<Mutex<Vec<(String, AbortHandle)>>>,
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Marks shutdown as started and cancels long-lived streams. Returns false if it had
+    /// already started.
+    pub fn begin(&self) -> bool {
+        let first = !self.shutting_down.swap(true, Ordering::SeqCst);
+        self.streams.cancel();
+        first
+    }
+
+    /// Cancelled once shutdown begins. WebSocket and SSE handlers end their streams on it,
+    /// otherwise the server would wait on them forever while draining connections.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.streams.clone()
+    }
+
+    /// Registers a background task to abort on shutdown. Tasks registered once shutdown has
+    /// started are aborted immediately.
+    pub fn register_task<T>(&self, name: &str, handle: &JoinHandle<T>) {
+        if self.is_shutting_down() {
+            tracing::debug!("Aborting background task '{}' registered during shutdown", name);
+            handle.abort();
+            return;
+        }
+        let mut tasks = self.tasks.lock().unwrap();
+        tasks.retain(|(_, task)| !task.is_finished());
+        tasks.push((name.to_string(), handle.abort_handle()));
+    }
+
+    /// Aborts every registered task that is still running and returns how many there were.
+    pub fn abort_background_tasks(&self) -> usize {
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap());
+        let mut aborted = 0;
+        for (name, task) in tasks {
+            if !task.is_finished() {
+                tracing::debug!("Aborting background task '{}'", name);
+                task.abort();
+                aborted += 1;
+            }
+        }
+        aborted
+    }
+
+    /// Cancels the long-lived streams, then aborts the registered background tasks.
+    pub fn finish(&self) -> ShutdownReport {
+        self.begin();
+        let aborted_tasks = self.abort_background_tasks();
+        tracing::info!(
+            "Shutdown cleanup finished: aborted {} background task(s)",
+            aborted_tasks
+        );
+        ShutdownReport { aborted_tasks }
+    }
+}
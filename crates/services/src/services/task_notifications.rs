@@ -0,0 +1,257 @@
+//! Remote task notifications
+//!
+//! Sends task events through every remote channel Forge supports: Omni and the signed
+//! webhook. Both see the same events and apply the same subscription filter.
+
+use std::sync::Arc;
+
+use forge_core_db::models::{
+    execution_process::{ExecutionContext, ExecutionProcessStatus},
+    task::{Task, TaskStatus},
+};
+use sqlx::SqlitePool;
+use tokio::sync::RwLock;
+
+use super::{
+    forge_config::ForgeConfigService,
+    omni::{NotificationEvent, OmniService},
+    webhook::WebhookDispatcher,
+};
+
+#[derive(Clone)]
+pub struct TaskNotifier {
+    pool: SqlitePool,
+    forge_config: ForgeConfigService,
+    omni: Arc<RwLock<OmniService>>,
+    webhooks: WebhookDispatcher,
+}
+
+impl TaskNotifier {
+    pub fn new(
+        pool: SqlitePool,
+        forge_config: ForgeConfigService,
+        omni: Arc<RwLock<OmniService>>,
+    ) -> Self {
+        Self {
+            pool,
+            forge_config,
+            omni,
+            webhooks: WebhookDispatcher::new(),
+        }
+    }
+
+    /// Announces that an attempt's execution stopped and its task moved to `status`. Cancelled
+    /// executions aren't announced.
+    pub async fn notify_execution_halted(&self, ctx: &ExecutionContext, status: TaskStatus) {
+        let event = match ctx.execution_process.status {
+            ExecutionProcessStatus::Completed => NotificationEvent::TaskCompleted,
+            ExecutionProcessStatus::Failed => NotificationEvent::TaskFailed,
+            _ => return,
+        };
+        self.notify(&ctx.task, status, event).await;
+    }
+
+    /// Sends `event` for `task` over Omni and the project's webhook. Delivery failures are
+    /// logged, so one channel failing doesn't keep the other from being tried.
+    pub async fn notify(&self, task: &Task, status: TaskStatus, event: NotificationEvent) {
+        let notification_type = event.notification_type();
+        let task_status = status.to_string();
+
+        if let Err(e) = self
+            .omni
+            .read()
+            .await
+            .dispatch_task_notification(
+                &self.pool,
+                task.id,
+                notification_type,
+                &task.title,
+                &task_status,
+                None,
+            )
+            .await
+        {
+            tracing::warn!(
+                "Failed to send '{}' Omni notification for task {}: {}",
+                notification_type,
+                task.id,
+                e
+            );
+        }
+
+        let webhook = match self
+            .forge_config
+            .effective_webhook_config(Some(task.project_id))
+            .await
+        {
+            Ok(Some(webhook)) => webhook,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load the webhook config for project {}: {}",
+                    task.project_id,
+                    e
+                );
+                return;
+            }
+        };
+        if let Err(e) = self
+            .webhooks
+            .dispatch_task_notification(
+                &webhook,
+                task.id,
+                notification_type,
+                &task.title,
+                &task_status,
+                None,
+            )
+            .await
+        {
+            tracing::warn!(
+                "Failed to deliver '{}' webhook for task {}: {}",
+                notification_type,
+                task.id,
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use forge_core_db::{
+        DBService,
+        models::{
+            execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutorActionField},
+            project::{CreateProject, Project},
+            task::CreateTask,
+            task_attempt::{CreateTaskAttempt, TaskAttempt},
+        },
+    };
+    use forge_core_executors::executors::BaseCodingAgent;
+    use uuid::Uuid;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path},
+    };
+
+    use super::*;
+    use crate::services::{
+        forge_config::{ForgeProjectSettings, SecretCipher},
+        omni::OmniConfig,
+        webhook::{EVENT_HEADER, WebhookConfig},
+    };
+
+    async fn halted_context(pool: &SqlitePool, status: ExecutionProcessStatus) -> ExecutionContext {
+        let project_id = Uuid::new_v4();
+        Project::create(
+            pool,
+            &CreateProject {
+                name: "Notifications".to_string(),
+                git_repo_path: format!("/tmp/test-repo-{project_id}"),
+                use_existing_repo: false,
+                setup_script: None,
+                dev_script: None,
+                cleanup_script: None,
+                copy_files: None,
+                commit_prompt: None,
+                clone_url: None,
+            },
+            project_id,
+        )
+        .await
+        .unwrap();
+        let create_task =
+            CreateTask::from_title_description(project_id, "Add greeting".to_string(), None);
+        let task = Task::create(pool, &create_task, Uuid::new_v4())
+            .await
+            .unwrap();
+        let create_attempt = CreateTaskAttempt {
+            executor: BaseCodingAgent::ClaudeCode,
+            variant: None,
+            base_branch: "main".to_string(),
+            branch: "forge/add-greeting".to_string(),
+        };
+        let task_attempt = TaskAttempt::create(pool, &create_attempt, Uuid::new_v4(), task.id)
+            .await
+            .unwrap();
+        let now = Utc::now();
+        let execution_process = ExecutionProcess {
+            id: Uuid::new_v4(),
+            task_attempt_id: Some(task_attempt.id),
+            execution_run_id: None,
+            run_reason: ExecutionProcessRunReason::CodingAgent,
+            executor_action: sqlx::types::Json(ExecutorActionField::Other(serde_json::json!({}))),
+            before_head_commit: None,
+            after_head_commit: None,
+            status,
+            exit_code: None,
+            dropped: false,
+            started_at: now,
+            completed_at: Some(now),
+            created_at: now,
+            updated_at: now,
+        };
+        ExecutionContext {
+            execution_process,
+            task_attempt,
+            task,
+        }
+    }
+
+    #[tokio::test]
+    async fn halted_executions_are_announced_over_the_webhook() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+            .await
+            .unwrap();
+        let forge_config = ForgeConfigService::new(
+            db.pool.clone(),
+            SecretCipher::from_key_material(b"task-notifications-test-key"),
+        );
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hooks/forge"))
+            .and(header(EVENT_HEADER, "task_completed"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        forge_config
+            .set_global_settings(&ForgeProjectSettings {
+                webhook: Some(WebhookConfig {
+                    url: format!("{}/hooks/forge", mock_server.uri()),
+                    // NOTE: The secret below is a fake test value.
+                    secret: "test-webhook-secret".to_string(),
+                    events: Default::default(),
+                }),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        // Omni is disabled, which must not keep the webhook from being sent
+        let notifier = TaskNotifier::new(
+            db.pool.clone(),
+            forge_config,
+            Arc::new(RwLock::new(OmniService::new(OmniConfig::default()))),
+        );
+        let completed = halted_context(&db.pool, ExecutionProcessStatus::Completed).await;
+        notifier
+            .notify_execution_halted(&completed, TaskStatus::InReview)
+            .await;
+        // Cancelled executions aren't announced
+        let killed = halted_context(&db.pool, ExecutionProcessStatus::Killed).await;
+        notifier
+            .notify_execution_halted(&killed, TaskStatus::InReview)
+            .await;
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        let payload: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(payload["task_id"], completed.task.id.to_string());
+        assert_eq!(payload["task_status"], "in-review");
+    }
+}
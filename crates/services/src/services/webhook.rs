@@ -0,0 +1,280 @@
+//! Webhook notifications
+//!
+//! An alternative to Omni for delivering task notifications: each event is POSTed as JSON to
+//! a configured URL, signed with HMAC-SHA256 so the receiver can verify it came from Forge.
+
+use std::{collections::HashSet, time::Duration};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use ts_rs_forge::TS;
+use uuid::Uuid;
+
+use super::omni::NotificationEvent;
+
+/// Header carrying `sha256=<hex HMAC of the request body>`
+pub const SIGNATURE_HEADER: &str = "X-Forge-Signature";
+/// Header carrying the `notification_type` of the payload
+pub const EVENT_HEADER: &str = "X-Forge-Event";
+/// How long a receiver gets to answer before a delivery is given up
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where to POST task notifications and the secret their signatures are keyed with.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub secret: String,
+    /// Events to deliver. Empty means every event, as with Omni.
+    #[serde(default)]
+    pub events: HashSet<NotificationEvent>,
+}
+
+impl WebhookConfig {
+    /// Whether a notification of `notification_type` should be delivered
+    pub fn notifies(&self, notification_type: &str) -> bool {
+        NotificationEvent::subscribed(&self.events, notification_type)
+    }
+}
+
+/// JSON body of a webhook delivery
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub event: String,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub task_status: String,
+    pub task_url: Option<String>,
+    pub sent_at: DateTime<Utc>,
+}
+
+/// A non-success response from a webhook receiver
+#[derive(Debug, thiserror::Error)]
+#[error("Webhook returned {status}: {body}")]
+pub struct WebhookDeliveryError {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+/// `sha256=<hex>` signature of `body` keyed with `secret`
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, body);
+    let hex: String = tag
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("sha256={hex}")
+}
+
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    client: reqwest::Client,
+}
+
+impl Default for WebhookDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebhookDispatcher {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(DELIVERY_TIMEOUT)
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// Delivers a task notification to `config.url` unless its event isn't subscribed to.
+    /// Returns whether a request was sent.
+    pub async fn dispatch_task_notification(
+        &self,
+        config: &WebhookConfig,
+        task_id: Uuid,
+        notification_type: &str,
+        task_title: &str,
+        task_status: &str,
+        task_url: Option<&str>,
+    ) -> Result<bool> {
+        if !config.notifies(notification_type) {
+            tracing::debug!(
+                "Skipping '{}' webhook for task {}: event not subscribed",
+                notification_type,
+                task_id
+            );
+            return Ok(false);
+        }
+
+        let payload = WebhookPayload {
+            event: notification_type.to_string(),
+            task_id,
+            task_title: task_title.to_string(),
+            task_status: task_status.to_string(),
+            task_url: task_url.map(str::to_string),
+            sent_at: Utc::now(),
+        };
+        // Sign the exact bytes sent so receivers can verify the raw body
+        let body = serde_json::to_vec(&payload)?;
+        let signature = sign_payload(&config.secret, &body);
+
+        let response = self
+            .client
+            .post(&config.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, notification_type)
+            .header(SIGNATURE_HEADER, signature)
+            .body(body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            tracing::error!("Webhook for task {} failed: {} {}", task_id, status, body);
+            return Err(WebhookDeliveryError { status, body }.into());
+        }
+
+        tracing::info!(
+            "Delivered '{}' webhook for task {}",
+            notification_type,
+            task_id
+        );
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, header_exists, method, path},
+    };
+
+    use super::*;
+
+    // NOTE: The secret below is a fake test value.
+    const SECRET: &str = "test-webhook-secret";
+
+    fn config(url: String, events: &[NotificationEvent]) -> WebhookConfig {
+        WebhookConfig {
+            url,
+            secret: SECRET.to_string(),
+            events: events.iter().copied().collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn delivers_a_signed_payload() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hooks/forge"))
+            .and(header("content-type", "application/json"))
+            .and(header(EVENT_HEADER, "task_completed"))
+            .and(header_exists(SIGNATURE_HEADER))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let task_id = Uuid::new_v4();
+        let sent = WebhookDispatcher::new()
+            .dispatch_task_notification(
+                &config(format!("{}/hooks/forge", mock_server.uri()), &[]),
+                task_id,
+                "task_completed",
+                "Add greeting",
+                "done",
+                Some("http://localhost:3000/tasks/1"),
+            )
+            .await
+            .unwrap();
+        assert!(sent);
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let request = &requests[0];
+
+        let signature = request.headers.get(SIGNATURE_HEADER).unwrap();
+        let key = hmac::Key::new(hmac::HMAC_SHA256, SECRET.as_bytes());
+        let expected = hmac::sign(&key, &request.body);
+        let hex = signature.to_str().unwrap().strip_prefix("sha256=").unwrap();
+        assert_eq!(hex.len(), 64);
+        let tag: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+        assert_eq!(tag, expected.as_ref());
+
+        let payload: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(payload["event"], "task_completed");
+        assert_eq!(payload["task_id"], task_id.to_string());
+        assert_eq!(payload["task_title"], "Add greeting");
+        assert_eq!(payload["task_status"], "done");
+        assert_eq!(payload["task_url"], "http://localhost:3000/tasks/1");
+        assert!(payload["sent_at"].is_string());
+    }
+
+    #[tokio::test]
+    async fn unsubscribed_events_are_not_delivered() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let sent = WebhookDispatcher::new()
+            .dispatch_task_notification(
+                &config(mock_server.uri(), &[NotificationEvent::TaskFailed]),
+                Uuid::new_v4(),
+                "task_completed",
+                "Add greeting",
+                "done",
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(!sent);
+    }
+
+    #[tokio::test]
+    async fn receiver_errors_are_reported() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("bad signature"))
+            .mount(&mock_server)
+            .await;
+
+        let error = WebhookDispatcher::new()
+            .dispatch_task_notification(
+                &config(mock_server.uri(), &[NotificationEvent::TaskFailed]),
+                Uuid::new_v4(),
+                "task_failed",
+                "Add greeting",
+                "failed",
+                None,
+            )
+            .await
+            .unwrap_err();
+        let delivery = error.downcast_ref::<WebhookDeliveryError>().unwrap();
+        assert_eq!(delivery.status, StatusCode::UNAUTHORIZED);
+        assert_eq!(delivery.body, "bad signature");
+    }
+
+    #[test]
+    fn signature_matches_a_known_vector() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Integration tests for the cross-project activity feed
+//!
+//! Run with: cargo test --package services --test activity_feed
+
+use forge_core_db::{
+    DBService,
+    models::{
+        activity::{ActivityEvent, ActivityEventType, ActivityQuery},
+        project::{CreateProject, Project},
+        task::{CreateTask, Task},
+    },
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+async fn create_test_project(pool: &sqlx::SqlitePool, name: &str) -> Uuid {
+    let project_id = Uuid::new_v4();
+    let create_data = CreateProject {
+        name: name.to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+
+    Project::create(pool, &create_data, project_id)
+        .await
+        .unwrap();
+
+    project_id
+}
+
+/// Creates a task whose creation time is pinned to `created_at`
+async fn create_task_at(
+    pool: &sqlx::SqlitePool,
+    project_id: Uuid,
+    title: &str,
+    created_at: &str,
+) -> Uuid {
+    let task_id = Uuid::new_v4();
+    let data = CreateTask::from_title_description(project_id, title.to_string(), None);
+    Task::create(pool, &data, task_id).await.unwrap();
+
+    sqlx::query("UPDATE tasks SET created_at = ?, updated_at = ? WHERE id = ?")
+        .bind(created_at)
+        .bind(created_at)
+        .bind(task_id)
+        .execute(pool)
+        .await
+        .unwrap();
+
+    task_id
+}
+
+fn query(limit: i64) -> ActivityQuery {
+    ActivityQuery {
+        limit,
+        since: None,
+        include_agents: false,
+    }
+}
+
+#[tokio::test]
+async fn global_feed_orders_events_across_projects_by_recency() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let alpha = create_test_project(&db.pool, "Alpha").await;
+    let beta = create_test_project(&db.pool, "Beta").await;
+
+    let oldest = create_task_at(&db.pool, alpha, "alpha old", "2025-01-01 10:00:00.000").await;
+    let middle = create_task_at(&db.pool, beta, "beta", "2025-01-02 10:00:00.000").await;
+    let newest = create_task_at(&db.pool, alpha, "alpha new", "2025-01-03 10:00:00.000").await;
+
+    let events = ActivityEvent::find_recent(&db.pool, &query(10)).await.unwrap();
+
+    let ids: Vec<Uuid> = events.iter().map(|e| e.entity_id).collect();
+    assert_eq!(ids, vec![newest, middle, oldest]);
+    assert!(
+        events
+            .iter()
+            .all(|e| e.event_type == ActivityEventType::TaskCreated)
+    );
+    assert_eq!(events[0].project_name, "Alpha");
+    assert_eq!(events[1].project_id, beta);
+
+    let limited = ActivityEvent::find_recent(&db.pool, &query(2)).await.unwrap();
+    assert_eq!(limited.len(), 2);
+    assert_eq!(limited[0].entity_id, newest);
+
+    let since = ActivityQuery {
+        since: Some("2025-01-02T00:00:00Z".parse().unwrap()),
+        ..query(10)
+    };
+    let recent = ActivityEvent::find_recent(&db.pool, &since).await.unwrap();
+    let ids: Vec<Uuid> = recent.iter().map(|e| e.entity_id).collect();
+    assert_eq!(ids, vec![newest, middle]);
+}
+
+#[tokio::test]
+async fn global_feed_excludes_agent_chat_tasks_by_default() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let project_id = create_test_project(&db.pool, "Agents").await;
+
+    let regular = create_task_at(&db.pool, project_id, "regular", "2025-01-01 10:00:00.000").await;
+    let agent = create_task_at(&db.pool, project_id, "genie chat", "2025-01-02 10:00:00.000").await;
+    sqlx::query(
+        "INSERT INTO forge_agents (id, project_id, agent_type, task_id) VALUES (?, ?, 'genie_chat', ?)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(project_id)
+    .bind(agent)
+    .execute(&db.pool)
+    .await
+    .unwrap();
+
+    let events = ActivityEvent::find_recent(&db.pool, &query(10)).await.unwrap();
+    let ids: Vec<Uuid> = events.iter().map(|e| e.entity_id).collect();
+    assert_eq!(ids, vec![regular]);
+
+    let with_agents = ActivityQuery {
+        include_agents: true,
+        ..query(10)
+    };
+    let events = ActivityEvent::find_recent(&db.pool, &with_agents).await.unwrap();
+    let ids: Vec<Uuid> = events.iter().map(|e| e.entity_id).collect();
+    assert_eq!(ids, vec![agent, regular]);
+}
@@ -0,0 +1,213 @@
+//! Integration tests for agent task / forge_agents reconciliation
+//!
+//! Run with: cargo test --package services --test agent_consistency
+
+use forge_core_db::{
+    DBService,
+    models::{
+        forge_agent::{AGENT_CHAT_TYPE, ForgeAgent},
+        project::{CreateProject, Project},
+        task::{CreateTask, Task, TaskStatus},
+    },
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+async fn create_test_project(pool: &sqlx::SqlitePool) -> Uuid {
+    let project_id = Uuid::new_v4();
+    let create_data = CreateProject {
+        name: "Agents".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+
+    Project::create(pool, &create_data, project_id)
+        .await
+        .unwrap();
+
+    project_id
+}
+
+async fn create_task_with_status(
+    pool: &sqlx::SqlitePool,
+    project_id: Uuid,
+    title: &str,
+    status: TaskStatus,
+) -> Uuid {
+    let task_id = Uuid::new_v4();
+    let data = CreateTask::from_title_description(project_id, title.to_string(), None);
+    Task::create_with_status(pool, &data, task_id, status)
+        .await
+        .unwrap();
+    task_id
+}
+
+async fn task_status(pool: &sqlx::SqlitePool, task_id: Uuid) -> TaskStatus {
+    Task::find_by_id(pool, task_id).await.unwrap().unwrap().status
+}
+
+/// Inserts a forge_agents row pointing at a task that does not exist
+async fn insert_orphaned_agent_row(pool: &sqlx::SqlitePool, project_id: Uuid) {
+    let mut conn = pool.acquire().await.unwrap();
+    sqlx::query("PRAGMA foreign_keys = OFF")
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO forge_agents (id, project_id, agent_type, task_id) VALUES (?, ?, 'review', ?)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(project_id)
+    .bind(Uuid::new_v4())
+    .execute(&mut *conn)
+    .await
+    .unwrap();
+    sqlx::query("PRAGMA foreign_keys = ON")
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn reconcile_repairs_drifted_agent_state() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+    let project_id = create_test_project(pool).await;
+
+    // Registered agent whose status was changed behind our back
+    let drifted = create_task_with_status(pool, project_id, "Wish agent", TaskStatus::Agent).await;
+    assert!(
+        ForgeAgent::register_task(pool, project_id, drifted, "wish")
+            .await
+            .unwrap()
+    );
+    sqlx::query("UPDATE tasks SET status = 'inreview' WHERE id = ?")
+        .bind(drifted)
+        .execute(pool)
+        .await
+        .unwrap();
+
+    // Agent tasks that never made it into forge_agents; only one agent chat fits per project
+    let unregistered =
+        create_task_with_status(pool, project_id, "Agent chat", TaskStatus::Agent).await;
+    let surplus =
+        create_task_with_status(pool, project_id, "Second agent chat", TaskStatus::Agent).await;
+
+    insert_orphaned_agent_row(pool, project_id).await;
+
+    // A regular task must be left alone
+    let regular = create_task_with_status(pool, project_id, "Kanban task", TaskStatus::Todo).await;
+
+    let report = ForgeAgent::reconcile(pool, false).await.unwrap();
+    assert!(!report.is_consistent());
+    assert!(!report.repaired);
+    assert_eq!(report.orphaned_rows, 1);
+    assert_eq!(report.status_drift, vec![drifted]);
+    assert_eq!(report.unregistered, vec![unregistered, surplus]);
+
+    // Checking alone must not change anything
+    assert_eq!(task_status(pool, drifted).await, TaskStatus::InReview);
+    assert!(!ForgeAgent::is_agent_task(pool, unregistered).await.unwrap());
+
+    let repaired = ForgeAgent::reconcile(pool, true).await.unwrap();
+    assert!(repaired.repaired);
+    assert_eq!(repaired.status_drift, report.status_drift);
+    assert_eq!(repaired.archived, vec![surplus]);
+
+    assert_eq!(task_status(pool, drifted).await, TaskStatus::Agent);
+    assert!(ForgeAgent::is_agent_task(pool, unregistered).await.unwrap());
+    assert!(!ForgeAgent::is_agent_task(pool, surplus).await.unwrap());
+    assert_eq!(task_status(pool, surplus).await, TaskStatus::Archived);
+    assert_eq!(task_status(pool, regular).await, TaskStatus::Todo);
+    assert!(!ForgeAgent::is_agent_task(pool, regular).await.unwrap());
+
+    // The repair converges: a second run finds nothing to do
+    let after = ForgeAgent::reconcile(pool, true).await.unwrap();
+    assert!(after.is_consistent(), "still inconsistent: {after:?}");
+    assert!(!after.repaired);
+    assert!(after.archived.is_empty());
+}
+
+#[tokio::test]
+async fn lifecycle_status_updates_leave_agent_tasks_alone() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+    let project_id = create_test_project(pool).await;
+
+    let agent = create_task_with_status(pool, project_id, "Agent chat", TaskStatus::Agent).await;
+    ForgeAgent::register_task(pool, project_id, agent, AGENT_CHAT_TYPE)
+        .await
+        .unwrap();
+    let regular = create_task_with_status(pool, project_id, "Kanban task", TaskStatus::Todo).await;
+
+    Task::update_status(pool, agent, TaskStatus::InProgress)
+        .await
+        .unwrap();
+    Task::update_status(pool, regular, TaskStatus::InProgress)
+        .await
+        .unwrap();
+
+    assert_eq!(task_status(pool, agent).await, TaskStatus::Agent);
+    assert_eq!(task_status(pool, regular).await, TaskStatus::InProgress);
+    assert!(
+        ForgeAgent::reconcile(pool, false)
+            .await
+            .unwrap()
+            .is_consistent()
+    );
+}
+
+#[tokio::test]
+async fn reconcile_announces_registrations_once_committed() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = db.pool.clone();
+    let project_id = create_test_project(&pool).await;
+    let agent = create_task_with_status(&pool, project_id, "Agent chat", TaskStatus::Agent).await;
+
+    let mut registrations = ForgeAgent::subscribe_registrations();
+
+    // Checking alone announces nothing (other tests may announce their own tasks)
+    ForgeAgent::reconcile(&pool, false).await.unwrap();
+    while let Ok(event) = registrations.try_recv() {
+        assert_ne!(event.task_id, agent);
+    }
+
+    // The listener reads membership through another pool connection, which only sees
+    // committed rows
+    let listener = tokio::spawn({
+        let pool = pool.clone();
+        async move {
+            loop {
+                let event = registrations.recv().await.unwrap();
+                if event.task_id == agent {
+                    assert_eq!(event.project_id, project_id);
+                    return ForgeAgent::is_agent_task(&pool, agent).await.unwrap();
+                }
+            }
+        }
+    });
+    ForgeAgent::reconcile(&pool, true).await.unwrap();
+
+    let committed = tokio::time::timeout(std::time::Duration::from_secs(5), listener)
+        .await
+        .expect("registration was not announced")
+        .unwrap();
+    assert!(
+        committed,
+        "registration was announced before it was committed"
+    );
+}
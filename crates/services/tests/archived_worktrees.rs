@@ -0,0 +1,195 @@
+//! Integration tests for restoring attempt worktrees when a task leaves Archived
+//!
+//! Run with: cargo test --package services --test archived_worktrees
+
+use std::path::{Path, PathBuf};
+
+use forge_core_db::{
+    DBService,
+    models::{
+        project::{CreateProject, Project},
+        task::{CreateTask, Task},
+        task_attempt::{CreateTaskAttempt, TaskAttempt},
+    },
+};
+use forge_core_executors::executors::BaseCodingAgent;
+use forge_core_services::services::{
+    container::{
+        ContainerError, WorktreeCleanupData, cleanup_worktrees_direct, restore_task_worktrees,
+    },
+    git::GitService,
+    worktree_manager::WorktreeManager,
+};
+use git2::{BranchType, Repository};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+const BRANCH: &str = "forge/archived-attempt";
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+/// Creates a project on a fresh repo with one task whose attempt has a worktree, then
+/// archives it the way the task route does: remove the worktree and flag the attempt.
+async fn archived_task(pool: &sqlx::SqlitePool, root: &TempDir) -> (PathBuf, Task, TaskAttempt) {
+    let git = GitService::new();
+    let repo_path = root.path().join("repo");
+    git.initialize_repo_with_main_branch(&repo_path).unwrap();
+    git.configure_user(&repo_path, "Test User", "test@example.com")
+        .unwrap();
+
+    let project_id = Uuid::new_v4();
+    let create_project = CreateProject {
+        name: "Archive".to_string(),
+        git_repo_path: repo_path.to_string_lossy().to_string(),
+        use_existing_repo: true,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+    Project::create(pool, &create_project, project_id)
+        .await
+        .unwrap();
+
+    let create_task =
+        CreateTask::from_title_description(project_id, "Archived work".to_string(), None);
+    let task = Task::create(pool, &create_task, Uuid::new_v4())
+        .await
+        .unwrap();
+
+    let create_attempt = CreateTaskAttempt {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+        base_branch: "main".to_string(),
+        branch: BRANCH.to_string(),
+    };
+    let attempt = TaskAttempt::create(pool, &create_attempt, Uuid::new_v4(), task.id)
+        .await
+        .unwrap();
+
+    let worktree_path = root.path().join("worktree");
+    WorktreeManager::create_worktree(&repo_path, BRANCH, &worktree_path, "main", true)
+        .await
+        .unwrap();
+    TaskAttempt::update_container_ref(pool, attempt.id, &worktree_path.to_string_lossy())
+        .await
+        .unwrap();
+
+    cleanup_worktrees_direct(&[WorktreeCleanupData {
+        attempt_id: attempt.id,
+        worktree_path: worktree_path.clone(),
+        git_repo_path: Some(repo_path.clone()),
+    }])
+    .await
+    .unwrap();
+    TaskAttempt::mark_worktree_deleted(pool, attempt.id)
+        .await
+        .unwrap();
+    assert!(!worktree_path.exists());
+
+    (repo_path, task, attempt)
+}
+
+fn worktree_branch(worktree_path: &Path) -> String {
+    let repo = Repository::open(worktree_path).unwrap();
+    repo.head().unwrap().shorthand().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn unarchiving_recreates_the_attempt_worktree() {
+    let (db, _db_dir) = setup_test_db().await;
+    let pool = &db.pool;
+    let root = TempDir::new().unwrap();
+    let (_repo_path, task, attempt) = archived_task(pool, &root).await;
+
+    let restore = restore_task_worktrees(pool, &GitService::new(), task.id)
+        .await
+        .unwrap();
+    assert_eq!(restore.restored, [attempt.id]);
+    assert!(restore.skipped.is_empty());
+
+    let attempt = TaskAttempt::find_by_id(pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!attempt.worktree_deleted);
+    let worktree_path = PathBuf::from(attempt.container_ref.unwrap());
+    assert_eq!(worktree_branch(&worktree_path), BRANCH);
+    assert!(GitService::new().is_worktree_clean(&worktree_path).unwrap());
+
+    // Nothing left to restore the second time around
+    let restore = restore_task_worktrees(pool, &GitService::new(), task.id)
+        .await
+        .unwrap();
+    assert!(restore.restored.is_empty());
+}
+
+#[tokio::test]
+async fn unarchiving_skips_attempts_whose_branch_is_gone() {
+    let (db, _db_dir) = setup_test_db().await;
+    let pool = &db.pool;
+    let root = TempDir::new().unwrap();
+    let (repo_path, task, attempt) = archived_task(pool, &root).await;
+
+    // A second attempt whose branch survived is still restored
+    let create_attempt = CreateTaskAttempt {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+        base_branch: "main".to_string(),
+        branch: "forge/kept-attempt".to_string(),
+    };
+    let kept = TaskAttempt::create(pool, &create_attempt, Uuid::new_v4(), task.id)
+        .await
+        .unwrap();
+    let kept_path = root.path().join("kept-worktree");
+    WorktreeManager::create_worktree(&repo_path, "forge/kept-attempt", &kept_path, "main", true)
+        .await
+        .unwrap();
+    TaskAttempt::update_container_ref(pool, kept.id, &kept_path.to_string_lossy())
+        .await
+        .unwrap();
+    cleanup_worktrees_direct(&[WorktreeCleanupData {
+        attempt_id: kept.id,
+        worktree_path: kept_path.clone(),
+        git_repo_path: Some(repo_path.clone()),
+    }])
+    .await
+    .unwrap();
+    TaskAttempt::mark_worktree_deleted(pool, kept.id)
+        .await
+        .unwrap();
+
+    let repo = Repository::open(&repo_path).unwrap();
+    repo.find_branch(BRANCH, BranchType::Local)
+        .unwrap()
+        .delete()
+        .unwrap();
+
+    let restore = restore_task_worktrees(pool, &GitService::new(), task.id)
+        .await
+        .unwrap();
+    assert_eq!(restore.restored, [kept.id]);
+    assert_eq!(restore.skipped.len(), 1);
+    let (skipped_id, reason) = &restore.skipped[0];
+    assert_eq!(*skipped_id, attempt.id);
+    assert_eq!(
+        *reason,
+        ContainerError::BranchNotFound(BRANCH.to_string()).to_string()
+    );
+    assert_eq!(worktree_branch(&kept_path), "forge/kept-attempt");
+
+    let attempt = TaskAttempt::find_by_id(pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(attempt.worktree_deleted);
+    assert!(!PathBuf::from(attempt.container_ref.unwrap()).exists());
+}
@@ -0,0 +1,125 @@
+//! Integration tests for attempt diff summaries
+//!
+//! Run with: cargo test --package services --test attempt_diff
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use forge_core_services::services::{
+    attempt_diff::DiffResult,
+    git::{DiffTarget, GitService},
+};
+use forge_core_utils::diff::{Diff, DiffChangeKind, create_unified_diff};
+use tempfile::TempDir;
+
+fn modified_file(path: &str, old: &str, new: &str) -> Diff {
+    Diff {
+        change: DiffChangeKind::Modified,
+        old_path: Some(path.to_string()),
+        new_path: Some(path.to_string()),
+        old_content: Some(old.to_string()),
+        new_content: Some(new.to_string()),
+        content_omitted: false,
+        additions: None,
+        deletions: None,
+    }
+}
+
+fn write_file(base: &Path, rel: &str, content: &str) {
+    let path = base.join(rel);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    fs::write(path, content).unwrap();
+}
+
+fn init_repo_main(root: &TempDir) -> PathBuf {
+    let path = root.path().join("repo");
+    let s = GitService::new();
+    s.initialize_repo_with_main_branch(&path).unwrap();
+    s.configure_user(&path, "Test User", "test@example.com")
+        .unwrap();
+    s.checkout_branch(&path, "main").unwrap();
+    path
+}
+
+#[test]
+fn patch_is_truncated_past_the_cap_but_stats_cover_every_file() {
+    let first = modified_file("a.txt", "one\n", "two\nthree\n");
+    let second = modified_file("b.txt", "", &"x\n".repeat(100));
+    let first_len = create_unified_diff("a.txt", "one\n", "two\nthree\n").len();
+    let diffs = [first, second];
+
+    // Exactly at the cap the first file still fits
+    let at_cap = DiffResult::from_diffs(&diffs[..1], first_len);
+    assert!(!at_cap.truncated);
+    assert!(at_cap.patch.contains("+three"));
+
+    let over_cap = DiffResult::from_diffs(&diffs[..1], first_len - 1);
+    assert!(over_cap.truncated);
+    assert!(!over_cap.patch.contains("+++ b/a.txt"));
+
+    let result = DiffResult::from_diffs(&diffs, first_len);
+    assert!(result.truncated);
+    assert!(result.patch.contains("+++ b/a.txt"));
+    assert!(!result.patch.contains("+++ b/b.txt"));
+    assert_eq!(result.files.len(), 2);
+    assert_eq!(result.files[0].path, "a.txt");
+    assert_eq!(
+        (result.files[0].additions, result.files[0].deletions),
+        (2, 1)
+    );
+    assert_eq!(
+        (result.files[1].additions, result.files[1].deletions),
+        (100, 0)
+    );
+    assert_eq!((result.additions, result.deletions), (102, 1));
+}
+
+#[test]
+fn path_filter_limits_diff_to_matching_files() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "src/lib.rs", "pub fn answer() -> u32 {\n    42\n}\n");
+    write_file(&repo_path, "README.md", "# Demo\n");
+    s.commit(&repo_path, "seed").unwrap();
+
+    s.create_branch(&repo_path, "feature").unwrap();
+    s.checkout_branch(&repo_path, "feature").unwrap();
+    write_file(&repo_path, "src/lib.rs", "pub fn answer() -> u32 {\n    43\n}\n");
+    write_file(&repo_path, "src/greet.rs", "pub fn greet() {}\n");
+    write_file(&repo_path, "README.md", "# Demo\n\nNow with greetings.\n");
+    s.commit(&repo_path, "feature work").unwrap();
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let diff = |paths: Option<&[&str]>| {
+        let diffs = s
+            .get_diffs(
+                DiffTarget::Worktree {
+                    worktree_path: &repo_path,
+                    base_commit: &base_commit,
+                },
+                paths,
+            )
+            .unwrap();
+        DiffResult::from_diffs(&diffs, 64 * 1024)
+    };
+
+    let all = diff(None);
+    assert_eq!(all.files.len(), 3);
+
+    let src = diff(Some(&["src"]));
+    let mut paths: Vec<_> = src.files.iter().map(|f| f.path.as_str()).collect();
+    paths.sort();
+    assert_eq!(paths, ["src/greet.rs", "src/lib.rs"]);
+    assert!(!src.patch.contains("README.md"));
+    assert!(src.patch.contains("+    43"));
+
+    let readme = diff(Some(&["README.md"]));
+    assert_eq!(readme.files.len(), 1);
+    assert_eq!((readme.additions, readme.deletions), (2, 0));
+}
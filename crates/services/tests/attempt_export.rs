@@ -0,0 +1,172 @@
+//! Integration tests for task attempt export bundles
+//!
+//! Run with: cargo test --package services --test attempt_export
+
+use forge_core_db::{
+    DBService,
+    models::{
+        execution_process::{
+            CreateExecutionProcess, ExecutionProcess, ExecutionProcessRunReason,
+            ExecutionProcessStatus,
+        },
+        executor_session::{CreateExecutorSession, ExecutorSession},
+        project::{CreateProject, Project},
+        task::{CreateTask, Task},
+        task_attempt::{CreateTaskAttempt, TaskAttempt},
+    },
+};
+use forge_core_executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+    },
+    executors::BaseCodingAgent,
+    profile::ExecutorProfileId,
+};
+use forge_core_services::services::attempt_export::{
+    AttemptExportBundle, load_transcript_turns, render_diff,
+};
+use forge_core_utils::diff::{Diff, DiffChangeKind};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+async fn create_attempt(pool: &sqlx::SqlitePool) -> (Task, TaskAttempt) {
+    let project_id = Uuid::new_v4();
+    let create_project = CreateProject {
+        name: "Export".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+    Project::create(pool, &create_project, project_id)
+        .await
+        .unwrap();
+
+    let create_task = CreateTask::from_title_description(
+        project_id,
+        "Add greeting".to_string(),
+        Some("Say hello from the library".to_string()),
+    );
+    let task = Task::create(pool, &create_task, Uuid::new_v4())
+        .await
+        .unwrap();
+
+    let create_attempt = CreateTaskAttempt {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+        base_branch: "main".to_string(),
+        branch: "forge/add-greeting".to_string(),
+    };
+    let attempt = TaskAttempt::create(pool, &create_attempt, Uuid::new_v4(), task.id)
+        .await
+        .unwrap();
+
+    (task, attempt)
+}
+
+fn modified_file(path: &str, old: &str, new: &str) -> Diff {
+    Diff {
+        change: DiffChangeKind::Modified,
+        old_path: Some(path.to_string()),
+        new_path: Some(path.to_string()),
+        old_content: Some(old.to_string()),
+        new_content: Some(new.to_string()),
+        content_omitted: false,
+        additions: None,
+        deletions: None,
+    }
+}
+
+#[tokio::test]
+async fn export_bundle_contains_transcript_and_diff() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+    let (task, attempt) = create_attempt(pool).await;
+
+    let action = ExecutorAction::new(
+        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt: "Add a greet function".to_string(),
+            executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+        }),
+        None,
+    );
+    let process = ExecutionProcess::create(
+        pool,
+        &CreateExecutionProcess {
+            task_attempt_id: Some(attempt.id),
+            execution_run_id: None,
+            executor_action: action,
+            run_reason: ExecutionProcessRunReason::CodingAgent,
+        },
+        Uuid::new_v4(),
+        None,
+    )
+    .await
+    .unwrap();
+    ExecutorSession::create(
+        pool,
+        &CreateExecutorSession {
+            task_attempt_id: Some(attempt.id),
+            execution_process_id: process.id,
+            prompt: Some("Add a greet function".to_string()),
+        },
+        Uuid::new_v4(),
+    )
+    .await
+    .unwrap();
+    ExecutorSession::update_summary(pool, process.id, "Added greet() to src/lib.rs")
+        .await
+        .unwrap();
+    ExecutionProcess::update_completion(
+        pool,
+        process.id,
+        ExecutionProcessStatus::Completed,
+        Some(0),
+    )
+    .await
+    .unwrap();
+
+    let turns = load_transcript_turns(pool, attempt.id).await.unwrap();
+    assert_eq!(turns.len(), 1);
+
+    let diffs = vec![modified_file(
+        "src/lib.rs",
+        "pub fn answer() -> u32 {\n    42\n}\n",
+        "pub fn answer() -> u32 {\n    42\n}\n\npub fn greet() -> &'static str {\n    \"hello\"\n}\n",
+    )];
+    let bundle = AttemptExportBundle::new(&task, &attempt, &turns, &diffs, None);
+
+    assert_eq!(bundle.metadata.attempt_id, attempt.id);
+    assert_eq!(bundle.metadata.task_title, "Add greeting");
+    assert!(bundle.transcript.contains("## Turn 1 (Completed, exit code 0)"));
+    assert!(bundle.transcript.contains("Add a greet function"));
+    assert!(bundle.transcript.contains("Added greet() to src/lib.rs"));
+    assert!(bundle.diff.contains("+++ b/src/lib.rs"));
+    assert!(bundle.diff.contains("+pub fn greet() -> &'static str {"));
+    assert!(!bundle.diff_truncated);
+}
+
+#[test]
+fn render_diff_omits_files_past_the_cap() {
+    let small = modified_file("a.txt", "one\n", "two\n");
+    let large = modified_file("b.txt", "", &"x\n".repeat(1024));
+
+    let (diff, truncated) = render_diff(&[small, large], 256);
+
+    assert!(truncated);
+    assert!(diff.contains("+++ b/a.txt"));
+    assert!(!diff.contains("b.txt"));
+    assert!(diff.contains("# 1 file(s) omitted"));
+}
@@ -0,0 +1,97 @@
+//! Integration tests for moving a task attempt to another task
+//!
+//! Run with: cargo test --package services --test attempt_move
+
+use forge_core_db::{
+    DBService,
+    models::{
+        project::{CreateProject, Project},
+        task::{CreateTask, Task},
+        task_attempt::{CreateTaskAttempt, TaskAttempt},
+    },
+};
+use forge_core_executors::executors::BaseCodingAgent;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+async fn create_task(pool: &sqlx::SqlitePool, project_id: Uuid, title: &str) -> Task {
+    let create_task = CreateTask::from_title_description(project_id, title.to_string(), None);
+    Task::create(pool, &create_task, Uuid::new_v4())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn moving_an_attempt_updates_its_task_and_keeps_child_tasks() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+
+    let project_id = Uuid::new_v4();
+    let create_project = CreateProject {
+        name: "Move".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+    Project::create(pool, &create_project, project_id)
+        .await
+        .unwrap();
+
+    let misfiled = create_task(pool, project_id, "Fix login redirect").await;
+    let correct = create_task(pool, project_id, "Rework session handling").await;
+
+    let create_attempt = CreateTaskAttempt {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+        base_branch: "main".to_string(),
+        branch: "forge/session-handling".to_string(),
+    };
+    let attempt = TaskAttempt::create(pool, &create_attempt, Uuid::new_v4(), misfiled.id)
+        .await
+        .unwrap();
+
+    let mut create_child =
+        CreateTask::from_title_description(project_id, "Follow-up cleanup".to_string(), None);
+    create_child.parent_task_attempt = Some(attempt.id);
+    let child = Task::create(pool, &create_child, Uuid::new_v4())
+        .await
+        .unwrap();
+
+    let moved_children = TaskAttempt::move_to_task(pool, attempt.id, correct.id)
+        .await
+        .unwrap();
+    assert_eq!(moved_children, 1);
+
+    let moved = TaskAttempt::find_by_id(pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(moved.task_id, correct.id);
+    assert!(
+        TaskAttempt::fetch_all(pool, Some(misfiled.id))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let target_attempts = TaskAttempt::fetch_all(pool, Some(correct.id)).await.unwrap();
+    assert_eq!(target_attempts.len(), 1);
+    assert_eq!(target_attempts[0].id, attempt.id);
+
+    let child = Task::find_by_id(pool, child.id).await.unwrap().unwrap();
+    assert_eq!(child.parent_task_attempt, Some(attempt.id));
+    let parent = moved.parent_task(pool).await.unwrap().unwrap();
+    assert_eq!(parent.id, correct.id);
+}
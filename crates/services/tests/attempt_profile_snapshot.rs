@@ -0,0 +1,103 @@
+//! Integration tests for executor profile snapshots taken when an attempt starts
+//!
+//! Run with: cargo test --package services --test attempt_profile_snapshot
+
+use forge_core_db::{
+    DBService,
+    models::{
+        attempt_profile_snapshot::AttemptProfileSnapshot,
+        project::{CreateProject, Project},
+        task::{CreateTask, Task},
+        task_attempt::{CreateTaskAttempt, TaskAttempt},
+    },
+};
+use forge_core_executors::{
+    executors::{BaseCodingAgent, CodingAgent},
+    profile::{ExecutorConfigs, ExecutorProfileId, GENIE_VARIANT},
+};
+use forge_core_services::services::container::snapshot_attempt_profile;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+fn set_claude_model(configs: &mut ExecutorConfigs, model: &str) {
+    let agent = configs
+        .executors
+        .get_mut(&BaseCodingAgent::ClaudeCode)
+        .and_then(|executor| executor.configurations.get_mut(GENIE_VARIANT))
+        .unwrap();
+    let CodingAgent::ClaudeCode(claude) = agent else {
+        panic!("GENIE variant of CLAUDE_CODE is not a Claude Code config");
+    };
+    claude.model = Some(model.to_string());
+}
+
+#[tokio::test]
+async fn snapshot_keeps_model_from_attempt_start() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+
+    let project_id = Uuid::new_v4();
+    let create_project = CreateProject {
+        name: "Snapshot".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+    Project::create(pool, &create_project, project_id)
+        .await
+        .unwrap();
+    let create_task =
+        CreateTask::from_title_description(project_id, "Add greeting".to_string(), None);
+    let task = Task::create(pool, &create_task, Uuid::new_v4())
+        .await
+        .unwrap();
+    let create_attempt = CreateTaskAttempt {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+        base_branch: "main".to_string(),
+        branch: "forge/add-greeting".to_string(),
+    };
+    let attempt = TaskAttempt::create(pool, &create_attempt, Uuid::new_v4(), task.id)
+        .await
+        .unwrap();
+
+    let profile_id = ExecutorProfileId::new(BaseCodingAgent::ClaudeCode);
+    let mut configs = ExecutorConfigs::from_defaults();
+    set_claude_model(&mut configs, "opus");
+
+    assert!(
+        snapshot_attempt_profile(pool, attempt.id, &profile_id, &configs)
+            .await
+            .unwrap()
+    );
+
+    // Editing the profile afterwards (and snapshotting again) leaves the pinned config alone
+    set_claude_model(&mut configs, "sonnet");
+    snapshot_attempt_profile(pool, attempt.id, &profile_id, &configs)
+        .await
+        .unwrap();
+
+    let snapshot = AttemptProfileSnapshot::find_by_task_attempt_id(pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(snapshot.executor_profile, profile_id.to_string());
+    assert_eq!(snapshot.config["CLAUDE_CODE"]["model"], "opus");
+    assert_eq!(
+        snapshot.config["CLAUDE_CODE"]["dangerously_skip_permissions"],
+        true
+    );
+}
@@ -0,0 +1,118 @@
+//! Integration tests for the executor variant stored alongside each attempt
+//!
+//! Run with: cargo test --package services --test attempt_variant
+
+use forge_core_db::{
+    DBService,
+    models::{
+        project::{CreateProject, Project},
+        task::{CreateTask, Task},
+        task_attempt::{CreateTaskAttempt, TaskAttempt},
+    },
+};
+use forge_core_executors::executors::BaseCodingAgent;
+use sqlx::SqlitePool;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+async fn create_task(pool: &SqlitePool) -> Task {
+    let project_id = Uuid::new_v4();
+    let create_project = CreateProject {
+        name: "Variants".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+    Project::create(pool, &create_project, project_id)
+        .await
+        .unwrap();
+    let create_task =
+        CreateTask::from_title_description(project_id, "Add greeting".to_string(), None);
+    Task::create(pool, &create_task, Uuid::new_v4())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn started_attempt_keeps_executor_and_variant_apart() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+    let task = create_task(pool).await;
+
+    let create_attempt = CreateTaskAttempt {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: Some("GENIE".to_string()),
+        base_branch: "main".to_string(),
+        branch: "forge/add-greeting".to_string(),
+    };
+    let created = TaskAttempt::create(pool, &create_attempt, Uuid::new_v4(), task.id)
+        .await
+        .unwrap();
+    assert_eq!(created.executor, "CLAUDE_CODE");
+    assert_eq!(created.variant.as_deref(), Some("GENIE"));
+
+    let loaded = TaskAttempt::find_by_id(pool, created.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(loaded.executor, "CLAUDE_CODE");
+    assert_eq!(loaded.variant.as_deref(), Some("GENIE"));
+    assert_eq!(loaded.executor_and_variant(), ("CLAUDE_CODE", Some("GENIE")));
+
+    let value = serde_json::to_value(&loaded).unwrap();
+    assert_eq!(value["executor"], "CLAUDE_CODE");
+    assert_eq!(value["variant"], "GENIE");
+
+    let default_attempt = CreateTaskAttempt {
+        variant: None,
+        branch: "forge/add-greeting-2".to_string(),
+        ..create_attempt
+    };
+    let created = TaskAttempt::create(pool, &default_attempt, Uuid::new_v4(), task.id)
+        .await
+        .unwrap();
+    assert_eq!(created.executor_and_variant(), ("CLAUDE_CODE", None));
+}
+
+#[tokio::test]
+async fn legacy_executor_variant_strings_are_split_on_read() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+    let task = create_task(pool).await;
+
+    let create_attempt = CreateTaskAttempt {
+        executor: BaseCodingAgent::Codex,
+        variant: None,
+        base_branch: "main".to_string(),
+        branch: "forge/add-greeting".to_string(),
+    };
+    let attempt = TaskAttempt::create(pool, &create_attempt, Uuid::new_v4(), task.id)
+        .await
+        .unwrap();
+    // Rows written by older releases carried the variant inside `executor`
+    sqlx::query("UPDATE task_attempts SET executor = 'CODEX:PLAN' WHERE id = ?")
+        .bind(attempt.id)
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let loaded = TaskAttempt::find_by_id(pool, attempt.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(loaded.variant, None);
+    assert_eq!(loaded.executor_and_variant(), ("CODEX", Some("PLAN")));
+}
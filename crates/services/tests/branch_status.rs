@@ -0,0 +1,112 @@
+use chrono::Utc;
+use forge_core_db::models::merge::{Merge, MergeStatus, PrMerge, PullRequestInfo};
+use forge_core_services::services::git::{BranchStatus, ConflictOp};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+fn conflicted_status() -> BranchStatus {
+    BranchStatus {
+        commits_behind: Some(3),
+        commits_ahead: Some(5),
+        has_uncommitted_changes: Some(true),
+        head_oid: Some("4b825dc642cb6eb9a060e54bf8d69288fbee4904".to_string()),
+        uncommitted_count: Some(2),
+        untracked_count: Some(1),
+        target_branch_name: "main".to_string(),
+        remote_commits_behind: Some(1),
+        remote_commits_ahead: Some(0),
+        merges: vec![Merge::Pr(PrMerge {
+            id: Uuid::new_v4(),
+            task_attempt_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            target_branch_name: "main".to_string(),
+            pr_info: PullRequestInfo {
+                number: 42,
+                url: "https://github.com/example/repo/pull/42".to_string(),
+                status: MergeStatus::Open,
+                merged_at: None,
+                merge_commit_sha: None,
+            },
+        })],
+        is_rebase_in_progress: true,
+        conflict_op: Some(ConflictOp::Rebase),
+        conflicted_files: vec!["src/lib.rs".to_string(), "README.md".to_string()],
+        last_fetch_at: None,
+    }
+}
+
+#[test]
+fn branch_status_round_trips_through_json() {
+    let status = conflicted_status();
+
+    let value = serde_json::to_value(&status).unwrap();
+    let decoded: BranchStatus = serde_json::from_value(value.clone()).unwrap();
+
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), value);
+    assert_eq!(decoded.conflict_op, Some(ConflictOp::Rebase));
+    assert_eq!(decoded.conflicted_files, status.conflicted_files);
+    assert_eq!(decoded.remote_commits_behind, Some(1));
+    assert_eq!(decoded.remote_commits_ahead, Some(0));
+    assert!(matches!(decoded.merges.as_slice(), [Merge::Pr(pr)] if pr.pr_info.number == 42));
+}
+
+#[test]
+fn branch_status_wire_format_is_stable() {
+    let value = serde_json::to_value(conflicted_status()).unwrap();
+
+    assert_eq!(value["conflict_op"], json!("rebase"));
+    assert_eq!(value["merges"][0]["type"], json!("pr"));
+
+    let mut keys: Vec<&str> = value
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    keys.sort_unstable();
+    assert_eq!(
+        keys,
+        [
+            "commits_ahead",
+            "commits_behind",
+            "conflict_op",
+            "conflicted_files",
+            "has_uncommitted_changes",
+            "head_oid",
+            "is_rebase_in_progress",
+            "merges",
+            "remote_commits_ahead",
+            "remote_commits_behind",
+            "target_branch_name",
+            "uncommitted_count",
+            "untracked_count",
+        ]
+    );
+}
+
+#[test]
+fn branch_status_accepts_clean_project_payload() {
+    // Shape produced for a project checkout: no merges and no conflict in progress
+    let payload: Value = json!({
+        "commits_behind": null,
+        "commits_ahead": null,
+        "has_uncommitted_changes": false,
+        "head_oid": null,
+        "uncommitted_count": null,
+        "untracked_count": null,
+        "target_branch_name": "dev",
+        "remote_commits_behind": null,
+        "remote_commits_ahead": null,
+        "merges": [],
+        "is_rebase_in_progress": false,
+        "conflict_op": null,
+        "conflicted_files": []
+    });
+
+    let status: BranchStatus = serde_json::from_value(payload.clone()).unwrap();
+
+    assert_eq!(status.target_branch_name, "dev");
+    assert_eq!(status.has_uncommitted_changes, Some(false));
+    assert!(status.conflict_op.is_none());
+    assert_eq!(serde_json::to_value(&status).unwrap(), payload);
+}
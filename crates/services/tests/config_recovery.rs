@@ -0,0 +1,86 @@
+use std::fs;
+
+use forge_core_services::services::config::{Config, ThemeMode, UiLanguage, load_config_from_file};
+use serde_json::json;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn config_with_one_bad_field_recovers_remaining_fields() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.json");
+    let raw_config = json!({
+        "config_version": "v7",
+        "theme": "DARK",
+        "executor_profile": { "executor": "CODEX" },
+        "disclaimer_acknowledged": true,
+        "onboarding_acknowledged": true,
+        "github_login_acknowledged": true,
+        "telemetry_acknowledged": true,
+        "notifications": {
+            "sound_enabled": false,
+            "push_enabled": false,
+            "sound_file": "PHONE_VIBRATION"
+        },
+        "editor": { "editor_type": "ZED", "custom_command": null },
+        "github": {
+            "pat": null,
+            "oauth_token": "gho_test",
+            "username": "octocat",
+            "primary_email": null,
+            "default_pr_base": "develop"
+        },
+        "analytics_enabled": "definitely",
+        "contact_email_opt_in": null,
+        "contact_username_opt_in": null,
+        "workspace_dir": "/tmp/forge-workspace",
+        "last_app_version": "0.7.3",
+        "show_release_notes": true,
+        "language": "JA",
+        "git_branch_prefix": "feature",
+        "showcases": { "seen_features": ["kanban"] }
+    })
+    .to_string();
+    fs::write(&config_path, &raw_config).unwrap();
+
+    let config = load_config_from_file(&config_path).await;
+
+    // The bad field falls back to its default...
+    assert_eq!(config.analytics_enabled, None);
+    // ...while everything else is kept.
+    assert!(matches!(config.theme, ThemeMode::Dark));
+    assert!(matches!(config.language, UiLanguage::Ja));
+    assert!(config.onboarding_acknowledged);
+    assert!(!config.notifications.sound_enabled);
+    assert_eq!(config.github.username.as_deref(), Some("octocat"));
+    assert_eq!(config.workspace_dir.as_deref(), Some("/tmp/forge-workspace"));
+    assert_eq!(config.git_branch_prefix, "feature");
+    assert_eq!(config.showcases.seen_features, vec!["kanban".to_string()]);
+
+    let backup = fs::read_to_string(temp_dir.path().join("config.json.bak")).unwrap();
+    assert_eq!(backup, raw_config);
+}
+
+#[tokio::test]
+async fn unrecoverable_config_falls_back_to_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.json");
+    fs::write(&config_path, "not json at all").unwrap();
+
+    let config = load_config_from_file(&config_path).await;
+
+    assert_eq!(config.config_version, "v8");
+    assert!(!config.onboarding_acknowledged);
+    assert!(temp_dir.path().join("config.json.bak").exists());
+}
+
+#[tokio::test]
+async fn valid_config_is_not_backed_up() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.json");
+    let raw_config = serde_json::to_string(&Config::default()).unwrap();
+    fs::write(&config_path, raw_config).unwrap();
+
+    load_config_from_file(&config_path).await;
+
+    assert!(!temp_dir.path().join("config.json.bak").exists());
+}
@@ -12,10 +12,22 @@
 use forge_core_db::{
     DBService,
     models::{
+        execution_process::{
+            CreateExecutionProcess, ExecutionProcess, ExecutionProcessRunReason,
+            ExecutionProcessStatus,
+        },
         execution_run::ExecutionRun,
         project::{CreateProject, Project},
     },
 };
+use forge_core_executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+    },
+    executors::BaseCodingAgent,
+    profile::ExecutorProfileId,
+};
+use forge_core_services::services::container::{RunResumeState, reconcile_run_process};
 use tempfile::TempDir;
 use uuid::Uuid;
 
@@ -50,6 +62,7 @@ async fn create_test_project(pool: &sqlx::SqlitePool) -> Uuid {
         cleanup_script: None,
         copy_files: None,
         commit_prompt: None,
+        clone_url: None,
     };
 
     Project::create(pool, &create_data, project_id)
@@ -276,3 +289,85 @@ async fn test_execution_run_mark_worktree_deleted() {
         .unwrap();
     assert!(deleted.worktree_deleted);
 }
+
+/// Create a run with a coding agent process the database still marks as running
+async fn create_running_run_process(pool: &sqlx::SqlitePool) -> ExecutionProcess {
+    let project_id = create_test_project(pool).await;
+    let run_id = Uuid::new_v4();
+    let create_data = forge_core_db::models::execution_run::CreateExecutionRun {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+        base_branch: "main".to_string(),
+        prompt: "Resume test".to_string(),
+    };
+    ExecutionRun::create(
+        pool,
+        &create_data,
+        run_id,
+        project_id,
+        &format!("run/{}", &run_id.to_string()[..8]),
+    )
+    .await
+    .unwrap();
+
+    let action = ExecutorAction::new(
+        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt: "Resume test".to_string(),
+            executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+        }),
+        None,
+    );
+    let process = ExecutionProcess::create(
+        pool,
+        &CreateExecutionProcess {
+            task_attempt_id: None,
+            execution_run_id: Some(run_id),
+            executor_action: action,
+            run_reason: ExecutionProcessRunReason::CodingAgent,
+        },
+        Uuid::new_v4(),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(process.status, ExecutionProcessStatus::Running);
+    process
+}
+
+#[tokio::test]
+async fn resuming_a_run_with_a_live_process_leaves_it_running() {
+    let (db, _temp) = setup_test_db().await;
+    let pool = &db.pool;
+    let process = create_running_run_process(pool).await;
+
+    let state = reconcile_run_process(pool, &process, true).await.unwrap();
+    assert_eq!(state, RunResumeState::Attached);
+
+    let process = ExecutionProcess::find_by_id(pool, process.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(process.status, ExecutionProcessStatus::Running);
+    assert!(process.completed_at.is_none());
+}
+
+#[tokio::test]
+async fn resuming_a_run_with_an_orphaned_process_marks_it_failed() {
+    let (db, _temp) = setup_test_db().await;
+    let pool = &db.pool;
+    let process = create_running_run_process(pool).await;
+
+    let state = reconcile_run_process(pool, &process, false).await.unwrap();
+    assert_eq!(state, RunResumeState::Orphaned);
+
+    let process = ExecutionProcess::find_by_id(pool, process.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(process.status, ExecutionProcessStatus::Failed);
+    assert!(process.completed_at.is_some());
+
+    // Resuming again finds a finished process and changes nothing
+    let state = reconcile_run_process(pool, &process, false).await.unwrap();
+    assert_eq!(state, RunResumeState::Finished);
+}
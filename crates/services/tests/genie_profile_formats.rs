@@ -0,0 +1,106 @@
+use std::{fs, path::PathBuf};
+
+use forge_core_executors::{executors::BaseCodingAgent, profile::ExecutorConfigs};
+use forge_core_services::services::profile_loader::GenieProfileLoader;
+use tempfile::TempDir;
+
+const MARKDOWN_AGENT: &str = r#"---
+name: reviewer
+genie:
+  executor: [CLAUDE_CODE, CODEX]
+forge:
+  CLAUDE_CODE:
+    model: opus
+  CODEX:
+    model_reasoning_effort: high
+---
+Review the diff and point out risky changes.
+"#;
+
+const JSON_AGENT: &str = r#"{
+  "name": "reviewer",
+  "genie": { "executor": ["CLAUDE_CODE", "CODEX"] },
+  "forge": {
+    "CLAUDE_CODE": { "model": "opus" },
+    "CODEX": { "model_reasoning_effort": "high" }
+  },
+  "instructions": "Review the diff and point out risky changes.\n"
+}
+"#;
+
+const YAML_AGENT: &str = r#"name: reviewer
+genie:
+  executor: [CLAUDE_CODE, CODEX]
+forge:
+  CLAUDE_CODE:
+    model: opus
+  CODEX:
+    model_reasoning_effort: high
+instructions: |
+  Review the diff and point out risky changes.
+"#;
+
+/// Workspaces share the directory name "project" because it prefixes derived variant names
+fn workspace_with_files(files: &[(&str, &str)]) -> (TempDir, PathBuf) {
+    let root = TempDir::new().unwrap();
+    let workspace = root.path().join("project");
+    let agents_dir = workspace.join(".genie").join("agents");
+    fs::create_dir_all(&agents_dir).unwrap();
+    for (name, content) in files {
+        fs::write(agents_dir.join(name), content).unwrap();
+    }
+    (root, workspace)
+}
+
+fn load(files: &[(&str, &str)]) -> ExecutorConfigs {
+    let (_root, workspace) = workspace_with_files(files);
+    GenieProfileLoader::new(workspace).load_profiles().unwrap()
+}
+
+#[test]
+fn json_and_markdown_agents_generate_identical_profiles() {
+    let markdown = load(&[("reviewer.md", MARKDOWN_AGENT)]);
+    let json = load(&[("reviewer.agent.json", JSON_AGENT)]);
+    let yaml = load(&[("reviewer.agent.yaml", YAML_AGENT)]);
+
+    assert_eq!(markdown.executors.len(), 2);
+    assert!(
+        markdown.executors[&BaseCodingAgent::ClaudeCode]
+            .configurations
+            .contains_key("PROJECT_REVIEWER")
+    );
+    assert_eq!(json, markdown);
+    assert_eq!(yaml, markdown);
+}
+
+#[test]
+fn structured_files_follow_the_documentation_exclusions() {
+    let configs = load(&[
+        ("README.agent.json", JSON_AGENT),
+        ("AGENTS.agent.yaml", YAML_AGENT),
+        ("notes.json", JSON_AGENT),
+    ]);
+
+    assert!(configs.executors.is_empty());
+}
+
+#[test]
+fn structured_agents_in_collectives_get_collective_context() {
+    let (_root, workspace) = workspace_with_files(&[]);
+    let collective = workspace.join(".genie").join("code");
+    fs::create_dir_all(collective.join("agents")).unwrap();
+    fs::write(collective.join("AGENTS.md"), "Code collective rules.\n").unwrap();
+    fs::write(
+        collective.join("agents").join("reviewer.agent.json"),
+        JSON_AGENT,
+    )
+    .unwrap();
+
+    let configs = GenieProfileLoader::new(&workspace).load_profiles().unwrap();
+    let claude = &configs.executors[&BaseCodingAgent::ClaudeCode];
+    let profile = serde_json::to_value(&claude.configurations["PROJECT_CODE_REVIEWER"]).unwrap();
+    let prompt = profile["CLAUDE_CODE"]["append_prompt"].as_str().unwrap();
+
+    assert!(prompt.starts_with("Code collective rules."));
+    assert!(prompt.ends_with("Review the diff and point out risky changes."));
+}
@@ -0,0 +1,92 @@
+use std::fs;
+
+use forge_core_executors::executors::{BaseCodingAgent, CodingAgent};
+use forge_core_services::services::profile_loader::GenieProfileLoader;
+use serde_json::json;
+use tempfile::TempDir;
+
+const AGENT_WITH_MCP_SERVERS: &str = r#"---
+name: researcher
+genie:
+  executor: [CLAUDE_CODE, CODEX]
+forge:
+  CLAUDE_CODE:
+    model: sonnet
+    mcp_servers:
+      filesystem:
+        command: npx
+        args: ["-y", "@modelcontextprotocol/server-filesystem", "."]
+      fetch:
+        command: uvx
+        args: ["mcp-server-fetch"]
+  CODEX:
+    mcp_servers:
+      fetch:
+        command: uvx
+        args: ["mcp-server-fetch"]
+---
+Research the codebase before answering.
+"#;
+
+fn workspace_with_agent(content: &str) -> TempDir {
+    let root = TempDir::new().unwrap();
+    let agents_dir = root.path().join(".genie").join("agents");
+    fs::create_dir_all(&agents_dir).unwrap();
+    fs::write(agents_dir.join("researcher.md"), content).unwrap();
+    root
+}
+
+fn single_profile(root: &TempDir, executor: BaseCodingAgent) -> CodingAgent {
+    let configs = GenieProfileLoader::new(root.path()).load_profiles().unwrap();
+    let executor_config = configs
+        .executors
+        .get(&executor)
+        .unwrap_or_else(|| panic!("no profiles generated for {executor}"));
+    assert_eq!(executor_config.configurations.len(), 1);
+    executor_config
+        .configurations
+        .values()
+        .next()
+        .cloned()
+        .unwrap()
+}
+
+#[test]
+fn profile_mcp_servers_are_applied_to_claude_code() {
+    let root = workspace_with_agent(AGENT_WITH_MCP_SERVERS);
+
+    let CodingAgent::ClaudeCode(claude) = single_profile(&root, BaseCodingAgent::ClaudeCode) else {
+        panic!("expected a ClaudeCode profile");
+    };
+
+    let servers = claude.mcp_servers.expect("mcp_servers should be set");
+    assert_eq!(servers.len(), 2);
+    assert_eq!(servers["filesystem"]["command"], json!("npx"));
+    assert_eq!(servers["fetch"]["args"], json!(["mcp-server-fetch"]));
+    assert_eq!(claude.model.as_deref(), Some("sonnet"));
+}
+
+#[test]
+fn profile_mcp_servers_are_scoped_per_executor() {
+    let root = workspace_with_agent(AGENT_WITH_MCP_SERVERS);
+
+    let CodingAgent::Codex(codex) = single_profile(&root, BaseCodingAgent::Codex) else {
+        panic!("expected a Codex profile");
+    };
+
+    let servers = codex.mcp_servers.expect("mcp_servers should be set");
+    assert_eq!(servers.keys().collect::<Vec<_>>(), ["fetch"]);
+}
+
+#[test]
+fn profile_without_mcp_servers_leaves_them_unset() {
+    let root = workspace_with_agent(
+        "---\nname: plain\nforge:\n  model: sonnet\n---\nNo extra servers.\n",
+    );
+
+    let CodingAgent::ClaudeCode(claude) = single_profile(&root, BaseCodingAgent::ClaudeCode) else {
+        panic!("expected a ClaudeCode profile");
+    };
+
+    assert!(claude.mcp_servers.is_none());
+}
@@ -0,0 +1,79 @@
+use std::fs;
+
+use forge_core_executors::{
+    executors::{BaseCodingAgent, CodingAgent},
+    safe_mode::suppress_dangerous_flags,
+};
+use forge_core_services::services::profile_loader::GenieProfileLoader;
+use tempfile::TempDir;
+
+const PERMISSIVE_AGENT: &str = r#"---
+name: yolo-runner
+genie:
+  executor: [CLAUDE_CODE, GEMINI]
+forge:
+  CLAUDE_CODE:
+    model: opus
+    dangerously_skip_permissions: true
+  GEMINI:
+    yolo: true
+---
+Ship it.
+"#;
+
+fn workspace_with_agent(content: &str) -> TempDir {
+    let root = TempDir::new().unwrap();
+    let agents_dir = root.path().join(".genie").join("agents");
+    fs::create_dir_all(&agents_dir).unwrap();
+    fs::write(agents_dir.join("yolo-runner.md"), content).unwrap();
+    root
+}
+
+/// The agent's generated profile, as the executor would spawn it with `safe_mode`
+fn profile(root: &TempDir, safe_mode: bool, executor: BaseCodingAgent) -> CodingAgent {
+    let configs = GenieProfileLoader::new(root.path())
+        .load_profiles()
+        .unwrap();
+    let agent = configs.executors[&executor]
+        .configurations
+        .values()
+        .next()
+        .cloned()
+        .unwrap();
+    if safe_mode {
+        suppress_dangerous_flags(agent).unwrap().0
+    } else {
+        agent
+    }
+}
+
+#[test]
+fn safe_mode_strips_permission_bypassing_flags() {
+    let root = workspace_with_agent(PERMISSIVE_AGENT);
+
+    let CodingAgent::ClaudeCode(claude) = profile(&root, true, BaseCodingAgent::ClaudeCode) else {
+        panic!("expected a ClaudeCode profile");
+    };
+    assert_eq!(claude.dangerously_skip_permissions, None);
+    assert_eq!(claude.model.as_deref(), Some("opus"));
+
+    let CodingAgent::Gemini(gemini) = profile(&root, true, BaseCodingAgent::Gemini) else {
+        panic!("expected a Gemini profile");
+    };
+    assert_eq!(gemini.yolo, None);
+}
+
+#[test]
+fn disabled_safe_mode_preserves_flags() {
+    let root = workspace_with_agent(PERMISSIVE_AGENT);
+
+    let CodingAgent::ClaudeCode(claude) = profile(&root, false, BaseCodingAgent::ClaudeCode) else {
+        panic!("expected a ClaudeCode profile");
+    };
+    assert_eq!(claude.dangerously_skip_permissions, Some(true));
+
+    let CodingAgent::Gemini(gemini) = profile(&root, false, BaseCodingAgent::Gemini) else {
+        panic!("expected a Gemini profile");
+    };
+    assert_eq!(gemini.yolo, Some(true));
+}
@@ -0,0 +1,81 @@
+use std::fs;
+
+use forge_core_services::services::profile_loader::GenieProfileLoader;
+use tempfile::TempDir;
+
+const GOOD_AGENT: &str = "---\nname: implementor\ngenie:\n  executor: CLAUDE_CODE\nforge:\n  model: sonnet\n---\nImplement the wish.\n";
+
+const UNKNOWN_EXECUTOR_AGENT: &str =
+    "---\nname: typo\ngenie:\n  executor: [CLAUDE_CODE, CLAUD]\n---\nMisspelled executor.\n";
+
+const MIXED_FORGE_AGENT: &str = r#"{
+  "name": "mixed",
+  "genie": { "executor": [] },
+  "forge": { "model": "sonnet", "CODEX": { "sandbox": "workspace-write" } }
+}
+"#;
+
+fn workspace() -> TempDir {
+    let root = TempDir::new().unwrap();
+    let agents_dir = root.path().join(".genie").join("agents");
+    fs::create_dir_all(&agents_dir).unwrap();
+    fs::write(agents_dir.join("implementor.md"), GOOD_AGENT).unwrap();
+    fs::write(agents_dir.join("typo.md"), UNKNOWN_EXECUTOR_AGENT).unwrap();
+    fs::write(agents_dir.join("mixed.agent.json"), MIXED_FORGE_AGENT).unwrap();
+    root
+}
+
+#[test]
+fn validation_reports_each_broken_field() {
+    let root = workspace();
+    let loader = GenieProfileLoader::new(root.path());
+
+    let mut issues = loader.validate_profiles();
+    issues.sort_by(|a, b| (&a.file_path, &a.field).cmp(&(&b.file_path, &b.field)));
+
+    let summary: Vec<(&str, &str)> = issues
+        .iter()
+        .map(|issue| (issue.file_path.as_str(), issue.field.as_str()))
+        .collect();
+    let mixed = format!(
+        ".genie{0}agents{0}mixed.agent.json",
+        std::path::MAIN_SEPARATOR
+    );
+    let typo = format!(".genie{0}agents{0}typo.md", std::path::MAIN_SEPARATOR);
+    assert_eq!(
+        summary,
+        vec![
+            (mixed.as_str(), "forge"),
+            (mixed.as_str(), "genie.executor"),
+            (typo.as_str(), "genie.executor"),
+        ]
+    );
+
+    assert!(issues[0].message.contains("cannot mix flat fields"));
+    assert!(issues[1].message.contains("cannot be empty"));
+    assert!(issues[2].message.contains("CLAUD"));
+}
+
+#[test]
+fn broken_files_do_not_stop_valid_profiles_from_loading() {
+    let root = workspace();
+    let loader = GenieProfileLoader::new(root.path());
+
+    let configs = loader.load_profiles().unwrap();
+    let variants: usize = configs
+        .executors
+        .values()
+        .map(|config| config.configurations.len())
+        .sum();
+    assert_eq!(variants, 1, "only the valid agent should load");
+}
+
+#[test]
+fn workspace_without_genie_has_no_issues() {
+    let root = TempDir::new().unwrap();
+    assert!(
+        GenieProfileLoader::new(root.path())
+            .validate_profiles()
+            .is_empty()
+    );
+}
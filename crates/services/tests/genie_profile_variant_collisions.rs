@@ -0,0 +1,90 @@
+use std::{fs, path::Path};
+
+use forge_core_executors::executors::BaseCodingAgent;
+use forge_core_services::services::profile_loader::GenieProfileLoader;
+use tempfile::TempDir;
+
+fn write_agent(dir: &Path, file_name: &str, name: &str, prompt: &str) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(
+        dir.join(file_name),
+        format!("---\nname: {name}\ngenie:\n  executor: [CLAUDE_CODE, CODEX]\n---\n{prompt}\n"),
+    )
+    .unwrap();
+}
+
+fn prompts_by_variant(workspace: &Path, executor: BaseCodingAgent) -> Vec<(String, String)> {
+    let configs = GenieProfileLoader::new(workspace).load_profiles().unwrap();
+    let mut prompts: Vec<(String, String)> = configs.executors[&executor]
+        .configurations
+        .iter()
+        .map(|(variant, profile)| {
+            let profile = serde_json::to_value(profile).unwrap();
+            let prompt = profile[executor.to_string()]["append_prompt"]
+                .as_str()
+                .unwrap()
+                .to_string();
+            (variant.clone(), prompt)
+        })
+        .collect();
+    prompts.sort();
+    prompts
+}
+
+#[test]
+fn colliding_variants_are_namespaced_by_collective() {
+    let root = TempDir::new().unwrap();
+    let workspace = root.path().join("project");
+    let genie = workspace.join(".genie");
+    // Both derive PROJECT_CODE_REVIEWER: a global "code-reviewer" and the code collective's
+    // "reviewer"
+    write_agent(
+        &genie.join("agents"),
+        "code-reviewer.md",
+        "code-reviewer",
+        "Global",
+    );
+    fs::create_dir_all(genie.join("code")).unwrap();
+    fs::write(genie.join("code").join("AGENTS.md"), "Code rules.\n").unwrap();
+    write_agent(
+        &genie.join("code").join("agents"),
+        "reviewer.md",
+        "reviewer",
+        "Collective",
+    );
+
+    for executor in [BaseCodingAgent::ClaudeCode, BaseCodingAgent::Codex] {
+        let prompts = prompts_by_variant(&workspace, executor);
+        let variants: Vec<&str> = prompts.iter().map(|(v, _)| v.as_str()).collect();
+        assert_eq!(
+            variants,
+            ["PROJECT_CODE_REVIEWER", "PROJECT_CODE_REVIEWER_CODE"]
+        );
+        assert_eq!(prompts[0].1, "Global");
+        assert!(prompts[1].1.ends_with("Collective"));
+    }
+}
+
+#[test]
+fn colliding_variants_without_collective_use_the_file_stem() {
+    let root = TempDir::new().unwrap();
+    let workspace = root.path().join("project");
+    let agents = workspace.join(".genie").join("agents");
+    write_agent(&agents, "alpha.md", "planner", "Alpha");
+    write_agent(&agents.join("nested"), "beta.md", "planner", "Beta");
+
+    let first = prompts_by_variant(&workspace, BaseCodingAgent::ClaudeCode);
+    assert_eq!(
+        first,
+        [
+            ("PROJECT_PLANNER".to_string(), "Alpha".to_string()),
+            ("PROJECT_PLANNER_BETA".to_string(), "Beta".to_string()),
+        ]
+    );
+
+    // Disambiguation doesn't depend on directory listing order
+    assert_eq!(
+        prompts_by_variant(&workspace, BaseCodingAgent::ClaudeCode),
+        first
+    );
+}
@@ -5,8 +5,8 @@ use std::{
 };
 
 use forge_core_services::services::{
-    git::GitService,
-    git_cli::{GitCli, GitCliError},
+    git::{ConflictOp, GitService, GitServiceError},
+    git_cli::{CommitSigning, GitCli, GitCliError},
 };
 use git2::{PushOptions, Repository, build::CheckoutBuilder};
 use tempfile::TempDir;
@@ -291,6 +291,139 @@ fn push_with_token_reports_non_fast_forward() {
     }
 }
 
+#[test]
+fn push_branch_fast_forwards_and_refuses_to_rewrite_remote_history() {
+    let temp_dir = TempDir::new().unwrap();
+    let remote_path = temp_dir.path().join("remote.git");
+    Repository::init_bare(&remote_path).expect("init bare remote");
+    let remote_url = remote_path.to_str().expect("remote path str");
+
+    let seed_path = temp_dir.path().join("seed");
+    let service = GitService::new();
+    service
+        .initialize_repo_with_main_branch(&seed_path)
+        .expect("init seed repo");
+    let seed_repo = Repository::open(&seed_path).expect("open seed repo");
+    configure_user(&seed_repo);
+    seed_repo.remote("origin", remote_url).expect("add remote");
+    push_ref(&seed_repo, "refs/heads/main", "refs/heads/main");
+    let remote_repo = Repository::open_bare(&remote_path).expect("open bare remote");
+    remote_repo
+        .set_head("refs/heads/main")
+        .expect("set remote HEAD");
+    let remote_main = || {
+        remote_repo
+            .find_reference("refs/heads/main")
+            .unwrap()
+            .target()
+            .unwrap()
+            .to_string()
+    };
+
+    let local_path = temp_dir.path().join("local");
+    let local_repo = Repository::clone(remote_url, &local_path).expect("clone local");
+    configure_user(&local_repo);
+    checkout_branch(&local_repo, "main");
+    let local_head = || local_repo.head().unwrap().target().unwrap().to_string();
+
+    // Plain fast-forward
+    write_file(&local_path, "file.txt", "local work\n");
+    commit_all(&local_repo, "local commit");
+    let pushed = service
+        .push_branch(&local_path, "main", "dummy-token", false)
+        .expect("fast-forward push");
+    assert!(pushed.fast_forward);
+    assert_eq!(pushed.remote_sha, local_head());
+    assert_eq!(remote_main(), local_head());
+
+    // Someone else pushes first; our diverged branch must not overwrite their commit
+    let updater_path = temp_dir.path().join("updater");
+    let updater_repo = Repository::clone(remote_url, &updater_path).expect("clone updater");
+    configure_user(&updater_repo);
+    checkout_branch(&updater_repo, "main");
+    write_file(&updater_path, "file.txt", "upstream change\n");
+    commit_all(&updater_repo, "upstream commit");
+    push_ref(&updater_repo, "refs/heads/main", "refs/heads/main");
+    let upstream = remote_main();
+
+    write_file(&local_path, "file.txt", "more local work\n");
+    commit_all(&local_repo, "second local commit");
+    match service.push_branch(&local_path, "main", "dummy-token", false) {
+        Err(GitServiceError::GitCLI(GitCliError::PushRejected(_))) => {}
+        other => panic!("expected push rejected, got {other:?}"),
+    }
+    assert_eq!(remote_main(), upstream);
+
+    // Forcing rewrites the remote branch and says so
+    let forced = service
+        .push_branch(&local_path, "main", "dummy-token", true)
+        .expect("forced push");
+    assert!(!forced.fast_forward);
+    assert_eq!(remote_main(), local_head());
+}
+
+fn setup_remote_with_feature_branch(root: &TempDir) -> (PathBuf, PathBuf) {
+    let remote_path = root.path().join("remote.git");
+    Repository::init_bare(&remote_path).expect("init bare remote");
+    let remote_url = remote_path.to_str().expect("remote path str");
+
+    let seed_path = root.path().join("seed");
+    let service = GitService::new();
+    service
+        .initialize_repo_with_main_branch(&seed_path)
+        .expect("init seed repo");
+    let seed_repo = Repository::open(&seed_path).expect("open seed repo");
+    configure_user(&seed_repo);
+    seed_repo.remote("origin", remote_url).expect("add remote");
+    push_ref(&seed_repo, "refs/heads/main", "refs/heads/main");
+    push_ref(&seed_repo, "refs/heads/main", "refs/heads/feature");
+    Repository::open_bare(&remote_path)
+        .expect("open bare remote")
+        .set_head("refs/heads/main")
+        .expect("set remote HEAD");
+
+    let local_path = root.path().join("local");
+    let local_repo = Repository::clone(remote_url, &local_path).expect("clone local");
+    checkout_branch(&local_repo, "main");
+    (remote_path, local_path)
+}
+
+#[test]
+fn delete_remote_branch_removes_remote_and_tracking_refs() {
+    let temp_dir = TempDir::new().unwrap();
+    let (remote_path, local_path) = setup_remote_with_feature_branch(&temp_dir);
+    let service = GitService::new();
+
+    service
+        .delete_remote_branch(&local_path, "feature", "dummy-token")
+        .expect("delete remote branch");
+
+    let remote_repo = Repository::open_bare(&remote_path).unwrap();
+    assert!(remote_repo.find_reference("refs/heads/feature").is_err());
+    assert!(remote_repo.find_reference("refs/heads/main").is_ok());
+    let local_repo = Repository::open(&local_path).unwrap();
+    assert!(
+        local_repo
+            .find_reference("refs/remotes/origin/feature")
+            .is_err()
+    );
+}
+
+#[test]
+fn delete_remote_branch_refuses_default_branch() {
+    let temp_dir = TempDir::new().unwrap();
+    let (remote_path, local_path) = setup_remote_with_feature_branch(&temp_dir);
+    let service = GitService::new();
+
+    match service.delete_remote_branch(&local_path, "main", "dummy-token") {
+        Err(GitServiceError::ProtectedBranch(branch, _)) => assert_eq!(branch, "main"),
+        other => panic!("expected protected branch error, got {other:?}"),
+    }
+
+    let remote_repo = Repository::open_bare(&remote_path).unwrap();
+    assert!(remote_repo.find_reference("refs/heads/main").is_ok());
+}
+
 #[test]
 fn fetch_with_token_missing_ref_returns_error() {
     let temp_dir = TempDir::new().unwrap();
@@ -496,6 +629,7 @@ fn merge_does_not_overwrite_main_repo_untracked_files() {
         "feature",
         "main",
         "squash merge",
+        None,
     );
     assert!(
         res.is_err(),
@@ -539,6 +673,7 @@ fn merge_does_not_touch_tracked_uncommitted_changes_in_base_worktree() {
         "feature",
         "main",
         "squash merge",
+        None,
     );
     assert!(
         res.is_ok(),
@@ -568,7 +703,14 @@ fn merge_refuses_with_staged_changes_on_base() {
     // main has staged change
     write_file(&repo_path, "staged.txt", "staged\n");
     s.add_path(&repo_path, "staged.txt").unwrap();
-    let res = s.merge_changes(&repo_path, &worktree_path, "feature", "main", "squash");
+    let res = s.merge_changes(
+        &repo_path,
+        &worktree_path,
+        "feature",
+        "main",
+        "squash",
+        None,
+    );
     assert!(res.is_err(), "should refuse merge due to staged changes");
     // staged file remains
     let content = std::fs::read_to_string(repo_path.join("staged.txt")).unwrap();
@@ -589,7 +731,14 @@ fn merge_preserves_unstaged_changes_on_base() {
     commit_all(&wt_repo, "feature merged");
 
     let _sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            None,
+        )
         .unwrap();
     // local edit preserved
     let loc = std::fs::read_to_string(repo_path.join("common.txt")).unwrap();
@@ -614,7 +763,14 @@ fn update_ref_does_not_destroy_feature_worktree_dirty_state() {
     write_file(&worktree_path, "dirty.txt", "unstaged\n");
     // merge from feature into main (CLI path updates task ref via update-ref)
     let sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            None,
+        )
         .unwrap();
     // uncommitted change in feature worktree preserved
     let dirty = std::fs::read_to_string(worktree_path.join("dirty.txt")).unwrap();
@@ -642,7 +798,14 @@ fn libgit2_merge_updates_base_ref_in_both_repos() {
 
     // Perform merge (squash) while main repo is NOT on base branch (libgit2 path)
     let sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            None,
+        )
         .expect("merge should succeed via libgit2 path");
 
     // Base branch ref advanced in both main and worktree repositories
@@ -652,6 +815,166 @@ fn libgit2_merge_updates_base_ref_in_both_repos() {
     assert_eq!(after_main_wt, sha);
 }
 
+#[test]
+fn annotate_branch_sync_compares_branches_with_default_branch() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, _worktree_path) = setup_repo_with_worktree(&td);
+    let s = GitService::new();
+    // Without a remote the checked-out branch is the default
+    s.checkout_branch(&repo_path, "main").unwrap();
+
+    let mut branches = s.get_all_branches(&repo_path).unwrap();
+    s.annotate_branch_sync(&repo_path, &mut branches).unwrap();
+    let sync = |name: &str| {
+        let branch = branches.iter().find(|b| b.name == name).unwrap();
+        (branch.ahead, branch.behind, branch.is_merged)
+    };
+
+    assert_eq!(sync("main"), (Some(0), Some(0), Some(true)));
+    assert_eq!(sync("old-base"), (Some(1), Some(0), Some(false)));
+    assert_eq!(sync("feature"), (Some(2), Some(0), Some(false)));
+
+    // Fast-forward main onto old-base: old-base is merged and new-base falls behind
+    let repo = Repository::open(&repo_path).unwrap();
+    let old_base = repo.revparse_single("old-base").unwrap().id();
+    repo.reference("refs/heads/main", old_base, true, "fast-forward main")
+        .unwrap();
+    let mut branches = s.get_all_branches(&repo_path).unwrap();
+    assert!(branches.iter().all(|b| b.ahead.is_none()));
+    s.annotate_branch_sync(&repo_path, &mut branches).unwrap();
+    let sync = |name: &str| {
+        let branch = branches.iter().find(|b| b.name == name).unwrap();
+        (branch.ahead, branch.behind, branch.is_merged)
+    };
+    assert_eq!(sync("old-base"), (Some(0), Some(0), Some(true)));
+    assert_eq!(sync("new-base"), (Some(1), Some(1), Some(false)));
+    assert_eq!(sync("feature"), (Some(1), Some(0), Some(false)));
+}
+
+/// Point the repo's `gpg.program` at a stub that records its arguments and prints a
+/// placeholder signature. Returns the path of the argument log.
+#[cfg(unix)]
+fn install_fake_gpg(root: &TempDir, repo: &Repository) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+    let log = root.path().join("gpg-args.log");
+    let script = root.path().join("fake-gpg");
+    fs::write(
+        &script,
+        format!(
+            "#!/bin/sh\necho \"$@\" >> {}\ncat > /dev/null\n\
+             printf '\\n[GNUPG:] SIG_CREATED D 1 8 00 0 0\\n' >&2\n\
+             printf -- '-----BEGIN PGP SIGNATURE-----\\n\\nfake\\n-----END PGP SIGNATURE-----\\n'\n",
+            log.display()
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    repo.config()
+        .unwrap()
+        .set_str("gpg.program", script.to_str().unwrap())
+        .unwrap();
+    log
+}
+
+#[cfg(unix)]
+#[test]
+fn merge_passes_signing_key_to_git_on_both_merge_paths() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let s = GitService::new();
+    let repo = Repository::open(&repo_path).unwrap();
+    let log = install_fake_gpg(&td, &repo);
+    let wt_repo = Repository::open(&worktree_path).unwrap();
+    let is_signed = |sha: &str| {
+        let repo = Repository::open(&repo_path).unwrap();
+        let commit = repo.find_commit(git2::Oid::from_str(sha).unwrap()).unwrap();
+        commit.header_field_bytes("gpgsig").is_ok()
+    };
+
+    // Base branch not checked out: the commit object is written by `git commit-tree`
+    let signing = CommitSigning {
+        key: Some("ABC123".to_string()),
+    };
+    let sha = s
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            Some(&signing),
+        )
+        .expect("signed libgit2-path merge");
+    assert!(is_signed(&sha));
+    assert!(fs::read_to_string(&log).unwrap().contains("-bsau ABC123"));
+
+    // Base branch checked out: the CLI squash merge runs `git commit -S<key>`
+    s.checkout_branch(&repo_path, "main").unwrap();
+    write_file(&worktree_path, "feat2.txt", "more\n");
+    commit_all(&wt_repo, "second feature commit");
+    let signing = CommitSigning {
+        key: Some("DEF456".to_string()),
+    };
+    let sha = s
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash 2",
+            Some(&signing),
+        )
+        .expect("signed CLI merge");
+    assert!(is_signed(&sha));
+    assert!(fs::read_to_string(&log).unwrap().contains("-bsau DEF456"));
+
+    // Without signing nothing is passed to gpg
+    write_file(&worktree_path, "feat3.txt", "unsigned\n");
+    commit_all(&wt_repo, "third feature commit");
+    let before = fs::read_to_string(&log).unwrap();
+    let sha = s
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash 3",
+            None,
+        )
+        .expect("unsigned merge");
+    assert!(!is_signed(&sha));
+    assert_eq!(fs::read_to_string(&log).unwrap(), before);
+}
+
+#[test]
+fn commit_signing_requires_a_key() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, _worktree_path) = setup_repo_with_worktree(&td);
+    let s = GitService::new();
+
+    assert_eq!(
+        s.commit_signing(&repo_path, Some("ABC123")).unwrap(),
+        CommitSigning {
+            key: Some("ABC123".to_string())
+        }
+    );
+    assert!(matches!(
+        s.commit_signing(&repo_path, Some("  ")),
+        Err(GitServiceError::SigningKeyMissing)
+    ));
+
+    // git's own signing key is enough; git picks it up when `-S` has no key
+    let repo = Repository::open(&repo_path).unwrap();
+    repo.config()
+        .unwrap()
+        .set_str("user.signingkey", "FROMGIT")
+        .unwrap();
+    assert_eq!(
+        s.commit_signing(&repo_path, None).unwrap(),
+        CommitSigning { key: None }
+    );
+}
+
 #[test]
 fn libgit2_merge_updates_task_ref_and_feature_head_preserves_dirty() {
     // Hit libgit2 path (main repo not on base) and verify task ref + HEAD update safely
@@ -664,7 +987,14 @@ fn libgit2_merge_updates_task_ref_and_feature_head_preserves_dirty() {
 
     // Perform merge (squash) from feature into main; this path uses libgit2
     let sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            None,
+        )
         .expect("merge should succeed via libgit2 path");
 
     // Dirty file preserved in worktree
@@ -715,6 +1045,95 @@ fn rebase_refuses_to_abort_existing_rebase() {
     // Note: We do not auto-abort; user should resolve or abort explicitly
 }
 
+#[test]
+fn abort_rebase_restores_pre_rebase_head_and_clean_worktree() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let svc = GitService::new();
+    let before = svc.get_branch_oid(&repo_path, "feature").unwrap();
+
+    let _ = svc
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+            None,
+        )
+        .expect_err("rebase should stop on conflicts");
+    assert_eq!(
+        svc.detect_conflict_op(&worktree_path).unwrap(),
+        Some(ConflictOp::Rebase)
+    );
+
+    svc.abort_rebase(&worktree_path).unwrap();
+
+    assert_eq!(svc.detect_conflict_op(&worktree_path).unwrap(), None);
+    assert!(svc.get_conflicted_files(&worktree_path).unwrap().is_empty());
+    assert!(svc.is_worktree_clean(&worktree_path).unwrap());
+    assert_eq!(before, svc.get_branch_oid(&repo_path, "feature").unwrap());
+}
+
+#[test]
+fn abort_merge_restores_pre_merge_head_and_clean_worktree() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_direct_conflict_repo(&td);
+    let svc = GitService::new();
+    let before = svc.get_branch_oid(&repo_path, "feature").unwrap();
+
+    GitCli::new()
+        .git(&worktree_path, ["merge", "main"])
+        .expect_err("merge should stop on conflicts");
+    assert_eq!(
+        svc.detect_conflict_op(&worktree_path).unwrap(),
+        Some(ConflictOp::Merge)
+    );
+
+    svc.abort_merge(&worktree_path).unwrap();
+
+    assert_eq!(svc.detect_conflict_op(&worktree_path).unwrap(), None);
+    assert!(svc.is_worktree_clean(&worktree_path).unwrap());
+    assert_eq!(before, svc.get_branch_oid(&repo_path, "feature").unwrap());
+}
+
+#[test]
+fn continue_rebase_requires_resolved_conflicts_then_completes() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let svc = GitService::new();
+    let _ = svc
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+            None,
+        )
+        .expect_err("rebase should stop on conflicts");
+
+    let res = svc.continue_operation(&worktree_path, ConflictOp::Rebase);
+    assert!(matches!(res, Err(GitServiceError::MergeConflicts(_))));
+
+    write_file(&worktree_path, "conflict.txt", "resolved\n");
+    GitCli::new()
+        .git(&worktree_path, ["add", "conflict.txt"])
+        .unwrap();
+    svc.continue_operation(&worktree_path, ConflictOp::Rebase)
+        .unwrap();
+
+    assert_eq!(svc.detect_conflict_op(&worktree_path).unwrap(), None);
+    assert!(svc.is_worktree_clean(&worktree_path).unwrap());
+    let parent = GitCli::new()
+        .git(&worktree_path, ["rev-parse", "HEAD^"])
+        .unwrap();
+    assert_eq!(
+        parent.trim(),
+        svc.get_branch_oid(&repo_path, "new-base").unwrap()
+    );
+}
+
 #[test]
 fn rebase_fast_forwards_when_no_unique_commits() {
     let td = TempDir::new().unwrap();
@@ -799,6 +1218,7 @@ fn merge_when_base_ahead_and_feature_ahead_fails() {
         "feature",
         "main",
         "squash merge",
+        None,
     );
 
     assert!(
@@ -831,6 +1251,7 @@ fn merge_conflict_does_not_move_base_ref() {
         "feature",
         "main",
         "squash merge",
+        None,
     );
 
     assert!(res.is_err(), "conflicting merge should fail");
@@ -839,6 +1260,33 @@ fn merge_conflict_does_not_move_base_ref() {
     assert_eq!(before, after, "main ref must remain unchanged on conflict");
 }
 
+#[test]
+fn merge_conflicted_files_lists_conflicts_without_moving_refs() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, _worktree_path) = setup_direct_conflict_repo(&td);
+    let g = GitService::new();
+    let before = g.get_branch_oid(&repo_path, "main").unwrap();
+
+    let conflicted = g
+        .merge_conflicted_files(&repo_path, "feature", "main")
+        .unwrap();
+
+    assert_eq!(conflicted, vec!["conflict.txt".to_string()]);
+    assert_eq!(before, g.get_branch_oid(&repo_path, "main").unwrap());
+}
+
+#[test]
+fn merge_conflicted_files_is_empty_for_clean_merge() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, _worktree_path) = setup_repo_with_worktree(&td);
+
+    let conflicted = GitService::new()
+        .merge_conflicted_files(&repo_path, "feature", "old-base")
+        .unwrap();
+
+    assert!(conflicted.is_empty());
+}
+
 #[test]
 fn merge_delete_vs_modify_conflict_behaves_safely() {
     // main modifies file, feature deletes it -> but now blocked by branch ahead check
@@ -874,6 +1322,7 @@ fn merge_delete_vs_modify_conflict_behaves_safely() {
         "feature",
         "main",
         "squash merge",
+        None,
     );
 
     // Should now fail due to base branch being ahead, not due to merge conflicts
@@ -940,7 +1389,7 @@ fn merge_refreshes_main_worktree_when_on_base() {
 
     // Merge into main (squash) and ensure main worktree is updated since it is on base
     let merge_sha = s
-        .merge_changes(&repo_path, &wt, "feature", "main", "squash")
+        .merge_changes(&repo_path, &wt, "feature", "main", "squash", None)
         .unwrap();
     // Since main is on base branch and we use safe CLI merge, both working tree
     // and ref should reflect the merged content.
@@ -1109,7 +1558,14 @@ fn merge_binary_conflict_does_not_move_ref() {
     let _ = s.commit(&repo_path, "main bin").unwrap();
 
     let before = s.get_branch_oid(&repo_path, "main").unwrap();
-    let res = s.merge_changes(&repo_path, &worktree_path, "feature", "main", "merge bin");
+    let res = s.merge_changes(
+        &repo_path,
+        &worktree_path,
+        "feature",
+        "main",
+        "merge bin",
+        None,
+    );
     assert!(res.is_err(), "binary conflict should fail");
     let after = s.get_branch_oid(&repo_path, "main").unwrap();
     assert_eq!(before, after, "main ref unchanged on conflict");
@@ -1147,6 +1603,7 @@ fn merge_rename_vs_modify_conflict_does_not_move_ref() {
         "feature",
         "main",
         "merge rename",
+        None,
     );
     match res {
         Err(_) => {
@@ -1201,6 +1658,7 @@ fn merge_leaves_no_staged_changes_on_target_branch() {
             "feature",
             "main",
             "merge feature",
+            None,
         )
         .expect("merge should succeed");
 
@@ -1272,6 +1730,7 @@ fn worktree_to_worktree_merge_leaves_no_staged_changes() {
         "feature-a",
         "feature-b",
         "merge feature-a into feature-b",
+        None,
     );
 
     // Verify no staged changes were introduced
@@ -1329,6 +1788,7 @@ fn merge_into_orphaned_branch_uses_libgit2_fallback() {
             "feature",
             "orphaned-feature",
             "merge into orphaned branch",
+            None,
         )
         .expect("libgit2 merge into orphaned branch should succeed");
 
@@ -1401,6 +1861,7 @@ fn merge_base_ahead_of_task_should_error() {
         "feature",
         "main",
         "attempt merge when base ahead",
+        None,
     );
 
     // TDD: This test will initially fail because merge currently succeeds
@@ -215,6 +215,31 @@ fn initialize_and_default_branch_and_head_info() {
     assert!(!head.oid.is_empty());
 }
 
+#[test]
+fn default_branch_follows_remote_head_then_checked_out_branch() {
+    let td = TempDir::new().unwrap();
+    let origin_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    // A local-only repo whose default branch is master
+    s.create_branch(&origin_path, "master").unwrap();
+    s.checkout_branch(&origin_path, "master").unwrap();
+    assert_eq!(s.get_default_branch_name(&origin_path).unwrap(), "master");
+
+    // A clone keeps reporting origin's default even with a feature branch checked out
+    let clone_path = td.path().join("clone");
+    git2::Repository::clone(origin_path.to_str().unwrap(), &clone_path).unwrap();
+    s.create_branch(&clone_path, "feature").unwrap();
+    s.checkout_branch(&clone_path, "feature").unwrap();
+    assert_eq!(s.get_default_branch_name(&clone_path).unwrap(), "master");
+
+    // Detached HEAD without a remote HEAD falls back to main
+    let repo = git2::Repository::open(&origin_path).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap().id();
+    repo.set_head_detached(head).unwrap();
+    assert_eq!(s.get_default_branch_name(&origin_path).unwrap(), "main");
+}
+
 #[test]
 fn commit_and_is_worktree_clean() {
     let td = TempDir::new().unwrap();
@@ -293,6 +318,50 @@ fn branch_status_ahead_and_behind() {
     assert_eq!((ahead2, behind2), (2, 1));
 }
 
+#[test]
+fn branch_commits_lists_only_commits_unique_to_the_branch() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+
+    write_file(&repo_path, "base.txt", "base\n");
+    s.commit(&repo_path, "base").unwrap();
+    s.create_branch(&repo_path, "feature").unwrap();
+
+    s.checkout_branch(&repo_path, "feature").unwrap();
+    let mut added = Vec::new();
+    for name in ["f1", "f2", "f3"] {
+        write_file(&repo_path, &format!("{name}.txt"), "f\n");
+        s.commit(&repo_path, name).unwrap();
+        added.push(s.get_head_info(&repo_path).unwrap().oid);
+    }
+    added.reverse();
+
+    // Commits landing on main after the fork don't belong to the branch
+    s.checkout_branch(&repo_path, "main").unwrap();
+    write_file(&repo_path, "main.txt", "m1\n");
+    s.commit(&repo_path, "m1").unwrap();
+
+    let (commits, total) = s
+        .branch_commits(&repo_path, "feature", "main", 0, 10)
+        .unwrap();
+    assert_eq!(total, 3);
+    let shas: Vec<&str> = commits.iter().map(|c| c.sha.as_str()).collect();
+    assert_eq!(shas, added);
+    let subjects: Vec<&str> = commits.iter().map(|c| c.subject.as_str()).collect();
+    assert_eq!(subjects, ["f3", "f2", "f1"]);
+    assert_eq!(commits[0].author_name.as_deref(), Some("Test User"));
+    assert_eq!(commits[0].author_email.as_deref(), Some("test@example.com"));
+
+    // Pages are sliced newest first
+    let (page, total) = s
+        .branch_commits(&repo_path, "feature", "main", 1, 1)
+        .unwrap();
+    assert_eq!(total, 3);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].sha, added[1]);
+}
+
 #[test]
 fn get_all_branches_lists_current_and_others() {
     let td = TempDir::new().unwrap();
@@ -630,7 +699,14 @@ fn squash_merge_libgit2_sets_author_without_user() {
 
     // Merge feature -> main (libgit2 squash)
     let merge_sha = s
-        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .merge_changes(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "squash",
+            None,
+        )
         .unwrap();
 
     // The squash commit author should not be the feature commit's author, and must be present.
@@ -644,3 +720,148 @@ fn squash_merge_libgit2_sets_author_without_user() {
         assert_eq!(email.as_deref(), Some("genie@namastex.ai"));
     }
 }
+
+#[tokio::test]
+async fn cleanup_run_removes_worktree_and_local_branch() {
+    use forge_core_services::services::{
+        container::{RunCleanupData, RunCleanupOptions, cleanup_run_direct},
+        worktree_manager::WorktreeManager,
+    };
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let worktree_path = td.path().join("wt-run");
+    let branch = "run/ab12cd34";
+
+    WorktreeManager::create_worktree(&repo_path, branch, &worktree_path, "main", true)
+        .await
+        .unwrap();
+    let s = GitService::new();
+    assert!(s.check_branch_exists(&repo_path, branch).unwrap());
+
+    let data = RunCleanupData {
+        run_id: uuid::Uuid::new_v4(),
+        worktree_path: Some(worktree_path.clone()),
+        git_repo_path: repo_path.clone(),
+        branch: branch.to_string(),
+    };
+    let options = RunCleanupOptions {
+        delete_branch: true,
+        ..Default::default()
+    };
+
+    let outcome = cleanup_run_direct(&s, &data, &options).await.unwrap();
+    assert!(outcome.worktree_removed);
+    assert!(outcome.local_branch_deleted);
+    assert!(!outcome.remote_branch_deleted);
+    assert!(outcome.branch_cleanup_complete);
+    assert!(!worktree_path.exists());
+    assert!(!s.check_branch_exists(&repo_path, branch).unwrap());
+
+    // A second cleanup finds nothing left to delete, which still counts as done
+    let data = RunCleanupData {
+        worktree_path: None,
+        ..data
+    };
+    let outcome = cleanup_run_direct(&s, &data, &options).await.unwrap();
+    assert!(!outcome.local_branch_deleted);
+    assert!(outcome.branch_cleanup_complete);
+}
+
+#[tokio::test]
+async fn cleanup_run_with_remote_deletion_checks_token_first_and_reports_failures() {
+    use forge_core_services::services::{
+        container::{ContainerError, RunCleanupData, RunCleanupOptions, cleanup_run_direct},
+        git::GitServiceError,
+        worktree_manager::WorktreeManager,
+    };
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let worktree_path = td.path().join("wt-run");
+    let branch = "run/ef56ab78";
+
+    WorktreeManager::create_worktree(&repo_path, branch, &worktree_path, "main", true)
+        .await
+        .unwrap();
+    let s = GitService::new();
+
+    let data = RunCleanupData {
+        run_id: uuid::Uuid::new_v4(),
+        worktree_path: Some(worktree_path.clone()),
+        git_repo_path: repo_path.clone(),
+        branch: branch.to_string(),
+    };
+    let mut options = RunCleanupOptions {
+        delete_branch: true,
+        delete_remote_branch: true,
+        github_token: None,
+    };
+
+    // Without a token nothing is touched
+    match cleanup_run_direct(&s, &data, &options).await {
+        Err(ContainerError::GitServiceError(GitServiceError::TokenUnavailable)) => {}
+        other => panic!("expected TokenUnavailable, got {other:?}"),
+    }
+    assert!(worktree_path.exists());
+    assert!(s.check_branch_exists(&repo_path, branch).unwrap());
+
+    // The repo has no remote, so the remote deletion fails and the cleanup is not complete
+    options.github_token = Some("dummy-token".to_string());
+    let outcome = cleanup_run_direct(&s, &data, &options).await.unwrap();
+    assert!(outcome.worktree_removed);
+    assert!(outcome.local_branch_deleted);
+    assert!(!outcome.remote_branch_deleted);
+    assert!(!outcome.branch_cleanup_complete);
+    assert!(!s.check_branch_exists(&repo_path, branch).unwrap());
+}
+
+#[tokio::test]
+async fn forked_worktree_starts_at_source_tip_or_chosen_commit() {
+    use forge_core_services::services::{git::GitServiceError, worktree_manager::WorktreeManager};
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "base.txt", "base\n");
+    s.commit(&repo_path, "base").unwrap();
+
+    // The source attempt's branch is two commits ahead of main
+    let source_wt = td.path().join("wt-source");
+    WorktreeManager::create_worktree(&repo_path, "source", &source_wt, "main", true)
+        .await
+        .unwrap();
+    write_file(&source_wt, "a.txt", "a\n");
+    s.commit(&source_wt, "a").unwrap();
+    let first = s.get_branch_oid(&repo_path, "source").unwrap();
+    write_file(&source_wt, "b.txt", "b\n");
+    s.commit(&source_wt, "b").unwrap();
+    let tip = s.get_branch_oid(&repo_path, "source").unwrap();
+
+    let fork_point = s.fork_point(&repo_path, "source", None).unwrap();
+    assert_eq!(fork_point, tip);
+    s.create_branch_at(&repo_path, "fork", &fork_point).unwrap();
+    let fork_wt = td.path().join("wt-fork");
+    WorktreeManager::create_worktree(&repo_path, "fork", &fork_wt, "main", false)
+        .await
+        .unwrap();
+    assert_eq!(s.get_head_info(&fork_wt).unwrap().oid, tip);
+    assert!(fork_wt.join("b.txt").exists());
+
+    let fork_point = s
+        .fork_point(&repo_path, "source", Some(&first[..8]))
+        .unwrap();
+    assert_eq!(fork_point, first);
+    s.create_branch_at(&repo_path, "fork-early", &fork_point)
+        .unwrap();
+    assert_eq!(s.get_branch_oid(&repo_path, "fork-early").unwrap(), first);
+
+    // Commits that are not on the source branch are refused
+    write_file(&repo_path, "main.txt", "m\n");
+    s.commit(&repo_path, "main only").unwrap();
+    let main_only = s.get_branch_oid(&repo_path, "main").unwrap();
+    assert!(matches!(
+        s.fork_point(&repo_path, "source", Some(&main_only)),
+        Err(GitServiceError::InvalidRepository(_))
+    ));
+}
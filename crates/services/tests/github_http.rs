@@ -0,0 +1,210 @@
+//! Integration tests for the GitHub retry helper
+//!
+//! Run with: cargo test --package services --test github_http
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use forge_core_services::services::github_http::{
+    GitHubHttpClient, GitHubHttpError, GitHubRateLimiter, GitHubRetryPolicy, rate_limit_wait,
+};
+use reqwest::{
+    StatusCode,
+    header::{HeaderMap, HeaderValue},
+};
+use serde::Deserialize;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+}
+
+fn fast_policy() -> GitHubRetryPolicy {
+    GitHubRetryPolicy {
+        max_retries: 3,
+        min_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(50),
+        max_rate_limit_wait: Duration::from_secs(5),
+        rate_limiter: Arc::new(GitHubRateLimiter::new(0.0)),
+    }
+}
+
+fn epoch_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[tokio::test]
+async fn bad_gateway_is_retried_until_success() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/releases"))
+        .respond_with(ResponseTemplate::new(502))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/releases"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([{ "tag_name": "v1.2.0" }])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let releases: Vec<Release> = GitHubHttpClient::with_policy(fast_policy())
+        .get_json(&format!("{}/releases", server.uri()))
+        .await
+        .unwrap();
+
+    assert_eq!(releases.len(), 1);
+    assert_eq!(releases[0].tag_name, "v1.2.0");
+}
+
+#[tokio::test]
+async fn rate_limit_reset_is_waited_out() {
+    let server = MockServer::start().await;
+    let reset = epoch_secs() + 2;
+    Mock::given(method("GET"))
+        .and(path("/releases"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", reset.to_string().as_str()),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/releases"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let started = Instant::now();
+    let releases: Vec<Release> = GitHubHttpClient::with_policy(fast_policy())
+        .get_json(&format!("{}/releases", server.uri()))
+        .await
+        .unwrap();
+
+    assert!(releases.is_empty());
+    assert!(
+        started.elapsed() >= Duration::from_secs(1),
+        "retried before the rate limit reset"
+    );
+}
+
+#[tokio::test]
+async fn long_rate_limits_surface_as_distinct_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/releases"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "3600"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = GitHubHttpClient::with_policy(fast_policy())
+        .get_json::<Vec<Release>>(&format!("{}/releases", server.uri()))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        GitHubHttpError::RateLimited {
+            retry_after: Some(wait)
+        } if wait == Duration::from_secs(3600)
+    ));
+}
+
+#[test]
+fn backoff_is_jittered_within_the_upper_half() {
+    let policy = GitHubRetryPolicy {
+        min_delay: Duration::from_secs(1),
+        max_delay: Duration::from_secs(30),
+        ..fast_policy()
+    };
+    for (attempt, full) in [(0, 1), (2, 4), (10, 30)] {
+        let full = Duration::from_secs(full);
+        let delays: Vec<Duration> = (0..50).map(|_| policy.backoff(attempt)).collect();
+        assert!(
+            delays.iter().all(|d| *d >= full / 2 && *d <= full),
+            "{delays:?}"
+        );
+        // Clients failing at the same moment don't all come back at the same moment
+        assert!(delays.iter().any(|d| *d != delays[0]), "{delays:?}");
+    }
+}
+
+#[test]
+fn forbidden_without_rate_limit_headers_is_not_a_rate_limit() {
+    let now = SystemTime::now();
+    assert_eq!(
+        rate_limit_wait(StatusCode::FORBIDDEN, &HeaderMap::new(), now),
+        None
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert("retry-after", HeaderValue::from_static("30"));
+    assert_eq!(
+        rate_limit_wait(StatusCode::FORBIDDEN, &headers, now),
+        Some(Some(Duration::from_secs(30)))
+    );
+}
+
+#[tokio::test]
+async fn bursts_are_paced_to_the_configured_rate() {
+    let limiter = GitHubRateLimiter::new(20.0);
+
+    // A second's worth of requests goes out at once
+    let started = Instant::now();
+    for _ in 0..20 {
+        limiter.acquire().await;
+    }
+    assert!(started.elapsed() < Duration::from_millis(200));
+
+    // The next ten have to wait for the bucket to refill at 20 per second
+    for _ in 0..10 {
+        limiter.acquire().await;
+    }
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(450), "{elapsed:?}");
+    assert!(elapsed < Duration::from_secs(2), "{elapsed:?}");
+}
+
+#[tokio::test]
+async fn client_requests_share_the_rate_limiter() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/releases"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([{ "tag_name": "v1.2.0" }])),
+        )
+        .expect(6)
+        .mount(&server)
+        .await;
+
+    let client = GitHubHttpClient::with_policy(GitHubRetryPolicy {
+        rate_limiter: Arc::new(GitHubRateLimiter::new(4.0)),
+        ..fast_policy()
+    });
+    let url = format!("{}/releases", server.uri());
+
+    let started = Instant::now();
+    for _ in 0..6 {
+        let _: Vec<Release> = client.get_json(&url).await.unwrap();
+    }
+    // Four go out straight away, the other two wait a quarter second each
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(450), "{elapsed:?}");
+}
@@ -0,0 +1,52 @@
+//! Integration tests for the shutdown cleanup routine
+//!
+//! Run with: cargo test --package services --test graceful_shutdown
+
+use std::time::Duration;
+
+use forge_core_services::services::shutdown::ShutdownCoordinator;
+
+#[tokio::test]
+async fn shutdown_aborts_background_tasks_and_cancels_streams() {
+    let coordinator = ShutdownCoordinator::new();
+
+    let refresher = tokio::spawn(async {
+        loop {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        }
+    });
+    coordinator.register_task("agent_cache_refresh", &refresher);
+
+    // A long-lived stream that only ends on shutdown
+    let token = coordinator.cancellation_token();
+    let stream = tokio::spawn(async move {
+        token
+            .run_until_cancelled(std::future::pending::<()>())
+            .await
+    });
+
+    let report = coordinator.finish();
+
+    assert!(coordinator.is_shutting_down());
+    assert_eq!(report.aborted_tasks, 1);
+    assert!(refresher.await.unwrap_err().is_cancelled());
+    let ended = tokio::time::timeout(Duration::from_secs(5), stream)
+        .await
+        .expect("stream did not end on shutdown")
+        .unwrap();
+    assert_eq!(ended, None);
+}
+
+#[tokio::test]
+async fn tasks_registered_after_shutdown_are_aborted_immediately() {
+    let coordinator = ShutdownCoordinator::new();
+    assert!(coordinator.begin());
+    assert!(!coordinator.begin());
+    assert!(coordinator.cancellation_token().is_cancelled());
+
+    let late = tokio::spawn(std::future::pending::<()>());
+    coordinator.register_task("late", &late);
+
+    assert!(late.await.unwrap_err().is_cancelled());
+    assert_eq!(coordinator.abort_background_tasks(), 0);
+}
@@ -0,0 +1,117 @@
+//! Integration tests for resolving tasks by a full UUID or a UUID prefix
+//!
+//! Run with: cargo test --package services --test id_prefix_resolution
+
+use forge_core_db::{
+    DBService,
+    models::{
+        id_prefix::IdResolution,
+        project::{CreateProject, Project},
+        task::{CreateTask, Task},
+    },
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+async fn create_test_project(pool: &sqlx::SqlitePool) -> Uuid {
+    let project_id = Uuid::new_v4();
+    let create_data = CreateProject {
+        name: "Test Project".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+
+    Project::create(pool, &create_data, project_id)
+        .await
+        .unwrap();
+
+    project_id
+}
+
+async fn create_task(pool: &sqlx::SqlitePool, project_id: Uuid, task_id: Uuid) {
+    let data = CreateTask::from_title_description(project_id, format!("Task {task_id}"), None);
+    Task::create(pool, &data, task_id).await.unwrap();
+}
+
+/// Returns a copy of `id` that differs only in its last byte
+fn sibling_id(id: Uuid) -> Uuid {
+    let mut bytes = *id.as_bytes();
+    bytes[15] ^= 0xFF;
+    Uuid::from_bytes(bytes)
+}
+
+#[tokio::test]
+async fn unique_prefix_resolves_task() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let project_id = create_test_project(&db.pool).await;
+    let task_id = Uuid::new_v4();
+    create_task(&db.pool, project_id, task_id).await;
+
+    let short = &task_id.to_string()[..8];
+    assert_eq!(
+        Task::resolve_id(&db.pool, short).await.unwrap(),
+        IdResolution::Found(task_id)
+    );
+    assert_eq!(
+        Task::resolve_id(&db.pool, &short.to_uppercase()).await.unwrap(),
+        IdResolution::Found(task_id)
+    );
+    assert_eq!(
+        Task::resolve_id(&db.pool, &task_id.to_string()).await.unwrap(),
+        IdResolution::Found(task_id)
+    );
+}
+
+#[tokio::test]
+async fn ambiguous_prefix_lists_candidates() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let project_id = create_test_project(&db.pool).await;
+    let first = Uuid::new_v4();
+    let second = sibling_id(first);
+    create_task(&db.pool, project_id, first).await;
+    create_task(&db.pool, project_id, second).await;
+
+    let resolution = Task::resolve_id(&db.pool, &first.to_string()[..8])
+        .await
+        .unwrap();
+    let IdResolution::Ambiguous(candidates) = &resolution else {
+        panic!("expected an ambiguous resolution, got {resolution:?}");
+    };
+    let mut candidates = candidates.clone();
+    candidates.sort();
+    let mut expected = vec![first, second];
+    expected.sort();
+    assert_eq!(candidates, expected);
+
+    // The full id still resolves to exactly one task
+    assert_eq!(
+        Task::resolve_id(&db.pool, &second.to_string()).await.unwrap(),
+        IdResolution::Found(second)
+    );
+}
+
+#[tokio::test]
+async fn short_or_malformed_references_are_invalid() {
+    let (db, _temp_dir) = setup_test_db().await;
+
+    for reference in ["abc", "not-a-uuid", ""] {
+        assert_eq!(
+            Task::resolve_id(&db.pool, reference).await.unwrap(),
+            IdResolution::Invalid
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Integration tests for execution process log truncation and compaction
+//!
+//! Run with: cargo test --package services --test log_retention
+
+use forge_core_db::{
+    DBService,
+    models::{
+        execution_process::{
+            CreateExecutionProcess, ExecutionProcess, ExecutionProcessRunReason,
+            ExecutionProcessStatus,
+        },
+        execution_process_logs::{CreateExecutionProcessLogs, ExecutionProcessLogs},
+        project::{CreateProject, Project},
+        task::{CreateTask, Task},
+        task_attempt::{CreateTaskAttempt, TaskAttempt},
+    },
+};
+use forge_core_executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+    },
+    executors::BaseCodingAgent,
+    profile::ExecutorProfileId,
+};
+use forge_core_services::services::log_retention::{
+    LogRetentionPolicy, compact_completed_process_logs,
+};
+use forge_core_utils::log_msg::LogMsg;
+use serde_json::json;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+const FINAL_MESSAGE: &str = "All tests pass; the greeting is now localized.";
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+/// JSONL logs of a long agent conversation ending with the final assistant message
+fn conversation_logs(turns: usize) -> String {
+    let mut messages: Vec<LogMsg> = (0..turns)
+        .map(|i| {
+            let text = format!("Step {i}: {}", "x".repeat(200));
+            LogMsg::Stdout(
+                json!({
+                    "type": "assistant",
+                    "message": { "content": [{ "type": "text", "text": text }] }
+                })
+                .to_string(),
+            )
+        })
+        .collect();
+    messages.push(LogMsg::Stdout(
+        json!({
+            "type": "assistant",
+            "message": { "content": [{ "type": "text", "text": FINAL_MESSAGE }] }
+        })
+        .to_string(),
+    ));
+    ExecutionProcessLogs::serialize_logs(&messages).unwrap()
+}
+
+fn record(logs: String) -> ExecutionProcessLogs {
+    ExecutionProcessLogs {
+        execution_id: Uuid::new_v4(),
+        byte_size: logs.len() as i64,
+        logs,
+        inserted_at: chrono::Utc::now(),
+    }
+}
+
+#[test]
+fn oversized_log_keeps_head_tail_and_last_assistant_message() {
+    let logs = conversation_logs(2_000);
+    let max_bytes = 32 * 1024;
+
+    let truncated = ExecutionProcessLogs::truncate_jsonl(&logs, max_bytes).unwrap();
+    assert!(truncated.len() <= max_bytes);
+
+    let messages = record(truncated.clone()).parse_logs().unwrap();
+    let markers: Vec<_> = messages
+        .iter()
+        .filter(|msg| ExecutionProcessLogs::is_elision_marker(msg))
+        .collect();
+    assert_eq!(markers.len(), 1);
+    assert!(matches!(&messages[0], LogMsg::Stdout(line) if line.contains("Step 0:")));
+    assert!(matches!(
+        messages.last(),
+        Some(LogMsg::Stdout(line)) if line.contains(FINAL_MESSAGE)
+    ));
+
+    // Truncating again keeps the original head and a single marker
+    let retruncated = ExecutionProcessLogs::truncate_jsonl(
+        &format!("{truncated}{}", conversation_logs(200)),
+        max_bytes,
+    )
+    .unwrap();
+    let messages = record(retruncated).parse_logs().unwrap();
+    assert!(matches!(&messages[0], LogMsg::Stdout(line) if line.contains("Step 0:")));
+    assert_eq!(
+        messages
+            .iter()
+            .filter(|msg| ExecutionProcessLogs::is_elision_marker(msg))
+            .count(),
+        1
+    );
+
+    assert!(ExecutionProcessLogs::truncate_jsonl(&conversation_logs(2), max_bytes).is_none());
+}
+
+#[tokio::test]
+async fn logs_of_old_finished_processes_are_compacted() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+
+    let project_id = Uuid::new_v4();
+    let create_project = CreateProject {
+        name: "Logs".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+    Project::create(pool, &create_project, project_id)
+        .await
+        .unwrap();
+    let create_task =
+        CreateTask::from_title_description(project_id, "Localize greeting".to_string(), None);
+    let task = Task::create(pool, &create_task, Uuid::new_v4())
+        .await
+        .unwrap();
+    let create_attempt = CreateTaskAttempt {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+        base_branch: "main".to_string(),
+        branch: "forge/localize-greeting".to_string(),
+    };
+    let attempt = TaskAttempt::create(pool, &create_attempt, Uuid::new_v4(), task.id)
+        .await
+        .unwrap();
+
+    let action = ExecutorAction::new(
+        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt: "Localize the greeting".to_string(),
+            executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+        }),
+        None,
+    );
+    let process = ExecutionProcess::create(
+        pool,
+        &CreateExecutionProcess {
+            task_attempt_id: Some(attempt.id),
+            execution_run_id: None,
+            executor_action: action,
+            run_reason: ExecutionProcessRunReason::CodingAgent,
+        },
+        Uuid::new_v4(),
+        None,
+    )
+    .await
+    .unwrap();
+    ExecutionProcess::update_completion(
+        pool,
+        process.id,
+        ExecutionProcessStatus::Completed,
+        Some(0),
+    )
+    .await
+    .unwrap();
+    sqlx::query(
+        "UPDATE execution_processes SET completed_at = datetime('now', '-60 days') WHERE id = ?",
+    )
+    .bind(process.id)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    let logs = conversation_logs(500);
+    ExecutionProcessLogs::upsert(
+        pool,
+        &CreateExecutionProcessLogs {
+            execution_id: process.id,
+            byte_size: logs.len() as i64,
+            logs,
+        },
+    )
+    .await
+    .unwrap();
+
+    let policy = LogRetentionPolicy {
+        max_bytes: 1024 * 1024,
+        compacted_bytes: 8 * 1024,
+        compact_after_days: 30,
+    };
+    assert_eq!(compact_completed_process_logs(pool, &policy).await.unwrap(), 1);
+    assert_eq!(compact_completed_process_logs(pool, &policy).await.unwrap(), 0);
+
+    let stored = ExecutionProcessLogs::find_by_execution_id(pool, process.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(stored.byte_size as usize <= policy.compacted_bytes);
+    assert_eq!(stored.byte_size as usize, stored.logs.len());
+    assert!(stored.logs.contains(FINAL_MESSAGE));
+}
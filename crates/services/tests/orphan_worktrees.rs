@@ -0,0 +1,127 @@
+//! Integration tests for orphaned worktree detection
+//!
+//! Run with: cargo test --package services --test orphan_worktrees
+
+use std::{fs, path::Path, time::Duration};
+
+use forge_core_db::{
+    DBService,
+    models::{
+        project::{CreateProject, Project},
+        task::{CreateTask, Task},
+        task_attempt::{CreateTaskAttempt, TaskAttempt},
+    },
+};
+use forge_core_executors::executors::BaseCodingAgent;
+use forge_core_services::services::orphan_worktrees::{
+    OrphanScanOptions, scan_orphaned_worktrees,
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+/// Creates an attempt whose worktree is `worktree`
+async fn create_attempt_with_worktree(pool: &sqlx::SqlitePool, worktree: &Path) {
+    let project_id = Uuid::new_v4();
+    let create_project = CreateProject {
+        name: "Worktrees".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+    Project::create(pool, &create_project, project_id)
+        .await
+        .unwrap();
+
+    let create_task =
+        CreateTask::from_title_description(project_id, "Live attempt".to_string(), None);
+    let task = Task::create(pool, &create_task, Uuid::new_v4())
+        .await
+        .unwrap();
+
+    let create_attempt = CreateTaskAttempt {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+        base_branch: "main".to_string(),
+        branch: "forge/live-attempt".to_string(),
+    };
+    let attempt = TaskAttempt::create(pool, &create_attempt, Uuid::new_v4(), task.id)
+        .await
+        .unwrap();
+    TaskAttempt::update_container_ref(pool, attempt.id, &worktree.to_string_lossy())
+        .await
+        .unwrap();
+}
+
+fn no_age_threshold(dry_run: bool) -> OrphanScanOptions {
+    OrphanScanOptions {
+        min_age: Duration::ZERO,
+        dry_run,
+    }
+}
+
+#[tokio::test]
+async fn detects_and_removes_unreferenced_worktrees() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+
+    let base_dir = TempDir::new().unwrap();
+    let referenced = base_dir.path().join("live-attempt");
+    let orphan = base_dir.path().join("crashed-attempt");
+    fs::create_dir_all(&referenced).unwrap();
+    fs::create_dir_all(orphan.join("src")).unwrap();
+    fs::write(base_dir.path().join("stray-file"), "not a worktree").unwrap();
+    create_attempt_with_worktree(pool, &referenced).await;
+
+    let report = scan_orphaned_worktrees(pool, base_dir.path(), no_age_threshold(true))
+        .await
+        .unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.scanned, 2);
+    assert_eq!(report.orphans.len(), 1);
+    assert_eq!(report.orphans[0].path, orphan.to_string_lossy());
+    assert!(!report.orphans[0].removed);
+    assert!(orphan.exists(), "dry run must not remove anything");
+
+    let report = scan_orphaned_worktrees(pool, base_dir.path(), no_age_threshold(false))
+        .await
+        .unwrap();
+    assert_eq!(report.orphans.len(), 1);
+    assert!(report.orphans[0].removed);
+    assert!(!orphan.exists());
+    assert!(referenced.exists());
+}
+
+#[tokio::test]
+async fn recent_orphans_are_left_alone() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+
+    let base_dir = TempDir::new().unwrap();
+    let orphan = base_dir.path().join("just-created");
+    fs::create_dir_all(&orphan).unwrap();
+
+    let options = OrphanScanOptions {
+        min_age: Duration::from_secs(3600),
+        dry_run: false,
+    };
+    let report = scan_orphaned_worktrees(pool, base_dir.path(), options)
+        .await
+        .unwrap();
+
+    assert!(report.orphans.is_empty());
+    assert_eq!(report.too_recent, 1);
+    assert!(orphan.exists());
+}
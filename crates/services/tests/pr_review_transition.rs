@@ -0,0 +1,118 @@
+//! Integration tests for moving tasks to in-review when a PR is opened
+//!
+//! Run with: cargo test --package services --test pr_review_transition
+
+use forge_core_db::{
+    DBService,
+    models::{
+        project::{CreateProject, Project},
+        task::{CreateTask, Task, TaskStatus},
+    },
+};
+use forge_core_services::services::{
+    forge_config::{ForgeConfigService, ForgeProjectSettings, SecretCipher},
+    pr_monitor::review_task_for_new_pr,
+};
+use sqlx::SqlitePool;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+async fn create_project(pool: &SqlitePool) -> Uuid {
+    let project_id = Uuid::new_v4();
+    let create_project = CreateProject {
+        name: "Reviews".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+    Project::create(pool, &create_project, project_id)
+        .await
+        .unwrap();
+    project_id
+}
+
+async fn create_task(pool: &SqlitePool, project_id: Uuid, status: TaskStatus) -> Task {
+    let create_task =
+        CreateTask::from_title_description(project_id, "Add greeting".to_string(), None);
+    Task::create_with_status(pool, &create_task, Uuid::new_v4(), status)
+        .await
+        .unwrap()
+}
+
+async fn status_of(pool: &SqlitePool, task: &Task) -> TaskStatus {
+    Task::find_by_id(pool, task.id)
+        .await
+        .unwrap()
+        .unwrap()
+        .status
+}
+
+#[tokio::test]
+async fn opening_a_pr_moves_an_in_progress_task_to_in_review() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+    let forge_config = ForgeConfigService::new(
+        pool.clone(),
+        SecretCipher::from_key_material(b"pr-review-test-key"),
+    );
+    let project_id = create_project(pool).await;
+
+    let in_progress = create_task(pool, project_id, TaskStatus::InProgress).await;
+    assert!(
+        review_task_for_new_pr(pool, &forge_config, &in_progress)
+            .await
+            .unwrap()
+    );
+    assert_eq!(status_of(pool, &in_progress).await, TaskStatus::InReview);
+
+    // Only in-progress tasks move
+    let done = create_task(pool, project_id, TaskStatus::Done).await;
+    assert!(
+        !review_task_for_new_pr(pool, &forge_config, &done)
+            .await
+            .unwrap()
+    );
+    assert_eq!(status_of(pool, &done).await, TaskStatus::Done);
+}
+
+#[tokio::test]
+async fn projects_can_turn_the_review_transition_off() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+    let forge_config = ForgeConfigService::new(
+        pool.clone(),
+        SecretCipher::from_key_material(b"pr-review-test-key"),
+    );
+    let project_id = create_project(pool).await;
+    forge_config
+        .set_forge_settings(
+            project_id,
+            &ForgeProjectSettings {
+                review_on_pr: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let task = create_task(pool, project_id, TaskStatus::InProgress).await;
+    assert!(
+        !review_task_for_new_pr(pool, &forge_config, &task)
+            .await
+            .unwrap()
+    );
+    assert_eq!(status_of(pool, &task).await, TaskStatus::InProgress);
+}
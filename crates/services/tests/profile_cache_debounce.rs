@@ -0,0 +1,96 @@
+use std::{
+    fs,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use forge_core_services::services::profile_loader::{ProfileCache, ProfileCacheConfig, ReloadMode};
+use tempfile::TempDir;
+
+fn write_agent(workspace: &Path, name: &str) {
+    let agents_dir = workspace.join(".genie").join("agents");
+    fs::create_dir_all(&agents_dir).unwrap();
+    fs::write(
+        agents_dir.join(format!("{name}.md")),
+        format!("---\nname: {name}\ngenie:\n  executor: CLAUDE_CODE\n---\nDo the work.\n"),
+    )
+    .unwrap();
+}
+
+async fn watched_cache(workspace: &Path, config: ProfileCacheConfig) -> Arc<ProfileCache> {
+    // Watch .genie itself so the first event is already an agent file change
+    fs::create_dir_all(workspace.join(".genie").join("agents")).unwrap();
+    let cache = Arc::new(ProfileCache::new(workspace.to_path_buf()).with_config(config));
+    cache.initialize().await.unwrap();
+    assert_eq!(cache.clone().start_watching(), ReloadMode::Watching);
+    cache
+}
+
+async fn wait_for_reloads(cache: &ProfileCache, count: u64, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if cache.reload_count() >= count {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    false
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn short_debounce_reloads_quickly() {
+    let root = TempDir::new().unwrap();
+    let cache = watched_cache(
+        root.path(),
+        ProfileCacheConfig {
+            debounce: Duration::from_millis(20),
+            poll_interval: Duration::from_millis(5),
+        },
+    )
+    .await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let started = Instant::now();
+    write_agent(root.path(), "quick");
+
+    assert!(wait_for_reloads(&cache, 1, Duration::from_secs(5)).await);
+    assert!(
+        started.elapsed() < ProfileCacheConfig::default().debounce,
+        "reload took {:?}, longer than the default debounce",
+        started.elapsed()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn long_debounce_coalesces_rapid_edits() {
+    let root = TempDir::new().unwrap();
+    let debounce = Duration::from_millis(400);
+    let cache = watched_cache(
+        root.path(),
+        ProfileCacheConfig {
+            debounce,
+            poll_interval: Duration::from_millis(10),
+        },
+    )
+    .await;
+
+    for name in ["one", "two", "three", "four", "five"] {
+        write_agent(root.path(), name);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+    }
+
+    assert!(wait_for_reloads(&cache, 1, Duration::from_secs(5)).await);
+    tokio::time::sleep(debounce * 2).await;
+    assert_eq!(cache.reload_count(), 1, "rapid edits should reload once");
+
+    let variants: usize = cache
+        .get()
+        .await
+        .executors
+        .values()
+        .flat_map(|config| config.configurations.keys())
+        .filter(|variant| variant.ends_with("_ONE") || variant.ends_with("_FIVE"))
+        .count();
+    assert_eq!(variants, 2);
+}
@@ -0,0 +1,39 @@
+use std::{fs, time::Duration};
+
+use forge_core_services::services::profile_loader::{ProfileCacheConfig, ProfileCacheManager};
+use tempfile::TempDir;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn touching_an_agent_file_publishes_a_reload_event() {
+    let root = TempDir::new().unwrap();
+    let workspace = root.path().to_path_buf();
+    let agents_dir = workspace.join(".genie").join("agents");
+    fs::create_dir_all(&agents_dir).unwrap();
+
+    let manager = ProfileCacheManager::new().with_config(ProfileCacheConfig {
+        debounce: Duration::from_millis(20),
+        poll_interval: Duration::from_millis(5),
+    });
+    let mut events = manager.subscribe();
+    let cache = manager.get_or_create(workspace.clone()).await.unwrap();
+    let initial_variants: usize = cache
+        .get()
+        .await
+        .executors
+        .values()
+        .map(|config| config.configurations.len())
+        .sum();
+
+    fs::write(
+        agents_dir.join("tester.md"),
+        "---\nname: tester\ngenie:\n  executor: CLAUDE_CODE\n---\nWrite the tests.\n",
+    )
+    .unwrap();
+
+    let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+        .await
+        .expect("no reload event within 5s")
+        .unwrap();
+    assert_eq!(event.workspace_root, workspace);
+    assert_eq!(event.variant_count, initial_variants + 1);
+}
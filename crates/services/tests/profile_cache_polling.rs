@@ -0,0 +1,72 @@
+use std::{fs, path::Path, sync::Arc, time::Duration};
+
+use forge_core_executors::{executors::BaseCodingAgent, profile::ExecutorConfigs};
+use forge_core_services::services::profile_loader::{
+    ProfileCache, ProfileCacheManager, ReloadMode,
+};
+use tempfile::TempDir;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+fn has_reviewer_profile(profiles: &ExecutorConfigs) -> bool {
+    profiles
+        .executors
+        .get(&BaseCodingAgent::ClaudeCode)
+        .is_some_and(|config| {
+            config
+                .configurations
+                .keys()
+                .any(|variant| variant.contains("REVIEWER"))
+        })
+}
+
+fn write_reviewer_agent(workspace: &Path) {
+    let agents_dir = workspace.join(".genie").join("agents");
+    fs::create_dir_all(&agents_dir).unwrap();
+    fs::write(
+        agents_dir.join("reviewer.md"),
+        "---\nname: reviewer\ngenie:\n  executor: CLAUDE_CODE\n---\nReview the change.\n",
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn unwatchable_workspace_falls_back_to_polling() {
+    let root = TempDir::new().unwrap();
+    // Watching a path that does not exist yet fails, just like an unwatchable mount
+    let workspace = root.path().join("workspace");
+
+    let cache = Arc::new(ProfileCache::new(workspace.clone()).with_poll_interval(POLL_INTERVAL));
+    cache.initialize().await.unwrap();
+    assert!(!has_reviewer_profile(&cache.get().await));
+
+    let mode = cache.clone().start_watching();
+    assert_eq!(mode, ReloadMode::Polling(POLL_INTERVAL));
+
+    write_reviewer_agent(&workspace);
+
+    let mut reloaded = false;
+    for _ in 0..50 {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        if has_reviewer_profile(&cache.get().await) {
+            reloaded = true;
+            break;
+        }
+    }
+    assert!(reloaded, "polling never picked up the new .genie profile");
+}
+
+#[tokio::test]
+async fn manager_serves_profiles_when_watching_fails() {
+    let root = TempDir::new().unwrap();
+    let workspace = root.path().join("missing-workspace");
+
+    let manager = ProfileCacheManager::new();
+    let profiles = manager
+        .get_profiles(&workspace)
+        .await
+        .expect("watch failure must not fail profile loading");
+
+    assert!(!has_reviewer_profile(&profiles));
+    assert!(!profiles.executors.is_empty());
+}
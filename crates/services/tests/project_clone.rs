@@ -0,0 +1,68 @@
+//! Tests for the git helpers behind creating a project from a cloned repository
+//!
+//! Run with: cargo test --package services --test project_clone
+
+use forge_core_services::services::git::GitService;
+
+#[test]
+fn repo_name_is_derived_from_clone_url() {
+    let cases = [
+        ("https://github.com/acme/widgets.git", Some("widgets")),
+        ("https://github.com/acme/widgets/", Some("widgets")),
+        ("git@github.com:acme/widgets.git", Some("widgets")),
+        ("widgets.git", Some("widgets")),
+        ("", None),
+    ];
+    for (url, expected) in cases {
+        assert_eq!(
+            GitService::repo_name_from_clone_url(url).as_deref(),
+            expected,
+            "{url}"
+        );
+    }
+}
+
+#[test]
+fn github_token_is_only_offered_to_github_over_https() {
+    assert!(GitService::is_github_https_url(
+        "https://github.com/acme/widgets.git"
+    ));
+    assert!(GitService::is_github_https_url(
+        "HTTPS://GitHub.com/acme/widgets"
+    ));
+    for url in [
+        "https://gitlab.com/acme/widgets.git",
+        "https://github.com.evil.example/acme/widgets.git",
+        "http://github.com/acme/widgets.git",
+        "git@github.com:acme/widgets.git",
+        "/srv/git/widgets.git",
+    ] {
+        assert!(!GitService::is_github_https_url(url), "{url}");
+    }
+}
+
+#[cfg(feature = "cloud")]
+#[test]
+fn clone_failures_tell_auth_errors_apart() {
+    use forge_core_services::services::git::GitServiceError;
+    use tempfile::TempDir;
+
+    let workspace = TempDir::new().unwrap();
+
+    let missing = workspace.path().join("missing.git");
+    let error = GitService::clone_repository(
+        &missing.to_string_lossy(),
+        &workspace.path().join("checkout"),
+        None,
+    )
+    .unwrap_err();
+    assert!(!error.is_auth_failure(), "{error}");
+
+    let rejected = GitServiceError::Git(git2::Error::new(
+        git2::ErrorCode::Auth,
+        git2::ErrorClass::Http,
+        "authentication required but no callback set",
+    ));
+    assert!(rejected.is_auth_failure());
+    assert!(GitServiceError::TokenUnavailable.is_auth_failure());
+}
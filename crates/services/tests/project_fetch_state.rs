@@ -0,0 +1,73 @@
+//! Integration tests for the persisted last-fetch time of projects
+//!
+//! Run with: cargo test --package services --test project_fetch_state
+
+use chrono::{Duration, Utc};
+use forge_core_db::{
+    DBService,
+    models::{
+        project::{CreateProject, Project},
+        project_fetch_state::ProjectFetchState,
+    },
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+#[tokio::test]
+async fn last_fetch_time_is_kept_per_project() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+
+    let project_id = Uuid::new_v4();
+    let create_project = CreateProject {
+        name: "Fetched".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+    Project::create(pool, &create_project, project_id)
+        .await
+        .unwrap();
+
+    // Never fetched
+    assert_eq!(
+        ProjectFetchState::last_fetch_at(pool, project_id)
+            .await
+            .unwrap(),
+        None
+    );
+
+    let earlier = Utc::now() - Duration::hours(2);
+    ProjectFetchState::record_fetch(pool, project_id, earlier)
+        .await
+        .unwrap();
+    ProjectFetchState::record_fetch(pool, project_id, Utc::now())
+        .await
+        .unwrap();
+
+    // The latest fetch replaces the earlier one
+    let last_fetch_at = ProjectFetchState::last_fetch_at(pool, project_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(Utc::now() - last_fetch_at < Duration::minutes(1));
+    assert_eq!(
+        ProjectFetchState::last_fetch_at(pool, Uuid::new_v4())
+            .await
+            .unwrap(),
+        None
+    );
+}
@@ -0,0 +1,124 @@
+//! Integration tests for per-project task counts
+//!
+//! Run with: cargo test --package services --test project_task_counts
+
+use forge_core_db::{
+    DBService,
+    models::{
+        execution_process::{CreateExecutionProcess, ExecutionProcess, ExecutionProcessRunReason},
+        forge_agent::{AGENT_CHAT_TYPE, ForgeAgent},
+        project::{CreateProject, Project},
+        task::{CreateTask, Task, TaskStatus},
+        task_attempt::{CreateTaskAttempt, TaskAttempt},
+    },
+};
+use forge_core_executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+    },
+    executors::BaseCodingAgent,
+    profile::ExecutorProfileId,
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+async fn create_project(pool: &sqlx::SqlitePool, name: &str) -> Uuid {
+    let project_id = Uuid::new_v4();
+    let create_project = CreateProject {
+        name: name.to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+    Project::create(pool, &create_project, project_id)
+        .await
+        .unwrap();
+    project_id
+}
+
+async fn create_task(pool: &sqlx::SqlitePool, project_id: Uuid, status: TaskStatus) -> Task {
+    let create_task = CreateTask::from_title_description(project_id, format!("{status}"), None);
+    let task = Task::create(pool, &create_task, Uuid::new_v4())
+        .await
+        .unwrap();
+    Task::update_status(pool, task.id, status).await.unwrap();
+    task
+}
+
+#[tokio::test]
+async fn counts_active_and_total_tasks_per_project() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+
+    let busy = create_project(pool, "Busy").await;
+    let quiet = create_project(pool, "Quiet").await;
+    let empty = create_project(pool, "Empty").await;
+
+    // Busy: one in-progress task, one todo task with a running attempt, one done task,
+    // plus an agent chat that must not be counted
+    create_task(pool, busy, TaskStatus::InProgress).await;
+    let running = create_task(pool, busy, TaskStatus::Todo).await;
+    create_task(pool, busy, TaskStatus::Done).await;
+    let agent = create_task(pool, busy, TaskStatus::Todo).await;
+    ForgeAgent::register_task(pool, busy, agent.id, AGENT_CHAT_TYPE)
+        .await
+        .unwrap();
+
+    let create_attempt = CreateTaskAttempt {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+        base_branch: "main".to_string(),
+        branch: "forge/running".to_string(),
+    };
+    let attempt = TaskAttempt::create(pool, &create_attempt, Uuid::new_v4(), running.id)
+        .await
+        .unwrap();
+    let action = ExecutorAction::new(
+        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt: "Keep going".to_string(),
+            executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+        }),
+        None,
+    );
+    ExecutionProcess::create(
+        pool,
+        &CreateExecutionProcess {
+            task_attempt_id: Some(attempt.id),
+            execution_run_id: None,
+            executor_action: action,
+            run_reason: ExecutionProcessRunReason::CodingAgent,
+        },
+        Uuid::new_v4(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    // Quiet: nothing active
+    create_task(pool, quiet, TaskStatus::Todo).await;
+    create_task(pool, quiet, TaskStatus::InReview).await;
+
+    let counts = Task::count_by_project(pool).await.unwrap();
+    assert_eq!(counts.len(), 3);
+    let counts_for = |project_id: Uuid| {
+        let c = counts.iter().find(|c| c.project_id == project_id).unwrap();
+        (c.active, c.total)
+    };
+
+    assert_eq!(counts_for(busy), (2, 3));
+    assert_eq!(counts_for(quiet), (0, 2));
+    assert_eq!(counts_for(empty), (0, 0));
+}
@@ -0,0 +1,127 @@
+//! Integration tests for task updates guarded by `updated_at`
+//!
+//! Run with: cargo test --package services --test task_update
+
+use chrono::Duration;
+use forge_core_db::{
+    DBService,
+    models::{
+        project::{CreateProject, Project},
+        task::{CreateTask, Task, TaskStatus},
+    },
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn setup_test_db() -> (DBService, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = DBService::new_at(&temp_dir.path().join("test.sqlite"))
+        .await
+        .unwrap();
+    (db, temp_dir)
+}
+
+async fn create_task(pool: &sqlx::SqlitePool) -> Task {
+    let project_id = Uuid::new_v4();
+    let create_project = CreateProject {
+        name: "Updates".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{project_id}"),
+        use_existing_repo: false,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+        commit_prompt: None,
+        clone_url: None,
+    };
+    Project::create(pool, &create_project, project_id)
+        .await
+        .unwrap();
+
+    let create_task =
+        CreateTask::from_title_description(project_id, "Original title".to_string(), None);
+    let task = Task::create(pool, &create_task, Uuid::new_v4())
+        .await
+        .unwrap();
+    // updated_at has millisecond precision; make sure the next write gets a later one
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    task
+}
+
+#[tokio::test]
+async fn update_with_matching_updated_at_succeeds_and_bumps_it() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+    let task = create_task(pool).await;
+
+    let updated = Task::update(
+        pool,
+        task.id,
+        task.project_id,
+        "Renamed".to_string(),
+        task.description.clone(),
+        TaskStatus::InProgress,
+        None,
+        Some(task.updated_at),
+    )
+    .await
+    .unwrap()
+    .expect("a matching updated_at should apply the update");
+    assert_eq!(updated.title, "Renamed");
+    assert_eq!(updated.status, TaskStatus::InProgress);
+    assert!(updated.updated_at > task.updated_at);
+}
+
+#[tokio::test]
+async fn update_with_stale_updated_at_is_rejected() {
+    let (db, _temp_dir) = setup_test_db().await;
+    let pool = &db.pool;
+    let task = create_task(pool).await;
+
+    // Another client updates the task first
+    Task::update(
+        pool,
+        task.id,
+        task.project_id,
+        task.title.clone(),
+        Some("Edited elsewhere".to_string()),
+        task.status,
+        None,
+        None,
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    let stale = Task::update(
+        pool,
+        task.id,
+        task.project_id,
+        task.title.clone(),
+        task.description.clone(),
+        TaskStatus::Done,
+        None,
+        Some(task.updated_at),
+    )
+    .await
+    .unwrap();
+    assert!(stale.is_none());
+
+    let too_new = Task::update(
+        pool,
+        task.id,
+        task.project_id,
+        task.title.clone(),
+        task.description.clone(),
+        TaskStatus::Done,
+        None,
+        Some(task.updated_at + Duration::hours(1)),
+    )
+    .await
+    .unwrap();
+    assert!(too_new.is_none());
+
+    let stored = Task::find_by_id(pool, task.id).await.unwrap().unwrap();
+    assert_eq!(stored.description.as_deref(), Some("Edited elsewhere"));
+    assert_eq!(stored.status, TaskStatus::Todo);
+}
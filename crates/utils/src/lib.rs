@@ -8,6 +8,7 @@ pub mod browser;
 pub mod diff;
 pub mod git;
 pub mod log_msg;
+pub mod metrics;
 pub mod msg_store;
 pub mod path;
 pub mod port_file;
@@ -0,0 +1,148 @@
+//! In-process call counts and latency histograms for API routes and MCP tools.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use ts_rs_forge::TS;
+
+/// Upper bounds of the latency histogram buckets, in milliseconds. Slower calls land in a
+/// final overflow bucket.
+pub const LATENCY_BUCKETS_MS: [u64; 11] = [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Most series kept per kind; calls under names first seen after that are counted under
+/// [`OTHER_SERIES`], so clients can't grow the registry without bound
+pub const MAX_SERIES_PER_KIND: usize = 256;
+pub const OTHER_SERIES: &str = "other";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricKind {
+    Route,
+    Tool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct LatencyStats {
+    pub count: u64,
+    /// Calls that failed (5xx responses, or tool results flagged as errors)
+    pub errors: u64,
+    pub total_ms: f64,
+    pub max_ms: f64,
+    /// Call counts per bucket of `LATENCY_BUCKETS_MS`, followed by the overflow bucket
+    pub buckets: Vec<u64>,
+}
+
+impl Default for LatencyStats {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            errors: 0,
+            total_ms: 0.0,
+            max_ms: 0.0,
+            buckets: vec![0; LATENCY_BUCKETS_MS.len() + 1],
+        }
+    }
+}
+
+impl LatencyStats {
+    fn record(&mut self, elapsed: Duration, failed: bool) {
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        self.count += 1;
+        if failed {
+            self.errors += 1;
+        }
+        self.total_ms += ms;
+        self.max_ms = self.max_ms.max(ms);
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| ms <= *bound as f64)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[bucket] += 1;
+    }
+
+    pub fn mean_ms(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.total_ms / self.count as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct MetricsSnapshot {
+    pub bucket_bounds_ms: Vec<u64>,
+    /// Keyed by `METHOD /matched/path`
+    pub routes: BTreeMap<String, LatencyStats>,
+    /// Keyed by tool name
+    pub tools: BTreeMap<String, LatencyStats>,
+}
+
+#[derive(Default)]
+pub struct MetricsRegistry {
+    snapshot: Mutex<MetricsSnapshot>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, kind: MetricKind, name: &str, elapsed: Duration, failed: bool) {
+        let mut snapshot = self.snapshot.lock().unwrap();
+        let series = match kind {
+            MetricKind::Route => &mut snapshot.routes,
+            MetricKind::Tool => &mut snapshot.tools,
+        };
+        let name = if series.contains_key(name) || series.len() < MAX_SERIES_PER_KIND {
+            name
+        } else {
+            OTHER_SERIES
+        };
+        series
+            .entry(name.to_string())
+            .or_default()
+            .record(elapsed, failed);
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let mut snapshot = self.snapshot.lock().unwrap().clone();
+        snapshot.bucket_bounds_ms = LATENCY_BUCKETS_MS.to_vec();
+        snapshot
+    }
+}
+
+static GLOBAL_METRICS: OnceLock<MetricsRegistry> = OnceLock::new();
+
+/// Process-wide registry shared by the route middleware and the MCP tool handler
+pub fn global() -> &'static MetricsRegistry {
+    GLOBAL_METRICS.get_or_init(MetricsRegistry::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_names_past_the_limit_share_one_series() {
+        let registry = MetricsRegistry::new();
+        for i in 0..MAX_SERIES_PER_KIND + 10 {
+            registry.record(
+                MetricKind::Tool,
+                &format!("tool_{i}"),
+                Duration::from_millis(1),
+                false,
+            );
+        }
+        registry.record(MetricKind::Tool, "tool_0", Duration::from_millis(1), false);
+
+        let snapshot = registry.snapshot();
+        assert_eq!(snapshot.tools.len(), MAX_SERIES_PER_KIND + 1);
+        assert_eq!(snapshot.tools["tool_0"].count, 2);
+        assert_eq!(snapshot.tools[OTHER_SERIES].count, 10);
+    }
+}
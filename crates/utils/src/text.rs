@@ -22,3 +22,61 @@ pub fn short_uuid(u: &Uuid) -> String {
     let full = u.simple().to_string();
     full.chars().take(4).collect() // grab the first 4 chars
 }
+
+/// Lowercases a title and collapses punctuation and whitespace runs into single
+/// spaces, so titles differing only in formatting compare equal.
+pub fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity of two titles between 0.0 and 1.0, based on the edit distance of
+/// their normalized forms.
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = normalize_title(a).chars().collect();
+    let b: Vec<char> = normalize_title(b).chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / max_len as f64
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn near_duplicate_titles_are_similar() {
+        assert_eq!(
+            normalize_title("  Fix: login-button   crash!"),
+            "fix login button crash"
+        );
+        assert_eq!(
+            title_similarity("Fix login button crash", "fix: Login-Button crash"),
+            1.0
+        );
+        assert!(title_similarity("Fix login button crash", "Fix login buton crash") > 0.9);
+        assert!(title_similarity("Fix login button crash", "Add dark mode toggle") < 0.5);
+    }
+}
@@ -14,7 +14,17 @@ export type Project = { id: string, name: string, git_repo_path: string, setup_s
  */
 commit_prompt: string | null, created_at: Date, updated_at: Date, };
 
-export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, commit_prompt: string | null, };
+export type CreateProject = { name: string, 
+/**
+ * May be left empty when `clone_url` is set, to clone into the workspace directory
+ */
+git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, commit_prompt: string | null, 
+/**
+ * Remote repository to clone for the project instead of using a local path
+ */
+clone_url?: string, };
+
+export type ProjectCloneError = { "type": "auth_failed", message: string, } | { "type": "path_exists", path: string, } | { "type": "clone_failed", message: string, };
 
 export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, commit_prompt: string | null, };
 
@@ -52,11 +62,21 @@ export type Task = { id: string, project_id: string, title: string, description:
 
 export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, has_merged_attempt: boolean, last_attempt_failed: boolean, executor: string, attempt_count: bigint, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, dev_server_id: string | null, created_at: string, updated_at: string, };
 
+export type ProjectTaskCounts = { project_id: string, 
+/**
+ * Tasks in progress or with a running attempt
+ */
+active: bigint, total: bigint, };
+
 export type TaskRelationships = { parent_task: Task | null, current_attempt: TaskAttempt, children: Array<Task>, };
 
 export type CreateTask = { project_id: string, title: string, description: string | null, parent_task_attempt: string | null, image_ids: Array<string> | null, };
 
-export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, image_ids: Array<string> | null, };
+export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, image_ids: Array<string> | null, 
+/**
+ * Only apply the update if the task's `updated_at` still equals this value
+ */
+updated_at?: string, };
 
 export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
@@ -90,6 +110,14 @@ export type ChangeTargetBranchRequest = { new_target_branch: string, };
 
 export type ChangeTargetBranchResponse = { new_target_branch: string, status: [number, number], };
 
+export type MoveTaskAttemptRequest = { target_task_id: string, };
+
+export type MoveTaskAttemptResponse = { attempt: TaskAttempt, previous_task_id: string, 
+/**
+ * Child tasks spawned from the attempt, which now follow it to the new task
+ */
+child_tasks: bigint, };
+
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, base_branch: string, 
 /**
  * Whether to use a git worktree for isolation (default: true)
@@ -100,9 +128,13 @@ export type CreateGitHubPrRequest = { title: string, body: string | null, target
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
-export enum GitHubServiceError { TOKEN_INVALID = "TOKEN_INVALID", INSUFFICIENT_PERMISSIONS = "INSUFFICIENT_PERMISSIONS", REPO_NOT_FOUND_OR_NO_ACCESS = "REPO_NOT_FOUND_OR_NO_ACCESS" }
+export enum GitHubServiceError { TOKEN_INVALID = "TOKEN_INVALID", INSUFFICIENT_PERMISSIONS = "INSUFFICIENT_PERMISSIONS", REPO_NOT_FOUND_OR_NO_ACCESS = "REPO_NOT_FOUND_OR_NO_ACCESS", RATE_LIMITED = "RATE_LIMITED" }
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, contact_email_opt_in: boolean | null, contact_username_opt_in: boolean | null, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, contact_email_opt_in: boolean | null, contact_username_opt_in: boolean | null, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, 
+/**
+ * Variant to use for each executor when none is chosen explicitly
+ */
+default_variant_per_executor: { [key in BaseCodingAgent]?: string }, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -126,7 +158,21 @@ export enum DevicePollStatus { SLOW_DOWN = "SLOW_DOWN", AUTHORIZATION_PENDING =
 
 export enum CheckTokenResponse { VALID = "VALID", INVALID = "INVALID" }
 
-export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
+export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, 
+/**
+ * Commits on this branch that the default branch doesn't have; set by
+ * `annotate_branch_sync`
+ */
+ahead?: number, 
+/**
+ * Commits on the default branch that this branch doesn't have
+ */
+behind?: number, 
+/**
+ * Every commit on this branch is already on the default branch. Squash-merged branches
+ * keep their own commits, so they still count as unmerged.
+ */
+is_merged?: boolean, };
 
 export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
 /**
@@ -166,7 +212,7 @@ export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } |
 
 export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER" }
 
-export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, mcp_servers?: { [key in string]?: JsonValue } | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
 export type Gemini = { append_prompt: AppendPrompt, model: GeminiModel, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
@@ -174,7 +220,7 @@ export type GeminiModel = "default" | "flash";
 
 export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
-export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_plan_tool?: boolean | null, include_apply_patch_tool?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_plan_tool?: boolean | null, include_apply_patch_tool?: boolean | null, mcp_servers?: { [key in string]?: JsonValue } | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
 export type SandboxMode = "auto" | "read-only" | "workspace-write" | "danger-full-access";
 
@@ -218,13 +264,19 @@ executor_profile_id: ExecutorProfileId, base_branch: string,
  */
 use_worktree: boolean | null, };
 
+export type ForkTaskAttemptBody = { 
+/**
+ * Commit of the source attempt's branch to start from; defaults to its tip
+ */
+from_commit: string | null, };
+
 export type RunAgentSetupRequest = { executor_profile_id: ExecutorProfileId, };
 
 export type RunAgentSetupResponse = Record<string, never>;
 
 export type RebaseTaskAttemptRequest = { old_base_branch: string | null, new_base_branch: string | null, };
 
-export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
+export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, conflicted_files: Array<string>, } | { "type": "rebase_in_progress" };
 
 export type ReplaceProcessRequest = { 
 /**
@@ -262,11 +314,64 @@ conflict_op: ConflictOp | null,
 /**
  * List of files currently in conflicted (unmerged) state
  */
-conflicted_files: Array<string>, };
+conflicted_files: Array<string>, 
+/**
+ * When the project's remote was last fetched successfully; only reported for projects
+ */
+last_fetch_at?: string, };
 
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
-export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, worktree_deleted: boolean, setup_completed_at: string | null, input_tokens: number | null, output_tokens: number | null, cache_creation_tokens: number | null, cache_read_tokens: number | null, created_at: string, updated_at: string, };
+export type PushResult = { 
+/**
+ * Commit the remote branch points to after the push
+ */
+remote_sha: string, 
+/**
+ * False when a forced push rewrote the remote branch's history
+ */
+fast_forward: boolean, };
+
+export type BranchCommit = { sha: string, subject: string, author_name: string | null, author_email: string | null, committed_at: Date, };
+
+export type AttemptCommits = { 
+/**
+ * Commits on the attempt branch that its target branch doesn't have, newest first
+ */
+commits: Array<BranchCommit>, 
+/**
+ * How many such commits there are across all pages
+ */
+total: number, target_branch_name: string, };
+
+export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, variant: string | null, worktree_deleted: boolean, setup_completed_at: string | null, input_tokens: number | null, output_tokens: number | null, cache_creation_tokens: number | null, cache_read_tokens: number | null, created_at: string, updated_at: string, };
+
+export type TaskAttemptDetail = { 
+/**
+ * Executor configuration the attempt was started with; absent for attempts started
+ * before snapshots were recorded
+ */
+profile_snapshot: AttemptProfileSnapshot | null, id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, variant: string | null, worktree_deleted: boolean, setup_completed_at: string | null, input_tokens: number | null, output_tokens: number | null, cache_creation_tokens: number | null, cache_read_tokens: number | null, created_at: string, updated_at: string, };
+
+export type TaskAttemptListItem = { 
+/**
+ * Status of the newest setup script, cleanup script or coding agent process
+ */
+latest_process_status: ExecutionProcessStatus | null, 
+/**
+ * Whether the branch was merged directly or through a merged PR
+ */
+merged: boolean, id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, variant: string | null, worktree_deleted: boolean, setup_completed_at: string | null, input_tokens: number | null, output_tokens: number | null, cache_creation_tokens: number | null, cache_read_tokens: number | null, created_at: string, updated_at: string, };
+
+export type AttemptProfileSnapshot = { task_attempt_id: string, 
+/**
+ * Profile the attempt was started with, e.g. `CLAUDE_CODE:GENIE`
+ */
+executor_profile: string, 
+/**
+ * Resolved coding agent configuration (model, flags, appended prompt)
+ */
+config: JsonValue, created_at: string, };
 
 export type ExecutionProcess = { id: string, 
 /**